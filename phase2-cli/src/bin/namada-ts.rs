@@ -1,10 +1,14 @@
 use phase2_coordinator::{
+    audit::verify_audit_log,
     authentication::{KeyPair, Production, Signature},
     commands::{Computation, RandomSource, SEED_LENGTH},
+    coordinator_state::CoordinatorState,
     io::{self, verify_signature, KeyPairUser},
     objects::{ContributionFileSignature, ContributionInfo, ContributionState, TrimmedContributionInfo},
-    rest_utils::{ContributorStatus, PostChunkRequest, TOKENS_ZIP_FILE, UPDATE_TIME},
+    rest_utils::{ContributorStatus, PostChunkRequest, TokenStatus, TOKENS_ZIP_FILE, UPDATE_TIME},
     storage::Object,
+    timestamp::verify_timestamp_token,
+    Round,
 };
 
 use reqwest::{Client, Url};
@@ -20,7 +24,8 @@ use futures_util::StreamExt;
 use phase2_cli::{
     ascii_logo::{ASCII_CONTRIBUTION_DONE, ASCII_LOGO},
     keys::{self, EncryptedKeypair, TomlConfig},
-    requests, CeremonyOpt, CoordinatorUrl, Token, VerifySignatureContribution,
+    generate_tokens, requests, validate_contributors, CeremonyOpt, CoordinatorUrl, StateSummary, SubmitContribution,
+    Token, VerifySignatureContribution, VerifyTimestamp,
 };
 use serde_json;
 use setup_utils::calculate_hash;
@@ -124,6 +129,38 @@ fn get_progress_bar(len: u64) -> ProgressBar {
     progress_bar
 }
 
+/// Submits a contribution computed elsewhere
+async fn submit_contribution(client: &Client, keypair: &KeyPair, opt: SubmitContribution) -> Result<u64> {
+    let contribution = async_fs::read(&opt.file).await?;
+    let locked_locators = match opt.locked_locators {
+        Some(path) => Some(serde_json::from_slice(&async_fs::read(path).await?)?),
+        None => None,
+    };
+
+    let mut contrib_info = ContributionInfo::default();
+    contrib_info.public_key = keypair.pubkey().to_owned();
+    contrib_info.is_another_machine = true;
+    contrib_info.timestamps.end_computation = Utc::now();
+
+    let round_height = requests::submit_contribution(
+        client,
+        &opt.url.coordinator,
+        keypair,
+        opt.chunk,
+        contribution,
+        locked_locators,
+        &mut contrib_info,
+    )
+    .await?;
+    async_fs::write(
+        format!("namada_contributor_info_round_{}.json", round_height),
+        &serde_json::to_vec(&contrib_info)?,
+    )
+    .await?;
+
+    Ok(round_height)
+}
+
 /// Contest and offline execution branches
 #[inline(always)]
 fn compute_contribution_offline() -> Result<()> {
@@ -461,7 +498,7 @@ async fn contribution_loop(
         .expect(&format!("{}", "Couldn't get the status of contributor".red().bold()));
     let mut init_queue_position = 0;
     match init_queue_status {
-        ContributorStatus::Queue(position, _) => {
+        ContributorStatus::Queue(position, _) | ContributorStatus::InQueue { position, .. } => {
             init_queue_position = position;
         }
         _ => {}
@@ -473,13 +510,33 @@ async fn contribution_loop(
             .await
             .expect(&format!("{}", "Couldn't get the status of contributor".red().bold()));
 
+        // Read the status of the coordinators predating the estimate as a queue status without one
+        let queue_status = match queue_status {
+            ContributorStatus::Queue(position, size) => ContributorStatus::InQueue {
+                position,
+                size,
+                cohort: None,
+                estimated_seconds: None,
+            },
+            status => status,
+        };
+
         match queue_status {
-            ContributorStatus::Queue(position, size) => {
+            ContributorStatus::InQueue {
+                position,
+                size,
+                estimated_seconds,
+                ..
+            } => {
+                let expected_waiting_time = match estimated_seconds {
+                    Some(seconds) => format!("{} min", seconds / 60),
+                    None => String::from("calculating…"),
+                };
                 let msg = format!(
-                    "Queue position: {}\nQueue size: {}\nExpected waiting time: {} min\nMax waiting time: {} min\nElapsed time in queue: {} min",
+                    "Queue position: {}\nQueue size: {}\nExpected waiting time: {}\nMax waiting time: {} min\nElapsed time in queue: {} min",
                     position,
                     size,
-                    init_queue_position * 4,
+                    expected_waiting_time,
                     init_queue_position * 20,
                     queue_timer.elapsed().as_secs() / 60
                 );
@@ -497,7 +554,7 @@ async fn contribution_loop(
                 );
                 status_count += 1;
             }
-            ContributorStatus::Round => {
+            ContributorStatus::Round | ContributorStatus::InRound { .. } => {
                 round_height = contribute(&client, &coordinator, &keypair, contrib_info.clone(), &heartbeat_handle)
                     .await
                     .expect(&format!("{}", "Contribution failed".red().bold()));
@@ -561,6 +618,7 @@ async fn contribution_loop(
                 println!("{}", "Did not retrieve a valid contributor state.".red().bold());
                 break;
             }
+            ContributorStatus::Queue(_, _) => unreachable!("The legacy queue status was converted above"),
         }
 
         // Get status updates
@@ -568,6 +626,36 @@ async fn contribution_loop(
     }
 }
 
+#[inline(always)]
+async fn check_eligibility(client: &Client, coordinator: &Url, token: &String) {
+    // The keypair of the contributor is only generated when contributing, the request is signed with a throwaway one
+    let keypair = KeyPair::new();
+    let eligibility = match requests::post_check_eligibility(client, coordinator, &keypair, token).await {
+        Ok(eligibility) => eligibility,
+        Err(e) => {
+            eprintln!("{}", e.to_string().red().bold());
+            return;
+        }
+    };
+
+    match eligibility.status {
+        TokenStatus::Fresh => println!("{}", "The token has not been used yet.".green().bold()),
+        TokenStatus::Spent => println!("{}", "The token is already in use by another contributor.".red().bold()),
+        TokenStatus::Blacklisted => println!("{}", "The token has already been used to contribute.".red().bold()),
+        TokenStatus::Unknown => println!("{}", "The token is not valid.".red().bold()),
+    }
+
+    match eligibility.token_cohort {
+        Some(cohort) => println!("The token belongs to cohort {}.", cohort),
+        None => println!("{}", "The token does not belong to any cohort.".red().bold()),
+    }
+
+    match eligibility.current_cohort {
+        Some(cohort) => println!("The cohort currently active is cohort {}.", cohort),
+        None => println!("{}", "The ceremony is over.".red().bold()),
+    }
+}
+
 #[inline(always)]
 async fn close_ceremony(client: &Client, coordinator: &Url, keypair: &KeyPair) {
     match requests::get_stop_coordinator(client, coordinator, keypair).await {
@@ -576,6 +664,40 @@ async fn close_ceremony(client: &Client, coordinator: &Url, keypair: &KeyPair) {
     }
 }
 
+#[inline(always)]
+async fn benchmark(client: &Client, coordinator: &Url) {
+    println!("{} Computing a contribution on a synthetic chunk", "[1/2]".bold().dimmed());
+    let rand_source = RandomSource::Entropy(String::from("benchmark"));
+    let elapsed = match tokio::task::spawn_blocking(move || Computation::benchmark(&rand_source))
+        .await
+        .unwrap()
+    {
+        Ok(elapsed) => elapsed,
+        Err(e) => {
+            eprintln!("{}", e.to_string().red().bold());
+            return;
+        }
+    };
+
+    println!("{} Retrieving the typical contribution time", "[2/2]".bold().dimmed());
+    let message = match requests::get_contributor_average_task_time(client, coordinator).await {
+        Ok(Some(average)) => format!(
+            "Your hardware will take ~{}s per chunk, typical is ~{}s.",
+            elapsed.as_secs(),
+            average
+        ),
+        Ok(None) => format!(
+            "Your hardware will take ~{}s per chunk, the typical time is not available yet.",
+            elapsed.as_secs()
+        ),
+        Err(e) => {
+            eprintln!("{}", e.to_string().red().bold());
+            format!("Your hardware will take ~{}s per chunk.", elapsed.as_secs())
+        }
+    };
+    println!("{}", message.bright_cyan());
+}
+
 #[cfg(debug_assertions)]
 #[inline(always)]
 async fn get_contributions(coordinator: &Url) {
@@ -589,8 +711,12 @@ async fn get_contributions(coordinator: &Url) {
 }
 
 #[inline(always)]
-async fn get_coordinator_state(coordinator: &Url, secret: &str) {
+async fn get_coordinator_state(coordinator: &Url, secret: &str, json: bool) {
     match requests::get_coordinator_state(coordinator, secret).await {
+        Ok(state) if json => match serde_json::from_slice::<CoordinatorState>(&state) {
+            Ok(state) => println!("{}", serde_json::to_string(&StateSummary::from(&state)).unwrap()),
+            Err(e) => eprintln!("{}", e.to_string().red().bold()),
+        },
         Ok(state) => {
             let state_str = std::str::from_utf8(&state).unwrap();
             println!("Coordinator state:\n{}", state_str)
@@ -765,6 +891,14 @@ async fn main() {
                 }
             }
         }
+        CeremonyOpt::Benchmark(url) => {
+            let client = Client::new();
+            benchmark(&client, &url.coordinator).await;
+        }
+        CeremonyOpt::CheckEligibility(request) => {
+            let client = Client::new();
+            check_eligibility(&client, &request.url.coordinator, &request.token).await;
+        }
         CeremonyOpt::CloseCeremony(url) => {
             let keypair = tokio::task::spawn_blocking(|| io::keypair_from_mnemonic())
                 .await
@@ -832,13 +966,47 @@ async fn main() {
             .await
             .expect(&format!("{}", "Error while generating the addresses".red().bold()));
         }
+        CeremonyOpt::GenerateTokens(opt) => {
+            let tokens = generate_tokens(opt.count, opt.private);
+            let output = opt.output.to_string_lossy();
+
+            match CoordinatorState::save_cohort_tokens(&output, opt.cohort, &tokens, opt.force) {
+                Ok(path) => println!(
+                    "{}",
+                    format!("{} tokens were correctly generated in the \"{}\" file.", tokens.len(), path)
+                        .bold()
+                        .green()
+                ),
+                Err(e) => {
+                    eprintln!("{}", e.to_string().red().bold());
+                    process::exit(1);
+                }
+            }
+        }
         #[cfg(debug_assertions)]
         CeremonyOpt::GetContributions(url) => {
             get_contributions(&url.coordinator).await;
         }
         CeremonyOpt::GetState(state) => {
-            let secret = state.token.as_str();
-            get_coordinator_state(&state.url.coordinator, secret).await;
+            let secret = state.request.token.as_str();
+            get_coordinator_state(&state.request.url.coordinator, secret, state.json).await;
+        }
+        CeremonyOpt::SubmitContribution(opt) => {
+            let keypair = tokio::task::spawn_blocking(|| io::keypair_from_mnemonic())
+                .await
+                .unwrap()
+                .expect(&format!("{}", "Error while generating the keypair".red().bold()));
+
+            let client = Client::new();
+            let round_height = submit_contribution(&client, &keypair, opt)
+                .await
+                .expect(&format!("{}", "Contribution submission failed".red().bold()));
+            println!(
+                "{}",
+                format!("Contribution submitted for round {}", round_height)
+                    .green()
+                    .bold()
+            );
         }
         CeremonyOpt::UpdateCohorts(url) => {
             let keypair = tokio::task::spawn_blocking(|| io::keypair_from_mnemonic())
@@ -892,5 +1060,87 @@ async fn main() {
                 println!("The contribution signature is not correct.")
             }
         }
+        CeremonyOpt::VerifyAuditLog(opt) => match verify_audit_log(&opt.path, &Production, &opt.public_keys) {
+            Ok(entries) => println!(
+                "{}",
+                format!("The audit log is valid, with {} entries.", entries).bold().green()
+            ),
+            Err(e) => {
+                eprintln!("{}", format!("The audit log is not valid: {}", e).red().bold());
+                process::exit(1);
+            }
+        },
+        CeremonyOpt::VerifyTimestamp(opt) => {
+            let round_file =
+                fs::read(&opt.round).expect(&format!("{}", "Error while reading the round file".red().bold()));
+            let round: Round = serde_json::from_slice(&round_file)
+                .expect(&format!("{}", "Error while parsing the round file".red().bold()));
+            let timestamp = round
+                .chunk(opt.chunk_id)
+                .and_then(|chunk| chunk.get_contribution(opt.contribution_id))
+                .map(|contribution| contribution.get_contributed_timestamp().clone());
+
+            let timestamp = match timestamp {
+                Ok(Some(timestamp)) => timestamp,
+                Ok(None) => {
+                    eprintln!("{}", "The contribution has no timestamp.".red().bold());
+                    process::exit(1);
+                }
+                Err(e) => {
+                    eprintln!("{}", format!("The contribution is not in the round: {}", e).red().bold());
+                    process::exit(1);
+                }
+            };
+
+            let contribution = fs::read(&opt.contribution)
+                .expect(&format!("{}", "Error while reading the contribution file".red().bold()));
+            match verify_timestamp_token(&timestamp, &calculate_hash(&contribution)) {
+                Ok(time) => {
+                    println!(
+                        "{}",
+                        format!(
+                            "The contribution matches the timestamp issued by {} at {}.",
+                            timestamp.authority, time
+                        )
+                        .bold()
+                        .green()
+                    );
+                    println!(
+                        "{}",
+                        "Only the hash of the contribution was checked, verify the signature of the authority over the token with `openssl ts -verify`."
+                            .yellow()
+                    );
+                }
+                Err(e) => {
+                    eprintln!("{}", format!("The timestamp is not valid: {}", e).red().bold());
+                    process::exit(1);
+                }
+            }
+        }
+        CeremonyOpt::ValidateContributors(contributors) => {
+            let content = fs::read_to_string(&contributors.path)
+                .expect(&format!("{}", "Error while reading the contributors file".red().bold()));
+
+            match validate_contributors(&content) {
+                Ok(count) => println!(
+                    "{}",
+                    format!("The contributors file is valid, with {} contributors.", count)
+                        .bold()
+                        .green()
+                ),
+                Err(issues) => {
+                    for issue in &issues {
+                        eprintln!("{}", issue.to_string().red());
+                    }
+                    eprintln!(
+                        "{}",
+                        format!("The contributors file has {} problems.", issues.len())
+                            .red()
+                            .bold()
+                    );
+                    process::exit(1);
+                }
+            }
+        }
     }
 }
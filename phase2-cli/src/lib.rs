@@ -1,21 +1,30 @@
 // Documentation
 #![doc = include_str!("../README.md")]
 
-use std::path::PathBuf;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    path::PathBuf,
+};
 
 pub mod ascii_logo;
 pub mod keys;
 pub mod requests;
 
 use phase2_coordinator::{
-    objects::round::LockedLocators,
+    coordinator_state::{CoordinatorState, PRIVATE_TOKEN_PREFIX},
+    objects::{round::LockedLocators, TrimmedContributionInfo},
     rest_utils::{ContributorStatus, PostChunkRequest},
 };
 
+use rand::{rngs::OsRng, RngCore};
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use structopt::StructOpt;
 
+/// The number of random bytes of a generated token
+const TOKEN_BYTES: usize = 16;
+
 #[derive(Debug, StructOpt)]
 pub struct CoordinatorUrl {
     #[structopt(
@@ -37,6 +46,46 @@ pub struct RequestWithToken {
     pub token: String,
 }
 
+#[derive(Debug, StructOpt)]
+pub struct GetState {
+    #[structopt(flatten)]
+    pub request: RequestWithToken,
+    #[structopt(
+        long,
+        help = "Print a compact JSON summary instead of the full state, with the fields: \
+        current_round_height, queue_length, active_contributors, pending_verifications, \
+        dropped_count, banned_count and current_cohort_index (all unsigned integers)"
+    )]
+    pub json: bool,
+}
+
+/// The machine-readable summary of the coordinator state, printed by `get-state --json`.
+/// Its fields are a stable projection of [CoordinatorState] for scripting and monitoring.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct StateSummary {
+    pub current_round_height: u64,
+    pub queue_length: usize,
+    pub active_contributors: usize,
+    pub pending_verifications: usize,
+    pub dropped_count: usize,
+    pub banned_count: usize,
+    pub current_cohort_index: usize,
+}
+
+impl From<&CoordinatorState> for StateSummary {
+    fn from(state: &CoordinatorState) -> Self {
+        Self {
+            current_round_height: state.current_round_height(),
+            queue_length: state.number_of_queue_contributors(),
+            active_contributors: state.current_contributors().len(),
+            pending_verifications: state.get_pending_verifications().len(),
+            dropped_count: state.dropped_participants().len(),
+            banned_count: state.number_of_banned_participants(),
+            current_cohort_index: state.get_current_cohort_index(),
+        }
+    }
+}
+
 #[derive(Debug, StructOpt)]
 pub struct MnemonicPath {
     #[structopt(help = "The path to the mnemonic file", required = true, parse(try_from_str))]
@@ -56,6 +105,188 @@ pub struct Contributors {
     pub amount: u32,
 }
 
+#[derive(Debug, StructOpt)]
+pub struct ContributorsPath {
+    #[structopt(
+        help = "The path to the contributors.json file",
+        required = true,
+        parse(try_from_str),
+        long
+    )]
+    pub path: PathBuf,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct GenerateTokens {
+    #[structopt(help = "The number of tokens to generate", required = true, long)]
+    pub count: usize,
+    #[structopt(help = "The cohort of the tokens, starting from 1", required = true, long)]
+    pub cohort: usize,
+    #[structopt(
+        help = "The folder of the token files",
+        required = true,
+        parse(try_from_str),
+        long
+    )]
+    pub output: PathBuf,
+    #[structopt(help = "Generate private tokens, prefixed with \"put_\"", long)]
+    pub private: bool,
+    #[structopt(help = "Overwrite the token file of the cohort if it already exists", long)]
+    pub force: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct VerifyAuditLog {
+    #[structopt(help = "The path of the audit log", required = true, parse(try_from_str), long)]
+    pub path: PathBuf,
+    #[structopt(
+        help = "The hex encoded public keys of the coordinator, one for each of its starts",
+        required = true,
+        long = "public-key"
+    )]
+    pub public_keys: Vec<String>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct VerifyTimestamp {
+    #[structopt(help = "The path of the state file of the round", required = true, parse(try_from_str), long)]
+    pub round: PathBuf,
+    #[structopt(help = "The id of the chunk of the contribution", required = true, long)]
+    pub chunk_id: u64,
+    #[structopt(help = "The id of the contribution in the chunk", required = true, long)]
+    pub contribution_id: u64,
+    #[structopt(help = "The path of the contribution file", required = true, parse(try_from_str), long)]
+    pub contribution: PathBuf,
+}
+
+/// Generates the given number of distinct cryptographically random tokens, prefixed with [PRIVATE_TOKEN_PREFIX] if
+/// private.
+pub fn generate_tokens(count: usize, private: bool) -> HashSet<String> {
+    let prefix = if private { PRIVATE_TOKEN_PREFIX } else { "" };
+    let mut tokens = HashSet::with_capacity(count);
+
+    while tokens.len() < count {
+        let mut bytes = [0u8; TOKEN_BYTES];
+        OsRng.fill_bytes(&mut bytes);
+        tokens.insert(format!("{}{}", prefix, hex::encode(bytes)));
+    }
+
+    tokens
+}
+
+/// A problem found in a contributors.json file.
+#[derive(Debug, PartialEq)]
+pub struct ContributorsIssue {
+    /// The line of the file the problem was found at, starting from 1.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ContributorsIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Checks that every entry of the given contributors.json content is a contribution info with a well-formed public
+/// key, from which the address of the contributor is derived, and that no public key is repeated.
+///
+/// Returns the number of contributors, or all the problems found.
+pub fn validate_contributors(content: &str) -> Result<usize, Vec<ContributorsIssue>> {
+    let entries: Vec<serde_json::Value> = serde_json::from_str(content).map_err(|e| {
+        vec![ContributorsIssue {
+            line: e.line(),
+            message: e.to_string(),
+        }]
+    })?;
+    let lines = array_element_lines(content);
+
+    let mut issues = Vec::new();
+    let mut public_keys: HashMap<String, usize> = HashMap::new();
+
+    for (index, entry) in entries.into_iter().enumerate() {
+        let line = lines.get(index).copied().unwrap_or(1);
+        let mut issue = |message: String| issues.push(ContributorsIssue { line, message });
+
+        let info: TrimmedContributionInfo = match serde_json::from_value(entry) {
+            Ok(info) => info,
+            Err(e) => {
+                issue(format!("entry {} is not a valid contribution info: {}", index, e));
+                continue;
+            }
+        };
+
+        let public_key = info.public_key();
+        let well_formed = hex::decode(public_key)
+            .ok()
+            .and_then(|bytes| ed25519_compact::PublicKey::from_slice(&bytes).ok())
+            .is_some();
+        if !well_formed {
+            issue(format!("entry {} has a malformed public key \"{}\"", index, public_key));
+            continue;
+        }
+
+        match public_keys.get(public_key) {
+            Some(first_line) => issue(format!(
+                "entry {} repeats the public key of the entry at line {}",
+                index, first_line
+            )),
+            None => {
+                public_keys.insert(public_key.to_string(), line);
+            }
+        }
+    }
+
+    match issues.is_empty() {
+        true => Ok(public_keys.len()),
+        false => Err(issues),
+    }
+}
+
+/// Returns the line, starting from 1, at which every element of the top level array of the given JSON starts.
+fn array_element_lines(content: &str) -> Vec<usize> {
+    let mut lines = Vec::new();
+    let mut line = 1;
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut expecting_element = false;
+
+    for c in content.chars() {
+        if c == '\n' {
+            line += 1;
+        }
+
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => (),
+            }
+            continue;
+        }
+
+        if expecting_element && depth == 1 && !c.is_whitespace() && c != ']' {
+            lines.push(line);
+            expecting_element = false;
+        }
+
+        match c {
+            '"' => in_string = true,
+            '[' | '{' => {
+                depth += 1;
+                expecting_element = depth == 1;
+            }
+            ']' | '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 1 => expecting_element = true,
+            _ => (),
+        }
+    }
+
+    lines
+}
+
 #[derive(Debug, StructOpt)]
 pub enum Branches {
     #[structopt(
@@ -126,25 +357,49 @@ pub struct VerifySignatureContribution {
     pub parameter_path: Option<PathBuf>
 }
 
+#[derive(Debug, StructOpt)]
+pub struct SubmitContribution {
+    #[structopt(flatten)]
+    pub url: CoordinatorUrl,
+    #[structopt(help = "The id of the chunk to contribute to", long)]
+    pub chunk: u64,
+    #[structopt(help = "The path to the contribution file", long, parse(try_from_str))]
+    pub file: PathBuf,
+    #[structopt(
+        help = "The path to the locked locators of a previous lock, the chunk is locked if not provided",
+        long,
+        parse(try_from_str)
+    )]
+    pub locked_locators: Option<PathBuf>,
+}
+
 #[derive(Debug, StructOpt)]
 #[structopt(name = "namada-ts", about = "Namada CLI for trusted setup.")]
 pub enum CeremonyOpt {
     #[structopt(about = "Contribute to the ceremony")]
     Contribute(Branches),
+    #[structopt(about = "Estimate the time this machine takes to contribute to a chunk")]
+    Benchmark(CoordinatorUrl),
+    #[structopt(about = "Check whether a token can be used to join the ceremony, without joining")]
+    CheckEligibility(RequestWithToken),
     #[structopt(about = "Stop the coordinator and close the ceremony")]
     CloseCeremony(CoordinatorUrl),
     #[structopt(about = "Generate a Namada keypair from a mnemonic")]
     ExportKeypair(MnemonicPath),
     #[structopt(about = "Generate the list of addresses of the contributors")]
     GenerateAddresses(Contributors),
+    #[structopt(about = "Generate the token file of a cohort")]
+    GenerateTokens(GenerateTokens),
     #[cfg(debug_assertions)]
     #[structopt(about = "Get a list of all the contributions received")]
     GetContributions(CoordinatorUrl),
     #[structopt(about = "Get the state of the coordinator")]
-    GetState(RequestWithToken),
+    GetState(GetState),
     #[cfg(debug_assertions)]
     #[structopt(about = "Verify the pending contributions")]
     VerifyContributions(CoordinatorUrl),
+    #[structopt(about = "Submit an already computed contribution")]
+    SubmitContribution(SubmitContribution),
     #[structopt(about = "Update the cohorts' tokens")]
     UpdateCohorts(CoordinatorUrl),
     #[cfg(debug_assertions)]
@@ -152,4 +407,12 @@ pub enum CeremonyOpt {
     UpdateCoordinator(CoordinatorUrl),
     #[structopt(about = "Verify a contribution")]
     VerifyContribution(VerifySignatureContribution),
+    #[structopt(about = "Verify the chain and the signatures of the audit log of the coordinator")]
+    VerifyAuditLog(VerifyAuditLog),
+    #[structopt(about = "Check the entries of a contributors.json file, without contacting the coordinator")]
+    ValidateContributors(ContributorsPath),
+    #[structopt(
+        about = "Check the trusted timestamp of a contribution against the contribution file. The signature of the time stamping authority is not checked"
+    )]
+    VerifyTimestamp(VerifyTimestamp),
 }
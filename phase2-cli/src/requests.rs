@@ -1,14 +1,15 @@
 //! Requests sent to the [Coordinator](`phase2-coordinator::Coordinator`) server.
 
 use bytes::Bytes;
-use futures_util::Stream;
+use futures_util::{Stream, StreamExt};
 use phase2_coordinator::{
     authentication::{KeyPair, Production, Signature},
-    objects::ContributionInfo,
+    objects::{ContributionInfo, ContributionState},
     rest_utils::{
-        RequestContent, SignatureHeaders, ACCESS_SECRET_HEADER, BODY_DIGEST_HEADER, CONTENT_LENGTH_HEADER,
-        PUBKEY_HEADER, SIGNATURE_HEADER,
+        RequestContent, SignatureHeaders, TokenEligibility, ACCESS_SECRET_HEADER, BODY_DIGEST_HEADER,
+        CONTENT_LENGTH_HEADER, JOIN_SIGNATURE_HEADER, PUBKEY_HEADER, SIGNATURE_HEADER,
     },
+    storage::Object,
     ContributionFileSignature,
 };
 use reqwest::{
@@ -16,6 +17,7 @@ use reqwest::{
     Client, RequestBuilder, Response, Url,
 };
 use serde::Serialize;
+use setup_utils::calculate_hash;
 use sha2::{Digest, Sha256};
 use std::convert::{TryFrom, TryInto};
 use thiserror::Error;
@@ -28,8 +30,14 @@ use crate::{ContributorStatus, LockedLocators, PostChunkRequest};
 pub enum RequestError {
     #[error("Error while parsing the coordinator url")]
     AddressParseError,
+    #[error("The contribution file doesn't start with the hash of the challenge")]
+    ChallengeHashMismatch,
+    #[error("Requested chunk {requested} but the locked chunk is {locked}")]
+    ChunkMismatch { requested: u64, locked: u64 },
     #[error("Client-side error: {0}")]
     Client(String),
+    #[error("Invalid contribution file size: expected at most {expected} bytes, got {actual}")]
+    InvalidContributionSize { expected: u64, actual: u64 },
     #[error("Invalid header value: {0}")]
     InvalidHeaderValue(#[from] reqwest::header::InvalidHeaderValue),
     #[error("Json serialization of body failed")]
@@ -185,12 +193,19 @@ pub async fn post_join_queue(
     keypair: &KeyPair,
     token: &String,
 ) -> Result<u64> {
+    // Bind the token to the key of the participant
+    let join_signature = Production
+        .sign(keypair.sigkey(), token)
+        .map_err(|_| RequestError::SigningError)?;
+    let mut header = HeaderMap::new();
+    header.insert(JOIN_SIGNATURE_HEADER, HeaderValue::from_str(&join_signature)?);
+
     let response = submit_request::<String>(
         client,
         coordinator_address,
         "contributor/join_queue",
         Some(keypair),
-        None,
+        Some(header),
         Request::Post(Some(token)),
     )
     .await?;
@@ -198,6 +213,26 @@ pub async fn post_join_queue(
     Ok(response.json::<u64>().await?)
 }
 
+/// Check the eligibility of a token to join the queue of contributors, without joining it.
+pub async fn post_check_eligibility(
+    client: &Client,
+    coordinator_address: &Url,
+    keypair: &KeyPair,
+    token: &String,
+) -> Result<TokenEligibility> {
+    let response = submit_request::<String>(
+        client,
+        coordinator_address,
+        "contributor/check_eligibility",
+        Some(keypair),
+        None,
+        Request::Post(Some(token)),
+    )
+    .await?;
+
+    Ok(response.json::<TokenEligibility>().await?)
+}
+
 /// Send a request to the [Coordinator](`phase2-coordinator::Coordinator`) to lock the next [Chunk](`phase2-coordinator::objects::Chunk`).
 pub async fn get_lock_chunk(client: &Client, coordinator_address: &Url, keypair: &KeyPair) -> Result<LockedLocators> {
     let response = submit_request::<String>(
@@ -436,11 +471,34 @@ pub async fn post_attestation(
 
 /// Query health endpoint of the Coordinator to check the connection
 pub async fn ping_coordinator(client: &Client, coordinator_address: &Url) -> Result<()> {
-    submit_request::<()>(client, coordinator_address, "/healthcheck", None, None, Request::Get).await?;
+    submit_request::<()>(
+        client,
+        coordinator_address,
+        "/healthcheck?shallow=true",
+        None,
+        None,
+        Request::Get,
+    )
+    .await?;
 
     Ok(())
 }
 
+/// Retrieve the average number of seconds a contributor takes to compute a chunk in the current round, if known
+pub async fn get_contributor_average_task_time(client: &Client, coordinator_address: &Url) -> Result<Option<u64>> {
+    let response = submit_request::<()>(
+        client,
+        coordinator_address,
+        "/contributor/average_task_time",
+        None,
+        None,
+        Request::Get,
+    )
+    .await?;
+
+    Ok(response.json::<Option<u64>>().await?)
+}
+
 /// Retrieve the list of contributions, json encoded
 #[cfg(debug_assertions)]
 pub async fn get_contributions_info(coordinator_address: &Url) -> Result<Vec<u8>> {
@@ -497,3 +555,101 @@ pub async fn post_update_cohorts(
 
     Ok(())
 }
+
+/// Submit an already computed contribution for the given chunk. The chunk gets locked first, unless the [`LockedLocators`]
+/// of a previous lock are provided. The contribution file is checked against the challenge before being uploaded, then
+/// the contribution info is posted and the coordinator is notified of the new contribution. Returns the round height.
+pub async fn submit_contribution(
+    client: &Client,
+    coordinator_address: &Url,
+    keypair: &KeyPair,
+    chunk_id: u64,
+    mut contribution: Vec<u8>,
+    locked_locators: Option<LockedLocators>,
+    contrib_info: &mut ContributionInfo,
+) -> Result<u64> {
+    let locked_locators = match locked_locators {
+        Some(locators) => locators,
+        None => get_lock_chunk(client, coordinator_address, keypair).await?,
+    };
+    let response_locator = locked_locators.next_contribution();
+    if response_locator.chunk_id() != chunk_id {
+        return Err(RequestError::ChunkMismatch {
+            requested: chunk_id,
+            locked: response_locator.chunk_id(),
+        });
+    }
+    let round_height = response_locator.round_height();
+    contrib_info.ceremony_round = round_height;
+
+    // The contribution file must start with the hash of the challenge it was computed from
+    let challenge_url = get_challenge_url(client, coordinator_address, keypair, &round_height).await?;
+    let (mut challenge_stream, _) = get_challenge(client, challenge_url.as_str()).await?;
+    let mut challenge: Vec<u8> = Vec::new();
+    while let Some(b) = challenge_stream.next().await {
+        challenge.extend_from_slice(&b?);
+    }
+    let challenge_hash = calculate_hash(challenge.as_ref());
+    if !contribution.starts_with(challenge_hash.as_slice()) {
+        return Err(RequestError::ChallengeHashMismatch);
+    }
+
+    // Extend the file with trailing 0s to the size expected by the coordinator
+    let expected_size = Object::anoma_contribution_file_size(round_height, response_locator.contribution_id());
+    if contribution.len() as u64 > expected_size {
+        return Err(RequestError::InvalidContributionSize {
+            expected: expected_size,
+            actual: contribution.len() as u64,
+        });
+    }
+    contribution.resize(expected_size as usize, 0);
+
+    let contribution_file_hash = calculate_hash(contribution.as_ref());
+    contrib_info.contribution_file_hash = hex::encode(contribution_file_hash);
+    contrib_info.contribution_file_signature = Production
+        .sign(keypair.sigkey(), contrib_info.contribution_file_hash.as_str())
+        .map_err(|_| RequestError::SigningError)?;
+    contrib_info.contribution_hash = hex::encode(calculate_hash(&contribution[challenge_hash.len()..]));
+    contrib_info.contribution_hash_signature = Production
+        .sign(keypair.sigkey(), contrib_info.contribution_hash.as_str())
+        .map_err(|_| RequestError::SigningError)?;
+
+    let contribution_state = ContributionState::new(challenge_hash.to_vec(), contribution_file_hash.to_vec(), None)
+        .map_err(|e| RequestError::Client(e.to_string()))?;
+    let signature = Production
+        .sign(
+            keypair.sigkey(),
+            &contribution_state
+                .signature_message()
+                .map_err(|_| RequestError::SigningError)?,
+        )
+        .map_err(|_| RequestError::SigningError)?;
+    let contribution_file_signature = ContributionFileSignature::new(signature, contribution_state)
+        .map_err(|e| RequestError::Client(e.to_string()))?;
+
+    let (contribution_url, contribution_signature_url) =
+        get_contribution_url(client, coordinator_address, keypair, &round_height).await?;
+    let contribution_len = contribution.len() as u64;
+    let contribution_stream = futures_util::stream::iter(vec![Ok::<_, std::io::Error>(Bytes::from(contribution))]);
+    upload_chunk(
+        client,
+        contribution_url.as_str(),
+        contribution_signature_url.as_str(),
+        contribution_stream,
+        contribution_len,
+        &contribution_file_signature,
+    )
+    .await?;
+
+    contrib_info.try_sign(keypair).map_err(|_| RequestError::SigningError)?;
+    post_contribution_info(client, coordinator_address, keypair, contrib_info).await?;
+
+    let post_chunk_req = PostChunkRequest::new(
+        round_height,
+        response_locator,
+        locked_locators.next_contribution_file_signature(),
+    );
+    post_contribute_chunk(client, coordinator_address, keypair, &post_chunk_req).await?;
+
+    Ok(round_height)
+}
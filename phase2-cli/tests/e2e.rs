@@ -9,7 +9,7 @@ use std::{io::Write, net::IpAddr, sync::Arc};
 use phase2_coordinator::{
     authentication::{KeyPair, Production, Signature},
     commands::{Computation, RandomSource},
-    coordinator_state::CoordinatorState,
+    coordinator_state::{CoordinatorState, PRIVATE_TOKEN_PREFIX},
     environment::Testing,
     objects::{ContributionInfo, LockedLocators, TrimmedContributionInfo},
     rest,
@@ -33,7 +33,7 @@ use async_stream::try_stream;
 use futures_util::StreamExt;
 use toml::Value;
 
-use phase2_cli::requests;
+use phase2_cli::{generate_tokens, requests, validate_contributors, StateSummary};
 use reqwest::{Client, Url};
 use zip::write::FileOptions;
 
@@ -320,6 +320,29 @@ async fn get_status() {
     assert!(status.get_current_ips().is_empty());
     assert!(status.get_current_tokens().is_empty());
 
+    // Check the JSON summary of the state
+    let summary = StateSummary::from(&status);
+    assert_eq!(summary.current_round_height, status.current_round_height());
+    assert_eq!(summary.active_contributors, status.current_contributors().len());
+    assert_eq!(summary.queue_length, status.number_of_queue_contributors());
+    assert_eq!(summary.dropped_count, 0);
+    assert_eq!(summary.banned_count, 0);
+    let summary_json: serde_json::Value = serde_json::to_value(&summary).unwrap();
+    let mut fields: Vec<&String> = summary_json.as_object().unwrap().keys().collect();
+    fields.sort();
+    assert_eq!(
+        fields,
+        vec![
+            "active_contributors",
+            "banned_count",
+            "current_cohort_index",
+            "current_round_height",
+            "dropped_count",
+            "pending_verifications",
+            "queue_length"
+        ]
+    );
+
     // Provide invalid token
     let response = requests::get_coordinator_state(&url, "wrong token").await;
     assert!(response.is_err());
@@ -347,7 +370,7 @@ async fn get_contributor_queue_status() {
     // Ok
     let response = requests::get_contributor_queue_status(&client, &url, &ctx.contributors[0].keypair).await;
     match response.unwrap() {
-        rest_utils::ContributorStatus::Round => (),
+        rest_utils::ContributorStatus::InRound { .. } => (),
         _ => panic!("Wrong ContributorStatus"),
     }
 
@@ -780,3 +803,159 @@ async fn contribution() {
     // Drop the server
     handle.abort()
 }
+
+/// Test the submission of an already computed contribution:
+/// - Refuse a chunk different from the locked one
+/// - Refuse a file not computed from the current challenge, before uploading it
+/// - Submit the contribution and check that it's been received
+#[tokio::test]
+async fn submit_contribution() {
+    use rand::Rng;
+    use setup_utils::calculate_hash;
+
+    let client = Client::new();
+    // Spawn the server and get the test context
+    let (ctx, handle) = test_prelude().await;
+    // Wait for server startup
+    time::sleep(Duration::from_secs(1)).await;
+    let url = Url::parse(&ctx.coordinator_url).unwrap();
+    let keypair = &ctx.contributors[0].keypair;
+    let locked_locators = ctx.contributors[0].locked_locators.clone();
+
+    // Compute the contribution
+    let challenge_url = requests::get_challenge_url(&client, &url, keypair, &ROUND_HEIGHT)
+        .await
+        .unwrap();
+    let mut challenge_stream = requests::get_challenge(&client, challenge_url.as_str()).await.unwrap();
+    let mut challenge: Vec<u8> = Vec::new();
+    while let Some(b) = challenge_stream.0.next().await {
+        challenge.extend_from_slice(&b.unwrap());
+    }
+    let challenge_hash = calculate_hash(challenge.as_ref());
+
+    let mut contribution: Vec<u8> = Vec::new();
+    contribution.write_all(challenge_hash.as_slice()).unwrap();
+    let seed = RandomSource::Seed(rand::thread_rng().gen::<[u8; 32]>());
+    Computation::contribute_test_masp(&challenge, &mut contribution, &seed);
+
+    let mut contrib_info = ContributionInfo::default();
+    contrib_info.public_key = keypair.pubkey().to_owned();
+
+    // Wrong chunk
+    let response = requests::submit_contribution(
+        &client,
+        &url,
+        keypair,
+        1,
+        contribution.clone(),
+        locked_locators.clone(),
+        &mut contrib_info,
+    )
+    .await;
+    assert!(matches!(
+        response,
+        Err(requests::RequestError::ChunkMismatch {
+            requested: 1,
+            locked: 0
+        })
+    ));
+
+    // Wrong challenge hash
+    let mut wrong_contribution = contribution.clone();
+    wrong_contribution[0] ^= 1;
+    let response = requests::submit_contribution(
+        &client,
+        &url,
+        keypair,
+        0,
+        wrong_contribution,
+        locked_locators.clone(),
+        &mut contrib_info,
+    )
+    .await;
+    assert!(matches!(response, Err(requests::RequestError::ChallengeHashMismatch)));
+
+    // Nothing has been received by the coordinator
+    requests::get_verify_chunks(&client, &url, &ctx.coordinator.keypair)
+        .await
+        .unwrap();
+    let summary_bytes = requests::get_contributions_info(&url).await.unwrap();
+    let summary: Vec<TrimmedContributionInfo> = serde_json::from_slice(&summary_bytes).unwrap();
+    assert!(summary.is_empty());
+
+    // Correct submission
+    let round_height = requests::submit_contribution(
+        &client,
+        &url,
+        keypair,
+        0,
+        contribution,
+        locked_locators,
+        &mut contrib_info,
+    )
+    .await
+    .unwrap();
+    assert_eq!(round_height, ROUND_HEIGHT);
+
+    requests::get_verify_chunks(&client, &url, &ctx.coordinator.keypair)
+        .await
+        .unwrap();
+    let summary_bytes = requests::get_contributions_info(&url).await.unwrap();
+    let summary: Vec<TrimmedContributionInfo> = serde_json::from_slice(&summary_bytes).unwrap();
+    assert_eq!(summary.len(), 1);
+    assert_eq!(summary[0].public_key(), keypair.pubkey());
+    assert_eq!(summary[0].ceremony_round(), ROUND_HEIGHT);
+
+    // Drop the server
+    handle.abort()
+}
+
+#[test]
+fn validate_contributors_file() {
+    let entry = |public_key: &str| {
+        let mut contrib_info = ContributionInfo::default();
+        contrib_info.public_key = public_key.to_owned();
+        TrimmedContributionInfo::from(contrib_info)
+    };
+    let keypair = KeyPair::new();
+    let other_keypair = KeyPair::new();
+
+    let valid = vec![entry(keypair.pubkey()), entry(other_keypair.pubkey())];
+    let content = serde_json::to_string_pretty(&valid).unwrap();
+    assert_eq!(validate_contributors(&content), Ok(2));
+
+    // The problems are reported at the line of their entry.
+    let invalid = vec![entry(keypair.pubkey()), entry("not a key"), entry(keypair.pubkey())];
+    let content = serde_json::to_string_pretty(&invalid).unwrap();
+    let entry_lines = serde_json::to_string_pretty(&invalid[0]).unwrap().lines().count();
+    let issues = validate_contributors(&content).unwrap_err();
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].line, 2 + entry_lines);
+    assert!(issues[0].message.contains("malformed public key"));
+    assert_eq!(issues[1].line, 2 + 2 * entry_lines);
+    assert!(issues[1].message.contains("repeats the public key of the entry at line 2"));
+
+    let issues = validate_contributors("[\n  {\n  ]\n").unwrap_err();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].line, 3);
+}
+
+#[test]
+fn generate_tokens_file() {
+    // The tokens are distinct, of the expected length, and only the private ones are prefixed.
+    let tokens = generate_tokens(100, false);
+    assert_eq!(tokens.len(), 100);
+    assert!(
+        tokens
+            .iter()
+            .all(|token| token.len() == 32 && hex::decode(token).is_ok())
+    );
+
+    let private_tokens = generate_tokens(100, true);
+    assert_eq!(private_tokens.len(), 100);
+    assert!(private_tokens.iter().all(|token| {
+        token.starts_with(PRIVATE_TOKEN_PREFIX) && hex::decode(&token[PRIVATE_TOKEN_PREFIX.len()..]).is_ok()
+    }));
+
+    assert!(generate_tokens(0, true).is_empty());
+}
@@ -0,0 +1,73 @@
+use phase2_coordinator::{
+    authentication::Dummy,
+    environment::{Environment, Parameters, Settings, Testing},
+    testing::initialize_test_environment,
+    Coordinator, Participant,
+};
+
+use phase2::{helpers::CurveKind, ContributionMode, ProvingSystem};
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use std::sync::Arc;
+
+/// Builds a coordinator in round 1 with the given number of contributors,
+/// and returns it along with the contributor to drop.
+fn large_round(number_of_contributors: usize) -> (Coordinator, Participant) {
+    let parameters = Parameters::Custom(Settings::new(
+        ContributionMode::Chunked,
+        ProvingSystem::Groth16,
+        CurveKind::Bls12_377,
+        6,  /* power */
+        16, /* batch_size */
+        16, /* chunk_size */
+    ));
+
+    let testing_deployment: Testing = Testing::from(parameters)
+        .minimum_contributors_per_round(number_of_contributors)
+        .maximum_contributors_per_round(number_of_contributors);
+    let environment = initialize_test_environment(&Environment::from(testing_deployment));
+
+    let mut coordinator = Coordinator::new(environment, Arc::new(Dummy)).unwrap();
+    coordinator.initialize().unwrap();
+
+    let contributors: Vec<Participant> = (0..number_of_contributors)
+        .map(|id| Participant::new_contributor(&format!("bench-contributor-{}", id)))
+        .collect();
+    for contributor in &contributors {
+        coordinator
+            .add_to_queue(contributor.clone(), None, String::from("bench_token"), 10)
+            .unwrap();
+    }
+
+    // Update the ceremony to round 1.
+    coordinator.update().unwrap();
+
+    (coordinator, contributors[0].clone())
+}
+
+// Benchmark the latency of dropping a contributor, which reassigns the
+// tasks of every other contributor in the round
+fn benchmark_drop_participant(c: &mut Criterion) {
+    let mut group = c.benchmark_group("drop_participant");
+    // building the round dominates the runtime
+    group.sample_size(10);
+
+    for number_of_contributors in &[16, 64, 256] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(number_of_contributors),
+            number_of_contributors,
+            |b, number_of_contributors| {
+                b.iter_batched(
+                    || large_round(*number_of_contributors),
+                    |(mut coordinator, contributor)| coordinator.drop_participant(&contributor).unwrap(),
+                    BatchSize::PerIteration,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_drop_participant);
+criterion_main!(benches);
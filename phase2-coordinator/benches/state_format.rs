@@ -0,0 +1,86 @@
+use phase2_coordinator::{
+    authentication::Dummy,
+    environment::{Environment, Parameters, StateFormat, Testing},
+    storage::{Disk, Locator, Object, StorageLocator},
+    testing::initialize_test_environment,
+    Coordinator, CoordinatorState, Participant,
+};
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use fs_err as fs;
+use serde_json::json;
+use std::sync::Arc;
+
+const NUMBER_OF_FINISHED_CONTRIBUTORS: usize = 1000;
+
+/// Builds a coordinator state in round 1 with the given number of finished contributors.
+fn large_state(environment: &Environment, number_of_finished_contributors: usize) -> CoordinatorState {
+    let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy)).unwrap();
+    coordinator.initialize().unwrap();
+
+    let contributor = Participant::new_contributor("bench-contributor");
+    coordinator
+        .add_to_queue(contributor.clone(), None, String::from("bench_token"), 10)
+        .unwrap();
+
+    // Update the ceremony to round 1.
+    coordinator.update().unwrap();
+
+    // Fill the finished contributors with copies of the current contributor.
+    let mut state = serde_json::to_value(coordinator.state()).unwrap();
+    let key = serde_json::to_value(&contributor).unwrap();
+    let info = state["current_contributors"][key.as_str().unwrap()].clone();
+    let mut finished = serde_json::Map::new();
+    for id in 0..number_of_finished_contributors {
+        let participant = Participant::new_contributor(&format!("bench-contributor-{}", id));
+        let participant = serde_json::to_value(participant).unwrap();
+        let mut info = info.clone();
+        info["id"] = participant.clone();
+        finished.insert(participant.as_str().unwrap().to_string(), info);
+    }
+    state["finished_contributors"] = json!({ "1": finished });
+
+    serde_json::from_value(state).unwrap()
+}
+
+// Benchmark the time to save and load the coordinator state, and report the size of the
+// state file, in each format
+fn benchmark_state_format(c: &mut Criterion) {
+    let environment = initialize_test_environment(&Testing::from(Parameters::Test8Chunks).into());
+    let state = large_state(&environment, NUMBER_OF_FINISHED_CONTRIBUTORS);
+
+    let mut group = c.benchmark_group("state_format");
+
+    for state_format in [StateFormat::Json, StateFormat::Bincode] {
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .state_format(state_format)
+            .into();
+        let mut disk = Disk::load(&environment).unwrap();
+        disk.update(&Locator::CoordinatorState, Object::CoordinatorState(state.clone()))
+            .unwrap();
+
+        let path = disk.to_path(&Locator::CoordinatorState).unwrap();
+        println!(
+            "{:?} state file with {} finished contributors: {} bytes",
+            state_format,
+            NUMBER_OF_FINISHED_CONTRIBUTORS,
+            fs::metadata(path).unwrap().len()
+        );
+
+        group.bench_function(BenchmarkId::new("save", format!("{:?}", state_format)), |b| {
+            b.iter_batched(
+                || Object::CoordinatorState(state.clone()),
+                |object| disk.update(&Locator::CoordinatorState, object).unwrap(),
+                BatchSize::SmallInput,
+            )
+        });
+        group.bench_function(BenchmarkId::new("load", format!("{:?}", state_format)), |b| {
+            b.iter(|| disk.get(&Locator::CoordinatorState).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_state_format);
+criterion_main!(benches);
@@ -0,0 +1,394 @@
+//! Tamper-evident log of the state transitions of the coordinator.
+//!
+//! When the [AUDIT_LOG_ENV] env variable is set, every significant transition of the
+//! [CoordinatorState](crate::CoordinatorState) is appended as a JSON line to the file at that path. Each entry has a
+//! monotonic sequence number, the hash of the previous line and a signature of the coordinator over both, so that
+//! removing, reordering or editing an entry breaks the chain. The log is checked with [verify_audit_log].
+//!
+//! The key of the coordinator is generated on each start, so the log records the public key signing the next
+//! entries in an [AuditEvent::KeyRotation] entry every time it is continued with a new key.
+
+use crate::{authentication::Signature, objects::Participant, CoordinatorError, TimeSource};
+use fs_err::{self as fs, File, OpenOptions};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use time::OffsetDateTime;
+use tracing::warn;
+
+/// The env variable holding the path of the audit log. The audit log is disabled if unset.
+pub const AUDIT_LOG_ENV: &str = "NAMADA_AUDIT_LOG";
+
+/// A state transition recorded in the audit log.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuditEvent {
+    /// The participant joined the queue.
+    Join { participant: Participant },
+    /// The participant locked the chunk.
+    Lock { participant: Participant, chunk_id: u64 },
+    /// The participant completed the contribution to the chunk.
+    Complete {
+        participant: Participant,
+        chunk_id: u64,
+        contribution_id: u64,
+    },
+    /// The participant was dropped from the ceremony.
+    Drop { participant: Participant },
+    /// The participant was banned from the ceremony.
+    Ban { participant: Participant },
+    /// The ceremony advanced to the round.
+    RoundAdvance { round_height: u64 },
+    /// The coordinator signs this entry and the next ones with the key.
+    KeyRotation { public_key: String },
+}
+
+/// An entry of the audit log.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// The position of the entry in the log, starting from 0.
+    pub sequence: u64,
+    /// The time the transition was recorded at.
+    pub timestamp: OffsetDateTime,
+    /// The hex encoded hash of the previous line of the log, empty for the first entry.
+    pub previous_hash: String,
+    /// The recorded transition.
+    pub event: AuditEvent,
+    /// The signature of the coordinator over all the other fields.
+    pub signature: String,
+}
+
+impl AuditEntry {
+    /// Returns the message signed by the coordinator for this entry.
+    fn message(&self) -> Result<String, CoordinatorError> {
+        Ok(serde_json::to_string(&(
+            self.sequence,
+            self.timestamp,
+            &self.previous_hash,
+            &self.event,
+        ))?)
+    }
+}
+
+/// Returns the hex encoded hash of the given line of the log.
+fn line_hash(line: &str) -> String {
+    hex::encode(Sha256::digest(line.as_bytes()))
+}
+
+/// The append-only writer of the audit log.
+pub struct AuditLog {
+    /// The file the entries are appended to.
+    file: File,
+    /// The signature scheme of the coordinator.
+    signature: Arc<dyn Signature>,
+    /// The signing key of the coordinator.
+    signing_key: String,
+    /// The sequence number of the next entry.
+    next_sequence: u64,
+    /// The hash of the last line of the log.
+    last_hash: String,
+}
+
+impl AuditLog {
+    ///
+    /// Opens the audit log at the given path, creating it if needed. The entries of an
+    /// existing log are continued from its last line, after a key rotation entry if the
+    /// given public key is not the one signing the last entries.
+    ///
+    pub fn open<P: Into<PathBuf>>(
+        path: P,
+        signature: Arc<dyn Signature>,
+        signing_key: String,
+        public_key: String,
+        time: &dyn TimeSource,
+    ) -> Result<Self, CoordinatorError> {
+        let path = path.into();
+
+        let mut next_sequence = 0;
+        let mut last_hash = String::new();
+        let mut last_public_key = None;
+        if path.exists() {
+            let contents = fs::read(&path)?;
+
+            // A crash in the middle of an append leaves a partial last line, which is dropped
+            // so that the log can be continued.
+            let complete_len = contents.iter().rposition(|byte| *byte == b'\n').map_or(0, |i| i + 1);
+            if complete_len < contents.len() {
+                warn!(
+                    "Truncating a partial last line of {} bytes in the audit log {}",
+                    contents.len() - complete_len,
+                    path.display()
+                );
+                OpenOptions::new()
+                    .write(true)
+                    .open(&path)?
+                    .set_len(complete_len as u64)?;
+            }
+
+            for line in String::from_utf8_lossy(&contents[..complete_len]).lines() {
+                let entry: AuditEntry = serde_json::from_str(line)?;
+                if let AuditEvent::KeyRotation { public_key } = entry.event {
+                    last_public_key = Some(public_key);
+                }
+                next_sequence = entry.sequence + 1;
+                last_hash = line_hash(line);
+            }
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        let mut log = Self {
+            file,
+            signature,
+            signing_key,
+            next_sequence,
+            last_hash,
+        };
+        if last_public_key.as_ref() != Some(&public_key) {
+            log.append(AuditEvent::KeyRotation { public_key }, time)?;
+        }
+
+        Ok(log)
+    }
+
+    ///
+    /// Appends the given transition to the log, chained to the previous entry.
+    ///
+    pub fn append(&mut self, event: AuditEvent, time: &dyn TimeSource) -> Result<(), CoordinatorError> {
+        let mut entry = AuditEntry {
+            sequence: self.next_sequence,
+            timestamp: time.now_utc(),
+            previous_hash: self.last_hash.clone(),
+            event,
+            signature: String::new(),
+        };
+        entry.signature = self.signature.sign(&self.signing_key, &entry.message()?)?;
+
+        let line = serde_json::to_string(&entry)?;
+        writeln!(self.file, "{}", line)?;
+        self.file.sync_data()?;
+
+        self.next_sequence += 1;
+        self.last_hash = line_hash(&line);
+
+        Ok(())
+    }
+}
+
+///
+/// Checks that the entries of the audit log at the given path form an unbroken chain,
+/// each one signed by the key of the last key rotation entry, which must be one of the
+/// given public keys published by the coordinator.
+///
+/// Returns the number of entries in the log.
+///
+pub fn verify_audit_log(
+    path: &Path,
+    signature: &dyn Signature,
+    public_keys: &[String],
+) -> Result<u64, CoordinatorError> {
+    let mut expected_sequence = 0;
+    let mut previous_hash = String::new();
+    let mut public_key: Option<String> = None;
+
+    for line in fs::read_to_string(path)?.lines() {
+        let entry: AuditEntry = serde_json::from_str(line)?;
+
+        if entry.sequence != expected_sequence {
+            return Err(CoordinatorError::AuditLogSequenceMismatch {
+                expected: expected_sequence,
+                found: entry.sequence,
+            });
+        }
+
+        if entry.previous_hash != previous_hash {
+            return Err(CoordinatorError::AuditLogHashMismatch {
+                sequence: entry.sequence,
+            });
+        }
+
+        if let AuditEvent::KeyRotation { public_key: key } = &entry.event {
+            if !public_keys.contains(key) {
+                return Err(CoordinatorError::AuditLogKeyUnknown {
+                    sequence: entry.sequence,
+                });
+            }
+            public_key = Some(key.clone());
+        }
+
+        // The entries before the first key rotation have no key to be checked against.
+        match &public_key {
+            Some(key) if signature.verify(key, &entry.message()?, &entry.signature) => (),
+            _ => {
+                return Err(CoordinatorError::AuditLogSignatureInvalid {
+                    sequence: entry.sequence,
+                });
+            }
+        }
+
+        expected_sequence += 1;
+        previous_hash = line_hash(line);
+    }
+
+    Ok(expected_sequence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        authentication::{KeyPair, Production},
+        SystemTimeSource,
+    };
+
+    fn append_events(log: &mut AuditLog) {
+        let time = SystemTimeSource::new();
+        let participant = Participant::new_contributor("contributor");
+
+        log.append(
+            AuditEvent::Join {
+                participant: participant.clone(),
+            },
+            &time,
+        )
+        .unwrap();
+        log.append(
+            AuditEvent::Lock {
+                participant: participant.clone(),
+                chunk_id: 0,
+            },
+            &time,
+        )
+        .unwrap();
+        log.append(AuditEvent::RoundAdvance { round_height: 2 }, &time)
+            .unwrap();
+    }
+
+    /// Opens the audit log at the given path, signed by the given key.
+    fn open_log(path: &Path, keypair: &KeyPair) -> AuditLog {
+        AuditLog::open(
+            path,
+            Arc::new(Production),
+            keypair.sigkey().to_string(),
+            keypair.pubkey().to_string(),
+            &SystemTimeSource::new(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_audit_log_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let keypair = KeyPair::new();
+        let public_keys = vec![keypair.pubkey().to_string()];
+
+        // The log is continued across reopenings, with a single key rotation for the same key.
+        let mut log = open_log(&path, &keypair);
+        append_events(&mut log);
+        let mut log = open_log(&path, &keypair);
+        append_events(&mut log);
+        assert_eq!(7, verify_audit_log(&path, &Production, &public_keys).unwrap());
+
+        // The entries must be signed by one of the given keys.
+        let other_keypair = KeyPair::new();
+        assert!(matches!(
+            verify_audit_log(&path, &Production, &[other_keypair.pubkey().to_string()]),
+            Err(CoordinatorError::AuditLogKeyUnknown { sequence: 0 })
+        ));
+
+        let lines: Vec<String> = fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect();
+
+        // Removing an entry breaks the sequence.
+        let mut removed = lines.clone();
+        removed.remove(2);
+        fs::write(&path, removed.join("\n")).unwrap();
+        assert!(matches!(
+            verify_audit_log(&path, &Production, &public_keys),
+            Err(CoordinatorError::AuditLogSequenceMismatch { expected: 2, found: 3 })
+        ));
+
+        // Editing an entry breaks the signature, or the hash chain if it is re-signed.
+        let mut edited = lines.clone();
+        edited[2] = edited[2].replace("\"chunk_id\":0", "\"chunk_id\":1");
+        fs::write(&path, edited.join("\n")).unwrap();
+        assert!(matches!(
+            verify_audit_log(&path, &Production, &public_keys),
+            Err(CoordinatorError::AuditLogSignatureInvalid { sequence: 2 })
+        ));
+
+        let mut entry: AuditEntry = serde_json::from_str(&edited[2]).unwrap();
+        entry.signature = Production.sign(keypair.sigkey(), &entry.message().unwrap()).unwrap();
+        edited[2] = serde_json::to_string(&entry).unwrap();
+        fs::write(&path, edited.join("\n")).unwrap();
+        assert!(matches!(
+            verify_audit_log(&path, &Production, &public_keys),
+            Err(CoordinatorError::AuditLogHashMismatch { sequence: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_audit_log_key_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let keypair = KeyPair::new();
+        let next_keypair = KeyPair::new();
+
+        // A restart of the coordinator with a new key rotates the key of the log.
+        let mut log = open_log(&path, &keypair);
+        append_events(&mut log);
+        let mut log = open_log(&path, &next_keypair);
+        append_events(&mut log);
+
+        let public_keys = vec![keypair.pubkey().to_string(), next_keypair.pubkey().to_string()];
+        assert_eq!(8, verify_audit_log(&path, &Production, &public_keys).unwrap());
+
+        // The new key must be published too.
+        assert!(matches!(
+            verify_audit_log(&path, &Production, &public_keys[..1]),
+            Err(CoordinatorError::AuditLogKeyUnknown { sequence: 4 })
+        ));
+
+        // The entries after the rotation are checked against the new key.
+        let lines: Vec<String> = fs::read_to_string(&path).unwrap().lines().map(str::to_string).collect();
+        let mut entry: AuditEntry = serde_json::from_str(&lines[5]).unwrap();
+        entry.signature = Production.sign(keypair.sigkey(), &entry.message().unwrap()).unwrap();
+        let mut edited = lines.clone();
+        edited[5] = serde_json::to_string(&entry).unwrap();
+        fs::write(&path, edited.join("\n")).unwrap();
+        assert!(matches!(
+            verify_audit_log(&path, &Production, &public_keys),
+            Err(CoordinatorError::AuditLogSignatureInvalid { sequence: 5 })
+        ));
+    }
+
+    #[test]
+    fn test_audit_log_torn_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let keypair = KeyPair::new();
+        let public_keys = vec![keypair.pubkey().to_string()];
+
+        let mut log = open_log(&path, &keypair);
+        append_events(&mut log);
+        drop(log);
+
+        // Simulate a crash in the middle of an append.
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        write!(file, "{{\"sequence\":4,\"timestamp\"").unwrap();
+        drop(file);
+
+        // The partial line is dropped and the log is continued after the last complete entry.
+        let mut log = open_log(&path, &keypair);
+        append_events(&mut log);
+        assert_eq!(7, verify_audit_log(&path, &Production, &public_keys).unwrap());
+    }
+}
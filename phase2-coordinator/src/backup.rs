@@ -0,0 +1,411 @@
+//! Backup of the coordinator state to S3.
+//!
+//! When the [BACKUP_INTERVAL_ENV] env variable is set, a zip archive of the serialized
+//! [CoordinatorState](crate::CoordinatorState) and of the round artifacts, see [backup_files], is uploaded on that
+//! interval and only the last [BACKUP_RETENTION_ENV] backups are kept. When [BACKUP_RESTORE_ENV] is `true` and the
+//! local storage has no coordinator state, the latest backup is extracted into it at startup.
+
+use crate::{
+    CoordinatorError,
+    TimeSource,
+    s3::S3Ctx,
+    storage::{Locator, Storage, StorageLocator},
+};
+use rocket::tokio::{sync::watch::Receiver, time::sleep};
+use std::{
+    future::Future,
+    io::{Cursor, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tracing::{error, info};
+
+/// The env variable holding the number of seconds between two backups. Backups are disabled if unset.
+pub const BACKUP_INTERVAL_ENV: &str = "NAMADA_MPC_BACKUP_INTERVAL_SECONDS";
+/// The env variable holding the number of backups to keep.
+pub const BACKUP_RETENTION_ENV: &str = "NAMADA_MPC_BACKUP_RETENTION";
+/// The env variable enabling the restore of the latest backup at startup.
+pub const BACKUP_RESTORE_ENV: &str = "NAMADA_MPC_BACKUP_RESTORE";
+
+const DEFAULT_BACKUP_RETENTION: usize = 24;
+
+/// The service the backups are stored in.
+#[rocket::async_trait]
+pub trait BackupStore: Send + Sync {
+    /// Stores the data at the given key.
+    async fn upload(&self, key: &str, data: Vec<u8>) -> Result<(), String>;
+    /// Retrieves the data at the given key.
+    async fn download(&self, key: &str) -> Result<Vec<u8>, String>;
+    /// Deletes the data at the given key.
+    async fn delete(&self, key: &str) -> Result<(), String>;
+    /// Lists the keys starting with the given prefix.
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, String>;
+}
+
+#[rocket::async_trait]
+impl BackupStore for S3Ctx {
+    async fn upload(&self, key: &str, data: Vec<u8>) -> Result<(), String> {
+        self.upload_object(key.to_string(), data)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    async fn download(&self, key: &str) -> Result<Vec<u8>, String> {
+        self.download_object(key.to_string()).await.map_err(|e| e.to_string())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        self.delete_object(key.to_string()).await.map_err(|e| e.to_string())
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, String> {
+        self.list_keys(prefix.to_string()).await.map_err(|e| e.to_string())
+    }
+}
+
+/// The backup settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupConfig {
+    /// The time between two backups.
+    pub interval: Duration,
+    /// The number of backups to keep.
+    pub retention: usize,
+}
+
+impl BackupConfig {
+    /// Reads the backup settings from the env, returns `None` if the backups are disabled.
+    pub fn from_env() -> Option<Self> {
+        let interval = std::env::var(BACKUP_INTERVAL_ENV)
+            .ok()?
+            .parse::<u64>()
+            .unwrap_or_else(|_| panic!("{} must be a number of seconds", BACKUP_INTERVAL_ENV));
+        let retention = match std::env::var(BACKUP_RETENTION_ENV) {
+            Ok(retention) => retention
+                .parse::<usize>()
+                .unwrap_or_else(|_| panic!("{} must be a number", BACKUP_RETENTION_ENV)),
+            Err(_) => DEFAULT_BACKUP_RETENTION,
+        };
+
+        Some(Self {
+            interval: Duration::from_secs(interval),
+            retention: retention.max(1),
+        })
+    }
+}
+
+/// Returns `true` if the latest backup should be restored at startup.
+pub fn is_restore_enabled() -> bool {
+    matches!(std::env::var(BACKUP_RESTORE_ENV), Ok(restore) if restore == "true")
+}
+
+/// Prefix of the keys of the backups for the current environment.
+fn backup_prefix() -> String {
+    match std::env::var("AWS_S3_PROD") {
+        Ok(t) if t == "true" => "production/backups/coordinator_state_".to_string(),
+        _ => "master/backups/coordinator_state_".to_string(),
+    }
+}
+
+/// Key of the backup taken at the given time. The timestamp is zero padded so that keys sort chronologically.
+fn backup_key(timestamp_millis: i128) -> String {
+    format!("{}{:020}.zip", backup_prefix(), timestamp_millis)
+}
+
+/// Returns the files to back up: the coordinator state, the round height, the state of every round and all the
+/// artifacts of the current round, which the coordinator needs to resume it. The contributions of the previous rounds
+/// are left out, their aggregated file being part of the current round.
+pub fn backup_files(storage: &dyn Storage, round_height: u64) -> Result<Vec<PathBuf>, CoordinatorError> {
+    let mut files: Vec<PathBuf> = vec![
+        storage.to_path(&Locator::CoordinatorState)?.into(),
+        storage.to_path(&Locator::RoundHeight)?.into(),
+    ];
+
+    for height in 0..round_height {
+        let round_state: PathBuf = storage.to_path(&Locator::RoundState { round_height: height })?.into();
+        if round_state.exists() {
+            files.push(round_state);
+        }
+    }
+
+    let round_state: PathBuf = storage.to_path(&Locator::RoundState { round_height })?.into();
+    if let Some(round_directory) = round_state.parent().filter(|directory| directory.exists()) {
+        list_files(round_directory, &mut files)?;
+    }
+
+    files.retain(|file| file.exists());
+
+    Ok(files)
+}
+
+/// Appends the files of the given directory and of its subdirectories.
+fn list_files(directory: &Path, files: &mut Vec<PathBuf>) -> Result<(), CoordinatorError> {
+    for entry in std::fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            list_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Serializes the given files into a zip archive, naming them by their path relative to the given base directory.
+pub fn archive_files(base: &Path, files: &[PathBuf]) -> Result<Vec<u8>, String> {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+
+    for file in files {
+        let name = file
+            .strip_prefix(base)
+            .map_err(|_| format!("{} is not in {}", file.display(), base.display()))?;
+        zip.start_file(name.to_string_lossy(), zip::write::FileOptions::default())
+            .map_err(|e| e.to_string())?;
+        zip.write_all(&std::fs::read(file).map_err(|e| e.to_string())?)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(zip.finish().map_err(|e| e.to_string())?.into_inner())
+}
+
+/// Lists the keys of the backups, from the oldest to the latest.
+async fn backup_keys<S: BackupStore>(store: &S) -> Result<Vec<String>, String> {
+    let mut keys = store.list(&backup_prefix()).await?;
+    keys.sort();
+
+    Ok(keys)
+}
+
+/// Uploads the given coordinator state and deletes the backups exceeding the retention. Returns the key of the backup.
+pub async fn backup_state<S: BackupStore>(
+    store: &S,
+    state: Vec<u8>,
+    timestamp_millis: i128,
+    retention: usize,
+) -> Result<String, String> {
+    let key = backup_key(timestamp_millis);
+    store.upload(&key, state).await?;
+
+    let keys = backup_keys(store).await?;
+    for old_key in keys.iter().take(keys.len().saturating_sub(retention)) {
+        store.delete(old_key).await?;
+    }
+
+    Ok(key)
+}
+
+/// Retrieves the latest backup of the coordinator state, if any.
+pub async fn latest_backup<S: BackupStore>(store: &S) -> Result<Option<Vec<u8>>, String> {
+    match backup_keys(store).await?.last() {
+        Some(key) => store.download(key).await.map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Extracts the latest backup into the given base directory of the storage, unless the given state file already
+/// exists. A backup taken before the round artifacts were archived only holds the state, which is written to the state
+/// file. Returns `true` if a backup was restored.
+pub async fn restore_state<S: BackupStore>(store: &S, base: &Path, state_path: &Path) -> Result<bool, String> {
+    if state_path.exists() {
+        return Ok(false);
+    }
+
+    let backup = match latest_backup(store).await? {
+        Some(backup) => backup,
+        None => return Ok(false),
+    };
+
+    match zip::ZipArchive::new(Cursor::new(&backup)) {
+        Ok(mut archive) => archive.extract(base).map_err(|e| e.to_string())?,
+        Err(_) => {
+            if let Some(parent) = state_path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            std::fs::write(state_path, backup).map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(true)
+}
+
+/// Backs up the archive returned by `read_state` on the configured interval, until the shutdown signal is received.
+/// The backups are keyed by the given time source. Failed backups are logged and retried at the next interval.
+pub async fn run_backups<S, F, Fut>(
+    store: &S,
+    config: BackupConfig,
+    mut read_state: F,
+    time: &dyn TimeSource,
+    recv: Receiver<bool>,
+) where
+    S: BackupStore,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Vec<u8>, String>>,
+{
+    loop {
+        sleep(config.interval).await;
+
+        // Return if shutdown signal has been received on the channel
+        if *recv.borrow() {
+            info!("Received shutdown signal, exiting backup task");
+            return;
+        }
+
+        let timestamp_millis = time.now_utc().unix_timestamp_nanos() / 1_000_000;
+        let result = match read_state().await {
+            Ok(state) => backup_state(store, state, timestamp_millis, config.retention).await,
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(key) => info!("Coordinator state backed up to {}", key),
+            Err(e) => error!("Error while backing up the coordinator state: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MockTimeSource;
+    use rocket::tokio::sync::watch;
+    use std::{
+        collections::BTreeMap,
+        sync::{Arc, Mutex},
+    };
+    use time::OffsetDateTime;
+
+    /// In-memory store of the backups.
+    #[derive(Default)]
+    struct MockStore {
+        objects: Mutex<BTreeMap<String, Vec<u8>>>,
+    }
+
+    impl MockStore {
+        fn keys(&self) -> Vec<String> {
+            self.objects.lock().unwrap().keys().cloned().collect()
+        }
+    }
+
+    #[rocket::async_trait]
+    impl BackupStore for MockStore {
+        async fn upload(&self, key: &str, data: Vec<u8>) -> Result<(), String> {
+            self.objects.lock().unwrap().insert(key.to_string(), data);
+            Ok(())
+        }
+
+        async fn download(&self, key: &str) -> Result<Vec<u8>, String> {
+            self.objects
+                .lock()
+                .unwrap()
+                .get(key)
+                .cloned()
+                .ok_or_else(|| format!("missing key {}", key))
+        }
+
+        async fn delete(&self, key: &str) -> Result<(), String> {
+            self.objects.lock().unwrap().remove(key);
+            Ok(())
+        }
+
+        async fn list(&self, prefix: &str) -> Result<Vec<String>, String> {
+            Ok(self.keys().into_iter().filter(|key| key.starts_with(prefix)).collect())
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_backup_state_retention() {
+        let store = MockStore::default();
+
+        for timestamp in 1..=5 {
+            backup_state(&store, vec![timestamp as u8], timestamp, 3).await.unwrap();
+        }
+
+        assert_eq!(store.keys(), vec![backup_key(3), backup_key(4), backup_key(5)]);
+        assert_eq!(latest_backup(&store).await.unwrap(), Some(vec![5]));
+    }
+
+    #[rocket::async_test]
+    async fn test_run_backups_on_interval() {
+        let store = Arc::new(MockStore::default());
+        let config = BackupConfig {
+            interval: Duration::from_millis(50),
+            retention: 2,
+        };
+        let (tx, rx) = watch::channel(false);
+        let start = OffsetDateTime::from_unix_timestamp(1_000).unwrap();
+        let time = Arc::new(MockTimeSource::new(start));
+
+        // Each backup is taken a second after the previous one
+        let task_store = store.clone();
+        let task_time = time.clone();
+        let handle = rocket::tokio::spawn(async move {
+            let read_state = || {
+                task_time.update(|now| now + time::Duration::seconds(1));
+                async { Ok(b"state".to_vec()) }
+            };
+            run_backups(&*task_store, config, read_state, task_time.as_ref(), rx).await
+        });
+
+        // No backup is taken before the first interval elapses
+        assert!(store.keys().is_empty());
+
+        sleep(Duration::from_millis(230)).await;
+        tx.send(true).unwrap();
+        handle.await.unwrap();
+
+        // Several backups were taken at the times of the time source, only the last ones are kept
+        let latest_millis = time.time().unix_timestamp_nanos() / 1_000_000;
+        let expected_keys = vec![backup_key(latest_millis - 1_000), backup_key(latest_millis)];
+        assert_eq!(store.keys(), expected_keys);
+        assert!(latest_millis >= 1_002_000);
+        assert_eq!(latest_backup(&*store).await.unwrap(), Some(b"state".to_vec()));
+    }
+
+    #[rocket::async_test]
+    async fn test_restore_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("transcript");
+        let state_path = base.join("coordinator.json");
+        let store = MockStore::default();
+
+        // Nothing to restore without backups
+        assert!(!restore_state(&store, &base, &state_path).await.unwrap());
+        assert!(!state_path.exists());
+
+        // The backups archive the state and the round artifacts
+        let source = tempfile::tempdir().unwrap();
+        let round_file = source.path().join("round_1/chunk_0/contribution_0.verified");
+        std::fs::create_dir_all(round_file.parent().unwrap()).unwrap();
+        std::fs::write(&round_file, b"contribution").unwrap();
+        std::fs::write(source.path().join("coordinator.json"), b"old").unwrap();
+        let old = archive_files(source.path(), &[source.path().join("coordinator.json")]).unwrap();
+        backup_state(&store, old, 1, 10).await.unwrap();
+
+        std::fs::write(source.path().join("coordinator.json"), b"new").unwrap();
+        let files = vec![source.path().join("coordinator.json"), round_file];
+        let new = archive_files(source.path(), &files).unwrap();
+        backup_state(&store, new, 2, 10).await.unwrap();
+
+        // Files out of the base directory are not archived
+        assert!(archive_files(&base, &files).is_err());
+
+        // The latest backup is restored when the local state is missing
+        assert!(restore_state(&store, &base, &state_path).await.unwrap());
+        assert_eq!(std::fs::read(&state_path).unwrap(), b"new");
+        assert_eq!(
+            std::fs::read(base.join("round_1/chunk_0/contribution_0.verified")).unwrap(),
+            b"contribution"
+        );
+
+        // An existing local state is never overwritten
+        std::fs::write(&state_path, b"local").unwrap();
+        assert!(!restore_state(&store, &base, &state_path).await.unwrap());
+        assert_eq!(std::fs::read(&state_path).unwrap(), b"local");
+
+        // A backup of the state alone, taken before the round artifacts were archived, is restored to the state file
+        std::fs::remove_file(&state_path).unwrap();
+        backup_state(&store, b"legacy".to_vec(), 3, 10).await.unwrap();
+        assert!(restore_state(&store, &base, &state_path).await.unwrap());
+        assert_eq!(std::fs::read(&state_path).unwrap(), b"legacy");
+    }
+}
@@ -0,0 +1,373 @@
+//! Load testing harness of the coordinator.
+//!
+//! A [VirtualContributor] goes through the lock, contribute and complete cycle of a real
+//! contributor against a [Coordinator], but uploads dummy contributions of the expected size
+//! instead of computing them, so that the measured time is spent in the coordinator only. The
+//! dummy contributions are verified the same way on behalf of the coordinator verifiers.
+//!
+//! [run_benchmark] runs a ceremony with the given number of virtual contributors and returns
+//! the timings of each coordinator operation, to spot performance regressions as the ceremony
+//! scales up.
+
+use crate::{
+    authentication::{Dummy, Signature},
+    environment::{Environment, Parameters, Testing},
+    objects::{ContributionFileSignature, ContributionState, Task},
+    storage::{ContributionLocator, ContributionSignatureLocator, Locator, Object},
+    testing::prelude::*,
+    Coordinator, MockTimeSource, Participant,
+};
+
+use setup_utils::calculate_hash;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use time::OffsetDateTime;
+
+/// The timings of the calls to an operation of the coordinator.
+#[derive(Debug, Clone, Default)]
+pub struct OperationStats {
+    /// The number of calls to the operation.
+    pub count: u32,
+    /// The total time spent in the operation.
+    pub total: Duration,
+    /// The longest call to the operation.
+    pub max: Duration,
+}
+
+impl OperationStats {
+    /// Returns the average time of a call to the operation.
+    pub fn mean(&self) -> Duration {
+        match self.count {
+            0 => Duration::ZERO,
+            count => self.total / count,
+        }
+    }
+
+    fn record(&mut self, elapsed: Duration) {
+        self.count += 1;
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+    }
+}
+
+/// The timings of the coordinator operations measured during a benchmark.
+#[derive(Debug, Clone, Default)]
+pub struct BenchmarkStats {
+    operations: BTreeMap<&'static str, OperationStats>,
+}
+
+impl BenchmarkStats {
+    /// Returns the timings of the given operation, if it was called.
+    pub fn get(&self, operation: &str) -> Option<&OperationStats> {
+        self.operations.get(operation)
+    }
+
+    /// Runs the given operation and records its timing.
+    fn time<T>(&mut self, operation: &'static str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.operations.entry(operation).or_default().record(start.elapsed());
+        result
+    }
+}
+
+impl fmt::Display for BenchmarkStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (operation, stats) in &self.operations {
+            writeln!(
+                f,
+                "{:<20} {:>6} calls, mean {:?}, max {:?}, total {:?}",
+                operation,
+                stats.count,
+                stats.mean(),
+                stats.max,
+                stats.total
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns a dummy contribution file of the given size, starting with the given hash
+/// as the coordinator expects.
+fn dummy_contribution(hash: &[u8], size: u64) -> Vec<u8> {
+    let mut contribution = hash.to_vec();
+    contribution.resize(size as usize, 0);
+    contribution
+}
+
+/// Returns the contribution file signature of the given hashes, signed with the dummy scheme.
+fn dummy_signature(
+    challenge_hash: Vec<u8>,
+    response_hash: Vec<u8>,
+    next_challenge_hash: Option<Vec<u8>>,
+) -> anyhow::Result<ContributionFileSignature> {
+    let state = ContributionState::new(challenge_hash, response_hash, next_challenge_hash)?;
+    let signature = Dummy.sign("", &serde_json::to_string(&state)?)?;
+    Ok(ContributionFileSignature::new(signature, state)?)
+}
+
+/// A contributor uploading dummy contributions, to load test the coordinator.
+pub struct VirtualContributor {
+    participant: Participant,
+}
+
+impl VirtualContributor {
+    pub fn new(id: usize) -> Self {
+        Self {
+            participant: Participant::new_contributor(&format!("virtual-contributor-{}", id)),
+        }
+    }
+
+    pub fn participant(&self) -> &Participant {
+        &self.participant
+    }
+
+    /// Returns `true` if the contributor has tasks left in the current round.
+    fn has_assigned_tasks(&self, coordinator: &Coordinator) -> bool {
+        coordinator
+            .current_contributors()
+            .iter()
+            .any(|(participant, info)| {
+                participant == &self.participant && !info.assigned_tasks().is_empty()
+            })
+    }
+
+    ///
+    /// Locks the next task of the contributor, uploads a dummy contribution for it
+    /// and completes it.
+    ///
+    /// Returns the completed task, now pending verification.
+    ///
+    pub fn contribute(&self, coordinator: &mut Coordinator, stats: &mut BenchmarkStats) -> anyhow::Result<Task> {
+        let (chunk_id, locked_locators) = stats.time("try_lock", || coordinator.try_lock(&self.participant))?;
+        let response_locator = locked_locators.next_contribution();
+
+        let challenge_locator = Locator::ContributionFile(locked_locators.current_contribution());
+        let challenge_hash = coordinator.get_file_hash(&challenge_locator)?;
+        let response = dummy_contribution(
+            &challenge_hash,
+            Object::anoma_contribution_file_size(response_locator.round_height(), response_locator.contribution_id()),
+        );
+        let response_hash = calculate_hash(&response).to_vec();
+
+        coordinator.write_contribution(response_locator, response)?;
+        coordinator.write_contribution_file_signature(
+            locked_locators.next_contribution_file_signature(),
+            dummy_signature(challenge_hash, response_hash, None)?,
+        )?;
+
+        stats.time("try_contribute", || {
+            coordinator.try_contribute(&self.participant, chunk_id)
+        })?;
+
+        Ok(Task::new(chunk_id, response_locator.contribution_id()))
+    }
+}
+
+///
+/// Verifies the contribution of the given task on behalf of the verifier it is
+/// assigned to, uploading a dummy next challenge.
+///
+fn verify(coordinator: &mut Coordinator, task: &Task, stats: &mut BenchmarkStats) -> anyhow::Result<()> {
+    let verifier = match coordinator.get_pending_verifications().get(task) {
+        Some(verifier) => verifier.clone(),
+        None => test_coordinator_verifier(coordinator.environment())?,
+    };
+
+    let round = coordinator.current_round()?;
+    let round_height = round.round_height();
+    let chunk_id = task.chunk_id();
+    let contribution_id = task.contribution_id();
+
+    // The final contribution of the round is verified as the first one of the next round.
+    let is_final_contribution = contribution_id == round.expected_number_of_contributions() - 1;
+    let (next_challenge_locator, signature_locator) = match is_final_contribution {
+        true => (
+            ContributionLocator::new(round_height + 1, chunk_id, 0, true),
+            ContributionSignatureLocator::new(round_height + 1, chunk_id, 0, true),
+        ),
+        false => (
+            ContributionLocator::new(round_height, chunk_id, contribution_id, true),
+            ContributionSignatureLocator::new(round_height, chunk_id, contribution_id, true),
+        ),
+    };
+
+    let challenge_hash = coordinator.get_file_hash(&Locator::ContributionFile(ContributionLocator::new(
+        round_height,
+        chunk_id,
+        contribution_id - 1,
+        true,
+    )))?;
+    let response_hash = coordinator.get_file_hash(&Locator::ContributionFile(ContributionLocator::new(
+        round_height,
+        chunk_id,
+        contribution_id,
+        false,
+    )))?;
+    let next_challenge = dummy_contribution(
+        &response_hash,
+        Object::anoma_contribution_file_size(
+            next_challenge_locator.round_height(),
+            next_challenge_locator.contribution_id(),
+        ),
+    );
+    let next_challenge_hash = calculate_hash(&next_challenge).to_vec();
+
+    // Initialize the files uploaded by the verifier.
+    let storage = coordinator.storage_mut();
+    let locator = Locator::ContributionFile(next_challenge_locator);
+    if !storage.exists(&locator) {
+        storage.initialize(locator, next_challenge.len() as u64)?;
+    }
+    let locator = Locator::ContributionFileSignature(signature_locator);
+    if !storage.exists(&locator) {
+        storage.initialize(locator, Object::contribution_file_signature_size(true))?;
+    }
+
+    coordinator.write_contribution(next_challenge_locator, next_challenge)?;
+    coordinator.write_contribution_file_signature(
+        signature_locator,
+        dummy_signature(challenge_hash, response_hash, Some(next_challenge_hash))?,
+    )?;
+
+    coordinator.started_verification(task)?;
+    stats.time("try_verify", || coordinator.try_verify(&verifier, task))?;
+
+    Ok(())
+}
+
+///
+/// Runs a ceremony with the given number of virtual contributors, for parameters
+/// sized for the given number of chunks, and returns the timings of the coordinator
+/// operations.
+///
+/// Each contributor joins the queue, is dropped from it and joins it again, before
+/// contributing in its own round. Then, as many other contributors are dropped from
+/// a last round while holding a lock, each one replaced by the next one from the queue,
+/// and the last replacement completes the round.
+///
+pub fn run_benchmark(num_contributors: usize, num_chunks: usize) -> anyhow::Result<BenchmarkStats> {
+    let mut stats = BenchmarkStats::default();
+
+    // The time is frozen, so that no participant is dropped for being slow.
+    let time = Arc::new(MockTimeSource::new(OffsetDateTime::now_utc()));
+    let parameters = Parameters::TestChunks {
+        number_of_chunks: num_chunks,
+    };
+    let environment = initialize_test_environment(&Environment::from(Testing::from(parameters)));
+
+    let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Dummy), time)?;
+    coordinator.initialize()?;
+
+    let contributors: Vec<VirtualContributor> = (0..num_contributors).map(VirtualContributor::new).collect();
+    for contributor in &contributors {
+        let participant = contributor.participant();
+        let token = format!("virtual-token-{}", participant);
+        stats.time("add_to_queue", || {
+            coordinator.add_to_queue(participant.clone(), None, token.clone(), 10)
+        })?;
+        stats.time("drop_participant", || coordinator.drop_participant(participant))?;
+        stats.time("add_to_queue", || {
+            coordinator.add_to_queue(participant.clone(), None, token, 10)
+        })?;
+    }
+
+    // Each round is aggregated, and the next one started, by the following update.
+    for _ in 0..=num_contributors {
+        stats.time("update_queue", || coordinator.update_queue())?;
+        stats.time("update", || coordinator.update())?;
+
+        let current_contributors = coordinator.current_contributors();
+        if current_contributors.is_empty() {
+            break;
+        }
+
+        for contributor in &contributors {
+            if !current_contributors
+                .iter()
+                .any(|(participant, _)| participant == contributor.participant())
+            {
+                continue;
+            }
+
+            while contributor.has_assigned_tasks(&coordinator) {
+                let task = contributor.contribute(&mut coordinator, &mut stats)?;
+                verify(&mut coordinator, &task, &mut stats)?;
+            }
+        }
+    }
+
+    // Drop the current contributors holding a lock, each one replaced by the next one in the queue.
+    let stalled_contributors: Vec<VirtualContributor> = (num_contributors..=2 * num_contributors)
+        .map(VirtualContributor::new)
+        .collect();
+    for contributor in &stalled_contributors {
+        let participant = contributor.participant();
+        coordinator.add_to_queue(participant.clone(), None, format!("virtual-token-{}", participant), 10)?;
+    }
+    stats.time("update_queue", || coordinator.update_queue())?;
+    stats.time("update", || coordinator.update())?;
+
+    for _ in 0..num_contributors {
+        let current_contributors = coordinator.current_contributors();
+        let (participant, _) = current_contributors
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("No current contributor to drop"))?;
+        coordinator.try_lock(participant)?;
+        stats.time("drop_current_participant", || coordinator.drop_participant(participant))?;
+    }
+
+    // The last replacement completes the round, aggregated by the following update.
+    for contributor in &stalled_contributors {
+        while contributor.has_assigned_tasks(&coordinator) {
+            let task = contributor.contribute(&mut coordinator, &mut stats)?;
+            verify(&mut coordinator, &task, &mut stats)?;
+        }
+    }
+    stats.time("update", || coordinator.update())?;
+
+    let finished_contributors = coordinator.state().number_of_finished_contributors();
+    anyhow::ensure!(
+        finished_contributors == num_contributors + 1,
+        "Only {} of the {} virtual contributors finished",
+        finished_contributors,
+        num_contributors + 1
+    );
+
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[serial]
+    fn test_run_benchmark() {
+        let stats = run_benchmark(3, 8).unwrap();
+
+        // The contributors, and the replacement completing the last round.
+        for operation in ["try_lock", "try_contribute", "try_verify"] {
+            assert_eq!(4, stats.get(operation).unwrap().count, "{}", operation);
+        }
+        for operation in ["drop_participant", "drop_current_participant"] {
+            assert_eq!(3, stats.get(operation).unwrap().count, "{}", operation);
+        }
+        assert_eq!(6, stats.get("add_to_queue").unwrap().count);
+        assert!(stats.get("update").unwrap().count >= 5);
+    }
+
+    /// Prints the timings of a larger ceremony, run with `cargo test -- --ignored`.
+    #[test]
+    #[serial]
+    #[ignore]
+    fn benchmark_virtual_contributors() {
+        let stats = run_benchmark(100, 8).unwrap();
+        println!("{}", stats);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     environment::Environment,
     objects::Round,
-    storage::{ContributionLocator, Disk, DiskObjectReader, Locator, Object, StorageLocator, StorageObject},
+    storage::{ContributionLocator, Locator, Object, ObjectReader, Storage, StorageLocator},
     CoordinatorError,
 };
 
@@ -14,7 +14,7 @@ impl Aggregation {
     /// Runs aggregation for a given environment, storage, and round.
     /// NOTE: The Namada Trusted Setup runs only phase 2 and doesn't chunk the contributions. The aggregation has been left as a placeholder to avoid breaking the whole library logic. In practice, there is no aggregation.
     #[inline]
-    pub(crate) fn run(environment: &Environment, storage: &mut Disk, round: &Round) -> anyhow::Result<()> {
+    pub(crate) fn run(environment: &Environment, storage: &mut dyn Storage, round: &Round) -> anyhow::Result<()> {
         let start = Instant::now();
 
         // Fetch the round height.
@@ -39,7 +39,7 @@ impl Aggregation {
 
         // Load the contribution files.
         let readers = Self::readers(environment, storage, round)?;
-        let _contribution_readers: Vec<_> = readers.iter().map(|r| (r.as_ref(), compressed_output)).collect();
+        let _contribution_readers: Vec<_> = readers.iter().map(|r| (&r[..], compressed_output)).collect();
 
         let elapsed = Instant::now().duration_since(start);
         debug!("Completed aggregation on round {} in {:?}", round_height, elapsed);
@@ -49,11 +49,11 @@ impl Aggregation {
     /// Attempts to open every contribution for the given round and
     /// returns readers to each chunk contribution file.
     #[inline]
-    fn readers<'a>(
+    fn readers(
         environment: &Environment,
-        storage: &'a Disk,
+        storage: &dyn Storage,
         round: &Round,
-    ) -> anyhow::Result<Vec<DiskObjectReader>> {
+    ) -> anyhow::Result<Vec<Box<dyn ObjectReader>>> {
         let mut readers = vec![];
 
         // Fetch the round height.
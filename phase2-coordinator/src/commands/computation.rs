@@ -1,15 +1,19 @@
 use crate::{
     authentication::Signature,
-    commands::SigningKey,
+    commands::{Initialization, SigningKey},
     environment::Environment,
-    storage::{Disk, Locator, StorageLocator, StorageObject},
+    storage::{Locator, Storage, StorageLocator},
     CoordinatorError,
 };
 use phase2::helpers::CurveKind;
 
 use setup_utils::calculate_hash;
 
-use std::{io::Write, sync::Arc, time::Instant};
+use std::{
+    io::Write,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tracing::{debug, error, info, trace};
 
 pub const SEED_LENGTH: usize = 32;
@@ -40,13 +44,13 @@ impl Computation {
     ///
     pub fn run(
         environment: &Environment,
-        storage: &mut Disk,
+        storage: &mut dyn Storage,
         signature: Arc<dyn Signature>,
         contributor_signing_key: &SigningKey,
         challenge_locator: &Locator,
         response_locator: &Locator,
         contribution_file_signature_locator: &Locator,
-        _seed: &Seed,
+        seed: &Seed,
     ) -> anyhow::Result<()> {
         let start = Instant::now();
         info!(
@@ -68,27 +72,21 @@ impl Computation {
         // Run computation on chunk.
         let settings = environment.parameters();
         let curve = settings.curve();
+        let mut response_writer = storage.writer(response_locator)?;
         if let Err(error) = match curve {
-            CurveKind::Bls12_381 => Self::contribute(
-                storage.reader(challenge_locator)?.as_ref(),
-                storage.writer(response_locator)?.as_mut(),
-            ),
-            CurveKind::Bls12_377 => Self::contribute(
-                storage.reader(challenge_locator)?.as_ref(),
-                storage.writer(response_locator)?.as_mut(),
-            ),
-            CurveKind::BW6 => Self::contribute(
-                storage.reader(challenge_locator)?.as_ref(),
-                storage.writer(response_locator)?.as_mut(),
-            ),
+            CurveKind::Bls12_381 => Self::contribute(&storage.reader(challenge_locator)?, &mut response_writer, seed),
+            CurveKind::Bls12_377 => Self::contribute(&storage.reader(challenge_locator)?, &mut response_writer, seed),
+            CurveKind::BW6 => Self::contribute(&storage.reader(challenge_locator)?, &mut response_writer, seed),
         } {
             error!("Computation failed with {}", error);
             return Err(CoordinatorError::ComputationFailed.into());
         }
+        response_writer.flush()?;
+        drop(response_writer);
 
         // Load a contribution response reader.
         let reader = storage.reader(response_locator)?;
-        let contribution_hash = calculate_hash(reader.as_ref());
+        let contribution_hash = calculate_hash(&reader);
         drop(reader);
         debug!("Response hash is {}", pretty_hash!(&contribution_hash));
 
@@ -123,7 +121,11 @@ impl Computation {
         Ok(())
     }
 
-    fn contribute(challenge_reader: &[u8], mut response_writer: &mut [u8]) -> Result<(), CoordinatorError> {
+    fn contribute(
+        challenge_reader: &[u8],
+        mut response_writer: &mut [u8],
+        seed: &Seed,
+    ) -> Result<(), CoordinatorError> {
         trace!("Calculating previous contribution hash and writing it to the response");
 
         let challenge_hash = calculate_hash(&challenge_reader);
@@ -144,19 +146,47 @@ impl Computation {
         // Perform the transformation
         trace!("Computing and writing your contribution, this could take a while");
 
-        // Contribute to the MASP circuit
-        let rand_source = RandomSource::Entropy(String::from("entropy"));
+        // Contribute to the MASP circuit. The test circuit derives its randomness from the seed so that
+        // the tests are reproducible, the real circuit always gathers it from the operating system.
         #[cfg(debug_assertions)]
-        Self::contribute_test_masp(&challenge_reader, &mut response_writer, &rand_source);
+        Self::contribute_test_masp(&challenge_reader, &mut response_writer, &RandomSource::Seed(*seed));
 
         #[cfg(not(debug_assertions))]
-        Self::contribute_masp(&challenge_reader, &mut response_writer, &rand_source);
+        {
+            let _ = seed;
+            let rand_source = RandomSource::Entropy(String::from("entropy"));
+            Self::contribute_masp(&challenge_reader, &mut response_writer, &rand_source);
+        }
 
         trace!("Finishing writing your contribution to response file");
 
         Ok(())
     }
 
+    ///
+    /// Measures the time this machine takes to contribute to a chunk.
+    ///
+    /// The contribution is computed on a synthetic challenge and the response is discarded.
+    ///
+    #[cfg(any(test, feature = "operator"))]
+    pub fn benchmark(rand_source: &RandomSource) -> Result<Duration, CoordinatorError> {
+        let challenge = Initialization::synthetic_challenge()?;
+
+        info!("Starting benchmark contribution");
+        let start = Instant::now();
+
+        #[cfg(debug_assertions)]
+        Self::contribute_test_masp(&challenge, std::io::sink(), rand_source);
+
+        #[cfg(not(debug_assertions))]
+        Self::contribute_masp(&challenge, std::io::sink(), rand_source);
+
+        let elapsed = Instant::now().duration_since(start);
+        info!("Completed benchmark contribution in {:?}", elapsed);
+
+        Ok(elapsed)
+    }
+
     // The [`ContributionFile`] has the following format
     // | previous_contribution_file_hash (64 bytes) |
     // | masp_mpc_new_parameters_contribution |
@@ -320,8 +350,8 @@ impl Computation {
 mod tests {
     use crate::{
         authentication::{Dummy, Signature},
-        commands::{Computation, Initialization, Seed, SEED_LENGTH},
-        storage::{ContributionLocator, ContributionSignatureLocator, Locator, Object, StorageObject},
+        commands::{Computation, Initialization, RandomSource, Seed, SEED_LENGTH},
+        storage::{ContributionLocator, ContributionSignatureLocator, Locator, Object},
         testing::prelude::*,
     };
     use setup_utils::calculate_hash;
@@ -417,4 +447,14 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_computation_benchmark() {
+        let challenge = Initialization::synthetic_challenge().unwrap();
+        assert_eq!(challenge.len() as u64, Object::anoma_contribution_file_size(0, 0));
+        assert_eq!(&challenge[..64], setup_utils::blank_hash().as_slice());
+
+        let seed: Seed = [0; SEED_LENGTH];
+        assert!(Computation::benchmark(&RandomSource::Seed(seed)).is_ok());
+    }
 }
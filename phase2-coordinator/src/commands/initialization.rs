@@ -1,6 +1,6 @@
 use crate::{
     environment::Environment,
-    storage::{ContributionLocator, Disk, Locator, Object, StorageObject},
+    storage::{ContributionLocator, Locator, Object, Storage},
     CoordinatorError,
 };
 
@@ -40,7 +40,7 @@ impl Initialization {
     #[inline]
     pub(crate) fn run(
         environment: &Environment,
-        storage: &mut Disk,
+        storage: &mut dyn Storage,
         round_height: u64,
         chunk_id: u64,
     ) -> anyhow::Result<Vec<u8>> {
@@ -58,14 +58,17 @@ impl Initialization {
         // Run ceremony initialization on chunk.
         let settings = environment.parameters();
 
+        let mut writer = storage.writer(&contribution_locator)?;
         if let Err(error) = match settings.curve() {
-            CurveKind::Bls12_381 => Self::initialization(storage.writer(&contribution_locator)?.as_mut()),
-            CurveKind::Bls12_377 => Self::initialization(storage.writer(&contribution_locator)?.as_mut()),
-            CurveKind::BW6 => Self::initialization(storage.writer(&contribution_locator)?.as_mut()),
+            CurveKind::Bls12_381 => Self::initialization(&mut writer),
+            CurveKind::Bls12_377 => Self::initialization(&mut writer),
+            CurveKind::BW6 => Self::initialization(&mut writer),
         } {
             error!("Initialization failed with {}", error);
             return Err(CoordinatorError::InitializationFailed.into());
         }
+        writer.flush()?;
+        drop(writer);
 
         // Copy the current transcript to the next transcript.
         // This operation will *overwrite* the contents of `next_transcript`.
@@ -84,6 +87,20 @@ impl Initialization {
         Ok(hash)
     }
 
+    ///
+    /// Generates an initial challenge in memory, without touching the storage.
+    ///
+    /// The challenge has the same size and format as the one of a real round, so that it
+    /// can be used to estimate the cost of a contribution locally.
+    ///
+    #[cfg(any(test, feature = "operator"))]
+    pub(crate) fn synthetic_challenge() -> Result<Vec<u8>, CoordinatorError> {
+        let mut challenge = vec![0; Object::anoma_contribution_file_size(0, 0) as usize];
+        Self::initialization(&mut challenge)?;
+
+        Ok(challenge)
+    }
+
     /// Runs Phase 2 initialization on the given parameters.
     #[inline]
     fn initialization(mut writer: &mut [u8]) -> Result<(), CoordinatorError> {
@@ -116,7 +133,7 @@ impl Initialization {
     /// Compute both contribution hashes and check for equivalence.
     #[inline]
     fn check_hash(
-        storage: &Disk,
+        storage: &dyn Storage,
         contribution_locator: &Locator,
         next_contribution_locator: &Locator,
     ) -> anyhow::Result<Vec<u8>> {
@@ -124,8 +141,8 @@ impl Initialization {
         let next = storage.reader(next_contribution_locator)?;
 
         // Compare the contribution hashes of both files to ensure the copy succeeded.
-        let contribution_hash_0 = calculate_hash(current.as_ref());
-        let contribution_hash_1 = calculate_hash(next.as_ref());
+        let contribution_hash_0 = calculate_hash(&current);
+        let contribution_hash_1 = calculate_hash(&next);
         if contribution_hash_0 != contribution_hash_1 {
             return Err(CoordinatorError::InitializationTranscriptsDiffer.into());
         }
@@ -227,7 +244,7 @@ impl Initialization {
 mod tests {
     use crate::{
         commands::Initialization,
-        storage::{ContributionLocator, Locator, StorageObject},
+        storage::{ContributionLocator, Locator},
         testing::prelude::*,
     };
     use setup_utils::{blank_hash, calculate_hash, GenericArray};
@@ -18,7 +18,7 @@ pub(crate) use verification::*;
 use crate::{
     authentication::Signature,
     objects::{ContributionFileSignature, ContributionState},
-    storage::{Disk, Locator, StorageLocator, StorageObject},
+    storage::{Locator, Storage, StorageLocator},
     CoordinatorError,
 };
 
@@ -47,7 +47,7 @@ pub type SigningKey = String;
 #[cfg(any(test, feature = "operator"))]
 #[inline]
 pub(crate) fn write_contribution_file_signature(
-    storage: &mut Disk,
+    storage: &mut dyn Storage,
     signature: Arc<dyn Signature>,
     signing_key: &SigningKey,
     challenge_locator: &Locator,
@@ -55,21 +55,19 @@ pub(crate) fn write_contribution_file_signature(
     next_challenge_locator: Option<&Locator>,
     contribution_file_signature_locator: &Locator,
 ) -> Result<(), CoordinatorError> {
-    use crate::storage::ObjectWriter;
-
     // Calculate the challenge hash.
     let challenge_reader = storage.reader(challenge_locator)?;
-    let challenge_hash = calculate_hash(challenge_reader.as_ref()).to_vec();
+    let challenge_hash = calculate_hash(&challenge_reader).to_vec();
 
     // Calculate the response hash.
     let response_reader = storage.reader(response_locator)?;
-    let response_hash = calculate_hash(response_reader.as_ref()).to_vec();
+    let response_hash = calculate_hash(&response_reader).to_vec();
 
     // Calculate the next challenge hash.
     let next_challenge_hash = match next_challenge_locator {
         Some(next_challenge_locator) => {
             let next_challenge_reader = storage.reader(next_challenge_locator)?;
-            let next_challenge_hash = calculate_hash(next_challenge_reader.as_ref()).to_vec();
+            let next_challenge_hash = calculate_hash(&next_challenge_reader).to_vec();
 
             Some(next_challenge_hash)
         }
@@ -94,9 +92,7 @@ pub(crate) fn write_contribution_file_signature(
         &storage.to_path(&contribution_file_signature_locator)?
     );
 
-    contribution_file_signature_writer
-        .as_mut()
-        .write_all(&contribution_file_signature_bytes[..])?;
+    (&mut contribution_file_signature_writer[..]).write_all(&contribution_file_signature_bytes[..])?;
     contribution_file_signature_writer.flush()?;
 
     Ok(())
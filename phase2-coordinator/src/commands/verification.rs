@@ -2,16 +2,15 @@ use crate::{
     authentication::Signature,
     commands::SigningKey,
     environment::Environment,
-    storage::{
-        ContributionLocator, ContributionSignatureLocator, Disk, Locator, Object, StorageLocator, StorageObject,
-    },
+    objects::VerificationCache,
+    storage::{ContributionLocator, ContributionSignatureLocator, Locator, Object, Storage, StorageLocator},
     CoordinatorError,
 };
 use phase2::helpers::CurveKind;
 use setup_utils::{calculate_hash, GenericArray, U64};
 
 use std::{io::Write, sync::Arc, time::Instant};
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, warn};
 
 use blake2::{Blake2b512, Digest};
 use itertools::Itertools;
@@ -28,7 +27,7 @@ impl Verification {
     #[inline]
     pub(crate) fn run(
         environment: &Environment,
-        storage: &mut Disk,
+        storage: &mut dyn Storage,
         signature: Arc<dyn Signature>,
         signing_key: &SigningKey,
         round_height: u64,
@@ -155,7 +154,7 @@ impl Verification {
     #[inline]
     fn verification(
         environment: &Environment,
-        storage: &mut Disk,
+        storage: &mut dyn Storage,
         _chunk_id: u64,
         challenge_locator: Locator,
         response_locator: Locator,
@@ -168,21 +167,61 @@ impl Verification {
             return Err(CoordinatorError::ContributionLocatorMissing);
         }
 
-        // Execute ceremony verification on chunk.
+        // Fetch the hashes of the challenge and response files, if the verdicts are cached.
+        let cache_key = match environment.verification_cache() {
+            true => Some((
+                calculate_hash(&storage.reader(&challenge_locator)?),
+                calculate_hash(&storage.reader(&response_locator)?),
+            )),
+            false => None,
+        };
+        let cached_verdict = match &cache_key {
+            Some((challenge_hash, response_hash)) => {
+                Self::load_cache(storage, round_height)?.get(challenge_hash, response_hash)
+            }
+            None => None,
+        };
+
+        // Execute ceremony verification on chunk, unless the same files were already verified.
         let settings = environment.parameters();
-        let result = match settings.curve() {
-            CurveKind::Bls12_381 => Self::transform_pok_and_correctness(
-                storage.reader(&challenge_locator)?.as_ref(),
-                storage.reader(&response_locator)?.as_ref(),
-            ),
-            CurveKind::Bls12_377 => Self::transform_pok_and_correctness(
-                storage.reader(&challenge_locator)?.as_ref(),
-                storage.reader(&response_locator)?.as_ref(),
-            ),
-            CurveKind::BW6 => Self::transform_pok_and_correctness(
-                storage.reader(&challenge_locator)?.as_ref(),
-                storage.reader(&response_locator)?.as_ref(),
-            ),
+        let result = match (cached_verdict, &cache_key) {
+            (Some(true), Some((_, response_hash))) => {
+                debug!("Reusing the cached verdict of the verification");
+                Ok(*response_hash)
+            }
+            (Some(_), _) => {
+                debug!("Reusing the cached verdict of the verification");
+                Err(CoordinatorError::VerificationFailed)
+            }
+            (None, _) => {
+                let result = match settings.curve() {
+                    CurveKind::Bls12_381 => Self::transform_pok_and_correctness(
+                        &storage.reader(&challenge_locator)?,
+                        &storage.reader(&response_locator)?,
+                    ),
+                    CurveKind::Bls12_377 => Self::transform_pok_and_correctness(
+                        &storage.reader(&challenge_locator)?,
+                        &storage.reader(&response_locator)?,
+                    ),
+                    CurveKind::BW6 => Self::transform_pok_and_correctness(
+                        &storage.reader(&challenge_locator)?,
+                        &storage.reader(&response_locator)?,
+                    ),
+                };
+
+                // Cache the verdict, unless the verification could not complete.
+                if let Some((challenge_hash, response_hash)) = &cache_key {
+                    match &result {
+                        Ok(_) => Self::cache_verdict(storage, round_height, challenge_hash, response_hash, true)?,
+                        Err(CoordinatorError::ContributionHashMismatch) => {
+                            Self::cache_verdict(storage, round_height, challenge_hash, response_hash, false)?
+                        }
+                        Err(_) => (),
+                    }
+                }
+
+                result
+            }
         };
         let response_hash = match result {
             Ok(response_hash) => response_hash,
@@ -217,25 +256,28 @@ impl Verification {
                 )?;
             }
 
+            let mut next_challenge_writer = storage.writer(&next_challenge_locator)?;
             match settings.curve() {
                 CurveKind::Bls12_381 => Self::decompress(
-                    storage.reader(&response_locator)?.as_ref(),
-                    storage.writer(&next_challenge_locator)?.as_mut(),
+                    &storage.reader(&response_locator)?,
+                    &mut next_challenge_writer,
                     response_hash.as_ref(),
                 )?,
                 CurveKind::Bls12_377 => Self::decompress(
-                    storage.reader(&response_locator)?.as_ref(),
-                    storage.writer(&next_challenge_locator)?.as_mut(),
+                    &storage.reader(&response_locator)?,
+                    &mut next_challenge_writer,
                     response_hash.as_ref(),
                 )?,
                 CurveKind::BW6 => Self::decompress(
-                    storage.reader(&response_locator)?.as_ref(),
-                    storage.writer(&next_challenge_locator)?.as_mut(),
+                    &storage.reader(&response_locator)?,
+                    &mut next_challenge_writer,
                     response_hash.as_ref(),
                 )?,
             };
+            next_challenge_writer.flush()?;
+            drop(next_challenge_writer);
 
-            calculate_hash(storage.reader(&next_challenge_locator)?.as_ref())
+            calculate_hash(&storage.reader(&next_challenge_locator)?)
         };
 
         debug!("The next challenge hash is {}", pretty_hash!(&next_challenge_hash));
@@ -244,7 +286,6 @@ impl Verification {
             // Fetch the saved response hash in the next challenge file.
             let saved_response_hash = storage
                 .reader(&next_challenge_locator)?
-                .as_ref()
                 .chunks(64)
                 .next()
                 .unwrap()
@@ -262,6 +303,58 @@ impl Verification {
         Ok(())
     }
 
+    ///
+    /// Loads the verdicts of the previous verifications of the given round from storage,
+    /// discarding the verdicts of the previous rounds and of another version of the
+    /// verification logic.
+    ///
+    fn load_cache(storage: &dyn Storage, round_height: u64) -> Result<VerificationCache, CoordinatorError> {
+        if !storage.exists(&Locator::VerificationCache) {
+            return Ok(VerificationCache::new(round_height));
+        }
+
+        match storage.get(&Locator::VerificationCache) {
+            Ok(Object::VerificationCache(cache)) if cache.is_current(round_height) => Ok(cache),
+            Ok(Object::VerificationCache(_)) => {
+                debug!("Discarding the verdicts of a previous round or version of the verification");
+                Ok(VerificationCache::new(round_height))
+            }
+            Ok(_) => Err(CoordinatorError::StorageFailed),
+            Err(error) => {
+                warn!("Discarding the unreadable verification cache: {}", error);
+                Ok(VerificationCache::new(round_height))
+            }
+        }
+    }
+
+    ///
+    /// Records the verdict of the verification of the given challenge and response hashes in storage.
+    ///
+    /// The verdict is appended to the cache of the given round, which replaces the cache of
+    /// a previous round, so that the cache only grows with the verifications of a round.
+    ///
+    fn cache_verdict(
+        storage: &mut dyn Storage,
+        round_height: u64,
+        challenge_hash: &[u8],
+        response_hash: &[u8],
+        verdict: bool,
+    ) -> Result<(), CoordinatorError> {
+        let cache = Self::load_cache(storage, round_height)?;
+        if storage.exists(&Locator::VerificationCache) && !cache.is_empty() {
+            let entry = VerificationCache::entry_bytes(challenge_hash, response_hash, verdict);
+            return storage.append(&Locator::VerificationCache, &entry);
+        }
+
+        let mut cache = VerificationCache::new(round_height);
+        cache.insert(challenge_hash, response_hash, verdict);
+
+        match storage.exists(&Locator::VerificationCache) {
+            true => storage.update(&Locator::VerificationCache, Object::VerificationCache(cache)),
+            false => storage.insert(Locator::VerificationCache, Object::VerificationCache(cache)),
+        }
+    }
+
     #[inline]
     fn transform_pok_and_correctness(
         challenge_reader: &[u8],
@@ -417,13 +510,16 @@ mod tests {
     use crate::{
         authentication::Dummy,
         commands::{Computation, Seed, Verification, SEED_LENGTH},
+        environment::{Environment, Parameters, Testing},
+        objects::VerificationCache,
         storage::{ContributionLocator, ContributionSignatureLocator, Locator, Object},
         testing::prelude::*,
-        Coordinator,
+        Coordinator, CoordinatorError,
     };
 
     use once_cell::sync::Lazy;
     use rand::RngCore;
+    use setup_utils::calculate_hash;
     use time::OffsetDateTime;
 
     #[test]
@@ -525,4 +621,115 @@ mod tests {
             assert!(storage.exists(&next));
         }
     }
+
+    #[test]
+    #[serial]
+    fn test_verification_cache_hit() {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .verification_cache(true)
+        .into();
+        initialize_test_environment(&environment);
+
+        let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy)).unwrap();
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let signing_key = "secret_key".to_string();
+
+        coordinator.run_initialization(OffsetDateTime::now_utc()).unwrap();
+        coordinator.next_round(*TEST_STARTED_AT, vec![contributor]).unwrap();
+
+        let round_height = coordinator.current_round_height().unwrap();
+        let chunk_id = 0;
+        let challenge_locator = &Locator::ContributionFile(ContributionLocator::new(round_height, chunk_id, 0, true));
+        let response_locator = &Locator::ContributionFile(ContributionLocator::new(round_height, chunk_id, 1, false));
+        let contribution_file_signature_locator =
+            &Locator::ContributionFileSignature(ContributionSignatureLocator::new(round_height, chunk_id, 1, false));
+
+        let signature = coordinator.signature();
+        let storage = coordinator.storage_mut();
+
+        storage
+            .initialize(
+                response_locator.clone(),
+                Object::anoma_contribution_file_size(round_height, 1),
+            )
+            .unwrap();
+        storage
+            .initialize(
+                contribution_file_signature_locator.clone(),
+                Object::contribution_file_signature_size(false),
+            )
+            .unwrap();
+
+        // Run a valid computation on the chunk.
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        Computation::run(
+            &environment,
+            storage,
+            signature.clone(),
+            &signing_key,
+            challenge_locator,
+            response_locator,
+            contribution_file_signature_locator,
+            &seed,
+        )
+        .unwrap();
+
+        let challenge_hash = calculate_hash(&storage.reader(challenge_locator).unwrap());
+        let response_hash = calculate_hash(&storage.reader(response_locator).unwrap());
+
+        // A cached rejection skips the verification of the valid contribution.
+        let mut cache = VerificationCache::new(round_height);
+        cache.insert(&challenge_hash, &response_hash, false);
+        storage
+            .insert(Locator::VerificationCache, Object::VerificationCache(cache))
+            .unwrap();
+
+        let result = Verification::run(
+            &environment,
+            storage,
+            signature.clone(),
+            &signing_key,
+            round_height,
+            chunk_id,
+            1,
+            true,
+        );
+        assert!(matches!(result, Err(CoordinatorError::VerificationFailed)));
+
+        // The verdicts of a previous round are discarded, the verification runs and caches its verdict.
+        let mut cache = VerificationCache::new(round_height - 1);
+        cache.insert(&challenge_hash, &response_hash, false);
+        storage
+            .update(&Locator::VerificationCache, Object::VerificationCache(cache))
+            .unwrap();
+
+        Verification::run(
+            &environment,
+            storage,
+            signature,
+            &signing_key,
+            round_height,
+            chunk_id,
+            1,
+            true,
+        )
+        .unwrap();
+
+        let cache = Verification::load_cache(storage, round_height).unwrap();
+        assert_eq!(1, cache.len());
+        assert_eq!(Some(true), cache.get(&challenge_hash, &response_hash));
+
+        // The next verdict of the round is appended to the cache.
+        Verification::cache_verdict(storage, round_height, &[0; 64], &[1; 64], false).unwrap();
+        let cache = Verification::load_cache(storage, round_height).unwrap();
+        assert_eq!(2, cache.len());
+        assert_eq!(Some(true), cache.get(&challenge_hash, &response_hash));
+        assert_eq!(Some(false), cache.get(&[0; 64], &[1; 64]));
+    }
 }
@@ -3,206 +3,464 @@
 //! [CoordinatorState] object.
 
 use crate::{
+    audit::{AuditEvent, AuditLog},
     authentication::Signature,
     commands::{Aggregation, Initialization},
     coordinator_state::{
-        CeremonyStorageAction, CoordinatorState, DropParticipant, ParticipantInfo, ResetCurrentRoundStorageAction,
-        RoundMetrics, IP_BAN, TOKEN_BLACKLIST,
+        CeremonyStorageAction, ChunkUpload, CoordinatorState, Diagnostics, DropParticipant, FinishedParticipants,
+        ParticipantInfo, ResetCurrentRoundStorageAction, RoundMetrics, IP_BAN, REJECT_DUPLICATES, TOKEN_BLACKLIST,
     },
-    environment::{Deployment, Environment},
+    environment::{Deployment, Environment, Timeouts},
+    merkle::{leaf_hash, MerkleHash, MerkleProof, MerkleTree},
     objects::{
-        participant::*, task::TaskInitializationError, ContributionFileSignature, ContributionInfo, LockedLocators,
-        Round, Task, TrimmedContributionInfo,
+        participant::*,
+        task::{check_contribution_id, TaskInitializationError},
+        ContributionFileSignature, ContributionInfo, LockedLocators, Round, Task, TrimmedContributionInfo,
     },
     storage::{
-        ContributionLocator, ContributionSignatureLocator, Disk, Locator, LocatorPath, Object, StorageAction,
-        StorageLocator, StorageObject, UpdateAction,
+        ContributionLocator, ContributionSignatureLocator, Locator, LocatorPath, Object, Storage, StorageAction,
+        StorageLocator, UpdateAction,
     },
+    timestamp::{PendingTimestamp, TimestampAuthority, TimestampToken},
 };
+use rand::{rngs::OsRng, RngCore, SeedableRng};
+use rand_chacha::ChaChaRng;
+use rocket::tokio::sync::broadcast;
+use serde::{Deserialize, Serialize};
 use setup_utils::calculate_hash;
+use sha2::{Digest, Sha256};
 
 use std::{
-    collections::HashSet,
-    fmt,
+    collections::{BTreeSet, HashMap, HashSet},
+    fmt::Debug,
+    io::{Seek, Write},
     net::IpAddr,
-    sync::{Arc, RwLock},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+        Mutex,
+        RwLock,
+    },
 };
+use thiserror::Error;
 use time::OffsetDateTime;
 use tracing::*;
+use zip::{write::FileOptions, ZipWriter};
 
-#[cfg(any(test, feature = "operator"))]
-use std::collections::HashMap;
-
-#[derive(Debug)]
+#[derive(Debug, Error)]
 pub enum CoordinatorError {
+    #[error("The size of the aggregated contribution file is incorrect")]
     AggregateContributionFileSizeMismatch,
+    #[error("The entry {sequence} of the audit log doesn't follow the previous one")]
+    AuditLogHashMismatch { sequence: u64 },
+    #[error("The key rotated in the entry {sequence} of the audit log is not a key of the coordinator")]
+    AuditLogKeyUnknown { sequence: u64 },
+    #[error("The audit log expected the entry {expected} but found the entry {found}")]
+    AuditLogSequenceMismatch { expected: u64, found: u64 },
+    #[error("The signature of the entry {sequence} of the audit log is invalid")]
+    AuditLogSignatureInvalid { sequence: u64 },
+    #[error("Bincode error: {0}")]
+    BincodeError(bincode::Error),
+    #[error("The ceremony is over")]
     CeremonyIsOver,
+    #[error("The ceremony is paused until {next_open}")]
+    CeremonyPaused { next_open: OffsetDateTime },
+    #[error("The size of the challenge hash is invalid")]
     ChallengeHashSizeInvalid,
+    #[error("The chunk is already complete")]
     ChunkAlreadyComplete,
+    #[error("The chunk is already verified")]
     ChunkAlreadyVerified,
+    #[error("The chunk id was already added")]
     ChunkIdAlreadyAdded,
+    #[error("The chunk id is invalid")]
     ChunkIdInvalid,
+    #[error("The chunk id doesn't match the expected one")]
     ChunkIdMismatch,
+    #[error("The chunk id is missing")]
     ChunkIdMissing,
+    #[error("The chunk lock was already acquired")]
     ChunkLockAlreadyAcquired,
+    #[error("The limit of chunk locks was reached")]
     ChunkLockLimitReached,
+    #[error("The chunk is missing")]
     ChunkMissing,
+    #[error("The chunk is missing a verification")]
     ChunkMissingVerification,
+    #[error("The chunk upload is missing parts")]
+    ChunkUploadIncomplete,
+    #[error("The chunk upload is missing")]
+    ChunkUploadMissing,
+    #[error("The part is outside of the chunk upload or overlaps another part")]
+    ChunkUploadPartInvalid,
+    #[error("Chunk {chunk_id} cannot be locked without contributions")]
     ChunkCannotLockZeroContributions { chunk_id: u64 },
+    #[error("The chunk is not locked or is locked by another participant")]
     ChunkNotLockedOrByWrongParticipant,
+    #[error("The cohort durations {0:?} must be a comma separated list of positive numbers of seconds")]
+    CohortDurationsInvalid(String),
+    #[error("The cohorts cannot be merged: {0}")]
+    CohortMergeInvalid(String),
+    #[error("The computation failed")]
     ComputationFailed,
+    #[error("Hashing compressed contributions is not supported")]
     CompressedContributionHashingUnsupported,
+    #[error("The pending tasks of contributor {0} cannot be empty")]
     ContributorPendingTasksCannotBeEmpty(Participant),
+    #[error("The contribution was already assigned a verified locator")]
     ContributionAlreadyAssignedVerifiedLocator,
+    #[error("The contribution was already assigned a verifier")]
     ContributionAlreadyAssignedVerifier,
+    #[error("The contribution is already verified")]
     ContributionAlreadyVerified,
+    #[error("The batch of contributions is empty or contributes to a chunk more than once")]
+    ContributionBatchInvalid,
+    #[error("The contribution {second} is a duplicate of the contribution {first}")]
+    ContributionDuplicated { first: Task, second: Task },
+    #[error("The contribution failed")]
     ContributionFailed,
+    #[error("The contribution file signature locator already exists")]
     ContributionFileSignatureLocatorAlreadyExists,
+    #[error("The size of the contribution file is incorrect")]
     ContributionFileSizeMismatch,
+    #[error("The contribution hash doesn't match the expected one")]
     ContributionHashMismatch,
+    #[error("The contribution id should be zero")]
     ContributionIdIsNonzero,
+    #[error("The contribution id doesn't match the expected one")]
     ContributionIdMismatch,
+    #[error("The contribution id must be nonzero")]
     ContributionIdMustBeNonzero,
+    #[error("The contribution locator already exists")]
     ContributionLocatorAlreadyExists,
+    #[error("The contribution locator is incorrect")]
     ContributionLocatorIncorrect,
+    #[error("The contribution locator is missing")]
     ContributionLocatorMissing,
+    #[error("The contribution is missing")]
     ContributionMissing,
+    #[error("The contribution is missing a verification")]
     ContributionMissingVerification,
+    #[error("The contribution is missing a verified locator")]
     ContributionMissingVerifiedLocator,
+    #[error("The contribution is missing a verifier")]
     ContributionMissingVerifier,
+    #[error("The contribution failed the sanity checks")]
+    ContributionSanityCheckFailed,
+    #[error("The contribution should not exist")]
     ContributionShouldNotExist,
+    #[error("The size of the contribution signature file is incorrect")]
     ContributionSignatureFileSizeMismatch,
+    #[error("The size of the contribution signature is incorrect")]
     ContributionSignatureSizeMismatch,
+    #[error("The contributions are complete")]
     ContributionsComplete,
+    #[error("The contributor already contributed")]
     ContributorAlreadyContributed,
+    #[error("The contributor signature is invalid")]
     ContributorSignatureInvalid,
+    #[error("The contributors are missing")]
     ContributorsMissing,
+    #[error("The coordinator contributor is missing")]
     CoordinatorContributorMissing,
+    #[error("The coordinator is shutting down and doesn't accept new participants or locks")]
+    CoordinatorDraining,
+    #[error("The coordinator state is not initialized")]
     CoordinatorStateNotInitialized,
+    #[error("The current round is aggregating")]
     CurrentRoundAggregating,
+    #[error("The current round is aggregated")]
     CurrentRoundAggregated,
+    #[error("The current round is finished")]
     CurrentRoundFinished,
+    #[error("The current round is not aggregated")]
     CurrentRoundNotAggregated,
+    #[error("The current round is not finished")]
     CurrentRoundNotFinished,
+    #[error("Failed to drop the participant")]
     DropParticipantFailed,
+    #[error("Expected a contributor")]
     ExpectedContributor,
+    #[error("Expected a verifier")]
     ExpectedVerifier,
+    #[error(transparent)]
     Error(anyhow::Error),
+    #[error("The initialization failed")]
     InitializationFailed,
+    #[error("The initialization transcripts differ")]
     InitializationTranscriptsDiffer,
+    #[error("Failed to parse an integer: {0}")]
     Integer(std::num::ParseIntError),
+    #[error("IO error: {0}")]
     IOError(std::io::Error),
+    #[error("Failed to decode hex: {0}")]
     Hex(hex::FromHexError),
+    #[error("JSON error: {0}")]
     JsonError(serde_json::Error),
+    #[error("The justification is invalid")]
     JustificationInvalid,
+    #[error("Failed to deserialize the locator")]
     LocatorDeserializationFailed,
+    #[error("The locator file already exists")]
     LocatorFileAlreadyExists,
+    #[error("The locator file already exists and is open")]
     LocatorFileAlreadyExistsAndOpen,
+    #[error("The locator file is already open")]
     LocatorFileAlreadyOpen,
+    #[error("The locator file is missing")]
     LocatorFileMissing,
+    #[error("The locator file is not open")]
     LocatorFileNotOpen,
+    #[error("The locator file should be open")]
     LocatorFileShouldBeOpen,
+    #[error("Failed to serialize the locator")]
     LocatorSerializationFailed,
+    #[error("The chunk lock limit of a contributor must be at least 1")]
+    LockLimitInvalid,
+    #[error("The next challenge hash already exists")]
     NextChallengeHashAlreadyExists,
+    #[error("The size of the next challenge hash is invalid")]
     NextChallengeHashSizeInvalid,
+    #[error("The next challenge hash is missing")]
     NextChallengeHashMissing,
+    #[error("The next round is already in precommit")]
     NextRoundAlreadyInPrecommit,
+    #[error("The next round is not in precommit")]
+    NextRoundNotInPrecommit,
+    #[error("The next round should be empty")]
     NextRoundShouldBeEmpty,
+    #[error("The number of chunks is invalid")]
     NumberOfChunksInvalid,
+    #[error("The number of contributions differs")]
     NumberOfContributionsDiffer,
+    #[error("Contributing out of the order of the assigned tasks is disabled")]
+    OutOfOrderContributionsDisabled,
+    #[error("The participant was already added")]
     ParticipantAlreadyAdded,
+    #[error("The participant already added the chunk")]
     ParticipantAlreadyAddedChunk,
+    #[error("The participant is already banned")]
     ParticipantAlreadyBanned,
+    #[error("The participant was already dropped")]
     ParticipantAlreadyDropped,
+    #[error("The participant already finished")]
     ParticipantAlreadyFinished,
+    #[error("The participant already finished chunk {chunk_id}")]
     ParticipantAlreadyFinishedChunk { chunk_id: u64 },
+    #[error("The participant already finished task {0}")]
     ParticipantAlreadyFinishedTask(Task),
+    #[error("The participant already holds a chunk lock")]
     ParticipantAlreadyHasLockedChunk,
+    #[error("The participant already holds chunk locks")]
     ParticipantAlreadyHasLockedChunks,
+    #[error("The participant is already precommitted")]
     ParticipantAlreadyPrecommitted,
+    #[error("The participant already started")]
     ParticipantAlreadyStarted,
+    #[error("The participant is already working on chunk {chunk_id}")]
     ParticipantAlreadyWorkingOnChunk { chunk_id: u64 },
+    #[error("The participant is banned")]
     ParticipantBanned,
+    #[error("The participant did not complete all the expected tasks")]
+    ParticipantDidNotCompleteAllTasks,
+    #[error("The participant did not do any work")]
     ParticipantDidNotDoWork,
+    #[error("The participant didn't lock the chunk id")]
     ParticipantDidntLockChunkId,
+    #[error("The participant has assigned tasks")]
     ParticipantHasAssignedTasks,
+    #[error("The participant holds the maximum number of chunk locks")]
     ParticipantHasLockedMaximumChunks,
+    #[error("The participant has not started")]
     ParticipantHasNotStarted,
+    #[error("The participant has no remaining tasks")]
     ParticipantHasNoRemainingTasks,
+    #[error("The participant has remaining tasks")]
     ParticipantHasRemainingTasks,
+    #[error("The participant is in the current round and cannot join the queue")]
     ParticipantInCurrentRoundCannotJoinQueue,
+    #[error("The participant ip address was already added")]
     ParticipantIpAlreadyAdded,
+    #[error("The participant locked a chunk with many contributions")]
     ParticipantLockedChunkWithManyContributions,
+    #[error("The participant is missing")]
     ParticipantMissing,
+    #[error("The participant is missing the completed task {completed_task}")]
+    ParticipantMissingCompletedTask { completed_task: Task },
+    #[error("The participant is missing the disposing task")]
     ParticipantMissingDisposingTask,
+    #[error("The participant is missing the pending task {pending_task}")]
     ParticipantMissingPendingTask { pending_task: Task },
+    #[error("The participant {0} was not found")]
     ParticipantNotFound(Participant),
+    #[error("The participant is not ready")]
     ParticipantNotReady,
+    #[error("The participant left the queue too recently to join it again")]
+    ParticipantRejoinedQueueTooSoon,
+    #[error("The round height of the participant is invalid")]
     ParticipantRoundHeightInvalid,
+    #[error("The round height of the participant is missing")]
     ParticipantRoundHeightMissing,
+    #[error("The participant should have pending tasks")]
     ParticipantShouldHavePendingTasks,
+    #[error("The participant should not be finished")]
     ParticipantShouldNotBeFinished,
+    #[error("The participant still holds a lock")]
     ParticipantStillHasLock,
+    #[error("The participant still holds locks")]
     ParticipantStillHasLocks,
+    #[error("The participant still has the task as assigned")]
     ParticipantStillHasTaskAsAssigned,
+    #[error("The participant still has the task as pending")]
     ParticipantStillHasTaskAsPending,
+    #[error("The reordering of the participant tasks is invalid")]
+    ParticipantTasksReorderInvalid,
+    #[error("The participant is unauthorized")]
     ParticipantUnauthorized,
+    #[error("The participant is unauthorized for chunk {chunk_id}")]
     ParticipantUnauthorizedForChunkId { chunk_id: u64 },
+    #[error("The participant was dropped")]
     ParticipantWasDropped,
+    #[error("The pending tasks must contain the response task {response_task}")]
     PendingTasksMustContainResponseTask { response_task: Task },
+    #[error("Phase 2 setup error: {0}")]
     Phase2Setup(setup_utils::Error),
+    #[error("The queue is frozen and doesn't accept new participants")]
+    QueueFrozen,
+    #[error("The queue is empty")]
     QueueIsEmpty,
+    #[error("The queue wait time is not over")]
     QueueWaitTimeIncomplete,
+    #[error("The size of the response hash is invalid")]
     ResponseHashSizeInvalid,
+    #[error("The round aggregation failed")]
     RoundAggregationFailed,
+    #[error("The round is already initialized")]
     RoundAlreadyInitialized,
+    #[error("The round is already aggregated")]
     RoundAlreadyAggregated,
+    #[error("The round commit failed or is corrupted")]
     RoundCommitFailedOrCorrupted,
+    #[error("The round contributor is missing")]
     RoundContributorMissing,
+    #[error("The round contributors are missing")]
     RoundContributorsMissing,
+    #[error("The round contributors are not unique")]
     RoundContributorsNotUnique,
+    #[error("The round directory is missing")]
     RoundDirectoryMissing,
+    #[error("The round does not exist")]
     RoundDoesNotExist,
+    #[error("The round file is missing")]
     RoundFileMissing,
+    #[error("The size of the round file is incorrect")]
     RoundFileSizeMismatch,
+    #[error("The round height is zero")]
     RoundHeightIsZero,
+    #[error("The round height doesn't match the expected one")]
     RoundHeightMismatch,
+    #[error("The round height is not set")]
     RoundHeightNotSet,
+    #[error("The round locator already exists")]
     RoundLocatorAlreadyExists,
+    #[error("The round locator is missing")]
     RoundLocatorMissing,
+    #[error("The round is not aggregated")]
     RoundNotAggregated,
+    #[error("The round is not complete")]
     RoundNotComplete,
+    #[error("The round is not ready")]
     RoundNotReady,
+    #[error("The number of contributors of the round is unauthorized")]
     RoundNumberOfContributorsUnauthorized,
+    #[error("The number of verifiers of the round is unauthorized")]
     RoundNumberOfVerifiersUnauthorized,
+    #[error("The round should not exist")]
     RoundShouldNotExist,
+    #[error("The round state is missing")]
     RoundStateMissing,
+    #[error("The round update corrupted the state of the contributors")]
     RoundUpdateCorruptedStateOfContributors,
+    #[error("The round update corrupted the state of the verifiers")]
     RoundUpdateCorruptedStateOfVerifiers,
+    #[error("The round verifiers are missing")]
     RoundVerifiersMissing,
+    #[error("The round verifiers are not unique")]
     RoundVerifiersNotUnique,
+    #[error("The signature scheme is insecure")]
     SignatureSchemeIsInsecure,
+    #[error("The contribution is stale")]
+    StaleContribution,
+    #[error("The storage copy failed")]
     StorageCopyFailed,
+    #[error("The storage encryption failed")]
+    StorageEncryptionFailed,
+    #[error("The storage encryption key is invalid")]
+    StorageEncryptionKeyInvalid,
+    #[error("The storage failed")]
     StorageFailed,
+    #[error("The storage initialization failed")]
     StorageInitializationFailed,
+    #[error("The storage locator already exists")]
     StorageLocatorAlreadyExists,
+    #[error("The storage locator already exists and is open")]
     StorageLocatorAlreadyExistsAndOpen,
+    #[error("The format of the storage locator is incorrect")]
     StorageLocatorFormatIncorrect,
+    #[error("The storage locator is missing")]
     StorageLocatorMissing,
+    #[error("The storage locator is not open")]
     StorageLocatorNotOpen,
+    #[error("The storage lock failed")]
     StorageLockFailed,
+    #[error("The storage reader failed")]
     StorageReaderFailed,
+    #[error("The storage size lookup failed")]
     StorageSizeLookupFailed,
+    #[error("The storage is running out of space: {available} bytes available, {required} bytes required")]
+    StorageSpaceInsufficient { available: u64, required: u64 },
+    #[error("The storage update failed")]
     StorageUpdateFailed,
+    #[error("The task initialization failed: {0}")]
     TaskInitializationFailed(TaskInitializationError),
+    #[error("The contribution previous to task {current_task} is missing")]
     PreviousContributionMissing { current_task: Task },
+    #[error("The timestamp request failed: {0}")]
+    TimestampRequestFailed(String),
+    #[error("The timestamp token doesn't match the contribution")]
+    TimestampTokenMismatch,
+    #[error("The timestamp token is invalid: {0}")]
+    TimestampTokenInvalid(String),
+    #[error("The token file {path} is malformed: {source}")]
+    TokenFileMalformed { path: String, source: std::io::Error },
+    #[error("The token is valid for the cohort {cohort}, which opens at {opens_at}")]
+    TokenForFutureCohort { cohort: usize, opens_at: OffsetDateTime },
+    #[error("The token is not in use")]
+    TokenNotInUse,
+    #[error("The token schedule contains the token {0} more than once")]
+    TokenScheduleDuplicateToken(String),
+    #[error("The token schedule is inconsistent with the ceremony: {0}")]
+    TokenScheduleInconsistent(String),
+    #[error("The token schedule must have at least one cohort and non-zero cohort durations")]
+    TokenScheduleInvalid,
+    #[error("The token is still in use by the active participant {0}")]
+    TokenStillInUse(Participant),
+    #[error("Failed to convert a slice: {0}")]
     TryFromSliceError(std::array::TryFromSliceError),
+    #[error("The chunk contributor is unauthorized")]
     UnauthorizedChunkContributor,
+    #[error("The chunk verifier is unauthorized")]
     UnauthorizedChunkVerifier,
+    #[error("The verification failed")]
     VerificationFailed,
+    #[error("The verification of contribution id zero is not allowed")]
     VerificationOnContributionIdZero,
+    #[error("The verifier is missing")]
     VerifierMissing,
+    #[error("The verifier signature is invalid")]
     VerifierSignatureInvalid,
+    #[error("The verifiers are missing")]
     VerifiersMissing,
 }
 
@@ -230,6 +488,12 @@ impl From<serde_json::Error> for CoordinatorError {
     }
 }
 
+impl From<bincode::Error> for CoordinatorError {
+    fn from(error: bincode::Error) -> Self {
+        CoordinatorError::BincodeError(error)
+    }
+}
+
 impl From<setup_utils::Error> for CoordinatorError {
     fn from(error: setup_utils::Error) -> Self {
         CoordinatorError::Phase2Setup(error)
@@ -254,23 +518,6 @@ impl From<std::num::ParseIntError> for CoordinatorError {
     }
 }
 
-impl fmt::Display for CoordinatorError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        error!("{}", self);
-        write!(f, "{:?}", self)
-    }
-}
-
-impl From<CoordinatorError> for anyhow::Error {
-    fn from(error: CoordinatorError) -> Self {
-        error!("{}", error);
-        match error {
-            CoordinatorError::Error(anyhow_error) => anyhow_error,
-            _ => Self::msg(error.to_string()),
-        }
-    }
-}
-
 /// A trait for providing a source of time to the coordinator, used
 /// for mocking system time during testing.
 pub trait TimeSource: Send + Sync {
@@ -295,6 +542,119 @@ impl TimeSource for SystemTimeSource {
     }
 }
 
+/// A trait for providing a source of randomness to the coordinator,
+/// used for seeding it deterministically during testing.
+pub trait RngSource: Debug + Send + Sync {
+    /// Fill the given buffer with random bytes
+    fn fill_bytes(&self, dest: &mut [u8]);
+}
+
+// Private tuple field to force use of constructor.
+/// A [RngSource] implementation that fetches the random bytes from
+/// the operating system using [OsRng].
+#[derive(Debug)]
+pub(crate) struct SystemRngSource(());
+
+impl SystemRngSource {
+    pub fn new() -> Self {
+        Self(())
+    }
+}
+
+impl RngSource for SystemRngSource {
+    fn fill_bytes(&self, dest: &mut [u8]) {
+        OsRng.fill_bytes(dest)
+    }
+}
+
+/// A trait for classifying the IP address of a joining participant
+/// into a geographic region, used to prevent a single region from
+/// dominating a round. The classifier is supplied by the operator.
+pub trait RegionClassifier: Send + Sync {
+    /// Returns the region of the given IP address, if it can be classified.
+    fn classify(&self, ip: &IpAddr) -> Option<String>;
+}
+
+/// A [RegionClassifier] implementation that doesn't classify any
+/// address, allowing every participant regardless of the region.
+pub struct NoRegionClassifier;
+
+impl RegionClassifier for NoRegionClassifier {
+    fn classify(&self, _ip: &IpAddr) -> Option<String> {
+        None
+    }
+}
+
+/// A trait for selecting the verifier to which a task pending verification
+/// is assigned, allowing to balance the verifications between verifiers.
+pub trait VerifierSelector: Debug + Send + Sync {
+    /// Returns the verifier, among the given ones, to assign the given task
+    /// to, knowing the tasks already pending verification.
+    fn select(
+        &self,
+        task: &Task,
+        verifiers: &[Participant],
+        pending_verification: &HashMap<Task, Participant>,
+    ) -> Option<Participant>;
+}
+
+/// A [VerifierSelector] implementation that assigns every task to the first
+/// verifier, the single verifier deployments rely on.
+#[derive(Debug)]
+pub struct FirstVerifierSelector;
+
+impl VerifierSelector for FirstVerifierSelector {
+    fn select(
+        &self,
+        _task: &Task,
+        verifiers: &[Participant],
+        _pending_verification: &HashMap<Task, Participant>,
+    ) -> Option<Participant> {
+        verifiers.first().cloned()
+    }
+}
+
+/// A [VerifierSelector] implementation that assigns every task to the
+/// verifier with the fewest tasks pending verification, the first one in
+/// case of a tie.
+#[derive(Debug)]
+pub struct LeastLoadedVerifierSelector;
+
+impl VerifierSelector for LeastLoadedVerifierSelector {
+    fn select(
+        &self,
+        _task: &Task,
+        verifiers: &[Participant],
+        pending_verification: &HashMap<Task, Participant>,
+    ) -> Option<Participant> {
+        verifiers
+            .iter()
+            .min_by_key(|verifier| {
+                pending_verification
+                    .values()
+                    .filter(|assigned| assigned == verifier)
+                    .count()
+            })
+            .cloned()
+    }
+}
+
+/// A trait for querying the free space of the volume holding the storage
+/// of the coordinator, used for mocking a full disk during testing.
+pub trait DiskSpace: Send + Sync {
+    /// Returns the number of bytes available on the volume holding the given path
+    fn available_space(&self, path: &Path) -> std::io::Result<u64>;
+}
+
+/// A [DiskSpace] implementation that queries the file system.
+pub struct SystemDiskSpace;
+
+impl DiskSpace for SystemDiskSpace {
+    fn available_space(&self, path: &Path) -> std::io::Result<u64> {
+        fs2::available_space(path)
+    }
+}
+
 /// A time source to use for testing, allows the current time to be
 /// set manually.
 pub struct MockTimeSource {
@@ -327,6 +687,213 @@ impl TimeSource for MockTimeSource {
     }
 }
 
+/// A random source to use for testing, produces the same sequence of
+/// bytes for the same seed.
+#[derive(Debug)]
+pub struct MockRngSource {
+    rng: Mutex<ChaChaRng>,
+}
+
+impl MockRngSource {
+    pub fn new(seed: [u8; 32]) -> Self {
+        Self {
+            rng: Mutex::new(ChaChaRng::from_seed(seed)),
+        }
+    }
+}
+
+impl RngSource for MockRngSource {
+    fn fill_bytes(&self, dest: &mut [u8]) {
+        self.rng
+            .lock()
+            .expect("Unable to obtain lock to read random bytes")
+            .fill_bytes(dest)
+    }
+}
+
+/// Public summary of the progress of the ceremony. It only contains
+/// data which is safe to expose without authentication.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransparencyInfo {
+    /// The height of the current round.
+    pub current_round_height: u64,
+    /// The index of the current cohort.
+    pub current_cohort: usize,
+    /// The number of contributions published in the summary so far.
+    pub total_contributions: usize,
+    /// The number of contributors who finished a round so far.
+    pub finished_contributors: usize,
+    /// The hex encoded hash of the state file of the latest round.
+    pub latest_round_manifest_hash: String,
+}
+
+/// Summary of the ceremony, written to storage once all the cohorts are over.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CeremonyCompletion {
+    /// The number of rounds of the ceremony.
+    pub total_rounds: u64,
+    /// The number of contributors who finished a round.
+    pub total_contributors: usize,
+    /// The number of seconds between the start of the ceremony and its completion.
+    pub total_duration_seconds: i64,
+    /// The hex encoded SHA-256 hash of the manifest of the transcript, see [TRANSCRIPT_MANIFEST_FILE].
+    pub final_manifest_hash: String,
+    /// The time of the completion.
+    pub completed_at: OffsetDateTime,
+}
+
+/// The name of the manifest at the root of the transcript archive, see [Coordinator::export_transcript].
+pub const TRANSCRIPT_MANIFEST_FILE: &str = "manifest.json";
+
+/// A file of the transcript archive, see [Coordinator::export_transcript].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptFile {
+    /// The path of the file in the archive.
+    pub path: String,
+    /// The size of the file in bytes.
+    pub size: u64,
+    /// The hex encoded SHA-256 hash of the file.
+    pub sha256: String,
+}
+
+/// A writer hashing the bytes written through it, to hash the files of the
+/// transcript while they are streamed into the archive.
+struct HashingWriter<W> {
+    inner: W,
+    hasher: Sha256,
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// The manifest of the transcript archive, listing the hashes of its files
+/// so that anyone can re-hash them to check the integrity of the archive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptManifest {
+    /// The heights of the completed rounds included in the archive.
+    pub rounds: Vec<u64>,
+    /// The files of the archive, except the manifest itself.
+    pub files: Vec<TranscriptFile>,
+}
+
+/// Result of the deep health check of the coordinator, which verifies the
+/// integrity of its storage rather than only its liveness.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// Whether all the checks passed.
+    pub healthy: bool,
+    /// Whether the state of the current round exists in storage.
+    pub round_state: bool,
+    /// Whether the coordinator state in storage can be deserialized.
+    pub coordinator_state: bool,
+    /// Whether the token files of the cohorts can be read.
+    pub tokens_readable: bool,
+    /// Whether the number of cohorts of the token files matches the one
+    /// of the coordinator.
+    pub cohorts_consistent: bool,
+}
+
+/// The number of status events buffered for each subscriber, the oldest
+/// ones being dropped for subscribers lagging behind.
+pub const STATUS_EVENTS_CAPACITY: usize = 16;
+
+/// Compact snapshot of the status of the coordinator, published after
+/// every update of the coordinator and round advance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusEvent {
+    /// The height of the current round.
+    pub round_height: u64,
+    /// The number of contributors in the queue.
+    pub queue_length: usize,
+    /// The number of contributors participating in the current round.
+    pub active_contributors: usize,
+}
+
+/// The minimum entropy, in bits per byte, expected of the bytes of a
+/// contribution, see [contribution_anomalies].
+pub const MINIMUM_CONTRIBUTION_ENTROPY: f64 = 4.0;
+
+/// A suspicious property of a contribution, found by the cheap sanity
+/// checks run before the verification of the contribution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ContributionAnomaly {
+    /// The contribution is smaller than the minimum of its round once its
+    /// trailing zero bytes are stripped.
+    TooSmall { size: u64, minimum: u64 },
+    /// The entropy of the bytes of the contribution is below
+    /// [MINIMUM_CONTRIBUTION_ENTROPY].
+    LowEntropy,
+}
+
+/// Returns the Shannon entropy, in bits per byte, of the distribution of the given bytes.
+fn byte_entropy(bytes: &[u8]) -> f64 {
+    let mut counts = [0u64; 256];
+    for byte in bytes {
+        counts[*byte as usize] += 1;
+    }
+
+    let total = bytes.len() as f64;
+    counts
+        .iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let probability = *count as f64 / total;
+            -probability * probability.log2()
+        })
+        .sum()
+}
+
+///
+/// Runs cheap sanity checks on the bytes of a contribution, to flag the files
+/// of the expected size which are trivially derived, and returns the anomalies
+/// found. A `minimum_size` of 0 disables the size check.
+///
+/// This scans the whole contribution, so it should run before the lock of the
+/// coordinator is taken, see [Coordinator::validate_contribution_sanity].
+///
+pub fn contribution_anomalies(bytes: &[u8], minimum_size: u64) -> BTreeSet<ContributionAnomaly> {
+    let mut anomalies = BTreeSet::new();
+
+    let size = bytes
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |position| position + 1) as u64;
+    if size < minimum_size {
+        anomalies.insert(ContributionAnomaly::TooSmall {
+            size,
+            minimum: minimum_size,
+        });
+    }
+
+    if byte_entropy(bytes) < MINIMUM_CONTRIBUTION_ENTROPY {
+        anomalies.insert(ContributionAnomaly::LowEntropy);
+    }
+
+    anomalies
+}
+
+/// Build and runtime information about the running coordinator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VersionInfo {
+    /// The version of the coordinator crate.
+    pub version: String,
+    /// The git commit the coordinator was built from, if provided at build time.
+    pub git_hash: Option<String>,
+    /// The number of seconds since the coordinator started.
+    pub uptime_seconds: i64,
+    /// The deployment mode of the coordinator.
+    pub deployment: Deployment,
+}
+
 /// A core structure for operating the Phase 1 ceremony. This struct
 /// is designed to be [Send] + [Sync]. The state of the ceremony is
 /// stored in a [CoordinatorState] object.
@@ -336,13 +903,43 @@ pub struct Coordinator {
     /// The signature scheme for contributors & verifiers with this coordinator.
     signature: Arc<dyn Signature>,
     /// The storage of contributions and rounds for this coordinator.
-    storage: Disk,
+    storage: Box<dyn Storage>,
     /// The current round and participant self.
     state: CoordinatorState,
     /// The source of time, allows mocking system time for testing.
     time: Arc<dyn TimeSource>,
+    /// The source of randomness, allows seeding it for testing.
+    rng: Arc<dyn RngSource>,
     /// Callback to call after aggregation is done
     aggregation_callback: Arc<dyn Fn(Vec<Participant>) -> () + Send + Sync>,
+    /// The cache of the hashes of the files in storage, invalidated on updates of the files.
+    hash_cache: RwLock<HashMap<Locator, Vec<u8>>>,
+    /// The classifier of the region of the participants joining the queue.
+    region_classifier: Arc<dyn RegionClassifier>,
+    /// The query of the free space of the storage volume.
+    disk_space: Arc<dyn DiskSpace>,
+    /// Callback to call when the coordinator enters read-only mode or runs out of disk space
+    storage_alert_callback: Arc<dyn Fn(String) -> () + Send + Sync>,
+    /// The number of consecutive failures to save the coordinator state.
+    storage_failures: u32,
+    /// Whether the coordinator rejects state changes because its storage cannot be written.
+    read_only: bool,
+    /// The time the coordinator started at, the runtime state is reset on every start.
+    started_at: OffsetDateTime,
+    /// The log of the state transitions, if enabled.
+    audit_log: Option<AuditLog>,
+    /// The channel the status events are published to.
+    status_events: broadcast::Sender<StatusEvent>,
+    /// The current interval between two periodic updates, if they are run periodically.
+    update_interval: Option<std::time::Duration>,
+    /// The time stamping authority timestamping the contributions, if enabled.
+    timestamp_authority: Option<TimestampAuthority>,
+    /// The contributions accepted since their timestamps were last requested. They are only kept in memory: the
+    /// contributions still pending when the coordinator restarts are left without a timestamp.
+    pending_timestamps: Vec<PendingTimestamp>,
+    /// Whether the coordinator is shutting down, rejecting new participants and locks while the
+    /// chunks already locked are contributed.
+    draining: AtomicBool,
 }
 
 impl Coordinator {
@@ -359,27 +956,87 @@ impl Coordinator {
         Self::new_with_time(environment, signature, Arc::new(SystemTimeSource::new()))
     }
 
+    ///
+    /// Creates a new instance of the `Coordinator` on the given storage, which must
+    /// have been loaded for the given environment.
+    ///
+    pub fn new_with_storage(
+        environment: Environment,
+        storage: Box<dyn Storage>,
+        signature: Arc<dyn Signature>,
+    ) -> Result<Self, CoordinatorError> {
+        Self::from_storage(
+            environment,
+            storage,
+            signature,
+            Arc::new(SystemTimeSource::new()),
+            Arc::new(SystemRngSource::new()),
+        )
+    }
+
     /// Constructor that allows mocking time for testing.
     pub fn new_with_time(
         environment: Environment,
         signature: Arc<dyn Signature>,
         time: Arc<dyn TimeSource>,
+    ) -> Result<Self, CoordinatorError> {
+        Self::new_with_time_and_rng(environment, signature, time, Arc::new(SystemRngSource::new()))
+    }
+
+    /// Constructor that allows mocking time and randomness for testing.
+    pub fn new_with_time_and_rng(
+        environment: Environment,
+        signature: Arc<dyn Signature>,
+        time: Arc<dyn TimeSource>,
+        rng: Arc<dyn RngSource>,
     ) -> Result<Self, CoordinatorError> {
         // Load an instance of storage.
-        let storage = environment.storage()?;
+        let storage = Box::new(environment.storage()?);
+        Self::from_storage(environment, storage, signature, time, rng)
+    }
+
+    /// Creates a new instance of the `Coordinator` on the given storage and sources of time and randomness.
+    fn from_storage(
+        environment: Environment,
+        storage: Box<dyn Storage>,
+        signature: Arc<dyn Signature>,
+        time: Arc<dyn TimeSource>,
+        rng: Arc<dyn RngSource>,
+    ) -> Result<Self, CoordinatorError> {
         // Load an instance of coordinator self.
-        let state = match storage.get(&Locator::CoordinatorState)? {
+        let mut state = match storage.get(&Locator::CoordinatorState)? {
             Object::CoordinatorState(state) => state,
             _ => return Err(CoordinatorError::StorageFailed),
         };
+        // The tokens are not persisted, reload them from files.
+        state.update_tokens(CoordinatorState::load_tokens(&environment)?);
+        // Restore the tokens and the ip addresses in use, if persisted.
+        state.restore_runtime_snapshot()?;
+        state.update_ip_ban_exemptions(CoordinatorState::load_ip_ban_exemptions()?);
+        state.set_random_source(rng.clone());
+        let (status_events, _) = broadcast::channel(STATUS_EVENTS_CAPACITY);
 
         Ok(Self {
             environment: environment.clone(),
             signature,
             storage,
             state,
+            started_at: time.now_utc(),
             time,
+            rng,
             aggregation_callback: Arc::new(|_| ()),
+            hash_cache: RwLock::new(HashMap::new()),
+            region_classifier: Arc::new(NoRegionClassifier),
+            disk_space: Arc::new(SystemDiskSpace),
+            storage_alert_callback: Arc::new(|_| ()),
+            storage_failures: 0,
+            read_only: false,
+            audit_log: None,
+            status_events,
+            update_interval: None,
+            timestamp_authority: None,
+            pending_timestamps: Vec::new(),
+            draining: AtomicBool::new(false),
         })
     }
 
@@ -391,6 +1048,101 @@ impl Coordinator {
     pub fn set_aggregation_callback(&mut self, callback: Arc<dyn Fn(Vec<Participant>) -> () + Send + Sync>) {
         self.aggregation_callback = callback;
     }
+
+    ///
+    /// Set the classifier of the region of the participants joining the queue.
+    /// The number of contributors of the same region in a round is capped by
+    /// [Environment::maximum_contributors_per_region].
+    ///
+    pub fn set_region_classifier(&mut self, classifier: Arc<dyn RegionClassifier>) {
+        self.region_classifier = classifier;
+    }
+
+    ///
+    /// Enable the signed log of the state transitions, appended to the
+    /// file at the given path. See [crate::audit].
+    ///
+    pub fn enable_audit_log<P: Into<PathBuf>>(&mut self, path: P) -> Result<(), CoordinatorError> {
+        let public_key = self
+            .environment
+            .coordinator_verifiers()
+            .first()
+            .map(|verifier| verifier.address())
+            .unwrap_or_default();
+        self.audit_log = Some(AuditLog::open(
+            path,
+            self.signature.clone(),
+            self.environment.default_verifier_signing_key(),
+            public_key,
+            self.time.as_ref(),
+        )?);
+
+        Ok(())
+    }
+
+    ///
+    /// Enable the RFC 3161 timestamps of the contributions by the time
+    /// stamping authority at the given url. See [crate::timestamp].
+    ///
+    pub fn enable_timestamping<S: Into<String>>(&mut self, url: S) {
+        self.timestamp_authority = Some(TimestampAuthority::new(url));
+    }
+
+    ///
+    /// Subscribe to the status events published after every update of the
+    /// coordinator and round advance. A subscriber lagging behind by more than
+    /// [STATUS_EVENTS_CAPACITY] events misses the oldest ones.
+    ///
+    pub fn subscribe_status(&self) -> broadcast::Receiver<StatusEvent> {
+        self.status_events.subscribe()
+    }
+
+    /// Publish the current status to the subscribers, if any.
+    fn publish_status(&self) {
+        let event = StatusEvent {
+            round_height: self.state.current_round_height(),
+            queue_length: self.state.number_of_queue_contributors(),
+            active_contributors: self.state.number_of_current_contributors(),
+        };
+
+        // Sending only fails when there are no subscribers.
+        let _ = self.status_events.send(event);
+    }
+
+    ///
+    /// Set the selector of the verifier to which the tasks pending
+    /// verification are assigned, [FirstVerifierSelector] by default.
+    ///
+    pub fn set_verifier_selector(&mut self, selector: Arc<dyn VerifierSelector>) {
+        self.state.set_verifier_selector(selector);
+    }
+
+    ///
+    /// Set the query of the free space of the storage volume, checked
+    /// against [Environment::minimum_free_disk_space] before accepting
+    /// contributions.
+    ///
+    pub fn set_disk_space(&mut self, disk_space: Arc<dyn DiskSpace>) {
+        self.disk_space = disk_space;
+    }
+
+    ///
+    /// Set the current interval between two periodic updates, reported in
+    /// the status report of the updates.
+    ///
+    pub fn set_update_interval(&mut self, update_interval: std::time::Duration) {
+        self.update_interval = Some(update_interval);
+    }
+
+    ///
+    /// Set a callback which will be called when the coordinator enters
+    /// read-only mode or rejects a contribution for lack of disk space. A
+    /// description of the storage failure will be passed to the callback
+    /// as an argument
+    ///
+    pub fn set_storage_alert_callback(&mut self, callback: Arc<dyn Fn(String) -> () + Send + Sync>) {
+        self.storage_alert_callback = callback;
+    }
 }
 
 impl Coordinator {
@@ -407,6 +1159,12 @@ impl Coordinator {
         info!("Coordinator is booting up");
         info!("{:#?}", self.environment.parameters());
 
+        // Refuse to restart a completed ceremony.
+        if self.storage.exists(&Locator::CeremonyCompletion) {
+            error!("The ceremony is already completed, refusing to initialize");
+            return Err(CoordinatorError::CeremonyIsOver);
+        }
+
         // Ensure the ceremony is initialized, if it has not started yet.
         {
             // Check if the ceremony has been initialized yet.
@@ -426,6 +1184,15 @@ impl Coordinator {
         let current_round_height = self.current_round_height()?;
 
         info!("Current round height is {}", current_round_height);
+
+        // Remove the duplicate tasks a state file persisted by an older version may contain.
+        if self.state.dedup_tasks() > 0 {
+            self.save_state()?;
+        }
+
+        // Release the locks left behind by an unclean shutdown, instead of waiting for them to time out.
+        self.release_orphaned_locks()?;
+
         info!("{}", serde_json::to_string_pretty(&self.current_round()?)?);
         info!("Coordinator has booted up");
 
@@ -433,8 +1200,151 @@ impl Coordinator {
     }
 
     /// Save the current state of the coordinator to storage.
+    ///
+    /// After [Environment::storage_failure_threshold] consecutive failures the
+    /// coordinator enters read-only mode: state changes are rejected with
+    /// [CoordinatorError::StorageUpdateFailed] until the coordinator is restarted,
+    /// while reads keep being served.
     pub fn save_state(&mut self) -> Result<(), CoordinatorError> {
-        self.state.save(&mut self.storage)
+        self.check_writable()?;
+
+        match self.state.save(&mut self.storage) {
+            Ok(()) => {
+                self.storage_failures = 0;
+                Ok(())
+            }
+            Err(e) => {
+                self.storage_failures += 1;
+                error!(
+                    "Failed to save the coordinator state ({} consecutive failures): {}",
+                    self.storage_failures, e
+                );
+
+                let threshold = self.environment.storage_failure_threshold();
+                if threshold > 0 && self.storage_failures >= threshold {
+                    self.read_only = true;
+
+                    let alert = format!(
+                        "Coordinator entered read-only mode after {} consecutive failures to save its state: {}",
+                        self.storage_failures, e
+                    );
+                    error!("{}", alert);
+                    (self.storage_alert_callback)(alert);
+
+                    return Err(CoordinatorError::StorageUpdateFailed);
+                }
+
+                Err(e)
+            }
+        }
+    }
+
+    /// Record the given state transition in the audit log, if enabled.
+    ///
+    /// The transition already happened, a failure to record it is reported
+    /// to the storage alert callback instead of failing the request.
+    fn audit(&mut self, event: AuditEvent) {
+        if let Some(audit_log) = self.audit_log.as_mut() {
+            if let Err(e) = audit_log.append(event, self.time.as_ref()) {
+                let alert = format!("Failed to append to the audit log: {}", e);
+                error!("{}", alert);
+                (self.storage_alert_callback)(alert);
+            }
+        }
+    }
+
+    ///
+    /// Returns `true` if the coordinator is in read-only mode, i.e. it
+    /// rejects state changes because its storage cannot be written.
+    ///
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    ///
+    /// Returns [CoordinatorError::StorageUpdateFailed] if the coordinator is in read-only mode.
+    ///
+    fn check_writable(&self) -> Result<(), CoordinatorError> {
+        if self.read_only {
+            return Err(CoordinatorError::StorageUpdateFailed);
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Checks that the storage volume has room for a contribution of the given
+    /// size while keeping [Environment::minimum_free_disk_space] bytes free, so
+    /// that a full disk doesn't corrupt the round mid-write.
+    ///
+    /// Returns [CoordinatorError::StorageSpaceInsufficient] and alerts the
+    /// operators otherwise.
+    ///
+    pub fn check_free_disk_space(&self, contribution_size: u64) -> Result<(), CoordinatorError> {
+        let minimum_free_disk_space = self.environment.minimum_free_disk_space();
+        if minimum_free_disk_space == 0 {
+            return Ok(());
+        }
+
+        let available = self
+            .disk_space
+            .available_space(Path::new(self.environment.local_base_directory()))?;
+        let required = minimum_free_disk_space.saturating_add(contribution_size);
+        if available < required {
+            let alert = format!(
+                "Rejecting a contribution of {} bytes, the storage has {} bytes available out of the {} bytes to keep free",
+                contribution_size, available, minimum_free_disk_space
+            );
+            error!("{}", alert);
+            (self.storage_alert_callback)(alert);
+
+            return Err(CoordinatorError::StorageSpaceInsufficient { available, required });
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Logs the anomalies found by [contribution_anomalies] in the given
+    /// contribution to the task, and returns them. The verification of the
+    /// contribution remains authoritative.
+    ///
+    /// Returns [CoordinatorError::ContributionSanityCheckFailed] instead if
+    /// an anomaly is found and [Environment::strict_contribution_sanity] is
+    /// set.
+    ///
+    pub fn validate_contribution_sanity(
+        &self,
+        task: &Task,
+        anomalies: BTreeSet<ContributionAnomaly>,
+    ) -> Result<BTreeSet<ContributionAnomaly>, CoordinatorError> {
+        let round_height = self.current_round_height()?;
+
+        for anomaly in anomalies.iter() {
+            warn!(
+                "Contribution {} to chunk {} of round {} is anomalous - {:?}",
+                task.contribution_id(),
+                task.chunk_id(),
+                round_height,
+                anomaly
+            );
+        }
+
+        match !anomalies.is_empty() && self.environment.strict_contribution_sanity() {
+            true => Err(CoordinatorError::ContributionSanityCheckFailed),
+            false => Ok(anomalies),
+        }
+    }
+
+    ///
+    /// Returns [CoordinatorError::CeremonyPaused] if the chunks can not be
+    /// locked at the current time.
+    ///
+    fn check_contribution_window(&self) -> Result<(), CoordinatorError> {
+        match self.environment.contribution_paused_until(self.time.now_utc()) {
+            Some(next_open) => Err(CoordinatorError::CeremonyPaused { next_open }),
+            None => Ok(()),
+        }
     }
 
     ///
@@ -442,10 +1352,13 @@ impl Coordinator {
     /// newly finished, dropped, or banned participants.
     ///
     pub fn update(&mut self) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
         // Process ceremony updates for the current round and queue.
         let (is_current_round_finished, is_current_round_aggregated) = {
             // Acquire the state write lock.
-            info!("\n{}", self.state.status_report(self.time.as_ref()));
+            info!("\n{}", self.state.status_report(self.time.as_ref(), self.update_interval));
 
             // Update the metrics for the current round and participants.
             self.state.update_round_metrics();
@@ -455,12 +1368,25 @@ impl Coordinator {
             self.state.update_current_contributors(self.time.as_ref())?;
             self.save_state()?;
 
+            // Release the contributions held during the settle window of the round.
+            self.state.update_settled_tasks(self.time.as_ref())?;
+            self.save_state()?;
+
             // Drop disconnected participants from the current round.
-            for drop in self.state.update_dropped_participants(self.time.as_ref())? {
+            let drops = self.state.update_dropped_participants(self.time.as_ref())?;
+            for drop in &drops {
                 // Update the round to reflect the coordinator state changes.
-                self.drop_participant_from_storage(&drop)?;
+                self.drop_participant_from_storage(drop)?;
             }
             self.save_state()?;
+            for drop in drops {
+                self.audit(AuditEvent::Drop {
+                    participant: drop.participant().clone(),
+                });
+            }
+
+            // Release the locks held in the round by the disconnected contributors.
+            self.release_disconnected_locks()?;
 
             self.state.update_dropped_queued_participants(self.time.as_ref())?;
             self.save_state()?;
@@ -515,6 +1441,7 @@ impl Coordinator {
             // Check if the manual lock is enabled.
             if self.state.is_manual_lock_enabled() {
                 info!("Manual lock is enabled");
+                self.publish_status();
                 return Ok(());
             }
         }
@@ -537,24 +1464,63 @@ impl Coordinator {
         // If cohorts are over, shut the coordinator down
         if self.state.get_current_cohort_index() >= self.state.get_number_of_cohorts() {
             info!("Completed all the scheduled cohorts");
+            self.write_completion()?;
             // Return an error to force the calling task to request a graceful shutdown of the server
             return Err(CoordinatorError::CeremonyIsOver);
         }
 
+        self.publish_status();
+
         Ok(())
     }
 
     ///
-    /// Initializes a listener to handle the shutdown signal.
+    /// Starts the drain before the shutdown: new participants and locks are
+    /// rejected, while the chunks already locked can still be contributed.
     ///
-    pub fn shutdown(&mut self) -> Result<(), CoordinatorError> {
-        warn!("\n\nATTENTION - Coordinator is shutting down...\n");
+    pub fn start_draining(&self) {
+        if !self.draining.swap(true, Ordering::SeqCst) {
+            info!("Coordinator is draining, no new participants or locks are accepted");
+        }
+    }
 
-        // Save the coordinator state to storage.
-        self.save_state()?;
-        debug!("Coordinator has safely shutdown storage");
+    ///
+    /// Returns `true` if the coordinator is draining before the shutdown.
+    ///
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
 
-        // Print the final coordinator self.
+    ///
+    /// Returns the number of chunks locked by the current contributors.
+    ///
+    pub fn number_of_contributor_locks(&self) -> usize {
+        self.state
+            .current_contributors()
+            .iter()
+            .map(|(_, info)| info.locked_chunks().len())
+            .sum()
+    }
+
+    ///
+    /// Initializes a listener to handle the shutdown signal.
+    ///
+    /// The drain, if any, is over: the chunks still locked are abandoned.
+    ///
+    pub fn shutdown(&mut self) -> Result<(), CoordinatorError> {
+        warn!("\n\nATTENTION - Coordinator is shutting down...\n");
+        self.start_draining();
+
+        let locks = self.number_of_contributor_locks();
+        if locks > 0 {
+            warn!("Shutting down with {} chunks still locked by contributors", locks);
+        }
+
+        // Save the coordinator state to storage.
+        self.save_state()?;
+        debug!("Coordinator has safely shutdown storage");
+
+        // Print the final coordinator self.
         let final_state = serde_json::to_string_pretty(&self.state).map_err(|e| CoordinatorError::JsonError(e))?;
         info!("\n\nCoordinator State at Shutdown\n\n{}\n", final_state);
 
@@ -570,6 +1536,156 @@ impl Coordinator {
         self.state.update_tokens(tokens)
     }
 
+    ///
+    /// Merges the tokens of the cohort `from` into the ones of the cohort `into`,
+    /// both starting from 0, see [CoordinatorState::merge_cohorts]. The token
+    /// files of the cohorts are rewritten accordingly.
+    ///
+    pub fn merge_cohorts(&mut self, from: usize, into: usize) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
+        // Merge the cohorts.
+        self.state.merge_cohorts(from, into)?;
+        info!("Merged the cohort {} into the cohort {}", from + 1, into + 1);
+
+        // Persist the new tokens and save the coordinator state in storage.
+        CoordinatorState::save_tokens(&self.environment.tokens_path(), self.state.get_tokens())?;
+        self.save_state()?;
+
+        Ok(())
+    }
+
+    ///
+    /// Atomically replaces the tokens and the durations of the cohorts of the
+    /// ceremony, keeping the tokens in use and the blacklisted tokens which
+    /// are still part of the schedule.
+    ///
+    pub fn replace_token_schedule(
+        &mut self,
+        tokens: Vec<HashSet<String>>,
+        cohort_durations: Vec<u64>,
+    ) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
+        // Replace the token schedule.
+        self.state.replace_token_schedule(tokens, cohort_durations)?;
+        info!(
+            "Replaced the token schedule with {} cohorts",
+            self.state.get_tokens().len()
+        );
+
+        // Save the coordinator state in storage.
+        self.save_state()?;
+
+        Ok(())
+    }
+
+    ///
+    /// Reloads the set of ip addresses exempted from the ip ban from the file set in the
+    /// NAMADA_MPC_IP_BAN_EXEMPTIONS env variable, returning the new set.
+    ///
+    pub fn reload_ip_ban_exemptions(&mut self) -> Result<HashSet<IpAddr>, CoordinatorError> {
+        let exemptions = CoordinatorState::load_ip_ban_exemptions()?;
+        info!("Loaded {} ip ban exemptions", exemptions.len());
+        self.state.update_ip_ban_exemptions(exemptions.clone());
+
+        Ok(exemptions)
+    }
+
+    ///
+    /// Releases the given token held by a participant who is no longer active
+    /// in the ceremony, returning the participant.
+    ///
+    pub fn release_token(&mut self, token: &str) -> Result<Participant, CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
+        let participant = self.state.release_token(token)?;
+        info!("Released the token of {}", participant);
+
+        // Save the coordinator state in storage.
+        self.save_state()?;
+
+        Ok(participant)
+    }
+
+    ///
+    /// Starts or resumes a resumable upload of the contribution of the given
+    /// current contributor.
+    ///
+    pub fn initiate_chunk_upload(
+        &mut self,
+        participant: &Participant,
+        round_height: u64,
+        total_size: u64,
+        checksum: String,
+    ) -> Result<ChunkUpload, CoordinatorError> {
+        let upload = self
+            .state
+            .initiate_chunk_upload(participant, round_height, total_size, checksum, &*self.rng)?;
+        debug!(
+            "Upload {} of {} has {} parts",
+            upload.upload_id,
+            participant,
+            upload.parts.len()
+        );
+
+        Ok(upload)
+    }
+
+    ///
+    /// Returns the given resumable upload of the contributor.
+    ///
+    #[inline]
+    pub fn chunk_upload(&self, participant: &Participant, upload_id: &str) -> Result<&ChunkUpload, CoordinatorError> {
+        self.state.chunk_upload(participant, upload_id)
+    }
+
+    ///
+    /// Checks that a part can be added to the given resumable upload of the
+    /// contributor.
+    ///
+    #[inline]
+    pub fn check_chunk_upload_part(
+        &self,
+        participant: &Participant,
+        upload_id: &str,
+        offset: u64,
+        length: u64,
+    ) -> Result<&ChunkUpload, CoordinatorError> {
+        self.state
+            .check_chunk_upload_part(participant, upload_id, offset, length)
+    }
+
+    ///
+    /// Records a part, already staged, of the given resumable upload of the
+    /// contributor.
+    ///
+    #[inline]
+    pub fn add_chunk_upload_part(
+        &mut self,
+        participant: &Participant,
+        upload_id: &str,
+        offset: u64,
+        length: u64,
+    ) -> Result<(), CoordinatorError> {
+        self.state.add_chunk_upload_part(participant, upload_id, offset, length)
+    }
+
+    ///
+    /// Removes the given resumable upload of the contributor and returns it.
+    ///
+    #[inline]
+    pub fn remove_chunk_upload(
+        &mut self,
+        participant: &Participant,
+        upload_id: &str,
+    ) -> Result<ChunkUpload, CoordinatorError> {
+        self.state.remove_chunk_upload(participant, upload_id)
+    }
+
     ///
     /// Returns `true` if the given participant is a contributor in the queue.
     ///
@@ -618,6 +1734,46 @@ impl Coordinator {
         self.state.current_round_metrics()
     }
 
+    ///
+    /// Checks the signature of the token by the participant joining the queue,
+    /// if required in the environment.
+    ///
+    pub fn check_join_signature(
+        &self,
+        participant: &Participant,
+        token: &str,
+        signature: Option<&str>,
+    ) -> Result<(), CoordinatorError> {
+        if !self.environment.queue_join_signature_required() {
+            return Ok(());
+        }
+
+        // Check the encoding first, as the signature scheme may not handle malformed inputs.
+        let signature = signature.ok_or(CoordinatorError::ContributorSignatureInvalid)?;
+        match hex::decode(signature) {
+            Ok(bytes) if bytes.len() == 64 => (),
+            _ => return Err(CoordinatorError::ContributorSignatureInvalid),
+        }
+
+        match self.signature.verify(&participant.address(), token, signature) {
+            true => Ok(()),
+            false => Err(CoordinatorError::ContributorSignatureInvalid),
+        }
+    }
+
+    ///
+    /// Performs the safety checks before adding the given participant to the queue.
+    ///
+    #[inline]
+    pub fn add_to_queue_checks(
+        &self,
+        participant: &Participant,
+        participant_ip: Option<&IpAddr>,
+    ) -> Result<(), CoordinatorError> {
+        self.state
+            .add_to_queue_checks(participant, participant_ip, self.time.as_ref())
+    }
+
     ///
     /// Adds the given participant to the queue if they are permitted to participate.
     ///
@@ -629,18 +1785,32 @@ impl Coordinator {
         token: String,
         reliability_score: u8,
     ) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
+        // Classify the region of the participant.
+        let region = participant_ip
+            .as_ref()
+            .and_then(|ip| self.region_classifier.classify(ip));
+
         // Attempt to add the participant to the next round.
         self.state.add_to_queue(
-            participant,
+            participant.clone(),
             participant_ip,
             token,
             reliability_score,
             self.time.as_ref(),
         )?;
 
+        if let Some(region) = region {
+            self.state.set_participant_region(participant.clone(), region);
+        }
+
         // Save the coordinator state in storage.
         self.save_state()?;
 
+        self.audit(AuditEvent::Join { participant });
+
         Ok(())
     }
 
@@ -649,6 +1819,9 @@ impl Coordinator {
     ///
     #[inline]
     pub fn remove_from_queue(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
         // Attempt to remove the participant from the next round.
         self.state.remove_from_queue(participant)?;
 
@@ -658,6 +1831,44 @@ impl Coordinator {
         Ok(())
     }
 
+    ///
+    /// Removes the given contributor from the queue on their own request.
+    /// The contributor cannot join the queue again before the cooldown
+    /// set in the environment.
+    ///
+    #[inline]
+    pub fn leave_queue(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
+        // Attempt to remove the contributor from the queue.
+        self.state.leave_queue(participant, self.time.as_ref())?;
+
+        // Save the coordinator state in storage.
+        self.save_state()?;
+
+        Ok(())
+    }
+
+    ///
+    /// Restores the given contributor, disconnected for not being seen,
+    /// to the current round if it reconnects within the reconnect window
+    /// set in the environment.
+    ///
+    #[inline]
+    pub fn reconnect(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
+        // Attempt to restore the contributor to the current round.
+        self.state.reconnect(participant, self.time.as_ref())?;
+
+        // Save the coordinator state in storage.
+        self.save_state()?;
+
+        Ok(())
+    }
+
     ///
     /// Drops the given participant from the ceremony.
     ///
@@ -666,6 +1877,9 @@ impl Coordinator {
         fields(participant = %participant)
     )]
     pub fn drop_participant(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
         // Drop the participant from the ceremony.
         let drop = self.state.drop_participant(participant, self.time.as_ref())?;
 
@@ -675,6 +1889,10 @@ impl Coordinator {
         // Save the coordinator state in storage.
         self.save_state()?;
 
+        self.audit(AuditEvent::Drop {
+            participant: drop.participant().clone(),
+        });
+
         Ok(())
     }
 
@@ -683,6 +1901,9 @@ impl Coordinator {
     ///
     #[inline]
     pub fn ban_participant(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
         // Ban the participant from the ceremony.
         let drop = self.state.ban_participant(participant, self.time.as_ref())?;
 
@@ -692,6 +1913,10 @@ impl Coordinator {
         // Save the coordinator state in storage.
         self.save_state()?;
 
+        self.audit(AuditEvent::Ban {
+            participant: participant.clone(),
+        });
+
         Ok(())
     }
 
@@ -700,6 +1925,9 @@ impl Coordinator {
     ///
     #[inline]
     pub fn unban_participant(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
         // Unban the participant from the ceremony.
         self.state.unban_participant(participant);
 
@@ -709,6 +1937,24 @@ impl Coordinator {
         Ok(())
     }
 
+    ///
+    /// Reinstates the given contributor who was dropped from the ceremony by mistake,
+    /// adding them back to the queue.
+    ///
+    pub fn reinstate_dropped_participant(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
+        // Add the participant back to the queue.
+        self.state
+            .reinstate_dropped_participant(participant, self.time.as_ref())?;
+
+        // Save the coordinator state in storage.
+        self.save_state()?;
+
+        Ok(())
+    }
+
     ///
     /// Returns `true` if the manual lock for transitioning to the next round is enabled.
     ///
@@ -723,6 +1969,9 @@ impl Coordinator {
     ///
     #[inline]
     pub fn enable_manual_lock(&mut self) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
         // Sets the manual lock to `true`.
         self.state.enable_manual_lock();
 
@@ -737,6 +1986,9 @@ impl Coordinator {
     ///
     #[inline]
     pub fn disable_manual_lock(&mut self) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
         // Sets the manual lock to `false`.
         self.state.disable_manual_lock();
 
@@ -746,6 +1998,106 @@ impl Coordinator {
         Ok(())
     }
 
+    ///
+    /// Sets the number of chunks the given contributor can lock at once, lower than the
+    /// limit of the environment, or clears it with `None`.
+    ///
+    pub fn set_participant_lock_limit(
+        &mut self,
+        participant: &Participant,
+        limit: Option<usize>,
+    ) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
+        self.state.set_participant_lock_limit(participant, limit)?;
+        info!("Set the chunk lock limit of {} to {:?}", participant, limit);
+
+        // Save the coordinator state in storage.
+        self.save_state()?;
+
+        Ok(())
+    }
+
+    ///
+    /// Returns `true` if the queue is frozen and rejects new participants.
+    ///
+    #[inline]
+    pub fn is_queue_frozen(&self) -> bool {
+        self.state.is_queue_frozen()
+    }
+
+    ///
+    /// Freezes the queue, rejecting new participants while the participants
+    /// already in the queue or in the current round proceed normally.
+    ///
+    pub fn freeze_queue(&mut self) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
+        self.state.freeze_queue();
+        info!("Froze the queue");
+
+        // Save the coordinator state in storage.
+        self.save_state()?;
+
+        Ok(())
+    }
+
+    ///
+    /// Unfreezes the queue, accepting new participants again.
+    ///
+    pub fn unfreeze_queue(&mut self) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
+        self.state.unfreeze_queue();
+        info!("Unfroze the queue");
+
+        // Save the coordinator state in storage.
+        self.save_state()?;
+
+        Ok(())
+    }
+
+    ///
+    /// Returns the number of seconds to wait after aggregation before starting the next round.
+    ///
+    #[inline]
+    pub fn queue_wait_time(&self) -> u64 {
+        self.state.queue_wait_time()
+    }
+
+    ///
+    /// Returns the timeouts applied by the coordinator, including the queue wait time
+    /// set while running, which overrides the environment.
+    ///
+    #[inline]
+    pub fn timeouts(&self) -> Timeouts {
+        Timeouts {
+            queue_wait_time: self.queue_wait_time(),
+            ..self.environment.timeouts()
+        }
+    }
+
+    ///
+    /// Sets the number of seconds to wait after aggregation before starting the next round,
+    /// overriding the environment.
+    ///
+    #[inline]
+    pub fn set_queue_wait_time(&mut self, seconds: u64) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
+        // Sets the queue wait time.
+        self.state.set_queue_wait_time(seconds);
+
+        // Save the coordinator state in storage.
+        self.save_state()?;
+
+        Ok(())
+    }
+
     ///
     /// Returns `true` if the given participant is authorized as a
     /// contributor and listed in the contributor IDs for this round.
@@ -886,7 +2238,7 @@ impl Coordinator {
         match round_height <= current_round_height {
             // Fetch the round corresponding to the given round height from storage.
             true => Ok(serde_json::from_slice(
-                self.storage.reader(&Locator::RoundState { round_height })?.as_ref(),
+                &self.storage.reader(&Locator::RoundState { round_height })?,
             )?),
             // The given round height does not exist.
             false => Err(CoordinatorError::RoundDoesNotExist),
@@ -895,10 +2247,46 @@ impl Coordinator {
 
     /// Lets the coordinator know that the participant is still alive
     /// and participating (or waiting to participate) in the ceremony.
+    ///
+    /// The heartbeats are accepted in read-only mode, as they are not
+    /// saved and keep the participants from being dropped while the
+    /// storage is being fixed.
     pub fn heartbeat(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
         self.state.heartbeat(participant, self.time.as_ref())
     }
 
+    ///
+    /// Records a work request of the given participant as a heartbeat,
+    /// if enabled in the environment.
+    ///
+    pub fn record_activity(&mut self, participant: &Participant) {
+        if !self.environment.heartbeat_on_activity() {
+            return;
+        }
+
+        if let Err(e) = self.state.heartbeat(participant, self.time.as_ref()) {
+            debug!("Activity of {} not recorded: {}", participant, e);
+        }
+    }
+
+    ///
+    /// Reorders the assigned tasks of the given contributor, to let them choose
+    /// which chunk to attempt next. The given tasks must be a permutation of the
+    /// tasks currently assigned to the contributor.
+    ///
+    pub fn reorder_assigned_tasks(
+        &mut self,
+        participant: &Participant,
+        tasks: Vec<Task>,
+    ) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
+        self.state
+            .reorder_assigned_tasks(participant, tasks, self.time.as_ref())?;
+        self.save_state()
+    }
+
     ///
     /// Attempts to acquire the lock to a chunk for the given participant.
     ///
@@ -911,32 +2299,79 @@ impl Coordinator {
         err
     )]
     pub fn try_lock(&mut self, participant: &Participant) -> Result<(u64, LockedLocators), CoordinatorError> {
-        if participant.is_verifier() {
-            return Err(CoordinatorError::ExpectedContributor);
-        }
-
-        // Check that the participant is in the current round, and has not been dropped or finished.
-        if !self.state.is_current_contributor(participant) {
-            return Err(CoordinatorError::ParticipantUnauthorized);
-        }
-
-        // Check that the current round is not yet finished.
-        if self.state.is_current_round_finished() {
-            return Err(CoordinatorError::CurrentRoundFinished);
-        }
+        self.try_lock_task(participant, None)
+    }
 
-        // Check that the current round is not yet aggregating.
-        if self.state.is_current_round_aggregating() {
-            return Err(CoordinatorError::CurrentRoundAggregating);
-        }
+    ///
+    /// Attempts to acquire the lock to the given chunk for the given participant,
+    /// out of the order of the assigned tasks of the participant. The previous
+    /// contribution to the chunk must exist.
+    ///
+    /// Requires [Environment::out_of_order_contributions].
+    ///
+    #[tracing::instrument(
+        level = "error",
+        skip(self),
+        fields(participant = %participant),
+        err
+    )]
+    pub fn try_lock_assigned(
+        &mut self,
+        participant: &Participant,
+        chunk_id: u64,
+    ) -> Result<(u64, LockedLocators), CoordinatorError> {
+        self.try_lock_task(participant, Some(chunk_id))
+    }
+
+    ///
+    /// Attempts to acquire the lock to the given chunk for the given participant,
+    /// or to the chunk of the next assigned task if unspecified.
+    ///
+    fn try_lock_task(
+        &mut self,
+        participant: &Participant,
+        chunk_id: Option<u64>,
+    ) -> Result<(u64, LockedLocators), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
+        if participant.is_verifier() {
+            return Err(CoordinatorError::ExpectedContributor);
+        }
+
+        // Check that the participant is in the current round, and has not been dropped or finished.
+        if !self.state.is_current_contributor(participant) {
+            return Err(CoordinatorError::ParticipantUnauthorized);
+        }
+
+        self.record_activity(participant);
+
+        // Check that new locks are accepted at this time. A chunk locked in the window
+        // can still be contributed after it closes.
+        self.check_contribution_window()?;
+
+        // Check that the current round is not yet finished.
+        if self.state.is_current_round_finished() {
+            return Err(CoordinatorError::CurrentRoundFinished);
+        }
+
+        // Check that the current round is not yet aggregating.
+        if self.state.is_current_round_aggregating() {
+            return Err(CoordinatorError::CurrentRoundAggregating);
+        }
 
         // Check that the current round is not yet aggregated.
         if self.state.is_current_round_aggregated() {
             return Err(CoordinatorError::CurrentRoundAggregated);
         }
 
-        // Attempt to fetch the next chunk ID and contribution ID for the given participant.
-        let current_task = self.state.fetch_task(participant, self.time.as_ref())?;
+        // Attempt to fetch the chunk ID and contribution ID for the given participant.
+        let current_task = match chunk_id {
+            Some(chunk_id) => self
+                .state
+                .fetch_task_for_chunk(participant, chunk_id, self.time.as_ref())?,
+            None => self.state.fetch_task(participant, self.time.as_ref())?,
+        };
         trace!("Fetched task {} for {}", current_task, participant);
 
         let round = Self::load_current_round(&self.storage)?;
@@ -958,6 +2393,11 @@ impl Coordinator {
                 // Save the coordinator state in storage.
                 self.save_state()?;
 
+                self.audit(AuditEvent::Lock {
+                    participant: participant.clone(),
+                    chunk_id: current_task.chunk_id(),
+                });
+
                 info!("Acquired lock on chunk {} for {}", current_task.chunk_id(), participant);
                 Ok((current_task.chunk_id(), locked_locators))
             }
@@ -1006,6 +2446,399 @@ impl Coordinator {
         round.initialize_verifier_response_files(&self.environment, &mut self.storage, participant, chunk_id, locators)
     }
 
+    ///
+    /// Returns the public summary of the progress of the ceremony.
+    ///
+    pub fn transparency_info(&self) -> Result<TransparencyInfo, CoordinatorError> {
+        let current_round_height = self.current_round_height()?;
+
+        let total_contributions = match self.storage.get(&Locator::ContributionsInfoSummary)? {
+            Object::ContributionsInfoSummary(summary) => summary.len(),
+            _ => return Err(CoordinatorError::StorageFailed),
+        };
+
+        Ok(TransparencyInfo {
+            current_round_height,
+            current_cohort: self.state.get_current_cohort_index(),
+            total_contributions,
+            finished_contributors: self.state.number_of_finished_contributors(),
+            latest_round_manifest_hash: self.round_manifest_hash(current_round_height)?,
+        })
+    }
+
+    ///
+    /// Returns the hex encoded hash of the state file of the given round.
+    ///
+    fn round_manifest_hash(&self, round_height: u64) -> Result<String, CoordinatorError> {
+        // The round state changes throughout the round, hash it directly instead of going through the cache.
+        let reader = self.storage.reader(&Locator::RoundState { round_height })?;
+
+        Ok(hex::encode(calculate_hash(&reader)))
+    }
+
+    ///
+    /// Returns the summary of the ceremony, if it is completed.
+    ///
+    pub fn ceremony_completion(&self) -> Result<Option<CeremonyCompletion>, CoordinatorError> {
+        if !self.storage.exists(&Locator::CeremonyCompletion) {
+            return Ok(None);
+        }
+
+        match self.storage.get(&Locator::CeremonyCompletion)? {
+            Object::CeremonyCompletion(completion) => Ok(Some(completion)),
+            _ => Err(CoordinatorError::StorageFailed),
+        }
+    }
+
+    ///
+    /// Writes the summary of the ceremony to storage, unless it was already written.
+    ///
+    fn write_completion(&mut self) -> Result<(), CoordinatorError> {
+        if self.storage.exists(&Locator::CeremonyCompletion) {
+            return Ok(());
+        }
+
+        let total_rounds = self.current_round_height()?;
+        let completed_at = self.time.now_utc();
+        let completion = CeremonyCompletion {
+            total_rounds,
+            total_contributors: self.state.number_of_finished_contributors(),
+            total_duration_seconds: (completed_at - self.state.ceremony_start_time()).whole_seconds(),
+            final_manifest_hash: self.transcript_manifest_hash()?,
+            completed_at,
+        };
+
+        self.storage.insert(
+            Locator::CeremonyCompletion,
+            Object::CeremonyCompletion(completion.clone()),
+        )?;
+
+        info!(
+            "\n\n==================== CEREMONY COMPLETED ====================\n\n{}\n",
+            serde_json::to_string_pretty(&completion)?
+        );
+
+        Ok(())
+    }
+
+    ///
+    /// Returns the version of the coordinator, along with its uptime.
+    ///
+    pub fn version_info(&self) -> VersionInfo {
+        VersionInfo {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_hash: option_env!("GIT_HASH").map(String::from),
+            uptime_seconds: (self.time.now_utc() - self.started_at).whole_seconds(),
+            deployment: self.environment.deployment().clone(),
+        }
+    }
+
+    ///
+    /// Checks the integrity of the storage of the coordinator: the state of
+    /// the current round and of the coordinator must be readable, and so must
+    /// the token files, whose number of cohorts must match the one in use.
+    ///
+    pub fn health_report(&self) -> HealthReport {
+        let round_state = Self::load_current_round_height(&self.storage)
+            .map_or(false, |round_height| self.storage.exists(&Locator::RoundState { round_height }));
+        let coordinator_state = matches!(
+            self.storage.get(&Locator::CoordinatorState),
+            Ok(Object::CoordinatorState(_))
+        );
+        let tokens = CoordinatorState::load_tokens(&self.environment);
+        let tokens_readable = tokens.is_ok();
+        let cohorts_consistent = tokens.map_or(false, |tokens| tokens.len() == self.state.get_number_of_cohorts());
+
+        HealthReport {
+            healthy: round_state && coordinator_state && tokens_readable && cohorts_consistent,
+            round_state,
+            coordinator_state,
+            tokens_readable,
+            cohorts_consistent,
+        }
+    }
+
+    ///
+    /// Returns a machine-readable snapshot of the coordinator state machine.
+    ///
+    pub fn diagnostics(&self) -> Diagnostics {
+        self.state.diagnostics(self.time.as_ref())
+    }
+
+    ///
+    /// Returns the summarized contributions authored by the given participant.
+    ///
+    pub fn participant_contributions_info(
+        &self,
+        participant: &Participant,
+    ) -> Result<Vec<TrimmedContributionInfo>, CoordinatorError> {
+        match self.storage.get(&Locator::ContributionsInfoSummary)? {
+            Object::ContributionsInfoSummary(summary) => Ok(summary
+                .into_iter()
+                .filter(|info| info.public_key() == participant.address())
+                .collect()),
+            _ => Err(CoordinatorError::StorageFailed),
+        }
+    }
+
+    ///
+    /// Returns the contributions' info of the given round, once it is finished.
+    ///
+    pub fn round_contributions_info(
+        &self,
+        round_height: u64,
+    ) -> Result<Vec<TrimmedContributionInfo>, CoordinatorError> {
+        // The info of the current round is only complete once the round is finished.
+        let current_round_height = self.current_round_height()?;
+        if round_height > current_round_height {
+            return Err(CoordinatorError::RoundDoesNotExist);
+        }
+        if round_height == current_round_height && !self.state.is_current_round_finished() {
+            return Err(CoordinatorError::RoundNotComplete);
+        }
+
+        match self.storage.get(&Locator::ContributionsInfoSummary)? {
+            Object::ContributionsInfoSummary(summary) => Ok(summary
+                .into_iter()
+                .filter(|info| info.ceremony_round() == round_height)
+                .collect()),
+            _ => Err(CoordinatorError::StorageFailed),
+        }
+    }
+
+    ///
+    /// Returns the Merkle tree over the contributions of the given round,
+    /// along with the public keys of the contributors of its leaves.
+    ///
+    /// The leaves commit to the public key of each contributor and the
+    /// hash of their contribution, sorted by public key.
+    ///
+    fn round_merkle_tree(&self, round_height: u64) -> Result<(Vec<String>, MerkleTree), CoordinatorError> {
+        let mut contributions: Vec<(String, String)> = match self.storage.get(&Locator::ContributionsInfoSummary)? {
+            Object::ContributionsInfoSummary(summary) => summary
+                .into_iter()
+                .filter(|info| info.ceremony_round() == round_height)
+                .map(|info| (info.public_key().to_string(), info.contribution_hash().to_string()))
+                .collect(),
+            _ => return Err(CoordinatorError::StorageFailed),
+        };
+
+        if contributions.is_empty() {
+            return Err(CoordinatorError::RoundDoesNotExist);
+        }
+        contributions.sort();
+
+        let leaves = contributions
+            .iter()
+            .map(|(public_key, contribution_hash)| leaf_hash(public_key, contribution_hash))
+            .collect();
+        let public_keys = contributions.into_iter().map(|(public_key, _)| public_key).collect();
+
+        Ok((public_keys, MerkleTree::new(leaves)))
+    }
+
+    ///
+    /// Returns the Merkle root over the contributions of the given round,
+    /// see [merkle](crate::merkle).
+    ///
+    pub fn round_merkle_root(&self, round_height: u64) -> Result<MerkleHash, CoordinatorError> {
+        Ok(self.round_merkle_tree(round_height)?.1.root())
+    }
+
+    ///
+    /// Returns the Merkle proof that the given participant contributed to
+    /// the given round, to be verified against [Self::round_merkle_root].
+    ///
+    pub fn round_merkle_proof(
+        &self,
+        round_height: u64,
+        participant: &Participant,
+    ) -> Result<MerkleProof, CoordinatorError> {
+        let (public_keys, tree) = self.round_merkle_tree(round_height)?;
+
+        public_keys
+            .iter()
+            .position(|public_key| *public_key == participant.address())
+            .and_then(|index| tree.proof(index))
+            .ok_or_else(|| CoordinatorError::ParticipantNotFound(participant.clone()))
+    }
+
+    ///
+    /// Writes the transcript of the ceremony to the given writer, as a zip
+    /// archive of the final contribution of every completed round, the
+    /// signatures of the contributions and the contribution info records.
+    ///
+    /// The files keep their path relative to the storage directory, and the
+    /// archive contains a manifest, [TRANSCRIPT_MANIFEST_FILE], listing the
+    /// SHA-256 hash of each of them to let anyone check their integrity.
+    ///
+    pub fn export_transcript<W: Write + Seek>(&self, writer: W) -> Result<(), CoordinatorError> {
+        Self::export_storage_transcript(&self.environment, &self.storage, writer)
+    }
+
+    ///
+    /// Returns the heights of the completed rounds in the given storage, along with
+    /// the locators of the files of their transcript.
+    ///
+    fn transcript_locators(storage: &dyn Storage) -> Result<(Vec<u64>, Vec<Locator>), CoordinatorError> {
+        // The rounds before the current one are completed, the current one only once all its chunks are verified.
+        let current_round_height = Self::load_current_round_height(storage)?;
+        let mut rounds: Vec<u64> = (0..current_round_height).collect();
+        if Self::load_current_round(storage)?.is_complete() {
+            rounds.push(current_round_height);
+        }
+
+        let mut locators = Vec::new();
+        for round_height in &rounds {
+            let round = Self::load_round(storage, *round_height)?;
+            for chunk in round.chunks() {
+                // The final contribution of a chunk is the verified file of its last contribution.
+                locators.push(Locator::ContributionFile(ContributionLocator::new(
+                    *round_height,
+                    chunk.chunk_id(),
+                    chunk.current_contribution_id(),
+                    true,
+                )));
+
+                for contribution_id in chunk.get_contributions().keys() {
+                    for verified in [false, true] {
+                        let signature = Locator::ContributionFileSignature(ContributionSignatureLocator::new(
+                            *round_height,
+                            chunk.chunk_id(),
+                            *contribution_id,
+                            verified,
+                        ));
+                        if storage.exists(&signature) {
+                            locators.push(signature);
+                        }
+                    }
+                }
+            }
+
+            let info = Locator::ContributionInfoFile {
+                round_height: *round_height,
+            };
+            if storage.exists(&info) {
+                locators.push(info);
+            }
+        }
+
+        Ok((rounds, locators))
+    }
+
+    ///
+    /// Returns the path of the file of the given locator in the transcript archive,
+    /// i.e. its path relative to the storage directory.
+    ///
+    fn transcript_file_name(
+        environment: &Environment,
+        storage: &dyn Storage,
+        locator: &Locator,
+    ) -> Result<String, CoordinatorError> {
+        let path = storage.to_path(locator)?;
+        Ok(path
+            .as_path()
+            .strip_prefix(Path::new(environment.local_base_directory()))
+            .map_err(|_| CoordinatorError::StorageFailed)?
+            .to_string_lossy()
+            .to_string())
+    }
+
+    ///
+    /// Returns the manifest of the transcript of the ceremony in the given storage,
+    /// hashing its files without writing an archive, see [Coordinator::export_transcript].
+    ///
+    fn storage_transcript_manifest(
+        environment: &Environment,
+        storage: &dyn Storage,
+    ) -> Result<TranscriptManifest, CoordinatorError> {
+        let (rounds, locators) = Self::transcript_locators(storage)?;
+
+        let mut files = Vec::with_capacity(locators.len());
+        for locator in &locators {
+            let mut writer = HashingWriter {
+                inner: std::io::sink(),
+                hasher: Sha256::new(),
+            };
+            let size = storage.copy_to(locator, &mut writer)?;
+            files.push(TranscriptFile {
+                path: Self::transcript_file_name(environment, storage, locator)?,
+                size,
+                sha256: hex::encode(writer.hasher.finalize()),
+            });
+        }
+
+        Ok(TranscriptManifest { rounds, files })
+    }
+
+    ///
+    /// Returns the hex encoded hash of the manifest of the transcript, which lists the
+    /// hashes of the final contributions, signatures and contribution info records of
+    /// the completed rounds. Unlike the round state, these files no longer change once
+    /// their round is completed.
+    ///
+    fn transcript_manifest_hash(&self) -> Result<String, CoordinatorError> {
+        let manifest = Self::storage_transcript_manifest(&self.environment, &self.storage)?;
+
+        Ok(hex::encode(Sha256::digest(&serde_json::to_vec_pretty(&manifest)?)))
+    }
+
+    ///
+    /// Writes the transcript of the ceremony in the given storage to the given
+    /// writer, see [Coordinator::export_transcript]. Only the storage is read,
+    /// so that the transcript can be exported without loading a coordinator
+    /// and its token files.
+    ///
+    pub fn export_storage_transcript<W: Write + Seek>(
+        environment: &Environment,
+        storage: &dyn Storage,
+        writer: W,
+    ) -> Result<(), CoordinatorError> {
+        let (rounds, locators) = Self::transcript_locators(storage)?;
+
+        let options = FileOptions::default();
+        let mut archive = ZipWriter::new(writer);
+        let mut files = Vec::with_capacity(locators.len());
+
+        for locator in &locators {
+            let name = Self::transcript_file_name(environment, storage, locator)?;
+
+            archive
+                .start_file(name.as_str(), options)
+                .map_err(std::io::Error::from)?;
+            let mut writer = HashingWriter {
+                inner: &mut archive,
+                hasher: Sha256::new(),
+            };
+            let size = storage.copy_to(locator, &mut writer)?;
+            files.push(TranscriptFile {
+                path: name,
+                size,
+                sha256: hex::encode(writer.hasher.finalize()),
+            });
+        }
+        debug!("Exported {} files of rounds {:?}", files.len(), rounds);
+
+        let manifest = TranscriptManifest { rounds, files };
+        archive
+            .start_file(TRANSCRIPT_MANIFEST_FILE, options)
+            .map_err(std::io::Error::from)?;
+        archive.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+        archive.finish().map_err(std::io::Error::from)?;
+
+        Ok(())
+    }
+
+    ///
+    /// Checks that the lock of the given contributor on the given chunk ID is still valid,
+    /// i.e. that it was not invalidated by a reset of the current round.
+    ///
+    /// Returns [CoordinatorError::StaleContribution] otherwise.
+    ///
+    pub fn check_contribution_epoch(&self, participant: &Participant, chunk_id: u64) -> Result<(), CoordinatorError> {
+        self.state.check_contribution_epoch(participant, chunk_id)
+    }
+
     ///
     /// Attempts to add a contribution for the given chunk ID from the given participant.
     ///
@@ -1030,35 +2863,11 @@ impl Coordinator {
         participant: &Participant,
         chunk_id: u64,
     ) -> Result<ContributionLocator, CoordinatorError> {
-        // Check that the participant is a contributor.
-        if !participant.is_contributor() {
-            return Err(CoordinatorError::ExpectedContributor);
-        }
-
-        // Check that the chunk ID is valid.
-        if chunk_id > self.environment.number_of_chunks() {
-            return Err(CoordinatorError::ChunkIdInvalid);
-        }
-
-        // Check that the participant is in the current round, and has not been dropped or finished.
-        if !self.state.is_current_contributor(participant) {
-            return Err(CoordinatorError::ParticipantUnauthorized);
-        }
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
 
-        // Check that the current round is not yet finished.
-        if self.state.is_current_round_finished() {
-            return Err(CoordinatorError::CurrentRoundFinished);
-        }
-
-        // Check that the current round is not yet aggregating.
-        if self.state.is_current_round_aggregating() {
-            return Err(CoordinatorError::CurrentRoundAggregating);
-        }
-
-        // Check that the current round is not yet aggregated.
-        if self.state.is_current_round_aggregated() {
-            return Err(CoordinatorError::CurrentRoundAggregated);
-        }
+        // Check that the participant can contribute to the chunk.
+        self.check_contribution(participant, chunk_id)?;
 
         // Fetch the current round height from storage.
         let round_height = Self::load_current_round_height(&self.storage)?;
@@ -1131,6 +2940,12 @@ impl Coordinator {
                     // Save the coordinator state in storage.
                     self.save_state()?;
 
+                    self.audit(AuditEvent::Complete {
+                        participant: participant.clone(),
+                        chunk_id,
+                        contribution_id,
+                    });
+
                     info!("Added contribution");
                     return Ok(locator);
                 }
@@ -1156,42 +2971,196 @@ impl Coordinator {
     }
 
     ///
-    /// Attempts to add a verification for the given chunk ID from the given participant.
-    ///
-    /// This function constructs the expected next challenge locator for the given participant
-    /// and chunk ID, and checks that the participant has uploaded the next challenge file and
-    /// contribution file signature prior to adding the verified contribution to the round state.
+    /// Attempts to add the contributions for the given chunk IDs from the given participant
+    /// at once, the participant holding the locks on all the chunks.
     ///
-    /// On success, this function releases the lock from the verifier.
+    /// The batch is atomic: if any of the contributions fails, the state of the coordinator
+    /// and of the round are rolled back and the response files of the batch are removed.
     ///
-    /// On failure, it returns a `CoordinatorError`.
+    /// On success, this function releases the locks from the contributor and returns
+    /// the response file locators, in the order of the given chunk IDs.
     ///
     #[tracing::instrument(
         level = "error",
-        skip(self, task),
-        fields(participant = %participant),
+        skip(self, participant, chunk_ids),
+        fields(participant = %participant, chunks = ?chunk_ids),
         err
     )]
-    pub fn try_verify(&mut self, participant: &Participant, task: &Task) -> Result<(), CoordinatorError> {
-        // Check that the participant is a verifier.
-        if !participant.is_verifier() {
-            return Err(CoordinatorError::ExpectedVerifier);
+    pub fn try_contribute_batch(
+        &mut self,
+        participant: &Participant,
+        chunk_ids: &[u64],
+    ) -> Result<Vec<ContributionLocator>, CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
+        // Check that the batch is not empty and contributes to each chunk once.
+        let unique_chunk_ids: HashSet<&u64> = chunk_ids.iter().collect();
+        if chunk_ids.is_empty() || unique_chunk_ids.len() != chunk_ids.len() {
+            return Err(CoordinatorError::ContributionBatchInvalid);
         }
 
-        // Check that the chunk ID is valid.
-        if task.chunk_id() > self.environment.number_of_chunks() {
-            return Err(CoordinatorError::ChunkIdInvalid);
+        // Check that the participant holds the locks on all the chunks, with a pending task for each.
+        let mut tasks = Vec::with_capacity(chunk_ids.len());
+        for chunk_id in chunk_ids {
+            self.check_contribution(participant, *chunk_id)?;
+            match self.state.lookup_pending_task(participant, *chunk_id)? {
+                Some(task) => tasks.push(*task),
+                None => return Err(CoordinatorError::ChunkNotLockedOrByWrongParticipant),
+            }
         }
 
-        // Check that the current round is not yet finished.
-        if self.state.is_current_round_finished() {
-            return Err(CoordinatorError::CurrentRoundFinished);
-        }
+        // Fetch the current round height from storage.
+        let round_height = Self::load_current_round_height(&self.storage)?;
+        trace!("Current round height in storage is {}", round_height);
 
-        // Check that the current round is not yet aggregating.
-        if self.state.is_current_round_aggregating() {
-            return Err(CoordinatorError::CurrentRoundAggregating);
-        }
+        // Keep the state of the coordinator and of the round to roll back the batch on failure.
+        let state = self.state.clone();
+        let round = Self::load_current_round(&self.storage)?;
+
+        let result = chunk_ids
+            .iter()
+            .map(|chunk_id| -> Result<ContributionLocator, CoordinatorError> {
+                let (locator, contribution_id) = self.add_contribution(*chunk_id, participant)?;
+                let completed_task = Task::new(*chunk_id, contribution_id);
+                self.state
+                    .completed_task(participant, &completed_task, self.time.as_ref())?;
+                Ok(locator)
+            })
+            .collect::<Result<Vec<_>, _>>();
+
+        let locators = match result {
+            Ok(locators) => locators,
+            Err(error) => {
+                info!("Failed to add the batch of contributions, rolling back the batch");
+                self.state = state;
+                self.storage
+                    .update(&Locator::RoundState { round_height }, Object::RoundState(round))?;
+
+                // Remove the response files of the batch.
+                for task in tasks {
+                    self.invalidate_chunk_hashes(round_height, task.chunk_id());
+                    let response = Locator::ContributionFile(ContributionLocator::new(
+                        round_height,
+                        task.chunk_id(),
+                        task.contribution_id(),
+                        false,
+                    ));
+                    if self.storage.exists(&response) {
+                        self.storage.remove(&response)?;
+                    }
+                }
+
+                error!("{}", error);
+                return Err(error);
+            }
+        };
+
+        // Blacklist participant's token and ip
+        if *TOKEN_BLACKLIST {
+            self.state.blacklist_participant_token(participant)?;
+        }
+
+        if *IP_BAN {
+            self.state.blacklist_participant_ip(participant)?;
+        }
+
+        // Save the coordinator state in storage.
+        self.save_state()?;
+
+        for task in tasks {
+            self.audit(AuditEvent::Complete {
+                participant: participant.clone(),
+                chunk_id: task.chunk_id(),
+                contribution_id: task.contribution_id(),
+            });
+        }
+
+        info!("Added a batch of {} contributions", locators.len());
+        Ok(locators)
+    }
+
+    ///
+    /// Checks that the given participant can contribute to the given chunk ID
+    /// in the current round, and records the activity of the participant.
+    ///
+    fn check_contribution(&mut self, participant: &Participant, chunk_id: u64) -> Result<(), CoordinatorError> {
+        // Check that the participant is a contributor.
+        if !participant.is_contributor() {
+            return Err(CoordinatorError::ExpectedContributor);
+        }
+
+        // Check that the chunk ID is valid.
+        if chunk_id > self.environment.number_of_chunks() {
+            return Err(CoordinatorError::ChunkIdInvalid);
+        }
+
+        // Check that the participant is in the current round, and has not been dropped or finished.
+        if !self.state.is_current_contributor(participant) {
+            return Err(CoordinatorError::ParticipantUnauthorized);
+        }
+
+        self.record_activity(participant);
+
+        // Check that the current round is not yet finished.
+        if self.state.is_current_round_finished() {
+            return Err(CoordinatorError::CurrentRoundFinished);
+        }
+
+        // Check that the current round is not yet aggregating.
+        if self.state.is_current_round_aggregating() {
+            return Err(CoordinatorError::CurrentRoundAggregating);
+        }
+
+        // Check that the current round is not yet aggregated.
+        if self.state.is_current_round_aggregated() {
+            return Err(CoordinatorError::CurrentRoundAggregated);
+        }
+
+        // Check that the lock on the chunk was not invalidated by a reset of the round.
+        self.state.check_contribution_epoch(participant, chunk_id)
+    }
+
+    ///
+    /// Attempts to add a verification for the given chunk ID from the given participant.
+    ///
+    /// This function constructs the expected next challenge locator for the given participant
+    /// and chunk ID, and checks that the participant has uploaded the next challenge file and
+    /// contribution file signature prior to adding the verified contribution to the round state.
+    ///
+    /// On success, this function releases the lock from the verifier.
+    ///
+    /// On failure, it returns a `CoordinatorError`.
+    ///
+    #[tracing::instrument(
+        level = "error",
+        skip(self, task),
+        fields(participant = %participant),
+        err
+    )]
+    pub fn try_verify(&mut self, participant: &Participant, task: &Task) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
+        // Check that the participant is a verifier.
+        if !participant.is_verifier() {
+            return Err(CoordinatorError::ExpectedVerifier);
+        }
+
+        // Check that the chunk ID is valid.
+        if task.chunk_id() > self.environment.number_of_chunks() {
+            return Err(CoordinatorError::ChunkIdInvalid);
+        }
+
+        // Check that the current round is not yet finished.
+        if self.state.is_current_round_finished() {
+            return Err(CoordinatorError::CurrentRoundFinished);
+        }
+
+        // Check that the current round is not yet aggregating.
+        if self.state.is_current_round_aggregating() {
+            return Err(CoordinatorError::CurrentRoundAggregating);
+        }
 
         // Check that the current round is not yet aggregated.
         if self.state.is_current_round_aggregated() {
@@ -1213,6 +3182,13 @@ impl Coordinator {
                 }
                 self.state.completed_task(participant, task, self.time.as_ref())?;
 
+                // The contributor gets the full retry budget back for the chunk.
+                let round = Self::load_current_round(&self.storage)?;
+                let contribution = round.chunk(task.chunk_id())?.get_contribution(contribution_id)?;
+                if let Some(contributor) = contribution.get_contributor() {
+                    self.state.reset_contribution_retries(contributor, task.chunk_id());
+                }
+
                 // Save the coordinator state in storage.
                 self.save_state()?;
 
@@ -1288,6 +3264,19 @@ impl Coordinator {
             return Err(CoordinatorError::RoundAlreadyAggregated);
         }
 
+        // Check that no contribution was submitted more than once in the current round.
+        if current_round_height != 0 {
+            if let Some((first, second)) = self.detect_duplicate_contributions(current_round_height)?.first() {
+                if *REJECT_DUPLICATES {
+                    error!("Coordinator is rejecting the aggregation of round {}", current_round_height);
+                    return Err(CoordinatorError::ContributionDuplicated {
+                        first: *first,
+                        second: *second,
+                    });
+                }
+            }
+        }
+
         // Update the coordinator state to set the start of aggregation for the current round.
         self.state.aggregating_current_round(self.time.as_ref())?;
 
@@ -1335,6 +3324,9 @@ impl Coordinator {
     ///
     #[tracing::instrument(skip(self, started_at))]
     pub fn try_advance(&mut self, started_at: OffsetDateTime) -> Result<u64, CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
         tracing::debug!("Trying to advance to the next round.");
 
         // Check that the current round height matches in storage and self.
@@ -1385,7 +3377,12 @@ impl Coordinator {
                     Ok(next_round_height) => {
                         // If success, update coordinator state to next round.
                         info!("Coordinator has advanced to round {}", next_round_height);
-                        self.state.commit_next_round();
+                        self.state.commit_next_round(self.time.as_ref());
+                        self.archive_finished_rounds();
+                        self.audit(AuditEvent::RoundAdvance {
+                            round_height: next_round_height,
+                        });
+                        self.publish_status();
                         Ok(next_round_height)
                     }
                     // Case 1b - Coordinator failed to advance the round.
@@ -1414,6 +3411,70 @@ impl Coordinator {
         result
     }
 
+    ///
+    /// Moves the finished participants of the rounds exceeding the retention
+    /// from memory to storage. A round failing to be archived is kept in
+    /// memory and retried on the next round transition.
+    ///
+    fn archive_finished_rounds(&mut self) {
+        for round_height in self.state.expired_finished_rounds() {
+            let participants = self.state.finished_participants(round_height).unwrap_or_default();
+            let locator = Locator::FinishedParticipants { round_height };
+            let object = Object::FinishedParticipants(participants);
+            let result = match self.storage.exists(&locator) {
+                true => self.storage.update(&locator, object),
+                false => self.storage.insert(locator, object),
+            };
+
+            match result {
+                Ok(()) => {
+                    debug!("Archived the finished participants of round {}", round_height);
+                    self.state.remove_finished_round(round_height);
+                }
+                Err(e) => error!(
+                    "Failed to archive the finished participants of round {}: {}",
+                    round_height, e
+                ),
+            }
+        }
+    }
+
+    ///
+    /// Returns the participants who finished the given round, reading them
+    /// from storage if the round has been archived.
+    ///
+    pub fn finished_participants(&self, round_height: u64) -> Result<FinishedParticipants, CoordinatorError> {
+        if let Some(participants) = self.state.finished_participants(round_height) {
+            return Ok(participants);
+        }
+
+        let locator = Locator::FinishedParticipants { round_height };
+        if !self.storage.exists(&locator) {
+            return Err(CoordinatorError::RoundDoesNotExist);
+        }
+
+        match self.storage.get(&locator)? {
+            Object::FinishedParticipants(participants) => Ok(participants),
+            _ => Err(CoordinatorError::StorageFailed),
+        }
+    }
+
+    ///
+    /// Cancels the precommit for the next round, moving the staged participants
+    /// back into the queue. Returns the list of the un-staged participants.
+    ///
+    pub fn cancel_precommit(&mut self) -> Result<Vec<Participant>, CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
+        let participants = self.state.cancel_precommit_next_round(self.time.as_ref())?;
+
+        // Save the coordinator state in storage.
+        self.save_state()?;
+
+        Ok(participants)
+    }
+
     ///
     /// Returns the chunk ID from the given contribution file locator path.
     ///
@@ -1555,7 +3616,7 @@ impl Coordinator {
         let (challenge_hash, response_hash) = {
             // Compute the challenge hash using the challenge file.
             let challenge_reader = self.storage.reader(&challenge_file_locator)?;
-            let challenge_hash = calculate_hash(challenge_reader.as_ref());
+            let challenge_hash = self.get_file_hash(&challenge_file_locator)?;
             info!(
                 "Challenge is located in {}",
                 self.storage.to_path(&challenge_file_locator)?
@@ -1566,7 +3627,7 @@ impl Coordinator {
 
             // Compute the response hash.
             let response_reader = self.storage.reader(&Locator::ContributionFile(response_file_locator))?;
-            let response_hash = calculate_hash(response_reader.as_ref());
+            let response_hash = calculate_hash(&response_reader);
             info!(
                 "Response is located in {}",
                 self.storage
@@ -1643,6 +3704,17 @@ impl Coordinator {
             self.storage.to_path(&contribution_file_signature_locator)?,
         )?;
 
+        // Queue the trusted timestamp of the contribution, if enabled. It is requested once the coordinator lock
+        // is released, see [Coordinator::take_pending_timestamps].
+        if self.timestamp_authority.is_some() {
+            self.pending_timestamps.push(PendingTimestamp {
+                round_height: current_round_height,
+                chunk_id,
+                contribution_id,
+                response_hash: response_hash.to_vec(),
+            });
+        }
+
         // Add the updated round to storage.
         match self.storage.update(
             &Locator::RoundState {
@@ -1653,12 +3725,54 @@ impl Coordinator {
             Ok(_) => {
                 debug!("Updated round {} in storage", current_round_height);
                 debug!("{} added a contribution to chunk {}", participant, chunk_id);
+                self.invalidate_chunk_hashes(current_round_height, chunk_id);
                 Ok((response_file_locator, contribution_id))
             }
             _ => Err(CoordinatorError::StorageUpdateFailed),
         }
     }
 
+    ///
+    /// Returns the time stamping authority, if enabled, with the contributions
+    /// accepted since the last call.
+    ///
+    /// The timestamps are requested from the authority, an external service,
+    /// without holding the coordinator lock, and stored back with
+    /// [Coordinator::store_timestamp]. The pending contributions are not
+    /// persisted, so the ones not yet timestamped are lost on restart.
+    ///
+    pub fn take_pending_timestamps(&mut self) -> Option<(TimestampAuthority, Vec<PendingTimestamp>)> {
+        let authority = self.timestamp_authority.clone()?;
+
+        Some((authority, std::mem::take(&mut self.pending_timestamps)))
+    }
+
+    ///
+    /// Stores the trusted timestamp of the given contribution alongside
+    /// its signature, in the state of its round.
+    ///
+    pub fn store_timestamp(
+        &mut self,
+        pending: &PendingTimestamp,
+        timestamp: TimestampToken,
+    ) -> Result<(), CoordinatorError> {
+        self.check_writable()?;
+
+        let mut round = Self::load_round(&self.storage, pending.round_height)?;
+        round
+            .chunk_mut(pending.chunk_id)?
+            .set_contribution_timestamp(pending.contribution_id, timestamp)?;
+
+        self.storage.update(
+            &Locator::RoundState {
+                round_height: pending.round_height,
+            },
+            Object::RoundState(round),
+        )?;
+
+        Ok(())
+    }
+
     #[inline]
     pub(crate) fn get_challenge(
         &self,
@@ -1690,11 +3804,121 @@ impl Coordinator {
     where
         T: Into<Vec<u8>>,
     {
+        let locator = Locator::ContributionFile(contribution_locator);
+        self.hash_cache
+            .write()
+            .expect("Unable to lock to write hash cache")
+            .remove(&locator);
+
         // Can use update instead of insert because the path is already initialized by other functions
-        self.storage.update(
-            &Locator::ContributionFile(contribution_locator),
-            Object::ContributionFile(contribution.into()),
-        )
+        self.storage
+            .update(&locator, Object::ContributionFile(contribution.into()))
+    }
+
+    /// Writes the bytes of a contribution to storage like [Coordinator::write_contribution], and caches the given
+    /// hash of the bytes. The callers compute the hash before taking the lock on the coordinator, so that the
+    /// detection of the duplicate contributions at aggregation doesn't have to hash the file again.
+    pub(crate) fn write_contribution_with_hash(
+        &mut self,
+        contribution_locator: ContributionLocator,
+        contribution: Vec<u8>,
+        hash: Vec<u8>,
+    ) -> Result<(), CoordinatorError> {
+        self.write_contribution(contribution_locator, contribution)?;
+        self.hash_cache
+            .write()
+            .expect("Unable to lock to write hash cache")
+            .insert(Locator::ContributionFile(contribution_locator), hash);
+
+        Ok(())
+    }
+
+    ///
+    /// Returns the hash of the file at the given locator. The hash is
+    /// computed on the first request and then served from the cache
+    /// until the file gets updated by the coordinator.
+    ///
+    pub fn get_file_hash(&self, locator: &Locator) -> Result<Vec<u8>, CoordinatorError> {
+        if let Some(hash) = self
+            .hash_cache
+            .read()
+            .expect("Unable to obtain lock to read hash cache")
+            .get(locator)
+        {
+            return Ok(hash.clone());
+        }
+
+        let reader = self.storage.reader(locator)?;
+        let hash = calculate_hash(&reader).to_vec();
+        self.hash_cache
+            .write()
+            .expect("Unable to lock to write hash cache")
+            .insert(*locator, hash.clone());
+
+        Ok(hash)
+    }
+
+    ///
+    /// Returns the pairs of contributions of the given round whose files are identical,
+    /// each pair made of the first contribution with the content and a later duplicate.
+    ///
+    pub fn detect_duplicate_contributions(&self, round_height: u64) -> Result<Vec<(Task, Task)>, CoordinatorError> {
+        let round = self.get_round(round_height)?;
+
+        let mut contributions: HashMap<Vec<u8>, (Task, Option<Participant>)> = HashMap::new();
+        let mut duplicates = Vec::new();
+        for chunk in round.chunks() {
+            // The contribution 0 is the initial challenge of the chunk.
+            for (contribution_id, contribution) in chunk.get_contributions().iter().skip(1) {
+                let path = match contribution.get_contributed_location() {
+                    Some(path) => path,
+                    None => continue,
+                };
+                let hash = self.get_file_hash(&self.storage.to_locator(path)?)?;
+                let task = Task::new(chunk.chunk_id(), *contribution_id);
+                let contributor = contribution.get_contributor().clone();
+
+                match contributions.get(&hash) {
+                    Some((first, first_contributor)) => {
+                        warn!(
+                            "Contribution {} by {:?} in round {} duplicates contribution {} by {:?}",
+                            task, contributor, round_height, first, first_contributor
+                        );
+                        duplicates.push((*first, task));
+                    }
+                    None => {
+                        contributions.insert(hash, (task, contributor));
+                    }
+                }
+            }
+        }
+
+        Ok(duplicates)
+    }
+
+    ///
+    /// Removes from the hash cache the contribution files of the given chunk in the given round.
+    ///
+    fn invalidate_chunk_hashes(&self, round_height: u64, chunk_id: u64) {
+        self.hash_cache
+            .write()
+            .expect("Unable to lock to write hash cache")
+            .retain(|locator, _| match locator {
+                Locator::ContributionFile(contribution_locator) => {
+                    contribution_locator.round_height() != round_height || contribution_locator.chunk_id() != chunk_id
+                }
+                _ => true,
+            });
+    }
+
+    ///
+    /// Clears the hash cache, on round transitions.
+    ///
+    fn clear_hash_cache(&self) {
+        self.hash_cache
+            .write()
+            .expect("Unable to lock to write hash cache")
+            .clear();
     }
 
     /// Writes the contribution metadata to storage at the appropriate locator.
@@ -1783,6 +4007,28 @@ impl Coordinator {
         )
     }
 
+    /// Removes the contribution file and its signature, if present, written for a contribution which could not be
+    /// added to the round.
+    pub(crate) fn remove_contribution(
+        &mut self,
+        contribution_locator: ContributionLocator,
+        signature_locator: ContributionSignatureLocator,
+    ) -> Result<(), CoordinatorError> {
+        let locator = Locator::ContributionFile(contribution_locator);
+        self.hash_cache
+            .write()
+            .expect("Unable to lock to write hash cache")
+            .remove(&locator);
+
+        for locator in [locator, Locator::ContributionFileSignature(signature_locator)] {
+            if self.storage.exists(&locator) {
+                self.storage.remove(&locator)?;
+            }
+        }
+
+        Ok(())
+    }
+
     ///
     /// Attempts to run verification in the current round for a given
     /// chunk ID and participant.
@@ -1878,8 +4124,7 @@ impl Coordinator {
         // Check the challenge-response hash chain.
         let (challenge_hash, response_hash) = {
             // Compute the challenge hash using the challenge file.
-            let challenge_reader = self.storage.reader(&challenge_file_locator)?;
-            let challenge_hash = calculate_hash(challenge_reader.as_ref());
+            let challenge_hash = self.get_file_hash(&challenge_file_locator)?;
             trace!(
                 "Challenge is located in {}",
                 self.storage.to_path(&challenge_file_locator)?
@@ -1888,7 +4133,7 @@ impl Coordinator {
 
             // Compute the response hash.
             let response_reader = self.storage.reader(&response_file_locator)?;
-            let response_hash = calculate_hash(response_reader.as_ref());
+            let response_hash = calculate_hash(&response_reader);
             trace!(
                 "Response is located in {}",
                 self.storage.to_path(&response_file_locator)?
@@ -1916,7 +4161,7 @@ impl Coordinator {
         let next_challenge_hash = {
             // Compute the next challenge hash.
             let next_challenge_reader = self.storage.reader(&next_challenge_locator)?;
-            let next_challenge_hash = calculate_hash(next_challenge_reader.as_ref());
+            let next_challenge_hash = calculate_hash(&next_challenge_reader);
             trace!(
                 "Next challenge is located in {}",
                 self.storage.to_path(&next_challenge_locator)?
@@ -1927,7 +4172,7 @@ impl Coordinator {
             );
 
             // Fetch the saved response hash in the next challenge file.
-            let saved_response_hash = next_challenge_reader.as_ref().chunks(64).next().unwrap().to_vec();
+            let saved_response_hash = next_challenge_reader.chunks(64).next().unwrap().to_vec();
             let pretty_hash = pretty_hash!(&saved_response_hash);
 
             // Check that the response hash matches the next challenge hash.
@@ -2015,6 +4260,9 @@ impl Coordinator {
                     "{} verified chunk {} contribution {}",
                     participant, chunk_id, contribution_id
                 );
+                // The final verification of a chunk is stored in the next round's directory.
+                self.invalidate_chunk_hashes(current_round_height, chunk_id);
+                self.invalidate_chunk_hashes(current_round_height + 1, chunk_id);
                 Ok(contribution_id)
             }
             _ => Err(CoordinatorError::StorageUpdateFailed),
@@ -2149,6 +4397,9 @@ impl Coordinator {
             return Err(CoordinatorError::ContributorsMissing);
         }
 
+        // Files of the rounds are going to be updated, drop their cached hashes.
+        self.clear_hash_cache();
+
         // Fetch the current round height from storage.
         let current_round_height = Self::load_current_round_height(&self.storage)?;
 
@@ -2393,7 +4644,7 @@ impl Coordinator {
     }
 
     #[inline]
-    fn load_current_round_height(storage: &Disk) -> Result<u64, CoordinatorError> {
+    fn load_current_round_height(storage: &dyn Storage) -> Result<u64, CoordinatorError> {
         if storage.exists(&Locator::RoundHeight) {
             // Fetch the current round height from storage.
             match storage.get(&Locator::RoundHeight)? {
@@ -2408,7 +4659,7 @@ impl Coordinator {
     }
 
     #[inline]
-    fn load_current_round(storage: &Disk) -> Result<Round, CoordinatorError> {
+    fn load_current_round(storage: &dyn Storage) -> Result<Round, CoordinatorError> {
         // Fetch the current round height from storage.
         let current_round_height = Self::load_current_round_height(storage)?;
 
@@ -2417,7 +4668,7 @@ impl Coordinator {
     }
 
     #[inline]
-    fn load_round(storage: &Disk, round_height: u64) -> Result<Round, CoordinatorError> {
+    fn load_round(storage: &dyn Storage, round_height: u64) -> Result<Round, CoordinatorError> {
         // Fetch the current round height from storage.
         let current_round_height = Self::load_current_round_height(storage)?;
 
@@ -2440,8 +4691,8 @@ impl Coordinator {
     /// coordinator is using.
     ///
     #[inline]
-    pub(super) fn storage(&self) -> &Disk {
-        &self.storage
+    pub(super) fn storage(&self) -> &dyn Storage {
+        &*self.storage
     }
 
     ///
@@ -2450,8 +4701,18 @@ impl Coordinator {
     ///
     #[cfg(test)]
     #[inline]
-    pub(super) fn storage_mut(&mut self) -> &mut Disk {
-        &mut self.storage
+    pub(super) fn storage_mut(&mut self) -> &mut dyn Storage {
+        &mut *self.storage
+    }
+
+    ///
+    /// Updates the queue of the ceremony on its own, as done on
+    /// every call to [Coordinator::update].
+    ///
+    #[cfg(test)]
+    pub(crate) fn update_queue(&mut self) -> Result<(), CoordinatorError> {
+        self.state.update_queue()?;
+        self.save_state()
     }
 
     ///
@@ -2469,6 +4730,9 @@ impl Coordinator {
     /// previous round to invite new participants into the round.
     ///
     pub fn reset_round(&mut self) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
         let reset_action = self.state.reset_current_round(true, &*self.time)?;
 
         self.storage
@@ -2478,24 +4742,83 @@ impl Coordinator {
         Ok(())
     }
 
-    /// Reset the current round in storage.
     ///
-    /// + `remove_participants` is a list of participants that will
-    ///   have their contributions removed from the round.
-    /// + If `rollback` is set to `true`, the current round will be
-    ///   decremented by 1. If `rollback` is set to `true` and the
-    ///   current round height is `0` then this will return an error
-    ///   [CoordinatorError::RoundHeightIsZero]
-    fn reset_round_storage(&mut self, reset_action: &ResetCurrentRoundStorageAction) -> Result<(), CoordinatorError> {
-        // Fetch the current height of the ceremony.
-        let current_round_height = Self::load_current_round_height(&mut self.storage)?;
-        let span = tracing::error_span!("reset_round", round = current_round_height);
-        let _guard = span.enter();
-        warn!("Resetting round {}", current_round_height);
+    /// Handles a contribution of the given contributor which failed
+    /// verification on the given task.
+    ///
+    /// If the contributor has retries left for the chunk, the contribution is
+    /// removed from the chunk and the task is given back to the contributor,
+    /// the rest of the round is left untouched. Otherwise, the round is rolled
+    /// back and the contributor is banned.
+    ///
+    /// Returns the number of retries left, or `None` if the contributor was banned.
+    ///
+    pub fn handle_failed_verification(
+        &mut self,
+        contributor: &Participant,
+        task: &Task,
+    ) -> Result<Option<u8>, CoordinatorError> {
+        let chunk_id = task.chunk_id();
+        match self.state.consume_contribution_retry(contributor, chunk_id)? {
+            Some(remaining) => {
+                warn!(
+                    "{} failed verification of chunk {}, {} retries left",
+                    contributor, chunk_id, remaining
+                );
 
-        let mut round = Self::load_round(&mut self.storage, current_round_height)?;
+                // Give the task back to the contributor.
+                self.state
+                    .retry_contribution_task(contributor, task, self.time.as_ref())?;
 
-        tracing::debug!("Resetting round and applying storage changes");
+                // Remove the failed contribution from the chunk, the files of the round are going to change.
+                self.clear_hash_cache();
+                let mut round = Self::load_current_round(&self.storage)?;
+                round.remove_chunk_contributions_unsafe(&mut self.storage, contributor, &[*task])?;
+                self.storage.update(
+                    &Locator::RoundState {
+                        round_height: round.round_height(),
+                    },
+                    Object::RoundState(round),
+                )?;
+
+                self.save_state()?;
+
+                Ok(Some(remaining))
+            }
+            None => {
+                warn!("{} exhausted the retries for chunk {}", contributor, chunk_id);
+
+                // Reset the round to prevent a coordinator stall. The contributor must be banned
+                // after the reset because one can't ban a finished contributor.
+                self.reset_round()?;
+                self.ban_participant(contributor)?;
+
+                Ok(None)
+            }
+        }
+    }
+
+    /// Reset the current round in storage.
+    ///
+    /// + `remove_participants` is a list of participants that will
+    ///   have their contributions removed from the round.
+    /// + If `rollback` is set to `true`, the current round will be
+    ///   decremented by 1. If `rollback` is set to `true` and the
+    ///   current round height is `0` then this will return an error
+    ///   [CoordinatorError::RoundHeightIsZero]
+    fn reset_round_storage(&mut self, reset_action: &ResetCurrentRoundStorageAction) -> Result<(), CoordinatorError> {
+        // Fetch the current height of the ceremony.
+        let current_round_height = Self::load_current_round_height(&mut self.storage)?;
+        let span = tracing::error_span!("reset_round", round = current_round_height);
+        let _guard = span.enter();
+        warn!("Resetting round {}", current_round_height);
+
+        let mut round = Self::load_round(&mut self.storage, current_round_height)?;
+
+        // Files of the round are going to be cleared, drop their cached hashes.
+        self.clear_hash_cache();
+
+        tracing::debug!("Resetting round and applying storage changes");
         self.storage.process(round.reset(&reset_action.remove_participants))?;
 
         // Clear all files
@@ -2524,7 +4847,7 @@ impl Coordinator {
 }
 
 #[cfg(any(test, feature = "operator"))]
-use crate::commands::{Computation, Seed, SigningKey, Verification};
+use crate::commands::{Computation, Seed, SigningKey, Verification, SEED_LENGTH};
 
 #[cfg(any(test, feature = "operator"))]
 impl Coordinator {
@@ -2582,26 +4905,81 @@ impl Coordinator {
         Ok(())
     }
 
+    ///
+    /// Returns a seed for a contribution, drawn from the random source of the
+    /// coordinator so that the contributions are reproducible in the tests.
+    ///
+    pub fn generate_seed(&self) -> Seed {
+        let mut seed: Seed = [0; SEED_LENGTH];
+        self.rng.fill_bytes(&mut seed);
+        seed
+    }
+
     pub fn get_pending_verifications(&self) -> &HashMap<Task, Participant> {
         self.state.get_pending_verifications()
     }
 
-    /// Verify a contribution using the coordinator's default verifier.
+    ///
+    /// Returns the verifiers which have been assigned tasks pending verification,
+    /// including the verifiers of a cohort which are not among the default ones.
+    ///
+    pub fn get_assigned_verifiers(&self) -> Vec<Participant> {
+        let mut verifiers: Vec<Participant> = Vec::new();
+        for verifier in self.state.get_pending_verifications().values() {
+            if !verifiers.contains(verifier) {
+                verifiers.push(verifier.clone());
+            }
+        }
+        verifiers
+    }
+
+    ///
+    /// Locks and returns the next task pending verification assigned to the given verifier,
+    /// respecting the verifier lock chunk limit. Returns `None` if there is nothing left to verify.
+    ///
+    pub fn fetch_verifier_task(&mut self, verifier: &Participant) -> Result<Option<Task>, CoordinatorError> {
+        self.state.fetch_verifier_task(verifier)
+    }
+
+    ///
+    /// Marks the verification of the given task as started, so that it is resumed first
+    /// if the coordinator restarts before completing it.
+    ///
+    pub fn started_verification(&mut self, task: &Task) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
+        self.state.started_verification(task, self.time.as_ref())?;
+        self.save_state()?;
+
+        Ok(())
+    }
+
+    /// Verify a contribution using the coordinator verifier the task is assigned to.
     /// This is just an interface to [`verify`]
     ///
     /// # Error
-    /// This function assumes that the given task has been indeed assigned to the
-    /// default verifier.
+    /// This function assumes that the given task has been indeed assigned to a
+    /// verifier of the coordinator, whose signing key is set in the environment.
     pub fn default_verify(&mut self, task: &Task) -> anyhow::Result<()> {
+        // Verify on behalf of the verifier the task was assigned to, if any.
         let verifier = self
-            .environment
-            .coordinator_verifiers()
-            .first()
+            .state
+            .get_pending_verifications()
+            .get(task)
+            .or_else(|| self.environment.coordinator_verifiers().first())
             .ok_or_else(|| CoordinatorError::VerifierMissing)?
             .clone();
-        let sigkey = self.environment.default_verifier_signing_key();
+        let sigkey = self
+            .environment
+            .verifier_signing_key(&verifier)
+            .ok_or_else(|| CoordinatorError::VerifierMissing)?;
 
-        self.verify(&verifier, &sigkey, task)
+        // Release the lock on the task if the verification fails, so that it can be retried.
+        self.verify(&verifier, &sigkey, task).map_err(|e| {
+            self.state.release_verifier_task(&verifier, task);
+            e
+        })
     }
 
     #[tracing::instrument(
@@ -2620,6 +4998,7 @@ impl Coordinator {
             round_height,
             task.chunk_id()
         );
+        self.started_verification(task)?;
         let _next_challenge = self.run_verification(round_height, task, verifier, verifier_signing_key)?;
         self.try_verify(verifier, task)?;
         debug!(
@@ -2657,9 +5036,7 @@ impl Coordinator {
         }
 
         // Check that the contribution ID is valid.
-        if contribution_id == 0 {
-            return Err(CoordinatorError::ContributionIdMustBeNonzero);
-        }
+        check_contribution_id(contribution_id)?;
 
         // Check that the participant is a contributor.
         if !participant.is_contributor() {
@@ -2747,9 +5124,7 @@ impl Coordinator {
         }
 
         // Check that the contribution ID is valid.
-        if contribution_id == 0 {
-            return Err(CoordinatorError::ContributionIdMustBeNonzero);
-        }
+        check_contribution_id(contribution_id)?;
 
         // Check that the participant is a verifier.
         if !participant.is_verifier() {
@@ -2838,6 +5213,95 @@ impl Coordinator {
         &self.environment
     }
 
+    ///
+    /// Returns the source of the current time of the coordinator.
+    ///
+    #[inline]
+    pub fn time_source(&self) -> Arc<dyn TimeSource> {
+        self.time.clone()
+    }
+
+    ///
+    /// Releases the locks held by the current contributors for longer than the
+    /// participant lock timeout, rolling back the locked tasks.
+    ///
+    /// Returns the released (contributor, task) pairs.
+    ///
+    fn release_orphaned_locks(&mut self) -> Result<Vec<(Participant, Task)>, CoordinatorError> {
+        let orphaned_locks = self.state.orphaned_locks(self.time.as_ref());
+
+        for (participant, task) in &orphaned_locks {
+            warn!(
+                "Releasing orphaned lock on chunk {} held by {}",
+                task.chunk_id(),
+                participant
+            );
+            self.rollback_locked_task(participant, *task)?;
+        }
+
+        Ok(orphaned_locks)
+    }
+
+    ///
+    /// Releases the locks held in the current round by the contributors disconnected
+    /// for not being seen, whose tasks were already rolled back in the state, so that
+    /// the chunks are not stuck while the contributors can reconnect.
+    ///
+    fn release_disconnected_locks(&mut self) -> Result<(), CoordinatorError> {
+        let mut round = self.current_round()?;
+
+        let mut disconnected_locks: HashMap<Participant, Vec<u64>> = HashMap::new();
+        for chunk in round.chunks() {
+            if let Some(participant) = chunk.lock_holder() {
+                if self.state.is_disconnected(participant) {
+                    disconnected_locks
+                        .entry(participant.clone())
+                        .or_default()
+                        .push(chunk.chunk_id());
+                }
+            }
+        }
+
+        if disconnected_locks.is_empty() {
+            return Ok(());
+        }
+
+        for (participant, chunk_ids) in &disconnected_locks {
+            warn!("Releasing the locks on chunks {:?} held by {}", chunk_ids, participant);
+            round.remove_locks_unsafe(&mut self.storage, participant, chunk_ids)?;
+        }
+
+        Ok(self.storage.process(StorageAction::Update(UpdateAction {
+            locator: Locator::RoundState {
+                round_height: self.current_round_height()?,
+            },
+            object: Object::RoundState(round),
+        }))?)
+    }
+
+    ///
+    /// Releases the lock held by the given participant on the given chunk ID, without
+    /// dropping the participant. Should be used by the operator to unlock a single chunk
+    /// stuck before the participant lock timeout.
+    ///
+    pub fn force_release_lock(&mut self, participant: &Participant, chunk_id: u64) -> Result<(), CoordinatorError> {
+        // Reject state changes while the storage is read-only.
+        self.check_writable()?;
+
+        let task = self.state.force_release_lock(participant, chunk_id, &*self.time)?;
+        self.save_state()?;
+
+        let mut round = self.current_round()?;
+        round.remove_locks_unsafe(&mut self.storage, participant, &[task.chunk_id()])?;
+
+        Ok(self.storage.process(StorageAction::Update(UpdateAction {
+            locator: Locator::RoundState {
+                round_height: self.current_round_height()?,
+            },
+            object: Object::RoundState(round),
+        }))?)
+    }
+
     ///
     /// Rollback a task which was locked by a contributor. Should be used to unlock
     /// chunks which become stuck during the ceremony.
@@ -2861,22 +5325,35 @@ impl Coordinator {
 #[cfg(test)]
 mod tests {
     use crate::{
-        authentication::Dummy,
+        audit::{AuditEntry, AuditEvent},
+        authentication::{Dummy, KeyPair, Production, Signature},
         commands::{Seed, SigningKey, SEED_LENGTH},
         environment::*,
-        objects::{Participant, Task},
+        merkle::leaf_hash,
+        objects::{ContributionInfo, Participant, Task},
+        storage::{
+            ContributionLocator, Disk, Locator, MemoryStorage, Object, StorageCipher, StorageLocator, STORAGE_KEY_ENV,
+        },
         testing::prelude::*,
-        Coordinator,
+        timestamp::TimestampToken,
+        contribution_anomalies, ContributionAnomaly, Coordinator, CoordinatorError, DiskSpace, MockRngSource,
+        MockTimeSource, RegionClassifier, StatusEvent, SystemTimeSource, TranscriptManifest, STATUS_EVENTS_CAPACITY,
+        TRANSCRIPT_MANIFEST_FILE,
     };
 
     use once_cell::sync::Lazy;
     use rand::RngCore;
+    use rocket::tokio::sync::broadcast::error::TryRecvError;
+    use setup_utils::calculate_hash;
+    use sha2::{Digest, Sha256};
     use std::{
         collections::HashMap,
+        io::Read,
         net::{IpAddr, Ipv4Addr},
-        sync::Arc,
+        path::Path,
+        sync::{Arc, Mutex},
     };
-    use time::OffsetDateTime;
+    use time::{Duration, OffsetDateTime};
 
     fn initialize_to_round_1(
         coordinator: &mut Coordinator,
@@ -3174,6 +5651,57 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[serial]
+    fn coordinator_timestamp_authority_unavailable() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_signing_key: SigningKey = "secret_key".to_string();
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator(&mut coordinator)?;
+        // Nothing listens on the discard port.
+        coordinator.enable_timestamping("http://127.0.0.1:9");
+
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        coordinator.try_lock_chunk(0, &contributor)?;
+        coordinator.run_computation(1, 0, 1, &contributor, &contributor_signing_key, &seed)?;
+
+        // The contribution is accepted without a timestamp, which is requested later.
+        coordinator.add_contribution(0, &contributor)?;
+        let round = coordinator.current_round()?;
+        let contribution = round.chunk(0)?.get_contribution(1)?;
+        assert_eq!(&Some(contributor), contribution.get_contributor());
+        assert!(contribution.get_contributed_timestamp().is_none());
+
+        let (authority, pending_timestamps) = coordinator.take_pending_timestamps().unwrap();
+        assert_eq!(1, pending_timestamps.len());
+        let pending = &pending_timestamps[0];
+        assert_eq!(
+            (1, 0, 1),
+            (pending.round_height, pending.chunk_id, pending.contribution_id)
+        );
+        assert!(coordinator.take_pending_timestamps().unwrap().1.is_empty());
+        assert!(matches!(
+            authority.timestamp(&pending.response_hash),
+            Err(CoordinatorError::TimestampRequestFailed(_))
+        ));
+
+        // A timestamp is stored in the state of the round of the contribution.
+        let timestamp = TimestampToken {
+            authority: authority.url().to_string(),
+            token: "token".to_string(),
+        };
+        coordinator.store_timestamp(pending, timestamp.clone())?;
+        let round = coordinator.current_round()?;
+        let contribution = round.chunk(0)?.get_contribution(1)?;
+        assert_eq!(&Some(timestamp), contribution.get_contributed_timestamp());
+
+        Ok(())
+    }
+
     #[test]
     #[serial]
     fn coordinator_verifier_verify_contribution() -> anyhow::Result<()> {
@@ -3632,4 +6160,1145 @@ mod tests {
             coordinator.get_round(0).unwrap().chunks().len() as u64
         );
     }
+
+    #[test]
+    #[serial]
+    fn coordinator_failed_verification_retry_budget() -> anyhow::Result<()> {
+        let budget = 1;
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .contribution_retry_budget(budget)
+        .into();
+        initialize_test_environment(&environment);
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_signing_key: SigningKey = "secret_key".to_string();
+
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        assert_eq!(
+            Some(budget),
+            coordinator.state.remaining_contribution_retries(&contributor)
+        );
+
+        // Contribute and fail the verification: the contributor is left in the round with one retry less.
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        coordinator.contribute(&contributor, &contributor_signing_key, &seed)?;
+        assert!(coordinator.is_finished_contributor(&contributor));
+        let task = coordinator.get_pending_verifications().keys().next().cloned().unwrap();
+
+        assert_eq!(
+            Some(budget - 1),
+            coordinator.handle_failed_verification(&contributor, &task)?
+        );
+        assert_eq!(1, coordinator.current_round_height()?);
+        assert!(coordinator.is_current_contributor(&contributor));
+        assert_eq!(
+            Some(budget - 1),
+            coordinator.state.remaining_contribution_retries(&contributor)
+        );
+
+        // Only the failed contribution is removed, and the contributor is given its task back.
+        assert!(coordinator.get_pending_verifications().is_empty());
+        let chunk = coordinator.current_round()?.chunk(task.chunk_id())?.clone();
+        assert_eq!(task.contribution_id(), chunk.get_contributions().len() as u64);
+        assert_eq!(
+            Some(&task),
+            coordinator
+                .state
+                .current_participant_info(&contributor)
+                .unwrap()
+                .assigned_tasks()
+                .front()
+        );
+
+        // Contribute again and fail the verification: the budget is exhausted and the contributor is banned.
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        coordinator.contribute(&contributor, &contributor_signing_key, &seed)?;
+
+        assert_eq!(None, coordinator.handle_failed_verification(&contributor, &task)?);
+        assert_eq!(0, coordinator.current_round_height()?);
+        assert!(!coordinator.is_current_contributor(&contributor));
+        assert!(coordinator.is_banned_participant(&contributor));
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_failed_verification_ban_by_default() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+        assert_eq!(0, TEST_ENVIRONMENT_ANOMA.contribution_retry_budget());
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_signing_key: SigningKey = "secret_key".to_string();
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        // The first failed verification rolls the round back and bans the contributor.
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        coordinator.contribute(&contributor, &contributor_signing_key, &seed)?;
+        let task = coordinator.get_pending_verifications().keys().next().cloned().unwrap();
+
+        assert_eq!(None, coordinator.handle_failed_verification(&contributor, &task)?);
+        assert_eq!(0, coordinator.current_round_height()?);
+        assert!(coordinator.is_banned_participant(&contributor));
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_failed_verification_retry_then_success() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .contribution_retry_budget(2)
+        .into();
+        initialize_test_environment(&environment);
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_signing_key: SigningKey = "secret_key".to_string();
+
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        // Fail the verification twice: the contributor is kept in the round each time.
+        let mut seed: Seed = [0; SEED_LENGTH];
+        for remaining in [1, 0] {
+            rand::thread_rng().fill_bytes(&mut seed[..]);
+            coordinator.contribute(&contributor, &contributor_signing_key, &seed)?;
+            let task = coordinator.get_pending_verifications().keys().next().cloned().unwrap();
+
+            assert_eq!(
+                Some(remaining),
+                coordinator.handle_failed_verification(&contributor, &task)?
+            );
+            assert_eq!(1, coordinator.current_round_height()?);
+            assert!(coordinator.is_current_contributor(&contributor));
+            assert_eq!(
+                2 - remaining as u32,
+                coordinator.state.verification_failures(&contributor)
+            );
+        }
+
+        // The third attempt passes verification without the contributor being dropped,
+        // and restores the full retry budget.
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        coordinator.contribute(&contributor, &contributor_signing_key, &seed)?;
+        let task = coordinator.get_pending_verifications().keys().next().cloned().unwrap();
+        coordinator.default_verify(&task)?;
+
+        assert!(coordinator.is_finished_contributor(&contributor));
+        assert!(coordinator.dropped_participants().is_empty());
+        assert!(!coordinator.is_banned_participant(&contributor));
+        let finished = coordinator.state.finished_participants(1).unwrap();
+        assert_eq!(2, finished.contributors[&contributor].remaining_retries(0, 2));
+        assert_eq!(0, coordinator.state.verification_failures(&contributor));
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_strict_task_completion_advances_round() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .strict_task_completion(true)
+        .into();
+        initialize_test_environment(&environment);
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_signing_key: SigningKey = "secret_key".to_string();
+
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        // Complete every task of the bucket of the contributor.
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        coordinator.contribute(&contributor, &contributor_signing_key, &seed)?;
+        let task = coordinator.get_pending_verifications().keys().next().cloned().unwrap();
+        coordinator.default_verify(&task)?;
+
+        // Queue a contributor for the next round.
+        let next_contributor = Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone();
+        coordinator.state.add_to_queue(
+            next_contributor.clone(),
+            Some(IpAddr::V4("0.0.0.2".parse().unwrap())),
+            String::from("irrelevant_token"),
+            10,
+            coordinator.time.as_ref(),
+        )?;
+
+        // The strict check lets the contributor finish, and the round advances.
+        coordinator.update()?;
+        assert!(coordinator.dropped_participants().is_empty());
+        assert!(coordinator.state.is_finished_contributor_at_round(&contributor, 1));
+        assert_eq!(2, coordinator.current_round_height()?);
+        assert!(coordinator.is_current_contributor(&next_contributor));
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_file_hash_cache() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        // The challenge of round 1 chunk 0.
+        let contribution_locator = ContributionLocator::new(1, 0, 0, true);
+        let locator = Locator::ContributionFile(contribution_locator);
+        let challenge = coordinator.get_challenge(1, 0, 0, true)?;
+
+        // The first request computes the hash and caches it.
+        let hash = coordinator.get_file_hash(&locator)?;
+        assert_eq!(calculate_hash(&challenge).to_vec(), hash);
+        assert!(coordinator.hash_cache.read().unwrap().contains_key(&locator));
+
+        // Update the file bypassing the coordinator: the second request hits the cache.
+        let mut updated_challenge = challenge.clone();
+        updated_challenge[0] = updated_challenge[0].wrapping_add(1);
+        coordinator
+            .storage_mut()
+            .update(&locator, Object::ContributionFile(updated_challenge.clone()))?;
+        assert_eq!(hash, coordinator.get_file_hash(&locator)?);
+
+        // Update the file through the coordinator: the cache is invalidated.
+        coordinator.write_contribution(contribution_locator, challenge.clone())?;
+        assert!(!coordinator.hash_cache.read().unwrap().contains_key(&locator));
+        assert_eq!(hash, coordinator.get_file_hash(&locator)?);
+
+        // Round transitions clear the cache.
+        coordinator.reset_round()?;
+        assert!(coordinator.hash_cache.read().unwrap().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_duplicate_contributions() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        let round_height = 1;
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_signing_key: SigningKey = "secret_key".to_string();
+
+        // Contribute to the chunks 0 and 1.
+        for chunk_id in 0..2 {
+            let mut seed: Seed = [0; SEED_LENGTH];
+            rand::thread_rng().fill_bytes(&mut seed[..]);
+
+            coordinator.try_lock_chunk(chunk_id, &contributor)?;
+            coordinator.run_computation(
+                round_height,
+                chunk_id,
+                1,
+                &contributor,
+                &contributor_signing_key,
+                &seed,
+            )?;
+            coordinator.add_contribution(chunk_id, &contributor)?;
+        }
+        assert!(coordinator.detect_duplicate_contributions(round_height)?.is_empty());
+
+        // Submit the contribution to chunk 0 as the contribution to chunk 1.
+        let contribution = coordinator
+            .storage()
+            .reader(&Locator::ContributionFile(ContributionLocator::new(round_height, 0, 1, false)))?
+            .to_vec();
+        coordinator.write_contribution(ContributionLocator::new(round_height, 1, 1, false), contribution)?;
+        assert_eq!(
+            vec![(Task::new(0, 1), Task::new(1, 1))],
+            coordinator.detect_duplicate_contributions(round_height)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_free_disk_space() -> anyhow::Result<()> {
+        // Reports a fixed amount of available space.
+        struct MockDiskSpace(u64);
+
+        impl DiskSpace for MockDiskSpace {
+            fn available_space(&self, _path: &Path) -> std::io::Result<u64> {
+                Ok(self.0)
+            }
+        }
+
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .minimum_free_disk_space(1000)
+            .into();
+        initialize_test_environment(&environment);
+
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        let alerts = Arc::new(Mutex::new(Vec::new()));
+        let callback_alerts = alerts.clone();
+        coordinator.set_storage_alert_callback(Arc::new(move |alert| callback_alerts.lock().unwrap().push(alert)));
+
+        // Contributions are accepted as long as the threshold remains free.
+        coordinator.set_disk_space(Arc::new(MockDiskSpace(1500)));
+        coordinator.check_free_disk_space(500)?;
+        assert!(alerts.lock().unwrap().is_empty());
+
+        // Contributions which would eat into the threshold are rejected and the operators alerted.
+        assert!(matches!(
+            coordinator.check_free_disk_space(501),
+            Err(CoordinatorError::StorageSpaceInsufficient {
+                available: 1500,
+                required: 1501
+            })
+        ));
+        coordinator.set_disk_space(Arc::new(MockDiskSpace(999)));
+        assert!(matches!(
+            coordinator.check_free_disk_space(0),
+            Err(CoordinatorError::StorageSpaceInsufficient { .. })
+        ));
+        assert_eq!(2, alerts.lock().unwrap().len());
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_contribution_sanity() -> anyhow::Result<()> {
+        let testing = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .minimum_contribution_size(1, 1024);
+        let environment: Environment = testing.clone().into();
+        initialize_test_environment(&environment);
+
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+        let task = Task::new(0, 1);
+
+        // A random contribution is accepted without anomalies.
+        let mut contribution = vec![0u8; 2048];
+        rand::thread_rng().fill_bytes(&mut contribution);
+        let minimum_size = coordinator.environment.minimum_contribution_size(1);
+        let anomalies = contribution_anomalies(&contribution, minimum_size);
+        assert!(coordinator.validate_contribution_sanity(&task, anomalies)?.is_empty());
+
+        // Zeros appended to a short contribution are flagged.
+        contribution[512..].iter_mut().for_each(|byte| *byte = 0);
+        let anomalies = contribution_anomalies(&contribution, minimum_size);
+        let anomalies = coordinator.validate_contribution_sanity(&task, anomalies)?;
+        assert!(anomalies.contains(&ContributionAnomaly::LowEntropy));
+        assert!(anomalies
+            .iter()
+            .any(|anomaly| matches!(anomaly, ContributionAnomaly::TooSmall { minimum: 1024, .. })));
+
+        // The anomalies are rejected in strict mode.
+        let environment: Environment = testing.strict_contribution_sanity(true).into();
+        initialize_test_environment(&environment);
+
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+        assert!(matches!(
+            coordinator.validate_contribution_sanity(&task, contribution_anomalies(&contribution, minimum_size)),
+            Err(CoordinatorError::ContributionSanityCheckFailed)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_region_cap() -> anyhow::Result<()> {
+        // Classifies the addresses 0.0.0.x in the region "x % 2".
+        struct MockRegionClassifier;
+
+        impl RegionClassifier for MockRegionClassifier {
+            fn classify(&self, ip: &IpAddr) -> Option<String> {
+                match ip {
+                    IpAddr::V4(ip) => Some((ip.octets()[3] % 2).to_string()),
+                    IpAddr::V6(_) => None,
+                }
+            }
+        }
+
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .maximum_contributors_per_round(2)
+        .maximum_contributors_per_region(Some(1))
+        .into();
+        initialize_test_environment(&environment);
+
+        let time = Arc::new(MockTimeSource::new(OffsetDateTime::now_utc()));
+        let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Dummy), time.clone())?;
+        coordinator.set_region_classifier(Arc::new(MockRegionClassifier));
+        coordinator.state.initialize(5);
+
+        // Add three contributors of region "1" followed by one contributor of region "0".
+        for id in [1, 3, 5, 2] {
+            coordinator.add_to_queue(
+                Participant::Contributor(id.to_string()),
+                Some(IpAddr::V4(format!("0.0.0.{}", id).parse().unwrap())),
+                format!("test_token_{}", id),
+                10,
+            )?;
+            // Make sure the contributors join at different times.
+            time.update(|prev| prev + Duration::seconds(1));
+        }
+
+        // The regions are persisted with the state.
+        coordinator.state = serde_json::from_value(serde_json::to_value(&coordinator.state)?)?;
+
+        // Contributors of region "1" are spread across rounds, the contributor of region "0" fills the first round.
+        coordinator.state.update_queue()?;
+        let assigned_round = |id: u8| {
+            coordinator
+                .state
+                .queue_contributor_info(&Participant::Contributor(id.to_string()))
+                .unwrap()
+                .1
+        };
+        assert_eq!(Some(6), assigned_round(1));
+        assert_eq!(Some(7), assigned_round(3));
+        assert_eq!(Some(8), assigned_round(5));
+        assert_eq!(Some(6), assigned_round(2));
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_stale_contribution_after_reset() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_signing_key: SigningKey = "secret_key".to_string();
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        // Lock the chunk, then reset the round keeping the contributor in it.
+        let (chunk_id, _) = coordinator.try_lock(&contributor)?;
+        let epoch = coordinator.state.round_epoch();
+        let reset_action = coordinator.state.reset_current_round(false, &*coordinator.time)?;
+        coordinator.reset_round_storage(&reset_action)?;
+        assert_eq!(epoch + 1, coordinator.state.round_epoch());
+        assert!(coordinator.is_current_contributor(&contributor));
+
+        // The contribution for the invalidated lock is rejected without altering the round.
+        assert!(matches!(
+            coordinator.try_contribute(&contributor, chunk_id),
+            Err(CoordinatorError::StaleContribution)
+        ));
+        assert!(matches!(
+            coordinator.check_contribution_epoch(&contributor, chunk_id),
+            Err(CoordinatorError::StaleContribution)
+        ));
+        assert!(coordinator.is_current_contributor(&contributor));
+        assert!(coordinator.get_pending_verifications().is_empty());
+
+        // Locking the chunk again in the new epoch allows the contribution.
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        coordinator.contribute(&contributor, &contributor_signing_key, &seed)?;
+        assert!(coordinator.is_finished_contributor(&contributor));
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_contribution_reproducible() -> anyhow::Result<()> {
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_signing_key: SigningKey = "secret_key".to_string();
+
+        // Contributes to round 1 chunk 0 with a seed drawn from a random source seeded with the given
+        // bytes, and returns the hash of the response.
+        let contribute = |rng_seed: [u8; 32]| -> anyhow::Result<Vec<u8>> {
+            initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+            let mut coordinator = Coordinator::new_with_time_and_rng(
+                TEST_ENVIRONMENT_ANOMA.clone(),
+                Arc::new(Dummy),
+                Arc::new(SystemTimeSource::new()),
+                Arc::new(MockRngSource::new(rng_seed)),
+            )?;
+            initialize_coordinator_single_contributor(&mut coordinator)?;
+
+            let seed = coordinator.generate_seed();
+            coordinator.contribute(&contributor, &contributor_signing_key, &seed)?;
+
+            let response_locator = Locator::ContributionFile(ContributionLocator::new(1, 0, 1, false));
+            Ok(coordinator.get_file_hash(&response_locator)?)
+        };
+
+        // The same random source gives the same contribution.
+        assert_eq!(contribute([7; 32])?, contribute([7; 32])?);
+        assert_ne!(contribute([7; 32])?, contribute([8; 32])?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_transparency_info() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_signing_key: SigningKey = "secret_key".to_string();
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        coordinator.contribute(&contributor, &contributor_signing_key, &seed)?;
+
+        let info = coordinator.transparency_info()?;
+        assert_eq!(coordinator.current_round_height()?, info.current_round_height);
+        assert_eq!(coordinator.state.get_current_cohort_index(), info.current_cohort);
+        assert_eq!(1, info.finished_contributors);
+        assert_eq!(
+            coordinator.state.number_of_finished_contributors(),
+            info.finished_contributors
+        );
+
+        let summary = match coordinator.storage.get(&Locator::ContributionsInfoSummary)? {
+            Object::ContributionsInfoSummary(summary) => summary,
+            _ => panic!("Unexpected object type for the contributions summary"),
+        };
+        assert_eq!(summary.len(), info.total_contributions);
+
+        let round_state = coordinator.storage.reader(&Locator::RoundState {
+            round_height: info.current_round_height,
+        })?;
+        assert_eq!(
+            hex::encode(calculate_hash(&round_state)),
+            info.latest_round_manifest_hash
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_participant_contributions_info() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        let contributor = Participant::new_contributor("contributor-public-key");
+        let other_contributor = Participant::new_contributor("other-contributor-public-key");
+
+        for (participant, round) in &[(&contributor, 1), (&other_contributor, 2), (&contributor, 3)] {
+            let mut info = ContributionInfo::default();
+            info.public_key = participant.address();
+            info.ceremony_round = *round;
+            coordinator.update_contribution_summary(info.into())?;
+        }
+
+        let contributions = coordinator.participant_contributions_info(&contributor)?;
+        assert_eq!(2, contributions.len());
+        assert!(contributions
+            .iter()
+            .all(|info| info.public_key() == contributor.address()));
+        assert_eq!(
+            vec![1, 3],
+            contributions
+                .iter()
+                .map(|info| info.ceremony_round())
+                .collect::<Vec<_>>()
+        );
+
+        let contributions = coordinator.participant_contributions_info(&other_contributor)?;
+        assert_eq!(1, contributions.len());
+        assert_eq!(2, contributions[0].ceremony_round());
+
+        let unknown = Participant::new_contributor("unknown-public-key");
+        assert!(coordinator.participant_contributions_info(&unknown)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_round_contributions_info() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+        assert_eq!(1, coordinator.current_round_height()?);
+
+        for round in 0..2 {
+            let mut info = ContributionInfo::default();
+            info.public_key = format!("contributor-{}", round);
+            info.ceremony_round = round;
+            coordinator.update_contribution_summary(info.into())?;
+        }
+
+        // Only the info of the finished rounds is returned.
+        let contributions = coordinator.round_contributions_info(0)?;
+        assert_eq!(1, contributions.len());
+        assert_eq!("contributor-0", contributions[0].public_key());
+        assert!(matches!(
+            coordinator.round_contributions_info(1),
+            Err(CoordinatorError::RoundNotComplete)
+        ));
+        assert!(matches!(
+            coordinator.round_contributions_info(2),
+            Err(CoordinatorError::RoundDoesNotExist)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_completion_when_cohorts_are_over() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+        assert!(coordinator.ceremony_completion()?.is_none());
+
+        // Exhaust the cohorts.
+        coordinator.update_tokens(vec![]);
+        assert!(matches!(coordinator.update(), Err(CoordinatorError::CeremonyIsOver)));
+
+        // The completion artifact is written to storage.
+        let completion = coordinator.ceremony_completion()?.expect("Missing ceremony completion");
+        let current_round_height = coordinator.current_round_height()?;
+        assert_eq!(current_round_height, completion.total_rounds);
+        assert_eq!(
+            coordinator.state.number_of_finished_contributors(),
+            completion.total_contributors
+        );
+
+        // The hash is the one of the manifest of the exported transcript.
+        let mut transcript = std::io::Cursor::new(Vec::new());
+        coordinator.export_transcript(&mut transcript)?;
+        let mut archive = zip::ZipArchive::new(transcript)?;
+        let mut manifest = Vec::new();
+        archive.by_name(TRANSCRIPT_MANIFEST_FILE)?.read_to_end(&mut manifest)?;
+        assert_eq!(hex::encode(Sha256::digest(&manifest)), completion.final_manifest_hash);
+
+        // A restarted coordinator refuses to initialize.
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        assert!(matches!(
+            coordinator.initialize(),
+            Err(CoordinatorError::CeremonyIsOver)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_status_events() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        let mut events = coordinator.subscribe_status();
+
+        // Advancing the round publishes the status.
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+        let expected = StatusEvent {
+            round_height: 1,
+            queue_length: 0,
+            active_contributors: 1,
+        };
+        assert_eq!(expected, events.try_recv()?);
+        assert!(matches!(events.try_recv(), Err(TryRecvError::Empty)));
+
+        // A lagging subscriber misses the oldest events only.
+        for _ in 0..STATUS_EVENTS_CAPACITY + 2 {
+            coordinator.publish_status();
+        }
+        assert!(matches!(events.try_recv(), Err(TryRecvError::Lagged(2))));
+        for _ in 0..STATUS_EVENTS_CAPACITY {
+            assert_eq!(expected, events.try_recv()?);
+        }
+        assert!(matches!(events.try_recv(), Err(TryRecvError::Empty)));
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_read_only_after_storage_failures() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        let alerts = Arc::new(Mutex::new(Vec::new()));
+        let callback_alerts = alerts.clone();
+        coordinator.set_storage_alert_callback(Arc::new(move |alert| callback_alerts.lock().unwrap().push(alert)));
+
+        // Make the coordinator state file unwritable by replacing it with a directory.
+        let path = coordinator.storage.to_path(&Locator::CoordinatorState)?;
+        fs_err::remove_file(&path)?;
+        fs_err::create_dir(&path)?;
+
+        // The coordinator tolerates failures up to the threshold.
+        let threshold = TEST_ENVIRONMENT_ANOMA.storage_failure_threshold();
+        assert!(threshold > 1);
+        for _ in 1..threshold {
+            assert!(coordinator.save_state().is_err());
+            assert!(!coordinator.is_read_only());
+        }
+        assert!(alerts.lock().unwrap().is_empty());
+
+        // The last failure engages the read-only mode and raises an alert.
+        assert!(matches!(
+            coordinator.save_state(),
+            Err(CoordinatorError::StorageUpdateFailed)
+        ));
+        assert!(coordinator.is_read_only());
+        assert_eq!(1, alerts.lock().unwrap().len());
+
+        // State changes are rejected, the heartbeats keeping the participants alive are still accepted.
+        coordinator.heartbeat(&contributor)?;
+        assert!(matches!(
+            coordinator.update(),
+            Err(CoordinatorError::StorageUpdateFailed)
+        ));
+
+        // Reads are still served.
+        assert_eq!(1, coordinator.current_round_height()?);
+        assert!(coordinator.is_current_contributor(&contributor));
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_join_signature_required() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .queue_join_signature_required(true)
+        .into();
+        initialize_test_environment(&environment);
+
+        let coordinator = Coordinator::new(environment, Arc::new(Production))?;
+
+        let keypair = KeyPair::new();
+        let other_keypair = KeyPair::new();
+        let participant = Participant::new_contributor(keypair.pubkey());
+        let token = "test_token";
+
+        // Signed with the key of the participant
+        let signature = Production.sign(keypair.sigkey(), token)?;
+        coordinator.check_join_signature(&participant, token, Some(&signature))?;
+
+        // Unsigned
+        assert!(matches!(
+            coordinator.check_join_signature(&participant, token, None),
+            Err(CoordinatorError::ContributorSignatureInvalid)
+        ));
+
+        // Signed with another key
+        let signature = Production.sign(other_keypair.sigkey(), token)?;
+        assert!(matches!(
+            coordinator.check_join_signature(&participant, token, Some(&signature)),
+            Err(CoordinatorError::ContributorSignatureInvalid)
+        ));
+
+        // Malformed signature
+        assert!(matches!(
+            coordinator.check_join_signature(&participant, token, Some("not_a_signature")),
+            Err(CoordinatorError::ContributorSignatureInvalid)
+        ));
+
+        // The signature is not required by default
+        let coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Production))?;
+        coordinator.check_join_signature(&participant, token, None)?;
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_encrypted_contributions() -> anyhow::Result<()> {
+        // Removes the key on drop, so that it does not leak to the other tests if this one fails.
+        struct StorageKey;
+        impl Drop for StorageKey {
+            fn drop(&mut self) {
+                std::env::remove_var(STORAGE_KEY_ENV);
+            }
+        }
+        std::env::set_var(STORAGE_KEY_ENV, hex::encode([7u8; 32]));
+        let _storage_key = StorageKey;
+
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .encrypt_contributions(true)
+        .into();
+        initialize_test_environment(&environment);
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID);
+        let contributor_signing_key: SigningKey = "secret_key".to_string();
+
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+        let round_height = coordinator.current_round_height()?;
+
+        // The challenge is encrypted on disk and transparently decrypted on read.
+        let challenge_locator = Locator::ContributionFile(ContributionLocator::new(round_height, 0, 0, true));
+        let challenge = match coordinator.storage.get(&challenge_locator)? {
+            Object::ContributionFile(challenge) => challenge,
+            _ => panic!("Unexpected object"),
+        };
+        let encrypted = std::fs::read(coordinator.storage.to_path(&challenge_locator)?)?;
+        assert_eq!(challenge.len() as u64 + StorageCipher::OVERHEAD, encrypted.len() as u64);
+        assert_eq!(challenge.len() as u64, coordinator.storage.size(&challenge_locator)?);
+        assert_eq!(&challenge[..], &coordinator.storage.reader(&challenge_locator)?[..]);
+
+        // An encrypted-then-read object matches the original.
+        coordinator
+            .storage
+            .update(&challenge_locator, Object::ContributionFile(challenge.clone()))?;
+        assert_ne!(
+            encrypted,
+            std::fs::read(coordinator.storage.to_path(&challenge_locator)?)?
+        );
+        assert_eq!(&challenge[..], &coordinator.storage.reader(&challenge_locator)?[..]);
+
+        // Contribute and verify round 1 chunk 0 contribution 1.
+        let chunk_id = 0;
+        let contribution_id = 1;
+        coordinator.try_lock_chunk(chunk_id, &contributor)?;
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        coordinator.run_computation(
+            round_height,
+            chunk_id,
+            contribution_id,
+            contributor,
+            &contributor_signing_key,
+            &seed,
+        )?;
+        coordinator.add_contribution(chunk_id, &contributor)?;
+
+        let verifier = Lazy::force(&TEST_VERIFIER_ID).clone();
+        let verifier_signing_key: SigningKey = "secret_key".to_string();
+        let task = Task::new(chunk_id, contribution_id);
+        coordinator.run_verification(round_height, &task, &verifier, &verifier_signing_key)?;
+        coordinator.verify_contribution(&task, &verifier)?;
+
+        // The encrypted files are written through a temporary file renamed over them.
+        let challenge_path = coordinator.storage.to_path(&challenge_locator)?;
+        assert!(!std::path::Path::new(&format!("{}.tmp", challenge_path)).exists());
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_round_merkle_root() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        let contributors: Vec<Participant> = (0..3)
+            .map(|i| Participant::new_contributor(&format!("contributor-public-key-{}", i)))
+            .collect();
+        let other_contributor = Participant::new_contributor("other-contributor-public-key");
+
+        for (participant, round) in contributors.iter().map(|p| (p, 1)).chain(Some((&other_contributor, 2))) {
+            let mut info = ContributionInfo::default();
+            info.public_key = participant.address();
+            info.ceremony_round = round;
+            info.contribution_file_hash = format!("hash-of-{}", participant.address());
+            coordinator.update_contribution_summary(info.into())?;
+        }
+
+        // The proof of every contributor of the round verifies against the root.
+        let root = coordinator.round_merkle_root(1)?;
+        for contributor in &contributors {
+            let proof = coordinator.round_merkle_proof(1, contributor)?;
+            assert_eq!(
+                leaf_hash(&contributor.address(), &format!("hash-of-{}", contributor.address())),
+                proof.leaf
+            );
+            assert!(proof.verify(&root));
+
+            // A proof tampered with doesn't verify.
+            let mut tampered = proof.clone();
+            tampered.leaf = leaf_hash(&contributor.address(), "another-hash");
+            assert!(!tampered.verify(&root));
+        }
+
+        // Contributors of other rounds have no proof for the round.
+        assert!(matches!(
+            coordinator.round_merkle_proof(1, &other_contributor),
+            Err(CoordinatorError::ParticipantNotFound(_))
+        ));
+        let other_proof = coordinator.round_merkle_proof(2, &other_contributor)?;
+        assert!(other_proof.verify(&coordinator.round_merkle_root(2)?));
+        assert!(!other_proof.verify(&root));
+
+        // Rounds without contributions have no root.
+        assert!(matches!(
+            coordinator.round_merkle_root(3),
+            Err(CoordinatorError::RoundDoesNotExist)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_audit_log_drops() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("audit.jsonl");
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        coordinator.enable_audit_log(&path)?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        // A failed drop is not recorded.
+        let unknown = Participant::new_contributor("unknown");
+        assert!(coordinator.drop_participant(&unknown).is_err());
+
+        // A ban is only recorded as such.
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        coordinator.ban_participant(&contributor)?;
+
+        let events: Vec<AuditEvent> = std::fs::read_to_string(&path)?
+            .lines()
+            .map(|line| serde_json::from_str::<AuditEntry>(line).map(|entry| entry.event))
+            .collect::<Result<_, _>>()?;
+        assert!(matches!(events.first(), Some(AuditEvent::KeyRotation { .. })));
+        assert!(!events.iter().any(|event| matches!(event, AuditEvent::Drop { .. })));
+        assert_eq!(
+            vec![&AuditEvent::Ban {
+                participant: contributor
+            }],
+            events
+                .iter()
+                .filter(|event| matches!(event, AuditEvent::Ban { .. }))
+                .collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_memory_storage() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .into();
+        initialize_test_environment(&environment);
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID);
+        let contributor_signing_key: SigningKey = "secret_key".to_string();
+        let verifier = Lazy::force(&TEST_VERIFIER_ID).clone();
+        let verifier_signing_key: SigningKey = "secret_key".to_string();
+
+        let storage = Box::new(MemoryStorage::load(&environment)?);
+        let mut coordinator = Coordinator::new_with_storage(environment, storage, Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+        let round_height = coordinator.current_round_height()?;
+
+        // Contribute and verify round 1 chunk 0 contribution 1, completing the round.
+        let chunk_id = 0;
+        let contribution_id = 1;
+        coordinator.try_lock_chunk(chunk_id, &contributor)?;
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        coordinator.run_computation(
+            round_height,
+            chunk_id,
+            contribution_id,
+            contributor,
+            &contributor_signing_key,
+            &seed,
+        )?;
+        coordinator.add_contribution(chunk_id, &contributor)?;
+        let task = Task::new(chunk_id, contribution_id);
+        coordinator.run_verification(round_height, &task, &verifier, &verifier_signing_key)?;
+        coordinator.verify_contribution(&task, &verifier)?;
+        assert!(coordinator.current_round()?.is_complete());
+
+        // The contributions are kept in memory only.
+        let locator =
+            Locator::ContributionFile(ContributionLocator::new(round_height, chunk_id, contribution_id, true));
+        assert!(coordinator.storage().exists(&locator));
+        assert!(!coordinator.storage().to_path(&locator)?.as_path().exists());
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_export_transcript() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .into();
+        initialize_test_environment(&environment);
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID);
+        let contributor_signing_key: SigningKey = "secret_key".to_string();
+        let verifier = Lazy::force(&TEST_VERIFIER_ID).clone();
+        let verifier_signing_key: SigningKey = "secret_key".to_string();
+
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+        let round_height = coordinator.current_round_height()?;
+
+        // Contribute and verify round 1 chunk 0 contribution 1, completing the round.
+        let chunk_id = 0;
+        let contribution_id = 1;
+        coordinator.try_lock_chunk(chunk_id, &contributor)?;
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        coordinator.run_computation(
+            round_height,
+            chunk_id,
+            contribution_id,
+            contributor,
+            &contributor_signing_key,
+            &seed,
+        )?;
+        coordinator.add_contribution(chunk_id, &contributor)?;
+        let task = Task::new(chunk_id, contribution_id);
+        coordinator.run_verification(round_height, &task, &verifier, &verifier_signing_key)?;
+        coordinator.verify_contribution(&task, &verifier)?;
+
+        let mut info = ContributionInfo::default();
+        info.public_key = contributor.address();
+        info.ceremony_round = round_height;
+        coordinator.write_contribution_info(info)?;
+
+        let mut transcript = std::io::Cursor::new(Vec::new());
+        coordinator.export_transcript(&mut transcript)?;
+
+        // The manifest lists the completed rounds and the hash of every other file of the archive.
+        let mut archive = zip::ZipArchive::new(transcript)?;
+        let manifest: TranscriptManifest = serde_json::from_reader(archive.by_name(TRANSCRIPT_MANIFEST_FILE)?)?;
+        assert_eq!(vec![0, 1], manifest.rounds);
+        assert_eq!(manifest.files.len() + 1, archive.len());
+        for file in &manifest.files {
+            let mut data = Vec::new();
+            archive.by_name(&file.path)?.read_to_end(&mut data)?;
+            assert_eq!(file.size, data.len() as u64);
+            assert_eq!(file.sha256, hex::encode(Sha256::digest(&data)));
+        }
+
+        let paths: Vec<&str> = manifest.files.iter().map(|file| file.path.as_str()).collect();
+        for path in [
+            "round_0/chunk_0/contribution_0.verified",
+            "round_1/chunk_0/contribution_1.verified",
+            "round_1/chunk_0/contribution_1.unverified.signature",
+            "contributors/namada_contributor_info_round_1.json",
+        ] {
+            assert!(paths.contains(&path), "Missing {} in {:?}", path, paths);
+        }
+
+        // The same transcript is exported from the storage alone, without the token files.
+        let environment = coordinator.environment().clone();
+        let storage = Disk::load(&environment)?;
+        let mut storage_transcript = std::io::Cursor::new(Vec::new());
+        Coordinator::export_storage_transcript(&environment, &storage, &mut storage_transcript)?;
+        let mut storage_archive = zip::ZipArchive::new(storage_transcript)?;
+        let storage_manifest: TranscriptManifest =
+            serde_json::from_reader(storage_archive.by_name(TRANSCRIPT_MANIFEST_FILE)?)?;
+        assert_eq!(manifest, storage_manifest);
+
+        Ok(())
+    }
+
+    #[test]
+    fn coordinator_error_messages() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tracing_subscriber::{layer::Context, prelude::*, Layer};
+
+        /// Counts the events emitted while the subscriber is set.
+        #[derive(Clone, Default)]
+        struct EventCounter(Arc<AtomicUsize>);
+
+        impl<S: tracing::Subscriber> Layer<S> for EventCounter {
+            fn on_event(&self, _event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let counter = EventCounter::default();
+        let subscriber = tracing_subscriber::registry().with(counter.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            let participant = Participant::new_contributor("contributor-public-key");
+
+            assert_eq!(
+                "The round does not exist",
+                CoordinatorError::RoundDoesNotExist.to_string()
+            );
+            assert_eq!(
+                "Chunk 3 cannot be locked without contributions",
+                CoordinatorError::ChunkCannotLockZeroContributions { chunk_id: 3 }.to_string()
+            );
+            assert_eq!(
+                format!("The participant {} was not found", participant),
+                CoordinatorError::ParticipantNotFound(participant).to_string()
+            );
+            let pending_task = Task::new(1, 2);
+            assert_eq!(
+                format!("The participant is missing the pending task {}", pending_task),
+                CoordinatorError::ParticipantMissingPendingTask { pending_task }.to_string()
+            );
+
+            // Wrapped errors keep their own message, also when converted back.
+            let error = CoordinatorError::Error(anyhow::anyhow!("Something went wrong"));
+            assert_eq!("Something went wrong", error.to_string());
+            assert_eq!("Something went wrong", anyhow::Error::from(error).to_string());
+            assert_eq!(
+                "The queue is empty",
+                anyhow::Error::from(CoordinatorError::QueueIsEmpty).to_string()
+            );
+        });
+
+        // Formatting the errors doesn't log anything.
+        assert_eq!(0, counter.0.load(Ordering::SeqCst));
+    }
 }
@@ -1,21 +1,24 @@
 use crate::{
-    environment::Environment,
+    environment::{Environment, QueuePriority, ReplacementSelection},
     objects::{
         participant::*,
-        task::{initialize_tasks, Task},
+        task::{check_contribution_id, expected_tasks_for_bucket, BucketLayout, Task},
     },
-    storage::{Disk, Locator, Object},
-    CoordinatorError, TimeSource,
+    storage::{Locator, Object, Storage},
+    CoordinatorError, FirstVerifierSelector, RngSource, SystemRngSource, TimeSource, VerifierSelector,
 };
 use anyhow::anyhow;
 use lazy_static::lazy_static;
 
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaChaRng;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{HashMap, HashSet, LinkedList},
+    collections::{BTreeMap, HashMap, HashSet, LinkedList},
     iter::FromIterator,
     net::IpAddr,
+    sync::Arc,
 };
 use time::{Duration, OffsetDateTime};
 use tracing::*;
@@ -30,10 +33,27 @@ lazy_static! {
         Ok(s) if s == "true" => true,
         _ => false,
     };
+    /// Fails the aggregation of a round in which the same contribution was submitted more than once.
+    pub(crate) static ref REJECT_DUPLICATES: bool = match std::env::var("NAMADA_REJECT_DUPLICATES") {
+        Ok(s) if s == "true" => true,
+        _ => false,
+    };
+    /// Persists the tokens and the ip addresses in use to a sidecar file, to survive an unclean restart.
+    static ref PERSIST_RUNTIME: bool = match std::env::var("NAMADA_PERSIST_RUNTIME") {
+        Ok(s) if s == "true" => true,
+        _ => false,
+    };
 }
 
+/// The number of characters of a token kept when redacting it.
+const REDACTED_TOKEN_PREFIX_LENGTH: usize = 6;
+/// The prefix of the tokens of the private cohorts.
+pub const PRIVATE_TOKEN_PREFIX: &str = "put_";
+/// The name of the sidecar file persisting the tokens and the ip addresses in use, in the storage of each ceremony.
+const RUNTIME_SNAPSHOT_FILE: &str = "runtime.json";
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub(super) enum CoordinatorStatus {
+pub enum CoordinatorStatus {
     Initializing,
     Initialized,
     Precommit,
@@ -50,15 +70,20 @@ pub struct ChunkLock {
     chunk_id: u64,
     /// The time that the chunk was locked.
     lock_time: OffsetDateTime,
+    /// The round epoch in which the chunk was locked.
+    #[serde(default)]
+    epoch: u64,
 }
 
 impl ChunkLock {
-    /// Create a new chunk lock for the specified `chunk_id`, and
-    /// recording the `lock_time` using the specified `time` source.
-    pub fn new(chunk_id: u64, time: &dyn TimeSource) -> Self {
+    /// Create a new chunk lock for the specified `chunk_id` in the
+    /// specified round `epoch`, and recording the `lock_time` using
+    /// the specified `time` source.
+    pub fn new(chunk_id: u64, epoch: u64, time: &dyn TimeSource) -> Self {
         Self {
             chunk_id,
             lock_time: time.now_utc(),
+            epoch,
         }
     }
 
@@ -71,6 +96,11 @@ impl ChunkLock {
     pub fn lock_time(&self) -> &OffsetDateTime {
         &self.lock_time
     }
+
+    /// The round epoch in which the chunk was locked.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -105,6 +135,12 @@ pub struct ParticipantInfo {
     disposing_tasks: LinkedList<Task>,
     /// The list of (chunk ID, contribution ID) tasks that are disposed of while computing.
     disposed_tasks: LinkedList<Task>,
+    /// A map of chunk IDs to the remaining retries after a failed verification.
+    #[serde(default)]
+    retry_budget: HashMap<u64, u8>,
+    /// A map of chunk IDs to the epoch of locks invalidated by a round reset.
+    #[serde(default)]
+    invalidated_locks: HashMap<u64, u64>,
 }
 
 impl PartialEq for ParticipantInfo {
@@ -113,6 +149,115 @@ impl PartialEq for ParticipantInfo {
     }
 }
 
+/// The tasks of a participant recomputed after another participant is dropped.
+#[derive(Debug, Clone)]
+struct TaskReassignment {
+    assigned_tasks: LinkedList<Task>,
+    pending_tasks: LinkedList<Task>,
+    completed_tasks: LinkedList<Task>,
+    disposing_tasks: LinkedList<Task>,
+    disposed_tasks: LinkedList<Task>,
+}
+
+///
+/// Removes the given task from the list of tasks in place, preserving
+/// the order of the remaining tasks.
+///
+fn remove_task(tasks: &mut LinkedList<Task>, task: &Task) {
+    while let Some(index) = tasks.iter().position(|t| t == task) {
+        let mut tail = tasks.split_off(index);
+        tail.pop_front();
+        tasks.append(&mut tail);
+    }
+}
+
+///
+/// Removes the duplicates from the given list of tasks in place, keeping
+/// the first occurrence of each task.
+///
+/// Returns the number of removed tasks.
+///
+fn dedup_tasks(tasks: &mut LinkedList<Task>) -> usize {
+    let mut seen = HashSet::new();
+    let length = tasks.len();
+    *tasks = std::mem::take(tasks)
+        .into_iter()
+        .filter(|task| seen.insert(*task))
+        .collect();
+    length - tasks.len()
+}
+
+///
+/// Returns the index, starting from 0, of the cohort active after the given
+/// number of seconds since the start of the ceremony.
+///
+/// The last of the given cohort durations applies to all the following cohorts.
+///
+fn cohort_index(cohort_durations: &[u64], elapsed: u64) -> usize {
+    let mut cohort_end = 0;
+    for (index, duration) in cohort_durations.iter().enumerate() {
+        cohort_end += duration;
+        if elapsed < cohort_end {
+            return index;
+        }
+    }
+
+    match cohort_durations.last() {
+        Some(&last_duration) => cohort_durations.len() + ((elapsed - cohort_end) / last_duration) as usize,
+        None => 0,
+    }
+}
+
+///
+/// Parses the given comma separated list of cohort durations in seconds.
+///
+/// The durations must be positive, as [cohort_index] divides by the last one.
+///
+fn parse_cohort_durations(durations: &str) -> Result<Vec<u64>, CoordinatorError> {
+    let invalid = || CoordinatorError::CohortDurationsInvalid(durations.to_string());
+    let parsed = durations
+        .split(',')
+        .map(|n| n.trim().parse::<u64>())
+        .collect::<Result<Vec<u64>, _>>()
+        .map_err(|_| invalid())?;
+
+    match parsed.contains(&0) {
+        true => Err(invalid()),
+        false => Ok(parsed),
+    }
+}
+
+///
+/// Deserializes the cohort durations, accepting the single cohort duration
+/// of the states saved before the durations could be set per cohort.
+///
+fn deserialize_cohort_durations<'de, D>(deserializer: D) -> Result<Vec<u64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum CohortDurations {
+        Single(u64),
+        PerCohort(Vec<u64>),
+    }
+
+    // The binary formats can't tell the single duration apart, and never contain one.
+    let durations = match deserializer.is_human_readable() {
+        true => match CohortDurations::deserialize(deserializer)? {
+            CohortDurations::Single(duration) => vec![duration],
+            CohortDurations::PerCohort(durations) => durations,
+        },
+        false => Vec::deserialize(deserializer)?,
+    };
+
+    if durations.is_empty() || durations.contains(&0) {
+        return Err(serde::de::Error::custom("the cohort durations must be positive"));
+    }
+
+    Ok(durations)
+}
+
 impl ParticipantInfo {
     #[inline]
     fn new(
@@ -140,6 +285,8 @@ impl ParticipantInfo {
             completed_tasks: LinkedList::new(),
             disposing_tasks: LinkedList::new(),
             disposed_tasks: LinkedList::new(),
+            retry_budget: HashMap::new(),
+            invalidated_locks: HashMap::new(),
         }
     }
 
@@ -192,6 +339,14 @@ impl ParticipantInfo {
         &self.disposed_tasks
     }
 
+    ///
+    /// Returns the number of retries left to this participant for the given chunk,
+    /// starting from the given `budget`.
+    ///
+    pub fn remaining_retries(&self, chunk_id: u64, budget: u8) -> u8 {
+        self.retry_budget.get(&chunk_id).copied().unwrap_or(budget)
+    }
+
     ///
     /// Returns `true` if the participant is dropped from the current round.
     ///
@@ -259,6 +414,20 @@ impl ParticipantInfo {
         self.disposed_tasks = Default::default();
     }
 
+    ///
+    /// Removes the duplicate tasks from the task lists of this participant,
+    /// which may be present in state files persisted by older versions.
+    ///
+    /// Returns the number of removed tasks.
+    ///
+    fn dedup_tasks(&mut self) -> usize {
+        dedup_tasks(&mut self.pending_tasks)
+            + dedup_tasks(&mut self.assigned_tasks)
+            + dedup_tasks(&mut self.completed_tasks)
+            + dedup_tasks(&mut self.disposing_tasks)
+            + dedup_tasks(&mut self.disposed_tasks)
+    }
+
     /// Clear the locked chunks.
     fn clear_locks(&mut self) {
         self.locked_chunks = HashMap::new();
@@ -272,9 +441,41 @@ impl ParticipantInfo {
         self.finished_at = None;
     }
 
+    ///
+    /// Consumes one retry for the given chunk after a failed verification.
+    ///
+    /// Returns the number of retries left, or `None` if the budget was already exhausted.
+    ///
+    fn consume_retry(&mut self, chunk_id: u64, budget: u8) -> Option<u8> {
+        let remaining = self.remaining_retries(chunk_id, budget).checked_sub(1)?;
+        self.retry_budget.insert(chunk_id, remaining);
+        Some(remaining)
+    }
+
+    /// Restores the full retry budget for the given chunk.
+    fn reset_retries(&mut self, chunk_id: u64) {
+        self.retry_budget.remove(&chunk_id);
+    }
+
+    ///
+    /// Returns the round epoch of the lock held on the given chunk ID,
+    /// or of the last lock on it invalidated by a round reset.
+    ///
+    fn lock_epoch(&self, chunk_id: u64) -> Option<u64> {
+        self.locked_chunks
+            .get(&chunk_id)
+            .map(|lock| lock.epoch())
+            .or_else(|| self.invalidated_locks.get(&chunk_id).copied())
+    }
+
     /// Clear tasks, locks and round times, and start this contributor
     /// again, assigning it new tasks.
     fn restart_tasks(&mut self, tasks: LinkedList<Task>, time: &dyn TimeSource) -> Result<(), CoordinatorError> {
+        // Remember the epoch of the locks being dropped, to reject stale contributions for them.
+        for (chunk_id, lock) in &self.locked_chunks {
+            self.invalidated_locks.insert(*chunk_id, lock.epoch());
+        }
+
         self.clear_tasks();
         self.clear_locks();
         self.clear_round_times();
@@ -328,6 +529,11 @@ impl ParticipantInfo {
             return Err(CoordinatorError::ParticipantAlreadyStarted);
         }
 
+        // Check that none of the given tasks targets the reserved contribution ID.
+        for task in &tasks {
+            check_contribution_id(task.contribution_id())?;
+        }
+
         // Fetch the current time.
         let now = time.now_utc();
 
@@ -343,6 +549,133 @@ impl ParticipantInfo {
         Ok(())
     }
 
+    ///
+    /// Replaces the assigned tasks of the participant with the given permutation of them,
+    /// changing the order in which they are popped.
+    ///
+    fn reorder_assigned_tasks(&mut self, tasks: Vec<Task>, time: &dyn TimeSource) -> Result<(), CoordinatorError> {
+        trace!("Reordering assigned tasks for {}", self.id);
+
+        // Check that the participant has started in the round.
+        if self.started_at.is_none() {
+            return Err(CoordinatorError::ParticipantHasNotStarted);
+        }
+
+        // Check that the participant was not dropped from the round.
+        if self.dropped_at.is_some() {
+            return Err(CoordinatorError::ParticipantWasDropped);
+        }
+
+        // Check that the participant has not finished the round.
+        if self.finished_at.is_some() {
+            return Err(CoordinatorError::ParticipantAlreadyFinished);
+        }
+
+        // Check that the given tasks are a permutation of the assigned tasks.
+        // Assigned tasks are unique, so equal lengths and sets imply a permutation.
+        let assigned: HashSet<&Task> = self.assigned_tasks.iter().collect();
+        let reordered: HashSet<&Task> = tasks.iter().collect();
+        if tasks.len() != self.assigned_tasks.len() || assigned != reordered {
+            return Err(CoordinatorError::ParticipantTasksReorderInvalid);
+        }
+
+        // Update the last seen time.
+        self.last_seen = time.now_utc();
+
+        self.assigned_tasks = tasks.into_iter().collect();
+
+        Ok(())
+    }
+
+    ///
+    /// Computes the tasks of this contributor after the contributor holding the given
+    /// (chunk ID, contribution ID) tasks is dropped, without modifying the participant.
+    ///
+    /// Only the tasks from the first chunk overlapping with the dropped tasks onwards
+    /// are reinitialized. Returns `None` if no task of this contributor overlaps with
+    /// the dropped tasks, in which case the tasks of this contributor are unaffected.
+    ///
+    fn reassign_dropped_tasks(
+        &self,
+        tasks_by_chunk: &HashMap<u64, u64>,
+        bucket_layout: BucketLayout,
+        number_of_chunks: u64,
+        number_of_contributors: u64,
+    ) -> Result<Option<TaskReassignment>, CoordinatorError> {
+        // If the pending task is in the same chunk with the dropped task
+        // then it should be recomputed
+        let (disposing_tasks, pending_tasks): (LinkedList<Task>, LinkedList<Task>) = self
+            .pending_tasks
+            .iter()
+            .cloned()
+            .partition(|task| tasks_by_chunk.get(&task.chunk_id()).is_some());
+
+        // If completed task is based on the dropped task, it should also be dropped
+        let (disposed_tasks, completed_tasks): (LinkedList<Task>, LinkedList<Task>) =
+            self.completed_tasks.iter().cloned().partition(|task| {
+                if let Some(contribution_id) = tasks_by_chunk.get(&task.chunk_id()) {
+                    *contribution_id < task.contribution_id()
+                } else {
+                    false
+                }
+            });
+
+        // Skip the contributor if none of its tasks overlaps with the dropped tasks.
+        if disposing_tasks.is_empty() && disposed_tasks.is_empty() {
+            return Ok(None);
+        }
+
+        // Determine the overlapping chunks, which must be reassigned.
+        let overlapping_chunks: HashSet<u64> = disposing_tasks
+            .iter()
+            .chain(disposed_tasks.iter())
+            .map(|task| task.chunk_id())
+            .collect();
+
+        // Determine the excluded tasks, which are filtered out from the list of newly assigned tasks.
+        let mut excluded_tasks: HashSet<u64> = HashSet::from_iter(completed_tasks.iter().map(|task| task.chunk_id()));
+        excluded_tasks.extend(pending_tasks.iter().map(|task| task.chunk_id()));
+
+        // Reinitialize the tasks of the affected contributor from the first overlapping chunk.
+        let reinitialized_tasks: LinkedList<Task> = bucket_layout
+            .initialize_tasks(self.bucket_id, number_of_chunks, number_of_contributors)?
+            .into_iter()
+            .skip_while(|task| !overlapping_chunks.contains(&task.chunk_id()))
+            .filter(|task| !excluded_tasks.contains(&task.chunk_id()))
+            .collect();
+        let reinitialized_chunks: HashSet<u64> = reinitialized_tasks.iter().map(|task| task.chunk_id()).collect();
+
+        // Keep the assigned tasks preceding the first overlapping chunk as they are.
+        let assigned_tasks = self
+            .assigned_tasks
+            .iter()
+            .filter(|task| !reinitialized_chunks.contains(&task.chunk_id()))
+            .cloned()
+            .chain(reinitialized_tasks)
+            .collect();
+
+        Ok(Some(TaskReassignment {
+            assigned_tasks,
+            pending_tasks,
+            completed_tasks,
+            disposing_tasks,
+            disposed_tasks,
+        }))
+    }
+
+    ///
+    /// Applies a reassignment computed by [ParticipantInfo::reassign_dropped_tasks].
+    ///
+    fn apply_reassignment(&mut self, reassignment: TaskReassignment) {
+        // TODO: revisit the handling of disposing_tasks and disposed_tasks
+        //       https://github.com/AleoHQ/aleo-setup/issues/249
+        self.disposing_tasks = reassignment.disposing_tasks;
+        self.pending_tasks = reassignment.pending_tasks;
+        self.completed_tasks = reassignment.completed_tasks;
+        self.disposed_tasks.extend(reassignment.disposed_tasks);
+        self.assigned_tasks = reassignment.assigned_tasks;
+    }
+
     ///
     /// Adds the given (chunk ID, contribution ID) task in LIFO order for the participant to process.
     ///
@@ -365,6 +698,9 @@ impl ParticipantInfo {
             return Err(CoordinatorError::ParticipantAlreadyFinished);
         }
 
+        // Check that the task does not target the reserved contribution ID.
+        check_contribution_id(task.contribution_id())?;
+
         // Check that if the participant is a contributor, this chunk is not currently locked.
         if self.id.is_contributor() && self.locked_chunks.contains_key(&task.chunk_id()) {
             return Err(CoordinatorError::ParticipantAlreadyWorkingOnChunk {
@@ -443,11 +779,49 @@ impl ParticipantInfo {
         }
     }
 
+    ///
+    /// Pops the assigned task for the given chunk ID, regardless of its
+    /// position in the assigned tasks, and adds it to the pending tasks.
+    ///
+    fn pop_task_for_chunk(&mut self, chunk_id: u64, time: &dyn TimeSource) -> Result<Task, CoordinatorError> {
+        trace!("Popping task on chunk {} for {}", chunk_id, self.id);
+
+        // Check that the participant has started in the round.
+        if self.started_at.is_none() {
+            return Err(CoordinatorError::ParticipantHasNotStarted);
+        }
+
+        // Check that the participant was not dropped from the round.
+        if self.dropped_at.is_some() {
+            return Err(CoordinatorError::ParticipantWasDropped);
+        }
+
+        // Check that the participant has not finished the round.
+        if self.finished_at.is_some() {
+            return Err(CoordinatorError::ParticipantAlreadyFinished);
+        }
+
+        // Check that the chunk ID is in the assigned tasks of the participant.
+        let task = match self.assigned_tasks.iter().find(|task| task.contains(chunk_id)) {
+            Some(task) => *task,
+            None => return Err(CoordinatorError::ParticipantUnauthorizedForChunkId { chunk_id }),
+        };
+
+        // Update the last seen time.
+        self.last_seen = time.now_utc();
+
+        // Move the task from the assigned tasks to the pending tasks.
+        remove_task(&mut self.assigned_tasks, &task);
+        self.pending_tasks.push_back(task);
+
+        Ok(task)
+    }
+
     ///
     /// Adds the given chunk ID to the locked chunks held by this participant.
     ///
     #[inline]
-    fn acquired_lock(&mut self, chunk_id: u64, time: &dyn TimeSource) -> Result<(), CoordinatorError> {
+    fn acquired_lock(&mut self, chunk_id: u64, epoch: u64, time: &dyn TimeSource) -> Result<(), CoordinatorError> {
         trace!("Acquiring lock on chunk {} for {}", chunk_id, self.id);
 
         // Check that the participant has started in the round.
@@ -471,35 +845,22 @@ impl ParticipantInfo {
         }
 
         // Check that if the participant is a contributor, this chunk was popped and already pending.
-        if self.id.is_contributor()
-            && self
-                .pending_tasks
-                .par_iter()
-                .filter(|task| task.contains(chunk_id))
-                .count()
-                == 0
-        {
+        if self.id.is_contributor() && !self.pending_tasks.iter().any(|task| task.contains(chunk_id)) {
             return Err(CoordinatorError::ParticipantUnauthorizedForChunkId { chunk_id });
         }
 
         // Check that if the participant is a contributor, this chunk was not already completed.
-        if self.id.is_contributor()
-            && self
-                .completed_tasks
-                .par_iter()
-                .filter(|task| task.contains(chunk_id))
-                .count()
-                > 0
-        {
+        if self.id.is_contributor() && self.completed_tasks.iter().any(|task| task.contains(chunk_id)) {
             return Err(CoordinatorError::ParticipantAlreadyFinishedChunk { chunk_id });
         }
 
         // Update the last seen time.
         self.last_seen = time.now_utc();
 
-        let chunk_lock = ChunkLock::new(chunk_id, time);
+        let chunk_lock = ChunkLock::new(chunk_id, epoch, time);
 
         self.locked_chunks.insert(chunk_id, chunk_lock);
+        self.invalidated_locks.remove(&chunk_id);
 
         Ok(())
     }
@@ -528,28 +889,14 @@ impl ParticipantInfo {
         }
 
         // Check that if the participant is a contributor, this chunk was popped and already pending.
-        if self.id.is_contributor()
-            && self
-                .pending_tasks
-                .par_iter()
-                .filter(|t| t.contains(task.chunk_id()))
-                .count()
-                == 0
-        {
+        if self.id.is_contributor() && !self.pending_tasks.iter().any(|t| t.contains(task.chunk_id())) {
             return Err(CoordinatorError::ParticipantUnauthorizedForChunkId {
                 chunk_id: task.chunk_id(),
             });
         }
 
         // Check that if the participant is a contributor, this chunk was not already completed.
-        if self.id.is_contributor()
-            && self
-                .completed_tasks
-                .par_iter()
-                .filter(|t| t.contains(task.chunk_id()))
-                .count()
-                > 0
-        {
+        if self.id.is_contributor() && self.completed_tasks.iter().any(|t| t.contains(task.chunk_id())) {
             return Err(CoordinatorError::ParticipantAlreadyFinishedChunk {
                 chunk_id: task.chunk_id(),
             });
@@ -562,12 +909,7 @@ impl ParticipantInfo {
         self.locked_chunks.remove(&task.chunk_id());
 
         // Remove the task from the pending tasks.
-        self.pending_tasks = self
-            .pending_tasks
-            .clone()
-            .into_par_iter()
-            .filter(|t| *t != task)
-            .collect();
+        remove_task(&mut self.pending_tasks, &task);
 
         // Add the task to the front of the assigned tasks.
         self.push_front_task(task, time)?;
@@ -607,28 +949,14 @@ impl ParticipantInfo {
         }
 
         // Check that if the participant is a contributor, this chunk was popped and already pending.
-        if self.id.is_contributor()
-            && self
-                .pending_tasks
-                .par_iter()
-                .filter(|t| t.contains(task.chunk_id()))
-                .count()
-                == 0
-        {
+        if self.id.is_contributor() && !self.pending_tasks.iter().any(|t| t.contains(task.chunk_id())) {
             return Err(CoordinatorError::ParticipantUnauthorizedForChunkId {
                 chunk_id: task.chunk_id(),
             });
         }
 
         // Check that if the participant is a contributor, this chunk was not already completed.
-        if self.id.is_contributor()
-            && self
-                .completed_tasks
-                .par_iter()
-                .filter(|t| t.contains(task.chunk_id()))
-                .count()
-                > 0
-        {
+        if self.id.is_contributor() && self.completed_tasks.iter().any(|t| t.contains(task.chunk_id())) {
             return Err(CoordinatorError::ParticipantAlreadyFinishedChunk {
                 chunk_id: task.chunk_id(),
             });
@@ -638,12 +966,7 @@ impl ParticipantInfo {
         self.last_seen = time.now_utc();
 
         // Remove the task from the pending tasks.
-        self.pending_tasks = self
-            .pending_tasks
-            .clone()
-            .into_par_iter()
-            .filter(|t| *t != task)
-            .collect();
+        remove_task(&mut self.pending_tasks, &task);
 
         // Add the task to the front of the assigned tasks.
         self.push_front_task(task, time)?;
@@ -697,14 +1020,7 @@ impl ParticipantInfo {
         }
 
         // Check that if the participant is a contributor, this chunk was not already completed.
-        if self.id.is_contributor()
-            && self
-                .completed_tasks
-                .par_iter()
-                .filter(|t| t.contains(task.chunk_id()))
-                .count()
-                > 0
-        {
+        if self.id.is_contributor() && self.completed_tasks.iter().any(|t| t.contains(task.chunk_id())) {
             return Err(CoordinatorError::ParticipantAlreadyFinishedChunk {
                 chunk_id: task.chunk_id(),
             });
@@ -717,12 +1033,7 @@ impl ParticipantInfo {
         self.locked_chunks.remove(&task.chunk_id());
 
         // Remove the task from the pending tasks.
-        self.pending_tasks = self
-            .pending_tasks
-            .clone()
-            .into_par_iter()
-            .filter(|t| t != task)
-            .collect();
+        remove_task(&mut self.pending_tasks, task);
 
         // Add the task to the completed tasks.
         self.completed_tasks.push_back(task.clone());
@@ -730,6 +1041,33 @@ impl ParticipantInfo {
         Ok(())
     }
 
+    ///
+    /// Moves the given completed task back to the front of the assigned tasks,
+    /// to compute it again after its contribution failed verification.
+    ///
+    fn retry_task(&mut self, task: &Task, time: &dyn TimeSource) -> Result<(), CoordinatorError> {
+        trace!("Retrying task for {}", self.id);
+
+        // Check that the participant was not dropped from the round.
+        if self.dropped_at.is_some() {
+            return Err(CoordinatorError::ParticipantWasDropped);
+        }
+
+        // Check that the participant has completed the task.
+        if !self.completed_tasks.contains(task) {
+            return Err(CoordinatorError::ParticipantMissingCompletedTask { completed_task: *task });
+        }
+
+        // The participant has not finished the round anymore.
+        self.finished_at = None;
+
+        // Remove the task from the completed tasks.
+        remove_task(&mut self.completed_tasks, task);
+
+        // Add the task to the front of the assigned tasks.
+        self.push_front_task(*task, time)
+    }
+
     ///
     /// Completes the disposal of a given chunk (chunk ID, contribution ID) task present in the `disposing_tasks` list to the list of disposed tasks
     /// and removes the given chunk ID from the locked chunks held by this participant.
@@ -785,12 +1123,7 @@ impl ParticipantInfo {
         self.locked_chunks.remove(&chunk_id);
 
         // Remove the task from the disposing tasks.
-        self.disposing_tasks = self
-            .disposing_tasks
-            .clone()
-            .into_par_iter()
-            .filter(|t| *t != task)
-            .collect();
+        remove_task(&mut self.disposing_tasks, &task);
 
         // Add the task to the completed tasks.
         self.disposed_tasks.push_back(task);
@@ -824,6 +1157,30 @@ impl ParticipantInfo {
         Ok(())
     }
 
+    ///
+    /// Checks that the contributor completed a task for every chunk
+    /// expected of its bucket, see [expected_tasks_for_bucket].
+    ///
+    #[inline]
+    fn check_expected_tasks(
+        &self,
+        bucket_layout: BucketLayout,
+        number_of_chunks: u64,
+        number_of_contributors: u64,
+    ) -> Result<(), CoordinatorError> {
+        let expected_tasks =
+            expected_tasks_for_bucket(bucket_layout, self.bucket_id, number_of_chunks, number_of_contributors)?;
+        let completed_chunks: HashSet<u64> = self.completed_tasks.iter().map(|task| task.chunk_id()).collect();
+
+        match expected_tasks
+            .iter()
+            .all(|task| completed_chunks.contains(&task.chunk_id()))
+        {
+            true => Ok(()),
+            false => Err(CoordinatorError::ParticipantDidNotCompleteAllTasks),
+        }
+    }
+
     ///
     /// Sets the participant to finished and saves the current time as the completed time.
     ///
@@ -945,44 +1302,407 @@ impl Default for RoundMetrics {
     }
 }
 
-/// A runtime state holding values which are specific to the current ceremony run. This state must not be persisted to
-/// storage to allow a reset of it in case of a ceremony restart
-#[derive(Debug, Clone)]
-struct RuntimeState {
-    /// The list of valid tokens for each cohort
-    tokens: Vec<HashSet<String>>,
-    /// The map of tokens currently in ceremony
-    tokens_in_use: HashMap<String, Participant>,
-    /// The map of ip addresses currently in ceremony
-    current_ips: HashMap<IpAddr, Participant>,
-}
+impl RoundMetrics {
+    ///
+    /// Returns the number of contributors participating in the current round.
+    ///
+    pub fn number_of_contributors(&self) -> u64 {
+        self.number_of_contributors
+    }
 
-impl Default for RuntimeState {
-    fn default() -> Self {
-        // Called when deserializing CoordinatorState from file
-        // Read tokens from files
-        Self {
-            tokens: CoordinatorState::load_tokens(),
-            tokens_in_use: Default::default(),
-            current_ips: Default::default(),
-        }
+    ///
+    /// Returns the number of verifiers participating in the current round.
+    ///
+    pub fn number_of_verifiers(&self) -> u64 {
+        self.number_of_verifiers
+    }
+
+    ///
+    /// Returns the average number of seconds a contributor took to compute a task in the current round.
+    ///
+    pub fn contributor_average_per_task(&self) -> Option<u64> {
+        self.contributor_average_per_task
+    }
+
+    ///
+    /// Returns the estimated number of seconds remaining for the current round to finish.
+    ///
+    pub fn estimated_finish_time(&self) -> Option<u64> {
+        self.estimated_finish_time
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CoordinatorState {
-    /// The parameters and settings of this coordinator.
-    environment: Environment,
-    /// The current status of the coordinator.
-    status: CoordinatorStatus,
-    /// The map of queue participants with a reliability score, an assigned future
-    /// round, a last seen timestamp, and their time of joining.
-    queue: HashMap<Participant, (u8, Option<u64>, OffsetDateTime, OffsetDateTime)>,
-    /// The map of unique participants for the next round.
-    next: HashMap<Participant, ParticipantInfo>,
-    /// The metrics for the current round of the ceremony.
-    current_metrics: Option<RoundMetrics>,
-    /// The height for the current round of the ceremony.
+/// A structured view of a contributor waiting in the queue.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueueEntry {
+    /// The participant in the queue.
+    pub participant: Participant,
+    /// The reliability score of the participant.
+    pub reliability: u8,
+    /// The future round the participant has been assigned to, if any.
+    pub assigned_round: Option<u64>,
+    /// The timestamp when the participant was last seen.
+    pub last_seen: OffsetDateTime,
+    /// The timestamp when the participant joined the queue.
+    pub joined: OffsetDateTime,
+    /// The 1-based position of the participant in the queue, ordered by time joined.
+    pub position: u64,
+}
+
+/// A contributor of the queue tentatively assigned to the next round.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NextRoundAssignment {
+    /// The contributor assigned to the next round.
+    pub participant: Participant,
+    /// The reliability score of the contributor.
+    pub reliability: u8,
+    /// The bucket the contributor starts contributing from.
+    pub bucket_id: u64,
+}
+
+/// A chunk of the tasks of a contributor, along with the derivation of its chunk ID.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExplainedChunk {
+    /// The position of the chunk in the tasks of the contributor.
+    pub id: u64,
+    /// The chunk the contributor contributes to.
+    pub chunk_id: u64,
+    /// The contribution ID of the task.
+    pub contribution_id: u64,
+    /// The formula applied to the inputs, resulting in the chunk ID.
+    pub derivation: String,
+}
+
+/// The inputs and result of the assignment of the tasks of a contributor
+/// of the current round, see [BucketLayout::initialize_tasks].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssignmentExplanation {
+    /// The contributor the tasks are assigned to.
+    pub participant: Participant,
+    /// The height of the current round.
+    pub round_height: u64,
+    /// The layout of the buckets of the ceremony.
+    pub bucket_layout: BucketLayout,
+    /// The bucket the contributor starts contributing from, i.e. `INDEX`.
+    pub bucket_id: u64,
+    /// The number of chunks of the ceremony.
+    pub number_of_chunks: u64,
+    /// The number of contributors the round started with.
+    pub number_of_contributors: u64,
+    /// The size of the buckets, only fixed with the [BucketLayout::Fixed] layout.
+    pub bucket_size: Option<u64>,
+    /// The formula giving the chunk ID of the task at position `ID`.
+    pub formula: String,
+    /// The chunks of the tasks, in the order they were assigned.
+    pub chunks: Vec<ExplainedChunk>,
+}
+
+/// The timing metrics of a participant in the current round.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParticipantMetrics {
+    /// The average number of seconds the participant took per task, if any task was completed.
+    pub seconds_per_task: Option<u64>,
+    /// The number of tasks completed by the participant.
+    pub completed_tasks: usize,
+    /// The number of tasks the participant is working on.
+    pub pending_tasks: usize,
+    /// The number of tasks left to the participant.
+    pub assigned_tasks: usize,
+    /// The estimated number of seconds remaining for the current round to finish.
+    pub estimated_finish_time: Option<u64>,
+    /// The average seconds per task of the current contributors, only reported to the coordinator verifier.
+    pub contributor_average_per_task: Option<u64>,
+    /// The average seconds per task of the current verifiers, only reported to the coordinator verifier.
+    pub verifier_average_per_task: Option<u64>,
+}
+
+/// The width of the buckets of the queue reliability histogram.
+const RELIABILITY_BUCKET_WIDTH: u16 = 10;
+
+/// The reliability score of a contributor who was never dropped and whose contributions
+/// did not fail verification.
+pub const MAX_RELIABILITY_SCORE: u8 = 10;
+
+/// The number of queued contributors whose reliability falls in `[min, max]`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReliabilityBucket {
+    /// The lowest reliability score of the bucket.
+    pub min: u8,
+    /// The highest reliability score of the bucket.
+    pub max: u8,
+    /// The number of queued contributors in the bucket.
+    pub count: usize,
+}
+
+/// A token blacklisted by the coordinator, with the participant who used it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlacklistedToken {
+    /// The token, or only its prefix if redacted.
+    pub token: String,
+    /// The participant who used the token.
+    pub participant: Participant,
+}
+
+/// An ip address blacklisted by the coordinator, with the participant who used it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlacklistedIp {
+    /// The ip address.
+    pub ip: IpAddr,
+    /// The participant who used the ip address.
+    pub participant: Participant,
+}
+
+/// A token in use in the ceremony, with the participant who uses it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenInUse {
+    /// The token.
+    pub token: String,
+    /// The participant who uses the token.
+    pub participant: Participant,
+}
+
+/// A resumable upload of a contribution. The parts are staged until the assembled contribution matches the
+/// checksum declared by the contributor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChunkUpload {
+    /// The id of the upload session.
+    pub upload_id: String,
+    /// The contributor uploading the contribution.
+    pub participant: Participant,
+    /// The round of the contribution.
+    pub round_height: u64,
+    /// The expected size in bytes of the contribution.
+    pub total_size: u64,
+    /// The base64 encoded SHA-256 of the contribution, declared by the contributor.
+    pub checksum: String,
+    /// The received parts, mapping their byte offset to their length.
+    pub parts: BTreeMap<u64, u64>,
+}
+
+impl ChunkUpload {
+    ///
+    /// Returns `true` if the received parts cover the whole contribution.
+    ///
+    pub fn is_complete(&self) -> bool {
+        let mut end = 0;
+        for (offset, length) in self.parts.iter() {
+            if *offset != end {
+                return false;
+            }
+            end += length;
+        }
+
+        end == self.total_size
+    }
+}
+
+/// The tokens and ip addresses blacklisted by the coordinator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Blacklists {
+    /// The blacklisted tokens, ordered by token.
+    pub tokens: Vec<BlacklistedToken>,
+    /// The blacklisted ip addresses, ordered by ip address.
+    pub ips: Vec<BlacklistedIp>,
+}
+
+/// The activity of a verifier of the coordinator in the current round, telling an idle verifier from a stuck one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerifierActivity {
+    /// The verifier.
+    pub verifier: Participant,
+    /// The number of tasks verified in the current round.
+    pub tasks_verified: u64,
+    /// The average number of seconds per verification in the current round, if any.
+    pub average_seconds_per_task: Option<u64>,
+    /// The number of tasks pending verification assigned to the verifier.
+    pub pending_verifications: u64,
+    /// The timestamp of the last verification started or completed by the verifier in the current round, if any.
+    pub last_active_at: Option<OffsetDateTime>,
+}
+
+/// A machine-readable snapshot of the coordinator state machine.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostics {
+    /// The status of the coordinator state machine.
+    pub status: CoordinatorStatus,
+    /// The current round height, if set.
+    pub current_round_height: Option<u64>,
+    /// Whether all participants of the current round are done.
+    pub is_current_round_finished: bool,
+    /// Whether the current round has been aggregated.
+    pub is_current_round_aggregated: bool,
+    /// Whether the precommit for the next round is ready.
+    pub is_precommit_next_round_ready: bool,
+    /// The number of contributors in the queue.
+    pub number_of_queue_contributors: usize,
+    /// The number of contributors in the queue assigned to the next round.
+    pub number_of_assigned_contributors: usize,
+    /// The number of contributors active in the current round.
+    pub number_of_current_contributors: usize,
+    /// The number of contributors who finished the current round.
+    pub number_of_finished_contributors: usize,
+    /// The number of chunks pending verification.
+    pub number_of_pending_verifications: usize,
+    /// The number of dropped participants.
+    pub number_of_dropped_participants: usize,
+    /// The number of banned participants.
+    pub number_of_banned_participants: usize,
+    /// The reasons preventing the next round from starting.
+    pub advance_blockers: Vec<String>,
+}
+
+/// The participants who finished a round, as archived to storage.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FinishedParticipants {
+    /// The contributors who finished the round.
+    pub contributors: HashMap<Participant, ParticipantInfo>,
+    /// The verifiers who finished the round.
+    pub verifiers: HashMap<Participant, ParticipantInfo>,
+}
+
+/// Bounds the number of participants dropped in a single update cycle.
+#[derive(Debug, Clone)]
+struct DropBudget {
+    /// The number of drops left in this cycle, if bounded.
+    remaining: Option<usize>,
+    /// The participants whose drop was deferred to the next cycle.
+    deferred: HashSet<Participant>,
+}
+
+impl DropBudget {
+    fn new(limit: Option<usize>) -> Self {
+        Self {
+            remaining: limit,
+            deferred: HashSet::new(),
+        }
+    }
+
+    ///
+    /// Returns `true` and consumes a drop if the budget allows dropping the
+    /// given participant, otherwise records the drop as deferred.
+    ///
+    fn try_consume(&mut self, participant: &Participant) -> bool {
+        match self.remaining {
+            Some(0) => {
+                self.deferred.insert(participant.clone());
+                false
+            }
+            Some(ref mut remaining) => {
+                *remaining -= 1;
+                true
+            }
+            None => true,
+        }
+    }
+}
+
+/// A runtime state holding values which are specific to the current ceremony run. This state must not be persisted to
+/// storage to allow a reset of it in case of a ceremony restart.
+///
+/// Since the tokens of the active participants are only blacklisted once they contribute, an unclean restart would let
+/// a token in use be reused. When the NAMADA_PERSIST_RUNTIME env variable is set to true, the tokens and the ip
+/// addresses in use are therefore snapshotted to a sidecar file, in the storage of the ceremony, on every save of the
+/// state, and restored from it by the coordinator on restart, while the rest of this state is still reset. The file
+/// must be deleted, along with the coordinator state, to reset them too.
+#[derive(Debug, Clone)]
+struct RuntimeState {
+    /// The list of valid tokens for each cohort
+    tokens: Vec<HashSet<String>>,
+    /// The map of tokens currently in ceremony
+    tokens_in_use: HashMap<String, Participant>,
+    /// The map of ip addresses currently in ceremony
+    current_ips: HashMap<IpAddr, Participant>,
+    /// The map of verifiers to the pending verification tasks they are working on
+    verifier_locks: HashMap<Participant, HashSet<Task>>,
+    /// The set of ip addresses exempted from the ip ban
+    ip_ban_exemptions: HashSet<IpAddr>,
+    /// The map of contributors to their resumable upload of a contribution
+    chunk_uploads: HashMap<Participant, ChunkUpload>,
+    /// The selector of the verifier to which the tasks pending verification are assigned
+    verifier_selector: Arc<dyn VerifierSelector>,
+    /// The source of randomness of the weighted selection of the replacement contributors
+    rng: Arc<dyn RngSource>,
+}
+
+impl Default for RuntimeState {
+    fn default() -> Self {
+        // Called when deserializing CoordinatorState from file
+        // The tokens, the snapshot and the ip ban exemptions are loaded by the coordinator, which can report a failure
+        Self {
+            tokens: Default::default(),
+            tokens_in_use: Default::default(),
+            current_ips: Default::default(),
+            verifier_locks: Default::default(),
+            chunk_uploads: Default::default(),
+            verifier_selector: Arc::new(FirstVerifierSelector),
+            rng: Arc::new(SystemRngSource::new()),
+            ip_ban_exemptions: Default::default(),
+        }
+    }
+}
+
+/// The part of the [RuntimeState] which must survive an unclean restart, persisted when the NAMADA_PERSIST_RUNTIME env
+/// variable is set to true.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct RuntimeSnapshot {
+    /// The map of tokens currently in ceremony
+    tokens_in_use: HashMap<String, Participant>,
+    /// The map of ip addresses currently in ceremony
+    current_ips: HashMap<IpAddr, Participant>,
+}
+
+impl RuntimeSnapshot {
+    /// Returns the path of the sidecar file of the ceremony of the given environment, next to its coordinator state.
+    fn path(environment: &Environment) -> String {
+        format!("{}/{}", environment.local_base_directory(), RUNTIME_SNAPSHOT_FILE)
+    }
+
+    /// Loads the snapshot from the given file, empty if the file doesn't exist yet.
+    fn load_from(path: &str) -> Result<Self, CoordinatorError> {
+        let file = match std::fs::read(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(anyhow!("Error while reading {}: {}", path, e).into()),
+        };
+
+        Ok(serde_json::from_slice(&file).map_err(|e| anyhow!("Error while parsing {}: {}", path, e))?)
+    }
+
+    /// Writes the snapshot to the given file, replacing it atomically so that a crash never leaves it truncated.
+    fn save_to(&self, path: &str) -> Result<(), CoordinatorError> {
+        let tmp_path = format!("{}.tmp", path);
+        let bytes = serde_json::to_vec(self).map_err(|e| anyhow!("Error while serializing {}: {}", path, e))?;
+        std::fs::write(&tmp_path, bytes).map_err(|e| anyhow!("Error while writing {}: {}", tmp_path, e))?;
+        std::fs::rename(&tmp_path, path).map_err(|e| anyhow!("Error while writing {}: {}", path, e))?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoordinatorState {
+    /// The parameters and settings of this coordinator.
+    environment: Environment,
+    /// The current status of the coordinator.
+    status: CoordinatorStatus,
+    /// The map of queue participants with a reliability score, an assigned future
+    /// round, a last seen timestamp, and their time of joining.
+    queue: HashMap<Participant, (u8, Option<u64>, OffsetDateTime, OffsetDateTime)>,
+    /// The map of contributors to the index of the cohort in which they last joined the queue, kept while they
+    /// contribute and once they finished.
+    #[serde(default, alias = "queue_cohorts")]
+    participant_cohorts: HashMap<Participant, usize>,
+    /// The map of queue participants to their region, as classified when they joined.
+    #[serde(default)]
+    queue_regions: HashMap<Participant, String>,
+    /// The map of contributors to the number of times they were pulled from the queue as a replacement.
+    #[serde(default)]
+    replacement_counts: HashMap<Participant, u64>,
+    /// The map of unique participants for the next round.
+    next: HashMap<Participant, ParticipantInfo>,
+    /// The metrics for the current round of the ceremony.
+    current_metrics: Option<RoundMetrics>,
+    /// The height for the current round of the ceremony.
     current_round_height: Option<u64>,
     /// The map of unique contributors for the current round.
     current_contributors: HashMap<Participant, ParticipantInfo>,
@@ -996,18 +1716,57 @@ pub struct CoordinatorState {
     finished_contributors: HashMap<u64, HashMap<Participant, ParticipantInfo>>,
     /// The map of each round height to the corresponding verifiers from that round.
     finished_verifiers: HashMap<u64, HashMap<Participant, ParticipantInfo>>,
+    /// The set of contributors who finished a round which has been archived to storage.
+    #[serde(default)]
+    archived_finished_contributors: HashSet<Participant>,
+    /// The map of contributors who voluntarily left the queue to the time they left.
+    #[serde(default)]
+    left_queue: HashMap<Participant, OffsetDateTime>,
+    /// The map of contributors to the chunk lock limit applying to them, lower than the one of the environment.
+    #[serde(default)]
+    per_participant_lock_limit: HashMap<Participant, usize>,
+    /// The map of contributors to the number of verifications their contributions failed since the last successful one.
+    #[serde(default)]
+    verification_failures: HashMap<Participant, u32>,
     /// The list of information about participants that dropped in current and past rounds.
     dropped: Vec<ParticipantInfo>,
     /// The list of participants that are banned from all current and future rounds.
     banned: HashSet<Participant>,
     /// The manual lock to hold the coordinator from transitioning to the next round.
     manual_lock: bool,
+    /// The freeze of the queue, rejecting new participants without holding the current round.
+    #[serde(default)]
+    queue_frozen: bool,
     /// The ceremony start time.
     ceremony_start_time: OffsetDateTime,
-    /// Duration, in seconds, of each cohort
-    cohort_duration: u64,
+    /// Durations, in seconds, of the cohorts, the last one applying to all the following cohorts
+    #[serde(alias = "cohort_duration", deserialize_with = "deserialize_cohort_durations")]
+    cohort_durations: Vec<u64>,
     /// Map of tokens which have been used in the ceremony
     blacklisted_tokens: HashMap<String, Participant>,
+    /// The epoch of the current round, incremented on each reset of the round
+    #[serde(default)]
+    round_epoch: u64,
+    /// The tasks whose verification was started but not completed, resumed first after a restart.
+    #[serde(default)]
+    verifications_in_progress: HashSet<Task>,
+    /// The tasks of the current round whose contribution has been verified.
+    #[serde(default)]
+    verified_tasks: HashSet<Task>,
+    /// The number of seconds to wait after aggregation before starting the next round, overriding the environment.
+    #[serde(default)]
+    queue_wait_time: Option<u64>,
+    /// The contributors dropped for not being seen, with the time of the drop,
+    /// who can still reconnect to the current round.
+    #[serde(default)]
+    recently_dropped: HashMap<Participant, (ParticipantInfo, OffsetDateTime)>,
+    /// The end of the settle window of the current round, during which the submitted
+    /// contributions are held back from verification.
+    #[serde(default)]
+    settle_until: Option<OffsetDateTime>,
+    /// The tasks submitted during the settle window, added to the pending verifications once it ends.
+    #[serde(default)]
+    settling_tasks: Vec<Task>,
     /// Temporary runtime state, should not be persisted to storage to reset it in case of restart
     #[serde(skip)]
     runtime_state: RuntimeState,
@@ -1015,26 +1774,32 @@ pub struct CoordinatorState {
 
 impl CoordinatorState {
     /// Reads tokens from disk and generates a vector of them. Expects tokens to be in a separate folder containing only those files.
-    ///
-    /// # Panics
-    /// If folder, file names or content don't respect the specified format.
-    pub(super) fn load_tokens() -> Vec<HashSet<String>> {
-        let tokens_file_prefix = std::env::var("TOKENS_FILE_PREFIX").unwrap_or("namada_tokens_cohort".to_string());
-        let tokens_dir =
-            std::fs::read_dir(TOKENS_PATH.as_str()).expect(format!("Error with path {}", &*TOKENS_PATH).as_str());
+    pub(super) fn load_tokens(environment: &Environment) -> Result<Vec<HashSet<String>>, CoordinatorError> {
+        Self::load_tokens_from_dir(&environment.tokens_path())
+    }
+
+    /// Reads the token files of the cohorts from the given folder.
+    fn load_tokens_from_dir(tokens_path: &str) -> Result<Vec<HashSet<String>>, CoordinatorError> {
+        let malformed = |path: &str, source: std::io::Error| CoordinatorError::TokenFileMalformed {
+            path: path.to_string(),
+            source,
+        };
+
+        let tokens_dir = std::fs::read_dir(tokens_path).map_err(|e| malformed(tokens_path, e))?;
         let number_of_cohorts = tokens_dir.count();
-        let mut tokens = vec![HashSet::default(); number_of_cohorts];
+        let mut tokens = Vec::with_capacity(number_of_cohorts);
 
         for cohort in 1..=number_of_cohorts {
-            let path = format!("{}/{}_{}.json", *TOKENS_PATH, tokens_file_prefix, cohort);
-            let file = std::fs::read(path).unwrap();
-            let token_set: HashSet<String> = serde_json::from_slice(&file).unwrap();
-            tokens[cohort - 1] = token_set;
+            let path = Self::cohort_tokens_path(tokens_path, cohort);
+            let file = std::fs::read(&path).map_err(|e| malformed(&path, e))?;
+            let token_set: HashSet<String> = serde_json::from_slice(&file).map_err(|e| malformed(&path, e.into()))?;
+            tokens.push(token_set);
         }
 
-        tokens
+        Ok(tokens)
     }
 
+    ///
     /// Reads tokens from bytes and generates a vector of them.
     ///
     /// # Panics
@@ -1053,6 +1818,55 @@ impl CoordinatorState {
         tokens
     }
 
+    /// Returns the name of the token file of the given cohort, starting from 1.
+    pub(crate) fn cohort_tokens_file_name(cohort: usize) -> String {
+        let tokens_file_prefix = std::env::var("TOKENS_FILE_PREFIX").unwrap_or("namada_tokens_cohort".to_string());
+        format!("{}_{}.json", tokens_file_prefix, cohort)
+    }
+
+    /// Returns the path of the token file of the given cohort, starting from 1, in the given folder.
+    fn cohort_tokens_path(tokens_path: &str, cohort: usize) -> String {
+        format!("{}/{}", tokens_path, Self::cohort_tokens_file_name(cohort))
+    }
+
+    /// Writes the tokens of the given cohort, starting from 1, to its file in the given folder, in the format read by
+    /// [Self::load_tokens]. An existing file is only overwritten if `force` is set. Returns the path of the file.
+    pub fn save_cohort_tokens(
+        tokens_path: &str,
+        cohort: usize,
+        tokens: &HashSet<String>,
+        force: bool,
+    ) -> Result<String, CoordinatorError> {
+        if cohort == 0 {
+            return Err(anyhow!("The cohorts start from 1").into());
+        }
+
+        let path = Self::cohort_tokens_path(tokens_path, cohort);
+        if !force && std::path::Path::new(&path).exists() {
+            return Err(anyhow!("The token file {} already exists, use --force to overwrite it", path).into());
+        }
+
+        let bytes = serde_json::to_vec(tokens).map_err(|e| anyhow!("Error while serializing {}: {}", path, e))?;
+        std::fs::write(&path, bytes).map_err(|e| anyhow!("Error while writing {}: {}", path, e))?;
+
+        Ok(path)
+    }
+
+    /// Rewrites the token files of all the cohorts in the given folder, removing the file of a cohort which no
+    /// longer exists.
+    pub(super) fn save_tokens(tokens_path: &str, tokens: &[HashSet<String>]) -> Result<(), CoordinatorError> {
+        for (cohort, cohort_tokens) in tokens.iter().enumerate() {
+            Self::save_cohort_tokens(tokens_path, cohort + 1, cohort_tokens, true)?;
+        }
+
+        let stale_path = Self::cohort_tokens_path(tokens_path, tokens.len() + 1);
+        if std::path::Path::new(&stale_path).exists() {
+            std::fs::remove_file(&stale_path).map_err(|e| anyhow!("Error while removing {}: {}", stale_path, e))?;
+        }
+
+        Ok(())
+    }
+
     ///
     /// Updates the set of tokens for the ceremony
     ///
@@ -1060,6 +1874,185 @@ impl CoordinatorState {
         self.runtime_state.tokens = tokens
     }
 
+    ///
+    /// Checks that the given token schedule can replace the current one while
+    /// the ceremony is running: the tokens must be unique, the durations of
+    /// the elapsed cohorts must be unchanged, the current cohort must be the
+    /// same and still have tokens, and the tokens in use must be kept.
+    ///
+    pub fn check_token_schedule(
+        &self,
+        tokens: &[HashSet<String>],
+        cohort_durations: &[u64],
+    ) -> Result<(), CoordinatorError> {
+        if tokens.is_empty() || cohort_durations.is_empty() || cohort_durations.contains(&0) {
+            return Err(CoordinatorError::TokenScheduleInvalid);
+        }
+
+        let mut scheduled_tokens = HashSet::new();
+        for token in tokens.iter().flatten() {
+            if !scheduled_tokens.insert(token) {
+                return Err(CoordinatorError::TokenScheduleDuplicateToken(token.clone()));
+            }
+        }
+
+        // The last duration applies to all the following cohorts.
+        let duration = |durations: &[u64], cohort: usize| durations.get(cohort).or(durations.last()).copied();
+        let current_cohort = self.get_current_cohort_index();
+        if (0..current_cohort)
+            .any(|cohort| duration(&self.cohort_durations, cohort) != duration(cohort_durations, cohort))
+        {
+            return Err(CoordinatorError::TokenScheduleInconsistent(
+                "the durations of the elapsed cohorts cannot change".to_string(),
+            ));
+        }
+
+        let elapsed = (OffsetDateTime::now_utc().unix_timestamp() - self.ceremony_start_time.unix_timestamp()) as u64;
+        if cohort_index(cohort_durations, elapsed) != current_cohort {
+            return Err(CoordinatorError::TokenScheduleInconsistent(format!(
+                "the current cohort {} must not change",
+                current_cohort + 1
+            )));
+        }
+
+        if current_cohort >= tokens.len() {
+            return Err(CoordinatorError::TokenScheduleInconsistent(format!(
+                "the current cohort {} has no tokens",
+                current_cohort + 1
+            )));
+        }
+
+        if let Some(token) = self
+            .runtime_state
+            .tokens_in_use
+            .keys()
+            .find(|token| !scheduled_tokens.contains(token))
+        {
+            return Err(CoordinatorError::TokenScheduleInconsistent(format!(
+                "the token {} is in use",
+                token
+            )));
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Replaces the tokens and the durations of the cohorts of the ceremony,
+    /// once the new schedule is checked with [Self::check_token_schedule].
+    /// The blacklisted tokens which are no longer scheduled are discarded.
+    ///
+    pub(super) fn replace_token_schedule(
+        &mut self,
+        tokens: Vec<HashSet<String>>,
+        cohort_durations: Vec<u64>,
+    ) -> Result<(), CoordinatorError> {
+        self.check_token_schedule(&tokens, &cohort_durations)?;
+
+        self.blacklisted_tokens
+            .retain(|token, _| tokens.iter().any(|cohort_tokens| cohort_tokens.contains(token)));
+        self.runtime_state.tokens = tokens;
+        self.cohort_durations = cohort_durations;
+
+        Ok(())
+    }
+
+    ///
+    /// Merges the tokens of the cohort `from` into the ones of the cohort `into`,
+    /// both starting from 0, and removes the emptied cohort. The following cohorts
+    /// keep their durations and open earlier by the duration of the removed one.
+    ///
+    /// The merged cohort must not have opened yet, the target cohort must not
+    /// be past.
+    ///
+    pub(super) fn merge_cohorts(&mut self, from: usize, into: usize) -> Result<(), CoordinatorError> {
+        let current_cohort = self.get_current_cohort_index();
+        let number_of_cohorts = self.get_number_of_cohorts();
+
+        if from == into || from >= number_of_cohorts || into >= number_of_cohorts {
+            return Err(CoordinatorError::CohortMergeInvalid(format!(
+                "cannot merge the cohort {} into the cohort {} out of {}",
+                from + 1,
+                into + 1,
+                number_of_cohorts
+            )));
+        }
+
+        if from <= current_cohort {
+            return Err(CoordinatorError::CohortMergeInvalid(format!(
+                "the cohort {} is active or past",
+                from + 1
+            )));
+        }
+
+        if into < current_cohort {
+            return Err(CoordinatorError::CohortMergeInvalid(format!("the cohort {} is past", into + 1)));
+        }
+
+        let from_tokens = self.runtime_state.tokens.remove(from);
+        let into = if into > from { into - 1 } else { into };
+        self.runtime_state.tokens[into].extend(from_tokens);
+
+        // Keep the cohorts of the participants pointing to the same tokens.
+        for cohort in self.participant_cohorts.values_mut() {
+            if *cohort == from {
+                *cohort = into;
+            } else if *cohort > from {
+                *cohort -= 1;
+            }
+        }
+
+        // The durations from the last one on all apply to the following cohorts, removing
+        // one of them would change the durations of the cohorts after it.
+        if from + 1 < self.cohort_durations.len() {
+            self.cohort_durations.remove(from);
+        }
+
+        Ok(())
+    }
+
+    /// Reads the ip addresses exempted from the ip ban from the json file at the path set in the
+    /// NAMADA_MPC_IP_BAN_EXEMPTIONS env variable, if any.
+    pub(super) fn load_ip_ban_exemptions() -> Result<HashSet<IpAddr>, CoordinatorError> {
+        let path = match std::env::var("NAMADA_MPC_IP_BAN_EXEMPTIONS") {
+            Ok(path) => path,
+            Err(_) => return Ok(HashSet::new()),
+        };
+
+        let file = std::fs::read(&path).map_err(|e| anyhow!("Error while reading {}: {}", path, e))?;
+        let exemptions: HashSet<IpAddr> =
+            serde_json::from_slice(&file).map_err(|e| anyhow!("Error while parsing {}: {}", path, e))?;
+
+        Ok(exemptions)
+    }
+
+    ///
+    /// Updates the set of ip addresses exempted from the ip ban
+    ///
+    pub(super) fn update_ip_ban_exemptions(&mut self, exemptions: HashSet<IpAddr>) {
+        self.runtime_state.ip_ban_exemptions = exemptions
+    }
+
+    ///
+    /// Returns `true` if the ip ban is enabled and the given ip address is
+    /// blacklisted or already used by a participant, unless it is exempted.
+    /// The exemptions only lift the ip ban: the tokens of the participants
+    /// joining from an exempted address are still checked against the
+    /// tokens in use and the blacklisted ones.
+    ///
+    pub fn is_ip_banned(&self, ip: &IpAddr) -> bool {
+        *IP_BAN
+            && !self.runtime_state.ip_ban_exemptions.contains(ip)
+            && (self.blacklisted_ips.contains_key(ip) || self.runtime_state.current_ips.contains_key(ip))
+    }
+
+    ///
+    /// Returns the set of ip addresses exempted from the ip ban
+    ///
+    pub fn ip_ban_exemptions(&self) -> &HashSet<IpAddr> {
+        &self.runtime_state.ip_ban_exemptions
+    }
+
     fn get_ceremony_start_time() -> OffsetDateTime {
         #[cfg(debug_assertions)]
         let ceremony_start_time = OffsetDateTime::now_utc();
@@ -1079,22 +2072,50 @@ impl CoordinatorState {
     /// NOTE: At startup the coordinator will try to recover this state from disk instead of calling this initializer
     /// So we need to clear the coordinator.json file if we want to reset the following variables:
     ///     - CEREMONY_START_TIMESTAMP
-    ///     - NAMADA_COHORT_TIME
+    ///     - NAMADA_COHORT_TIME, a comma separated list of the durations of the cohorts in seconds
     /// These two parameters are meant to stay constant during the entire ceremony.
-    /// The tokens are instead reloaded from files when restarting a coordinator to support a token update
+    /// The tokens are instead loaded from files by the coordinator, on every start, to support a token update
     #[inline]
-    pub(super) fn new(environment: Environment) -> Self {
-        let cohort_duration = match std::env::var("NAMADA_COHORT_TIME") {
-            Ok(n) => n.parse::<u64>().unwrap(),
-            Err(_) => 86400,
-        };
-
+    pub(super) fn new(environment: Environment) -> Result<Self, CoordinatorError> {
+        let cohort_durations = Self::cohort_durations_from_env()?;
         let ceremony_start_time = CoordinatorState::get_ceremony_start_time();
 
+        Ok(Self::with_runtime_state(
+            environment,
+            ceremony_start_time,
+            cohort_durations,
+            RuntimeState::default(),
+        ))
+    }
+
+    ///
+    /// Reads the durations of the cohorts from the NAMADA_COHORT_TIME env variable, one day by default.
+    /// Returns [CoordinatorError::CohortDurationsInvalid] if any of them is not a positive number of seconds.
+    ///
+    pub fn cohort_durations_from_env() -> Result<Vec<u64>, CoordinatorError> {
+        match std::env::var("NAMADA_COHORT_TIME") {
+            Ok(durations) => parse_cohort_durations(&durations),
+            Err(_) => Ok(vec![86400]),
+        }
+    }
+
+    ///
+    /// Creates an instance of `CoordinatorState` with the given ceremony schedule and runtime state, and no
+    /// participants. Unlike [CoordinatorState::new], it reads no setting nor file, so that it can't fail.
+    ///
+    fn with_runtime_state(
+        environment: Environment,
+        ceremony_start_time: OffsetDateTime,
+        cohort_durations: Vec<u64>,
+        runtime_state: RuntimeState,
+    ) -> Self {
         Self {
             environment,
             status: CoordinatorStatus::Initializing,
             queue: HashMap::default(),
+            participant_cohorts: HashMap::default(),
+            queue_regions: HashMap::default(),
+            replacement_counts: HashMap::default(),
             next: HashMap::default(),
             current_metrics: None,
             current_round_height: None,
@@ -1104,13 +2125,25 @@ impl CoordinatorState {
             pending_verification: HashMap::default(),
             finished_contributors: HashMap::default(),
             finished_verifiers: HashMap::default(),
+            archived_finished_contributors: HashSet::new(),
+            left_queue: HashMap::new(),
+            per_participant_lock_limit: HashMap::new(),
+            verification_failures: HashMap::new(),
             dropped: Vec::new(),
             banned: HashSet::new(),
             manual_lock: false,
+            queue_frozen: false,
             ceremony_start_time,
-            cohort_duration,
+            cohort_durations,
             blacklisted_tokens: HashMap::default(),
-            runtime_state: RuntimeState::default(),
+            round_epoch: 0,
+            verifications_in_progress: HashSet::new(),
+            verified_tasks: HashSet::new(),
+            queue_wait_time: None,
+            recently_dropped: HashMap::default(),
+            settle_until: None,
+            settling_tasks: Vec::new(),
+            runtime_state,
         }
     }
 
@@ -1138,38 +2171,39 @@ impl CoordinatorState {
         let span = tracing::error_span!("reset_round", round = self.current_round_height.unwrap_or(0));
         let _guard = span.enter();
 
-        let current_round_height = self.current_round_height.ok_or(CoordinatorError::RoundDoesNotExist)?;
-        if current_round_height == 0 {
-            return Err(CoordinatorError::RoundHeightIsZero);
-        }
+        let RoundReset {
+            round_height: current_round_height,
+            contributors,
+            rollback: need_to_rollback,
+        } = self.plan_round_reset(force_rollback)?;
 
         tracing::warn!("Resetting round {}.", current_round_height);
 
-        let finished_contributors = self
-            .finished_contributors
-            .get(&current_round_height)
-            .cloned()
-            .unwrap_or_else(|| HashMap::new());
+        // The contributors waiting to reconnect cannot rejoin the reset round.
+        let disconnected_contributors = self.drop_recently_dropped(time)?;
 
-        let number_of_contributors = self.current_contributors.len() + finished_contributors.len();
+        let number_of_contributors = contributors.len();
         let number_of_chunks = self.environment.number_of_chunks() as u64;
+        let round_contributors: Vec<Participant> = contributors
+            .iter()
+            .map(|(participant, _)| participant.clone())
+            .collect();
 
-        let current_contributors = self
-            .current_contributors
-            .clone()
+        let current_contributors = contributors
             .into_iter()
-            .chain(finished_contributors.clone().into_iter())
             .enumerate()
             .map(|(bucket_index, (participant, mut participant_info))| {
                 let bucket_id = bucket_index as u64;
-                let tasks = initialize_tasks(bucket_id, number_of_chunks, number_of_contributors as u64)?;
+                let tasks = self.environment.bucket_layout().initialize_tasks(
+                    bucket_id,
+                    number_of_chunks,
+                    number_of_contributors as u64,
+                )?;
                 participant_info.restart_tasks(tasks, time)?;
                 Ok((participant, participant_info))
             })
             .collect::<Result<HashMap<Participant, ParticipantInfo>, CoordinatorError>>()?;
 
-        let need_to_rollback = force_rollback || number_of_contributors == 0;
-
         if need_to_rollback {
             // Will roll back to the previous round and await new
             // contributors/verifiers before starting the round again.
@@ -1183,12 +2217,9 @@ impl CoordinatorState {
                 );
             }
 
-            let remove_participants: Vec<Participant> = self
-                .current_contributors
-                .clone()
+            let remove_participants: Vec<Participant> = round_contributors
                 .into_iter()
-                .chain(finished_contributors.into_iter())
-                .map(|(participant, _participant_info)| participant)
+                .chain(disconnected_contributors.into_iter())
                 .collect();
 
             let current_metrics = Some(RoundMetrics {
@@ -1214,16 +2245,27 @@ impl CoordinatorState {
             }
 
             *self = Self {
-                ceremony_start_time: std::mem::replace(&mut self.ceremony_start_time, OffsetDateTime::now_utc()),
-                cohort_duration: std::mem::take(&mut self.cohort_duration),
                 current_metrics,
                 current_round_height: Some(new_round_height),
                 blacklisted_ips: std::mem::take(&mut self.blacklisted_ips),
                 queue,
+                participant_cohorts: std::mem::take(&mut self.participant_cohorts),
+                queue_regions: std::mem::take(&mut self.queue_regions),
                 banned: std::mem::take(&mut self.banned),
                 blacklisted_tokens: std::mem::take(&mut self.blacklisted_tokens),
-                runtime_state: std::mem::take(&mut self.runtime_state),
-                ..Self::new(self.environment.clone())
+                round_epoch: self.round_epoch + 1,
+                queue_wait_time: self.queue_wait_time,
+                queue_frozen: self.queue_frozen,
+                per_participant_lock_limit: std::mem::take(&mut self.per_participant_lock_limit),
+                verification_failures: std::mem::take(&mut self.verification_failures),
+                replacement_counts: std::mem::take(&mut self.replacement_counts),
+                left_queue: std::mem::take(&mut self.left_queue),
+                ..Self::with_runtime_state(
+                    self.environment.clone(),
+                    self.ceremony_start_time,
+                    std::mem::take(&mut self.cohort_durations),
+                    std::mem::take(&mut self.runtime_state),
+                )
             };
 
             self.initialize(new_round_height);
@@ -1259,39 +2301,105 @@ impl CoordinatorState {
             // Will reset the round to run with the remaining participants.
 
             *self = Self {
-                ceremony_start_time: std::mem::replace(&mut self.ceremony_start_time, OffsetDateTime::now_utc()),
-                cohort_duration: std::mem::take(&mut self.cohort_duration),
                 current_contributors,
                 current_verifiers: Default::default(),
                 blacklisted_ips: std::mem::take(&mut self.blacklisted_ips),
                 queue: std::mem::take(&mut self.queue),
+                participant_cohorts: std::mem::take(&mut self.participant_cohorts),
+                queue_regions: std::mem::take(&mut self.queue_regions),
                 banned: std::mem::take(&mut self.banned),
                 dropped: std::mem::take(&mut self.dropped),
                 blacklisted_tokens: std::mem::take(&mut self.blacklisted_tokens),
-                runtime_state: std::mem::take(&mut self.runtime_state),
-                ..Self::new(self.environment.clone())
+                round_epoch: self.round_epoch + 1,
+                queue_wait_time: self.queue_wait_time,
+                queue_frozen: self.queue_frozen,
+                per_participant_lock_limit: std::mem::take(&mut self.per_participant_lock_limit),
+                verification_failures: std::mem::take(&mut self.verification_failures),
+                replacement_counts: std::mem::take(&mut self.replacement_counts),
+                left_queue: std::mem::take(&mut self.left_queue),
+                ..Self::with_runtime_state(
+                    self.environment.clone(),
+                    self.ceremony_start_time,
+                    std::mem::take(&mut self.cohort_durations),
+                    std::mem::take(&mut self.runtime_state),
+                )
             };
 
             self.initialize(current_round_height);
             self.update_round_metrics();
 
             Ok(ResetCurrentRoundStorageAction {
-                remove_participants: Vec::new(),
+                remove_participants: disconnected_contributors,
                 rollback: false,
             })
         }
     }
 
+    /// Computes what [CoordinatorState::reset_current_round] would do
+    /// with the given `force_rollback`, without changing the state.
     ///
-    /// Initializes the coordinator state by setting the round height & metrics, and instantiating
-    /// the finished contributors and verifiers map for the given round in the coordinator state.
-    ///
-    #[inline]
-    pub(super) fn initialize(&mut self, current_round_height: u64) {
-        // Set the current round height to the given round height.
-        if self.current_round_height.is_none() {
-            self.current_round_height = Some(current_round_height);
-        }
+    /// Returns the same errors as [CoordinatorState::reset_current_round]
+    /// on a missing or zero current round height.
+    pub fn preview_reset_current_round(&self, force_rollback: bool) -> Result<ResetPreview, CoordinatorError> {
+        let RoundReset {
+            round_height,
+            contributors,
+            rollback,
+        } = self.plan_round_reset(force_rollback)?;
+
+        // The contributors waiting to reconnect are always removed, the round ones only on a rollback.
+        let mut remove_participants: Vec<Participant> = self.recently_dropped.keys().cloned().collect();
+        if rollback {
+            remove_participants.extend(contributors.into_iter().map(|(participant, _)| participant));
+        }
+        remove_participants.sort_by_key(|participant| participant.to_string());
+
+        Ok(ResetPreview {
+            rollback,
+            remove_participants,
+            target_round_height: match rollback {
+                true => round_height - 1,
+                false => round_height,
+            },
+        })
+    }
+
+    /// Collects the contributors of the current round, current and
+    /// finished ones, and decides whether a reset with the given
+    /// `force_rollback` rolls back to the previous round, for both
+    /// [CoordinatorState::reset_current_round] and
+    /// [CoordinatorState::preview_reset_current_round].
+    fn plan_round_reset(&self, force_rollback: bool) -> Result<RoundReset, CoordinatorError> {
+        let round_height = self.current_round_height.ok_or(CoordinatorError::RoundDoesNotExist)?;
+        if round_height == 0 {
+            return Err(CoordinatorError::RoundHeightIsZero);
+        }
+
+        let contributors: Vec<(Participant, ParticipantInfo)> = self
+            .current_contributors
+            .iter()
+            .chain(self.finished_contributors.get(&round_height).into_iter().flatten())
+            .map(|(participant, participant_info)| (participant.clone(), participant_info.clone()))
+            .collect();
+        let rollback = force_rollback || contributors.is_empty();
+
+        Ok(RoundReset {
+            round_height,
+            contributors,
+            rollback,
+        })
+    }
+
+    ///
+    /// Initializes the coordinator state by setting the round height & metrics, and instantiating
+    /// the finished contributors and verifiers map for the given round in the coordinator state.
+    ///
+    #[inline]
+    pub(super) fn initialize(&mut self, current_round_height: u64) {
+        // Set the current round height to the given round height.
+        if self.current_round_height.is_none() {
+            self.current_round_height = Some(current_round_height);
+        }
 
         // Initialize the metrics for this round.
         if self.current_metrics.is_none() {
@@ -1384,6 +2492,66 @@ impl CoordinatorState {
                 .contains_key(participant)
     }
 
+    ///
+    /// Returns the number of contributors who finished a round, across all the rounds.
+    ///
+    pub fn number_of_finished_contributors(&self) -> usize {
+        self.finished_contributors
+            .values()
+            .map(|contributors| contributors.len())
+            .sum::<usize>()
+            + self.archived_finished_contributors.len()
+    }
+
+    ///
+    /// Returns the participants who finished the given round, if they
+    /// are still held in memory.
+    ///
+    pub fn finished_participants(&self, round_height: u64) -> Option<FinishedParticipants> {
+        let contributors = self.finished_contributors.get(&round_height)?;
+
+        Some(FinishedParticipants {
+            contributors: contributors.clone(),
+            verifiers: self.finished_verifiers.get(&round_height).cloned().unwrap_or_default(),
+        })
+    }
+
+    ///
+    /// Returns the heights of the rounds whose finished participants
+    /// exceed the retention and should be archived to storage.
+    ///
+    pub(super) fn expired_finished_rounds(&self) -> Vec<u64> {
+        let (retention, current_round_height) =
+            match (self.environment.finished_rounds_retention(), self.current_round_height) {
+                (Some(retention), Some(current_round_height)) => (retention, current_round_height),
+                _ => return vec![],
+            };
+
+        let mut rounds: Vec<u64> = self
+            .finished_contributors
+            .keys()
+            .chain(self.finished_verifiers.keys())
+            .filter(|round_height| **round_height < current_round_height.saturating_sub(retention))
+            .cloned()
+            .collect::<HashSet<u64>>()
+            .into_iter()
+            .collect();
+        rounds.sort_unstable();
+
+        rounds
+    }
+
+    ///
+    /// Removes the finished participants of the given round from memory,
+    /// once they have been archived to storage.
+    ///
+    pub(super) fn remove_finished_round(&mut self, round_height: u64) {
+        if let Some(contributors) = self.finished_contributors.remove(&round_height) {
+            self.archived_finished_contributors.extend(contributors.into_keys());
+        }
+        self.finished_verifiers.remove(&round_height);
+    }
+
     pub fn current_round_finished_contributors(&self) -> anyhow::Result<Vec<Participant>> {
         let current_round_height = self
             .current_round_height
@@ -1413,7 +2581,7 @@ impl CoordinatorState {
     ///
     #[inline]
     pub fn is_coordinator_verifier(&self, participant: &Participant) -> bool {
-        participant.is_verifier() && self.environment.coordinator_verifiers().contains(participant)
+        participant.is_verifier() && self.environment.is_coordinator_verifier(participant)
     }
 
     ///
@@ -1424,6 +2592,14 @@ impl CoordinatorState {
         self.queue.par_iter().filter(|(p, _)| p.is_contributor()).count()
     }
 
+    ///
+    /// Returns the number of contributors participating in the current round.
+    ///
+    #[inline]
+    pub fn number_of_current_contributors(&self) -> usize {
+        self.current_contributors.len()
+    }
+
     ///
     /// Returns the information of a queued contributor.
     ///
@@ -1446,6 +2622,211 @@ impl CoordinatorState {
             .collect()
     }
 
+    ///
+    /// Returns a structured list of the contributors currently in the queue,
+    /// ordered by their position in the queue.
+    ///
+    pub fn queue_entries(&self) -> Vec<QueueEntry> {
+        let mut queue = self.queue_contributors();
+        queue.par_sort_by(|a, b| ((a.1).3).cmp(&(b.1).3));
+
+        queue
+            .into_iter()
+            .enumerate()
+            .map(
+                |(index, (participant, (reliability, assigned_round, last_seen, joined)))| QueueEntry {
+                    participant,
+                    reliability,
+                    assigned_round,
+                    last_seen,
+                    joined,
+                    position: index as u64 + 1,
+                },
+            )
+            .collect()
+    }
+
+    ///
+    /// Returns the estimated number of seconds the given contributor of the
+    /// queue has to wait before joining a round, or `None` if the contributor
+    /// is not in the queue or the round metrics are not available yet.
+    ///
+    /// The contributor waits for the round it is assigned to by the last
+    /// update of the queue. Before that, the contributors in the queue join
+    /// the rounds in batches of the maximum contributors per round of the
+    /// current cohort, in the order of the queue priority. Each of the rounds
+    /// ahead of the one of the contributor is estimated to take the
+    /// contributions to all the chunks, the aggregation and the queue wait.
+    ///
+    pub fn estimated_wait_for(&self, participant: &Participant) -> Option<u64> {
+        let metrics = self.current_metrics.as_ref()?;
+        let estimated_wait_time = metrics.estimated_wait_time?;
+        let round_time = metrics.contributor_average_per_task? * self.environment.number_of_chunks()
+            + metrics.estimated_aggregation_time?
+            + self.queue_wait_time();
+
+        let rounds_ahead = match self.queue.get(participant)? {
+            (_, Some(assigned_round), _, _) => {
+                assigned_round.saturating_sub(self.current_round_height.unwrap_or_default() + 1)
+            }
+            (_, None, _, _) => {
+                let position = self
+                    .prioritized_queue()
+                    .into_iter()
+                    .filter(|(p, _, _, _)| p.is_contributor())
+                    .position(|(p, _, _, _)| p == *participant)?;
+                (position / self.maximum_contributors_per_round().max(1)) as u64
+            }
+        };
+
+        Some(estimated_wait_time + rounds_ahead * round_time)
+    }
+
+    ///
+    /// Returns the contributors of the queue assigned to the given round,
+    /// sorted by their reliability (in order of highest to lowest number).
+    ///
+    fn next_round_queue_contributors(
+        &self,
+        next_round_height: u64,
+    ) -> Vec<(Participant, (u8, u64, OffsetDateTime, OffsetDateTime))> {
+        let mut contributors: Vec<(_, (_, _, _, _))> = self
+            .queue
+            .clone()
+            .into_par_iter()
+            .map(|(p, (r, rh, ls, j))| (p, (r, rh.unwrap_or_default(), ls, j)))
+            .filter(|(p, (_, rh, _, _))| p.is_contributor() && *rh == next_round_height)
+            .collect();
+
+        contributors.par_sort_by(|a, b| ((b.1).0).cmp(&(&a.1).0));
+
+        contributors
+    }
+
+    ///
+    /// Returns the tentative assignment of the contributors to the next round,
+    /// ordered by bucket ID.
+    ///
+    /// Before the precommit, this is the assignment `precommit_next_round` would
+    /// produce given the current queue. After the precommit, this is the staged
+    /// assignment of the next round.
+    ///
+    pub fn next_round_assignment(&self) -> Vec<NextRoundAssignment> {
+        if self.status == CoordinatorStatus::Precommit {
+            let mut assignment: Vec<NextRoundAssignment> = self
+                .next
+                .iter()
+                .map(|(participant, info)| NextRoundAssignment {
+                    participant: participant.clone(),
+                    reliability: info.reliability,
+                    bucket_id: info.bucket_id,
+                })
+                .collect();
+            assignment.sort_by_key(|entry| entry.bucket_id);
+
+            return assignment;
+        }
+
+        let next_round_height = match self.current_round_height {
+            Some(current_round_height) => current_round_height + 1,
+            None => return vec![],
+        };
+
+        self.next_round_queue_contributors(next_round_height)
+            .into_iter()
+            .enumerate()
+            .map(
+                |(bucket_index, (participant, (reliability, _, _, _)))| NextRoundAssignment {
+                    participant,
+                    reliability,
+                    bucket_id: bucket_index as u64,
+                },
+            )
+            .collect()
+    }
+
+    ///
+    /// Returns the inputs of the assignment of the tasks of the given
+    /// current contributor and the chunks resulting from them, following
+    /// the scheme described in `precommit_next_round`.
+    ///
+    pub fn assignment_explanation(&self, participant: &Participant) -> Result<AssignmentExplanation, CoordinatorError> {
+        let info = self
+            .current_contributors
+            .get(participant)
+            .ok_or_else(|| CoordinatorError::ParticipantNotFound(participant.clone()))?;
+        let number_of_contributors = self
+            .current_metrics
+            .as_ref()
+            .ok_or(CoordinatorError::CoordinatorStateNotInitialized)?
+            .number_of_contributors;
+        let number_of_chunks = self.environment.number_of_chunks();
+        let bucket_layout = self.environment.bucket_layout();
+        let bucket_id = info.bucket_id;
+
+        let tasks = bucket_layout.initialize_tasks(bucket_id, number_of_chunks, number_of_contributors)?;
+
+        let (bucket_size, formula) = match bucket_layout {
+            BucketLayout::Fixed => (
+                Some(number_of_chunks / number_of_contributors),
+                "CHUNK_ID := (INDEX * BUCKET_SIZE + ID) % NUMBER_OF_CHUNKS",
+            ),
+            BucketLayout::Adaptive => (
+                None,
+                "CHUNK_ID := (INDEX * NUMBER_OF_CHUNKS / NUMBER_OF_CONTRIBUTORS + ID) % NUMBER_OF_CHUNKS",
+            ),
+        };
+        let chunks = tasks
+            .iter()
+            .enumerate()
+            .map(|(id, task)| {
+                let start = match bucket_size {
+                    Some(bucket_size) => format!("{} * {}", bucket_id, bucket_size),
+                    None => format!("{} * {} / {}", bucket_id, number_of_chunks, number_of_contributors),
+                };
+                ExplainedChunk {
+                    id: id as u64,
+                    chunk_id: task.chunk_id(),
+                    contribution_id: task.contribution_id(),
+                    derivation: format!("({} + {}) % {} = {}", start, id, number_of_chunks, task.chunk_id()),
+                }
+            })
+            .collect();
+
+        Ok(AssignmentExplanation {
+            participant: participant.clone(),
+            round_height: info.round_height,
+            bucket_layout,
+            bucket_id,
+            number_of_chunks,
+            number_of_contributors,
+            bucket_size,
+            formula: formula.to_string(),
+            chunks,
+        })
+    }
+
+    ///
+    /// Returns the histogram of the reliability scores of the contributors
+    /// in the queue, covering the whole range of scores.
+    ///
+    pub fn queue_reliability_histogram(&self) -> Vec<ReliabilityBucket> {
+        let mut buckets: Vec<ReliabilityBucket> = (0..=u8::MAX as u16)
+            .step_by(RELIABILITY_BUCKET_WIDTH as usize)
+            .map(|min| ReliabilityBucket {
+                min: min as u8,
+                max: (min + RELIABILITY_BUCKET_WIDTH - 1).min(u8::MAX as u16) as u8,
+                count: 0,
+            })
+            .collect();
+
+        for (_, (reliability, _, _, _)) in self.queue_contributors() {
+            buckets[(reliability as u16 / RELIABILITY_BUCKET_WIDTH) as usize].count += 1;
+        }
+
+        buckets
+    }
+
     ///
     /// Returns a list of the contributors currently in the round.
     ///
@@ -1472,6 +2853,132 @@ impl CoordinatorState {
         }
     }
 
+    ///
+    /// Returns the index of the cohort in which the given contributor joined the queue,
+    /// if they are in the queue, in the next or current round, or finished a round.
+    ///
+    pub fn participant_cohort(&self, participant: &Participant) -> Option<usize> {
+        let known = self.queue.contains_key(participant)
+            || self.current_contributors.contains_key(participant)
+            || self.next.contains_key(participant)
+            || self
+                .finished_contributors
+                .values()
+                .any(|contributors| contributors.contains_key(participant));
+
+        match known {
+            true => self.participant_cohorts.get(participant).copied(),
+            false => None,
+        }
+    }
+
+    ///
+    /// Returns the lowest number of retries left to a current contributor
+    /// across all the chunks of the round.
+    ///
+    pub fn remaining_contribution_retries(&self, participant: &Participant) -> Option<u8> {
+        let budget = self.environment.contribution_retry_budget();
+        let participant_info = self.current_contributors.get(participant)?;
+
+        (0..self.environment.number_of_chunks())
+            .map(|chunk_id| participant_info.remaining_retries(chunk_id, budget))
+            .min()
+    }
+
+    ///
+    /// Returns the number of verifications the contributions of the given participant
+    /// failed since the last successful one.
+    ///
+    pub fn verification_failures(&self, participant: &Participant) -> u32 {
+        self.verification_failures.get(participant).copied().unwrap_or_default()
+    }
+
+    ///
+    /// Consumes one retry of a contributor of the current round for the given chunk,
+    /// after its contribution failed verification.
+    ///
+    /// Returns the number of retries left, or `None` if the retry budget is exhausted.
+    ///
+    pub(super) fn consume_contribution_retry(
+        &mut self,
+        participant: &Participant,
+        chunk_id: u64,
+    ) -> Result<Option<u8>, CoordinatorError> {
+        let budget = self.environment.contribution_retry_budget();
+        let remaining = self
+            .current_round_contributor_mut(participant)?
+            .consume_retry(chunk_id, budget);
+
+        *self.verification_failures.entry(participant.clone()).or_default() += 1;
+
+        Ok(remaining)
+    }
+
+    ///
+    /// Gives the given task back to a contributor of the current round after its
+    /// contribution failed verification, moving the contributor back to the
+    /// current contributors if it already finished the round.
+    ///
+    pub(super) fn retry_contribution_task(
+        &mut self,
+        participant: &Participant,
+        task: &Task,
+        time: &dyn TimeSource,
+    ) -> Result<(), CoordinatorError> {
+        // Fetch the current round height.
+        let current_round_height = self.current_round_height.ok_or(CoordinatorError::RoundHeightNotSet)?;
+
+        let mut participant_info = self.current_round_contributor_mut(participant)?.clone();
+        participant_info.retry_task(task, time)?;
+
+        // Remove the failed contribution from the pending verifications.
+        if self.pending_verification.contains_key(task) {
+            self.remove_pending_verification(task)?;
+        }
+        self.verifications_in_progress.remove(task);
+
+        if let Some(contributors) = self.finished_contributors.get_mut(&current_round_height) {
+            contributors.remove(participant);
+        }
+        self.current_contributors.insert(participant.clone(), participant_info);
+
+        Ok(())
+    }
+
+    ///
+    /// Restores the full retry budget of a contributor of the current round for the
+    /// given chunk, after its contribution passed verification.
+    ///
+    pub(super) fn reset_contribution_retries(&mut self, participant: &Participant, chunk_id: u64) {
+        self.verification_failures.remove(participant);
+
+        // The contributor may have been dropped from the round since.
+        if let Ok(participant_info) = self.current_round_contributor_mut(participant) {
+            participant_info.reset_retries(chunk_id);
+        }
+    }
+
+    ///
+    /// Returns the info of a contributor of the current round, who may have already
+    /// finished the round.
+    ///
+    fn current_round_contributor_mut(
+        &mut self,
+        participant: &Participant,
+    ) -> Result<&mut ParticipantInfo, CoordinatorError> {
+        // Fetch the current round height.
+        let current_round_height = self.current_round_height.ok_or(CoordinatorError::RoundHeightNotSet)?;
+
+        match self.current_contributors.get_mut(participant) {
+            Some(participant_info) => Ok(participant_info),
+            None => self
+                .finished_contributors
+                .get_mut(&current_round_height)
+                .and_then(|contributors| contributors.get_mut(participant))
+                .ok_or_else(|| CoordinatorError::ParticipantNotFound(participant.clone())),
+        }
+    }
+
     ///
     /// Returns a list of participants that were dropped from the current round.
     ///
@@ -1480,6 +2987,14 @@ impl CoordinatorState {
         self.dropped.clone()
     }
 
+    ///
+    /// Returns the number of participants banned from the ceremony.
+    ///
+    #[inline]
+    pub fn number_of_banned_participants(&self) -> usize {
+        self.banned.len()
+    }
+
     ///
     /// Returns the current round height stored in the coordinator state.
     ///
@@ -1499,14 +3014,56 @@ impl CoordinatorState {
     }
 
     ///
-    /// Computes the current ceremony cohort, starting from 0, depending on the cohort duration.
+    /// Returns the timing metrics of the given participant, if they are a contributor of the
+    /// current round or a verifier of the coordinator.
+    ///
+    /// The tasks of a verifier of the coordinator are the verifications assigned to it.
+    ///
+    pub fn participant_metrics(&self, participant: &Participant) -> Option<ParticipantMetrics> {
+        let is_coordinator_verifier = self.environment.is_coordinator_verifier(participant);
+        let (completed_tasks, pending_tasks, assigned_tasks) = match self.current_participant_info(participant) {
+            Some(info) => (
+                info.completed_tasks.len(),
+                info.pending_tasks.len(),
+                info.assigned_tasks.len(),
+            ),
+            None if is_coordinator_verifier => (
+                self.verified_tasks.len(),
+                self.runtime_state
+                    .verifier_locks
+                    .get(participant)
+                    .map_or(0, |locks| locks.len()),
+                self.pending_verification
+                    .values()
+                    .filter(|verifier| *verifier == participant)
+                    .count(),
+            ),
+            None => return None,
+        };
+
+        let metrics = self.current_metrics.as_ref();
+        let averages = metrics.filter(|_| is_coordinator_verifier);
+
+        Some(ParticipantMetrics {
+            seconds_per_task: metrics.and_then(|metrics| metrics.seconds_per_task.get(participant).copied()),
+            completed_tasks,
+            pending_tasks,
+            assigned_tasks,
+            estimated_finish_time: metrics.and_then(|metrics| metrics.estimated_finish_time),
+            contributor_average_per_task: averages.and_then(|metrics| metrics.contributor_average_per_task),
+            verifier_average_per_task: averages.and_then(|metrics| metrics.verifier_average_per_task),
+        })
+    }
+
+    ///
+    /// Computes the current ceremony cohort, starting from 0, depending on the cohort durations.
     ///
     pub fn get_current_cohort_index(&self) -> usize {
         let ceremony_start_time = self.ceremony_start_time;
         let now = OffsetDateTime::now_utc();
         let timestamp_diff = (now.unix_timestamp() - ceremony_start_time.unix_timestamp()) as u64;
 
-        (timestamp_diff / self.cohort_duration) as usize
+        cohort_index(&self.cohort_durations, timestamp_diff)
     }
 
     ///
@@ -1517,6 +3074,16 @@ impl CoordinatorState {
         self.runtime_state.tokens.len()
     }
 
+    ///
+    /// Returns the maximum number of contributors permitted to participate
+    /// in a round of the current cohort.
+    ///
+    #[inline]
+    fn maximum_contributors_per_round(&self) -> usize {
+        self.environment
+            .cohort_maximum_contributors_per_round(self.get_current_cohort_index())
+    }
+
     ///
     /// Returns the list of valid tokens for a given cohort.
     ///
@@ -1525,6 +3092,41 @@ impl CoordinatorState {
         self.runtime_state.tokens.get(cohort)
     }
 
+    ///
+    /// Returns the cohort, starting from 0, of the given token, if any.
+    ///
+    pub fn token_cohort(&self, token: &str) -> Option<usize> {
+        self.runtime_state
+            .tokens
+            .iter()
+            .position(|tokens| tokens.contains(token))
+    }
+
+    ///
+    /// Returns the time at which the given cohort, starting from 0, opens.
+    ///
+    pub fn cohort_start_time(&self, cohort: usize) -> OffsetDateTime {
+        // The last duration applies to all the following cohorts.
+        let elapsed: u64 = (0..cohort)
+            .filter_map(|cohort| self.cohort_durations.get(cohort).or(self.cohort_durations.last()))
+            .sum();
+
+        self.ceremony_start_time + Duration::seconds(elapsed as i64)
+    }
+
+    ///
+    /// Checks that the given token does not belong to a cohort which is not open yet.
+    ///
+    pub fn check_token_cohort(&self, token: &str) -> Result<(), CoordinatorError> {
+        match self.token_cohort(token) {
+            Some(cohort) if cohort > self.get_current_cohort_index() => Err(CoordinatorError::TokenForFutureCohort {
+                cohort: cohort + 1,
+                opens_at: self.cohort_start_time(cohort),
+            }),
+            _ => Ok(()),
+        }
+    }
+
     pub fn get_tokens(&self) -> &Vec<HashSet<String>> {
         &self.runtime_state.tokens
     }
@@ -1538,30 +3140,199 @@ impl CoordinatorState {
     }
 
     ///
-    /// Moves the token from the list of currently in use to the black list
+    /// Returns the tokens currently in use with the associated participants, ordered by token.
     ///
-    pub fn blacklist_participant_token(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
-        let target_token = self
+    pub fn tokens_in_use(&self) -> Vec<TokenInUse> {
+        let mut tokens: Vec<TokenInUse> = self
             .runtime_state
             .tokens_in_use
             .iter()
-            .find(|(_, part)| *part == participant)
-            .ok_or(CoordinatorError::Error(anyhow!(
-                "Missing token for participant {}",
-                participant
-            )))?
-            .0
-            .clone();
+            .map(|(token, participant)| TokenInUse {
+                token: token.clone(),
+                participant: participant.clone(),
+            })
+            .collect();
+        tokens.sort_by(|a, b| a.token.cmp(&b.token));
 
-        // Safe to unwrap here
-        let (token, part) = self.runtime_state.tokens_in_use.remove_entry(&target_token).unwrap();
+        tokens
+    }
 
-        match self.blacklisted_tokens.insert(token, part) {
-            Some(part) => Err(CoordinatorError::Error(anyhow!(
-                "Token {} was already blacklisted for participant {}!",
-                target_token,
-                part
-            ))),
+    ///
+    /// Returns `true` if the given participant is in the queue, in the current
+    /// or next round, or can still reconnect to the current round.
+    ///
+    fn is_active_participant(&self, participant: &Participant) -> bool {
+        self.queue.contains_key(participant)
+            || self.next.contains_key(participant)
+            || self.current_contributors.contains_key(participant)
+            || self.current_verifiers.contains_key(participant)
+            || self.recently_dropped.contains_key(participant)
+    }
+
+    ///
+    /// Removes the given token from the tokens in use, if the participant
+    /// using it is no longer active, and returns the participant.
+    ///
+    pub(super) fn release_token(&mut self, token: &str) -> Result<Participant, CoordinatorError> {
+        let participant = self
+            .runtime_state
+            .tokens_in_use
+            .get(token)
+            .ok_or(CoordinatorError::TokenNotInUse)?
+            .clone();
+
+        if self.is_active_participant(&participant) {
+            return Err(CoordinatorError::TokenStillInUse(participant));
+        }
+
+        self.runtime_state.tokens_in_use.remove(token);
+
+        Ok(participant)
+    }
+
+    ///
+    /// Starts a resumable upload of the contribution of the given current
+    /// contributor and returns it. An upload of the same contribution, with
+    /// the same size and checksum, is resumed with the parts received so far.
+    /// Any other upload of the contributor is discarded.
+    ///
+    pub(super) fn initiate_chunk_upload(
+        &mut self,
+        participant: &Participant,
+        round_height: u64,
+        total_size: u64,
+        checksum: String,
+        rng: &dyn RngSource,
+    ) -> Result<ChunkUpload, CoordinatorError> {
+        if !self.is_current_contributor(participant) {
+            return Err(CoordinatorError::ParticipantNotFound(participant.clone()));
+        }
+
+        if round_height != self.current_round_height() {
+            return Err(CoordinatorError::RoundHeightMismatch);
+        }
+
+        if let Some(upload) = self.runtime_state.chunk_uploads.get(participant) {
+            if (upload.round_height, upload.total_size, &upload.checksum) == (round_height, total_size, &checksum) {
+                return Ok(upload.clone());
+            }
+        }
+
+        let mut id = [0u8; 16];
+        rng.fill_bytes(&mut id);
+        let upload = ChunkUpload {
+            upload_id: hex::encode(id),
+            participant: participant.clone(),
+            round_height,
+            total_size,
+            checksum,
+            parts: BTreeMap::new(),
+        };
+        self.runtime_state
+            .chunk_uploads
+            .insert(participant.clone(), upload.clone());
+
+        Ok(upload)
+    }
+
+    ///
+    /// Returns the given resumable upload of the contributor.
+    ///
+    pub fn chunk_upload(&self, participant: &Participant, upload_id: &str) -> Result<&ChunkUpload, CoordinatorError> {
+        self.runtime_state
+            .chunk_uploads
+            .get(participant)
+            .filter(|upload| upload.upload_id == upload_id)
+            .ok_or(CoordinatorError::ChunkUploadMissing)
+    }
+
+    ///
+    /// Checks that a part can be added to the given resumable upload of the
+    /// contributor. A part must lie within the contribution and can only
+    /// overlap a previous part starting at the same offset, which it replaces.
+    ///
+    pub fn check_chunk_upload_part(
+        &self,
+        participant: &Participant,
+        upload_id: &str,
+        offset: u64,
+        length: u64,
+    ) -> Result<&ChunkUpload, CoordinatorError> {
+        let upload = self.chunk_upload(participant, upload_id)?;
+
+        let end = offset
+            .checked_add(length)
+            .ok_or(CoordinatorError::ChunkUploadPartInvalid)?;
+        if length == 0 || end > upload.total_size {
+            return Err(CoordinatorError::ChunkUploadPartInvalid);
+        }
+
+        let overlaps = upload.parts.iter().any(|(part_offset, part_length)| {
+            *part_offset != offset && offset < part_offset + part_length && *part_offset < end
+        });
+        if overlaps {
+            return Err(CoordinatorError::ChunkUploadPartInvalid);
+        }
+
+        Ok(upload)
+    }
+
+    ///
+    /// Records a part of the given resumable upload of the contributor.
+    ///
+    pub(super) fn add_chunk_upload_part(
+        &mut self,
+        participant: &Participant,
+        upload_id: &str,
+        offset: u64,
+        length: u64,
+    ) -> Result<(), CoordinatorError> {
+        self.check_chunk_upload_part(participant, upload_id, offset, length)?;
+        if let Some(upload) = self.runtime_state.chunk_uploads.get_mut(participant) {
+            upload.parts.insert(offset, length);
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Removes the given resumable upload of the contributor and returns it.
+    ///
+    pub(super) fn remove_chunk_upload(
+        &mut self,
+        participant: &Participant,
+        upload_id: &str,
+    ) -> Result<ChunkUpload, CoordinatorError> {
+        self.chunk_upload(participant, upload_id)?;
+
+        Ok(self.runtime_state.chunk_uploads.remove(participant).unwrap())
+    }
+
+    ///
+    /// Moves the token from the list of currently in use to the black list
+    ///
+    pub fn blacklist_participant_token(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
+        let target_token = self
+            .runtime_state
+            .tokens_in_use
+            .iter()
+            .find(|(_, part)| *part == participant)
+            .ok_or(CoordinatorError::Error(anyhow!(
+                "Missing token for participant {}",
+                participant
+            )))?
+            .0
+            .clone();
+
+        // Safe to unwrap here
+        let (token, part) = self.runtime_state.tokens_in_use.remove_entry(&target_token).unwrap();
+
+        match self.blacklisted_tokens.insert(token, part) {
+            Some(part) => Err(CoordinatorError::Error(anyhow!(
+                "Token {} was already blacklisted for participant {}!",
+                target_token,
+                part
+            ))),
             None => Ok(()),
         }
     }
@@ -1607,6 +3378,42 @@ impl CoordinatorState {
         self.blacklisted_tokens.contains_key(token)
     }
 
+    ///
+    /// Returns the blacklisted tokens and ip addresses with the associated participants.
+    ///
+    /// If `redact_tokens` is set, only the first characters of each token are returned.
+    ///
+    pub fn blacklists(&self, redact_tokens: bool) -> Blacklists {
+        let mut tokens: Vec<BlacklistedToken> = self
+            .blacklisted_tokens
+            .iter()
+            .map(|(token, participant)| BlacklistedToken {
+                token: token.clone(),
+                participant: participant.clone(),
+            })
+            .collect();
+        tokens.sort_by(|a, b| a.token.cmp(&b.token));
+
+        if redact_tokens {
+            for entry in tokens.iter_mut() {
+                let prefix: String = entry.token.chars().take(REDACTED_TOKEN_PREFIX_LENGTH).collect();
+                entry.token = format!("{}...", prefix);
+            }
+        }
+
+        let mut ips: Vec<BlacklistedIp> = self
+            .blacklisted_ips
+            .iter()
+            .map(|(ip, participant)| BlacklistedIp {
+                ip: *ip,
+                participant: participant.clone(),
+            })
+            .collect();
+        ips.sort_by_key(|entry| entry.ip);
+
+        Blacklists { tokens, ips }
+    }
+
     ///
     /// Returns `true` if all participants in the current round have no more pending chunks.
     ///
@@ -1616,6 +3423,10 @@ impl CoordinatorState {
         self.pending_verification.is_empty()
             // Check that all current contributors are finished.
             && self.current_contributors.is_empty()
+            // Check that no contributor can reconnect to the round.
+            && self.recently_dropped.is_empty()
+            // Check that no contribution is held by the settle window.
+            && self.settling_tasks.is_empty()
     }
 
     ///
@@ -1659,39 +3470,51 @@ impl CoordinatorState {
     ///
     #[inline]
     pub(super) fn is_precommit_next_round_ready(&self, time: &dyn TimeSource) -> bool {
+        let blockers = self.advance_blockers(time);
+        for blocker in &blockers {
+            trace!("{}", blocker);
+        }
+
+        blockers.is_empty()
+    }
+
+    ///
+    /// Returns the reasons preventing the precommit for the next round,
+    /// see [CoordinatorState::is_precommit_next_round_ready].
+    ///
+    /// The precommit is ready if and only if the returned list is empty.
+    ///
+    pub(super) fn advance_blockers(&self, time: &dyn TimeSource) -> Vec<String> {
+        let mut blockers = vec![];
+
         // Check that the coordinator is initialized and is not already in a precommit stage.
         if self.status == CoordinatorStatus::Initializing || self.status == CoordinatorStatus::Precommit {
-            return false;
+            blockers.push(format!("Coordinator is in the {:?} stage", self.status));
         }
 
         // Check that the queue contains participants.
         if self.queue.is_empty() {
-            trace!("Queue is currently empty");
-            return false;
+            blockers.push("Queue is currently empty".to_string());
         }
 
         // Check that the current round height is set.
         if self.current_round_height.is_none() {
-            warn!("Current round height is not set in the coordinator state");
-            return false;
+            blockers.push("Current round height is not set in the coordinator state".to_string());
         }
 
         // Check that the current round has been aggregated.
         if self.current_round_height() > 0 && !self.is_current_round_aggregated() {
-            trace!("Current round has not been aggregated");
-            return false;
+            blockers.push("Current round has not been aggregated".to_string());
         }
 
         // Check that the time to trigger the next round has been reached.
         if let Some(metrics) = &self.current_metrics {
-            if let Some(next_round_after) = metrics.next_round_after {
-                if time.now_utc() < next_round_after {
-                    trace!("Required queue wait time has not been reached yet");
-                    return false;
+            match metrics.next_round_after {
+                Some(next_round_after) if time.now_utc() < next_round_after => {
+                    blockers.push("Required queue wait time has not been reached yet".to_string())
                 }
-            } else {
-                trace!("Required queue wait time has not been set yet");
-                return false;
+                Some(_) => (),
+                None => blockers.push("Required queue wait time has not been set yet".to_string()),
             }
         }
 
@@ -1700,30 +3523,31 @@ impl CoordinatorState {
 
         // Fetch the state of assigned contributors for the next round in the queue.
         let minimum_contributors = self.environment.minimum_contributors_per_round();
-        let maximum_contributors = self.environment.maximum_contributors_per_round();
-        let number_of_assigned_contributors = self
-            .queue
-            .clone()
-            .into_par_iter()
-            .filter(|(p, (_, rh, _, _))| p.is_contributor() && rh.unwrap_or_default() == next_round_height)
-            .count();
-
-        trace!(
-            "Prepare precommit status - {} contributors assigned ({}-{} required)",
-            number_of_assigned_contributors,
-            minimum_contributors,
-            maximum_contributors,
-        );
+        let maximum_contributors = self.maximum_contributors_per_round();
+        let number_of_assigned_contributors = self.number_of_assigned_contributors(next_round_height);
 
         // Check that the next round contains a permitted number of contributors.
         if number_of_assigned_contributors < minimum_contributors
             || number_of_assigned_contributors > maximum_contributors
         {
-            trace!("Insufficient or unauthorized number of contributors");
-            return false;
+            blockers.push(format!(
+                "{} contributors assigned to round {} ({}-{} required)",
+                number_of_assigned_contributors, next_round_height, minimum_contributors, maximum_contributors,
+            ));
         }
 
-        true
+        blockers
+    }
+
+    ///
+    /// Returns the number of contributors in the queue assigned to the given round.
+    ///
+    #[inline]
+    fn number_of_assigned_contributors(&self, round_height: u64) -> usize {
+        self.queue
+            .par_iter()
+            .filter(|(p, (_, rh, _, _))| p.is_contributor() && rh.unwrap_or_default() == round_height)
+            .count()
     }
 
     ///
@@ -1733,12 +3557,16 @@ impl CoordinatorState {
         &self,
         participant: &Participant,
         participant_ip: Option<&IpAddr>,
+        time: &dyn TimeSource,
     ) -> Result<(), CoordinatorError> {
-        // Check that the pariticipant IP is not known.
-        if let Some(ip) = participant_ip {
-            if *IP_BAN && (self.blacklisted_ips.contains_key(ip) || self.runtime_state.current_ips.contains_key(ip)) {
-                return Err(CoordinatorError::ParticipantIpAlreadyAdded);
-            }
+        // Check that the queue accepts new participants.
+        if self.queue_frozen {
+            return Err(CoordinatorError::QueueFrozen);
+        }
+
+        // Check that the pariticipant IP is not known, unless exempted from the ban.
+        if participant_ip.map_or(false, |ip| self.is_ip_banned(ip)) {
+            return Err(CoordinatorError::ParticipantIpAlreadyAdded);
         }
 
         // Check that the participant is not banned from participating.
@@ -1762,6 +3590,16 @@ impl CoordinatorState {
                 return Err(CoordinatorError::ParticipantAlreadyAdded);
             }
         }
+        if self.archived_finished_contributors.contains(participant) {
+            return Err(CoordinatorError::ParticipantAlreadyAdded);
+        }
+
+        // Check that the participant did not leave the queue too recently.
+        if let Some(left_at) = self.left_queue.get(participant) {
+            if time.now_utc() < *left_at + self.environment.queue_rejoin_cooldown() {
+                return Err(CoordinatorError::ParticipantRejoinedQueueTooSoon);
+            }
+        }
 
         match participant {
             Participant::Contributor(_) => {
@@ -1785,6 +3623,18 @@ impl CoordinatorState {
         Ok(())
     }
 
+    ///
+    /// Returns the reliability score of the given participant, from [MAX_RELIABILITY_SCORE]
+    /// down to 0. Each drop of the participant from a round and each failed verification
+    /// since their last successful one lowers the score by one.
+    ///
+    pub fn reliability_score(&self, participant: &Participant) -> u8 {
+        let drops = self.dropped.iter().filter(|info| &info.id == participant).count();
+        let failures = self.verification_failures.get(participant).copied().unwrap_or(0) as usize;
+
+        MAX_RELIABILITY_SCORE.saturating_sub((drops + failures).min(MAX_RELIABILITY_SCORE as usize) as u8)
+    }
+
     ///
     /// Adds the given participant to the queue if they are permitted to participate.
     ///
@@ -1804,9 +3654,13 @@ impl CoordinatorState {
             (reliability_score, None, time.now_utc(), time.now_utc()),
         );
 
-        // Add ip (if env set and if any) to the set of currently known addresses
+        // Record the cohort of the participant, which determines the tokens valid for them.
+        let cohort = self.get_current_cohort_index();
+        self.participant_cohorts.insert(participant.clone(), cohort);
+
+        // Add ip (if env set, if any and if not exempted) to the set of currently known addresses
         if *IP_BAN {
-            if let Some(ip) = participant_ip {
+            if let Some(ip) = participant_ip.filter(|ip| !self.runtime_state.ip_ban_exemptions.contains(ip)) {
                 self.runtime_state.current_ips.insert(ip, participant.clone());
             }
         }
@@ -1819,6 +3673,14 @@ impl CoordinatorState {
         Ok(())
     }
 
+    ///
+    /// Sets the region of the given participant in the queue.
+    ///
+    #[inline]
+    pub(super) fn set_participant_region(&mut self, participant: Participant, region: String) {
+        self.queue_regions.insert(participant, region);
+    }
+
     ///
     /// Removes the given participant from the queue.
     ///
@@ -1836,6 +3698,82 @@ impl CoordinatorState {
 
         // Remove the participant from the queue.
         self.queue.remove(participant);
+        self.queue_regions.remove(participant);
+
+        Ok(())
+    }
+
+    ///
+    /// Removes the given contributor from the queue on their own request,
+    /// and saves the current time to prevent them from rejoining the
+    /// queue before the cooldown set in the environment.
+    ///
+    pub(super) fn leave_queue(
+        &mut self,
+        participant: &Participant,
+        time: &dyn TimeSource,
+    ) -> Result<(), CoordinatorError> {
+        // Check that the participant is a contributor.
+        if !participant.is_contributor() {
+            return Err(CoordinatorError::ExpectedContributor);
+        }
+
+        // Remove the participant from the queue.
+        self.remove_from_queue(participant)?;
+
+        // Remove ip (if any) and token from the current ones to allow the participant to rejoin.
+        self.runtime_state.current_ips.retain(|_, part| part != participant);
+        self.runtime_state.tokens_in_use.retain(|_, part| part != participant);
+
+        self.prune_left_queue(time);
+        self.left_queue.insert(participant.clone(), time.now_utc());
+
+        Ok(())
+    }
+
+    /// Removes the contributors whose rejoin cooldown has expired from the ones who left the queue.
+    fn prune_left_queue(&mut self, time: &dyn TimeSource) {
+        let now = time.now_utc();
+        let cooldown = self.environment.queue_rejoin_cooldown();
+        self.left_queue.retain(|_, left_at| now < *left_at + cooldown);
+    }
+
+    ///
+    /// Returns the number of chunks the given contributor can lock at once, the
+    /// limit of the environment unless a lower one is set for the contributor.
+    ///
+    pub(super) fn contributor_lock_chunk_limit(&self, participant: &Participant) -> usize {
+        let limit = self.environment.contributor_lock_chunk_limit();
+
+        match self.per_participant_lock_limit.get(participant) {
+            Some(participant_limit) => limit.min(*participant_limit),
+            None => limit,
+        }
+    }
+
+    ///
+    /// Sets the chunk lock limit of the given contributor, or clears it to fall back
+    /// to the limit of the environment.
+    ///
+    /// Returns [CoordinatorError::LockLimitInvalid] for a limit of 0, which would
+    /// prevent the contributor from locking any chunk until dropped as stalled.
+    ///
+    pub(super) fn set_participant_lock_limit(
+        &mut self,
+        participant: &Participant,
+        limit: Option<usize>,
+    ) -> Result<(), CoordinatorError> {
+        if !participant.is_contributor() {
+            return Err(CoordinatorError::ExpectedContributor);
+        }
+        if limit == Some(0) {
+            return Err(CoordinatorError::LockLimitInvalid);
+        }
+
+        match limit {
+            Some(limit) => self.per_participant_lock_limit.insert(participant.clone(), limit),
+            None => self.per_participant_lock_limit.remove(participant),
+        };
 
         Ok(())
     }
@@ -1849,7 +3787,7 @@ impl CoordinatorState {
         time: &dyn TimeSource,
     ) -> Result<Task, CoordinatorError> {
         // Fetch the contributor chunk lock limit.
-        let contributor_limit = self.environment.contributor_lock_chunk_limit();
+        let contributor_limit = self.contributor_lock_chunk_limit(participant);
 
         // Remove the next chunk ID from the pending chunks of the given participant.
         match participant {
@@ -1872,71 +3810,217 @@ impl CoordinatorState {
     }
 
     ///
-    /// Adds the given chunk ID to the locks held by the given participant.
+    /// Pops the task for the given chunk ID from the assigned tasks of the given
+    /// contributor, out of the order of the assigned tasks. The previous contribution
+    /// to the chunk must be completed.
     ///
-    #[inline]
-    pub(super) fn acquired_lock(
+    /// Requires [Environment::out_of_order_contributions].
+    ///
+    pub(super) fn fetch_task_for_chunk(
         &mut self,
         participant: &Participant,
         chunk_id: u64,
         time: &dyn TimeSource,
-    ) -> Result<(), CoordinatorError> {
+    ) -> Result<Task, CoordinatorError> {
+        // Check that contributing out of order is enabled.
+        if !self.environment.out_of_order_contributions() {
+            return Err(CoordinatorError::OutOfOrderContributionsDisabled);
+        }
+
         // Check that the chunk ID is valid.
         if chunk_id > self.environment.number_of_chunks() {
             return Err(CoordinatorError::ChunkIdInvalid);
         }
 
-        match participant {
-            Participant::Contributor(_) => match self.current_contributors.get_mut(participant) {
-                // Acquire the chunk lock for the contributor.
-                Some(participant) => Ok(participant.acquired_lock(chunk_id, time)?),
-                None => Err(CoordinatorError::ParticipantNotFound(participant.clone())),
+        // Fetch the contributor chunk lock limit.
+        let contributor_limit = self.contributor_lock_chunk_limit(participant);
+
+        let participant_info = match participant {
+            Participant::Contributor(_) => match self.current_contributors.get(participant) {
+                Some(participant_info) => participant_info,
+                None => return Err(CoordinatorError::ParticipantNotFound(participant.clone())),
             },
-            Participant::Verifier(_) => {
-                return Err(CoordinatorError::ExpectedContributor);
+            Participant::Verifier(_) => return Err(CoordinatorError::ExpectedContributor),
+        };
+
+        // Check that the participant is holding less than the chunk lock limit.
+        if participant_info.locked_chunks.len() >= contributor_limit {
+            return Err(CoordinatorError::ParticipantHasLockedMaximumChunks);
+        }
+
+        // Check that the previous contribution to the chunk is completed.
+        if let Some(task) = participant_info.assigned_tasks.iter().find(|task| task.contains(chunk_id)) {
+            if !self.is_previous_contribution_completed(task) {
+                return Err(CoordinatorError::PreviousContributionMissing { current_task: *task });
             }
         }
+
+        let task = match self.current_contributors.get_mut(participant) {
+            Some(participant_info) => participant_info.pop_task_for_chunk(chunk_id, time)?,
+            None => return Err(CoordinatorError::ParticipantNotFound(participant.clone())),
+        };
+        self.start_task_timer(participant, &task, time);
+
+        Ok(task)
     }
 
     ///
-    /// Reverts the given (chunk ID, contribution ID) task to the list of assigned tasks
-    /// from the list of pending tasks.
+    /// Returns `true` if no current contributor is still expected to contribute
+    /// to the chunk of the given task before it.
     ///
-    #[inline]
-    pub(super) fn rollback_pending_task(
-        &mut self,
-        participant: &Participant,
-        task: Task,
-        time: &dyn TimeSource,
-    ) -> Result<(), CoordinatorError> {
-        // Check that the chunk ID is valid.
-        if task.chunk_id() > self.environment.number_of_chunks() {
-            return Err(CoordinatorError::ChunkIdInvalid);
-        }
-
-        match self.current_participant_info_mut(participant) {
-            Some(participant) => Ok(participant.rollback_pending_task(task, time)?),
-            None => Err(CoordinatorError::ParticipantNotFound(participant.clone())),
-        }
+    fn is_previous_contribution_completed(&self, task: &Task) -> bool {
+        !self.current_contributors.values().any(|participant_info| {
+            participant_info
+                .assigned_tasks
+                .iter()
+                .chain(participant_info.pending_tasks.iter())
+                .any(|t| t.chunk_id() == task.chunk_id() && t.contribution_id() < task.contribution_id())
+        })
     }
 
-    pub(super) fn rollback_locked_task(
+    ///
+    /// Reorders the assigned tasks of the given contributor of the current round.
+    ///
+    pub(super) fn reorder_assigned_tasks(
         &mut self,
         participant: &Participant,
-        task: Task,
+        tasks: Vec<Task>,
         time: &dyn TimeSource,
     ) -> Result<(), CoordinatorError> {
-        // Check that the chunk ID is valid.
-        if task.chunk_id() > self.environment.number_of_chunks() {
-            return Err(CoordinatorError::ChunkIdInvalid);
-        }
-
-        match self.current_participant_info_mut(participant) {
-            Some(participant) => participant.rollback_locked_task(task, time),
-            None => return Err(CoordinatorError::ParticipantNotFound(participant.clone())),
+        match participant {
+            Participant::Contributor(_) => match self.current_contributors.get_mut(participant) {
+                Some(participant_info) => participant_info.reorder_assigned_tasks(tasks, time),
+                None => Err(CoordinatorError::ParticipantNotFound(participant.clone())),
+            },
+            Participant::Verifier(_) => Err(CoordinatorError::ExpectedContributor),
         }
     }
 
+    ///
+    /// Returns the epoch of the current round.
+    ///
+    #[inline]
+    pub fn round_epoch(&self) -> u64 {
+        self.round_epoch
+    }
+
+    ///
+    /// Returns [CoordinatorError::StaleContribution] if the lock of the given contributor
+    /// on the given chunk ID was acquired before the last reset of the current round.
+    ///
+    pub(super) fn check_contribution_epoch(
+        &self,
+        participant: &Participant,
+        chunk_id: u64,
+    ) -> Result<(), CoordinatorError> {
+        let participant_info = self
+            .current_contributors
+            .get(participant)
+            .ok_or_else(|| CoordinatorError::ParticipantNotFound(participant.clone()))?;
+
+        match participant_info.lock_epoch(chunk_id) {
+            Some(epoch) if epoch != self.round_epoch => Err(CoordinatorError::StaleContribution),
+            _ => Ok(()),
+        }
+    }
+
+    ///
+    /// Adds the given chunk ID to the locks held by the given participant.
+    ///
+    #[inline]
+    pub(super) fn acquired_lock(
+        &mut self,
+        participant: &Participant,
+        chunk_id: u64,
+        time: &dyn TimeSource,
+    ) -> Result<(), CoordinatorError> {
+        // Check that the chunk ID is valid.
+        if chunk_id > self.environment.number_of_chunks() {
+            return Err(CoordinatorError::ChunkIdInvalid);
+        }
+
+        let epoch = self.round_epoch;
+
+        match participant {
+            Participant::Contributor(_) => match self.current_contributors.get_mut(participant) {
+                // Acquire the chunk lock for the contributor.
+                Some(participant) => Ok(participant.acquired_lock(chunk_id, epoch, time)?),
+                None => Err(CoordinatorError::ParticipantNotFound(participant.clone())),
+            },
+            Participant::Verifier(_) => {
+                return Err(CoordinatorError::ExpectedContributor);
+            }
+        }
+    }
+
+    ///
+    /// Reverts the given (chunk ID, contribution ID) task to the list of assigned tasks
+    /// from the list of pending tasks.
+    ///
+    #[inline]
+    pub(super) fn rollback_pending_task(
+        &mut self,
+        participant: &Participant,
+        task: Task,
+        time: &dyn TimeSource,
+    ) -> Result<(), CoordinatorError> {
+        // Check that the chunk ID is valid.
+        if task.chunk_id() > self.environment.number_of_chunks() {
+            return Err(CoordinatorError::ChunkIdInvalid);
+        }
+
+        match self.current_participant_info_mut(participant) {
+            Some(participant) => Ok(participant.rollback_pending_task(task, time)?),
+            None => Err(CoordinatorError::ParticipantNotFound(participant.clone())),
+        }
+    }
+
+    pub(super) fn rollback_locked_task(
+        &mut self,
+        participant: &Participant,
+        task: Task,
+        time: &dyn TimeSource,
+    ) -> Result<(), CoordinatorError> {
+        // Check that the chunk ID is valid.
+        if task.chunk_id() > self.environment.number_of_chunks() {
+            return Err(CoordinatorError::ChunkIdInvalid);
+        }
+
+        match self.current_participant_info_mut(participant) {
+            Some(participant) => participant.rollback_locked_task(task, time),
+            None => return Err(CoordinatorError::ParticipantNotFound(participant.clone())),
+        }
+    }
+
+    ///
+    /// Releases the lock held by the given participant on the given chunk ID, rolling back
+    /// the locked task to the front of the assigned tasks of the participant, who is
+    /// otherwise left intact.
+    ///
+    /// Returns the released task.
+    ///
+    pub(super) fn force_release_lock(
+        &mut self,
+        participant: &Participant,
+        chunk_id: u64,
+        time: &dyn TimeSource,
+    ) -> Result<Task, CoordinatorError> {
+        // Fetch the task locked by the participant on the given chunk ID.
+        let task = match self.lookup_pending_task(participant, chunk_id) {
+            Ok(Some(task)) => *task,
+            Ok(None) | Err(CoordinatorError::ParticipantDidntLockChunkId) => {
+                return Err(CoordinatorError::ChunkNotLockedOrByWrongParticipant);
+            }
+            Err(error) => return Err(error),
+        };
+
+        warn!("Force releasing the lock on chunk {} held by {}", chunk_id, participant);
+
+        self.rollback_locked_task(participant, task, time)?;
+
+        Ok(task)
+    }
+
     ///
     /// Returns the (chunk ID, contribution ID) task if the given participant has the
     /// given chunk ID in a pending task.
@@ -2066,7 +4150,7 @@ impl CoordinatorState {
 
     ///
     /// Adds the given (chunk ID, contribution ID) task to the pending verification set.
-    /// The verification task is then assigned to the verifier with the least number of tasks in its queue.
+    /// The verification task is then assigned to a verifier by the [VerifierSelector] of the coordinator.
     ///
     #[inline]
     pub(super) fn add_pending_verification(&mut self, task: &Task) -> Result<(), CoordinatorError> {
@@ -2080,12 +4164,16 @@ impl CoordinatorState {
             return Err(CoordinatorError::ChunkIdAlreadyAdded);
         }
 
+        // Assign the task to one of the verifiers of the current cohort.
         let verifier = self
-            .environment
-            .coordinator_verifiers()
-            .first()
-            .ok_or_else(|| CoordinatorError::VerifierMissing)?
-            .clone();
+            .runtime_state
+            .verifier_selector
+            .select(
+                task,
+                self.environment.cohort_verifiers(self.get_current_cohort_index()),
+                &self.pending_verification,
+            )
+            .ok_or_else(|| CoordinatorError::VerifierMissing)?;
 
         info!(
             "Adding (chunk {}, contribution {}) to pending verifications",
@@ -2093,15 +4181,203 @@ impl CoordinatorState {
             task.contribution_id(),
         );
 
-        self.pending_verification.insert(task.clone(), verifier.clone());
+        self.pending_verification.insert(task.clone(), verifier);
 
         Ok(())
     }
 
+    ///
+    /// Sets the selector of the verifier to which the tasks pending verification are assigned.
+    ///
+    pub(super) fn set_verifier_selector(&mut self, selector: Arc<dyn VerifierSelector>) {
+        self.runtime_state.verifier_selector = selector;
+    }
+
+    ///
+    /// Sets the source of randomness of the weighted selection of the replacement contributors.
+    ///
+    pub(super) fn set_random_source(&mut self, rng: Arc<dyn RngSource>) {
+        self.runtime_state.rng = rng;
+    }
+
     pub fn get_pending_verifications(&self) -> &HashMap<Task, Participant> {
         &self.pending_verification
     }
 
+    ///
+    /// Returns `true` if the current round is in its settle window,
+    /// during which the submitted contributions are held back from verification.
+    ///
+    pub fn is_settling(&self, time: &dyn TimeSource) -> bool {
+        self.settle_until
+            .map_or(false, |settle_until| time.now_utc() < settle_until)
+    }
+
+    ///
+    /// Returns the tasks submitted during the settle window which are not yet pending verification.
+    ///
+    pub fn settling_tasks(&self) -> &[Task] {
+        &self.settling_tasks
+    }
+
+    ///
+    /// Adds the tasks held during the settle window to the pending verifications,
+    /// once the settle window of the current round has ended.
+    ///
+    pub(super) fn update_settled_tasks(&mut self, time: &dyn TimeSource) -> Result<(), CoordinatorError> {
+        if self.settle_until.is_none() || self.is_settling(time) {
+            return Ok(());
+        }
+
+        self.settle_until = None;
+        for task in std::mem::take(&mut self.settling_tasks) {
+            self.add_pending_verification(&task)?;
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Locks and returns the next task pending verification assigned to the given verifier,
+    /// or `None` if all the tasks assigned to the verifier are already locked.
+    ///
+    /// A verifier can hold at most [`Environment::verifier_lock_chunk_limit`] tasks at once,
+    /// the lock on a task is released once its verification is completed.
+    ///
+    pub(super) fn fetch_verifier_task(&mut self, verifier: &Participant) -> Result<Option<Task>, CoordinatorError> {
+        if !verifier.is_verifier() {
+            return Err(CoordinatorError::ExpectedVerifier);
+        }
+
+        let pending_verification = &self.pending_verification;
+        let verifications_in_progress = &mut self.verifications_in_progress;
+        let locks = self.runtime_state.verifier_locks.entry(verifier.clone()).or_default();
+
+        // Release the locks on tasks that are no longer pending, e.g. after a round reset.
+        locks.retain(|task| pending_verification.get(task) == Some(verifier));
+        verifications_in_progress.retain(|task| pending_verification.contains_key(task));
+
+        if locks.len() >= self.environment.verifier_lock_chunk_limit() {
+            return Err(CoordinatorError::ParticipantHasLockedMaximumChunks);
+        }
+
+        let task = pending_verification
+            .iter()
+            .filter(|(task, assignee)| *assignee == verifier && !locks.contains(task))
+            .map(|(task, _)| *task)
+            // Resume first the verifications interrupted by a restart of the coordinator.
+            .min_by_key(|task| {
+                (
+                    !verifications_in_progress.contains(task),
+                    task.chunk_id(),
+                    task.contribution_id(),
+                )
+            });
+
+        if let Some(task) = task {
+            if verifications_in_progress.contains(&task) {
+                info!(
+                    "Resuming verification of chunk {} contribution {}",
+                    task.chunk_id(),
+                    task.contribution_id()
+                );
+            }
+            locks.insert(task);
+        }
+
+        Ok(task)
+    }
+
+    ///
+    /// Releases the lock of the given verifier on the given task, if any, so that
+    /// the task can be fetched again after a failed verification.
+    ///
+    pub(super) fn release_verifier_task(&mut self, verifier: &Participant, task: &Task) {
+        if let Some(locks) = self.runtime_state.verifier_locks.get_mut(verifier) {
+            locks.remove(task);
+        }
+    }
+
+    ///
+    /// Marks the verification of the given pending task as started, until it is removed from the pending verifications.
+    ///
+    pub(super) fn started_verification(&mut self, task: &Task, time: &dyn TimeSource) -> Result<(), CoordinatorError> {
+        let verifier = self
+            .pending_verification
+            .get(task)
+            .ok_or(CoordinatorError::ChunkIdMissing)?
+            .clone();
+
+        self.verifications_in_progress.insert(*task);
+        self.start_task_timer(&verifier, task, time);
+
+        Ok(())
+    }
+
+    ///
+    /// Returns `true` if the verification of the given task was started but not completed.
+    ///
+    pub fn is_verification_in_progress(&self, task: &Task) -> bool {
+        self.verifications_in_progress.contains(task)
+    }
+
+    ///
+    /// Returns the activity in the current round of the verifiers of the
+    /// coordinator, ordered by verifier.
+    ///
+    pub fn verifier_activity(&self) -> Vec<VerifierActivity> {
+        let mut verifiers: Vec<&Participant> = self
+            .environment
+            .coordinator_verifiers()
+            .iter()
+            .chain(self.pending_verification.values())
+            .collect();
+        verifiers.sort_by_key(|verifier| verifier.to_string());
+        verifiers.dedup();
+
+        verifiers
+            .into_iter()
+            .map(|verifier| {
+                let task_timer = self
+                    .current_metrics
+                    .as_ref()
+                    .and_then(|metrics| metrics.task_timer.get(verifier));
+                let timed_tasks: Vec<(i64, i64)> = task_timer
+                    .iter()
+                    .flat_map(|tasks| tasks.values())
+                    .filter_map(|(start, end)| end.map(|end| (*start, end)))
+                    .collect();
+                let last_active_at = task_timer
+                    .iter()
+                    .flat_map(|tasks| tasks.values())
+                    .map(|(start, end)| end.unwrap_or(*start))
+                    .max()
+                    .and_then(|timestamp| OffsetDateTime::from_unix_timestamp(timestamp).ok());
+
+                VerifierActivity {
+                    verifier: verifier.clone(),
+                    tasks_verified: timed_tasks.len() as u64,
+                    average_seconds_per_task: match timed_tasks.is_empty() {
+                        true => None,
+                        false => Some(
+                            timed_tasks
+                                .iter()
+                                .map(|(start, end)| (end - start).max(0) as u64)
+                                .sum::<u64>()
+                                / timed_tasks.len() as u64,
+                        ),
+                    },
+                    pending_verifications: self
+                        .pending_verification
+                        .values()
+                        .filter(|assignee| *assignee == verifier)
+                        .count() as u64,
+                    last_active_at,
+                }
+            })
+            .collect()
+    }
+
     ///
     /// Remove the given (chunk ID, contribution ID) task from the map of chunks that are pending verification.
     ///
@@ -2124,14 +4400,54 @@ impl CoordinatorState {
         );
 
         // Remove the task from the pending verification.
-        let _verifier = self
+        let verifier = self
             .pending_verification
             .remove(task)
             .ok_or(CoordinatorError::VerifierMissing)?;
 
+        // Release the verifier lock on the task, if any.
+        if let Some(locks) = self.runtime_state.verifier_locks.get_mut(&verifier) {
+            locks.remove(task);
+        }
+        self.verifications_in_progress.remove(task);
+        self.verified_tasks.insert(*task);
+
         Ok(())
     }
 
+    ///
+    /// Returns the tasks completed by the contributors of the current round which are
+    /// neither pending verification nor verified, ordered by (chunk ID, contribution ID).
+    ///
+    /// These tasks will never be verified and stall the round.
+    ///
+    pub fn unassigned_completed_tasks(&self) -> Vec<Task> {
+        let finished_contributors = self
+            .current_round_height
+            .and_then(|round_height| self.finished_contributors.get(&round_height));
+
+        let mut tasks: Vec<Task> = self
+            .current_contributors
+            .values()
+            .chain(
+                finished_contributors
+                    .into_iter()
+                    .flat_map(|contributors| contributors.values()),
+            )
+            .flat_map(|participant_info| participant_info.completed_tasks.iter())
+            .filter(|task| {
+                !self.pending_verification.contains_key(task)
+                    && !self.verified_tasks.contains(task)
+                    && !self.settling_tasks.contains(task)
+            })
+            .cloned()
+            .collect();
+        tasks.sort_by_key(|task| task.to_tuple());
+        tasks.dedup();
+
+        tasks
+    }
+
     ///
     /// Adds the given (chunk ID, contribution ID) task to the completed tasks of the given participant,
     /// and removes the chunk ID from the locks held by the given participant.
@@ -2155,6 +4471,17 @@ impl CoordinatorState {
             return Err(CoordinatorError::ChunkIdInvalid);
         }
 
+        // Check that the contribution ID is valid.
+        check_contribution_id(task.contribution_id())?;
+
+        // Check that the contribution builds on the previous one when contributing out of order.
+        if participant.is_contributor()
+            && self.environment.out_of_order_contributions()
+            && !self.is_previous_contribution_completed(task)
+        {
+            return Err(CoordinatorError::PreviousContributionMissing { current_task: *task });
+        }
+
         match participant {
             Participant::Contributor(_) => match self.current_contributors.get_mut(participant) {
                 // Adds the task to the list of completed tasks for the contributor,
@@ -2162,13 +4489,28 @@ impl CoordinatorState {
                 Some(participant_info) => {
                     participant_info.completed_task(task, time)?;
                     self.stop_task_timer(participant, &task, time);
+
+                    // Hold the task until the end of the settle window of the round.
+                    if self.is_settling(time) {
+                        info!(
+                            "Holding (chunk {}, contribution {}) until the end of the settle window",
+                            task.chunk_id(),
+                            task.contribution_id(),
+                        );
+                        self.settling_tasks.push(*task);
+                        return Ok(());
+                    }
+
                     self.add_pending_verification(task)
                 }
                 None => Err(CoordinatorError::ParticipantNotFound(participant.clone())),
             },
             Participant::Verifier(_) => {
                 // Remove the task from the pending verification set.
-                self.remove_pending_verification(task)
+                self.remove_pending_verification(task)?;
+                self.stop_task_timer(participant, &task, time);
+
+                Ok(())
             }
         }
     }
@@ -2305,11 +4647,38 @@ impl CoordinatorState {
     }
 
     /// Set the `current_metrics` ([RoundMetrics]) `next_round_after`
-    /// field to the appropriate value specified in the [Environment].
+    /// field to the appropriate value specified by [CoordinatorState::queue_wait_time].
     fn update_next_round_after(&mut self, time: &dyn TimeSource) {
+        let queue_wait_time = self.queue_wait_time();
+        if let Some(metrics) = &mut self.current_metrics {
+            metrics.next_round_after = Some(time.now_utc() + Duration::seconds(queue_wait_time as i64));
+        }
+    }
+
+    ///
+    /// Returns the number of seconds to wait after aggregation before starting the next round.
+    ///
+    #[inline]
+    pub fn queue_wait_time(&self) -> u64 {
+        self.queue_wait_time
+            .unwrap_or_else(|| self.environment.queue_wait_time())
+    }
+
+    ///
+    /// Sets the number of seconds to wait after aggregation before starting the next round,
+    /// overriding the environment. If the current round is already aggregated, the start
+    /// of the next round is rescheduled from the end of the aggregation.
+    ///
+    #[inline]
+    pub(super) fn set_queue_wait_time(&mut self, seconds: u64) {
+        self.queue_wait_time = Some(seconds);
+
         if let Some(metrics) = &mut self.current_metrics {
-            metrics.next_round_after =
-                Some(time.now_utc() + Duration::seconds(self.environment.queue_wait_time() as i64));
+            if let (Some(_), Some(finished_aggregation_at)) =
+                (metrics.next_round_after, metrics.finished_aggregation_at)
+            {
+                metrics.next_round_after = Some(finished_aggregation_at + Duration::seconds(seconds as i64));
+            }
         }
     }
 
@@ -2379,6 +4748,9 @@ impl CoordinatorState {
 
             // Remove token from the list of current tokens
             self.runtime_state.tokens_in_use.retain(|_, part| part != participant);
+
+            // Discard the resumable upload of the contribution, if any
+            self.runtime_state.chunk_uploads.remove(participant);
         }
 
         // Remove the participant from the queue and precommit, if present.
@@ -2387,6 +4759,7 @@ impl CoordinatorState {
             if self.queue.contains_key(participant) {
                 trace!("Removing {} from the queue", participant);
                 self.queue.remove(participant);
+                self.queue_regions.remove(participant);
             }
 
             // Remove the participant from the precommit for the next round.
@@ -2398,10 +4771,15 @@ impl CoordinatorState {
             }
 
             return Ok(DropParticipant::DropQueue(DropQueueParticipantData {
-                _participant: participant.clone(),
+                participant: participant.clone(),
             }));
         }
 
+        // Restore the contributor waiting to reconnect, if any, to drop it from the current round.
+        if let Some((participant_info, _)) = self.recently_dropped.remove(participant) {
+            self.current_contributors.insert(participant.clone(), participant_info);
+        }
+
         // Fetch the current participant information.
         let participant_info = match participant {
             Participant::Contributor(_) => self
@@ -2442,21 +4820,12 @@ impl CoordinatorState {
         // Drop the contributor from the current round, and update participant info and coordinator state.
         let storage_action: CeremonyStorageAction = match participant {
             Participant::Contributor(_id) => {
-                // TODO (howardwu): Optimization only.
-                //  -----------------------------------------------------------------------------------
-                //  Update this implementation to minimize recomputation by not re-assigning
-                //  tasks for affected contributors which are not affected by the dropped contributor.
-                //  It sounds like a mess, but is easier than you think, once you've loaded state.
-                //  In short, compute the minimum overlapping chunk ID between affected & dropped contributor,
-                //  and reinitialize from there. If there is no overlap, you can skip reinitializing
-                //  any tasks for the affected contributor.
-                //  -----------------------------------------------------------------------------------
-
                 // Set the participant as dropped.
                 let mut dropped_info = participant_info.clone();
                 dropped_info.drop(time)?;
 
-                // Fetch the number of chunks and number of contributors.
+                // Fetch the bucket layout, number of chunks and number of contributors.
+                let bucket_layout = self.environment.bucket_layout();
                 let number_of_chunks = self.environment.number_of_chunks() as u64;
                 let number_of_contributors = self
                     .current_metrics
@@ -2471,78 +4840,68 @@ impl CoordinatorState {
                 let tasks_by_chunk: HashMap<u64, u64> = tasks.iter().map(|task| task.to_tuple()).collect();
 
                 // For every contributor we check if there are affected tasks. If the task
-                // is affected, it will be dropped and reassigned
-                for contributor_info in self.current_contributors.values_mut() {
-                    // If the pending task is in the same chunk with the dropped task
-                    // then it should be recomputed
-                    let (disposing_tasks, pending_tasks) = contributor_info
-                        .pending_tasks
-                        .iter()
-                        .cloned()
-                        .partition(|task| tasks_by_chunk.get(&task.chunk_id()).is_some());
-
-                    // TODO: revisit the handling of disposing_tasks
-                    //       https://github.com/AleoHQ/aleo-setup/issues/249
-
-                    contributor_info.disposing_tasks = disposing_tasks;
-                    contributor_info.pending_tasks = pending_tasks;
-
-                    // If completed task is based on the dropped task, it should also be dropped
-                    let (disposed_tasks, completed_tasks) =
-                        contributor_info.completed_tasks.iter().cloned().partition(|task| {
-                            if let Some(contribution_id) = tasks_by_chunk.get(&task.chunk_id()) {
-                                *contribution_id < task.contribution_id()
-                            } else {
-                                false
-                            }
-                        });
-
-                    // TODO: revisit the handling of disposed_tasks
-                    // https://github.com/AleoHQ/aleo-setup/issues/249
-                    contributor_info.completed_tasks = completed_tasks;
-                    contributor_info.disposed_tasks.extend(disposed_tasks);
-
-                    all_disposed_tasks.extend(contributor_info.disposed_tasks.iter());
-
-                    // Determine the excluded tasks, which are filtered out from the list of newly assigned tasks.
-                    let mut excluded_tasks: HashSet<u64> =
-                        HashSet::from_iter(contributor_info.completed_tasks.iter().map(|task| task.chunk_id()));
-                    excluded_tasks.extend(contributor_info.pending_tasks.iter().map(|task| task.chunk_id()));
+                // is affected, it will be dropped and reassigned. The reassignment of each
+                // contributor only depends on its own tasks, so they are computed in parallel
+                // and applied afterwards, yielding the same assignments as a sequential pass.
+                // Contributors with no affected task are left untouched.
+                let reassignments: HashMap<Participant, Option<TaskReassignment>> = self
+                    .current_contributors
+                    .par_iter()
+                    .map(|(contributor, contributor_info)| {
+                        let reassignment = contributor_info.reassign_dropped_tasks(
+                            &tasks_by_chunk,
+                            bucket_layout,
+                            number_of_chunks,
+                            number_of_contributors,
+                        )?;
+                        Ok((contributor.clone(), reassignment))
+                    })
+                    .collect::<Result<_, CoordinatorError>>()?;
 
-                    // Reassign tasks for the affected contributor.
-                    contributor_info.assigned_tasks =
-                        initialize_tasks(contributor_info.bucket_id, number_of_chunks, number_of_contributors)?
-                            .into_iter()
-                            .filter(|task| !excluded_tasks.contains(&task.chunk_id()))
-                            .collect();
+                for (contributor, reassignment) in reassignments {
+                    if let Some(contributor_info) = self.current_contributors.get_mut(&contributor) {
+                        if let Some(reassignment) = reassignment {
+                            contributor_info.apply_reassignment(reassignment);
+                        }
+                        all_disposed_tasks.extend(contributor_info.disposed_tasks.iter());
+                    }
                 }
 
                 // All verifiers assigned to affected tasks must dispose their affected
                 // pending and completed tasks.
-                for verifier_info in self.current_verifiers.values_mut() {
-                    // Filter the current verifier for pending tasks that have been disposed.
-                    let (disposing_tasks, pending_tasks) = verifier_info
-                        .pending_tasks
-                        .iter()
-                        .cloned()
-                        .partition(|task| all_disposed_tasks.contains(&task));
-
-                    // TODO: revisit the handling of disposing_tasks
-                    //       https://github.com/AleoHQ/aleo-setup/issues/249
-                    verifier_info.pending_tasks = pending_tasks;
-                    verifier_info.disposing_tasks = disposing_tasks;
-
-                    // Filter the current verifier for completed tasks that have been disposed.
-                    let (disposed_tasks, completed_tasks) = verifier_info
-                        .completed_tasks
-                        .iter()
-                        .cloned()
-                        .partition(|task| all_disposed_tasks.contains(&task));
+                let verifier_reassignments: HashMap<Participant, TaskReassignment> = self
+                    .current_verifiers
+                    .par_iter()
+                    .map(|(verifier, verifier_info)| {
+                        // Filter the current verifier for pending tasks that have been disposed.
+                        let (disposing_tasks, pending_tasks) = verifier_info
+                            .pending_tasks
+                            .iter()
+                            .cloned()
+                            .partition(|task| all_disposed_tasks.contains(&task));
+
+                        // Filter the current verifier for completed tasks that have been disposed.
+                        let (disposed_tasks, completed_tasks) = verifier_info
+                            .completed_tasks
+                            .iter()
+                            .cloned()
+                            .partition(|task| all_disposed_tasks.contains(&task));
+
+                        let reassignment = TaskReassignment {
+                            assigned_tasks: verifier_info.assigned_tasks.clone(),
+                            pending_tasks,
+                            completed_tasks,
+                            disposing_tasks,
+                            disposed_tasks,
+                        };
+                        (verifier.clone(), reassignment)
+                    })
+                    .collect();
 
-                    // TODO: revisit the handling of disposed_tasks
-                    //       https://github.com/AleoHQ/aleo-setup/issues/249
-                    verifier_info.completed_tasks = completed_tasks;
-                    verifier_info.disposed_tasks.extend(disposed_tasks);
+                for (verifier, reassignment) in verifier_reassignments {
+                    if let Some(verifier_info) = self.current_verifiers.get_mut(&verifier) {
+                        verifier_info.apply_reassignment(reassignment);
+                    }
                 }
 
                 // Remove the current verifier from the coordinator state.
@@ -2603,7 +4962,7 @@ impl CoordinatorState {
         };
 
         let drop_data = DropCurrentParticpantData {
-            _participant: participant.clone(),
+            participant: participant.clone(),
             storage_action: final_storage_action,
         };
 
@@ -2658,30 +5017,136 @@ impl CoordinatorState {
     }
 
     ///
-    /// Adds a replacement contributor from the coordinator as a current contributor
-    /// and assigns them tasks from the given starting bucket ID.
+    /// Reinstates the given contributor who was dropped from the ceremony by adding them back
+    /// to the queue with their original reliability score and cohort.
     ///
-    #[inline]
-    pub(crate) fn add_replacement_contributor_unsafe(
+    /// A banned contributor must be unbanned before being reinstated.
+    ///
+    pub(super) fn reinstate_dropped_participant(
         &mut self,
-        bucket_id: u64,
+        participant: &Participant,
         time: &dyn TimeSource,
-    ) -> Result<Participant, CoordinatorError> {
-        // Get the contributor assigned to the closest next round or the one who joined the queue first
-        let (next_contributor, contributor_info) = match self
-            .queue_contributors()
-            .iter()
-            .filter(|(_, (_, rh, _, _))| rh.is_some())
-            .min_by_key(|(_, (_, rh, _, _))| rh)
-        {
-            Some((part, info)) => (part.clone(), info.clone()),
-            None => self
-                .queue_contributors()
-                .iter()
-                .min_by_key(|(_, (_, _, _, tj))| tj)
-                .cloned()
-                .ok_or(CoordinatorError::QueueIsEmpty)?,
-        };
+    ) -> Result<(), CoordinatorError> {
+        // Check that the participant is a contributor.
+        if !participant.is_contributor() {
+            return Err(CoordinatorError::ExpectedContributor);
+        }
+
+        // Check that the participant is not banned.
+        if self.banned.contains(participant) {
+            return Err(CoordinatorError::ParticipantBanned);
+        }
+
+        // Fetch the information of the participant from the last time they were dropped.
+        let participant_info = self
+            .dropped
+            .iter()
+            .rev()
+            .find(|info| info.id == *participant)
+            .ok_or(CoordinatorError::ParticipantMissing)?
+            .clone();
+
+        // Check that the participant did not join the ceremony again since.
+        if self.queue.contains_key(participant)
+            || self.next.contains_key(participant)
+            || self.current_contributors.contains_key(participant)
+            || self.recently_dropped.contains_key(participant)
+        {
+            return Err(CoordinatorError::ParticipantAlreadyAdded);
+        }
+
+        warn!("Reinstating {} to the queue", participant);
+
+        // Remove the participant from the dropped participants.
+        self.dropped.retain(|info| info.id != *participant);
+
+        // Move the ip and token of the participant from the blacklists back to the ones in use, so that nobody else
+        // can use them and they are blacklisted again once the participant contributes.
+        let (ips, blacklisted_ips): (HashMap<_, _>, _) = std::mem::take(&mut self.blacklisted_ips)
+            .into_iter()
+            .partition(|(_, part)| part == participant);
+        self.blacklisted_ips = blacklisted_ips;
+        self.runtime_state.current_ips.extend(ips);
+
+        let (tokens, blacklisted_tokens): (HashMap<_, _>, _) = std::mem::take(&mut self.blacklisted_tokens)
+            .into_iter()
+            .partition(|(_, part)| part == participant);
+        self.blacklisted_tokens = blacklisted_tokens;
+        self.runtime_state.tokens_in_use.extend(tokens);
+
+        // Add the participant back to the queue.
+        self.queue.insert(
+            participant.clone(),
+            (participant_info.reliability, None, time.now_utc(), time.now_utc()),
+        );
+
+        Ok(())
+    }
+
+    ///
+    /// Returns the contributor of the queue to pull in as a replacement, following
+    /// the replacement selection of the environment.
+    ///
+    fn select_replacement_contributor(
+        &self,
+    ) -> Result<(Participant, (u8, Option<u64>, OffsetDateTime, OffsetDateTime)), CoordinatorError> {
+        let contributors = self.queue_contributors();
+
+        match self.environment.replacement_selection() {
+            // Get the contributor assigned to the closest next round or the one who joined the queue first
+            ReplacementSelection::Deterministic => match contributors
+                .iter()
+                .filter(|(_, (_, rh, _, _))| rh.is_some())
+                .min_by_key(|(_, (_, rh, _, _))| rh)
+            {
+                Some((part, info)) => Ok((part.clone(), info.clone())),
+                None => contributors
+                    .iter()
+                    .min_by_key(|(_, (_, _, _, tj))| tj)
+                    .cloned()
+                    .ok_or(CoordinatorError::QueueIsEmpty),
+            },
+            // Draw a contributor with a probability proportional to their weight
+            ReplacementSelection::Weighted => {
+                let weights: Vec<u64> = contributors
+                    .iter()
+                    .map(|(part, (reliability, _, _, _))| {
+                        let replacement_count = self.replacement_counts.get(part).copied().unwrap_or_default();
+                        ReplacementSelection::weight(*reliability, replacement_count).max(1)
+                    })
+                    .collect();
+                let total_weight: u64 = weights.iter().sum();
+                if total_weight == 0 {
+                    return Err(CoordinatorError::QueueIsEmpty);
+                }
+
+                let mut seed = [0u8; 32];
+                self.runtime_state.rng.fill_bytes(&mut seed);
+                let mut draw = ChaChaRng::from_seed(seed).gen_range(0..total_weight);
+                for (contributor, weight) in contributors.iter().zip(weights) {
+                    if draw < weight {
+                        return Ok(contributor.clone());
+                    }
+                    draw -= weight;
+                }
+
+                Err(CoordinatorError::QueueIsEmpty)
+            }
+        }
+    }
+
+    ///
+    /// Adds a replacement contributor from the coordinator as a current contributor
+    /// and assigns them tasks from the given starting bucket ID.
+    ///
+    #[inline]
+    pub(crate) fn add_replacement_contributor_unsafe(
+        &mut self,
+        bucket_id: u64,
+        time: &dyn TimeSource,
+    ) -> Result<Participant, CoordinatorError> {
+        let (next_contributor, contributor_info) = self.select_replacement_contributor()?;
+        *self.replacement_counts.entry(next_contributor.clone()).or_default() += 1;
 
         // Remove participant from queue
         self.remove_from_queue(&next_contributor)?;
@@ -2696,7 +5161,11 @@ impl CoordinatorState {
         // TODO (raychu86): Update the participant info (interleave the tasks by contribution id).
         // TODO (raychu86): Add tasks to the replacement contributor if it already has pending tasks.
 
-        let tasks = initialize_tasks(bucket_id, self.environment.number_of_chunks(), number_of_contributors)?;
+        let tasks = self.environment.bucket_layout().initialize_tasks(
+            bucket_id,
+            self.environment.number_of_chunks(),
+            number_of_contributors,
+        )?;
         let mut participant_info = ParticipantInfo::new(
             next_contributor.clone(),
             self.current_round_height(),
@@ -2736,6 +5205,31 @@ impl CoordinatorState {
         self.manual_lock = false;
     }
 
+    ///
+    /// Returns `true` if the queue is frozen and rejects new participants.
+    ///
+    #[inline]
+    pub(super) fn is_queue_frozen(&self) -> bool {
+        self.queue_frozen
+    }
+
+    ///
+    /// Freezes the queue, rejecting new participants. The participants already in the
+    /// queue or in the current round proceed normally.
+    ///
+    #[inline]
+    pub(super) fn freeze_queue(&mut self) {
+        self.queue_frozen = true;
+    }
+
+    ///
+    /// Unfreezes the queue, accepting new participants again.
+    ///
+    #[inline]
+    pub(super) fn unfreeze_queue(&mut self) {
+        self.queue_frozen = false;
+    }
+
     ///
     /// Returns the current round height stored in the coordinator state.
     ///
@@ -2746,6 +5240,30 @@ impl CoordinatorState {
         self.ceremony_start_time
     }
 
+    ///
+    /// Returns the participants in the queue with their reliability, last seen
+    /// and joined times, in the order they are assigned to the next rounds:
+    /// by time joined, after their reliability tier if prioritized.
+    ///
+    fn prioritized_queue(&self) -> Vec<(Participant, u8, OffsetDateTime, OffsetDateTime)> {
+        let mut queue: Vec<_> = self
+            .queue
+            .clone()
+            .into_par_iter()
+            .map(|(p, (r, _, ls, j))| (p, r, ls, j))
+            .collect();
+        match self.environment.queue_priority() {
+            QueuePriority::Fifo => queue.par_sort_by(|a, b| (a.3).cmp(&b.3)),
+            QueuePriority::Reliability => queue.par_sort_by(|a, b| {
+                QueuePriority::reliability_tier(b.1)
+                    .cmp(&QueuePriority::reliability_tier(a.1))
+                    .then((a.3).cmp(&b.3))
+            }),
+        }
+
+        queue
+    }
+
     ///
     /// Updates the state of the queue for all waiting participants.
     ///
@@ -2757,14 +5275,8 @@ impl CoordinatorState {
             _ => return Err(CoordinatorError::RoundHeightNotSet),
         };
 
-        // Sort the participants in the queue by time joined.
-        let mut queue: Vec<_> = self
-            .queue
-            .clone()
-            .into_par_iter()
-            .map(|(p, (r, _, ls, j))| (p, r, ls, j))
-            .collect();
-        queue.par_sort_by(|a, b| (a.3).cmp(&b.3));
+        // Sort the participants in the queue by time joined, after their reliability tier if prioritized.
+        let queue = self.prioritized_queue();
 
         // Parse the queue participants into contributors and verifiers,
         // and check that they are not banned participants.
@@ -2774,33 +5286,62 @@ impl CoordinatorState {
             .filter(|(p, _, _, _)| p.is_contributor() && !self.banned.contains(&p))
             .collect();
 
-        // Fetch the permitted number of contributors
-        let maximum_contributors = self.environment.maximum_contributors_per_round();
+        // Fetch the permitted number of contributors, per round of the current cohort and per region.
+        let maximum_contributors = self.maximum_contributors_per_round();
+        let maximum_contributors_per_region = self.environment.maximum_contributors_per_region();
 
         // Initialize the updated queue.
         let mut updated_queue = HashMap::with_capacity(contributors.len());
 
-        // Update assigned round height for each contributor.
-        for (index, round) in contributors.chunks(maximum_contributors).enumerate() {
-            for (contributor, reliability, last_seen, joined) in round.into_iter() {
-                let assigned_round = next_round + index as u64;
-                trace!(
-                    "Assigning contributor {} who joined at {} with reliability {} in queue to round {}",
-                    contributor,
-                    joined,
-                    reliability,
-                    assigned_round
-                );
-                updated_queue.insert(
-                    contributor.clone(),
-                    (*reliability, Some(assigned_round), *last_seen, *joined),
-                );
+        // Initialize the number of contributors assigned to each round, in total and per region.
+        let mut assigned_rounds: Vec<(usize, HashMap<&String, usize>)> = Vec::new();
+
+        // Update assigned round height for each contributor, picking the earliest
+        // round with a spot left for the region of the contributor.
+        for (contributor, reliability, last_seen, joined) in contributors.iter() {
+            let region = self.queue_regions.get(contributor);
+            let index = assigned_rounds
+                .iter()
+                .position(|(total, regions)| {
+                    *total < maximum_contributors
+                        && match (region, maximum_contributors_per_region) {
+                            (Some(region), Some(maximum)) => regions.get(region).copied().unwrap_or_default() < maximum,
+                            _ => true,
+                        }
+                })
+                .unwrap_or_else(|| {
+                    assigned_rounds.push(Default::default());
+                    assigned_rounds.len() - 1
+                });
+
+            let (total, regions) = &mut assigned_rounds[index];
+            *total += 1;
+            if let Some(region) = region {
+                *regions.entry(region).or_default() += 1;
             }
+
+            let assigned_round = next_round + index as u64;
+            trace!(
+                "Assigning contributor {} who joined at {} with reliability {} in queue to round {}",
+                contributor,
+                joined,
+                reliability,
+                assigned_round
+            );
+            updated_queue.insert(
+                contributor.clone(),
+                (*reliability, Some(assigned_round), *last_seen, *joined),
+            );
         }
 
         // Set the queue to the updated queue.
         self.queue = updated_queue;
 
+        // Forget the regions of the participants which left the queue.
+        let queue = &self.queue;
+        self.queue_regions
+            .retain(|participant, _| queue.contains_key(participant));
+
         Ok(())
     }
 
@@ -2819,9 +5360,8 @@ impl CoordinatorState {
         let mut newly_finished: HashMap<Participant, ParticipantInfo> = HashMap::new();
 
         // Iterate through all of the current contributors and check if they have finished.
-        self.current_contributors = self
-            .current_contributors
-            .clone()
+        let current_contributors = self.current_contributors.clone();
+        self.current_contributors = current_contributors
             .into_iter()
             .filter(|(contributor, contributor_info)| {
                 // Check if the contributor has finished.
@@ -2835,6 +5375,13 @@ impl CoordinatorState {
                     return true;
                 }
 
+                // Check that the contributor completed every expected task, if required.
+                // Otherwise, it is dropped in [CoordinatorState::update_dropped_participants].
+                if let Err(error) = self.check_strict_task_completion(contributor_info) {
+                    warn!("{} cannot finish the round: {:?}", contributor, error);
+                    return true;
+                }
+
                 // Add the contributor to the set of finished contributors.
                 newly_finished.insert(contributor.clone(), finished_info);
 
@@ -2859,6 +5406,30 @@ impl CoordinatorState {
         Ok(())
     }
 
+    ///
+    /// Checks that the given current contributor completed every task expected
+    /// of its bucket, if the strict task completion is enabled.
+    ///
+    fn check_strict_task_completion(&self, contributor_info: &ParticipantInfo) -> Result<(), CoordinatorError> {
+        if !self.environment.strict_task_completion() {
+            return Ok(());
+        }
+
+        // The buckets are split among the current and finished contributors of the round.
+        let current_round_height = self.current_round_height.ok_or(CoordinatorError::RoundHeightNotSet)?;
+        let number_of_contributors = (self.current_contributors.len()
+            + self
+                .finished_contributors
+                .get(&current_round_height)
+                .map_or(0, |contributors| contributors.len())) as u64;
+
+        contributor_info.check_expected_tasks(
+            self.environment.bucket_layout(),
+            self.environment.number_of_chunks(),
+            number_of_contributors,
+        )
+    }
+
     ///
     /// Updates the current round for dropped participants.
     ///
@@ -2868,11 +5439,24 @@ impl CoordinatorState {
         &mut self,
         time: &dyn TimeSource,
     ) -> Result<Vec<DropParticipant>, CoordinatorError> {
-        Ok(self
-            .update_contributor_seen_drops(time)?
+        let mut budget = DropBudget::new(self.environment.maximum_drops_per_update());
+
+        let drops = self
+            .update_contributor_seen_drops(time, &mut budget)?
             .into_iter()
-            .chain(self.update_participant_lock_drops(time)?.into_iter())
-            .collect())
+            .chain(self.update_participant_lock_drops(time, &mut budget)?.into_iter())
+            .chain(self.update_recently_dropped(time, &mut budget)?.into_iter())
+            .chain(self.update_incomplete_contributor_drops(time, &mut budget)?.into_iter())
+            .collect();
+
+        if !budget.deferred.is_empty() {
+            warn!(
+                "Deferred dropping {} participants to the next update cycle",
+                budget.deferred.len()
+            );
+        }
+
+        Ok(drops)
     }
 
     pub(super) fn update_dropped_queued_participants(&mut self, time: &dyn TimeSource) -> Result<(), CoordinatorError> {
@@ -2886,9 +5470,62 @@ impl CoordinatorState {
             }
         }
 
+        self.prune_left_queue(time);
+
         Ok(())
     }
 
+    ///
+    /// Returns the (contributor, task) pairs of the locks which have been held for longer than
+    /// [crate::environment::Environment]'s `participant_lock_timeout` as of now.
+    ///
+    pub(super) fn orphaned_locks(&self, time: &dyn TimeSource) -> Vec<(Participant, Task)> {
+        let participant_lock_timeout = self.environment.participant_lock_timeout();
+        let now = time.now_utc();
+
+        self.current_contributors
+            .iter()
+            .flat_map(|(participant, participant_info)| {
+                participant_info
+                    .locked_chunks
+                    .values()
+                    .filter(move |lock| now - lock.lock_time > participant_lock_timeout)
+                    .filter_map(move |lock| {
+                        participant_info
+                            .pending_tasks
+                            .iter()
+                            .find(|task| task.contains(lock.chunk_id))
+                            .map(|task| (participant.clone(), *task))
+                    })
+            })
+            .collect()
+    }
+
+    ///
+    /// Removes the duplicate tasks from the task lists of the participants
+    /// of the current and next rounds, preserving the order of the tasks.
+    ///
+    /// Returns the number of removed tasks.
+    ///
+    pub(super) fn dedup_tasks(&mut self) -> usize {
+        let mut number_of_duplicates = 0;
+
+        for (participant, participant_info) in self
+            .current_contributors
+            .iter_mut()
+            .chain(self.current_verifiers.iter_mut())
+            .chain(self.next.iter_mut())
+        {
+            let duplicates = participant_info.dedup_tasks();
+            if duplicates > 0 {
+                warn!("Removed {} duplicate tasks of {}", duplicates, participant);
+                number_of_duplicates += duplicates;
+            }
+        }
+
+        number_of_duplicates
+    }
+
     /// This will drop a participant (verifier or contributor) if it
     /// has been holding a lock for longer than
     /// [crate::environment::Environment]'s
@@ -2896,6 +5533,7 @@ impl CoordinatorState {
     fn update_participant_lock_drops(
         &mut self,
         time: &dyn TimeSource,
+        budget: &mut DropBudget,
     ) -> Result<Vec<DropParticipant>, CoordinatorError> {
         // Fetch the timeout threshold for contributors.
         let participant_lock_timeout = self.environment.participant_lock_timeout();
@@ -2931,6 +5569,13 @@ impl CoordinatorState {
                     Participant::Verifier(_) => false,
                 };
 
+                // Check that the participant can be dropped in this update cycle.
+                let should_drop = !self.is_coordinator_contributor(&participant)
+                    && (!exceeded_chunk_names.is_empty() || exceeded_round_timeout);
+                if should_drop && !budget.try_consume(participant) {
+                    return None;
+                }
+
                 if !self.is_coordinator_contributor(&participant) && !exceeded_chunk_names.is_empty() {
                     let exceeded_chunks_string: String = exceeded_chunk_names.join(", ");
 
@@ -2960,12 +5605,48 @@ impl CoordinatorState {
     /// This will drop a contributor if it hasn't been seen for more
     /// than [crate::environment::Environment]'s
     /// `contributor_seen_timeout`.
+    ///
+    /// Drops the current contributors which have no task left, but did not complete
+    /// every task expected of their bucket while the strict task completion is enabled.
+    /// The drop reassigns their missing tasks, so that the round can still finish.
+    ///
+    fn update_incomplete_contributor_drops(
+        &mut self,
+        time: &dyn TimeSource,
+        budget: &mut DropBudget,
+    ) -> Result<Vec<DropParticipant>, CoordinatorError> {
+        let incomplete: Vec<Participant> = self
+            .current_contributors
+            .iter()
+            .filter(|(_, participant_info)| {
+                !participant_info.is_finished()
+                    && participant_info.clone().finish(time).is_ok()
+                    && self.check_strict_task_completion(participant_info).is_err()
+            })
+            .map(|(participant, _)| participant.clone())
+            .collect();
+
+        incomplete
+            .iter()
+            .filter(|participant| budget.try_consume(participant))
+            .map(|participant| {
+                tracing::warn!(
+                    "Dropping participant {} because it did not complete every task expected of its bucket.",
+                    participant
+                );
+                self.drop_participant(participant, time)
+            })
+            .collect()
+    }
+
     fn update_contributor_seen_drops(
         &mut self,
         time: &dyn TimeSource,
+        budget: &mut DropBudget,
     ) -> Result<Vec<DropParticipant>, CoordinatorError> {
         // Fetch the timeout threshold for contributors.
         let contributor_seen_timeout = self.environment.contributor_seen_timeout();
+        let reconnect_window = self.environment.reconnect_window();
 
         // Fetch the current time.
         let now = time.now_utc();
@@ -2979,6 +5660,41 @@ impl CoordinatorState {
 
                 // Check if the participant is still live and not a coordinator contributor.
                 if elapsed > contributor_seen_timeout && !self.is_coordinator_contributor(&participant) {
+                    // Check that the participant can be dropped in this update cycle.
+                    if !budget.try_consume(participant) {
+                        return None;
+                    }
+
+                    // Let the participant reconnect before dropping it, if enabled.
+                    if reconnect_window > Duration::ZERO {
+                        tracing::warn!(
+                            "Disconnecting participant {} because it has exceeded the maximum ({:?}s) allowed time \
+                            since it was last seen by the coordinator, it can reconnect within {:?}s.",
+                            participant,
+                            contributor_seen_timeout.whole_seconds(),
+                            reconnect_window.whole_seconds()
+                        );
+
+                        // Release the locks of the participant, the coordinator releases them in the round.
+                        let mut participant_info = participant_info.clone();
+                        let locked_tasks: Vec<Task> = participant_info
+                            .pending_tasks
+                            .iter()
+                            .filter(|task| participant_info.locked_chunks.contains_key(&task.chunk_id()))
+                            .cloned()
+                            .collect();
+                        for task in locked_tasks {
+                            if let Err(error) = participant_info.rollback_locked_task(task, time) {
+                                return Some(Err(error));
+                            }
+                        }
+
+                        self.current_contributors.remove(participant);
+                        self.recently_dropped
+                            .insert(participant.clone(), (participant_info, now));
+                        return None;
+                    }
+
                     tracing::warn!(
                         "Dropping participant {} because it has exceeded the maximum ({:?}s) allowed time \
                         since it was last seen by the coordinator (last seen {:?}s ago).",
@@ -2995,6 +5711,103 @@ impl CoordinatorState {
             .collect()
     }
 
+    /// This will drop a contributor disconnected for not being seen if it
+    /// has not reconnected within [crate::environment::Environment]'s
+    /// `reconnect_window`.
+    fn update_recently_dropped(
+        &mut self,
+        time: &dyn TimeSource,
+        budget: &mut DropBudget,
+    ) -> Result<Vec<DropParticipant>, CoordinatorError> {
+        // Fetch the reconnect window.
+        let reconnect_window = self.environment.reconnect_window();
+
+        // Fetch the current time.
+        let now = time.now_utc();
+
+        let expired: Vec<Participant> = self
+            .recently_dropped
+            .iter()
+            .filter(|(_, (_, dropped_at))| now - *dropped_at > reconnect_window)
+            .map(|(participant, _)| participant.clone())
+            .collect();
+
+        let mut drops = Vec::new();
+        for participant in expired {
+            // Check that the participant was not dropped by a round reset in this update cycle.
+            if !self.recently_dropped.contains_key(&participant) || !budget.try_consume(&participant) {
+                continue;
+            }
+
+            tracing::warn!(
+                "Dropping participant {} because it has not reconnected within the allowed time ({:?}s).",
+                participant,
+                reconnect_window.whole_seconds()
+            );
+            drops.push(self.drop_participant(&participant, time)?);
+        }
+
+        Ok(drops)
+    }
+
+    ///
+    /// Returns `true` if the given contributor was disconnected for not being seen
+    /// and can still reconnect to the current round.
+    ///
+    pub(super) fn is_disconnected(&self, participant: &Participant) -> bool {
+        self.recently_dropped.contains_key(participant)
+    }
+
+    ///
+    /// Drops the contributors waiting to reconnect to the current round, without
+    /// reassigning their tasks, and returns them.
+    ///
+    fn drop_recently_dropped(&mut self, time: &dyn TimeSource) -> Result<Vec<Participant>, CoordinatorError> {
+        let mut participants = Vec::new();
+
+        for (participant, (mut participant_info, _)) in std::mem::take(&mut self.recently_dropped) {
+            warn!("Dropping {} from the ceremony", participant);
+
+            // Remove ip (if any) and token from the lists of current ones to allow the participant to rejoin.
+            self.runtime_state.current_ips.retain(|_, part| *part != participant);
+            self.runtime_state.tokens_in_use.retain(|_, part| *part != participant);
+
+            participant_info.drop(time)?;
+            self.dropped.push(participant_info);
+            participants.push(participant);
+        }
+
+        Ok(participants)
+    }
+
+    ///
+    /// Restores a contributor disconnected for not being seen to the current round,
+    /// with its progress intact, if it reconnects within the reconnect window.
+    ///
+    pub(super) fn reconnect(
+        &mut self,
+        participant: &Participant,
+        time: &dyn TimeSource,
+    ) -> Result<(), CoordinatorError> {
+        let now = time.now_utc();
+
+        match self.recently_dropped.get(participant) {
+            // Let the next update drop the participant once the reconnect window has elapsed.
+            Some((_, dropped_at)) if now - *dropped_at > self.environment.reconnect_window() => {
+                Err(CoordinatorError::ParticipantWasDropped)
+            }
+            Some(_) => {
+                if let Some((mut participant_info, _)) = self.recently_dropped.remove(participant) {
+                    info!("Reconnecting {} to the current round", participant);
+                    participant_info.last_seen = now;
+                    self.current_contributors.insert(participant.clone(), participant_info);
+                }
+                Ok(())
+            }
+            None => Err(CoordinatorError::ParticipantNotFound(participant.clone())),
+        }
+    }
+
     ///
     /// Updates the list of dropped participants for participants who
     /// meet the ban criteria of the coordinator.
@@ -3123,7 +5936,7 @@ impl CoordinatorState {
                         let estimated_aggregation_time = (contributor_average_per_task + verifier_average_per_task)
                             * self.environment.number_of_chunks();
 
-                        let estimated_queue_time = self.environment.queue_wait_time();
+                        let estimated_queue_time = self.queue_wait_time();
 
                         // Note that these are extremely rough estimates. These should be updated
                         // to be much more granular, if used in mission-critical logic.
@@ -3205,14 +6018,8 @@ impl CoordinatorState {
             }
         }
 
-        // Parse the queued participants for the next round and split into contributors and verifiers.
-        let mut contributors: Vec<(_, (_, _, _, _))> = self
-            .queue
-            .clone()
-            .into_par_iter()
-            .map(|(p, (r, rh, ls, j))| (p, (r, rh.unwrap_or_default(), ls, j)))
-            .filter(|(p, (_, rh, _, _))| p.is_contributor() && *rh == next_round_height)
-            .collect();
+        // Parse the queued contributors for the next round, sorted by their reliability.
+        let contributors = self.next_round_queue_contributors(next_round_height);
 
         // Check that each participant in the next round is authorized.
         if contributors
@@ -3226,7 +6033,7 @@ impl CoordinatorState {
 
         // Check that the next round contains a permitted number of contributors.
         let minimum_contributors = self.environment.minimum_contributors_per_round();
-        let maximum_contributors = self.environment.maximum_contributors_per_round();
+        let maximum_contributors = self.maximum_contributors_per_round();
         let number_of_contributors = contributors.len();
         if number_of_contributors < minimum_contributors || number_of_contributors > maximum_contributors {
             warn!(
@@ -3250,6 +6057,9 @@ impl CoordinatorState {
              *   N := NUMBER_OF_CONTRIBUTORS
              *   BUCKET_SIZE := NUMBER_OF_CHUNKS / NUMBER_OF_CONTRIBUTORS
              *
+             *   With the adaptive bucket layout, the buckets are resized to
+             *   distribute the remaining chunks, see `initialize_adaptive_tasks`.
+             *
              * ***********************************************************************************
              *
              *   [    BUCKET 1    |    BUCKET 2    |    BUCKET 3    |  . . .  |    BUCKET N    ]
@@ -3279,16 +6089,15 @@ impl CoordinatorState {
              * ***********************************************************************************
              */
 
-            // Sort the contributors by their reliability (in order of highest to lowest number).
-            contributors.par_sort_by(|a, b| ((b.1).0).cmp(&(&a.1).0));
-
-            // Fetch the number of chunks and bucket size.
+            // Fetch the number of chunks and bucket layout.
             let number_of_chunks = self.environment.number_of_chunks() as u64;
+            let bucket_layout = self.environment.bucket_layout();
 
             // Set the chunk ID ordering for each contributor.
             for (bucket_index, (participant, (reliability, next_round, _, _))) in contributors.into_iter().enumerate() {
                 let bucket_id = bucket_index as u64;
-                let tasks = initialize_tasks(bucket_id, number_of_chunks, number_of_contributors as u64)?;
+                let tasks =
+                    bucket_layout.initialize_tasks(bucket_id, number_of_chunks, number_of_contributors as u64)?;
 
                 // Check that each participant is storing the correct round height.
                 if next_round != next_round_height && next_round != current_round_height + 1 {
@@ -3320,6 +6129,9 @@ impl CoordinatorState {
         // Update the coordinator state to the updated queue and next map.
         self.queue = queue;
         self.next = next;
+        let queue = &self.queue;
+        self.queue_regions
+            .retain(|participant, _| queue.contains_key(participant));
 
         // Set the coordinator status to precommit.
         self.status = CoordinatorStatus::Precommit;
@@ -3334,7 +6146,7 @@ impl CoordinatorState {
     /// if the commit was unauthorized.
     ///
     #[inline]
-    pub(super) fn commit_next_round(&mut self) {
+    pub(super) fn commit_next_round(&mut self, time: &dyn TimeSource) {
         // Check that the coordinator is authorized to advance to the next round.
         if self.status != CoordinatorStatus::Precommit {
             error!("Coordinator is not in the precommit stage and cannot advance the round");
@@ -3354,9 +6166,20 @@ impl CoordinatorState {
         };
         self.current_round_height = Some(next_round_height);
 
+        // Clear the verified tasks of the previous round.
+        self.verified_tasks.clear();
+
         // Set the current status to the commit.
         self.status = CoordinatorStatus::Commit;
 
+        // Open the settle window of the round, if enabled.
+        let settle_time = self.environment.contribution_settle_time();
+        self.settle_until = if settle_time > Duration::ZERO {
+            Some(time.now_utc() + settle_time)
+        } else {
+            None
+        };
+
         // Add all participants from next to current.
         let mut number_of_contributors = 0;
         let mut number_of_verifiers = 0;
@@ -3439,47 +6262,83 @@ impl CoordinatorState {
     }
 
     ///
-    /// Returns the status of the coordinator state.
+    /// Cancels the precommit for the next round on operator request,
+    /// returning the participants that were moved from the next round
+    /// back into the queue.
     ///
     #[inline]
-    pub(super) fn status_report(&self, time: &dyn TimeSource) -> String {
-        let current_round_height = self.current_round_height.unwrap_or_default();
-        let next_round_height = current_round_height + 1;
+    pub(super) fn cancel_precommit_next_round(
+        &mut self,
+        time: &dyn TimeSource,
+    ) -> Result<Vec<Participant>, CoordinatorError> {
+        // Check that the coordinator is in the precommit stage.
+        if self.status != CoordinatorStatus::Precommit {
+            return Err(CoordinatorError::NextRoundNotInPrecommit);
+        }
 
-        let current_round_finished = match self.is_current_round_finished() {
-            true => format!("Round {} is finished", current_round_height),
-            false => format!("Round {} is in progress", current_round_height),
-        };
-        let current_round_aggregated = match (self.is_current_round_aggregated(), current_round_height) {
-            (_, 0) => format!("Round {} can skip aggregation", current_round_height),
-            (true, _) => format!("Round {} is aggregated", current_round_height),
-            (false, _) => format!("Round {} is awaiting aggregation", current_round_height),
-        };
-        let precommit_next_round_ready = match self.is_precommit_next_round_ready(time) {
-            true => format!("Round {} is ready to begin", next_round_height),
-            false => format!("Round {} is awaiting participants", next_round_height),
-        };
+        // Fetch the participants staged for the next round.
+        let participants: Vec<Participant> = self.next.keys().cloned().collect();
 
-        let number_of_current_contributors = self.current_contributors.len();
-        let number_of_finished_contributors = self
-            .finished_contributors
-            .get(&current_round_height)
-            .get_or_insert(&HashMap::new())
-            .len();
-        let number_of_pending_verifications = self.pending_verification.len();
+        // Add the staged participants back into the queue.
+        self.rollback_next_round(time);
 
-        // Parse the queue for assigned contributors and verifiers of the next round.
-        let number_of_assigned_contributors = self
-            .queue
-            .clone()
-            .into_par_iter()
-            .filter(|(p, (_, rh, _, _))| p.is_contributor() && rh.unwrap_or_default() == next_round_height)
-            .count();
+        info!("Precommit for the next round has been canceled");
+        Ok(participants)
+    }
+
+    ///
+    /// Returns a machine-readable snapshot of the coordinator state.
+    ///
+    pub(super) fn diagnostics(&self, time: &dyn TimeSource) -> Diagnostics {
+        let current_round_height = self.current_round_height();
+        let advance_blockers = self.advance_blockers(time);
+
+        Diagnostics {
+            status: self.status.clone(),
+            current_round_height: self.current_round_height,
+            is_current_round_finished: self.is_current_round_finished(),
+            is_current_round_aggregated: self.is_current_round_aggregated(),
+            is_precommit_next_round_ready: advance_blockers.is_empty(),
+            number_of_queue_contributors: self.number_of_queue_contributors(),
+            number_of_assigned_contributors: self.number_of_assigned_contributors(current_round_height + 1),
+            number_of_current_contributors: self.current_contributors.len(),
+            number_of_finished_contributors: self
+                .finished_contributors
+                .get(&current_round_height)
+                .map_or(0, |contributors| contributors.len()),
+            number_of_pending_verifications: self.pending_verification.len(),
+            number_of_dropped_participants: self.dropped.len(),
+            number_of_banned_participants: self.banned.len(),
+            advance_blockers,
+        }
+    }
 
-        let number_of_queue_contributors = self.number_of_queue_contributors();
+    ///
+    /// Returns the status of the coordinator state.
+    ///
+    #[inline]
+    pub(super) fn status_report(&self, time: &dyn TimeSource, update_interval: Option<std::time::Duration>) -> String {
+        let diagnostics = self.diagnostics(time);
+        let current_round_height = self.current_round_height();
+        let next_round_height = current_round_height + 1;
 
-        let number_of_dropped_participants = self.dropped.len();
-        let number_of_banned_participants = self.banned.len();
+        let current_round_finished = match diagnostics.is_current_round_finished {
+            true => format!("Round {} is finished", current_round_height),
+            false => format!("Round {} is in progress", current_round_height),
+        };
+        let current_round_aggregated = match (diagnostics.is_current_round_aggregated, current_round_height) {
+            (_, 0) => format!("Round {} can skip aggregation", current_round_height),
+            (true, _) => format!("Round {} is aggregated", current_round_height),
+            (false, _) => format!("Round {} is awaiting aggregation", current_round_height),
+        };
+        let precommit_next_round_ready = match diagnostics.is_precommit_next_round_ready {
+            true => format!("Round {} is ready to begin", next_round_height),
+            false => format!("Round {} is awaiting participants", next_round_height),
+        };
+        let update_interval = match update_interval {
+            Some(interval) => format!("{} seconds between the updates", interval.as_secs()),
+            None => "Updates are run manually".to_string(),
+        };
 
         format!(
             r#"
@@ -3501,17 +6360,20 @@ impl CoordinatorState {
     | {} participants dropped
     | {} participants banned
 
+    | {}
+
     "#,
             current_round_finished,
             current_round_aggregated,
             precommit_next_round_ready,
-            number_of_current_contributors,
-            number_of_finished_contributors,
-            number_of_pending_verifications,
-            number_of_assigned_contributors,
-            number_of_queue_contributors,
-            number_of_dropped_participants,
-            number_of_banned_participants
+            diagnostics.number_of_current_contributors,
+            diagnostics.number_of_finished_contributors,
+            diagnostics.number_of_pending_verifications,
+            diagnostics.number_of_assigned_contributors,
+            diagnostics.number_of_queue_contributors,
+            diagnostics.number_of_dropped_participants,
+            diagnostics.number_of_banned_participants,
+            update_interval
         )
     }
 
@@ -3569,8 +6431,38 @@ impl CoordinatorState {
 
     /// Save the coordinator state in storage.
     #[inline]
-    pub(crate) fn save(&self, storage: &mut Disk) -> Result<(), CoordinatorError> {
-        storage.update(&Locator::CoordinatorState, Object::CoordinatorState(self.clone()))
+    pub(crate) fn save(&self, storage: &mut dyn Storage) -> Result<(), CoordinatorError> {
+        storage.update(&Locator::CoordinatorState, Object::CoordinatorState(self.clone()))?;
+
+        // Snapshot the tokens and the ip addresses in use, if enabled.
+        match *PERSIST_RUNTIME {
+            true => self
+                .runtime_snapshot()
+                .save_to(&RuntimeSnapshot::path(&self.environment)),
+            false => Ok(()),
+        }
+    }
+
+    ///
+    /// Restores the tokens and the ip addresses in use from the sidecar file of the ceremony,
+    /// if their persistence is enabled.
+    ///
+    pub(super) fn restore_runtime_snapshot(&mut self) -> Result<(), CoordinatorError> {
+        if *PERSIST_RUNTIME {
+            let snapshot = RuntimeSnapshot::load_from(&RuntimeSnapshot::path(&self.environment))?;
+            self.runtime_state.tokens_in_use = snapshot.tokens_in_use;
+            self.runtime_state.current_ips = snapshot.current_ips;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the snapshot of the part of the runtime state persisted across restarts.
+    fn runtime_snapshot(&self) -> RuntimeSnapshot {
+        RuntimeSnapshot {
+            tokens_in_use: self.runtime_state.tokens_in_use.clone(),
+            current_ips: self.runtime_state.current_ips.clone(),
+        }
     }
 }
 
@@ -3585,6 +6477,29 @@ pub struct ResetCurrentRoundStorageAction {
     pub rollback: bool,
 }
 
+/// The contributors of the current round to reset and whether the
+/// reset rolls back to the previous round.
+struct RoundReset {
+    round_height: u64,
+    contributors: Vec<(Participant, ParticipantInfo)>,
+    rollback: bool,
+}
+
+/// What a reset of the current round with
+/// [CoordinatorState::reset_current_round] would do, computed by
+/// [CoordinatorState::preview_reset_current_round] without resetting it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResetPreview {
+    /// Whether the reset would roll back to the previous round to
+    /// await new participants.
+    pub rollback: bool,
+    /// The participants which would be removed from the round,
+    /// ordered by participant.
+    pub remove_participants: Vec<Participant>,
+    /// The height of the current round after the reset.
+    pub target_round_height: u64,
+}
+
 /// Action to update the storage to reflect a contributor being
 /// replaced in [CoordinatorState].
 #[derive(Debug)]
@@ -3619,7 +6534,7 @@ pub enum CeremonyStorageAction {
 #[derive(Debug)]
 pub(crate) struct DropCurrentParticpantData {
     /// The participant being dropped.
-    _participant: Participant,
+    participant: Participant,
     /// Action to perform to update the round/storage after the drop
     /// to match the current coordinator state.
     pub storage_action: CeremonyStorageAction,
@@ -3628,7 +6543,7 @@ pub(crate) struct DropCurrentParticpantData {
 #[derive(Debug)]
 pub(crate) struct DropQueueParticipantData {
     /// The participant being dropped.
-    _participant: Participant,
+    participant: Participant,
 }
 
 /// Returns information/actions for the coordinator to perform in
@@ -3643,25 +6558,113 @@ pub(crate) enum DropParticipant {
     DropQueue(DropQueueParticipantData),
 }
 
+impl DropParticipant {
+    /// Returns the participant being dropped.
+    pub(crate) fn participant(&self) -> &Participant {
+        match self {
+            DropParticipant::DropCurrent(data) => &data.participant,
+            DropParticipant::DropQueue(data) => &data.participant,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::net::Ipv4Addr;
 
     use crate::{
+        authentication::KeyPair,
         coordinator_state::*,
         environment::{Parameters, Testing},
+        objects::task::initialize_tasks,
         testing::prelude::*,
-        CoordinatorState, MockTimeSource, SystemTimeSource,
+        CoordinatorState, LeastLoadedVerifierSelector, MockRngSource, MockTimeSource, SystemTimeSource,
     };
 
-    fn fetch_task_for_verifier(state: &CoordinatorState) -> Option<Task> {
-        state.get_pending_verifications().keys().next().cloned()
+    fn fetch_task_for_verifier(state: &mut CoordinatorState) -> Option<Task> {
+        let verifier = state.environment.coordinator_verifiers().first()?.clone();
+        state.fetch_verifier_task(&verifier).unwrap()
+    }
+
+    #[test]
+    fn test_remove_task() {
+        let mut tasks: LinkedList<Task> = (0..5).map(|chunk_id| Task::new(chunk_id, 1)).collect();
+
+        remove_task(&mut tasks, &Task::new(2, 1));
+        remove_task(&mut tasks, &Task::new(2, 2));
+
+        let expected: LinkedList<Task> = [0, 1, 3, 4].iter().map(|chunk_id| Task::new(*chunk_id, 1)).collect();
+        assert_eq!(expected, tasks);
+    }
+
+    #[test]
+    fn test_cohort_index() {
+        // A single duration applies to all the cohorts.
+        let durations = [100];
+        assert_eq!(0, cohort_index(&durations, 0));
+        assert_eq!(0, cohort_index(&durations, 99));
+        assert_eq!(1, cohort_index(&durations, 100));
+        assert_eq!(1, cohort_index(&durations, 199));
+        assert_eq!(2, cohort_index(&durations, 200));
+
+        // A longer first cohort followed by shorter ones.
+        let durations = [300, 100, 50];
+        assert_eq!(0, cohort_index(&durations, 0));
+        assert_eq!(0, cohort_index(&durations, 299));
+        assert_eq!(1, cohort_index(&durations, 300));
+        assert_eq!(1, cohort_index(&durations, 399));
+        assert_eq!(2, cohort_index(&durations, 400));
+        assert_eq!(2, cohort_index(&durations, 449));
+
+        // The last duration applies to the cohorts past the given durations.
+        assert_eq!(3, cohort_index(&durations, 450));
+        assert_eq!(3, cohort_index(&durations, 499));
+        assert_eq!(4, cohort_index(&durations, 500));
+    }
+
+    #[test]
+    fn test_deserialize_cohort_duration() {
+        let state = CoordinatorState::new(TEST_ENVIRONMENT.clone()).unwrap();
+        let mut value = serde_json::to_value(&state).unwrap();
+        let fields = value.as_object_mut().unwrap();
+
+        // The states saved with a single cohort duration are still loaded.
+        fields.remove("cohort_durations");
+        fields.insert("cohort_duration".to_string(), serde_json::json!(100));
+        let state: CoordinatorState = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(vec![100], state.cohort_durations);
+
+        let fields = value.as_object_mut().unwrap();
+        fields.remove("cohort_duration");
+        fields.insert("cohort_durations".to_string(), serde_json::json!([300, 100]));
+        let state: CoordinatorState = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(vec![300, 100], state.cohort_durations);
+
+        // A cohort of zero seconds is rejected.
+        for durations in [serde_json::json!(0), serde_json::json!([300, 0]), serde_json::json!([])] {
+            let mut value = value.clone();
+            value["cohort_durations"] = durations;
+            assert!(serde_json::from_value::<CoordinatorState>(value).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_cohort_durations() {
+        assert_eq!(vec![86400], parse_cohort_durations("86400").unwrap());
+        assert_eq!(vec![300, 100], parse_cohort_durations("300, 100").unwrap());
+
+        for durations in ["0", "300,0", "", "300,abc"] {
+            assert!(matches!(
+                parse_cohort_durations(durations),
+                Err(CoordinatorError::CohortDurationsInvalid(invalid)) if invalid == durations
+            ));
+        }
     }
 
     #[test]
     fn test_new() {
         // Initialize a new coordinator state.
-        let state = CoordinatorState::new(TEST_ENVIRONMENT.clone());
+        let state = CoordinatorState::new(TEST_ENVIRONMENT.clone()).unwrap();
         assert_eq!(0, state.queue.len());
         assert_eq!(0, state.next.len());
         assert_eq!(None, state.current_round_height);
@@ -3674,10 +6677,100 @@ mod tests {
         assert_eq!(0, state.banned.len());
     }
 
+    #[test]
+    fn test_runtime_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("runtime.json");
+        let path = path.to_str().unwrap();
+
+        // A missing snapshot is empty.
+        assert_eq!(RuntimeSnapshot::default(), RuntimeSnapshot::load_from(path).unwrap());
+
+        // The tokens and the ip addresses in use survive a restart.
+        let mut state = CoordinatorState::new(TEST_ENVIRONMENT.clone()).unwrap();
+        state.initialize(1);
+        state
+            .runtime_state
+            .tokens_in_use
+            .insert(String::from("token_1"), TEST_CONTRIBUTOR_ID.clone());
+        state
+            .runtime_state
+            .current_ips
+            .insert(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4)), TEST_CONTRIBUTOR_ID.clone());
+        state.runtime_snapshot().save_to(path).unwrap();
+
+        let snapshot = RuntimeSnapshot::load_from(path).unwrap();
+        assert_eq!(state.runtime_snapshot(), snapshot);
+        assert_eq!(Some(&*TEST_CONTRIBUTOR_ID), snapshot.tokens_in_use.get("token_1"));
+
+        // A malformed snapshot is reported.
+        std::fs::write(path, "{").unwrap();
+        assert!(RuntimeSnapshot::load_from(path).is_err());
+
+        // Each ceremony has its own snapshot, in its storage.
+        let ceremony_1 = TEST_ENVIRONMENT.for_ceremony("ceremony_1");
+        let ceremony_2 = TEST_ENVIRONMENT.for_ceremony("ceremony_2");
+        assert_ne!(RuntimeSnapshot::path(&ceremony_1), RuntimeSnapshot::path(&ceremony_2));
+        assert!(RuntimeSnapshot::path(&ceremony_1).starts_with(ceremony_1.local_base_directory()));
+    }
+
+    #[test]
+    fn test_save_cohort_tokens() {
+        let dir = tempfile::tempdir().unwrap();
+        let tokens_path = dir.path().to_str().unwrap();
+        let cohort_tokens = |tokens: &[&str]| -> HashSet<String> { tokens.iter().map(|t| t.to_string()).collect() };
+
+        // The saved token files round-trip through the loading of the tokens.
+        let tokens = vec![cohort_tokens(&["token_1", "token_2"]), cohort_tokens(&[&format!("{}token_3", PRIVATE_TOKEN_PREFIX)])];
+        for (cohort, cohort_tokens) in tokens.iter().enumerate() {
+            CoordinatorState::save_cohort_tokens(tokens_path, cohort + 1, cohort_tokens, false).unwrap();
+        }
+        assert_eq!(tokens, CoordinatorState::load_tokens_from_dir(tokens_path).unwrap());
+
+        // An existing token file is only overwritten when forced.
+        let new_tokens = cohort_tokens(&["token_4"]);
+        assert!(CoordinatorState::save_cohort_tokens(tokens_path, 2, &new_tokens, false).is_err());
+        assert_eq!(tokens, CoordinatorState::load_tokens_from_dir(tokens_path).unwrap());
+        CoordinatorState::save_cohort_tokens(tokens_path, 2, &new_tokens, true).unwrap();
+        assert_eq!(new_tokens, CoordinatorState::load_tokens_from_dir(tokens_path).unwrap()[1]);
+
+        assert!(CoordinatorState::save_cohort_tokens(tokens_path, 0, &new_tokens, false).is_err());
+    }
+
+    #[test]
+    fn test_load_tokens_malformed_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let tokens_path = dir.path().to_str().unwrap();
+        let cohort_path = |cohort: usize| dir.path().join(format!("namada_tokens_cohort_{}.json", cohort));
+
+        std::fs::write(cohort_path(1), r#"["token_1"]"#).unwrap();
+        std::fs::write(cohort_path(2), r#"["token_2""#).unwrap();
+
+        // The malformed cohort file is reported.
+        match CoordinatorState::load_tokens_from_dir(tokens_path) {
+            Err(CoordinatorError::TokenFileMalformed { path, .. }) => {
+                assert_eq!(cohort_path(2).to_str().unwrap(), path)
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+
+        // A missing folder is reported.
+        let missing_path = dir.path().join("missing");
+        assert!(matches!(
+            CoordinatorState::load_tokens_from_dir(missing_path.to_str().unwrap()),
+            Err(CoordinatorError::TokenFileMalformed { .. })
+        ));
+
+        std::fs::write(cohort_path(2), r#"["token_2"]"#).unwrap();
+        let tokens = CoordinatorState::load_tokens_from_dir(tokens_path).unwrap();
+        assert_eq!(2, tokens.len());
+        assert!(tokens[1].contains("token_2"));
+    }
+
     #[test]
     fn test_set_current_round_height() {
         // Initialize a new coordinator state.
-        let mut state = CoordinatorState::new(TEST_ENVIRONMENT.clone());
+        let mut state = CoordinatorState::new(TEST_ENVIRONMENT.clone()).unwrap();
         assert_eq!(None, state.current_round_height);
 
         // Set the current round height for coordinator state.
@@ -3699,7 +6792,7 @@ mod tests {
         assert!(contributor.is_contributor());
 
         // Initialize a new coordinator state.
-        let mut state = CoordinatorState::new(environment.clone());
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
         assert_eq!(0, state.queue.len());
 
         // Add the contributor of the coordinator.
@@ -3745,7 +6838,7 @@ mod tests {
         let token2 = String::from("test_token_2");
 
         // Initialize a new coordinator state.
-        let mut state = CoordinatorState::new(environment.clone());
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
         let current_round_height = 5;
         state.initialize(current_round_height);
         assert!(state.queue.is_empty());
@@ -3782,6 +6875,398 @@ mod tests {
         assert!(!state.blacklisted_ips.contains_key(&contributor_ip));
     }
 
+    #[test]
+    fn test_release_token() {
+        let time = SystemTimeSource::new();
+        let environment = TEST_ENVIRONMENT.clone();
+
+        let contributor_1 = TEST_CONTRIBUTOR_ID.clone();
+        let contributor_2 = TEST_CONTRIBUTOR_ID_2.clone();
+        let token_1 = String::from("test_token");
+        let token_2 = String::from("test_token_2");
+
+        // Initialize a new coordinator state with two contributors in the queue.
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(5);
+        state
+            .add_to_queue(contributor_1.clone(), None, token_1.clone(), 10, &time)
+            .unwrap();
+        state
+            .add_to_queue(contributor_2.clone(), None, token_2.clone(), 10, &time)
+            .unwrap();
+
+        // Track the tokens in use regardless of the blacklisting settings.
+        for (token, contributor) in [(&token_1, &contributor_1), (&token_2, &contributor_2)] {
+            state
+                .runtime_state
+                .tokens_in_use
+                .insert(token.clone(), contributor.clone());
+        }
+        assert_eq!(
+            vec![
+                TokenInUse {
+                    token: token_1.clone(),
+                    participant: contributor_1.clone()
+                },
+                TokenInUse {
+                    token: token_2.clone(),
+                    participant: contributor_2.clone()
+                }
+            ],
+            state.tokens_in_use()
+        );
+
+        // Drop the first contributor, leaving its token stuck in use.
+        state.drop_participant(&contributor_1, &time).unwrap();
+        state
+            .runtime_state
+            .tokens_in_use
+            .insert(token_1.clone(), contributor_1.clone());
+
+        // The token of the active contributor cannot be released.
+        assert!(matches!(
+            state.release_token(&token_2),
+            Err(CoordinatorError::TokenStillInUse(participant)) if participant == contributor_2
+        ));
+        assert!(state.is_token_in_use(&token_2));
+
+        // The token of the dropped contributor is released.
+        assert_eq!(contributor_1, state.release_token(&token_1).unwrap());
+        assert!(!state.is_token_in_use(&token_1));
+        assert!(matches!(
+            state.release_token(&token_1),
+            Err(CoordinatorError::TokenNotInUse)
+        ));
+    }
+
+    #[test]
+    fn test_blacklists() {
+        let time = SystemTimeSource::new();
+        let environment = TEST_ENVIRONMENT.clone();
+
+        let contributor = TEST_CONTRIBUTOR_ID.clone();
+        let contributor_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let token = String::from("test_token");
+
+        // Initialize a new coordinator state.
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(5);
+        assert_eq!(
+            Blacklists {
+                tokens: vec![],
+                ips: vec![]
+            },
+            state.blacklists(false)
+        );
+
+        // Blacklist the token and ip address of the contributor, tracked regardless of the blacklisting settings.
+        state
+            .add_to_queue(contributor.clone(), Some(contributor_ip), token.clone(), 10, &time)
+            .unwrap();
+        state
+            .runtime_state
+            .tokens_in_use
+            .insert(token.clone(), contributor.clone());
+        state
+            .runtime_state
+            .current_ips
+            .insert(contributor_ip, contributor.clone());
+        state.blacklist_participant_token(&contributor).unwrap();
+        state.blacklist_participant_ip(&contributor).unwrap();
+
+        let blacklists = state.blacklists(false);
+        assert_eq!(
+            vec![BlacklistedToken {
+                token,
+                participant: contributor.clone()
+            }],
+            blacklists.tokens
+        );
+        assert_eq!(
+            vec![BlacklistedIp {
+                ip: contributor_ip,
+                participant: contributor.clone()
+            }],
+            blacklists.ips
+        );
+
+        // Only the prefix of the tokens is returned when redacted.
+        let blacklists = state.blacklists(true);
+        assert_eq!("test_t...", blacklists.tokens[0].token);
+        assert_eq!(contributor, blacklists.tokens[0].participant);
+        assert_eq!(1, blacklists.ips.len());
+    }
+
+    #[test]
+    fn test_reinstate_dropped_participant() {
+        test_logger();
+
+        let time = SystemTimeSource::new();
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .coordinator_contributors(&[])
+            .into();
+
+        let contributor_1 = TEST_CONTRIBUTOR_ID.clone();
+        let contributor_1_ip = IpAddr::V4("0.0.0.1".parse().unwrap());
+        let contributor_2 = TEST_CONTRIBUTOR_ID_2.clone();
+        let contributor_2_ip = IpAddr::V4("0.0.0.2".parse().unwrap());
+
+        // Initialize a new coordinator state and advance it to the next round.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(current_round_height);
+        state
+            .add_to_queue(
+                contributor_1.clone(),
+                Some(contributor_1_ip),
+                String::from("test_token"),
+                7,
+                &time,
+            )
+            .unwrap();
+        state
+            .add_to_queue(
+                contributor_2.clone(),
+                Some(contributor_2_ip),
+                String::from("test_token_2"),
+                9,
+                &time,
+            )
+            .unwrap();
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        state.commit_next_round(&time);
+
+        // A contributor who was never dropped cannot be reinstated.
+        assert!(matches!(
+            state.reinstate_dropped_participant(&contributor_1, &time),
+            Err(CoordinatorError::ParticipantMissing)
+        ));
+
+        // Drop the first contributor, whose token was blacklisted.
+        state
+            .runtime_state
+            .tokens_in_use
+            .insert(String::from("test_token"), contributor_1.clone());
+        state.blacklist_participant_token(&contributor_1).unwrap();
+        state.drop_participant(&contributor_1, &time).unwrap();
+        assert!(state.is_token_blacklisted("test_token"));
+        assert!(state.dropped.iter().any(|info| info.id == contributor_1));
+
+        // The contributor is back in the queue with its original reliability score.
+        state.reinstate_dropped_participant(&contributor_1, &time).unwrap();
+        assert!(!state.dropped.iter().any(|info| info.id == contributor_1));
+        assert!(!state.is_token_blacklisted("test_token"));
+        assert_eq!(
+            Some(&contributor_1),
+            state.runtime_state.tokens_in_use.get("test_token")
+        );
+        assert_eq!(7, state.queue[&contributor_1].0);
+        assert!(matches!(
+            state.reinstate_dropped_participant(&contributor_1, &time),
+            Err(CoordinatorError::ParticipantMissing)
+        ));
+
+        // A banned contributor must be unbanned first.
+        state.ban_participant(&contributor_2, &time).unwrap();
+        assert!(matches!(
+            state.reinstate_dropped_participant(&contributor_2, &time),
+            Err(CoordinatorError::ParticipantBanned)
+        ));
+        state.unban_participant(&contributor_2);
+        state.reinstate_dropped_participant(&contributor_2, &time).unwrap();
+        assert_eq!(9, state.queue[&contributor_2].0);
+    }
+
+    /// Test that a contributor reinstated after contributing and being dropped holds
+    /// their token and ip address again, and can contribute once more.
+    #[test]
+    fn test_reinstate_dropped_participant_contributes() {
+        test_logger();
+
+        let time = SystemTimeSource::new();
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .coordinator_contributors(&[])
+            .into();
+
+        let contributor = TEST_CONTRIBUTOR_ID.clone();
+        let contributor_ip = IpAddr::V4("0.0.0.1".parse().unwrap());
+        let token = String::from("test_token");
+
+        // Initialize a new coordinator state and advance it to the next round.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(current_round_height);
+        state
+            .add_to_queue(contributor.clone(), Some(contributor_ip), token.clone(), 10, &time)
+            .unwrap();
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        state.commit_next_round(&time);
+
+        // The contributor contributes, their token and ip are blacklisted as the coordinator does,
+        // and they are dropped.
+        state
+            .runtime_state
+            .tokens_in_use
+            .insert(token.clone(), contributor.clone());
+        state
+            .runtime_state
+            .current_ips
+            .insert(contributor_ip, contributor.clone());
+        let task = state.fetch_task(&contributor, &time).unwrap();
+        state.acquired_lock(&contributor, task.chunk_id(), &time).unwrap();
+        state.completed_task(&contributor, &task, &time).unwrap();
+        state.blacklist_participant_token(&contributor).unwrap();
+        state.blacklist_participant_ip(&contributor).unwrap();
+        state.drop_participant(&contributor, &time).unwrap();
+
+        // Once reinstated, the token and ip are in use by the contributor again.
+        state.reinstate_dropped_participant(&contributor, &time).unwrap();
+        assert!(!state.is_token_blacklisted(&token));
+        assert!(state.is_token_in_use(&token));
+        assert_eq!(Some(&contributor), state.runtime_state.tokens_in_use.get(&token));
+        assert_eq!(Some(&contributor), state.runtime_state.current_ips.get(&contributor_ip));
+        assert!(!state.blacklisted_ips.contains_key(&contributor_ip));
+
+        // Roll back the round left without contributors, and run the reinstated contributor in the next one.
+        state.reset_current_round(true, &time).unwrap();
+        state.update_queue().unwrap();
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        state.commit_next_round(&time);
+        assert!(state.current_contributors.contains_key(&contributor));
+
+        let task = state.fetch_task(&contributor, &time).unwrap();
+        state.acquired_lock(&contributor, task.chunk_id(), &time).unwrap();
+        state.completed_task(&contributor, &task, &time).unwrap();
+
+        // The finished contribution blacklists the token and ip again.
+        state.blacklist_participant_token(&contributor).unwrap();
+        state.blacklist_participant_ip(&contributor).unwrap();
+        assert!(state.is_token_blacklisted(&token));
+        assert!(!state.is_token_in_use(&token));
+        assert_eq!(Some(&contributor), state.blacklisted_ips.get(&contributor_ip));
+    }
+
+    #[test]
+    fn test_chunk_upload() {
+        test_logger();
+
+        let time = SystemTimeSource::new();
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .coordinator_contributors(&[])
+            .into();
+
+        let contributor = TEST_CONTRIBUTOR_ID.clone();
+        let checksum = String::from("checksum");
+        let seed = [7u8; 32];
+        let rng = MockRngSource::new(seed);
+
+        // Initialize a new coordinator state and advance it to the next round.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(current_round_height);
+        state
+            .add_to_queue(contributor.clone(), None, String::from("test_token"), 10, &time)
+            .unwrap();
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        state.commit_next_round(&time);
+        let round_height = state.current_round_height();
+
+        // Only uploads of a current contributor for the current round are allowed.
+        assert!(matches!(
+            state.initiate_chunk_upload(&TEST_CONTRIBUTOR_ID_2, round_height, 10, checksum.clone(), &rng),
+            Err(CoordinatorError::ParticipantNotFound(_))
+        ));
+        assert!(matches!(
+            state.initiate_chunk_upload(&contributor, round_height + 1, 10, checksum.clone(), &rng),
+            Err(CoordinatorError::RoundHeightMismatch)
+        ));
+
+        let upload = state
+            .initiate_chunk_upload(&contributor, round_height, 10, checksum.clone(), &rng)
+            .unwrap();
+        assert_eq!(10, upload.total_size);
+        assert!(upload.parts.is_empty());
+        assert!(!upload.is_complete());
+
+        // The upload id is drawn from the random source of the coordinator.
+        let mut expected_id = [0u8; 16];
+        MockRngSource::new(seed).fill_bytes(&mut expected_id);
+        assert_eq!(hex::encode(expected_id), upload.upload_id);
+
+        // Parts must be within the contribution and not overlap.
+        state
+            .add_chunk_upload_part(&contributor, &upload.upload_id, 0, 4)
+            .unwrap();
+        for (offset, length) in [(8, 4), (2, 4), (4, 0)] {
+            assert!(matches!(
+                state.add_chunk_upload_part(&contributor, &upload.upload_id, offset, length),
+                Err(CoordinatorError::ChunkUploadPartInvalid)
+            ));
+        }
+        assert!(matches!(
+            state.add_chunk_upload_part(&contributor, "unknown", 4, 4),
+            Err(CoordinatorError::ChunkUploadMissing)
+        ));
+
+        // The upload is resumed with the parts received so far.
+        let resumed = state
+            .initiate_chunk_upload(&contributor, round_height, 10, checksum.clone(), &rng)
+            .unwrap();
+        assert_eq!(upload.upload_id, resumed.upload_id);
+        assert_eq!(Some(&4), resumed.parts.get(&0));
+
+        // A part at the same offset replaces the previous one.
+        state
+            .add_chunk_upload_part(&contributor, &upload.upload_id, 0, 5)
+            .unwrap();
+        state
+            .add_chunk_upload_part(&contributor, &upload.upload_id, 5, 5)
+            .unwrap();
+        assert!(state
+            .chunk_upload(&contributor, &upload.upload_id)
+            .unwrap()
+            .is_complete());
+
+        // An upload with another checksum replaces the previous one.
+        let restarted = state
+            .initiate_chunk_upload(&contributor, round_height, 10, String::from("other"), &rng)
+            .unwrap();
+        assert_ne!(upload.upload_id, restarted.upload_id);
+        assert!(restarted.parts.is_empty());
+        assert!(matches!(
+            state.chunk_upload(&contributor, &upload.upload_id),
+            Err(CoordinatorError::ChunkUploadMissing)
+        ));
+
+        assert_eq!(
+            restarted,
+            state.remove_chunk_upload(&contributor, &restarted.upload_id).unwrap()
+        );
+        assert!(matches!(
+            state.remove_chunk_upload(&contributor, &restarted.upload_id),
+            Err(CoordinatorError::ChunkUploadMissing)
+        ));
+
+        // The upload of a dropped contributor is discarded.
+        let upload = state
+            .initiate_chunk_upload(&contributor, round_height, 10, checksum.clone(), &rng)
+            .unwrap();
+        state.drop_participant(&contributor, &time).unwrap();
+        assert!(matches!(
+            state.chunk_upload(&contributor, &upload.upload_id),
+            Err(CoordinatorError::ChunkUploadMissing)
+        ));
+    }
+
     #[test]
     fn test_add_to_queue_verifier() {
         let time = SystemTimeSource::new();
@@ -3794,7 +7279,7 @@ mod tests {
         let token2 = String::from("test_token_2");
 
         // Initialize a new coordinator state.
-        let mut state = CoordinatorState::new(environment.clone());
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
         assert_eq!(0, state.queue.len());
 
         // Add the verifier of the coordinator.
@@ -3835,7 +7320,7 @@ mod tests {
         let token2 = String::from("test_token_2");
 
         // Initialize a new coordinator state.
-        let mut state = CoordinatorState::new(environment.clone());
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
         assert_eq!(0, state.queue.len());
         assert_eq!(None, state.current_round_height);
 
@@ -3891,7 +7376,7 @@ mod tests {
         let environment = TEST_ENVIRONMENT.clone();
 
         // Initialize a new coordinator state.
-        let mut state = CoordinatorState::new(environment.clone());
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
         assert_eq!(0, state.queue.len());
         assert_eq!(None, state.current_round_height);
 
@@ -3959,17 +7444,459 @@ mod tests {
     }
 
     #[test]
-    fn test_remove_from_queue_contributor() {
-        let time = SystemTimeSource::new();
-        let environment = TEST_ENVIRONMENT.clone();
+    fn test_update_queue_reliability_priority() {
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let testing = Testing::from(Parameters::Test8Chunks)
+            .coordinator_contributors(&[])
+            .maximum_contributors_per_round(1);
 
-        // Fetch the contributor of the coordinator.
+        // A low-reliability contributor joins one second before a high-reliability one.
+        let early = Participant::Contributor("early".to_string());
+        let late = Participant::Contributor("late".to_string());
+        let assigned_rounds = |queue_priority: QueuePriority| {
+            let environment: Environment = testing.queue_priority(queue_priority).into();
+            let mut state = CoordinatorState::new(environment).unwrap();
+            state.initialize(5);
+
+            time.set_time(OffsetDateTime::now_utc());
+            state
+                .add_to_queue(early.clone(), None, "token_early".to_string(), 2, &time)
+                .unwrap();
+            time.update(|prev| prev + Duration::seconds(1));
+            state
+                .add_to_queue(late.clone(), None, "token_late".to_string(), 9, &time)
+                .unwrap();
+
+            state.update_queue().unwrap();
+            (state.queue[&early].1, state.queue[&late].1)
+        };
+
+        // The contributors are assigned by time joined by default.
+        assert_eq!((Some(6), Some(7)), assigned_rounds(QueuePriority::Fifo));
+
+        // The more reliable late joiner gets the earlier round in priority mode.
+        assert_eq!((Some(7), Some(6)), assigned_rounds(QueuePriority::Reliability));
+    }
+
+    #[test]
+    fn test_queue_priority() {
+        assert_eq!(QueuePriority::Fifo, QueuePriority::default());
+        assert_eq!(Ok(QueuePriority::Reliability), "reliability".parse::<QueuePriority>());
+        assert!("lifo".parse::<QueuePriority>().is_err());
+
+        let tiers: Vec<u8> = [0, 3, 4, 7, 8, 10].iter().map(|r| QueuePriority::reliability_tier(*r)).collect();
+        assert_eq!(vec![0, 0, 1, 1, 2, 2], tiers);
+    }
+
+    #[test]
+    fn test_weighted_replacement_selection() {
+        let time = SystemTimeSource::new();
+        let current_contributor = Participant::new_contributor("current-contributor");
+        let contributors: Vec<Participant> = (1..=3)
+            .map(|id| Participant::new_contributor(&format!("contributor-{}", id)))
+            .collect();
+
+        // Starts a round with the current contributor and queues the other contributors, drawing
+        // the replacements from a random source seeded with the given bytes.
+        let start_round = |environment: Environment, seed: [u8; 32]| {
+            let mut state = CoordinatorState::new(environment).unwrap();
+            state.set_random_source(Arc::new(MockRngSource::new(seed)));
+            state.initialize(5);
+            state
+                .add_to_queue(current_contributor.clone(), None, String::from("test_token"), 10, &time)
+                .unwrap();
+            state.update_queue().unwrap();
+            state.aggregating_current_round(&time).unwrap();
+            state.aggregated_current_round(&time).unwrap();
+            state.precommit_next_round(6, &time).unwrap();
+            state.commit_next_round(&time);
+
+            for (id, contributor) in contributors.iter().enumerate() {
+                state
+                    .add_to_queue(contributor.clone(), None, format!("test_token_{}", id), 10, &time)
+                    .unwrap();
+            }
+            state
+        };
+        let draw = |state: &CoordinatorState| -> Vec<Participant> {
+            (0..20)
+                .map(|_| state.select_replacement_contributor().unwrap().0)
+                .collect()
+        };
+
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .coordinator_contributors(&[])
+            .into();
+
+        // The deterministic selection always picks the same contributor.
+        let state = start_round(environment.clone(), [7; 32]);
+        assert_eq!(1, draw(&state).into_iter().collect::<HashSet<_>>().len());
+
+        // The weighted selection follows the random source and spreads the replacements across the queue.
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .coordinator_contributors(&[])
+            .replacement_selection(ReplacementSelection::Weighted)
+            .into();
+        let picked = draw(&start_round(environment.clone(), [7; 32]));
+        assert_eq!(picked, draw(&start_round(environment.clone(), [7; 32])));
+        assert_eq!(3, picked.into_iter().collect::<HashSet<_>>().len());
+
+        // Each replacement is counted against the contributor pulled in.
+        let mut state = start_round(environment.clone(), [7; 32]);
+        let replacement = state.add_replacement_contributor_unsafe(0, &time).unwrap();
+        assert_eq!(Some(&1), state.replacement_counts.get(&replacement));
+        assert!(state.current_contributors.contains_key(&replacement));
+        assert!(!state.queue.contains_key(&replacement));
+
+        // The weight of a contributor decays with the number of times they were pulled in, down to
+        // a floor at which they are practically never picked over the others.
+        let mut state = start_round(environment, [7; 32]);
+        state.replacement_counts.insert(contributors[0].clone(), 100_000);
+        assert!(!draw(&state).contains(&contributors[0]));
+
+        assert!(ReplacementSelection::weight(10, 1) < ReplacementSelection::weight(10, 0));
+        assert!(ReplacementSelection::weight(5, 0) < ReplacementSelection::weight(10, 0));
+        assert_eq!(0, ReplacementSelection::weight(10, 100_000));
+        assert_eq!(ReplacementSelection::default(), ReplacementSelection::Deterministic);
+        assert_eq!(Ok(ReplacementSelection::Weighted), "weighted".parse::<ReplacementSelection>());
+    }
+
+    #[test]
+    fn test_queue_entries() {
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let environment = TEST_ENVIRONMENT.clone();
+
+        // Initialize a new coordinator state.
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(5);
+        assert!(state.queue_entries().is_empty());
+
+        // Add contributors to the queue, each joining one second after the previous one.
+        let number_of_contributors = 3;
+        for id in 1..=number_of_contributors {
+            let contributor = Participant::Contributor(id.to_string());
+            let contributor_ip = IpAddr::V4(format!("0.0.0.{}", id).parse().unwrap());
+            let token = format!("test_token_{}", id);
+            state
+                .add_to_queue(contributor, Some(contributor_ip), token, 10 - id as u8, &time)
+                .unwrap();
+            time.update(|prev| prev + Duration::seconds(1));
+        }
+
+        // Assign the queued contributors to future rounds.
+        state.update_queue().unwrap();
+
+        // Check that the structured entries match the underlying queue.
+        let entries = state.queue_entries();
+        assert_eq!(number_of_contributors, entries.len());
+        for (index, entry) in entries.iter().enumerate() {
+            let (reliability, assigned_round, last_seen, joined) = state.queue.get(&entry.participant).unwrap();
+            assert_eq!(Participant::Contributor((index + 1).to_string()), entry.participant);
+            assert_eq!(*reliability, entry.reliability);
+            assert_eq!(*assigned_round, entry.assigned_round);
+            assert_eq!(*last_seen, entry.last_seen);
+            assert_eq!(*joined, entry.joined);
+            assert_eq!(index as u64 + 1, entry.position);
+        }
+    }
+
+    #[test]
+    fn test_estimated_wait_for() {
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .maximum_contributors_per_round(2)
+            .into();
+
+        // Initialize a new coordinator state.
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(5);
+
+        // Add contributors to the queue, each joining one second after the previous one.
+        let contributors: Vec<Participant> = (1..=5).map(|id| Participant::Contributor(id.to_string())).collect();
+        for (id, contributor) in contributors.iter().enumerate() {
+            let contributor_ip = IpAddr::V4(format!("0.0.0.{}", id).parse().unwrap());
+            let token = format!("test_token_{}", id);
+            state
+                .add_to_queue(contributor.clone(), Some(contributor_ip), token, 10, &time)
+                .unwrap();
+            time.update(|prev| prev + Duration::seconds(1));
+        }
+
+        // Without round metrics, there's no estimate.
+        state.current_metrics = None;
+        assert_eq!(None, state.estimated_wait_for(&contributors[0]));
+        state.current_metrics = Some(Default::default());
+        assert_eq!(None, state.estimated_wait_for(&contributors[0]));
+
+        // The contributors wait for the current round, then for a whole round per batch ahead of them.
+        state.current_metrics = Some(RoundMetrics {
+            contributor_average_per_task: Some(10),
+            estimated_aggregation_time: Some(50),
+            estimated_wait_time: Some(100),
+            ..Default::default()
+        });
+        let round_time = 10 * environment.number_of_chunks() + 50 + state.queue_wait_time();
+        let expected = [100, 100, 100 + round_time, 100 + round_time, 100 + 2 * round_time];
+        for (contributor, expected) in contributors.iter().zip(expected) {
+            assert_eq!(Some(expected), state.estimated_wait_for(contributor));
+        }
+
+        // The contributors assigned to a round wait for the rounds before it.
+        state.update_queue().unwrap();
+        for (contributor, expected) in contributors.iter().zip(expected) {
+            assert_eq!(Some(expected), state.estimated_wait_for(contributor));
+        }
+
+        // Participants outside of the queue have no estimate.
+        assert_eq!(None, state.estimated_wait_for(&Participant::Contributor(String::from("unknown"))));
+    }
+
+    #[test]
+    fn test_estimated_wait_for_reliability_priority() {
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .coordinator_contributors(&[])
+            .maximum_contributors_per_round(1)
+            .queue_priority(QueuePriority::Reliability)
+            .into();
+
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(5);
+        state.current_metrics = Some(RoundMetrics {
+            contributor_average_per_task: Some(10),
+            estimated_aggregation_time: Some(50),
+            estimated_wait_time: Some(100),
+            ..Default::default()
+        });
+        let round_time = 10 * environment.number_of_chunks() + 50 + state.queue_wait_time();
+
+        // A low-reliability contributor joins one second before a high-reliability one.
+        let early = Participant::Contributor("early".to_string());
+        let late = Participant::Contributor("late".to_string());
+        state
+            .add_to_queue(early.clone(), None, "token_early".to_string(), 2, &time)
+            .unwrap();
+        time.update(|prev| prev + Duration::seconds(1));
+        state
+            .add_to_queue(late.clone(), None, "token_late".to_string(), 9, &time)
+            .unwrap();
+
+        // The more reliable late joiner is expected to join a round first, before and after the queue update.
+        assert_eq!(Some(100), state.estimated_wait_for(&late));
+        assert_eq!(Some(100 + round_time), state.estimated_wait_for(&early));
+        state.update_queue().unwrap();
+        assert_eq!(Some(100), state.estimated_wait_for(&late));
+        assert_eq!(Some(100 + round_time), state.estimated_wait_for(&early));
+    }
+
+    #[test]
+    fn test_queue_reliability_histogram() {
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let environment = TEST_ENVIRONMENT.clone();
+
+        // Initialize a new coordinator state.
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(5);
+        let histogram = state.queue_reliability_histogram();
+        assert_eq!((0, 9), (histogram[0].min, histogram[0].max));
+        assert_eq!((250, 255), (histogram[25].min, histogram[25].max));
+        assert!(histogram.iter().all(|bucket| bucket.count == 0));
+
+        // Add contributors with various reliability scores to the queue.
+        let reliabilities = [0u8, 5, 9, 10, 42, 255];
+        for (id, reliability) in reliabilities.iter().enumerate() {
+            let contributor = Participant::Contributor(id.to_string());
+            let contributor_ip = IpAddr::V4(format!("0.0.0.{}", id).parse().unwrap());
+            let token = format!("test_token_{}", id);
+            state
+                .add_to_queue(contributor, Some(contributor_ip), token, *reliability, &time)
+                .unwrap();
+        }
+
+        // Check that the buckets sum up to the queue size.
+        let histogram = state.queue_reliability_histogram();
+        assert_eq!(
+            state.number_of_queue_contributors(),
+            histogram.iter().map(|bucket| bucket.count).sum::<usize>()
+        );
+        assert_eq!(3, histogram[0].count);
+        assert_eq!(1, histogram[1].count);
+        assert_eq!(1, histogram[4].count);
+        assert_eq!(1, histogram[25].count);
+    }
+
+    #[test]
+    fn test_reliability_score() {
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let environment = TEST_ENVIRONMENT.clone();
+        let contributor = Participant::Contributor("contributor".to_string());
+
+        // Initialize a new coordinator state.
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(5);
+        assert_eq!(MAX_RELIABILITY_SCORE, state.reliability_score(&contributor));
+
+        // Each drop and failed verification lowers the score.
+        for _ in 0..2 {
+            let participant_info = ParticipantInfo::new(contributor.clone(), 5, MAX_RELIABILITY_SCORE, 0, &time);
+            state.dropped.push(participant_info);
+        }
+        state.verification_failures.insert(contributor.clone(), 3);
+        assert_eq!(MAX_RELIABILITY_SCORE - 5, state.reliability_score(&contributor));
+
+        // The drops of the other participants are not counted.
+        let other = Participant::Contributor("other".to_string());
+        assert_eq!(MAX_RELIABILITY_SCORE, state.reliability_score(&other));
+
+        // The score does not go below 0.
+        state.verification_failures.insert(contributor.clone(), 100);
+        assert_eq!(0, state.reliability_score(&contributor));
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let environment = TEST_ENVIRONMENT.clone();
+
+        let contributor = test_coordinator_contributor(&environment).unwrap();
+        let contributor_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+        let token = String::from("test_token");
+
+        // Initialize a new coordinator state.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(current_round_height);
+
+        // The empty queue and the missing aggregation block the next round.
+        let diagnostics = state.diagnostics(&time);
+        assert_eq!(state.status, diagnostics.status);
+        assert_eq!(Some(current_round_height), diagnostics.current_round_height);
+        assert_eq!(state.is_current_round_finished(), diagnostics.is_current_round_finished);
+        assert!(!diagnostics.is_current_round_aggregated);
+        assert!(!diagnostics.is_precommit_next_round_ready);
+        assert_eq!(0, diagnostics.number_of_queue_contributors);
+        assert_eq!(state.advance_blockers(&time), diagnostics.advance_blockers);
+        assert!(diagnostics.advance_blockers.len() >= 2);
+
+        // Add a contributor to the queue and aggregate the current round.
+        state
+            .add_to_queue(contributor.clone(), Some(contributor_ip), token, 10, &time)
+            .unwrap();
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+
+        let diagnostics = state.diagnostics(&time);
+        assert!(diagnostics.is_current_round_aggregated);
+        assert_eq!(
+            state.is_precommit_next_round_ready(&time),
+            diagnostics.is_precommit_next_round_ready
+        );
+        assert!(diagnostics.is_precommit_next_round_ready);
+        assert!(diagnostics.advance_blockers.is_empty());
+        assert_eq!(
+            state.number_of_queue_contributors(),
+            diagnostics.number_of_queue_contributors
+        );
+        assert_eq!(1, diagnostics.number_of_assigned_contributors);
+
+        // Advance to the next round.
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        assert_eq!(CoordinatorStatus::Precommit, state.diagnostics(&time).status);
+        state.commit_next_round(&time);
+
+        let diagnostics = state.diagnostics(&time);
+        assert_eq!(state.status, diagnostics.status);
+        assert_eq!(Some(current_round_height + 1), diagnostics.current_round_height);
+        assert!(!diagnostics.is_current_round_finished);
+        assert_eq!(
+            state.current_contributors.len(),
+            diagnostics.number_of_current_contributors
+        );
+        assert_eq!(0, diagnostics.number_of_finished_contributors);
+        assert_eq!(
+            state.pending_verification.len(),
+            diagnostics.number_of_pending_verifications
+        );
+        assert_eq!(state.dropped.len(), diagnostics.number_of_dropped_participants);
+        assert_eq!(state.banned.len(), diagnostics.number_of_banned_participants);
+        assert!(!diagnostics.advance_blockers.is_empty());
+    }
+
+    #[test]
+    fn test_set_queue_wait_time() {
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let environment = TEST_ENVIRONMENT.clone();
+
+        let contributor = test_coordinator_contributor(&environment).unwrap();
+        let contributor_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+        let token = String::from("test_token");
+
+        // Initialize a new coordinator state.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(current_round_height);
+        assert_eq!(environment.queue_wait_time(), state.queue_wait_time());
+
+        // Aggregate the current round with the queue wait time of the environment.
+        state
+            .add_to_queue(contributor.clone(), Some(contributor_ip), token, 10, &time)
+            .unwrap();
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+
+        let metrics = state.current_round_metrics().unwrap();
+        let finished_aggregation_at = metrics.finished_aggregation_at.unwrap();
+        assert_eq!(
+            Some(finished_aggregation_at + Duration::seconds(environment.queue_wait_time() as i64)),
+            metrics.next_round_after
+        );
+
+        // Changing the queue wait time reschedules the next round.
+        let queue_wait_time = environment.queue_wait_time() + 120;
+        state.set_queue_wait_time(queue_wait_time);
+        assert_eq!(queue_wait_time, state.queue_wait_time());
+        assert_eq!(
+            Some(finished_aggregation_at + Duration::seconds(queue_wait_time as i64)),
+            state.current_round_metrics().unwrap().next_round_after
+        );
+
+        // The queue wait time is persisted with the coordinator state.
+        let serialized = serde_json::to_vec(&state).unwrap();
+        let deserialized: CoordinatorState = serde_json::from_slice(&serialized).unwrap();
+        assert_eq!(queue_wait_time, deserialized.queue_wait_time());
+
+        // The next round waits for the new queue wait time.
+        time.update(|prev| prev + Duration::seconds(environment.queue_wait_time() as i64));
+        assert!(!state.is_precommit_next_round_ready(&time));
+        time.set_time(finished_aggregation_at + Duration::seconds(queue_wait_time as i64));
+        assert!(state.is_precommit_next_round_ready(&time));
+
+        // The next round keeps the queue wait time.
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        state.commit_next_round(&time);
+        time.update(|prev| prev + Duration::seconds(10));
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+        assert_eq!(
+            Some(time.now_utc() + Duration::seconds(queue_wait_time as i64)),
+            state.current_round_metrics().unwrap().next_round_after
+        );
+    }
+
+    #[test]
+    fn test_remove_from_queue_contributor() {
+        let time = SystemTimeSource::new();
+        let environment = TEST_ENVIRONMENT.clone();
+
+        // Fetch the contributor of the coordinator.
         let contributor = test_coordinator_contributor(&environment).unwrap();
         let contributor_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
         let token = String::from("test_token");
 
         // Initialize a new coordinator state.
-        let mut state = CoordinatorState::new(environment.clone());
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
         assert_eq!(0, state.queue.len());
 
         // Add the contributor of the coordinator.
@@ -3999,6 +7926,100 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_leave_queue_rejoin_cooldown() {
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .queue_rejoin_cooldown(Duration::minutes(5))
+            .into();
+
+        let contributor = test_coordinator_contributor(&environment).unwrap();
+        let contributor_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+        let token = String::from("test_token");
+
+        // Initialize a new coordinator state and add the contributor to the queue.
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(1);
+        state
+            .add_to_queue(contributor.clone(), Some(contributor_ip), token.clone(), 10, &time)
+            .unwrap();
+
+        // The contributor leaves the queue.
+        state.leave_queue(&contributor, &time).unwrap();
+        assert_eq!(0, state.queue.len());
+        assert!(state.leave_queue(&contributor, &time).is_err());
+
+        // Rejoining within the cooldown is rejected.
+        time.update(|prev| prev + Duration::minutes(4));
+        assert!(matches!(
+            state.add_to_queue_checks(&contributor, Some(&contributor_ip), &time),
+            Err(CoordinatorError::ParticipantRejoinedQueueTooSoon)
+        ));
+
+        // Rejoining after the cooldown is allowed.
+        time.update(|prev| prev + Duration::minutes(1));
+        state
+            .add_to_queue_checks(&contributor, Some(&contributor_ip), &time)
+            .unwrap();
+        state
+            .add_to_queue(contributor.clone(), Some(contributor_ip), token.clone(), 10, &time)
+            .unwrap();
+        assert_eq!(1, state.queue.len());
+
+        // The contributor is forgotten once the cooldown has expired.
+        assert!(state.left_queue.contains_key(&contributor));
+        state.update_dropped_queued_participants(&time).unwrap();
+        assert!(state.left_queue.is_empty());
+
+        // Without a cooldown, the contributor can rejoin right away.
+        let mut state = CoordinatorState::new(TEST_ENVIRONMENT.clone()).unwrap();
+        state.initialize(1);
+        state
+            .add_to_queue(contributor.clone(), Some(contributor_ip), token, 10, &time)
+            .unwrap();
+        state.leave_queue(&contributor, &time).unwrap();
+        state
+            .add_to_queue_checks(&contributor, Some(&contributor_ip), &time)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_freeze_queue() {
+        let time = SystemTimeSource::new();
+        let environment = TEST_ENVIRONMENT.clone();
+
+        let contributor = test_coordinator_contributor(&environment).unwrap();
+        let contributor_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+        let token = String::from("test_token");
+
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(5);
+        state
+            .add_to_queue(contributor.clone(), Some(contributor_ip), token, 10, &time)
+            .unwrap();
+
+        // New participants are rejected while the queue is frozen.
+        let contributor_2 = Participant::new_contributor("contributor_2");
+        state.freeze_queue();
+        assert!(state.is_queue_frozen());
+        assert!(matches!(
+            state.add_to_queue_checks(&contributor_2, None, &time),
+            Err(CoordinatorError::QueueFrozen)
+        ));
+
+        // The participants already in the queue proceed normally.
+        state.update_queue().unwrap();
+        assert_eq!(Some(6), state.queue.get(&contributor).unwrap().1);
+
+        // The queue stays frozen across a reset of the round.
+        state.reset_current_round(true, &time).unwrap();
+        assert!(state.is_queue_frozen());
+
+        state.unfreeze_queue();
+        assert!(!state.is_queue_frozen());
+        state.add_to_queue_checks(&contributor_2, None, &time).unwrap();
+    }
+
     #[test]
     fn test_commit_next_round() {
         test_logger();
@@ -4011,7 +8032,7 @@ mod tests {
         let token = String::from("test_token");
 
         // Initialize a new coordinator state.
-        let mut state = CoordinatorState::new(environment.clone());
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
         assert_eq!(0, state.queue.len());
         assert_eq!(None, state.current_round_height);
 
@@ -4076,7 +8097,7 @@ mod tests {
         assert!(!state.is_precommit_next_round_ready(&time));
 
         // Advance the coordinator to the next round.
-        state.commit_next_round();
+        state.commit_next_round(&time);
         assert_eq!(0, state.queue.len());
         assert_eq!(0, state.next.len());
         assert_eq!(Some(next_round_height), state.current_round_height);
@@ -4105,7 +8126,7 @@ mod tests {
         let token = String::from("test_token");
 
         // Initialize a new coordinator state.
-        let mut state = CoordinatorState::new(environment.clone());
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
         assert_eq!(0, state.queue.len());
         assert_eq!(None, state.current_round_height);
 
@@ -4185,45 +8206,344 @@ mod tests {
     }
 
     #[test]
-    fn test_pop_and_complete_tasks_contributor() {
+    fn test_cancel_precommit_next_round() {
+        test_logger();
+
         let time = SystemTimeSource::new();
         let environment = TEST_ENVIRONMENT.clone();
 
-        // Fetch the contributor and verifier of the coordinator.
+        // Fetch the contributor of the coordinator.
         let contributor = test_coordinator_contributor(&environment).unwrap();
         let contributor_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
         let token = String::from("test_token");
 
         // Initialize a new coordinator state.
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
         let current_round_height = 5;
-        let mut state = CoordinatorState::new(environment.clone());
         state.initialize(current_round_height);
+
+        // Canceling is not allowed outside of the precommit stage.
+        assert!(matches!(
+            state.cancel_precommit_next_round(&time),
+            Err(CoordinatorError::NextRoundNotInPrecommit)
+        ));
+
+        // Add the contributor of the coordinator and prepare the current round for the precommit.
         state
             .add_to_queue(contributor.clone(), Some(contributor_ip), token, 10, &time)
             .unwrap();
         state.update_queue().unwrap();
         state.aggregating_current_round(&time).unwrap();
         state.aggregated_current_round(&time).unwrap();
-        assert!(state.is_current_round_finished());
-        assert!(state.is_current_round_aggregated());
+        state.update_round_metrics();
+        state.update_current_contributors(&time).unwrap();
+        state.update_dropped_participants(&time).unwrap();
+        state.update_banned_participants().unwrap();
         assert!(state.is_precommit_next_round_ready(&time));
 
-        // Advance the coordinator to the next round.
-        let next_round_height = current_round_height + 1;
-        state.precommit_next_round(next_round_height, &time).unwrap();
-        state.commit_next_round();
+        // Run the precommit for the next round.
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        assert_eq!(CoordinatorStatus::Precommit, state.status);
         assert_eq!(0, state.queue.len());
-        assert_eq!(0, state.next.len());
-        assert_eq!(Some(next_round_height), state.current_round_height);
-        assert_eq!(1, state.current_contributors.len());
-        assert_eq!(0, state.current_verifiers.len());
-        assert_eq!(0, state.pending_verification.len());
-        assert_eq!(0, state.finished_contributors.get(&next_round_height).unwrap().len());
-        assert_eq!(0, state.finished_verifiers.get(&next_round_height).unwrap().len());
-        assert_eq!(0, state.dropped.len());
-        assert_eq!(0, state.banned.len());
+        assert_eq!(1, state.next.len());
 
-        // Fetch the maximum number of tasks permitted for a contributor.
+        // Cancel the precommit and check that the contributor is back in the queue.
+        let canceled = state.cancel_precommit_next_round(&time).unwrap();
+        assert_eq!(vec![contributor.clone()], canceled);
+        assert_eq!(CoordinatorStatus::Rollback, state.status);
+        assert_eq!(1, state.queue.len());
+        assert_eq!(0, state.next.len());
+        assert_eq!(Some(current_round_height), state.current_round_height);
+        assert_eq!(Some(current_round_height + 1), state.queue.get(&contributor).unwrap().1);
+        assert!(state.is_current_round_finished());
+        assert!(state.is_current_round_aggregated());
+        assert!(state.is_precommit_next_round_ready(&time));
+    }
+
+    #[test]
+    fn test_replace_token_schedule() {
+        let time = SystemTimeSource::new();
+        let environment = TEST_ENVIRONMENT.clone();
+
+        let contributor = TEST_CONTRIBUTOR_ID.clone();
+        let cohort_tokens = |tokens: &[&str]| -> HashSet<String> { tokens.iter().map(|t| t.to_string()).collect() };
+
+        // Initialize a new coordinator state in the middle of its second cohort.
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(5);
+        state.update_tokens(vec![
+            cohort_tokens(&["token_1"]),
+            cohort_tokens(&["token_2", "token_3"]),
+            cohort_tokens(&["token_4"]),
+        ]);
+        state.cohort_durations = vec![100];
+        state.ceremony_start_time = OffsetDateTime::now_utc() - Duration::seconds(150);
+        assert_eq!(1, state.get_current_cohort_index());
+
+        // A contributor of the current cohort is active, the token of a past one is blacklisted.
+        state
+            .add_to_queue(contributor.clone(), None, String::from("token_2"), 10, &time)
+            .unwrap();
+        state
+            .runtime_state
+            .tokens_in_use
+            .insert(String::from("token_2"), contributor.clone());
+        state
+            .blacklisted_tokens
+            .insert(String::from("token_1"), TEST_CONTRIBUTOR_ID_2.clone());
+        state
+            .blacklisted_tokens
+            .insert(String::from("token_4"), TEST_CONTRIBUTOR_ID_2.clone());
+
+        // Inconsistent schedules are rejected without changing anything.
+        let inconsistent_schedules = vec![
+            (vec![], vec![100]),
+            (vec![cohort_tokens(&["token_1"]), cohort_tokens(&["token_2"])], vec![
+                100, 0,
+            ]),
+            (
+                vec![cohort_tokens(&["token_1"]), cohort_tokens(&["token_2", "token_1"])],
+                vec![100],
+            ),
+            // The first cohort already elapsed.
+            (vec![cohort_tokens(&["token_1"]), cohort_tokens(&["token_2"])], vec![
+                120, 100,
+            ]),
+            // The current cohort would end before now.
+            (
+                vec![
+                    cohort_tokens(&["token_1"]),
+                    cohort_tokens(&["token_2"]),
+                    cohort_tokens(&["token_4"]),
+                ],
+                vec![100, 40],
+            ),
+            // The current cohort has no tokens.
+            (vec![cohort_tokens(&["token_1"])], vec![100]),
+            // The token in use is dropped.
+            (vec![cohort_tokens(&["token_1"]), cohort_tokens(&["token_3"])], vec![
+                100,
+            ]),
+        ];
+        for (tokens, cohort_durations) in inconsistent_schedules {
+            let result = state.replace_token_schedule(tokens, cohort_durations);
+            assert!(matches!(
+                result,
+                Err(CoordinatorError::TokenScheduleInvalid)
+                    | Err(CoordinatorError::TokenScheduleDuplicateToken(_))
+                    | Err(CoordinatorError::TokenScheduleInconsistent(_))
+            ));
+            assert_eq!(3, state.get_tokens().len());
+            assert_eq!(vec![100], state.cohort_durations);
+        }
+
+        // A consistent schedule extends the current cohort and drops the last one.
+        state
+            .replace_token_schedule(
+                vec![cohort_tokens(&["token_1"]), cohort_tokens(&["token_2", "token_5"])],
+                vec![100, 200],
+            )
+            .unwrap();
+        assert_eq!(1, state.get_current_cohort_index());
+        assert_eq!(vec![100, 200], state.cohort_durations);
+        assert!(state.tokens(1).unwrap().contains("token_5"));
+        assert!(state.tokens(2).is_none());
+
+        // The active contributor and the tokens which still apply are preserved.
+        assert!(state.queue.contains_key(&contributor));
+        assert!(state.is_token_in_use("token_2"));
+        assert!(state.is_token_blacklisted("token_1"));
+        assert!(!state.is_token_blacklisted("token_4"));
+    }
+
+    #[test]
+    fn test_merge_cohorts() {
+        let cohort_tokens = |tokens: &[&str]| -> HashSet<String> { tokens.iter().map(|t| t.to_string()).collect() };
+
+        // Initialize a new coordinator state in the middle of its second cohort.
+        let mut state = CoordinatorState::new(TEST_ENVIRONMENT.clone()).unwrap();
+        state.initialize(5);
+        state.update_tokens(vec![
+            cohort_tokens(&["token_1"]),
+            cohort_tokens(&["token_2"]),
+            cohort_tokens(&["token_3"]),
+            cohort_tokens(&["token_4"]),
+            cohort_tokens(&["token_5"]),
+        ]);
+        state.cohort_durations = vec![100, 100, 100, 200, 300];
+        state.ceremony_start_time = OffsetDateTime::now_utc() - Duration::seconds(150);
+        assert_eq!(1, state.get_current_cohort_index());
+
+        // The active and the past cohorts cannot be merged, nor into a past cohort.
+        for (from, into) in [(1, 2), (0, 2), (3, 0), (2, 2), (2, 5)] {
+            assert!(matches!(
+                state.merge_cohorts(from, into),
+                Err(CoordinatorError::CohortMergeInvalid(_))
+            ));
+            assert_eq!(5, state.get_number_of_cohorts());
+        }
+
+        // The cohorts of the queue participants follow the merge.
+        let (before, merged, after) = (
+            Participant::new_contributor("before"),
+            Participant::new_contributor("merged"),
+            Participant::new_contributor("after"),
+        );
+        state.participant_cohorts.insert(before.clone(), 1);
+        state.participant_cohorts.insert(merged.clone(), 2);
+        state.participant_cohorts.insert(after.clone(), 4);
+
+        // Merge into a later cohort, the following cohorts keep their durations.
+        state.merge_cohorts(2, 3).unwrap();
+        assert_eq!(4, state.get_number_of_cohorts());
+        assert_eq!(Some(&1), state.participant_cohorts.get(&before));
+        assert_eq!(Some(&2), state.participant_cohorts.get(&merged));
+        assert_eq!(Some(&3), state.participant_cohorts.get(&after));
+        assert_eq!(&cohort_tokens(&["token_3", "token_4"]), state.tokens(2).unwrap());
+        assert_eq!(vec![100, 100, 200, 300], state.cohort_durations);
+        assert_eq!(1, state.get_current_cohort_index());
+
+        // Merge into an earlier cohort, here the active one.
+        state.merge_cohorts(3, 1).unwrap();
+        assert_eq!(3, state.get_number_of_cohorts());
+        assert_eq!(&cohort_tokens(&["token_2", "token_5"]), state.tokens(1).unwrap());
+        assert_eq!(&cohort_tokens(&["token_3", "token_4"]), state.tokens(2).unwrap());
+        // The last duration still applies to the following cohorts.
+        assert_eq!(vec![100, 100, 200, 300], state.cohort_durations);
+        assert_eq!(1, state.get_current_cohort_index());
+    }
+
+    #[test]
+    fn test_check_token_cohort() {
+        let cohort_tokens = |tokens: &[&str]| -> HashSet<String> { tokens.iter().map(|t| t.to_string()).collect() };
+
+        // Initialize a new coordinator state in the middle of its second cohort.
+        let mut state = CoordinatorState::new(TEST_ENVIRONMENT.clone()).unwrap();
+        state.initialize(1);
+        state.update_tokens(vec![
+            cohort_tokens(&["token_1"]),
+            cohort_tokens(&["token_2"]),
+            cohort_tokens(&["token_3"]),
+            cohort_tokens(&["token_4"]),
+        ]);
+        state.cohort_durations = vec![100, 200];
+        state.ceremony_start_time = OffsetDateTime::now_utc() - Duration::seconds(150);
+        assert_eq!(1, state.get_current_cohort_index());
+
+        // The tokens of the past and current cohorts, and the unknown ones, are left to the other checks.
+        state.check_token_cohort("token_1").unwrap();
+        state.check_token_cohort("token_2").unwrap();
+        state.check_token_cohort("unknown_token").unwrap();
+
+        // The tokens of the future cohorts are rejected with the opening time of their cohort.
+        match state.check_token_cohort("token_3") {
+            Err(CoordinatorError::TokenForFutureCohort { cohort, opens_at }) => {
+                assert_eq!(3, cohort);
+                assert_eq!(state.ceremony_start_time + Duration::seconds(300), opens_at);
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+        match state.check_token_cohort("token_4") {
+            Err(CoordinatorError::TokenForFutureCohort { cohort, opens_at }) => {
+                assert_eq!(4, cohort);
+                assert_eq!(state.ceremony_start_time + Duration::seconds(500), opens_at);
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+
+        // Once the cohort opens, its tokens are accepted.
+        state.ceremony_start_time -= Duration::seconds(200);
+        assert_eq!(2, state.get_current_cohort_index());
+        state.check_token_cohort("token_3").unwrap();
+        assert!(state.check_token_cohort("token_4").is_err());
+    }
+
+    #[test]
+    fn test_participant_cohort() {
+        let time = SystemTimeSource::new();
+        let environment = TEST_ENVIRONMENT.clone();
+
+        // Fetch the contributor of the coordinator.
+        let contributor = test_coordinator_contributor(&environment).unwrap();
+        let contributor_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+        let token = String::from("test_token");
+
+        // Initialize a new coordinator state.
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        let current_round_height = 5;
+        state.initialize(current_round_height);
+        assert_eq!(None, state.participant_cohort(&contributor));
+
+        // Join the queue during the first cohort.
+        let cohort_duration = Duration::seconds(state.cohort_durations[0] as i64);
+        state.ceremony_start_time = OffsetDateTime::now_utc();
+        assert_eq!(0, state.get_current_cohort_index());
+        state
+            .add_to_queue(contributor.clone(), Some(contributor_ip), token, 10, &time)
+            .unwrap();
+        assert_eq!(Some(0), state.participant_cohort(&contributor));
+
+        // The current cohort advances while the contributor is queued.
+        state.ceremony_start_time -= cohort_duration;
+        assert_eq!(1, state.get_current_cohort_index());
+        state.update_queue().unwrap();
+        assert_eq!(Some(0), state.participant_cohort(&contributor));
+
+        // The cohort at join time is kept once the contributor is in the round.
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+        state.update_round_metrics();
+        state.update_current_contributors(&time).unwrap();
+        state.update_dropped_participants(&time).unwrap();
+        state.update_banned_participants().unwrap();
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        assert_eq!(Some(0), state.participant_cohort(&contributor));
+
+        // And once the contributor is back in the queue.
+        state.cancel_precommit_next_round(&time).unwrap();
+        assert_eq!(Some(0), state.participant_cohort(&contributor));
+    }
+
+    #[test]
+    fn test_pop_and_complete_tasks_contributor() {
+        let time = SystemTimeSource::new();
+        let environment = TEST_ENVIRONMENT.clone();
+
+        // Fetch the contributor and verifier of the coordinator.
+        let contributor = test_coordinator_contributor(&environment).unwrap();
+        let contributor_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+        let token = String::from("test_token");
+
+        // Initialize a new coordinator state.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(current_round_height);
+        state
+            .add_to_queue(contributor.clone(), Some(contributor_ip), token, 10, &time)
+            .unwrap();
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+        assert!(state.is_current_round_finished());
+        assert!(state.is_current_round_aggregated());
+        assert!(state.is_precommit_next_round_ready(&time));
+
+        // Advance the coordinator to the next round.
+        let next_round_height = current_round_height + 1;
+        state.precommit_next_round(next_round_height, &time).unwrap();
+        state.commit_next_round(&time);
+        assert_eq!(0, state.queue.len());
+        assert_eq!(0, state.next.len());
+        assert_eq!(Some(next_round_height), state.current_round_height);
+        assert_eq!(1, state.current_contributors.len());
+        assert_eq!(0, state.current_verifiers.len());
+        assert_eq!(0, state.pending_verification.len());
+        assert_eq!(0, state.finished_contributors.get(&next_round_height).unwrap().len());
+        assert_eq!(0, state.finished_verifiers.get(&next_round_height).unwrap().len());
+        assert_eq!(0, state.dropped.len());
+        assert_eq!(0, state.banned.len());
+
+        // Fetch the maximum number of tasks permitted for a contributor.
         let contributor_lock_chunk_limit = environment.contributor_lock_chunk_limit();
         for chunk_id in 0..contributor_lock_chunk_limit {
             // Fetch a pending task for the contributor.
@@ -4248,6 +8568,265 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_completed_task_contribution_id_nonzero() {
+        let time = SystemTimeSource::new();
+        let environment = TEST_ENVIRONMENT.clone();
+
+        let contributor = test_coordinator_contributor(&environment).unwrap();
+        let contributor_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+        let token = String::from("test_token");
+
+        // Initialize a new coordinator state and advance it to a fresh round.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(current_round_height);
+        state
+            .add_to_queue(contributor.clone(), Some(contributor_ip), token, 10, &time)
+            .unwrap();
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        state.commit_next_round(&time);
+
+        // The first task of a fresh chunk is contribution 1.
+        let task = state.fetch_task(&contributor, &time).unwrap();
+        assert_eq!(1, task.contribution_id());
+        state.acquired_lock(&contributor, task.chunk_id(), &time).unwrap();
+
+        // The contribution 0 is reserved for the initialization of the round.
+        let reserved_task = Task::new(task.chunk_id(), 0);
+        assert!(matches!(
+            state.completed_task(&contributor, &reserved_task, &time),
+            Err(CoordinatorError::ContributionIdMustBeNonzero)
+        ));
+        assert_eq!(0, state.pending_verification.len());
+
+        state.completed_task(&contributor, &task, &time).unwrap();
+        assert_eq!(1, state.pending_verification.len());
+    }
+
+    #[test]
+    fn test_force_release_lock() {
+        let time = SystemTimeSource::new();
+        let environment = TEST_ENVIRONMENT.clone();
+
+        let contributor = test_coordinator_contributor(&environment).unwrap();
+        let contributor_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+        let token = String::from("test_token");
+
+        // Initialize a new coordinator state and advance it to the next round.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(current_round_height);
+        state
+            .add_to_queue(contributor.clone(), Some(contributor_ip), token, 10, &time)
+            .unwrap();
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        state.commit_next_round(&time);
+
+        // Lock the first task, and fetch the second one without locking it.
+        let task = state.fetch_task(&contributor, &time).unwrap();
+        state.acquired_lock(&contributor, task.chunk_id(), &time).unwrap();
+        let unlocked_task = state.fetch_task(&contributor, &time).unwrap();
+
+        // Only a chunk locked by the participant can be released.
+        assert!(matches!(
+            state.force_release_lock(&contributor, unlocked_task.chunk_id(), &time),
+            Err(CoordinatorError::ChunkNotLockedOrByWrongParticipant)
+        ));
+        let other_contributor = Participant::new_contributor("other-contributor");
+        assert!(matches!(
+            state.force_release_lock(&other_contributor, task.chunk_id(), &time),
+            Err(CoordinatorError::ParticipantNotFound(_))
+        ));
+
+        // The released task is assigned back to the participant, who stays in the round.
+        assert_eq!(task, state.force_release_lock(&contributor, task.chunk_id(), &time).unwrap());
+        let participant_info = state.current_contributors.get(&contributor).unwrap();
+        assert!(participant_info.locked_chunks.is_empty());
+        assert_eq!(Some(&task), participant_info.assigned_tasks.front());
+        assert!(!participant_info.pending_tasks.contains(&task));
+        assert!(state.is_current_contributor(&contributor));
+
+        // The lock can't be released twice.
+        assert!(matches!(
+            state.force_release_lock(&contributor, task.chunk_id(), &time),
+            Err(CoordinatorError::ChunkNotLockedOrByWrongParticipant)
+        ));
+    }
+
+    #[test]
+    fn test_out_of_order_contributions() {
+        let time = SystemTimeSource::new();
+        let contributor_1 = Participant::new_contributor("contributor-1");
+        let contributor_2 = Participant::new_contributor("contributor-2");
+
+        // Start two contributors of the current round, the first one expected to build
+        // on the contribution of the second one to chunk 0.
+        let start_round = |environment: Environment| {
+            let current_round_height = 5;
+            let mut state = CoordinatorState::new(environment).unwrap();
+            state.initialize(current_round_height);
+
+            for (contributor, tasks) in [
+                (&contributor_1, vec![Task::new(0, 2), Task::new(1, 1)]),
+                (&contributor_2, vec![Task::new(0, 1)]),
+            ] {
+                let mut info = ParticipantInfo::new(contributor.clone(), current_round_height, 10, 0, &time);
+                info.start(tasks.into_iter().collect(), &time).unwrap();
+                state.current_contributors.insert(contributor.clone(), info);
+            }
+            state
+        };
+
+        // Contributing out of order is opt-in.
+        let mut state = start_round(TEST_ENVIRONMENT.clone());
+        assert!(matches!(
+            state.fetch_task_for_chunk(&contributor_1, 1, &time),
+            Err(CoordinatorError::OutOfOrderContributionsDisabled)
+        ));
+
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .contributor_lock_chunk_limit(2)
+            .out_of_order_contributions(true)
+            .into();
+        let mut state = start_round(environment);
+
+        // A task can't be fetched before the previous contribution to its chunk.
+        assert!(matches!(
+            state.fetch_task_for_chunk(&contributor_1, 0, &time),
+            Err(CoordinatorError::PreviousContributionMissing { current_task }) if current_task == Task::new(0, 2)
+        ));
+
+        // A later task can be fetched ahead of the next one, as long as it is assigned.
+        assert_eq!(
+            Task::new(1, 1),
+            state.fetch_task_for_chunk(&contributor_1, 1, &time).unwrap()
+        );
+        assert!(matches!(
+            state.fetch_task_for_chunk(&contributor_1, 1, &time),
+            Err(CoordinatorError::ParticipantUnauthorizedForChunkId { chunk_id: 1 })
+        ));
+        let info = state.current_contributors.get(&contributor_1).unwrap();
+        assert_eq!(vec![Task::new(0, 2)], info.assigned_tasks.iter().cloned().collect::<Vec<_>>());
+        assert_eq!(vec![Task::new(1, 1)], info.pending_tasks.iter().cloned().collect::<Vec<_>>());
+
+        // A contribution can't be completed before the previous contribution to its chunk.
+        let task = state.fetch_task(&contributor_1, &time).unwrap();
+        state.acquired_lock(&contributor_1, task.chunk_id(), &time).unwrap();
+        assert!(matches!(
+            state.completed_task(&contributor_1, &task, &time),
+            Err(CoordinatorError::PreviousContributionMissing { .. })
+        ));
+
+        // Once the previous contribution is completed, the chain can be extended.
+        let previous_task = state.fetch_task(&contributor_2, &time).unwrap();
+        state
+            .acquired_lock(&contributor_2, previous_task.chunk_id(), &time)
+            .unwrap();
+        state.completed_task(&contributor_2, &previous_task, &time).unwrap();
+        state.completed_task(&contributor_1, &task, &time).unwrap();
+    }
+
+    #[test]
+    fn test_per_participant_lock_limit() {
+        let time = SystemTimeSource::new();
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .contributor_lock_chunk_limit(3)
+            .into();
+        let contributor = Participant::new_contributor("contributor");
+
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment).unwrap();
+        state.initialize(current_round_height);
+        let mut info = ParticipantInfo::new(contributor.clone(), current_round_height, 10, 0, &time);
+        let tasks = (0..4).map(|chunk_id| Task::new(chunk_id, 1));
+        info.start(tasks.collect(), &time).unwrap();
+        state.current_contributors.insert(contributor.clone(), info);
+
+        // The limit of the participant can only lower the one of the environment.
+        state.set_participant_lock_limit(&contributor, Some(5)).unwrap();
+        assert_eq!(3, state.contributor_lock_chunk_limit(&contributor));
+        state.set_participant_lock_limit(&contributor, Some(1)).unwrap();
+        assert_eq!(1, state.contributor_lock_chunk_limit(&contributor));
+
+        let task = state.fetch_task(&contributor, &time).unwrap();
+        state.acquired_lock(&contributor, task.chunk_id(), &time).unwrap();
+        assert!(matches!(
+            state.fetch_task(&contributor, &time),
+            Err(CoordinatorError::ParticipantHasLockedMaximumChunks)
+        ));
+
+        // Clearing the limit falls back to the one of the environment.
+        state.set_participant_lock_limit(&contributor, None).unwrap();
+        assert_eq!(3, state.contributor_lock_chunk_limit(&contributor));
+        state.fetch_task(&contributor, &time).unwrap();
+
+        // The limit of the participant is kept across a reset of the round.
+        state.set_participant_lock_limit(&contributor, Some(2)).unwrap();
+        state.reset_current_round(true, &time).unwrap();
+        assert_eq!(2, state.contributor_lock_chunk_limit(&contributor));
+
+        // A limit of 0 is rejected.
+        assert!(matches!(
+            state.set_participant_lock_limit(&contributor, Some(0)),
+            Err(CoordinatorError::LockLimitInvalid)
+        ));
+        assert_eq!(2, state.contributor_lock_chunk_limit(&contributor));
+
+        assert!(matches!(
+            state.set_participant_lock_limit(&Participant::new_verifier("verifier"), Some(1)),
+            Err(CoordinatorError::ExpectedContributor)
+        ));
+    }
+
+    #[test]
+    fn test_unassigned_completed_tasks() {
+        let time = SystemTimeSource::new();
+        let environment = TEST_ENVIRONMENT.clone();
+
+        let contributor = test_coordinator_contributor(&environment).unwrap();
+        let contributor_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+        let verifier = test_coordinator_verifier(&environment).unwrap();
+        let token = String::from("test_token");
+
+        // Initialize a new coordinator state and advance it to the next round.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(current_round_height);
+        state
+            .add_to_queue(contributor.clone(), Some(contributor_ip), token, 10, &time)
+            .unwrap();
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        state.commit_next_round(&time);
+
+        // Complete two tasks, both pending verification.
+        let mut completed = vec![];
+        for _ in 0..2 {
+            let task = state.fetch_task(&contributor, &time).unwrap();
+            state.acquired_lock(&contributor, task.chunk_id(), &time).unwrap();
+            state.completed_task(&contributor, &task, &time).unwrap();
+            completed.push(task);
+        }
+        assert!(state.unassigned_completed_tasks().is_empty());
+
+        // A completed task without pending verification is reported.
+        state.pending_verification.remove(&completed[1]);
+        assert_eq!(vec![completed[1]], state.unassigned_completed_tasks());
+
+        // A verified task is not reported.
+        state.completed_task(&verifier, &completed[0], &time).unwrap();
+        assert_eq!(vec![completed[1]], state.unassigned_completed_tasks());
+    }
+
     #[test]
     fn test_pop_and_complete_tasks_verifier() {
         let time = SystemTimeSource::new();
@@ -4261,7 +8840,7 @@ mod tests {
 
         // Initialize a new coordinator state.
         let current_round_height = 5;
-        let mut state = CoordinatorState::new(environment.clone());
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
         state.initialize(current_round_height);
         state
             .add_to_queue(contributor.clone(), Some(contributor_ip), token, 10, &time)
@@ -4269,62 +8848,693 @@ mod tests {
         state.update_queue().unwrap();
         state.aggregating_current_round(&time).unwrap();
         state.aggregated_current_round(&time).unwrap();
-        assert!(state.is_current_round_finished());
-        assert!(state.is_current_round_aggregated());
-        assert!(state.is_precommit_next_round_ready(&time));
+        assert!(state.is_current_round_finished());
+        assert!(state.is_current_round_aggregated());
+        assert!(state.is_precommit_next_round_ready(&time));
+
+        // Advance the coordinator to the next round.
+        let next_round_height = current_round_height + 1;
+        state.precommit_next_round(next_round_height, &time).unwrap();
+        state.commit_next_round(&time);
+        assert_eq!(Some(next_round_height), state.current_round_height);
+        assert_eq!(1, state.current_contributors.len());
+        assert_eq!(0, state.current_verifiers.len());
+        assert_eq!(0, state.pending_verification.len());
+        assert_eq!(0, state.finished_contributors.get(&next_round_height).unwrap().len());
+        assert_eq!(0, state.finished_verifiers.get(&next_round_height).unwrap().len());
+
+        // Ensure that the verifier cannot pop a task prior to a contributor completing a task.
+        let try_task = state.fetch_task(&verifier, &time);
+        assert!(try_task.is_err());
+
+        // Fetch the maximum number of tasks permitted for a contributor.
+        let contributor_lock_chunk_limit = environment.contributor_lock_chunk_limit();
+        for i in 0..contributor_lock_chunk_limit {
+            // Fetch a pending task for the contributor.
+            let task = state.fetch_task(&contributor, &time).unwrap();
+            let chunk_id = i as u64;
+            assert_eq!((chunk_id, 1), (task.chunk_id(), task.contribution_id()));
+
+            state.acquired_lock(&contributor, chunk_id, &time).unwrap();
+            let completed_task = Task::new(chunk_id, task.contribution_id());
+            state.completed_task(&contributor, &completed_task, &time).unwrap();
+            assert_eq!(i + 1, state.pending_verification.len());
+        }
+        assert_eq!(Some(next_round_height), state.current_round_height);
+        assert_eq!(1, state.current_contributors.len());
+        assert_eq!(0, state.current_verifiers.len());
+        assert_eq!(contributor_lock_chunk_limit, state.pending_verification.len());
+        assert_eq!(0, state.finished_contributors.get(&next_round_height).unwrap().len());
+        assert_eq!(0, state.finished_verifiers.get(&next_round_height).unwrap().len());
+
+        // Fetch the maximum number of tasks permitted for a verifier.
+        for _ in 0..environment.verifier_lock_chunk_limit() {
+            // Fetch a pending task for the verifier.
+            let task = fetch_task_for_verifier(&mut state).unwrap();
+            state.completed_task(&verifier, &task, &time).unwrap();
+        }
+        assert_eq!(Some(next_round_height), state.current_round_height);
+        assert_eq!(1, state.current_contributors.len());
+        assert_eq!(0, state.current_verifiers.len());
+        assert_eq!(0, state.pending_verification.len());
+        assert_eq!(0, state.finished_contributors.get(&next_round_height).unwrap().len());
+        assert_eq!(0, state.finished_verifiers.get(&next_round_height).unwrap().len());
+
+        // Attempt to fetch past the permitted lock chunk limit.
+        for _ in 0..10 {
+            assert_eq!(None, fetch_task_for_verifier(&mut state));
+        }
+    }
+
+    #[test]
+    fn test_fetch_verifier_task_lock_limit() {
+        let time = SystemTimeSource::new();
+        let environment = TEST_ENVIRONMENT.clone();
+
+        // Fetch the contributor and verifier of the coordinator.
+        let contributor = test_coordinator_contributor(&environment).unwrap();
+        let contributor_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+        let verifier = test_coordinator_verifier(&environment).unwrap();
+        let token = String::from("test_token");
+
+        // Initialize a new coordinator state and advance it to the next round.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(current_round_height);
+        state
+            .add_to_queue(contributor.clone(), Some(contributor_ip), token, 10, &time)
+            .unwrap();
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        state.commit_next_round(&time);
+
+        // Contributors cannot fetch verification tasks.
+        assert!(matches!(
+            state.fetch_verifier_task(&contributor),
+            Err(CoordinatorError::ExpectedVerifier)
+        ));
+
+        // Nothing to verify yet.
+        assert_eq!(None, state.fetch_verifier_task(&verifier).unwrap());
+
+        // Contribute to every chunk, leaving more pending verifications than the verifier lock limit.
+        let number_of_chunks = environment.number_of_chunks() as usize;
+        let verifier_lock_chunk_limit = environment.verifier_lock_chunk_limit();
+        assert!(number_of_chunks > verifier_lock_chunk_limit);
+        for _ in 0..number_of_chunks {
+            let task = state.fetch_task(&contributor, &time).unwrap();
+            state.acquired_lock(&contributor, task.chunk_id(), &time).unwrap();
+            state.completed_task(&contributor, &task, &time).unwrap();
+        }
+        assert_eq!(number_of_chunks, state.pending_verification.len());
+
+        // Lock tasks up to the limit, each fetch returns a distinct task.
+        let mut locked = HashSet::new();
+        for _ in 0..verifier_lock_chunk_limit {
+            let task = state.fetch_verifier_task(&verifier).unwrap().unwrap();
+            assert!(locked.insert(task));
+        }
+
+        // The verifier cannot exceed its lock limit.
+        assert!(matches!(
+            state.fetch_verifier_task(&verifier),
+            Err(CoordinatorError::ParticipantHasLockedMaximumChunks)
+        ));
+
+        // Completing a verification releases its lock.
+        let task = *locked.iter().next().unwrap();
+        state.completed_task(&verifier, &task, &time).unwrap();
+        let next_task = state.fetch_verifier_task(&verifier).unwrap().unwrap();
+        assert!(!locked.contains(&next_task));
+        assert!(matches!(
+            state.fetch_verifier_task(&verifier),
+            Err(CoordinatorError::ParticipantHasLockedMaximumChunks)
+        ));
+
+        // Releasing the lock after a failed verification lets the task be fetched again.
+        state.release_verifier_task(&verifier, &next_task);
+        assert_eq!(Some(next_task), state.fetch_verifier_task(&verifier).unwrap());
+    }
+
+    #[test]
+    fn test_resume_verification_after_restart() {
+        let time = SystemTimeSource::new();
+        let environment = TEST_ENVIRONMENT.clone();
+
+        // Fetch the contributor and verifier of the coordinator.
+        let contributor = test_coordinator_contributor(&environment).unwrap();
+        let contributor_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+        let verifier = test_coordinator_verifier(&environment).unwrap();
+        let token = String::from("test_token");
+
+        // Initialize a new coordinator state and advance it to the next round.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(current_round_height);
+        state
+            .add_to_queue(contributor.clone(), Some(contributor_ip), token, 10, &time)
+            .unwrap();
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        state.commit_next_round(&time);
+
+        // Contribute to every chunk.
+        for _ in 0..environment.number_of_chunks() {
+            let task = state.fetch_task(&contributor, &time).unwrap();
+            state.acquired_lock(&contributor, task.chunk_id(), &time).unwrap();
+            state.completed_task(&contributor, &task, &time).unwrap();
+        }
+
+        // Start verifying the last task, which would otherwise be fetched last.
+        let interrupted = *state
+            .pending_verification
+            .keys()
+            .max_by_key(|task| (task.chunk_id(), task.contribution_id()))
+            .unwrap();
+        state.started_verification(&interrupted, &time).unwrap();
+        assert!(state.is_verification_in_progress(&interrupted));
+
+        // Restart the coordinator, dropping the runtime state.
+        let mut state: CoordinatorState = serde_json::from_value(serde_json::to_value(&state).unwrap()).unwrap();
+        assert!(state.is_verification_in_progress(&interrupted));
+
+        // The interrupted verification is resumed first.
+        assert_eq!(Some(interrupted), state.fetch_verifier_task(&verifier).unwrap());
+
+        // Completing the verification clears the marker.
+        state.completed_task(&verifier, &interrupted, &time).unwrap();
+        assert!(!state.is_verification_in_progress(&interrupted));
+        assert_ne!(Some(interrupted), state.fetch_verifier_task(&verifier).unwrap());
+
+        // Only pending tasks can be marked.
+        assert!(matches!(
+            state.started_verification(&interrupted, &time),
+            Err(CoordinatorError::ChunkIdMissing)
+        ));
+    }
+
+    #[test]
+    fn test_cohort_verifiers() {
+        let default_verifier = test_coordinator_verifier(&TEST_ENVIRONMENT).unwrap();
+        let cohort_keypair = KeyPair::new();
+        let cohort_verifier = Participant::new_verifier(cohort_keypair.pubkey());
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .cohort_verifiers(1, &[cohort_keypair])
+            .into();
+
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(1);
+        assert!(state.is_coordinator_verifier(&default_verifier));
+        assert!(state.is_coordinator_verifier(&cohort_verifier));
+
+        // Verifications of each cohort are assigned to the verifier of the cohort, unmapped
+        // cohorts fall back to the default verifier.
+        let cohort_duration = Duration::seconds(state.cohort_durations[0] as i64);
+        let start_time = OffsetDateTime::now_utc();
+        for (cohort, expected_verifier) in [(0, &default_verifier), (1, &cohort_verifier), (2, &default_verifier)] {
+            state.ceremony_start_time = start_time - cohort_duration * cohort as u32;
+            assert_eq!(cohort, state.get_current_cohort_index());
+
+            let task = Task::new(0, cohort as u64 + 1);
+            state.add_pending_verification(&task).unwrap();
+            assert_eq!(Some(expected_verifier), state.pending_verification.get(&task));
+        }
+    }
+
+    #[test]
+    fn test_cohort_maximum_contributors() {
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .maximum_contributors_per_round(3)
+            .cohort_maximum_contributors_per_round(1, 1)
+            .into();
+
+        // Initialize a new coordinator state.
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(5);
+
+        // Add contributors to the queue, each joining one second after the previous one.
+        let contributors: Vec<Participant> = (1..=3).map(|id| Participant::Contributor(id.to_string())).collect();
+        for (id, contributor) in contributors.iter().enumerate() {
+            let contributor_ip = IpAddr::V4(format!("0.0.0.{}", id).parse().unwrap());
+            let token = format!("test_token_{}", id);
+            state
+                .add_to_queue(contributor.clone(), Some(contributor_ip), token, 10, &time)
+                .unwrap();
+            time.update(|prev| prev + Duration::seconds(1));
+        }
+
+        // The contributors are batched by the cap of the current cohort, unmapped
+        // cohorts fall back to the default cap.
+        let cohort_duration = Duration::seconds(state.cohort_durations[0] as i64);
+        let start_time = OffsetDateTime::now_utc();
+        for (cohort, expected_rounds) in [(0, [6, 6, 6]), (1, [6, 7, 8]), (2, [6, 6, 6])] {
+            state.ceremony_start_time = start_time - cohort_duration * cohort as u32;
+            assert_eq!(cohort, state.get_current_cohort_index());
+
+            state.update_queue().unwrap();
+            for (contributor, expected_round) in contributors.iter().zip(expected_rounds) {
+                assert_eq!(Some(expected_round), state.queue.get(contributor).unwrap().1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_verifier_selector() {
+        let first_verifier = Participant::new_verifier("first-verifier-public-key");
+        let second_verifier = Participant::new_verifier("second-verifier-public-key");
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .coordinator_verifiers(&[first_verifier.clone(), second_verifier.clone()])
+            .into();
+
+        // By default, every verification is assigned to the first verifier.
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(1);
+        for chunk_id in 0..4 {
+            let task = Task::new(chunk_id, 1);
+            state.add_pending_verification(&task).unwrap();
+            assert_eq!(Some(&first_verifier), state.pending_verification.get(&task));
+        }
+
+        // The least loaded selector alternates between the two verifiers.
+        let mut state = CoordinatorState::new(environment).unwrap();
+        state.initialize(1);
+        state.set_verifier_selector(Arc::new(LeastLoadedVerifierSelector));
+        for chunk_id in 0..4 {
+            let task = Task::new(chunk_id, 1);
+            state.add_pending_verification(&task).unwrap();
+            let expected_verifier = match chunk_id % 2 {
+                0 => &first_verifier,
+                _ => &second_verifier,
+            };
+            assert_eq!(Some(expected_verifier), state.pending_verification.get(&task));
+        }
+
+        // Once a verification completes, the next task goes to the verifier which freed up.
+        state.remove_pending_verification(&Task::new(1, 1)).unwrap();
+        let task = Task::new(4, 1);
+        state.add_pending_verification(&task).unwrap();
+        assert_eq!(Some(&second_verifier), state.pending_verification.get(&task));
+    }
+
+    #[test]
+    fn test_reorder_assigned_tasks() {
+        let time = SystemTimeSource::new();
+        let environment = TEST_ENVIRONMENT.clone();
+
+        let contributor = test_coordinator_contributor(&environment).unwrap();
+        let contributor_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+        let token = String::from("test_token");
+
+        // Initialize a new coordinator state and advance it to the next round.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(current_round_height);
+        state
+            .add_to_queue(contributor.clone(), Some(contributor_ip), token, 10, &time)
+            .unwrap();
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        state.commit_next_round(&time);
+
+        let assigned: Vec<Task> = state
+            .current_contributors
+            .get(&contributor)
+            .unwrap()
+            .assigned_tasks
+            .iter()
+            .cloned()
+            .collect();
+        assert!(assigned.len() > 1);
+
+        // Reject an order which is not a permutation of the assigned tasks.
+        let mut missing = assigned.clone();
+        missing.pop();
+        assert!(matches!(
+            state.reorder_assigned_tasks(&contributor, missing, &time),
+            Err(CoordinatorError::ParticipantTasksReorderInvalid)
+        ));
+        let mut duplicated = assigned.clone();
+        duplicated[0] = duplicated[1];
+        assert!(matches!(
+            state.reorder_assigned_tasks(&contributor, duplicated, &time),
+            Err(CoordinatorError::ParticipantTasksReorderInvalid)
+        ));
+
+        // Reverse the order of the assigned tasks.
+        let reversed: Vec<Task> = assigned.iter().rev().cloned().collect();
+        state
+            .reorder_assigned_tasks(&contributor, reversed.clone(), &time)
+            .unwrap();
+
+        // The tasks are popped in the new order, and the overall set is unchanged.
+        let popped: Vec<Task> = (0..assigned.len())
+            .map(|_| state.fetch_task(&contributor, &time).unwrap())
+            .collect();
+        assert_eq!(reversed, popped);
+        assert_eq!(
+            assigned.iter().collect::<HashSet<_>>(),
+            popped.iter().collect::<HashSet<_>>()
+        );
+    }
+
+    #[test]
+    fn test_strict_task_completion() {
+        let time = SystemTimeSource::new();
+        let environment = TEST_ENVIRONMENT.clone();
+        let contributor = test_coordinator_contributor(&environment).unwrap();
+
+        // Start a contributor of bucket 0 in a round of 4 chunks and 2 contributors.
+        let number_of_chunks = 4;
+        let number_of_contributors = 2;
+        let mut info = ParticipantInfo::new(contributor, 1, 10, 0, &time);
+        let tasks = initialize_tasks(0, number_of_chunks, number_of_contributors).unwrap();
+        info.start(tasks, &time).unwrap();
+
+        // Complete only half of the assigned tasks and skip the rest.
+        for _ in 0..2 {
+            let task = info.pop_task(&time).unwrap();
+            info.acquired_lock(task.chunk_id(), 0, &time).unwrap();
+            info.completed_task(&task, &time).unwrap();
+        }
+        info.assigned_tasks.clear();
+
+        // The lenient checks let the contributor finish.
+        assert!(info.clone().finish(&time).is_ok());
+
+        // The strict check rejects the premature finish.
+        assert!(matches!(
+            info.check_expected_tasks(BucketLayout::Fixed, number_of_chunks, number_of_contributors),
+            Err(CoordinatorError::ParticipantDidNotCompleteAllTasks)
+        ));
+
+        // Once every expected chunk is completed, the strict check passes.
+        let remaining =
+            expected_tasks_for_bucket(BucketLayout::Fixed, 0, number_of_chunks, number_of_contributors).unwrap();
+        for task in remaining.iter().skip(2) {
+            info.assigned_tasks.push_back(*task);
+            let task = info.pop_task(&time).unwrap();
+            info.acquired_lock(task.chunk_id(), 0, &time).unwrap();
+            info.completed_task(&task, &time).unwrap();
+        }
+        assert!(info
+            .check_expected_tasks(BucketLayout::Fixed, number_of_chunks, number_of_contributors)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_strict_task_completion_drops_incomplete_contributor() {
+        test_logger();
+
+        let time = SystemTimeSource::new();
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .maximum_contributors_per_round(2)
+            .strict_task_completion(true)
+            .into();
+
+        let contributor = Participant::new_contributor("contributor-1");
+        let contributor_2 = Participant::new_contributor("contributor-2");
+        let replacement = Participant::new_contributor("replacement");
+
+        // Initialize a new coordinator state and advance it to the next round.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(current_round_height);
+        for participant in [&contributor, &contributor_2] {
+            state
+                .add_to_queue(participant.clone(), None, String::from("test_token"), 10, &time)
+                .unwrap();
+        }
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        state.commit_next_round(&time);
+
+        // Queue a replacement so that the drop does not reset the round.
+        state
+            .add_to_queue(replacement.clone(), None, String::from("test_token"), 10, &time)
+            .unwrap();
+
+        // The contributor completes one task and skips the rest.
+        {
+            let info = state.current_contributors.get_mut(&contributor).unwrap();
+            let task = info.pop_task(&time).unwrap();
+            info.acquired_lock(task.chunk_id(), 0, &time).unwrap();
+            info.completed_task(&task, &time).unwrap();
+            info.assigned_tasks.clear();
+        }
+
+        // The contributor cannot finish the round.
+        state.update_current_contributors(&time).unwrap();
+        assert!(!state.is_finished_contributor_at_round(&contributor, current_round_height + 1));
+
+        // It is dropped instead, and its tasks are reassigned to the replacement.
+        let dropped = state.update_dropped_participants(&time).unwrap();
+        assert_eq!(1, dropped.len());
+        assert!(!state.current_contributors.contains_key(&contributor));
+        assert!(state.current_contributors.contains_key(&contributor_2));
+        assert!(state.current_contributors.contains_key(&replacement));
+    }
+
+    #[test]
+    fn test_drops_batched_across_update_cycles() {
+        test_logger();
+
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .maximum_contributors_per_round(3)
+            .maximum_drops_per_update(Some(1))
+            .into();
+
+        let contributors: Vec<Participant> = (1..=3)
+            .map(|id| Participant::new_contributor(&format!("contributor-{}", id)))
+            .collect();
+        let replacements: Vec<Participant> = (1..=3)
+            .map(|id| Participant::new_contributor(&format!("replacement-{}", id)))
+            .collect();
+
+        // Initialize a new coordinator state and advance it to the next round.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(current_round_height);
+        for contributor in &contributors {
+            state
+                .add_to_queue(contributor.clone(), None, String::from("test_token"), 10, &time)
+                .unwrap();
+        }
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        state.commit_next_round(&time);
+        assert_eq!(3, state.current_contributors.len());
+
+        // Queue replacements so that drops do not reset the round.
+        for replacement in &replacements {
+            state
+                .add_to_queue(replacement.clone(), None, String::from("test_token"), 10, &time)
+                .unwrap();
+        }
+
+        // Every contributor times out at once.
+        time.update(|prev| prev + environment.contributor_seen_timeout() + Duration::seconds(1));
+
+        // Only one contributor is dropped per update cycle.
+        for cycle in 1..=3 {
+            let dropped = state.update_dropped_participants(&time).unwrap();
+            assert_eq!(1, dropped.len());
+            assert_eq!(cycle, state.dropped.len());
+        }
+
+        // The replacements are live, so there is nothing left to drop.
+        let dropped = state.update_dropped_participants(&time).unwrap();
+        assert_eq!(0, dropped.len());
+        assert_eq!(3, state.dropped.len());
+        for contributor in &contributors {
+            assert!(!state.current_contributors.contains_key(contributor));
+        }
+        for replacement in &replacements {
+            assert!(state.current_contributors.contains_key(replacement));
+        }
+    }
+
+    #[test]
+    fn test_next_round_assignment_preview() {
+        test_logger();
+
+        let time = SystemTimeSource::new();
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .maximum_contributors_per_round(3)
+            .into();
+
+        // Initialize a new coordinator state with contributors of different reliability.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(current_round_height);
+        for (id, reliability) in [5, 10, 7].iter().enumerate() {
+            let contributor = Participant::new_contributor(&format!("contributor-{}", id));
+            state
+                .add_to_queue(contributor, None, String::from("test_token"), *reliability, &time)
+                .unwrap();
+        }
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
 
-        // Advance the coordinator to the next round.
-        let next_round_height = current_round_height + 1;
-        state.precommit_next_round(next_round_height, &time).unwrap();
-        state.commit_next_round();
-        assert_eq!(Some(next_round_height), state.current_round_height);
-        assert_eq!(1, state.current_contributors.len());
-        assert_eq!(0, state.current_verifiers.len());
-        assert_eq!(0, state.pending_verification.len());
-        assert_eq!(0, state.finished_contributors.get(&next_round_height).unwrap().len());
-        assert_eq!(0, state.finished_verifiers.get(&next_round_height).unwrap().len());
+        // The preview sorts the contributors by reliability and does not modify the state.
+        let preview = state.next_round_assignment();
+        assert_eq!(
+            vec![10, 7, 5],
+            preview.iter().map(|entry| entry.reliability).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![0, 1, 2],
+            preview.iter().map(|entry| entry.bucket_id).collect::<Vec<_>>()
+        );
+        assert_eq!(3, state.queue.len());
+        assert!(state.next.is_empty());
 
-        // Ensure that the verifier cannot pop a task prior to a contributor completing a task.
-        let try_task = state.fetch_task(&verifier, &time);
-        assert!(try_task.is_err());
+        // The precommit produces the previewed assignment.
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        for entry in &preview {
+            let info = state.next.get(&entry.participant).unwrap();
+            assert_eq!(entry.bucket_id, info.bucket_id);
+            assert_eq!(entry.reliability, info.reliability);
+        }
+        assert_eq!(preview.len(), state.next.len());
+        assert_eq!(preview, state.next_round_assignment());
+    }
 
-        // Fetch the maximum number of tasks permitted for a contributor.
-        let contributor_lock_chunk_limit = environment.contributor_lock_chunk_limit();
-        for i in 0..contributor_lock_chunk_limit {
-            // Fetch a pending task for the contributor.
-            let task = state.fetch_task(&contributor, &time).unwrap();
-            let chunk_id = i as u64;
-            assert_eq!((chunk_id, 1), (task.chunk_id(), task.contribution_id()));
+    #[test]
+    fn test_assignment_explanation() {
+        test_logger();
 
-            state.acquired_lock(&contributor, chunk_id, &time).unwrap();
-            let completed_task = Task::new(chunk_id, task.contribution_id());
-            state.completed_task(&contributor, &completed_task, &time).unwrap();
-            assert_eq!(i + 1, state.pending_verification.len());
-        }
-        assert_eq!(Some(next_round_height), state.current_round_height);
-        assert_eq!(1, state.current_contributors.len());
-        assert_eq!(0, state.current_verifiers.len());
-        assert_eq!(contributor_lock_chunk_limit, state.pending_verification.len());
-        assert_eq!(0, state.finished_contributors.get(&next_round_height).unwrap().len());
-        assert_eq!(0, state.finished_verifiers.get(&next_round_height).unwrap().len());
+        let time = SystemTimeSource::new();
 
-        // Fetch the maximum number of tasks permitted for a verifier.
-        for _ in 0..environment.verifier_lock_chunk_limit() {
-            // Fetch a pending task for the verifier.
-            let task = fetch_task_for_verifier(&state).unwrap();
-            state.completed_task(&verifier, &task, &time).unwrap();
+        for bucket_layout in [BucketLayout::Fixed, BucketLayout::Adaptive] {
+            let environment: Environment = Testing::from(Parameters::Test8Chunks)
+                .maximum_contributors_per_round(3)
+                .bucket_layout(bucket_layout)
+                .into();
+
+            // Initialize a new coordinator state and advance it to the next round with 3 contributors.
+            let current_round_height = 5;
+            let mut state = CoordinatorState::new(environment.clone()).unwrap();
+            state.initialize(current_round_height);
+            for id in 0..3 {
+                let contributor = Participant::new_contributor(&format!("contributor-{}", id));
+                state
+                    .add_to_queue(contributor, None, String::from("test_token"), 10, &time)
+                    .unwrap();
+            }
+            state.update_queue().unwrap();
+            state.aggregating_current_round(&time).unwrap();
+            state.aggregated_current_round(&time).unwrap();
+            state.precommit_next_round(current_round_height + 1, &time).unwrap();
+            state.commit_next_round(&time);
+
+            // The explained chunks are the tasks assigned to the contributors.
+            for (contributor, info) in state.current_contributors() {
+                let explanation = state.assignment_explanation(&contributor).unwrap();
+                assert_eq!(info.bucket_id, explanation.bucket_id);
+                assert_eq!(8, explanation.number_of_chunks);
+                assert_eq!(3, explanation.number_of_contributors);
+                assert_eq!(
+                    info.assigned_tasks.iter().cloned().collect::<Vec<_>>(),
+                    explanation
+                        .chunks
+                        .iter()
+                        .map(|chunk| Task::new(chunk.chunk_id, chunk.contribution_id))
+                        .collect::<Vec<_>>()
+                );
+
+                // The derivation shows the formula applied to the inputs.
+                if explanation.bucket_id == 2 {
+                    let expected = match bucket_layout {
+                        BucketLayout::Fixed => "(2 * 2 + 5) % 8 = 1",
+                        BucketLayout::Adaptive => "(2 * 8 / 3 + 5) % 8 = 2",
+                    };
+                    assert_eq!(expected, explanation.chunks[5].derivation);
+                }
+            }
         }
-        assert_eq!(Some(next_round_height), state.current_round_height);
-        assert_eq!(1, state.current_contributors.len());
-        assert_eq!(0, state.current_verifiers.len());
-        assert_eq!(0, state.pending_verification.len());
-        assert_eq!(0, state.finished_contributors.get(&next_round_height).unwrap().len());
-        assert_eq!(0, state.finished_verifiers.get(&next_round_height).unwrap().len());
 
-        // Attempt to fetch past the permitted lock chunk limit.
-        for _ in 0..10 {
-            assert_eq!(None, fetch_task_for_verifier(&state));
+        assert!(matches!(
+            CoordinatorState::new(Testing::from(Parameters::Test8Chunks).into())
+                .unwrap()
+                .assignment_explanation(&TEST_CONTRIBUTOR_ID),
+            Err(CoordinatorError::ParticipantNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_precommit_adaptive_bucket_layout() {
+        test_logger();
+
+        let time = SystemTimeSource::new();
+
+        // Returns the next round participants after a precommit of the given number of contributors.
+        let precommit = |bucket_layout: BucketLayout, number_of_contributors: usize| {
+            let environment: Environment = Testing::from(Parameters::Test8Chunks)
+                .maximum_contributors_per_round(number_of_contributors)
+                .bucket_layout(bucket_layout)
+                .into();
+
+            let current_round_height = 5;
+            let mut state = CoordinatorState::new(environment).unwrap();
+            state.initialize(current_round_height);
+            for id in 0..number_of_contributors {
+                let contributor = Participant::new_contributor(&format!("contributor-{}", id));
+                state
+                    .add_to_queue(contributor, None, String::from("test_token"), 10, &time)
+                    .unwrap();
+            }
+            state.update_queue().unwrap();
+            state.aggregating_current_round(&time).unwrap();
+            state.aggregated_current_round(&time).unwrap();
+            state
+                .precommit_next_round(current_round_height + 1, &time)
+                .map(|_| state.next.clone())
+        };
+
+        // Returns the number of chunks of each bucket, as the number of first contributions.
+        let bucket_sizes = |next: &HashMap<Participant, ParticipantInfo>| {
+            let mut sizes = vec![0; next.len()];
+            for info in next.values() {
+                sizes[info.bucket_id as usize] = info
+                    .assigned_tasks
+                    .iter()
+                    .filter(|task| task.contribution_id() == 1)
+                    .count();
+            }
+            sizes
+        };
+
+        // With few contributors, the adaptive layout spreads the remaining chunks.
+        let next = precommit(BucketLayout::Fixed, 3).unwrap();
+        assert_eq!(vec![2, 2, 4], bucket_sizes(&next));
+        let next = precommit(BucketLayout::Adaptive, 3).unwrap();
+        assert_eq!(vec![2, 3, 3], bucket_sizes(&next));
+
+        // With more contributors than chunks, only the adaptive layout assigns the tasks.
+        assert!(precommit(BucketLayout::Fixed, 10).is_err());
+        let next = precommit(BucketLayout::Adaptive, 10).unwrap();
+        assert_eq!(8, bucket_sizes(&next).iter().sum::<usize>());
+
+        // Every contributor contributes once to every chunk.
+        let mut all_tasks = HashSet::new();
+        for info in next.values() {
+            assert_eq!(8, info.assigned_tasks.len());
+            assert!(info.assigned_tasks.iter().all(|task| all_tasks.insert(*task)));
         }
+        assert_eq!(80, all_tasks.len());
     }
 
     #[test]
@@ -4345,7 +9555,7 @@ mod tests {
 
         // Initialize a new coordinator state.
         let current_round_height = 5;
-        let mut state = CoordinatorState::new(environment.clone());
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
         state.initialize(current_round_height);
         state
             .add_to_queue(contributor_1.clone(), Some(contributor_1_ip), token, 10, &time)
@@ -4367,7 +9577,7 @@ mod tests {
         state.precommit_next_round(next_round_height, &time).unwrap();
         assert_eq!(0, state.queue.len());
         assert_eq!(2, state.next.len());
-        state.commit_next_round();
+        state.commit_next_round(&time);
         assert_eq!(0, state.queue.len());
         assert_eq!(0, state.next.len());
 
@@ -4408,13 +9618,13 @@ mod tests {
             assert!(!state.is_current_round_finished());
 
             // Fetch a pending task for the verifier.
-            let task = fetch_task_for_verifier(&state).unwrap();
+            let task = fetch_task_for_verifier(&mut state).unwrap();
             state.completed_task(&verifier, &task, &time).unwrap();
             assert_eq!(1, state.pending_verification.len());
             assert!(!state.is_current_round_finished());
 
             // Fetch a pending task for the verifier.
-            let task = fetch_task_for_verifier(&state).unwrap();
+            let task = fetch_task_for_verifier(&mut state).unwrap();
             state.completed_task(&verifier, &task, &time).unwrap();
             assert_eq!(0, state.pending_verification.len());
             assert!(!state.is_current_round_finished());
@@ -4462,7 +9672,7 @@ mod tests {
 
         // Initialize a new coordinator state.
         let current_round_height = 5;
-        let mut state = CoordinatorState::new(environment.clone());
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
         state.initialize(current_round_height);
         state
             .add_to_queue(contributor_1.clone(), Some(contributor_1_ip), token, 10, &time)
@@ -4484,7 +9694,7 @@ mod tests {
         state.precommit_next_round(next_round_height, &time).unwrap();
         assert_eq!(0, state.queue.len());
         assert_eq!(2, state.next.len());
-        state.commit_next_round();
+        state.commit_next_round(&time);
         assert_eq!(0, state.queue.len());
         assert_eq!(0, state.next.len());
 
@@ -4513,7 +9723,7 @@ mod tests {
             assert!(!state.is_current_round_finished());
 
             // Fetch a pending task for the verifier.
-            let task = fetch_task_for_verifier(&state).unwrap();
+            let task = fetch_task_for_verifier(&mut state).unwrap();
             state.completed_task(&verifier_1, &task, &time).unwrap();
             assert_eq!(0, state.pending_verification.len());
             assert!(!state.is_current_round_finished());
@@ -4558,7 +9768,7 @@ mod tests {
             assert!(!state.is_current_round_finished());
 
             // Fetch a pending task for the verifier.
-            let task = fetch_task_for_verifier(&state).unwrap();
+            let task = fetch_task_for_verifier(&mut state).unwrap();
             state.completed_task(&verifier_1, &task, &time).unwrap();
             assert_eq!(0, state.pending_verification.len());
             assert!(!state.is_current_round_finished());
@@ -4589,6 +9799,55 @@ mod tests {
         assert_eq!(0, state.banned.len());
     }
 
+    /// Test that a round reset neither reloads the token files, missing here,
+    /// nor loses the blacklists and the tokens of the ceremony.
+    #[test]
+    fn test_round_reset_without_token_files() {
+        let time = SystemTimeSource::new();
+        let environment = TEST_ENVIRONMENT.for_ceremony("ceremony-without-token-files");
+        let contributor = TEST_CONTRIBUTOR_ID.clone();
+        let contributor_ip = IpAddr::V4("0.0.0.1".parse().unwrap());
+        let tokens = vec![HashSet::from(["test_token".to_string()])];
+
+        // Initialize a new coordinator state, and advance it to the next round with one contributor.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment).unwrap();
+        state.initialize(current_round_height);
+        state.update_tokens(tokens.clone());
+        state
+            .add_to_queue(
+                contributor.clone(),
+                Some(contributor_ip),
+                String::from("test_token"),
+                10,
+                &time,
+            )
+            .unwrap();
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        state.commit_next_round(&time);
+
+        state
+            .blacklisted_tokens
+            .insert(String::from("used_token"), contributor.clone());
+        let blacklisted_ip = IpAddr::V4("0.0.0.2".parse().unwrap());
+        state.blacklisted_ips.insert(blacklisted_ip, contributor.clone());
+        let cohort_durations = state.cohort_durations.clone();
+
+        // Both the reset with the remaining contributors and the rollback succeed.
+        for force_rollback in [false, true] {
+            state.reset_current_round(force_rollback, &time).unwrap();
+
+            assert_eq!(Some(&contributor), state.blacklisted_tokens.get("used_token"));
+            assert_eq!(Some(&contributor), state.blacklisted_ips.get(&blacklisted_ip));
+            assert_eq!(&tokens, state.get_tokens());
+            assert_eq!(cohort_durations, state.cohort_durations);
+        }
+        assert_eq!(current_round_height, state.current_round_height());
+    }
+
     /// Test a manually triggered round reset during a round with two
     /// contributors and two verifiers.
     #[test]
@@ -4611,7 +9870,7 @@ mod tests {
 
         // Initialize a new coordinator state.
         let current_round_height = 5;
-        let mut state = CoordinatorState::new(environment.clone());
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
         state.initialize(current_round_height);
         state
             .add_to_queue(contributor_1.clone(), Some(contributor_1_ip), token, 10, &time)
@@ -4626,7 +9885,7 @@ mod tests {
         // Advance the coordinator to the next round.
         let next_round_height = current_round_height + 1;
         state.precommit_next_round(next_round_height, &time).unwrap();
-        state.commit_next_round();
+        state.commit_next_round(&time);
 
         let number_of_chunks = environment.number_of_chunks();
         let chunks_3_4: u64 = (number_of_chunks * 3) / 4;
@@ -4639,7 +9898,7 @@ mod tests {
                 state.acquired_lock(&contributor_1, task.chunk_id(), &time).unwrap();
                 state.completed_task(&contributor_1, &task, &time).unwrap();
                 // Fetch a pending task for the verifier.
-                let task = fetch_task_for_verifier(&state).unwrap();
+                let task = fetch_task_for_verifier(&mut state).unwrap();
                 state.completed_task(&verifier_1, &task, &time).unwrap();
 
                 {
@@ -4665,7 +9924,7 @@ mod tests {
                 state.acquired_lock(&contributor_2, task.chunk_id(), &time).unwrap();
                 state.completed_task(&contributor_2, &task, &time).unwrap();
                 // Fetch a pending task for the verifier.
-                let task = fetch_task_for_verifier(&state).unwrap();
+                let task = fetch_task_for_verifier(&mut state).unwrap();
                 state.completed_task(&verifier_1, &task, &time).unwrap();
 
                 {
@@ -4700,7 +9959,7 @@ mod tests {
                 state.acquired_lock(&contributor_1, task.chunk_id(), &time).unwrap();
                 state.completed_task(&contributor_1, &task, &time).unwrap();
                 // Fetch a pending task for the verifier.
-                let task = fetch_task_for_verifier(&state).unwrap();
+                let task = fetch_task_for_verifier(&mut state).unwrap();
                 state.completed_task(&verifier_1, &task, &time).unwrap();
 
                 {
@@ -4726,7 +9985,7 @@ mod tests {
                 state.acquired_lock(&contributor_2, task.chunk_id(), &time).unwrap();
                 state.completed_task(&contributor_2, &task, &time).unwrap();
                 // Fetch a pending task for the verifier.
-                let task = fetch_task_for_verifier(&state).unwrap();
+                let task = fetch_task_for_verifier(&mut state).unwrap();
                 state.completed_task(&verifier_1, &task, &time).unwrap();
 
                 {
@@ -4749,6 +10008,67 @@ mod tests {
         assert!(state.is_current_round_finished());
     }
 
+    #[test]
+    fn test_preview_reset_current_round() {
+        let time = SystemTimeSource::new();
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .coordinator_contributors(&[])
+            .into();
+
+        let contributor = TEST_CONTRIBUTOR_ID.clone();
+
+        // A reset cannot be previewed without a current round.
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        assert!(matches!(
+            state.preview_reset_current_round(false),
+            Err(CoordinatorError::RoundDoesNotExist)
+        ));
+
+        // Initialize a new coordinator state and advance it to the next round.
+        let current_round_height = 5;
+        state.initialize(current_round_height);
+        state
+            .add_to_queue(contributor.clone(), None, String::from("test_token"), 10, &time)
+            .unwrap();
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        state.commit_next_round(&time);
+
+        let serialized_state = serde_json::to_value(&state).unwrap();
+
+        // The round would be reset with its contributor.
+        assert_eq!(
+            ResetPreview {
+                rollback: false,
+                remove_participants: vec![],
+                target_round_height: current_round_height + 1,
+            },
+            state.preview_reset_current_round(false).unwrap()
+        );
+
+        // A forced rollback would remove the contributor.
+        let preview = state.preview_reset_current_round(true).unwrap();
+        assert_eq!(
+            ResetPreview {
+                rollback: true,
+                remove_participants: vec![contributor.clone()],
+                target_round_height: current_round_height,
+            },
+            preview
+        );
+
+        // The previews leave the state untouched.
+        assert_eq!(serialized_state, serde_json::to_value(&state).unwrap());
+
+        // The actual reset does what the preview reported.
+        let reset_action = state.reset_current_round(true, &time).unwrap();
+        assert_eq!(preview.rollback, reset_action.rollback);
+        assert_eq!(preview.remove_participants, reset_action.remove_participants);
+        assert_eq!(preview.target_round_height, state.current_round_height());
+    }
+
     /// Test a round reset triggered by a drop of one contributor
     /// during a round with two contributors and two verifiers. The
     /// reset is triggered because there are no replacement
@@ -4773,7 +10093,7 @@ mod tests {
 
         // Initialize a new coordinator state.
         let current_round_height = 5;
-        let mut state = CoordinatorState::new(environment.clone());
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
         state.initialize(current_round_height);
         state
             .add_to_queue(contributor_1.clone(), Some(contributor_1_ip), token, 10, &time)
@@ -4788,7 +10108,7 @@ mod tests {
         // Advance the coordinator to the next round.
         let next_round_height = current_round_height + 1;
         state.precommit_next_round(next_round_height, &time).unwrap();
-        state.commit_next_round();
+        state.commit_next_round(&time);
 
         let number_of_chunks = environment.number_of_chunks();
         let chunks_3_4: u64 = (number_of_chunks * 3) / 4;
@@ -4801,7 +10121,7 @@ mod tests {
                 state.acquired_lock(&contributor_1, task.chunk_id(), &time).unwrap();
                 state.completed_task(&contributor_1, &task, &time).unwrap();
                 // Fetch a pending task for the verifier.
-                let task = fetch_task_for_verifier(&state).unwrap();
+                let task = fetch_task_for_verifier(&mut state).unwrap();
                 state.completed_task(&verifier_1, &task, &time).unwrap();
 
                 {
@@ -4827,7 +10147,7 @@ mod tests {
                 state.acquired_lock(&contributor_2, task.chunk_id(), &time).unwrap();
                 state.completed_task(&contributor_2, &task, &time).unwrap();
                 // Fetch a pending task for the verifier.
-                let task = fetch_task_for_verifier(&state).unwrap();
+                let task = fetch_task_for_verifier(&mut state).unwrap();
                 state.completed_task(&verifier_1, &task, &time).unwrap();
 
                 {
@@ -4878,7 +10198,7 @@ mod tests {
                 state.acquired_lock(&contributor_2, task.chunk_id(), &time).unwrap();
                 state.completed_task(&contributor_2, &task, &time).unwrap();
                 // Fetch a pending task for the verifier.
-                let task = fetch_task_for_verifier(&state).unwrap();
+                let task = fetch_task_for_verifier(&mut state).unwrap();
                 state.completed_task(&verifier_1, &task, &time).unwrap();
 
                 {
@@ -4901,6 +10221,355 @@ mod tests {
         assert!(state.is_current_round_finished());
     }
 
+    /// Test that dropping a contributor only reassigns the tasks of the other
+    /// contributors building on its contributions, and leaves the tasks of a
+    /// contributor with no overlapping chunk untouched.
+    #[test]
+    fn test_drop_contributor_unaffected_tasks() {
+        test_logger();
+
+        let time = SystemTimeSource::new();
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .coordinator_contributors(&[])
+            .into();
+
+        // Fetch two contributors and one verifier.
+        let contributor_1 = TEST_CONTRIBUTOR_ID.clone();
+        let contributor_1_ip = IpAddr::V4("0.0.0.1".parse().unwrap());
+        let contributor_2 = TEST_CONTRIBUTOR_ID_2.clone();
+        let contributor_2_ip = IpAddr::V4("0.0.0.2".parse().unwrap());
+        let contributor_3 = TEST_CONTRIBUTOR_ID_3.clone();
+        let contributor_3_ip = IpAddr::V4("0.0.0.3".parse().unwrap());
+        let verifier_1 = TEST_VERIFIER_ID.clone();
+        let token = String::from("test_token");
+        let token2 = String::from("test_token_2");
+        let token3 = String::from("test_token_3");
+
+        // Initialize a new coordinator state.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(current_round_height);
+        state
+            .add_to_queue(contributor_1.clone(), Some(contributor_1_ip), token, 10, &time)
+            .unwrap();
+        state
+            .add_to_queue(contributor_2.clone(), Some(contributor_2_ip), token2, 9, &time)
+            .unwrap();
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+
+        // Advance the coordinator to the next round.
+        let next_round_height = current_round_height + 1;
+        state.precommit_next_round(next_round_height, &time).unwrap();
+        state.commit_next_round(&time);
+
+        // Add a replacement for the dropped contributor to the queue, so that the round is not reset.
+        state
+            .add_to_queue(contributor_3.clone(), Some(contributor_3_ip), token3, 10, &time)
+            .unwrap();
+
+        // Only the first contributor contributes, in its own bucket.
+        let dropped_task = state.fetch_task(&contributor_1, &time).unwrap();
+        state
+            .acquired_lock(&contributor_1, dropped_task.chunk_id(), &time)
+            .unwrap();
+        state.completed_task(&contributor_1, &dropped_task, &time).unwrap();
+        let task = fetch_task_for_verifier(&mut state).unwrap();
+        state.completed_task(&verifier_1, &task, &time).unwrap();
+        state.update_round_metrics();
+
+        // Had the second contributor started its first task and completed, out of order, its task
+        // building on the dropped one, only the latter would be invalidated and assigned again.
+        let contributor_info = state.current_contributors.get(&contributor_2).unwrap();
+        let mut affected_info = contributor_info.clone();
+        let first_task = affected_info.assigned_tasks.pop_front().unwrap();
+        let overlapping_task = *affected_info
+            .assigned_tasks
+            .iter()
+            .find(|task| task.chunk_id() == dropped_task.chunk_id())
+            .unwrap();
+        assert!(overlapping_task.contribution_id() > dropped_task.contribution_id());
+        affected_info.assigned_tasks = affected_info
+            .assigned_tasks
+            .iter()
+            .filter(|task| **task != overlapping_task)
+            .cloned()
+            .collect();
+        affected_info.pending_tasks.push_back(first_task);
+        affected_info.completed_tasks.push_back(overlapping_task);
+
+        let tasks_by_chunk = HashMap::from([dropped_task.to_tuple()]);
+        let reassignment = affected_info
+            .reassign_dropped_tasks(
+                &tasks_by_chunk,
+                environment.bucket_layout(),
+                environment.number_of_chunks(),
+                state.current_metrics.as_ref().unwrap().number_of_contributors,
+            )
+            .unwrap()
+            .unwrap();
+
+        // The started task is kept, the invalidated task is disposed and back in place in the assigned tasks.
+        let mut expected_assigned_tasks = contributor_info.assigned_tasks.clone();
+        expected_assigned_tasks.pop_front();
+        assert_eq!(expected_assigned_tasks, reassignment.assigned_tasks);
+        assert_eq!(LinkedList::from([first_task]), reassignment.pending_tasks);
+        assert!(reassignment.completed_tasks.is_empty());
+        assert!(reassignment.disposing_tasks.is_empty());
+        assert_eq!(LinkedList::from([overlapping_task]), reassignment.disposed_tasks);
+
+        // The second contributor has no task building on the dropped one, so it is left untouched.
+        let contributor_tasks = |state: &CoordinatorState| {
+            let contributor_info = state.current_contributors.get(&contributor_2).unwrap();
+            serde_json::to_vec(&(
+                &contributor_info.assigned_tasks,
+                &contributor_info.pending_tasks,
+                &contributor_info.completed_tasks,
+                &contributor_info.disposed_tasks,
+            ))
+            .unwrap()
+        };
+        let contributor_tasks_before = contributor_tasks(&state);
+
+        state.drop_participant(&contributor_1, &time).unwrap();
+
+        assert_eq!(contributor_tasks_before, contributor_tasks(&state));
+
+        // The tasks of the dropped contributor are reassigned to the replacement.
+        let replacement_info = state.current_contributors.get(&contributor_3).unwrap();
+        assert!(replacement_info.assigned_tasks.contains(&dropped_task));
+    }
+
+    #[test]
+    fn test_dedup_tasks() {
+        test_logger();
+
+        let time = SystemTimeSource::new();
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .coordinator_contributors(&[])
+            .into();
+
+        let contributor = TEST_CONTRIBUTOR_ID.clone();
+        let contributor_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+        let token = String::from("test_token");
+
+        // Initialize a new coordinator state and advance it to the next round.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(current_round_height);
+        state
+            .add_to_queue(contributor.clone(), Some(contributor_ip), token, 10, &time)
+            .unwrap();
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        state.commit_next_round(&time);
+
+        // Complete the first task of the contributor.
+        let task = state.fetch_task(&contributor, &time).unwrap();
+        state.acquired_lock(&contributor, task.chunk_id(), &time).unwrap();
+        state.completed_task(&contributor, &task, &time).unwrap();
+
+        // Corrupt the task lists of the contributor with duplicates.
+        let contributor_info = state.current_contributors.get_mut(&contributor).unwrap();
+        let expected_assigned_tasks = contributor_info.assigned_tasks.clone();
+        let expected_completed_tasks = contributor_info.completed_tasks.clone();
+        let duplicate = *expected_assigned_tasks.front().unwrap();
+        contributor_info.assigned_tasks.push_back(duplicate);
+        contributor_info.assigned_tasks.push_back(duplicate);
+        contributor_info.completed_tasks.push_front(task);
+
+        // Load the corrupted state.
+        let serialized = serde_json::to_vec(&state).unwrap();
+        let mut state: CoordinatorState = serde_json::from_slice(&serialized).unwrap();
+
+        // The duplicates are removed, preserving the order of the tasks.
+        assert_eq!(3, state.dedup_tasks());
+        let contributor_info = state.current_contributors.get(&contributor).unwrap();
+        assert_eq!(expected_assigned_tasks, contributor_info.assigned_tasks);
+        assert_eq!(expected_completed_tasks, contributor_info.completed_tasks);
+
+        // The state is left untouched once normalized.
+        assert_eq!(0, state.dedup_tasks());
+
+        // The contributor fetches the next task as usual.
+        assert_eq!(duplicate, state.fetch_task(&contributor, &time).unwrap());
+    }
+
+    #[test]
+    fn test_reconnect() {
+        test_logger();
+
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .coordinator_contributors(&[])
+            .reconnect_window(Duration::minutes(5))
+            .maximum_drops_per_update(Some(1))
+            .into();
+
+        let contributor_1 = TEST_CONTRIBUTOR_ID.clone();
+        let contributor_1_ip = IpAddr::V4("0.0.0.1".parse().unwrap());
+        let contributor_2 = TEST_CONTRIBUTOR_ID_2.clone();
+        let contributor_2_ip = IpAddr::V4("0.0.0.2".parse().unwrap());
+
+        // Initialize a new coordinator state and advance it to the next round.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(current_round_height);
+        state
+            .add_to_queue(
+                contributor_1.clone(),
+                Some(contributor_1_ip),
+                String::from("test_token"),
+                10,
+                &time,
+            )
+            .unwrap();
+        state
+            .add_to_queue(
+                contributor_2.clone(),
+                Some(contributor_2_ip),
+                String::from("test_token_2"),
+                9,
+                &time,
+            )
+            .unwrap();
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        state.commit_next_round(&time);
+
+        // The first contributor completes a task.
+        let task = state.fetch_task(&contributor_1, &time).unwrap();
+        state.acquired_lock(&contributor_1, task.chunk_id(), &time).unwrap();
+        state.completed_task(&contributor_1, &task, &time).unwrap();
+        let completed_tasks = state.current_contributors[&contributor_1].completed_tasks.clone();
+
+        // The first contributor locks its next task.
+        let locked_task = state.fetch_task(&contributor_1, &time).unwrap();
+        state
+            .acquired_lock(&contributor_1, locked_task.chunk_id(), &time)
+            .unwrap();
+
+        // Both contributors are not seen, but only one can be dropped in an update cycle.
+        time.update(|prev| prev + environment.contributor_seen_timeout() + Duration::seconds(1));
+        assert!(state.update_dropped_participants(&time).unwrap().is_empty());
+        assert_eq!(1, state.recently_dropped.len());
+        let disconnected = state.recently_dropped.keys().next().unwrap().clone();
+        state.reconnect(&disconnected, &time).unwrap();
+
+        // The first contributor is disconnected instead of being dropped, and its lock is released.
+        time.update(|prev| prev + environment.contributor_seen_timeout() + Duration::seconds(1));
+        state.heartbeat(&contributor_2, &time).unwrap();
+        assert!(state.update_dropped_participants(&time).unwrap().is_empty());
+        assert!(!state.current_contributors.contains_key(&contributor_1));
+        assert!(state.is_disconnected(&contributor_1));
+        assert!(!state.is_current_round_finished());
+        let (participant_info, _) = &state.recently_dropped[&contributor_1];
+        assert!(participant_info.locked_chunks.is_empty());
+        assert!(participant_info.pending_tasks.is_empty());
+        assert_eq!(Some(&locked_task), participant_info.assigned_tasks.front());
+
+        // The contributor reconnects within the window with its progress intact.
+        time.update(|prev| prev + Duration::minutes(1));
+        state.reconnect(&contributor_1, &time).unwrap();
+        assert!(state.recently_dropped.is_empty());
+        assert_eq!(
+            completed_tasks,
+            state.current_contributors[&contributor_1].completed_tasks
+        );
+        assert!(state.dropped.is_empty());
+
+        // A contributor which is not disconnected cannot reconnect.
+        assert!(matches!(
+            state.reconnect(&contributor_2, &time),
+            Err(CoordinatorError::ParticipantNotFound(_))
+        ));
+
+        // The contributor is disconnected again and does not reconnect within the window.
+        time.update(|prev| prev + environment.contributor_seen_timeout() + Duration::seconds(1));
+        state.heartbeat(&contributor_2, &time).unwrap();
+        assert!(state.update_dropped_participants(&time).unwrap().is_empty());
+        time.update(|prev| prev + environment.reconnect_window() + Duration::seconds(1));
+        state.heartbeat(&contributor_2, &time).unwrap();
+        assert!(matches!(
+            state.reconnect(&contributor_1, &time),
+            Err(CoordinatorError::ParticipantWasDropped)
+        ));
+
+        // The contributor is then dropped as usual.
+        assert_eq!(1, state.update_dropped_participants(&time).unwrap().len());
+        assert!(state.recently_dropped.is_empty());
+        assert!(!state.current_contributors.contains_key(&contributor_1));
+        assert!(state.dropped.iter().any(|info| info.id == contributor_1));
+        assert!(state.current_contributors.contains_key(&contributor_2));
+    }
+
+    #[test]
+    fn test_contribution_settle_time() {
+        test_logger();
+
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .coordinator_contributors(&[])
+            .contribution_settle_time(Duration::seconds(30))
+            .into();
+
+        let contributor = TEST_CONTRIBUTOR_ID.clone();
+        let contributor_ip = IpAddr::V4("0.0.0.1".parse().unwrap());
+
+        // Initialize a new coordinator state and advance it to the next round.
+        let current_round_height = 5;
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
+        state.initialize(current_round_height);
+        state
+            .add_to_queue(
+                contributor.clone(),
+                Some(contributor_ip),
+                String::from("test_token"),
+                10,
+                &time,
+            )
+            .unwrap();
+        state.update_queue().unwrap();
+        state.aggregating_current_round(&time).unwrap();
+        state.aggregated_current_round(&time).unwrap();
+        state.precommit_next_round(current_round_height + 1, &time).unwrap();
+        state.commit_next_round(&time);
+        assert!(state.is_settling(&time));
+
+        // The contribution submitted during the settle window is held.
+        let task = state.fetch_task(&contributor, &time).unwrap();
+        state.acquired_lock(&contributor, task.chunk_id(), &time).unwrap();
+        state.completed_task(&contributor, &task, &time).unwrap();
+        assert!(state.get_pending_verifications().is_empty());
+        assert_eq!(&[task], state.settling_tasks());
+        assert!(state.unassigned_completed_tasks().is_empty());
+
+        // The contribution is still held before the end of the settle window.
+        time.update(|prev| prev + Duration::seconds(29));
+        state.update_settled_tasks(&time).unwrap();
+        assert!(state.get_pending_verifications().is_empty());
+        assert_eq!(1, state.settling_tasks().len());
+
+        // The contribution is pending verification after the settle window.
+        time.update(|prev| prev + Duration::seconds(1));
+        assert!(!state.is_settling(&time));
+        state.update_settled_tasks(&time).unwrap();
+        assert!(state.settling_tasks().is_empty());
+        assert!(state.get_pending_verifications().contains_key(&task));
+
+        // The next contributions are no longer held.
+        let task = state.fetch_task(&contributor, &time).unwrap();
+        state.acquired_lock(&contributor, task.chunk_id(), &time).unwrap();
+        state.completed_task(&contributor, &task, &time).unwrap();
+        assert!(state.settling_tasks().is_empty());
+        assert!(state.get_pending_verifications().contains_key(&task));
+    }
+
     /// Test round reset when all contributors have been dropped
     /// during a round that has two contributors and two verifiers.
     /// The reset is triggered because there are no replacement
@@ -4926,7 +10595,7 @@ mod tests {
 
         // Initialize a new coordinator state.
         let current_round_height = 5;
-        let mut state = CoordinatorState::new(environment.clone());
+        let mut state = CoordinatorState::new(environment.clone()).unwrap();
         state.initialize(current_round_height);
         state
             .add_to_queue(contributor_1.clone(), Some(contributor_1_ip), token, 10, &time)
@@ -4938,7 +10607,7 @@ mod tests {
         // Advance the coordinator to the next round.
         let next_round_height = current_round_height + 1;
         state.precommit_next_round(next_round_height, &time).unwrap();
-        state.commit_next_round();
+        state.commit_next_round(&time);
 
         let number_of_chunks = environment.number_of_chunks();
         let chunks_3_4: u64 = (number_of_chunks * 3) / 4;
@@ -4951,7 +10620,7 @@ mod tests {
                 state.acquired_lock(&contributor_1, task.chunk_id(), &time).unwrap();
                 state.completed_task(&contributor_1, &task, &time).unwrap();
                 // Fetch a pending task for the verifier.
-                let task = fetch_task_for_verifier(&state).unwrap();
+                let task = fetch_task_for_verifier(&mut state).unwrap();
                 state.completed_task(&verifier_1, &task, &time).unwrap();
 
                 {
@@ -1,9 +1,15 @@
-use crate::{authentication::KeyPair, objects::Participant, storage::Disk};
+use crate::{
+    authentication::KeyPair,
+    coordinator_state::TOKENS_PATH,
+    objects::{task::BucketLayout, Participant},
+    storage::Disk,
+};
 pub use phase2::{helpers::CurveKind, ContributionMode, ProvingSystem};
 use setup_utils::{CheckForCorrectness, UseCompression};
 
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 type BatchSize = usize;
 type ChunkSize = usize;
@@ -71,6 +77,161 @@ impl Settings {
     }
 }
 
+/// A daily window, in UTC, during which the chunks can be locked for contribution.
+/// The window wraps around midnight if it ends before it starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContributionWindow {
+    pub start: time::Time,
+    pub end: time::Time,
+}
+
+impl ContributionWindow {
+    pub fn new(start: time::Time, end: time::Time) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns `true` if the given time of the day is in the window.
+    pub fn contains(&self, time: time::Time) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+impl std::str::FromStr for ContributionWindow {
+    type Err = String;
+
+    /// Parses a window formatted as `HH:MM-HH:MM`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_time = |t: &str| -> Result<time::Time, String> {
+            let (hour, minute) = t
+                .trim()
+                .split_once(':')
+                .ok_or_else(|| format!("Invalid time {}, expected HH:MM", t))?;
+            let hour = hour.parse::<u8>().map_err(|e| e.to_string())?;
+            let minute = minute.parse::<u8>().map_err(|e| e.to_string())?;
+
+            time::Time::from_hms(hour, minute, 0).map_err(|e| e.to_string())
+        };
+
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| format!("Invalid contribution window {}, expected HH:MM-HH:MM", s))?;
+
+        Ok(Self::new(parse_time(start)?, parse_time(end)?))
+    }
+}
+
+/// The order in which the contributors of the queue are assigned to the next rounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueuePriority {
+    /// By time joined.
+    Fifo,
+    /// By reliability tier, from the highest, then by time joined.
+    Reliability,
+}
+
+impl Default for QueuePriority {
+    fn default() -> Self {
+        QueuePriority::Fifo
+    }
+}
+
+impl QueuePriority {
+    /// Returns the tier of the given reliability score: 0 for the scores 0-3, 1 for 4-7 and 2 for 8 and above.
+    pub fn reliability_tier(reliability: u8) -> u8 {
+        match reliability {
+            0..=3 => 0,
+            4..=7 => 1,
+            _ => 2,
+        }
+    }
+}
+
+impl std::str::FromStr for QueuePriority {
+    type Err = String;
+
+    /// Parses `fifo` or `reliability`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "fifo" => Ok(QueuePriority::Fifo),
+            "reliability" => Ok(QueuePriority::Reliability),
+            _ => Err(format!("Invalid queue priority {}, expected fifo or reliability", s)),
+        }
+    }
+}
+
+/// The encoding of the coordinator state file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StateFormat {
+    /// Pretty printed JSON, readable by the operators.
+    Json,
+    /// Bincode, smaller and faster to save for large ceremonies. A state saved in bincode can only
+    /// be loaded by a coordinator of the same version.
+    Bincode,
+}
+
+impl Default for StateFormat {
+    fn default() -> Self {
+        StateFormat::Json
+    }
+}
+
+impl std::str::FromStr for StateFormat {
+    type Err = String;
+
+    /// Parses `json` or `bincode`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "json" => Ok(StateFormat::Json),
+            "bincode" => Ok(StateFormat::Bincode),
+            _ => Err(format!("Invalid state format {}, expected json or bincode", s)),
+        }
+    }
+}
+
+/// The way the replacement of a dropped contributor is picked among the contributors of the queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplacementSelection {
+    /// The contributor assigned to the closest next round, or the one who joined the queue first.
+    Deterministic,
+    /// A random contributor, weighted by reliability and inversely by the number of times
+    /// they were already picked as a replacement.
+    Weighted,
+}
+
+impl Default for ReplacementSelection {
+    fn default() -> Self {
+        ReplacementSelection::Deterministic
+    }
+}
+
+impl ReplacementSelection {
+    /// Returns the weight of a contributor with the given reliability score, already picked
+    /// the given number of times as a replacement.
+    pub fn weight(reliability: u8, replacement_count: u64) -> u64 {
+        (u64::from(reliability) + 1) * 1000 / (replacement_count + 1)
+    }
+}
+
+impl std::str::FromStr for ReplacementSelection {
+    type Err = String;
+
+    /// Parses `deterministic` or `weighted`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "deterministic" => Ok(ReplacementSelection::Deterministic),
+            "weighted" => Ok(ReplacementSelection::Weighted),
+            _ => Err(format!("Invalid replacement selection {}, expected deterministic or weighted", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Deployment {
     Testing,
@@ -235,6 +396,9 @@ pub struct Environment {
     minimum_contributors_per_round: usize,
     /// The maximum number of contributors permitted to participate in a round.
     maximum_contributors_per_round: usize,
+    /// The maximum number of contributors from the same region permitted to participate in a round.
+    #[serde(default)]
+    maximum_contributors_per_region: Option<usize>,
     /// The minimum number of verifiers permitted to participate in a round.
     minimum_verifiers_per_round: usize,
     /// The maximum number of verifiers permitted to participate in a round.
@@ -259,6 +423,74 @@ pub struct Environment {
     queue_seen_timeout: time::Duration,
     /// The number of drops tolerated by a participant before banning them from future rounds.
     participant_ban_threshold: u16,
+    /// The number of failed verifications of a chunk tolerated for a contributor before banning them,
+    /// none by default.
+    #[serde(default)]
+    contribution_retry_budget: u8,
+    /// The maximum number of participants dropped in a single update cycle, if any.
+    #[serde(default)]
+    maximum_drops_per_update: Option<usize>,
+    /// The number of consecutive failures to save the state before entering read-only mode, 0 to disable it.
+    #[serde(default)]
+    storage_failure_threshold: u32,
+    /// The number of bytes to keep free on the storage volume on top of a contribution to accept it, 0 to disable the check.
+    #[serde(default)]
+    minimum_free_disk_space: u64,
+    /// The minimum number of bytes of the contributions of specific rounds, not counting their trailing zero bytes.
+    /// Set in production as comma separated `round_height:size` pairs.
+    #[serde(default)]
+    round_minimum_contribution_size: HashMap<u64, u64>,
+    /// The setting to reject the contributions failing the sanity checks, instead of only logging them.
+    #[serde(default)]
+    strict_contribution_sanity: bool,
+    /// The number of past rounds whose finished participants are kept in memory, if any. Older rounds are archived to storage.
+    #[serde(default)]
+    finished_rounds_retention: Option<u64>,
+    /// The setting to treat the work requests of a participant as heartbeats.
+    #[serde(default)]
+    heartbeat_on_activity: bool,
+    /// The setting to require a signature of the token from the participant joining the queue.
+    #[serde(default)]
+    queue_join_signature_required: bool,
+    /// The setting to require contributors to complete every task expected of their bucket before finishing.
+    #[serde(default)]
+    strict_task_completion: bool,
+    /// The setting to let contributors lock any of their assigned tasks whose previous contribution exists.
+    #[serde(default)]
+    out_of_order_contributions: bool,
+    /// The setting to encrypt the contribution files at rest, with the key in the `NAMADA_MPC_STORAGE_KEY` env.
+    #[serde(default)]
+    encrypt_contributions: bool,
+    /// The setting to reuse the verdict of a previous verification of the same challenge and response files,
+    /// within the same round.
+    #[serde(default)]
+    verification_cache: bool,
+    /// The partition of the chunks into the buckets the contributors of a round start in.
+    #[serde(default)]
+    bucket_layout: BucketLayout,
+    /// The order in which the contributors of the queue are assigned to the next rounds.
+    #[serde(default)]
+    queue_priority: QueuePriority,
+    /// The way the replacement of a dropped contributor is picked among the contributors of the queue.
+    #[serde(default)]
+    replacement_selection: ReplacementSelection,
+    /// The encoding of the coordinator state file, which is detected when loading it.
+    #[serde(default)]
+    state_format: StateFormat,
+    /// The minimum duration a contributor who left the queue has to wait before joining it again.
+    #[serde(default)]
+    queue_rejoin_cooldown: time::Duration,
+    /// The duration during which a contributor dropped for not being seen can reconnect
+    /// to the current round with their progress intact, disabled if zero.
+    #[serde(default)]
+    reconnect_window: time::Duration,
+    /// The duration after the start of a round during which the submitted contributions
+    /// are held back from verification, disabled if zero.
+    #[serde(default)]
+    contribution_settle_time: time::Duration,
+    /// The daily windows during which the chunks can be locked for contribution, always if empty.
+    #[serde(default)]
+    contribution_windows: Vec<ContributionWindow>,
     /// The setting to allow current contributors to join the queue for the next round.
     allow_current_contributors_in_queue: bool,
     /// The setting to allow current verifiers to join the queue for the next round.
@@ -270,6 +502,15 @@ pub struct Environment {
     coordinator_contributors: Vec<Participant>,
     /// The verifiers managed by the coordinator.
     coordinator_verifiers: Vec<Participant>,
+    /// The verifiers managed by the coordinator for specific cohorts, replacing the default ones.
+    #[serde(default)]
+    cohort_verifiers: HashMap<usize, Vec<Participant>>,
+    /// The maximum number of contributors permitted in a round of specific cohorts, replacing the default one.
+    #[serde(default)]
+    cohort_maximum_contributors: HashMap<usize, usize>,
+    /// The signing keys of the verifiers managed by the coordinator for specific cohorts.
+    #[serde(default)]
+    cohort_verifier_signing_keys: HashMap<Participant, String>,
     /// The signing key used by the default coordinator's verifier
     default_verifier_signing_key: String,
 
@@ -279,6 +520,9 @@ pub struct Environment {
     deployment: Deployment,
     /// The base directory for disk storage of this coordinator.
     local_base_directory: String,
+    /// The name of the ceremony of this coordinator, if hosted along other ceremonies.
+    #[serde(default)]
+    ceremony_name: Option<String>,
 
     disable_reliability_zeroing: bool,
 }
@@ -337,6 +581,26 @@ impl Environment {
         self.maximum_contributors_per_round
     }
 
+    ///
+    /// Returns the maximum number of contributors permitted to participate
+    /// in a round of the given cohort, falling back to the default one for
+    /// cohorts without their own cap.
+    ///
+    pub fn cohort_maximum_contributors_per_round(&self, cohort: usize) -> usize {
+        self.cohort_maximum_contributors
+            .get(&cohort)
+            .copied()
+            .unwrap_or(self.maximum_contributors_per_round)
+    }
+
+    ///
+    /// Returns the maximum number of contributors from the same region
+    /// permitted to participate in a round, if any.
+    ///
+    pub const fn maximum_contributors_per_region(&self) -> Option<usize> {
+        self.maximum_contributors_per_region
+    }
+
     ///
     /// Returns the number of chunks a contributor is
     /// authorized to lock in tandem at any point during a round.
@@ -396,6 +660,208 @@ impl Environment {
         self.participant_ban_threshold
     }
 
+    ///
+    /// Returns the number of times a contributor is allowed to retry
+    /// a chunk whose contribution failed verification.
+    ///
+    pub const fn contribution_retry_budget(&self) -> u8 {
+        self.contribution_retry_budget
+    }
+
+    ///
+    /// Returns the maximum number of participants dropped in a single
+    /// update cycle, if any. Remaining drops are deferred to the next cycle.
+    ///
+    pub const fn maximum_drops_per_update(&self) -> Option<usize> {
+        self.maximum_drops_per_update
+    }
+
+    ///
+    /// Returns the number of consecutive failures to save the coordinator
+    /// state after which the coordinator enters a degraded read-only mode.
+    /// A threshold of 0 disables the read-only mode.
+    ///
+    pub const fn storage_failure_threshold(&self) -> u32 {
+        self.storage_failure_threshold
+    }
+
+    ///
+    /// Returns the number of bytes which must remain free on the storage
+    /// volume once a contribution is written for the contribution to be
+    /// accepted. A minimum of 0 disables the check.
+    ///
+    pub const fn minimum_free_disk_space(&self) -> u64 {
+        self.minimum_free_disk_space
+    }
+
+    ///
+    /// Returns the minimum number of bytes of a contribution to the given
+    /// round, not counting its trailing zero bytes. A minimum of 0 disables
+    /// the check.
+    ///
+    pub fn minimum_contribution_size(&self, round_height: u64) -> u64 {
+        self.round_minimum_contribution_size
+            .get(&round_height)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    ///
+    /// Returns the setting to reject the contributions failing the sanity
+    /// checks, instead of only logging the anomalies found.
+    ///
+    pub const fn strict_contribution_sanity(&self) -> bool {
+        self.strict_contribution_sanity
+    }
+
+    ///
+    /// Returns the number of past rounds whose finished participants are
+    /// kept in memory, if any. Older rounds are archived to storage.
+    ///
+    pub const fn finished_rounds_retention(&self) -> Option<u64> {
+        self.finished_rounds_retention
+    }
+
+    ///
+    /// Returns the setting to treat the work requests of a participant
+    /// (lock, fetch, contribute) as heartbeats.
+    ///
+    pub const fn heartbeat_on_activity(&self) -> bool {
+        self.heartbeat_on_activity
+    }
+
+    ///
+    /// Returns the setting to require a signature of the token, with the
+    /// key of the participant, to join the queue.
+    ///
+    pub const fn queue_join_signature_required(&self) -> bool {
+        self.queue_join_signature_required
+    }
+
+    ///
+    /// Returns the setting to require contributors to complete every
+    /// task expected of their bucket before finishing the round.
+    ///
+    pub const fn strict_task_completion(&self) -> bool {
+        self.strict_task_completion
+    }
+
+    ///
+    /// Returns the setting to let contributors lock any of their assigned
+    /// tasks, instead of the next one, as long as the previous contribution
+    /// to the chunk exists.
+    ///
+    pub const fn out_of_order_contributions(&self) -> bool {
+        self.out_of_order_contributions
+    }
+
+    ///
+    /// Returns the setting to encrypt the contribution files at rest.
+    ///
+    /// An encrypted file is decrypted whole in memory while it is read or written,
+    /// instead of being mapped, so the memory must fit the largest contribution files.
+    ///
+    pub const fn encrypt_contributions(&self) -> bool {
+        self.encrypt_contributions
+    }
+
+    ///
+    /// Returns the setting to reuse the verdict of a previous verification
+    /// of the same challenge and response files.
+    ///
+    pub const fn verification_cache(&self) -> bool {
+        self.verification_cache
+    }
+
+    ///
+    /// Returns the partition of the chunks into the buckets
+    /// the contributors of a round start in.
+    ///
+    pub const fn bucket_layout(&self) -> BucketLayout {
+        self.bucket_layout
+    }
+
+    ///
+    /// Returns the order in which the contributors of the queue
+    /// are assigned to the next rounds.
+    ///
+    pub const fn queue_priority(&self) -> QueuePriority {
+        self.queue_priority
+    }
+
+    ///
+    /// Returns the way the replacement of a dropped contributor
+    /// is picked among the contributors of the queue.
+    ///
+    pub const fn replacement_selection(&self) -> ReplacementSelection {
+        self.replacement_selection
+    }
+
+    ///
+    /// Returns the encoding the coordinator state file is saved in.
+    ///
+    pub const fn state_format(&self) -> StateFormat {
+        self.state_format
+    }
+
+    ///
+    /// Returns the minimum duration a contributor who left the queue
+    /// has to wait before joining it again.
+    ///
+    pub const fn queue_rejoin_cooldown(&self) -> time::Duration {
+        self.queue_rejoin_cooldown
+    }
+
+    ///
+    /// Returns the duration during which a contributor dropped for not
+    /// being seen can reconnect to the current round.
+    ///
+    pub const fn reconnect_window(&self) -> time::Duration {
+        self.reconnect_window
+    }
+
+    ///
+    /// Returns the duration after the start of a round during which
+    /// the submitted contributions are held back from verification.
+    ///
+    pub const fn contribution_settle_time(&self) -> time::Duration {
+        self.contribution_settle_time
+    }
+
+    ///
+    /// Returns the daily windows during which the chunks can be locked for contribution.
+    ///
+    pub fn contribution_windows(&self) -> &[ContributionWindow] {
+        &self.contribution_windows
+    }
+
+    ///
+    /// Returns the time the next contribution window opens,
+    /// or `None` if the contributions are accepted at the given time.
+    ///
+    pub fn contribution_paused_until(&self, now: time::OffsetDateTime) -> Option<time::OffsetDateTime> {
+        let now = now.to_offset(time::UtcOffset::UTC);
+        if self
+            .contribution_windows
+            .iter()
+            .any(|window| window.contains(now.time()))
+        {
+            return None;
+        }
+
+        self.contribution_windows
+            .iter()
+            .map(|window| {
+                let opens_at = now.replace_time(window.start);
+                if opens_at > now {
+                    opens_at
+                } else {
+                    opens_at + time::Duration::days(1)
+                }
+            })
+            .min()
+    }
+
     ///
     /// Returns the setting to allow current contributors to
     /// join the queue for the next round.
@@ -438,11 +904,37 @@ impl Environment {
         &self.coordinator_verifiers
     }
 
+    /// Returns the verifiers managed by the coordinator for the given cohort,
+    /// falling back to the default ones for cohorts without their own set.
+    pub fn cohort_verifiers(&self, cohort: usize) -> &Vec<Participant> {
+        self.cohort_verifiers
+            .get(&cohort)
+            .unwrap_or(&self.coordinator_verifiers)
+    }
+
+    /// Returns `true` if the given participant is a verifier managed by the coordinator for any cohort.
+    pub fn is_coordinator_verifier(&self, participant: &Participant) -> bool {
+        self.coordinator_verifiers.contains(participant)
+            || self
+                .cohort_verifiers
+                .values()
+                .any(|verifiers| verifiers.contains(participant))
+    }
+
     /// Returns the default verifiers' signing key.
     pub fn default_verifier_signing_key(&self) -> String {
         self.default_verifier_signing_key.clone()
     }
 
+    /// Returns the signing key of the given verifier managed by the coordinator, if any.
+    pub fn verifier_signing_key(&self, verifier: &Participant) -> Option<String> {
+        match self.cohort_verifier_signing_keys.get(verifier) {
+            Some(signing_key) => Some(signing_key.clone()),
+            None if self.coordinator_verifiers.contains(verifier) => Some(self.default_verifier_signing_key()),
+            None => None,
+        }
+    }
+
     ///
     /// Returns the software version number of the coordinator.
     ///
@@ -464,6 +956,36 @@ impl Environment {
         &self.local_base_directory
     }
 
+    ///
+    /// Returns the name of the ceremony of this coordinator, if hosted along other ceremonies.
+    ///
+    pub fn ceremony_name(&self) -> Option<&str> {
+        self.ceremony_name.as_deref()
+    }
+
+    ///
+    /// Returns the directory of the token files of this coordinator.
+    ///
+    pub fn tokens_path(&self) -> String {
+        match &self.ceremony_name {
+            Some(name) => format!("{}/{}", *TOKENS_PATH, name),
+            None => TOKENS_PATH.to_string(),
+        }
+    }
+
+    ///
+    /// Returns the environment of the given ceremony, hosted along other ceremonies.
+    /// The storage and the token files of the ceremony are in subdirectories named
+    /// after the ceremony.
+    ///
+    pub fn for_ceremony(&self, name: &str) -> Self {
+        Self {
+            local_base_directory: format!("{}/{}", self.local_base_directory, name),
+            ceremony_name: Some(name.to_string()),
+            ..self.clone()
+        }
+    }
+
     ///
     /// Returns the appropriate number of chunks for the coordinator
     /// to run given a proof system, power and chunk size.
@@ -476,12 +998,38 @@ impl Environment {
         1
     }
 
+    ///
+    /// Returns the timeouts currently applied by the coordinator.
+    ///
+    pub const fn timeouts(&self) -> Timeouts {
+        Timeouts {
+            contributor_seen_timeout: self.contributor_seen_timeout.whole_seconds(),
+            verifier_seen_timeout: self.verifier_seen_timeout.whole_seconds(),
+            participant_lock_timeout: self.participant_lock_timeout.whole_seconds(),
+            queue_seen_timeout: self.queue_seen_timeout.whole_seconds(),
+            queue_wait_time: self.queue_wait_time,
+            queue_rejoin_cooldown: self.queue_rejoin_cooldown.whole_seconds(),
+        }
+    }
+
     /// Returns the storage system of the coordinator.
     pub(crate) fn storage(&self) -> anyhow::Result<Disk> {
         Ok(Disk::load(self)?)
     }
 }
 
+/// The timeouts applied by the coordinator, in seconds, including the
+/// overrides set when building the environment (e.g. `NAMADA_MPC_TIMEOUT_SECONDS`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timeouts {
+    pub contributor_seen_timeout: i64,
+    pub verifier_seen_timeout: i64,
+    pub participant_lock_timeout: i64,
+    pub queue_seen_timeout: i64,
+    pub queue_wait_time: u64,
+    pub queue_rejoin_cooldown: i64,
+}
+
 impl From<Testing> for Environment {
     fn from(deployment: Testing) -> Environment {
         deployment.environment
@@ -517,6 +1065,24 @@ impl Testing {
         self
     }
 
+    pub fn cohort_maximum_contributors_per_round(mut self, cohort: usize, maximum: usize) -> Self {
+        // Check that the cap admits the minimum number of contributors of a round.
+        if maximum == 0 || maximum < self.environment.minimum_contributors_per_round {
+            panic!(
+                "Specifying to environment a maximum of {} contributors for cohort {}, below the minimum per round.",
+                maximum, cohort
+            )
+        }
+
+        self.environment.cohort_maximum_contributors.insert(cohort, maximum);
+        self
+    }
+
+    pub fn maximum_contributors_per_region(mut self, maximum: Option<usize>) -> Self {
+        self.environment.maximum_contributors_per_region = maximum;
+        self
+    }
+
     pub fn disable_reliability_zeroing(mut self, disable_zeroing: bool) -> Self {
         self.environment.disable_reliability_zeroing = disable_zeroing;
         self
@@ -546,6 +1112,32 @@ impl Testing {
         deployment
     }
 
+    #[inline]
+    pub fn cohort_verifiers(&self, cohort: usize, verifiers: &[KeyPair]) -> Self {
+        // Check that the cohort has at least one verifier.
+        if verifiers.is_empty() {
+            panic!(
+                "Specifying to environment an empty list of verifiers for cohort {}.",
+                cohort
+            )
+        }
+
+        let mut deployment = self.clone();
+        let participants = verifiers
+            .iter()
+            .map(|keypair| {
+                let verifier = Participant::new_verifier(keypair.pubkey());
+                deployment
+                    .environment
+                    .cohort_verifier_signing_keys
+                    .insert(verifier.clone(), keypair.sigkey().to_owned());
+                verifier
+            })
+            .collect();
+        deployment.environment.cohort_verifiers.insert(cohort, participants);
+        deployment
+    }
+
     pub fn contributor_seen_timeout(&self, contributor_timeout: time::Duration) -> Self {
         let mut deployment = self.clone();
         deployment.environment.contributor_seen_timeout = contributor_timeout;
@@ -564,6 +1156,141 @@ impl Testing {
         deployment
     }
 
+    pub fn contribution_retry_budget(&self, contribution_retry_budget: u8) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.contribution_retry_budget = contribution_retry_budget;
+        deployment
+    }
+
+    pub fn maximum_drops_per_update(&self, maximum_drops_per_update: Option<usize>) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.maximum_drops_per_update = maximum_drops_per_update;
+        deployment
+    }
+
+    pub fn storage_failure_threshold(&self, storage_failure_threshold: u32) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.storage_failure_threshold = storage_failure_threshold;
+        deployment
+    }
+
+    pub fn minimum_free_disk_space(&self, minimum_free_disk_space: u64) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.minimum_free_disk_space = minimum_free_disk_space;
+        deployment
+    }
+
+    pub fn minimum_contribution_size(&self, round_height: u64, minimum_contribution_size: u64) -> Self {
+        let mut deployment = self.clone();
+        deployment
+            .environment
+            .round_minimum_contribution_size
+            .insert(round_height, minimum_contribution_size);
+        deployment
+    }
+
+    pub fn strict_contribution_sanity(&self, strict_contribution_sanity: bool) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.strict_contribution_sanity = strict_contribution_sanity;
+        deployment
+    }
+
+    pub fn finished_rounds_retention(&self, finished_rounds_retention: Option<u64>) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.finished_rounds_retention = finished_rounds_retention;
+        deployment
+    }
+
+    pub fn heartbeat_on_activity(&self, heartbeat_on_activity: bool) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.heartbeat_on_activity = heartbeat_on_activity;
+        deployment
+    }
+
+    pub fn queue_join_signature_required(&self, queue_join_signature_required: bool) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.queue_join_signature_required = queue_join_signature_required;
+        deployment
+    }
+
+    pub fn strict_task_completion(&self, strict_task_completion: bool) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.strict_task_completion = strict_task_completion;
+        deployment
+    }
+
+    pub fn out_of_order_contributions(&self, out_of_order_contributions: bool) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.out_of_order_contributions = out_of_order_contributions;
+        deployment
+    }
+
+    pub fn encrypt_contributions(&self, encrypt_contributions: bool) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.encrypt_contributions = encrypt_contributions;
+        deployment
+    }
+
+    pub fn verification_cache(&self, verification_cache: bool) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.verification_cache = verification_cache;
+        deployment
+    }
+
+    pub fn bucket_layout(&self, bucket_layout: BucketLayout) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.bucket_layout = bucket_layout;
+        deployment
+    }
+
+    pub fn queue_priority(&self, queue_priority: QueuePriority) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.queue_priority = queue_priority;
+        deployment
+    }
+
+    pub fn replacement_selection(&self, replacement_selection: ReplacementSelection) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.replacement_selection = replacement_selection;
+        deployment
+    }
+
+    pub fn state_format(&self, state_format: StateFormat) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.state_format = state_format;
+        deployment
+    }
+
+    pub fn queue_rejoin_cooldown(&self, queue_rejoin_cooldown: time::Duration) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.queue_rejoin_cooldown = queue_rejoin_cooldown;
+        deployment
+    }
+
+    pub fn contributor_lock_chunk_limit(&self, contributor_lock_chunk_limit: usize) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.contributor_lock_chunk_limit = contributor_lock_chunk_limit;
+        deployment
+    }
+
+    pub fn reconnect_window(&self, reconnect_window: time::Duration) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.reconnect_window = reconnect_window;
+        deployment
+    }
+
+    pub fn contribution_settle_time(&self, contribution_settle_time: time::Duration) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.contribution_settle_time = contribution_settle_time;
+        deployment
+    }
+
+    pub fn contribution_windows(&self, contribution_windows: &[ContributionWindow]) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.contribution_windows = contribution_windows.to_vec();
+        deployment
+    }
+
     fn generate_namada_env(keypair: &KeyPair) -> Self {
         Self {
             environment: Environment {
@@ -579,6 +1306,7 @@ impl Testing {
 
                 minimum_contributors_per_round: 1,
                 maximum_contributors_per_round: 1,
+                maximum_contributors_per_region: None,
                 minimum_verifiers_per_round: 1,
                 maximum_verifiers_per_round: 5,
                 contributor_lock_chunk_limit: 1,
@@ -588,17 +1316,42 @@ impl Testing {
                 participant_lock_timeout: time::Duration::minutes(20),
                 queue_seen_timeout: time::Duration::minutes(5),
                 participant_ban_threshold: 5,
+                contribution_retry_budget: 0,
+                maximum_drops_per_update: None,
+                storage_failure_threshold: 3,
+                minimum_free_disk_space: 0,
+                round_minimum_contribution_size: HashMap::new(),
+                strict_contribution_sanity: false,
+                finished_rounds_retention: None,
+                heartbeat_on_activity: true,
+                queue_join_signature_required: false,
+                strict_task_completion: false,
+                out_of_order_contributions: false,
+                encrypt_contributions: false,
+                verification_cache: false,
+                bucket_layout: BucketLayout::Fixed,
+                queue_priority: QueuePriority::Fifo,
+                replacement_selection: ReplacementSelection::Deterministic,
+                state_format: StateFormat::Json,
+                queue_rejoin_cooldown: time::Duration::ZERO,
+                reconnect_window: time::Duration::ZERO,
+                contribution_settle_time: time::Duration::ZERO,
+                contribution_windows: Vec::new(),
                 allow_current_contributors_in_queue: true,
                 allow_current_verifiers_in_queue: true,
                 queue_wait_time: 0,
 
                 coordinator_contributors: vec![Participant::new_contributor("testing-coordinator-contributor")],
                 coordinator_verifiers: vec![Participant::new_verifier(keypair.pubkey())],
+                cohort_verifiers: HashMap::new(),
+                cohort_verifier_signing_keys: HashMap::new(),
+                cohort_maximum_contributors: HashMap::new(),
                 default_verifier_signing_key: keypair.sigkey().to_owned(),
 
                 software_version: 1,
                 deployment: Deployment::Testing,
                 local_base_directory: "./transcript/testing".to_string(),
+                ceremony_name: None,
 
                 disable_reliability_zeroing: false,
             },
@@ -652,6 +1405,19 @@ impl Development {
         self
     }
 
+    pub fn cohort_maximum_contributors_per_round(mut self, cohort: usize, maximum: usize) -> Self {
+        // Check that the cap admits the minimum number of contributors of a round.
+        if maximum == 0 || maximum < self.environment.minimum_contributors_per_round {
+            panic!(
+                "Specifying to environment a maximum of {} contributors for cohort {}, below the minimum per round.",
+                maximum, cohort
+            )
+        }
+
+        self.environment.cohort_maximum_contributors.insert(cohort, maximum);
+        self
+    }
+
     pub fn contributor_seen_timeout(mut self, timeout: time::Duration) -> Self {
         self.environment.contributor_seen_timeout = timeout;
         self
@@ -691,6 +1457,32 @@ impl Development {
         deployment
     }
 
+    #[inline]
+    pub fn cohort_verifiers(&self, cohort: usize, verifiers: &[KeyPair]) -> Self {
+        // Check that the cohort has at least one verifier.
+        if verifiers.is_empty() {
+            panic!(
+                "Specifying to environment an empty list of verifiers for cohort {}.",
+                cohort
+            )
+        }
+
+        let mut deployment = self.clone();
+        let participants = verifiers
+            .iter()
+            .map(|keypair| {
+                let verifier = Participant::new_verifier(keypair.pubkey());
+                deployment
+                    .environment
+                    .cohort_verifier_signing_keys
+                    .insert(verifier.clone(), keypair.sigkey().to_owned());
+                verifier
+            })
+            .collect();
+        deployment.environment.cohort_verifiers.insert(cohort, participants);
+        deployment
+    }
+
     fn generate_namada_env(keypair: &KeyPair) -> Self {
         Self {
             environment: Environment {
@@ -706,6 +1498,7 @@ impl Development {
 
                 minimum_contributors_per_round: 1,
                 maximum_contributors_per_round: 1,
+                maximum_contributors_per_region: None,
                 minimum_verifiers_per_round: 1,
                 maximum_verifiers_per_round: 5,
                 contributor_lock_chunk_limit: 1,
@@ -715,17 +1508,42 @@ impl Development {
                 participant_lock_timeout: time::Duration::minutes(20),
                 queue_seen_timeout: time::Duration::minutes(10),
                 participant_ban_threshold: 5,
+                contribution_retry_budget: 0,
+                maximum_drops_per_update: None,
+                storage_failure_threshold: 3,
+                minimum_free_disk_space: 0,
+                round_minimum_contribution_size: HashMap::new(),
+                strict_contribution_sanity: false,
+                finished_rounds_retention: None,
+                heartbeat_on_activity: true,
+                queue_join_signature_required: false,
+                strict_task_completion: false,
+                out_of_order_contributions: false,
+                encrypt_contributions: false,
+                verification_cache: false,
+                bucket_layout: BucketLayout::Fixed,
+                queue_priority: QueuePriority::Fifo,
+                replacement_selection: ReplacementSelection::Deterministic,
+                state_format: StateFormat::Json,
+                queue_rejoin_cooldown: time::Duration::ZERO,
+                reconnect_window: time::Duration::ZERO,
+                contribution_settle_time: time::Duration::ZERO,
+                contribution_windows: Vec::new(),
                 allow_current_contributors_in_queue: true,
                 allow_current_verifiers_in_queue: true,
                 queue_wait_time: 60,
 
                 coordinator_contributors: vec![Participant::new_contributor("development-coordinator-contributor")],
                 coordinator_verifiers: vec![Participant::new_verifier(keypair.pubkey())],
+                cohort_verifiers: HashMap::new(),
+                cohort_verifier_signing_keys: HashMap::new(),
+                cohort_maximum_contributors: HashMap::new(),
                 default_verifier_signing_key: keypair.sigkey().to_owned(),
 
                 software_version: 1,
                 deployment: Deployment::Development,
                 local_base_directory: "./transcript/development".to_string(),
+                ceremony_name: None,
 
                 disable_reliability_zeroing: false,
             },
@@ -785,6 +1603,19 @@ impl Production {
         self
     }
 
+    pub fn cohort_maximum_contributors_per_round(mut self, cohort: usize, maximum: usize) -> Self {
+        // Check that the cap admits the minimum number of contributors of a round.
+        if maximum == 0 || maximum < self.environment.minimum_contributors_per_round {
+            panic!(
+                "Specifying to environment a maximum of {} contributors for cohort {}, below the minimum per round.",
+                maximum, cohort
+            )
+        }
+
+        self.environment.cohort_maximum_contributors.insert(cohort, maximum);
+        self
+    }
+
     pub fn contributor_seen_timeout(mut self, timeout: time::Duration) -> Self {
         self.environment.contributor_seen_timeout = timeout;
         self
@@ -829,13 +1660,138 @@ impl Production {
         deployment
     }
 
+    #[inline]
+    pub fn cohort_verifiers(&self, cohort: usize, verifiers: &[KeyPair]) -> Self {
+        // Check that the cohort has at least one verifier.
+        if verifiers.is_empty() {
+            panic!(
+                "Specifying to environment an empty list of verifiers for cohort {}.",
+                cohort
+            )
+        }
+
+        let mut deployment = self.clone();
+        let participants = verifiers
+            .iter()
+            .map(|keypair| {
+                let verifier = Participant::new_verifier(keypair.pubkey());
+                deployment
+                    .environment
+                    .cohort_verifier_signing_keys
+                    .insert(verifier.clone(), keypair.sigkey().to_owned());
+                verifier
+            })
+            .collect();
+        deployment.environment.cohort_verifiers.insert(cohort, participants);
+        deployment
+    }
+
     fn generate_namada_env(keypair: &KeyPair) -> Self {
         let test_timeout = match std::env::var("NAMADA_MPC_TIMEOUT_SECONDS") {
             Ok(t) => Some(time::Duration::seconds(t.parse::<i64>().unwrap())),
             Err(_) => None,
         };
+        let contribution_windows = match std::env::var("NAMADA_MPC_CONTRIBUTION_WINDOWS") {
+            Ok(windows) => windows
+                .split(',')
+                .map(|window| window.parse::<ContributionWindow>().unwrap())
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        let reconnect_window = match std::env::var("NAMADA_RECONNECT_WINDOW") {
+            Ok(seconds) => time::Duration::seconds(seconds.parse::<i64>().unwrap()),
+            Err(_) => time::Duration::ZERO,
+        };
+        let queue_rejoin_cooldown = match std::env::var("NAMADA_QUEUE_REJOIN_COOLDOWN") {
+            Ok(seconds) => time::Duration::seconds(seconds.parse::<i64>().unwrap()),
+            Err(_) => time::Duration::ZERO,
+        };
+        let contribution_settle_time = match std::env::var("NAMADA_CONTRIBUTION_SETTLE_TIME") {
+            Ok(seconds) => time::Duration::seconds(seconds.parse::<i64>().unwrap()),
+            Err(_) => time::Duration::ZERO,
+        };
+        let out_of_order_contributions = match std::env::var("NAMADA_OUT_OF_ORDER_CONTRIBUTIONS") {
+            Ok(s) if s == "true" => true,
+            _ => false,
+        };
+        let strict_contribution_sanity = match std::env::var("NAMADA_STRICT_CONTRIB") {
+            Ok(s) if s == "true" => true,
+            _ => false,
+        };
+        let strict_task_completion = match std::env::var("NAMADA_STRICT_TASK_COMPLETION") {
+            Ok(s) if s == "true" => true,
+            _ => false,
+        };
+        let queue_priority = match std::env::var("NAMADA_QUEUE_PRIORITY") {
+            Ok(priority) => priority.parse::<QueuePriority>().unwrap(),
+            Err(_) => QueuePriority::Fifo,
+        };
+        let replacement_selection = match std::env::var("NAMADA_REPLACEMENT_SELECTION") {
+            Ok(selection) => selection.parse::<ReplacementSelection>().unwrap(),
+            Err(_) => ReplacementSelection::Deterministic,
+        };
+        let bucket_layout = match std::env::var("NAMADA_BUCKET_LAYOUT") {
+            Ok(layout) => layout.parse::<BucketLayout>().unwrap(),
+            Err(_) => BucketLayout::Fixed,
+        };
+        let maximum_drops_per_update = match std::env::var("NAMADA_MAXIMUM_DROPS_PER_UPDATE") {
+            Ok(maximum) => Some(maximum.parse::<usize>().unwrap()),
+            Err(_) => None,
+        };
+        let finished_rounds_retention = match std::env::var("NAMADA_FINISHED_ROUNDS_RETENTION") {
+            Ok(rounds) => Some(rounds.parse::<u64>().unwrap()),
+            Err(_) => None,
+        };
+        let queue_join_signature_required = match std::env::var("NAMADA_QUEUE_JOIN_SIGNATURE_REQUIRED") {
+            Ok(s) if s == "true" => true,
+            _ => false,
+        };
+        let state_format = match std::env::var("NAMADA_STATE_FORMAT") {
+            Ok(format) => format.parse::<StateFormat>().unwrap(),
+            Err(_) => StateFormat::Json,
+        };
+        let round_minimum_contribution_size = match std::env::var("NAMADA_MINIMUM_CONTRIBUTION_SIZES") {
+            Ok(sizes) => sizes
+                .split(',')
+                .map(|size| {
+                    let (round_height, size) = size.split_once(':').unwrap();
+                    (round_height.parse::<u64>().unwrap(), size.parse::<u64>().unwrap())
+                })
+                .collect(),
+            Err(_) => HashMap::new(),
+        };
+        let encrypt_contributions = match std::env::var("NAMADA_ENCRYPT_CONTRIBUTIONS") {
+            Ok(s) if s == "true" => true,
+            _ => false,
+        };
+        let verification_cache = match std::env::var("NAMADA_VERIFICATION_CACHE") {
+            Ok(s) if s == "true" => true,
+            _ => false,
+        };
+        let contribution_retry_budget = match std::env::var("NAMADA_CONTRIBUTION_RETRY_BUDGET") {
+            Ok(budget) => budget.parse::<u8>().unwrap(),
+            Err(_) => 0,
+        };
+        let minimum_free_disk_space = match std::env::var("NAMADA_MINIMUM_FREE_DISK_SPACE") {
+            Ok(bytes) => bytes.parse::<u64>().unwrap(),
+            Err(_) => 1 << 30,
+        };
+        let maximum_contributors_per_region = match std::env::var("NAMADA_MAXIMUM_CONTRIBUTORS_PER_REGION") {
+            Ok(maximum) => Some(maximum.parse::<usize>().unwrap()),
+            Err(_) => None,
+        };
+        let cohort_caps = match std::env::var("NAMADA_COHORT_MAXIMUM_CONTRIBUTORS") {
+            Ok(caps) => caps
+                .split(',')
+                .map(|cap| {
+                    let (cohort, maximum) = cap.split_once(':').unwrap();
+                    (cohort.parse::<usize>().unwrap(), maximum.parse::<usize>().unwrap())
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        };
 
-        Self {
+        let production = Self {
             environment: Environment {
                 parameters: Parameters::Namada {
                     number_of_chunks: 1,
@@ -849,6 +1805,7 @@ impl Production {
 
                 minimum_contributors_per_round: 1,
                 maximum_contributors_per_round: 1,
+                maximum_contributors_per_region,
                 minimum_verifiers_per_round: 1,
                 maximum_verifiers_per_round: 5,
                 contributor_lock_chunk_limit: 1,
@@ -858,21 +1815,53 @@ impl Production {
                 participant_lock_timeout: test_timeout.unwrap_or_else(|| time::Duration::minutes(20)),
                 queue_seen_timeout: test_timeout.unwrap_or_else(|| time::Duration::seconds(150)),
                 participant_ban_threshold: 5,
+                contribution_retry_budget,
+                maximum_drops_per_update,
+                storage_failure_threshold: 3,
+                minimum_free_disk_space,
+                round_minimum_contribution_size,
+                strict_contribution_sanity,
+                finished_rounds_retention,
+                heartbeat_on_activity: true,
+                queue_join_signature_required,
+                strict_task_completion,
+                out_of_order_contributions,
+                encrypt_contributions,
+                verification_cache,
+                bucket_layout,
+                queue_priority,
+                replacement_selection,
+                state_format,
+                queue_rejoin_cooldown,
+                reconnect_window,
+                contribution_settle_time,
+                contribution_windows,
                 allow_current_contributors_in_queue: false,
                 allow_current_verifiers_in_queue: true,
                 queue_wait_time: 5,
 
                 coordinator_contributors: vec![Participant::new_contributor("coordinator-contributor")],
                 coordinator_verifiers: vec![Participant::new_verifier(keypair.pubkey())],
+                cohort_verifiers: HashMap::new(),
+                cohort_verifier_signing_keys: HashMap::new(),
+                cohort_maximum_contributors: HashMap::new(),
                 default_verifier_signing_key: keypair.sigkey().to_owned(),
 
                 software_version: 1,
                 deployment: Deployment::Production,
                 local_base_directory: "./transcript".to_string(),
+                ceremony_name: None,
 
                 disable_reliability_zeroing: false,
             },
-        }
+        };
+
+        // Validate the cohort caps against the minimum number of contributors per round.
+        cohort_caps
+            .into_iter()
+            .fold(production, |production, (cohort, maximum)| {
+                production.cohort_maximum_contributors_per_round(cohort, maximum)
+            })
     }
 
     /// Generate a new Production env with [`Parameters::Namada`] parameters
@@ -966,4 +1955,63 @@ mod tests {
         assert_eq!(ChunkSize::from(1639_usize), chunk_size);
         assert_eq!(number_of_chunks as u64, Testing::from(parameters).number_of_chunks());
     }
+
+    #[test]
+    fn test_timeouts() {
+        let base = Testing::default();
+        let environment: Environment = base.clone().into();
+
+        let timeouts = environment.timeouts();
+        assert_eq!(
+            environment.contributor_seen_timeout().whole_seconds(),
+            timeouts.contributor_seen_timeout
+        );
+        assert_eq!(
+            environment.participant_lock_timeout().whole_seconds(),
+            timeouts.participant_lock_timeout
+        );
+        assert_eq!(
+            environment.queue_seen_timeout().whole_seconds(),
+            timeouts.queue_seen_timeout
+        );
+        assert_eq!(environment.queue_wait_time(), timeouts.queue_wait_time);
+
+        let overridden: Environment = base
+            .contributor_seen_timeout(time::Duration::minutes(3))
+            .participant_lock_timeout(time::Duration::minutes(7))
+            .into();
+        let overridden_timeouts = overridden.timeouts();
+        assert_eq!(180, overridden_timeouts.contributor_seen_timeout);
+        assert_eq!(420, overridden_timeouts.participant_lock_timeout);
+        assert_eq!(
+            timeouts.verifier_seen_timeout,
+            overridden_timeouts.verifier_seen_timeout
+        );
+        assert_eq!(timeouts.queue_seen_timeout, overridden_timeouts.queue_seen_timeout);
+        assert_eq!(timeouts.queue_wait_time, overridden_timeouts.queue_wait_time);
+    }
+
+    #[test]
+    fn test_cohort_maximum_contributors() {
+        let environment: Environment = Testing::default()
+            .maximum_contributors_per_round(5)
+            .cohort_maximum_contributors_per_round(1, 2)
+            .into();
+        assert_eq!(5, environment.cohort_maximum_contributors_per_round(0));
+        assert_eq!(2, environment.cohort_maximum_contributors_per_round(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cohort_maximum_contributors_zero() {
+        Testing::default().cohort_maximum_contributors_per_round(1, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cohort_maximum_contributors_below_minimum() {
+        Testing::default()
+            .minimum_contributors_per_round(3)
+            .cohort_maximum_contributors_per_round(1, 2);
+    }
 }
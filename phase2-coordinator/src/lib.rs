@@ -28,8 +28,12 @@
 #[macro_use]
 pub mod macros;
 
+pub mod audit;
+
 pub mod authentication;
 
+pub mod backup;
+
 pub mod commands;
 
 pub mod coordinator;
@@ -47,19 +51,31 @@ pub use coordinator_state::CoordinatorState;
 
 pub mod environment;
 pub mod io;
+pub mod merkle;
+pub mod metrics;
 
 pub mod objects;
 pub use objects::{ContributionFileSignature, ContributionState, Participant, Round};
 
+pub mod preflight;
+
 pub mod storage;
 
 pub mod rest;
+pub mod rest_store;
 pub mod rest_utils;
 
 pub mod s3;
 
+pub mod secret;
+
+pub mod timestamp;
+
 #[cfg(any(test, feature = "testing"))]
 pub mod testing;
 
+#[cfg(test)]
+pub mod benchmark;
+
 #[cfg(test)]
 pub mod tests;
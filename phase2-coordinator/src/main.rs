@@ -1,10 +1,23 @@
 use phase2_coordinator::{
-    authentication::Production as ProductionSig,
+    audit::AUDIT_LOG_ENV,
+    authentication::{KeyPair, Production as ProductionSig},
+    backup::{self, BackupConfig, BACKUP_INTERVAL_ENV, BACKUP_RESTORE_ENV, BACKUP_RETENTION_ENV},
+    environment::Environment,
     io::{self, KeyPairUser},
+    metrics::{METRICS_ENABLED, METRICS_ENABLE_ENV, VERIFIER_METRICS_ENABLE_ENV},
+    preflight::{self, AwsPreflight},
     rest,
-    rest_utils::{self, ResponseError, TOKENS_PATH, TOKENS_ZIP_FILE, UPDATE_TIME},
+    rest_utils::{
+        self, AdaptiveInterval, Ceremonies, ResponseError, DRAIN_TIMEOUT_ENV, JSON_LIMIT_ENV, LOG_SAMPLE_RATE_ENV,
+        TOKENS_PATH, TOKENS_ZIP_FILE, UPDATE_INTERVAL_ENV, UPDATE_INTERVAL_MAX_ENV, UPLOAD_LIMIT_ENV,
+        VERIFY_INTERVAL_ENV, VERIFY_INTERVAL_MAX_ENV,
+    },
     s3::{S3Ctx, REGION},
+    secret::{publish_secret, SecretLocation, SsmSecretPublisher, SECRET_FALLBACK_PATH_ENV},
+    storage::{Disk, Locator, STORAGE_KEY_ENV},
+    timestamp::TSA_URL_ENV,
     Coordinator,
+    CoordinatorState,
 };
 
 #[cfg(debug_assertions)]
@@ -25,27 +38,59 @@ use rocket::{
 };
 
 use anyhow::Result;
+use futures::future;
 use rand::Rng;
 use rusoto_ssm::{Ssm, SsmClient};
-use std::{convert::TryInto, io::Write, sync::Arc};
+use std::{
+    convert::TryInto,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use tracing::{error, info, warn};
 
+/// The env variable enabling the upload of the completion record of the ceremony to S3.
+const UPLOAD_COMPLETION_ENV: &str = "NAMADA_MPC_UPLOAD_COMPLETION";
+/// The env variable holding the comma separated names of the ceremonies hosted by the server. If unset, a single
+/// ceremony is hosted at the root of the server. Otherwise, the token archive must contain a folder of token files per
+/// ceremony.
+const CEREMONIES_ENV: &str = "NAMADA_MPC_CEREMONIES";
+
 /// Periodically updates the [`Coordinator`]
-async fn update_coordinator(coordinator: Arc<RwLock<Coordinator>>, recv: Receiver<bool>) -> Result<()> {
+async fn update_coordinator(coordinator: Arc<RwLock<Coordinator>>, mut recv: Receiver<bool>) -> Result<()> {
+    let mut interval = AdaptiveInterval::from_env(UPDATE_INTERVAL_ENV, UPDATE_INTERVAL_MAX_ENV);
+
     loop {
-        tokio::time::sleep(UPDATE_TIME).await;
+        // Wake up early on the shutdown signal, the backed off interval can be long
+        tokio::select! {
+            _ = tokio::time::sleep(interval.current()) => (),
+            _ = recv.changed() => (),
+        }
+        if *recv.borrow() {
+            info!("Received shutdown signal, exiting update task");
+            return Ok(());
+        }
 
         info!("Updating coordinator...");
-        match rest_utils::perform_coordinator_update(coordinator.clone()).await {
-            Ok(_) => info!(
+        match rest_utils::perform_coordinator_update(coordinator.clone(), Some(interval.current())).await {
+            Ok(progressed) => info!(
                 "Update of coordinator completed, {:#?} to the next update round...",
-                UPDATE_TIME
+                interval.next(progressed)
             ),
             Err(e) => {
                 if let ResponseError::CoordinatorError(phase2_coordinator::CoordinatorError::CeremonyIsOver) = e {
+                    if matches!(std::env::var(UPLOAD_COMPLETION_ENV), Ok(upload) if upload == "true") {
+                        if let Err(e) = upload_completion(coordinator.clone()).await {
+                            error!("Error while uploading the completion record: {}", e);
+                        }
+                    }
+
                     // Return Ok to initialize the shutdown process in select! expression
                     return Ok(());
+                } else if coordinator.read().await.is_read_only() {
+                    // Keep serving reads to give the operators time to intervene
+                    error!("Coordinator is in read-only mode, skipping update: {}", e);
                 } else {
                     return Err(e.into());
                 }
@@ -60,24 +105,48 @@ async fn update_coordinator(coordinator: Arc<RwLock<Coordinator>>, recv: Receive
     }
 }
 
-/// Periodically verifies the pending contributions. Pending contributions are added to the queue by the try_contribute function,
+/// Uploads the completion record of the ceremony to S3
+async fn upload_completion(coordinator: Arc<RwLock<Coordinator>>) -> Result<()> {
+    let completion = coordinator.read().await.ceremony_completion()?;
+    if let Some(completion) = completion {
+        let s3_ctx = rest_utils::ceremony_s3_ctx(&coordinator).await?;
+        s3_ctx
+            .upload_completion(serde_json::to_vec_pretty(&completion)?)
+            .await?;
+        info!("Uploaded the completion record of the ceremony");
+    }
+
+    Ok(())
+}
+
+/// Periodically verifies the pending contributions, and requests their trusted timestamps if enabled. Pending contributions are added to the queue by the try_contribute function,
 /// no need to call an update on the coordinator.
 /// NOTE: a possible improvement could be to perform the verification when the try_contribute function gets called, allowing us to remove this task and
 /// speed up the verification process. This would also allow us to immediately provide to a client the state of validity of its contribution. This improvement could
 /// be possible because we only have one contribution per round and one verifier (the coordinator's one). To implement this logic though, it would require a major rework of the phase2_coordinator logic.
-async fn verify_contributions(coordinator: Arc<RwLock<Coordinator>>, recv: Receiver<bool>) -> Result<()> {
-    let s3_ctx = S3Ctx::new().await?;
+async fn verify_contributions(coordinator: Arc<RwLock<Coordinator>>, mut recv: Receiver<bool>) -> Result<()> {
+    let s3_ctx = rest_utils::ceremony_s3_ctx(&coordinator).await?;
+    let mut interval = AdaptiveInterval::from_env(VERIFY_INTERVAL_ENV, VERIFY_INTERVAL_MAX_ENV);
 
     loop {
-        tokio::time::sleep(UPDATE_TIME).await;
+        // Wake up early on the shutdown signal, the backed off interval can be long
+        tokio::select! {
+            _ = tokio::time::sleep(interval.current()) => (),
+            _ = recv.changed() => (),
+        }
+        if *recv.borrow() {
+            info!("Received shutdown signal, exiting verify task");
+            return Ok(());
+        }
 
         info!("Verifying contributions...");
         let start = std::time::Instant::now();
-        rest_utils::perform_verify_chunks(coordinator.clone(), &s3_ctx).await?;
+        let verified = rest_utils::perform_verify_chunks(coordinator.clone(), &s3_ctx).await?;
+        rest_utils::perform_timestamp_contributions(coordinator.clone()).await?;
         info!(
             "Verification of contributions completed in {:#?}. {:#?} to the next verification round...",
             start.elapsed(),
-            UPDATE_TIME
+            interval.next(verified)
         );
 
         // Return if shutdown signal has been received on the channel
@@ -88,6 +157,35 @@ async fn verify_contributions(coordinator: Arc<RwLock<Coordinator>>, recv: Recei
     }
 }
 
+/// Periodically uploads a backup of the coordinator state to S3
+async fn backup_coordinator_state(
+    coordinator: Arc<RwLock<Coordinator>>,
+    config: BackupConfig,
+    recv: Receiver<bool>,
+) -> Result<()> {
+    let s3_ctx = rest_utils::ceremony_s3_ctx(&coordinator).await?;
+    let time = coordinator.read().await.time_source();
+    let read_state = move || {
+        let coordinator = coordinator.clone();
+        async move {
+            let read_lock = coordinator.read_owned().await;
+            tokio::task::spawn_blocking(move || {
+                let round_height = read_lock.current_round_height().map_err(|e| e.to_string())?;
+                let files = backup::backup_files(read_lock.storage(), round_height).map_err(|e| e.to_string())?;
+                let base = Path::new(read_lock.environment().local_base_directory());
+
+                backup::archive_files(base, &files)
+            })
+            .await
+            .map_err(|e| e.to_string())?
+        }
+    };
+
+    backup::run_backups(&s3_ctx, config, read_state, time.as_ref(), recv).await;
+
+    Ok(())
+}
+
 /// Checks and prints the env variables of interest for the ceremony
 macro_rules! print_env {
     ($($env:expr),*) => {
@@ -116,46 +214,116 @@ async fn download_tokens() -> Result<()> {
     Ok(())
 }
 
-/// Generate the random secret to access reserved endpoints and exports it as env. Publish this secret to Amazon Parameter Store.
+/// Restore the latest backup of the coordinator state from S3, if the local storage has no coordinator state.
+async fn restore_coordinator_state(environment: &Environment) -> Result<()> {
+    let s3_ctx = S3Ctx::for_ceremony(environment.ceremony_name()).await?;
+    let base = Path::new(environment.local_base_directory());
+    let state_path = Disk::locator_path(environment, &Locator::CoordinatorState)?;
+
+    if backup::restore_state(&s3_ctx, base, state_path.as_path())
+        .await
+        .map_err(|e| anyhow::anyhow!("Error while restoring the backup: {}", e))?
+    {
+        info!("Coordinator state restored from the latest backup");
+    }
+
+    Ok(())
+}
+
+/// Generate the random secret to access reserved endpoints and exports it as env. Publish this secret to Amazon Parameter
+/// Store, falling back to the file set in the NAMADA_MPC_SECRET_FALLBACK_PATH env variable, if any, when the publication
+/// keeps failing.
 async fn generate_secret() -> Result<()> {
     let mut secret_bytes = [0u8; 32];
     rand::thread_rng().fill(&mut secret_bytes[..]);
     let secret = hex::encode(secret_bytes);
     std::env::set_var("ACCESS_SECRET", &secret);
+
+    let fallback_path = std::env::var(SECRET_FALLBACK_PATH_ENV).ok().map(PathBuf::from);
+    match publish_secret(&SsmSecretPublisher::default(), &secret, fallback_path.as_deref())
+        .await
+        .map_err(|e| anyhow::anyhow!("Error while publishing the secret: {}", e))?
+    {
+        SecretLocation::Published => info!("Secret published to Parameter Store"),
+        SecretLocation::Fallback(path) => warn!("Secret not published, stored in {}", path.display()),
+    }
+
+    Ok(())
+}
+
+/// Fetch the key to encrypt the contribution files at rest from Amazon Parameter Store and exports it as env, unless
+/// already set.
+async fn load_storage_key() -> Result<()> {
+    if std::env::var(STORAGE_KEY_ENV).is_ok() {
+        return Ok(());
+    }
+
     let env = match std::env::var("AWS_S3_PROD") {
         Ok(val) if val == "true" => "production",
         _ => "master",
     };
 
     let aws_client = SsmClient::new(REGION.clone());
-    let put_request = rusoto_ssm::PutParameterRequest {
-        description: Some("Trusted setup endpoints secret".to_string()),
-        key_id: None,
-        name: format!("/namada/trusted-setup/{}/secret", env),
-        overwrite: Some(true),
-        policies: None,
-        tags: None,
-        tier: None,
-        type_: Some("SecureString".to_string()),
-        value: secret.clone(),
-        allowed_pattern: None,
-        data_type: None,
+    let get_request = rusoto_ssm::GetParameterRequest {
+        name: format!("/namada/trusted-setup/{}/storage-key", env),
+        with_decryption: Some(true),
     };
-    aws_client.put_parameter(put_request).await?;
+    let key = aws_client
+        .get_parameter(get_request)
+        .await?
+        .parameter
+        .and_then(|parameter| parameter.value)
+        .ok_or_else(|| anyhow::anyhow!("Missing value of the storage key parameter"))?;
+    std::env::set_var(STORAGE_KEY_ENV, key);
+
+    Ok(())
+}
+
+/// Check S3 and SSM connectivity and permissions, logging the outcome of each check.
+async fn run_preflight() -> Result<()> {
+    let client = AwsPreflight::new()
+        .await
+        .map_err(|e| anyhow::anyhow!("Error while creating the AWS clients: {}", e))?;
+    let report = preflight::run_preflight(&client).await;
+
+    for check in &report.checks {
+        match &check.result {
+            Ok(()) => info!("Preflight check {}: OK", check.name),
+            Err(e) => error!("Preflight check {}: FAILED ({})", check.name, e),
+        }
+    }
+
+    if !report.is_ok() {
+        anyhow::bail!("{} preflight check(s) failed", report.failures().len());
+    }
 
     Ok(())
 }
 
+/// Post the alert to the webhook set in the NAMADA_MPC_ALERT_WEBHOOK env variable, if any. The request is sent from a
+/// separate thread to avoid holding the lock on the coordinator.
+fn send_alert(alert: String) {
+    if let Ok(url) = std::env::var("NAMADA_MPC_ALERT_WEBHOOK") {
+        std::thread::spawn(move || {
+            let body = serde_json::json!({ "text": alert });
+            if let Err(e) = reqwest::blocking::Client::new().post(&url).json(&body).send() {
+                error!("Error while sending the alert to the webhook: {}", e);
+            }
+        });
+    }
+}
+
 /// Perform the steps to finalize the ceremony state before shut down
 async fn finalize_ceremony(coordinator: Arc<RwLock<Coordinator>>) -> Result<()> {
     info!("Performing last contribution verification (if any)...");
-    if let Err(e) = rest_utils::perform_verify_chunks(coordinator.clone(), &S3Ctx::new().await?).await {
+    let s3_ctx = rest_utils::ceremony_s3_ctx(&coordinator).await?;
+    if let Err(e) = rest_utils::perform_verify_chunks(coordinator.clone(), &s3_ctx).await {
         // Log any error without interrupting the shutdown procedure
         warn!("Ignoring error while performing last verification: {}", e);
     }
 
     info!("Performing last coordinator update...");
-    if let Err(e) = rest_utils::perform_coordinator_update(coordinator.clone()).await {
+    if let Err(e) = rest_utils::perform_coordinator_update(coordinator.clone(), None).await {
         // Log any error without interrupting the shutdown procedure
         warn!("Ignoring error while performing last update: {}", e);
     }
@@ -166,6 +334,97 @@ async fn finalize_ceremony(coordinator: Arc<RwLock<Coordinator>>) -> Result<()>
     Ok(())
 }
 
+/// Returns the names of the ceremonies hosted by the server, set in [CEREMONIES_ENV]
+fn ceremony_names() -> Vec<String> {
+    match std::env::var(CEREMONIES_ENV) {
+        Ok(names) => names
+            .split(',')
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Export the transcript of the given ceremony from the local storage to a zip archive at the given path, see
+/// [`Coordinator::export_transcript`]. Only the storage is opened, so the ceremony is left untouched and the token
+/// files are not needed. The ceremony must be one of [CEREMONIES_ENV] if set, and is ignored otherwise
+async fn export_transcript(path: &str, ceremony: Option<&str>) -> Result<()> {
+    // The keypair of the coordinator is not needed to read the storage
+    let keypair = KeyPair::new();
+
+    #[cfg(debug_assertions)]
+    let environment: Testing = Testing::new(&keypair);
+
+    #[cfg(not(debug_assertions))]
+    let environment: Production = Production::new(&keypair);
+
+    let mut environment: Environment = environment.into();
+    let ceremony_names = ceremony_names();
+    if !ceremony_names.is_empty() {
+        match ceremony {
+            Some(name) if ceremony_names.iter().any(|ceremony_name| ceremony_name == name) => {
+                environment = environment.for_ceremony(name)
+            }
+            _ => anyhow::bail!("The ceremony to export must be one of {}", ceremony_names.join(", ")),
+        }
+    }
+    if environment.encrypt_contributions() {
+        load_storage_key().await?;
+    }
+
+    let storage = Disk::load(&environment)?;
+    let file = std::fs::File::create(path)?;
+    tokio::task::spawn_blocking(move || Coordinator::export_storage_transcript(&environment, &storage, file)).await??;
+
+    Ok(())
+}
+
+/// Instantiate and initialize the coordinator of the ceremony with the given environment
+async fn init_coordinator(environment: Environment) -> Arc<RwLock<Coordinator>> {
+    // Restore the coordinator state from S3 after the loss of the local storage
+    if backup::is_restore_enabled() {
+        restore_coordinator_state(&environment)
+            .await
+            .expect("Error while restoring the coordinator state");
+    }
+
+    // Each ceremony has its own audit log, suffixed with the name of the ceremony
+    let audit_log_path = std::env::var(AUDIT_LOG_ENV).ok().map(|path| match environment.ceremony_name() {
+        Some(name) => format!("{}.{}", path, name),
+        None => path,
+    });
+    let audit_log_key = environment
+        .coordinator_verifiers()
+        .first()
+        .map(|verifier| verifier.address())
+        .unwrap_or_default();
+
+    let storage = Disk::load(&environment).expect("Failed to load the storage");
+    let mut coordinator = Coordinator::new_with_storage(environment, Box::new(storage), Arc::new(ProductionSig))
+        .expect("Failed to instantiate coordinator");
+    coordinator.set_storage_alert_callback(Arc::new(send_alert));
+    if let Some(path) = audit_log_path {
+        coordinator
+            .enable_audit_log(&path)
+            .expect("Failed to open the audit log");
+        info!("Audit log {} signed by the public key {}", path, audit_log_key);
+    }
+    if let Ok(url) = std::env::var(TSA_URL_ENV) {
+        info!("Contributions timestamped by {}", url);
+        coordinator.enable_timestamping(url);
+    }
+    let coordinator: Arc<RwLock<Coordinator>> = Arc::new(RwLock::new(coordinator));
+
+    let mut write_lock = coordinator.clone().write_owned().await;
+
+    tokio::task::spawn_blocking(move || write_lock.initialize().expect("Initialization of coordinator failed!"))
+        .await
+        .expect("Initialization task panicked");
+
+    coordinator
+}
+
 /// Rocket main function using the [`tokio`] runtime
 #[rocket::main]
 pub async fn main() {
@@ -176,15 +435,79 @@ pub async fn main() {
         "AWS_S3_BUCKET",
         "AWS_REGION",
         "NAMADA_MPC_IP_BAN",
+        "NAMADA_MPC_IP_BAN_EXEMPTIONS",
         "NAMADA_MPC_TIMEOUT_SECONDS",
+        "NAMADA_MPC_CONTRIBUTION_WINDOWS",
+        UPLOAD_COMPLETION_ENV,
+        JSON_LIMIT_ENV,
+        UPLOAD_LIMIT_ENV,
+        METRICS_ENABLE_ENV,
+        VERIFIER_METRICS_ENABLE_ENV,
+        CEREMONIES_ENV,
         "HEALTH_PATH",
         "NAMADA_TOKENS_PATH",
         "CEREMONY_START_TIMESTAMP",
         "TOKENS_FILE_PREFIX",
         "NAMADA_COHORT_TIME",
-        "TOKEN_BLACKLIST"
+        "TOKEN_BLACKLIST",
+        LOG_SAMPLE_RATE_ENV,
+        "NAMADA_MPC_ALERT_WEBHOOK",
+        "NAMADA_MPC_SECRET_FALLBACK_PATH",
+        BACKUP_INTERVAL_ENV,
+        BACKUP_RETENTION_ENV,
+        BACKUP_RESTORE_ENV,
+        AUDIT_LOG_ENV,
+        TSA_URL_ENV,
+        UPDATE_INTERVAL_ENV,
+        UPDATE_INTERVAL_MAX_ENV,
+        VERIFY_INTERVAL_ENV,
+        VERIFY_INTERVAL_MAX_ENV,
+        DRAIN_TIMEOUT_ENV
     );
 
+    // Running the binary with the `export-transcript <path> [ceremony]` arguments only exports the transcript of the
+    // ceremony from the local storage to the given path and exits
+    if std::env::args().nth(1).as_deref() == Some("export-transcript") {
+        let path = std::env::args().nth(2).expect("Missing the path of the transcript archive");
+        let ceremony = std::env::args().nth(3);
+        match export_transcript(&path, ceremony.as_deref()).await {
+            Ok(()) => info!("Exported the transcript of the ceremony to {}", path),
+            Err(e) => {
+                error!("{}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Running the binary with the `preflight` argument only verifies the AWS setup, without touching the real tokens
+    // and secret, and exits. The boot itself doesn't depend on these checks
+    if std::env::args().nth(1).as_deref() == Some("preflight") {
+        match run_preflight().await {
+            Ok(()) => info!("All preflight checks passed"),
+            Err(e) => {
+                error!("{}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Validate the configuration of the request logging before starting the ceremony
+    let request_sampler = match rest_utils::RequestSampler::from_env() {
+        Ok(sampler) => sampler,
+        Err(e) => {
+            error!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Validate the durations of the cohorts before starting the ceremony
+    if let Err(e) = CoordinatorState::cohort_durations_from_env() {
+        error!("Invalid NAMADA_COHORT_TIME: {}", e);
+        std::process::exit(1);
+    }
+
     // Generate, publish and export the secret token
     generate_secret().await.expect("Error while generating secret token");
 
@@ -203,72 +526,184 @@ pub async fn main() {
     #[cfg(not(debug_assertions))]
     let environment: Production = { Production::new(&keypair) };
 
+    let environment: Environment = environment.into();
+    if environment.encrypt_contributions() {
+        load_storage_key().await.expect("Error while loading the storage key");
+    }
+
     // Always download token files from S3 to check for updates
     download_tokens().await.expect("Error while retrieving tokens");
 
-    // Initialize the coordinator
-    let coordinator =
-        Coordinator::new(environment.into(), Arc::new(ProductionSig)).expect("Failed to instantiate coordinator");
-    let coordinator: Arc<RwLock<Coordinator>> = Arc::new(RwLock::new(coordinator));
-    let up_coordinator = coordinator.clone();
-    let verify_coordinator = coordinator.clone();
-
-    let mut write_lock = coordinator.clone().write_owned().await;
-
-    tokio::task::spawn_blocking(move || write_lock.initialize().expect("Initialization of coordinator failed!"))
-        .await
-        .expect("Initialization task panicked");
+    // Initialize the coordinator of each ceremony, each one with its own storage and token files
+    let ceremony_names = ceremony_names();
+    let mut coordinators = Vec::new();
+    if ceremony_names.is_empty() {
+        coordinators.push(init_coordinator(environment).await);
+    } else {
+        for name in &ceremony_names {
+            coordinators.push(init_coordinator(environment.for_ceremony(name)).await);
+        }
+    }
 
     // Build Rocket REST server
     #[cfg(debug_assertions)]
     let routes = routes![
         rest::join_queue,
+        rest::check_eligibility,
         rest::lock_chunk,
         rest::get_challenge_url,
         rest::get_contribution_url,
+        rest::initiate_chunk_upload,
+        rest::put_chunk_part,
+        rest::complete_chunk_upload,
         rest::contribute_chunk,
+        rest::contribute_chunk_batch,
         rest::update_coordinator,
+        rest::reinstate_participant,
+        rest::force_release_lock,
+        rest::set_participant_lock_limit,
+        rest::freeze_queue,
+        rest::unfreeze_queue,
+        rest::preview_reset_round,
         rest::heartbeat,
+        rest::leave_queue,
+        rest::reconnect,
+        rest::reorder_tasks,
         rest::stop_coordinator,
+        rest::cancel_precommit,
         rest::verify_chunks,
         rest::get_contributor_queue_status,
+        rest::get_participant_metrics,
         rest::post_contribution_info,
         rest::get_contributions_info,
+        rest::get_participant_contributions_info,
+        rest::get_assignment_explanation,
+        rest::get_round_contribution_info,
+        rest::get_round_merkle_root,
+        rest::get_round_merkle_proof,
         rest::get_coordinator_state,
+        rest::get_queue,
+        rest::get_next_round_assignment,
+        rest::get_unassigned_completed_tasks,
+        rest::get_queue_reliability,
+        rest::get_diagnostics,
+        rest::get_blacklists,
+        rest::get_tokens_in_use,
+        rest::get_verifier_activity,
+        rest::release_token,
+        rest::get_timeouts,
+        rest::get_queue_wait_time,
+        rest::get_finished_participants,
+        rest::get_transparency,
+        rest::get_events,
+        rest::get_version,
+        rest::get_contributor_average_task_time,
         rest::get_healthcheck,
         rest::update_cohorts,
+        rest::replace_token_schedule,
+        rest::merge_cohorts,
+        rest::update_ip_ban_exemptions,
+        rest::update_queue_wait_time,
         rest::post_attestation
     ];
 
     #[cfg(not(debug_assertions))]
     let routes = routes![
         rest::join_queue,
+        rest::check_eligibility,
         rest::lock_chunk,
         rest::get_challenge_url,
         rest::get_contribution_url,
+        rest::initiate_chunk_upload,
+        rest::put_chunk_part,
+        rest::complete_chunk_upload,
         rest::contribute_chunk,
+        rest::contribute_chunk_batch,
         rest::heartbeat,
+        rest::leave_queue,
+        rest::reconnect,
+        rest::reorder_tasks,
         rest::stop_coordinator,
+        rest::cancel_precommit,
         rest::get_contributor_queue_status,
+        rest::get_participant_metrics,
         rest::post_contribution_info,
+        rest::get_participant_contributions_info,
+        rest::get_assignment_explanation,
+        rest::get_round_contribution_info,
+        rest::get_round_merkle_root,
+        rest::get_round_merkle_proof,
         rest::get_coordinator_state,
+        rest::get_queue,
+        rest::get_next_round_assignment,
+        rest::get_unassigned_completed_tasks,
+        rest::get_queue_reliability,
+        rest::get_diagnostics,
+        rest::get_blacklists,
+        rest::get_tokens_in_use,
+        rest::get_verifier_activity,
+        rest::release_token,
+        rest::get_timeouts,
+        rest::get_queue_wait_time,
+        rest::get_finished_participants,
+        rest::get_transparency,
+        rest::get_events,
+        rest::get_version,
+        rest::get_contributor_average_task_time,
         rest::get_healthcheck,
         rest::update_cohorts,
+        rest::replace_token_schedule,
+        rest::merge_cohorts,
+        rest::update_ip_ban_exemptions,
+        rest::update_queue_wait_time,
         rest::post_attestation
     ];
 
-    let build_rocket = rocket::build().mount("/", routes).manage(coordinator.clone()).register(
-        "/",
-        catchers![
+    // Apply the body size limits set in the env on top of the configured ones
+    let figment = rocket::Config::figment();
+    let limits = rest_utils::body_limits(figment.extract_inner("limits").unwrap_or_default());
+    let mut build_rocket = rocket::custom(figment.merge(("limits", limits)))
+        .attach(request_sampler)
+        .register("/", catchers![
             rest_utils::invalid_signature,
             rest_utils::unauthorized,
             rest_utils::missing_required_header,
             rest_utils::io_error,
             rest_utils::unprocessable_entity,
             rest_utils::mismatching_checksum,
-            rest_utils::invalid_header
-        ],
-    );
+            rest_utils::invalid_header,
+            rest_utils::participant_not_in_round,
+            rest_utils::payload_too_large,
+            rest_utils::too_many_requests
+        ]);
+
+    // Limit the rate of the requests to join the queue of each address, if configured
+    if let Some(limiter) = rest_utils::JoinRateLimiter::from_env() {
+        build_rocket = build_rocket.manage(limiter);
+    }
+
+    // Mount the routes of a single ceremony at the root, the ones of named ceremonies under their name
+    let mount_points: Vec<String> = if ceremony_names.is_empty() {
+        build_rocket = build_rocket.manage(coordinators[0].clone());
+        vec!["/".to_string()]
+    } else {
+        let ceremonies: Ceremonies = ceremony_names
+            .iter()
+            .cloned()
+            .zip(coordinators.iter().cloned())
+            .collect();
+        build_rocket = build_rocket.manage(ceremonies);
+        ceremony_names
+            .iter()
+            .map(|name| rest_utils::ceremony_mount_point(name))
+            .collect()
+    };
+    for mount_point in &mount_points {
+        build_rocket = build_rocket.mount(mount_point.as_str(), routes.clone());
+        if *METRICS_ENABLED {
+            build_rocket = build_rocket.mount(mount_point.as_str(), routes![rest::get_metrics]);
+        }
+    }
     let ignite_rocket = build_rocket.ignite().await.expect("Coordinator server didn't ignite");
 
     // Sleep until ceremony start time has been reached
@@ -299,11 +734,33 @@ pub async fn main() {
     let (tx, rx) = watch::channel(false);
     let shutdown = ignite_rocket.shutdown();
 
-    // Spawn task to update the coordinator periodically
-    let mut update_handle = rocket::tokio::spawn(update_coordinator(up_coordinator, rx.clone()));
+    // Spawn task to update the coordinators periodically, completing when all the ceremonies are over
+    let mut update_handle = rocket::tokio::spawn(future::try_join_all(
+        coordinators
+            .iter()
+            .map(|coordinator| update_coordinator(coordinator.clone(), rx.clone())),
+    ));
+
+    // Spawn tasks to back up the coordinator states periodically, if enabled
+    if let Some(config) = BackupConfig::from_env() {
+        for coordinator in &coordinators {
+            let backup_coordinator = coordinator.clone();
+            let backup_config = config.clone();
+            let backup_rx = rx.clone();
+            rocket::tokio::spawn(async move {
+                if let Err(e) = backup_coordinator_state(backup_coordinator, backup_config, backup_rx).await {
+                    error!("Backup of Coordinator failed: {}", e);
+                }
+            });
+        }
+    }
 
     // Spawn task to verify the contributions periodically
-    let mut verify_handle = rocket::tokio::spawn(verify_contributions(verify_coordinator, rx));
+    let mut verify_handle = rocket::tokio::spawn(future::try_join_all(
+        coordinators
+            .iter()
+            .map(|coordinator| verify_contributions(coordinator.clone(), rx.clone())),
+    ));
 
     // Spawn Rocket server task
     let mut rocket_handle = rocket::tokio::spawn(ignite_rocket.launch());
@@ -314,9 +771,11 @@ pub async fn main() {
     tokio::select! {
         update_result = &mut update_handle => {
             match update_result.expect("Update task panicked") {
-                Ok(()) => {
-                    // Cohorts are over, terminate the ceremony
-                    info!("Cohorts are over, notifying rest server to shut down...");
+                Ok(_) => {
+                    // Cohorts are over, terminate the ceremony once the locked chunks are contributed
+                    info!("Cohorts are over, draining the coordinators...");
+                    rest_utils::drain_coordinators(coordinators.clone()).await;
+                    info!("Notifying rest server to shut down...");
 
                     // Cancel concurrent tasks
                     info!("Cancelling concurrent tasks...");
@@ -338,14 +797,14 @@ pub async fn main() {
 
                     info!("Concurrent tasks terminated");
 
-                    finalize_ceremony(coordinator).await.expect("Failed ceremony state finalize");
+                    future::try_join_all(coordinators.into_iter().map(finalize_ceremony)).await.expect("Failed ceremony state finalize");
                 },
                 Err(e) => error!("Update of Coordinator failed: {}", e),
             }
         },
         verify_result = &mut verify_handle => {
             match verify_result.expect("Verify task panicked") {
-                Ok(()) => unreachable!(),
+                Ok(_) => unreachable!(),
                 Err(e) => error!("Verify of Coordinator failed: {}", e),
             }
         },
@@ -374,7 +833,7 @@ pub async fn main() {
 
                     info!("Concurrent tasks terminated");
 
-                    finalize_ceremony(coordinator).await.expect("Failed ceremony state finalize");
+                    future::try_join_all(coordinators.into_iter().map(finalize_ceremony)).await.expect("Failed ceremony state finalize");
                 },
                 Err(e) => error!("Rocket failed: {}", e)
             }
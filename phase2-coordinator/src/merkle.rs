@@ -0,0 +1,186 @@
+//! A binary Merkle tree over the contributions of a round, used to
+//! attest the participation of the contributors with a single root.
+//!
+//! Leaves and inner nodes are hashed with SHA-256 under distinct
+//! prefixes, so that a leaf can never be mistaken for an inner node.
+//! When a level has an odd number of nodes, the last one is promoted
+//! to the next level as is.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// The prefix of the hash of a leaf.
+const LEAF_PREFIX: u8 = 0x00;
+/// The prefix of the hash of an inner node.
+const NODE_PREFIX: u8 = 0x01;
+
+/// The hash of a node of the tree.
+pub type MerkleHash = [u8; 32];
+
+/// Returns the leaf committing to the given contributor and contribution hash.
+pub fn leaf_hash(public_key: &str, contribution_hash: &str) -> MerkleHash {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update((public_key.len() as u64).to_be_bytes());
+    hasher.update(public_key.as_bytes());
+    hasher.update((contribution_hash.len() as u64).to_be_bytes());
+    hasher.update(contribution_hash.as_bytes());
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &MerkleHash, right: &MerkleHash) -> MerkleHash {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// A sibling on the path from a leaf to the root.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MerkleSibling {
+    /// The hash of the sibling.
+    pub hash: MerkleHash,
+    /// Whether the sibling is on the left of the path.
+    pub is_left: bool,
+}
+
+/// The proof that a leaf belongs to the tree of a given root.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// The leaf to prove.
+    pub leaf: MerkleHash,
+    /// The siblings from the leaf up to the root.
+    pub siblings: Vec<MerkleSibling>,
+}
+
+impl MerkleProof {
+    /// Returns `true` if the proof leads from its leaf to the given root.
+    pub fn verify(&self, root: &MerkleHash) -> bool {
+        let computed = self
+            .siblings
+            .iter()
+            .fold(self.leaf, |hash, sibling| match sibling.is_left {
+                true => node_hash(&sibling.hash, &hash),
+                false => node_hash(&hash, &sibling.hash),
+            });
+
+        computed == *root
+    }
+}
+
+/// A binary Merkle tree, storing every level from the leaves to the root.
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    levels: Vec<Vec<MerkleHash>>,
+}
+
+impl MerkleTree {
+    /// Builds the tree over the given leaves. The tree of no leaves has a zeroed root.
+    pub fn new(leaves: Vec<MerkleHash>) -> Self {
+        let mut levels = vec![leaves];
+
+        while levels.last().map_or(false, |level| level.len() > 1) {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node_hash(left, right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Self { levels }
+    }
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> MerkleHash {
+        self.levels
+            .last()
+            .and_then(|level| level.first())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the proof of the leaf at the given index, if any.
+    pub fn proof(&self, index: usize) -> Option<MerkleProof> {
+        let leaf = *self.levels.first()?.get(index)?;
+
+        let mut siblings = vec![];
+        let mut index = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling_index = index ^ 1;
+            // The last node of an odd level is promoted without a sibling.
+            if let Some(hash) = level.get(sibling_index) {
+                siblings.push(MerkleSibling {
+                    hash: *hash,
+                    is_left: sibling_index < index,
+                });
+            }
+            index /= 2;
+        }
+
+        Some(MerkleProof { leaf, siblings })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(number: usize) -> Vec<MerkleHash> {
+        (0..number)
+            .map(|i| leaf_hash(&format!("contributor_{}", i), &format!("hash_{}", i)))
+            .collect()
+    }
+
+    #[test]
+    fn test_proofs_verify() {
+        for number in 1..=9 {
+            let tree = MerkleTree::new(leaves(number));
+            let root = tree.root();
+
+            for index in 0..number {
+                let proof = tree.proof(index).unwrap();
+                assert_eq!(leaves(number)[index], proof.leaf);
+                assert!(proof.verify(&root));
+            }
+            assert!(tree.proof(number).is_none());
+        }
+
+        // A single leaf is its own root.
+        assert_eq!(leaves(1)[0], MerkleTree::new(leaves(1)).root());
+        assert_eq!([0u8; 32], MerkleTree::new(vec![]).root());
+    }
+
+    #[test]
+    fn test_tampered_proofs_fail() {
+        let tree = MerkleTree::new(leaves(5));
+        let root = tree.root();
+        let proof = tree.proof(2).unwrap();
+
+        // Tampered leaf.
+        let mut tampered = proof.clone();
+        tampered.leaf = leaf_hash("contributor_2", "another_hash");
+        assert!(!tampered.verify(&root));
+
+        // Tampered sibling.
+        let mut tampered = proof.clone();
+        tampered.siblings[0].hash[0] ^= 1;
+        assert!(!tampered.verify(&root));
+
+        // Tampered direction.
+        let mut tampered = proof.clone();
+        tampered.siblings[0].is_left = !tampered.siblings[0].is_left;
+        assert!(!tampered.verify(&root));
+
+        // Tampered root.
+        let mut tampered_root = root;
+        tampered_root[31] ^= 1;
+        assert!(!proof.verify(&tampered_root));
+    }
+}
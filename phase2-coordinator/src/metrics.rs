@@ -0,0 +1,154 @@
+//! Metrics of the coordinator in the Prometheus text exposition format, so that the ceremony can be monitored
+//! without parsing the logs.
+
+use std::fmt::{Display, Write};
+
+use lazy_static::lazy_static;
+
+use crate::Coordinator;
+
+/// The env variable enabling the metrics endpoint.
+pub const METRICS_ENABLE_ENV: &str = "NAMADA_METRICS_ENABLE";
+/// The env variable enabling the metrics of each verifier of the coordinator, labelled by verifier.
+pub const VERIFIER_METRICS_ENABLE_ENV: &str = "NAMADA_VERIFIER_METRICS_ENABLE";
+
+lazy_static! {
+    pub static ref METRICS_ENABLED: bool = match std::env::var(METRICS_ENABLE_ENV) {
+        Ok(s) if s == "true" => true,
+        _ => false,
+    };
+    pub static ref VERIFIER_METRICS_ENABLED: bool = match std::env::var(VERIFIER_METRICS_ENABLE_ENV) {
+        Ok(s) if s == "true" => true,
+        _ => false,
+    };
+}
+
+/// Appends a gauge, with its help text, to the given exposition.
+fn write_gauge(exposition: &mut String, name: &str, help: &str, value: impl Display) {
+    // Writing to a String never fails
+    let _ = writeln!(exposition, "# HELP {} {}", name, help);
+    let _ = writeln!(exposition, "# TYPE {} gauge", name);
+    let _ = writeln!(exposition, "{} {}", name, value);
+}
+
+/// Appends a gauge with a sample per value of the given label, with its help text, to the given exposition.
+fn write_labelled_gauge<V: Display>(
+    exposition: &mut String,
+    name: &str,
+    help: &str,
+    label: &str,
+    samples: impl IntoIterator<Item = (String, V)>,
+) {
+    // Writing to a String never fails
+    let _ = writeln!(exposition, "# HELP {} {}", name, help);
+    let _ = writeln!(exposition, "# TYPE {} gauge", name);
+    for (label_value, value) in samples {
+        let _ = writeln!(exposition, "{}{{{}=\"{}\"}} {}", name, label, label_value, value);
+    }
+}
+
+///
+/// Renders the metrics of the current round of the coordinator as Prometheus gauges.
+///
+pub fn render(coordinator: &Coordinator) -> String {
+    let diagnostics = coordinator.diagnostics();
+    let round_metrics = coordinator.current_round_metrics().unwrap_or_default();
+
+    let mut exposition = String::new();
+    write_gauge(
+        &mut exposition,
+        "ceremony_round_height",
+        "The height of the current round.",
+        diagnostics.current_round_height.unwrap_or_default(),
+    );
+    write_gauge(
+        &mut exposition,
+        "ceremony_contributors_active",
+        "The number of contributors participating in the current round.",
+        round_metrics.number_of_contributors(),
+    );
+    write_gauge(
+        &mut exposition,
+        "ceremony_verifiers_active",
+        "The number of verifiers participating in the current round.",
+        round_metrics.number_of_verifiers(),
+    );
+    if let Some(estimated_finish_time) = round_metrics.estimated_finish_time() {
+        write_gauge(
+            &mut exposition,
+            "ceremony_round_estimated_finish_seconds",
+            "The estimated number of seconds remaining for the current round to finish.",
+            estimated_finish_time,
+        );
+    }
+    write_gauge(
+        &mut exposition,
+        "ceremony_pending_verifications",
+        "The number of tasks pending verification.",
+        diagnostics.number_of_pending_verifications,
+    );
+    write_gauge(
+        &mut exposition,
+        "ceremony_queue_length",
+        "The number of contributors in the queue.",
+        diagnostics.number_of_queue_contributors,
+    );
+    write_gauge(
+        &mut exposition,
+        "ceremony_participants_dropped",
+        "The number of participants dropped from the ceremony.",
+        diagnostics.number_of_dropped_participants,
+    );
+    write_gauge(
+        &mut exposition,
+        "ceremony_participants_banned",
+        "The number of participants banned from the ceremony.",
+        diagnostics.number_of_banned_participants,
+    );
+
+    if *VERIFIER_METRICS_ENABLED {
+        let verifier_activity = coordinator.state().verifier_activity();
+        write_labelled_gauge(
+            &mut exposition,
+            "ceremony_verifier_tasks_verified",
+            "The number of tasks verified in the current round by the verifier.",
+            "verifier",
+            verifier_activity
+                .iter()
+                .map(|activity| (activity.verifier.to_string(), activity.tasks_verified)),
+        );
+        write_labelled_gauge(
+            &mut exposition,
+            "ceremony_verifier_average_task_seconds",
+            "The average number of seconds per verification in the current round of the verifier.",
+            "verifier",
+            verifier_activity.iter().filter_map(|activity| {
+                activity
+                    .average_seconds_per_task
+                    .map(|seconds| (activity.verifier.to_string(), seconds))
+            }),
+        );
+        write_labelled_gauge(
+            &mut exposition,
+            "ceremony_verifier_pending_verifications",
+            "The number of tasks pending verification assigned to the verifier.",
+            "verifier",
+            verifier_activity
+                .iter()
+                .map(|activity| (activity.verifier.to_string(), activity.pending_verifications)),
+        );
+        write_labelled_gauge(
+            &mut exposition,
+            "ceremony_verifier_last_active_timestamp",
+            "The unix timestamp of the last verification started or completed in the current round by the verifier.",
+            "verifier",
+            verifier_activity.iter().filter_map(|activity| {
+                activity
+                    .last_active_at
+                    .map(|timestamp| (activity.verifier.to_string(), timestamp.unix_timestamp()))
+            }),
+        );
+    }
+
+    exposition
+}
@@ -1,6 +1,7 @@
 use crate::{
     objects::{participant::*, Contribution},
     storage::LocatorPath,
+    timestamp::TimestampToken,
     CoordinatorError,
 };
 
@@ -395,6 +396,23 @@ impl Chunk {
         Ok(())
     }
 
+    ///
+    /// Sets the trusted timestamp of the contribution corresponding to the given contribution ID.
+    ///
+    pub(crate) fn set_contribution_timestamp(
+        &mut self,
+        contribution_id: u64,
+        timestamp: TimestampToken,
+    ) -> Result<(), CoordinatorError> {
+        match self.contributions.get_mut(&contribution_id) {
+            Some(contribution) => {
+                contribution.set_contributed_timestamp(timestamp);
+                Ok(())
+            }
+            None => Err(CoordinatorError::ContributionMissing),
+        }
+    }
+
     ///
     /// Updates the contribution corresponding to the given contribution ID as verified.
     ///
@@ -1,4 +1,4 @@
-use crate::{objects::Participant, storage::LocatorPath, CoordinatorError};
+use crate::{objects::Participant, storage::LocatorPath, timestamp::TimestampToken, CoordinatorError};
 
 use serde::{Deserialize, Serialize};
 use tracing::trace;
@@ -17,6 +17,8 @@ pub struct Contribution {
     #[serde(rename = "verifiedSignatureLocation")]
     verified_signature_locator: Option<LocatorPath>,
     verified: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    contributed_timestamp: Option<TimestampToken>,
 }
 
 impl Contribution {
@@ -48,6 +50,13 @@ impl Contribution {
         &self.contributed_signature_locator
     }
 
+    /// Returns a reference to the trusted timestamp of the contribution, if it exists.
+    /// Otherwise returns `None`.
+    #[inline]
+    pub fn get_contributed_timestamp(&self) -> &Option<TimestampToken> {
+        &self.contributed_timestamp
+    }
+
     /// Returns a reference to the verifier, if it exists.
     /// Otherwise returns `None`.
     #[allow(dead_code)]
@@ -97,6 +106,7 @@ impl Contribution {
             verified_locator: None,
             verified_signature_locator: None,
             verified: false,
+            contributed_timestamp: None,
         })
     }
 
@@ -137,11 +147,21 @@ impl Contribution {
             verified_locator: Some(verified_locator),
             verified_signature_locator: Some(verified_signature_locator),
             verified: true,
+            contributed_timestamp: None,
         };
 
         Ok(contribution)
     }
 
+    ///
+    /// Sets the trusted timestamp of the contribution, issued once it
+    /// is added to the chunk.
+    ///
+    #[inline]
+    pub(crate) fn set_contributed_timestamp(&mut self, timestamp: TimestampToken) {
+        self.contributed_timestamp = Some(timestamp);
+    }
+
     ///
     /// Assign a verifier to this instance of `Contribution`.
     ///
@@ -194,6 +194,10 @@ impl TrimmedContributionInfo {
         self.ceremony_round
     }
 
+    pub fn contribution_hash(&self) -> &str {
+        self.contribution_hash.as_ref()
+    }
+
     #[cfg(debug_assertions)]
     pub fn is_another_machine(&self) -> bool {
         self.is_another_machine
@@ -18,3 +18,6 @@ pub use round::*;
 
 pub mod task;
 pub use task::Task;
+
+pub mod verification_cache;
+pub use verification_cache::*;
@@ -2,7 +2,7 @@ use crate::{
     environment::Environment,
     objects::{participant::*, Chunk},
     storage::{
-        ContributionLocator, ContributionSignatureLocator, Disk, Locator, LocatorPath, Object, StorageAction,
+        ContributionLocator, ContributionSignatureLocator, Locator, LocatorPath, Object, Storage, StorageAction,
         StorageLocator, UpdateAction,
     },
     CoordinatorError,
@@ -82,7 +82,7 @@ impl Round {
     #[inline]
     pub(crate) fn new(
         environment: &Environment,
-        storage: &mut Disk,
+        storage: &mut dyn Storage,
         round_height: u64,
         started_at: OffsetDateTime,
         contributor_ids: Vec<Participant>,
@@ -338,7 +338,7 @@ impl Round {
     )]
     pub(crate) fn current_contribution_locator(
         &self,
-        storage: &Disk,
+        storage: &dyn Storage,
         chunk_id: u64,
         verified: bool,
     ) -> Result<ContributionLocator, CoordinatorError> {
@@ -399,7 +399,7 @@ impl Round {
     )]
     pub(crate) fn next_contribution_locator(
         &self,
-        storage: &Disk,
+        storage: &dyn Storage,
         chunk_id: u64,
     ) -> Result<ContributionLocator, CoordinatorError> {
         // Fetch the current round height.
@@ -455,7 +455,7 @@ impl Round {
     #[inline]
     pub(crate) fn next_contribution_file_signature_locator(
         &self,
-        storage: &Disk,
+        storage: &dyn Storage,
         chunk_id: u64,
     ) -> Result<ContributionSignatureLocator, CoordinatorError> {
         // Fetch the current round height.
@@ -506,7 +506,7 @@ impl Round {
     pub(crate) fn try_lock_chunk(
         &mut self,
         environment: &Environment,
-        storage: &mut Disk,
+        storage: &mut dyn Storage,
         chunk_id: u64,
         participant: &Participant,
     ) -> Result<LockedLocators, CoordinatorError> {
@@ -639,7 +639,7 @@ impl Round {
     pub fn initialize_verifier_response_files(
         &self,
         _environment: &Environment,
-        storage: &mut Disk,
+        storage: &mut dyn Storage,
         participant: &Participant,
         _chunk_id: u64,
         locators: &LockedLocators,
@@ -668,7 +668,7 @@ impl Round {
     /// Returns previous contribution, current contribution and next contribution paths
     pub(crate) fn get_chunk_locators_for_verifier(
         &self,
-        storage: &Disk,
+        storage: &dyn Storage,
         participant: &Participant,
         chunk_id: u64,
         contribution_id: u64,
@@ -786,7 +786,7 @@ impl Round {
     /// Remove a contributor from the round.
     pub(crate) fn remove_contributor_unsafe(
         &mut self,
-        storage: &mut Disk,
+        storage: &mut dyn Storage,
         contributor: &Participant,
         locked_chunks: &[u64],
         tasks: &[Task],
@@ -823,7 +823,7 @@ impl Round {
     #[inline]
     pub(crate) fn remove_locks_unsafe(
         &mut self,
-        storage: &mut Disk,
+        storage: &mut dyn Storage,
         participant: &Participant,
         locked_chunks: &[u64],
     ) -> Result<(), CoordinatorError> {
@@ -997,7 +997,7 @@ impl Round {
     )]
     pub(crate) fn remove_chunk_contributions_unsafe(
         &mut self,
-        storage: &mut Disk,
+        storage: &mut dyn Storage,
         participant: &Participant,
         tasks: &[Task],
     ) -> Result<(), CoordinatorError> {
@@ -1160,15 +1160,12 @@ impl Round {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::testing::prelude::*;
+    use crate::{storage::MemoryStorage, testing::prelude::*};
 
     #[test]
-    #[serial]
     fn test_round_0_matches() {
-        initialize_test_environment(&TEST_ENVIRONMENT);
-
         // Define test storage.
-        let mut storage = test_storage(&TEST_ENVIRONMENT);
+        let mut storage = MemoryStorage::load(&TEST_ENVIRONMENT).unwrap();
 
         let expected = test_round_0().unwrap();
         let candidate = Round::new(
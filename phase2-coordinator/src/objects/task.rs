@@ -1,4 +1,6 @@
-use std::{collections::LinkedList, fmt::Debug, str::FromStr};
+use crate::CoordinatorError;
+
+use std::{collections::LinkedList, str::FromStr};
 
 use serde::{
     de::{self, Error},
@@ -62,15 +64,27 @@ impl Task {
     }
 }
 
+/// Checks that the given contribution ID can be computed or verified by a participant.
+///
+/// The contribution 0 of each chunk is reserved for the initialization of the round,
+/// so the contributions of the contributors start at 1.
+#[inline]
+pub fn check_contribution_id(contribution_id: u64) -> Result<(), CoordinatorError> {
+    match contribution_id {
+        0 => Err(CoordinatorError::ContributionIdMustBeNonzero),
+        _ => Ok(()),
+    }
+}
+
 impl std::fmt::Display for Task {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.to_tuple().fmt(f)
+        write!(f, "{}/{}", self.chunk_id, self.contribution_id)
     }
 }
 
 impl Serialize for Task {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        serializer.serialize_str(&format!("{}/{}", self.chunk_id, self.contribution_id))
+        serializer.serialize_str(&self.to_string())
     }
 }
 
@@ -247,15 +261,143 @@ pub fn initialize_tasks(
     Ok(tasks)
 }
 
+/// Constructs ceremony tasks for a participant like [initialize_tasks],
+/// adapting the size of the buckets to the number of contributors.
+/// The bucket `b` starts at chunk `b * number_of_chunks / number_of_contributors`,
+/// so that the sizes of the buckets differ by at most one chunk. When
+/// there are more contributors than chunks, some buckets are empty and
+/// their contributors start in the next non-empty bucket.
+///
+/// If the number of contributors divides the number of chunks, the
+/// tasks are the same as the ones of [initialize_tasks].
+///
+/// # Examples
+///
+/// With
+///
+/// + `number_of_chunks = 8`
+/// + `number_of_contributors = 3`
+///
+/// The buckets are the following, where [initialize_tasks] gives the
+/// remaining chunks to the last bucket:
+///
+/// ```txt, ignore
+///   +---------+---------+---------+---------+---------+---------+---------+---------+
+///   | Chunk 0 | Chunk 1 | Chunk 2 | Chunk 3 | Chunk 4 | Chunk 5 | Chunk 6 | Chunk 7 |
+///   +---------+---------+---------+---------+---------+---------+---------+---------+
+///   |      Bucket 0     |          Bucket 1           |          Bucket 2           |
+///   +---------+---------+---------+---------+---------+---------+---------+---------+
+/// ```
+pub fn initialize_adaptive_tasks(
+    starting_bucket_id: u64, // 0-indexed
+    number_of_chunks: u64,
+    number_of_contributors: u64,
+) -> Result<LinkedList<Task>, TaskInitializationError> {
+    if number_of_contributors == 0 {
+        return Ok(LinkedList::new());
+    }
+
+    // Check whether the starting bucket id is out of range
+    if starting_bucket_id >= number_of_contributors {
+        return Err(TaskInitializationError::StartingBucketIdOutOfRange {
+            starting_bucket_id,
+            number_of_contributors,
+        });
+    }
+
+    // The bucket of a chunk is the last bucket starting at or before it.
+    let bucket_id = |chunk_id: u64| ((chunk_id + 1) * number_of_contributors - 1) / number_of_chunks;
+
+    // Compute the start index.
+    let start = starting_bucket_id * number_of_chunks / number_of_contributors;
+
+    // Add the tasks in FIFO ordering, building on the contributions of the
+    // contributors starting in the buckets in between.
+    let tasks: LinkedList<Task> = (start..start + number_of_chunks)
+        .map(|current_index| {
+            let chunk_id = current_index % number_of_chunks;
+            let steps = (bucket_id(chunk_id) + number_of_contributors - starting_bucket_id) % number_of_contributors;
+            Task::new(chunk_id, steps + 1)
+        })
+        .collect();
+
+    assert!(tasks.len() == number_of_chunks as usize);
+
+    Ok(tasks)
+}
+
+/// The partition of the chunks into the buckets the contributors of a
+/// round start in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BucketLayout {
+    /// Buckets of `number_of_chunks / number_of_contributors` chunks, see [initialize_tasks].
+    Fixed,
+    /// Buckets adapted to the number of contributors, see [initialize_adaptive_tasks].
+    Adaptive,
+}
+
+impl Default for BucketLayout {
+    fn default() -> Self {
+        BucketLayout::Fixed
+    }
+}
+
+impl FromStr for BucketLayout {
+    type Err = String;
+
+    /// Parses `fixed` or `adaptive`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "fixed" => Ok(BucketLayout::Fixed),
+            "adaptive" => Ok(BucketLayout::Adaptive),
+            _ => Err(format!("Invalid bucket layout {}, expected fixed or adaptive", s)),
+        }
+    }
+}
+
+impl BucketLayout {
+    /// Constructs ceremony tasks for a participant starting in the
+    /// bucket `starting_bucket_id` of this layout.
+    pub fn initialize_tasks(
+        &self,
+        starting_bucket_id: u64,
+        number_of_chunks: u64,
+        number_of_contributors: u64,
+    ) -> Result<LinkedList<Task>, TaskInitializationError> {
+        match self {
+            BucketLayout::Fixed => initialize_tasks(starting_bucket_id, number_of_chunks, number_of_contributors),
+            BucketLayout::Adaptive => {
+                initialize_adaptive_tasks(starting_bucket_id, number_of_chunks, number_of_contributors)
+            }
+        }
+    }
+}
+
+/// Returns the tasks a contributor assigned to the bucket
+/// `bucket_id` is expected to complete in order to finish the round.
+/// The expected set is the same as the one handed out by
+/// [BucketLayout::initialize_tasks], so that a contributor is expected
+/// to contribute to every chunk exactly once.
+pub fn expected_tasks_for_bucket(
+    bucket_layout: BucketLayout,
+    bucket_id: u64,
+    number_of_chunks: u64,
+    number_of_contributors: u64,
+) -> Result<LinkedList<Task>, TaskInitializationError> {
+    bucket_layout.initialize_tasks(bucket_id, number_of_chunks, number_of_contributors)
+}
+
 #[cfg(test)]
 mod test {
-    use super::{initialize_tasks, Task, TaskInitializationError};
+    use super::{initialize_adaptive_tasks, initialize_tasks, BucketLayout, Task, TaskInitializationError};
     use crate::testing::prelude::test_logger;
     use std::collections::HashSet;
 
     #[test]
     fn test_task() {
         let task = Task::new(0, 1);
+        assert_eq!("0/1", task.to_string());
         assert_eq!("\"0/1\"", serde_json::to_string(&task).unwrap());
         assert_eq!(task, serde_json::from_str("\"0/1\"").unwrap());
     }
@@ -386,4 +528,89 @@ mod test {
             }
         }
     }
+
+    /// Returns the number of chunks of each bucket, as the number of
+    /// first contributions of each contributor.
+    fn bucket_sizes(bucket_layout: BucketLayout, number_of_chunks: u64, number_of_contributors: u64) -> Vec<usize> {
+        (0..number_of_contributors)
+            .map(|bucket_id| {
+                bucket_layout
+                    .initialize_tasks(bucket_id, number_of_chunks, number_of_contributors)
+                    .unwrap()
+                    .iter()
+                    .filter(|task| task.contribution_id() == 1)
+                    .count()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_initialize_adaptive_tasks_divisible() {
+        for number_of_contributors in 1..16 {
+            for number_of_chunks in (number_of_contributors..128).step_by(number_of_contributors as usize) {
+                for bucket_id in 0..number_of_contributors {
+                    assert_eq!(
+                        initialize_tasks(bucket_id, number_of_chunks, number_of_contributors).unwrap(),
+                        initialize_adaptive_tasks(bucket_id, number_of_chunks, number_of_contributors).unwrap()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_initialize_adaptive_tasks_few_contributors() {
+        // The fixed layout gives the remaining chunks to the last bucket.
+        assert_eq!(vec![2, 2, 4], bucket_sizes(BucketLayout::Fixed, 8, 3));
+        assert_eq!(
+            vec![3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 15],
+            bucket_sizes(BucketLayout::Fixed, 60, 16)
+        );
+
+        // The adaptive layout spreads them over all the buckets.
+        assert_eq!(vec![2, 3, 3], bucket_sizes(BucketLayout::Adaptive, 8, 3));
+        for number_of_contributors in 1..32 {
+            for number_of_chunks in number_of_contributors..256 {
+                let sizes = bucket_sizes(BucketLayout::Adaptive, number_of_chunks, number_of_contributors);
+                let smallest = sizes.iter().min().unwrap();
+                let largest = sizes.iter().max().unwrap();
+                assert!(largest - smallest <= 1);
+                assert_eq!(number_of_chunks as usize, sizes.iter().sum::<usize>());
+            }
+        }
+    }
+
+    #[test]
+    fn test_initialize_adaptive_tasks_many_contributors() {
+        // The fixed layout needs a chunk per contributor.
+        assert!(BucketLayout::Fixed.initialize_tasks(0, 2, 5).is_err());
+
+        // The adaptive layout merges the contributors in the non-empty buckets.
+        assert_eq!(vec![0, 0, 1, 0, 1], bucket_sizes(BucketLayout::Adaptive, 2, 5));
+        let mut tasks = initialize_adaptive_tasks(0, 2, 5).unwrap().into_iter();
+        assert_eq!(Some(Task::new(0, 3)), tasks.next());
+        assert_eq!(Some(Task::new(1, 5)), tasks.next());
+        assert!(tasks.next().is_none());
+        let mut tasks = initialize_adaptive_tasks(2, 2, 5).unwrap().into_iter();
+        assert_eq!(Some(Task::new(0, 1)), tasks.next());
+        assert_eq!(Some(Task::new(1, 3)), tasks.next());
+        assert!(tasks.next().is_none());
+    }
+
+    #[test]
+    fn test_initialize_adaptive_tasks_unique() {
+        for number_of_contributors in 1..32 {
+            for number_of_chunks in 1..64 {
+                let mut all_tasks = HashSet::new();
+                for bucket_id in 0..number_of_contributors {
+                    let tasks = initialize_adaptive_tasks(bucket_id, number_of_chunks, number_of_contributors).unwrap();
+                    assert_eq!(number_of_chunks as usize, tasks.len());
+                    for task in tasks {
+                        assert!(task.contribution_id() >= 1 && task.contribution_id() <= number_of_contributors);
+                        assert!(all_tasks.insert(task));
+                    }
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,200 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::warn;
+
+/// The version of the verification logic. It must be bumped whenever the verification
+/// changes, so that the verdicts cached by a previous version are discarded.
+pub const VERIFICATION_VERSION: u32 = 1;
+
+/// The first line of the cache file, identifying the verdicts which follow it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheHeader {
+    /// The version of the verification logic which computed the verdicts.
+    version: u32,
+    /// The round height the verdicts were computed in.
+    round_height: u64,
+}
+
+/// A line of the cache file following the header, holding a single verdict.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CacheEntry {
+    /// The hex encoded challenge hash and response hash.
+    key: String,
+    /// The verdict of the verification.
+    verdict: bool,
+}
+
+///
+/// The verdicts of the previous verifications of a round, keyed by the hashes of the
+/// verified challenge and response files.
+///
+/// The cache is stored as JSON lines, a header followed by one line per verdict, so that
+/// a new verdict is appended to the file instead of rewriting it. The verdicts are only
+/// kept for a single round, which bounds the cache to the verifications of that round.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationCache {
+    /// The version of the verification logic which computed the verdicts.
+    version: u32,
+    /// The round height the verdicts were computed in.
+    round_height: u64,
+    /// The verdicts, keyed by the hex encoded challenge hash and response hash.
+    verdicts: HashMap<String, bool>,
+}
+
+impl VerificationCache {
+    /// Creates a new empty cache of the given round for the current verification logic.
+    #[inline]
+    pub fn new(round_height: u64) -> Self {
+        Self {
+            version: VERIFICATION_VERSION,
+            round_height,
+            verdicts: HashMap::new(),
+        }
+    }
+
+    ///
+    /// Parses the cache from the lines of the cache file. The verdicts which can't be
+    /// parsed, such as a partial line left by a crash in the middle of an append, are
+    /// skipped, so that the verifications are run again.
+    ///
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, serde_json::Error> {
+        let contents = String::from_utf8_lossy(bytes);
+        let mut lines = contents.lines();
+
+        let header: CacheHeader = serde_json::from_str(lines.next().unwrap_or_default())?;
+        let mut cache = Self {
+            version: header.version,
+            round_height: header.round_height,
+            verdicts: HashMap::new(),
+        };
+
+        for line in lines {
+            match serde_json::from_str::<CacheEntry>(line) {
+                Ok(entry) => {
+                    cache.verdicts.insert(entry.key, entry.verdict);
+                }
+                Err(_) => warn!("Skipping a malformed line in the verification cache"),
+            }
+        }
+
+        Ok(cache)
+    }
+
+    /// Returns the lines of the cache file, the header followed by the verdicts.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let header = CacheHeader {
+            version: self.version,
+            round_height: self.round_height,
+        };
+
+        let mut bytes = Self::line(&header);
+        for (key, verdict) in &self.verdicts {
+            bytes.extend(Self::line(&CacheEntry {
+                key: key.clone(),
+                verdict: *verdict,
+            }));
+        }
+        bytes
+    }
+
+    /// Returns the line to append to the cache file to record the given verdict.
+    pub fn entry_bytes(challenge_hash: &[u8], response_hash: &[u8], verdict: bool) -> Vec<u8> {
+        Self::line(&CacheEntry {
+            key: Self::key(challenge_hash, response_hash),
+            verdict,
+        })
+    }
+
+    ///
+    /// Returns `true` if the verdicts were computed in the given round by the
+    /// current verification logic.
+    ///
+    #[inline]
+    pub fn is_current(&self, round_height: u64) -> bool {
+        self.version == VERIFICATION_VERSION && self.round_height == round_height
+    }
+
+    /// Returns the verdict of the verification of the given challenge and response hashes, if any.
+    #[inline]
+    pub fn get(&self, challenge_hash: &[u8], response_hash: &[u8]) -> Option<bool> {
+        self.verdicts.get(&Self::key(challenge_hash, response_hash)).copied()
+    }
+
+    /// Records the verdict of the verification of the given challenge and response hashes.
+    #[inline]
+    pub fn insert(&mut self, challenge_hash: &[u8], response_hash: &[u8], verdict: bool) {
+        self.verdicts.insert(Self::key(challenge_hash, response_hash), verdict);
+    }
+
+    /// Returns the number of cached verdicts.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.verdicts.len()
+    }
+
+    /// Returns `true` if no verdict is cached.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.verdicts.is_empty()
+    }
+
+    #[inline]
+    fn key(challenge_hash: &[u8], response_hash: &[u8]) -> String {
+        format!("{}:{}", hex::encode(challenge_hash), hex::encode(response_hash))
+    }
+
+    #[inline]
+    fn line<T: Serialize>(value: &T) -> Vec<u8> {
+        let mut line = serde_json::to_vec(value).expect("Verification cache to bytes failed");
+        line.push(b'\n');
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verification_cache_version() {
+        let mut cache = VerificationCache::new(1);
+        cache.insert(&[0; 64], &[1; 64], true);
+        assert!(cache.is_current(1));
+        assert_eq!(Some(true), cache.get(&[0; 64], &[1; 64]));
+        assert_eq!(None, cache.get(&[1; 64], &[0; 64]));
+
+        // A cache of another round is outdated.
+        assert!(!cache.is_current(2));
+
+        // A cache written by another version of the verification logic is outdated.
+        let mut outdated = cache.clone();
+        outdated.version = VERIFICATION_VERSION + 1;
+        let outdated = VerificationCache::from_bytes(&outdated.to_bytes()).unwrap();
+        assert!(!outdated.is_current(1));
+    }
+
+    #[test]
+    fn test_verification_cache_append() {
+        let mut cache = VerificationCache::new(1);
+        cache.insert(&[0; 64], &[1; 64], true);
+
+        // An appended verdict is read back with the others.
+        let mut bytes = cache.to_bytes();
+        bytes.extend(VerificationCache::entry_bytes(&[2; 64], &[3; 64], false));
+        let cache = VerificationCache::from_bytes(&bytes).unwrap();
+        assert_eq!(2, cache.len());
+        assert_eq!(Some(true), cache.get(&[0; 64], &[1; 64]));
+        assert_eq!(Some(false), cache.get(&[2; 64], &[3; 64]));
+
+        // A partial line is skipped, along with the verdict appended after it.
+        bytes.extend(&VerificationCache::entry_bytes(&[4; 64], &[5; 64], true)[..10]);
+        bytes.extend(VerificationCache::entry_bytes(&[6; 64], &[7; 64], true));
+        bytes.extend(VerificationCache::entry_bytes(&[8; 64], &[9; 64], true));
+        let cache = VerificationCache::from_bytes(&bytes).unwrap();
+        assert_eq!(3, cache.len());
+        assert_eq!(None, cache.get(&[4; 64], &[5; 64]));
+        assert_eq!(Some(true), cache.get(&[8; 64], &[9; 64]));
+    }
+}
@@ -0,0 +1,201 @@
+//! Preflight checks run by the operator before launching the ceremony.
+//!
+//! The coordinator relies on S3 (to fetch the tokens and serve challenges and contributions) and on SSM (to publish the
+//! access secret). A misconfigured IAM role or bucket would otherwise only surface as a panic halfway through boot.
+//! The checks in this module are read-only with respect to the real tokens and the real secret: SSM write permission is
+//! verified against a scratch parameter which is deleted right after.
+
+use crate::s3::{S3Ctx, REGION};
+use rusoto_ssm::{DeleteParameterRequest, PutParameterRequest, Ssm, SsmClient};
+use std::fmt;
+
+/// Name of the SSM parameter used to probe write permission, relative to the ceremony environment prefix.
+const SCRATCH_PARAMETER: &str = "preflight";
+
+/// The external services the coordinator depends on at startup.
+#[rocket::async_trait]
+pub trait PreflightClient: Send + Sync {
+    /// Checks that the S3 bucket is reachable with the current credentials.
+    async fn check_bucket_access(&self) -> Result<(), String>;
+    /// Checks that the tokens archive exists in the bucket.
+    async fn check_tokens_object(&self) -> Result<(), String>;
+    /// Checks that parameters can be written to SSM.
+    async fn check_ssm_write(&self) -> Result<(), String>;
+}
+
+/// The outcome of a single preflight check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreflightCheck {
+    pub name: &'static str,
+    pub result: Result<(), String>,
+}
+
+/// The aggregated outcome of all the preflight checks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreflightReport {
+    pub checks: Vec<PreflightCheck>,
+}
+
+impl PreflightReport {
+    ///
+    /// Returns `true` if every check succeeded.
+    ///
+    pub fn is_ok(&self) -> bool {
+        self.checks.iter().all(|check| check.result.is_ok())
+    }
+
+    ///
+    /// Returns the checks which failed.
+    ///
+    pub fn failures(&self) -> Vec<&PreflightCheck> {
+        self.checks.iter().filter(|check| check.result.is_err()).collect()
+    }
+}
+
+impl fmt::Display for PreflightReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            match &check.result {
+                Ok(()) => writeln!(f, "[OK]   {}", check.name)?,
+                Err(e) => writeln!(f, "[FAIL] {}: {}", check.name, e)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs all the checks against the given client. Every check is run even if a previous one failed, so that the
+/// operator gets the complete picture in a single pass.
+pub async fn run_preflight<C: PreflightClient>(client: &C) -> PreflightReport {
+    let checks = vec![
+        PreflightCheck {
+            name: "S3 bucket access",
+            result: client.check_bucket_access().await,
+        },
+        PreflightCheck {
+            name: "S3 tokens object",
+            result: client.check_tokens_object().await,
+        },
+        PreflightCheck {
+            name: "SSM write permission",
+            result: client.check_ssm_write().await,
+        },
+    ];
+
+    PreflightReport { checks }
+}
+
+/// [`PreflightClient`] backed by the real AWS services.
+pub struct AwsPreflight {
+    s3: S3Ctx,
+    ssm: SsmClient,
+}
+
+impl AwsPreflight {
+    pub async fn new() -> Result<Self, String> {
+        let s3 = S3Ctx::new().await.map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            s3,
+            ssm: SsmClient::new(REGION.clone()),
+        })
+    }
+}
+
+#[rocket::async_trait]
+impl PreflightClient for AwsPreflight {
+    async fn check_bucket_access(&self) -> Result<(), String> {
+        self.s3.check_bucket().await.map_err(|e| e.to_string())
+    }
+
+    async fn check_tokens_object(&self) -> Result<(), String> {
+        self.s3.check_tokens().await.map_err(|e| e.to_string())
+    }
+
+    async fn check_ssm_write(&self) -> Result<(), String> {
+        let env = match std::env::var("AWS_S3_PROD") {
+            Ok(val) if val == "true" => "production",
+            _ => "master",
+        };
+        let name = format!("/namada/trusted-setup/{}/{}", env, SCRATCH_PARAMETER);
+
+        let put_request = PutParameterRequest {
+            description: Some("Trusted setup preflight check".to_string()),
+            name: name.clone(),
+            overwrite: Some(true),
+            type_: Some("String".to_string()),
+            value: "preflight".to_string(),
+            ..Default::default()
+        };
+        self.ssm.put_parameter(put_request).await.map_err(|e| e.to_string())?;
+
+        self.ssm
+            .delete_parameter(DeleteParameterRequest { name })
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockPreflight {
+        bucket: Result<(), String>,
+        tokens: Result<(), String>,
+        ssm: Result<(), String>,
+    }
+
+    #[rocket::async_trait]
+    impl PreflightClient for MockPreflight {
+        async fn check_bucket_access(&self) -> Result<(), String> {
+            self.bucket.clone()
+        }
+
+        async fn check_tokens_object(&self) -> Result<(), String> {
+            self.tokens.clone()
+        }
+
+        async fn check_ssm_write(&self) -> Result<(), String> {
+            self.ssm.clone()
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_preflight_all_ok() {
+        let client = MockPreflight {
+            bucket: Ok(()),
+            tokens: Ok(()),
+            ssm: Ok(()),
+        };
+
+        let report = run_preflight(&client).await;
+        assert_eq!(report.checks.len(), 3);
+        assert!(report.is_ok());
+        assert!(report.failures().is_empty());
+    }
+
+    #[rocket::async_test]
+    async fn test_preflight_reports_every_failure() {
+        let client = MockPreflight {
+            bucket: Err("access denied".to_string()),
+            tokens: Ok(()),
+            ssm: Err("not authorized to perform ssm:PutParameter".to_string()),
+        };
+
+        let report = run_preflight(&client).await;
+        assert_eq!(report.checks.len(), 3);
+        assert!(!report.is_ok());
+
+        let failures = report.failures();
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].name, "S3 bucket access");
+        assert_eq!(failures[1].name, "SSM write permission");
+
+        let output = report.to_string();
+        assert!(output.contains("[FAIL] S3 bucket access: access denied"));
+        assert!(output.contains("[OK]   S3 tokens object"));
+    }
+}
@@ -3,47 +3,70 @@
 use std::{
     collections::{HashMap, HashSet},
     io::{Cursor, Read, Write},
+    net::IpAddr,
 };
 use tracing::warn;
 
 use crate::{
-    objects::{ContributionInfo, LockedLocators},
+    coordinator_state::{
+        AssignmentExplanation, Blacklists, ChunkUpload, Diagnostics, FinishedParticipants, NextRoundAssignment,
+        ParticipantMetrics, QueueEntry, ReliabilityBucket, TokenInUse, VerifierActivity,
+    },
+    environment::Timeouts,
+    merkle::{MerkleHash, MerkleProof},
+    metrics,
+    objects::{ContributionInfo, LockedLocators, Task, TrimmedContributionInfo},
     rest_utils::{
-        self, ContributorStatus, Coordinator, CurrentContributor, LazyJson, NewParticipant, PostChunkRequest,
-        ResponseError, Result, Secret, ServerAuth, HEALTH_PATH, TOKENS_PATH, TOKENS_ZIP_FILE,
+        self, Ceremonies, CeremonyCoordinator, ContributorStatus, CurrentContributor, InitiateChunkUploadRequest,
+        LazyJson, MergeCohortsRequest, NewParticipant, PostChunkBatchRequest, PostChunkRequest, PutChunkPartRequest,
+        ResponseError, Result, RoundParticipant, Secret, ServerAuth, TokenEligibility, TokenScheduleRequest,
+        TokenStatus, ValidJson, HEALTH_PATH, TOKENS_ZIP_FILE,
     },
-    s3::S3Ctx,
     storage::{Locator, Object},
-    CoordinatorState, Participant,
+    contribution_anomalies, CoordinatorError, CoordinatorState, HealthReport, Participant, TransparencyInfo,
+    VersionInfo,
 };
 use rocket::{
-    get, post,
+    get,
+    http::{ContentType, Status},
+    post,
+    response::stream::{Event, EventStream},
     serde::json::Json,
-    tokio::{fs, task},
-    Shutdown, State,
+    tokio::{fs, select, sync::broadcast::error::RecvError, task},
+    Either, Shutdown, State,
 };
+use setup_utils::calculate_hash;
 
 use url::Url;
 
 /// Add the incoming contributor to the queue of contributors.
 #[post("/contributor/join_queue", format = "json", data = "<token>")]
 pub async fn join_queue(
-    coordinator: &State<Coordinator>,
+    coordinator: CeremonyCoordinator,
     new_participant: NewParticipant,
     token: LazyJson<String>,
 ) -> Result<Json<u64>> {
     // NOTE: check on the token happens only here meaning that a contributor can join the ceremony at the very last moment of a cohort and
     // contribute effectively in the following cohort. Forcing the contribution to happen in the correct cohort would take more complicated checks
     // and could lower the amount of contributions received
+    if coordinator.read().await.is_draining() {
+        return Err(ResponseError::CoordinatorError(CoordinatorError::CoordinatorDraining));
+    }
     let cohort = rest_utils::token_check((*coordinator).clone(), token.as_str()).await?;
     let mut write_lock = (*coordinator).clone().write_owned().await;
 
     task::spawn_blocking(move || {
+        write_lock.check_join_signature(
+            &new_participant.participant,
+            token.as_str(),
+            new_participant.join_signature.as_deref(),
+        )?;
+        let reliability_score = write_lock.state().reliability_score(&new_participant.participant);
         write_lock.add_to_queue(
             new_participant.participant,
             new_participant.ip_address,
             token.clone(),
-            10,
+            reliability_score,
         )
     })
     .await?
@@ -52,14 +75,55 @@ pub async fn join_queue(
     Ok(Json(cohort))
 }
 
+/// Check the eligibility of a ceremony token without joining the queue, so that the token is not wasted on a cohort
+/// which is not active. The request is signed like the other contributor requests, with any keypair since the
+/// contributors only generate theirs when contributing, so it doesn't identify who checks the token.
+#[post("/contributor/check_eligibility", format = "json", data = "<token>")]
+pub async fn check_eligibility(
+    coordinator: CeremonyCoordinator,
+    _participant: Participant,
+    token: LazyJson<String>,
+) -> Json<TokenEligibility> {
+    let read_lock = coordinator.read().await;
+    let state = read_lock.state();
+
+    let token_cohort = state.token_cohort(token.as_str());
+    let status = if state.is_token_blacklisted(token.as_str()) {
+        TokenStatus::Blacklisted
+    } else if state.is_token_in_use(token.as_str()) {
+        TokenStatus::Spent
+    } else if token_cohort.is_none() {
+        TokenStatus::Unknown
+    } else {
+        TokenStatus::Fresh
+    };
+    let current_cohort = state.get_current_cohort_index();
+
+    Json(TokenEligibility {
+        status,
+        token_cohort: token_cohort.map(|cohort| cohort as u64 + 1),
+        current_cohort: state.tokens(current_cohort).map(|_| current_cohort as u64 + 1),
+    })
+}
+
 /// Lock a [Chunk](`crate::objects::Chunk`) in the ceremony. This should be the first function called when attempting to contribute to a chunk. Once the chunk is locked, it is ready to be downloaded.
-#[get("/contributor/lock_chunk", format = "json")]
+/// The chunk of the next assigned task is locked, unless another assigned chunk is given and the coordinator accepts contributions out of order.
+#[get("/contributor/lock_chunk?<chunk_id>", format = "json")]
 pub async fn lock_chunk(
-    coordinator: &State<Coordinator>,
+    coordinator: CeremonyCoordinator,
     participant: CurrentContributor,
+    chunk_id: Option<u64>,
 ) -> Result<Json<LockedLocators>> {
     let mut write_lock = (*coordinator).clone().write_owned().await;
-    match task::spawn_blocking(move || write_lock.try_lock(&participant)).await? {
+    if write_lock.is_draining() {
+        return Err(ResponseError::CoordinatorError(CoordinatorError::CoordinatorDraining));
+    }
+    match task::spawn_blocking(move || match chunk_id {
+        Some(chunk_id) => write_lock.try_lock_assigned(&participant, chunk_id),
+        None => write_lock.try_lock(&participant),
+    })
+    .await?
+    {
         Ok((_, locked_locators)) => Ok(Json(locked_locators)),
         Err(e) => Err(ResponseError::CoordinatorError(e)),
     }
@@ -68,11 +132,12 @@ pub async fn lock_chunk(
 /// Get the challenge key on Amazon S3 from the [Coordinator](`crate::Coordinator`).
 #[post("/contributor/challenge", format = "json", data = "<round_height>")]
 pub async fn get_challenge_url(
-    coordinator: &State<Coordinator>,
-    _participant: CurrentContributor,
+    coordinator: CeremonyCoordinator,
+    participant: CurrentContributor,
     round_height: LazyJson<u64>,
 ) -> Result<Json<String>> {
-    let s3_ctx = S3Ctx::new().await?;
+    coordinator.write().await.record_activity(&participant);
+    let s3_ctx = rest_utils::ceremony_s3_ctx(&coordinator).await?;
     let key = format!("round_{}/chunk_0/contribution_0.verified", *round_height);
 
     // If challenge is already on S3 (round rollback) immediately return the key
@@ -97,19 +162,128 @@ pub async fn get_challenge_url(
 /// Request the urls where to upload a [Chunk](`crate::objects::Chunk`) contribution and the ContributionFileSignature.
 #[post("/upload/chunk", format = "json", data = "<round_height>")]
 pub async fn get_contribution_url(
-    _participant: CurrentContributor,
+    coordinator: CeremonyCoordinator,
+    participant: CurrentContributor,
     round_height: LazyJson<u64>,
 ) -> Result<Json<(String, String)>> {
+    coordinator.write().await.record_activity(&participant);
     let contrib_key = format!("round_{}/chunk_0/contribution_1.unverified", *round_height);
     let contrib_sig_key = format!("round_{}/chunk_0/contribution_1.unverified.signature", *round_height);
 
     // Prepare urls for the upload
-    let s3_ctx = S3Ctx::new().await?;
+    let s3_ctx = rest_utils::ceremony_s3_ctx(&coordinator).await?;
     let urls = s3_ctx.get_contribution_urls(contrib_key, contrib_sig_key);
 
     Ok(Json(urls))
 }
 
+/// Start, or resume after an interruption, a resumable upload of a [Chunk](`crate::objects::Chunk`) contribution. Returns
+/// the upload session, with its id, the expected total size and the parts received so far. The contribution is only
+/// published where [contribute_chunk] expects it once [complete_chunk_upload] has checked its checksum.
+#[post("/upload/chunk/initiate", format = "json", data = "<upload_request>")]
+pub async fn initiate_chunk_upload(
+    coordinator: CeremonyCoordinator,
+    participant: CurrentContributor,
+    upload_request: ValidJson<InitiateChunkUploadRequest>,
+) -> Result<Json<ChunkUpload>> {
+    let upload = {
+        let mut write_lock = coordinator.write().await;
+        write_lock.record_activity(&participant);
+        write_lock
+            .initiate_chunk_upload(
+                &participant,
+                upload_request.round_height,
+                upload_request.total_size,
+                upload_request.checksum.clone(),
+            )
+            .map_err(ResponseError::CoordinatorError)?
+    };
+
+    // Discard the parts staged by a previous upload of the contributor
+    if upload.parts.is_empty() {
+        let s3_ctx = rest_utils::ceremony_s3_ctx(&coordinator).await?;
+        s3_ctx.delete_contribution_parts(upload.round_height).await?;
+    }
+
+    Ok(Json(upload))
+}
+
+/// Upload a part of a resumable upload of a [Chunk](`crate::objects::Chunk`) contribution. A part already received at
+/// the same offset is replaced.
+#[post("/upload/chunk/part", format = "json", data = "<part_request>")]
+pub async fn put_chunk_part(
+    coordinator: CeremonyCoordinator,
+    participant: CurrentContributor,
+    part_request: ValidJson<PutChunkPartRequest>,
+) -> Result<()> {
+    let ValidJson(part_request) = part_request;
+    let data = part_request
+        .decode_data()
+        .map_err(|invalid| ResponseError::InvalidField(invalid.field, invalid.reason))?;
+    let length = data.len() as u64;
+    let round_height = {
+        let mut write_lock = coordinator.write().await;
+        write_lock.record_activity(&participant);
+        write_lock
+            .check_chunk_upload_part(&participant, &part_request.upload_id, part_request.offset, length)
+            .map_err(ResponseError::CoordinatorError)?
+            .round_height
+    };
+
+    // Only record the part once it's staged
+    let s3_ctx = rest_utils::ceremony_s3_ctx(&coordinator).await?;
+    s3_ctx
+        .upload_contribution_part(round_height, part_request.offset, data)
+        .await?;
+
+    coordinator
+        .write()
+        .await
+        .add_chunk_upload_part(&participant, &part_request.upload_id, part_request.offset, length)
+        .map_err(ResponseError::CoordinatorError)
+}
+
+/// Complete a resumable upload of a [Chunk](`crate::objects::Chunk`) contribution. The assembled contribution is
+/// published only if it matches the checksum declared when initiating the upload, otherwise the upload is discarded.
+#[post("/upload/chunk/complete", format = "json", data = "<upload_id>")]
+pub async fn complete_chunk_upload(
+    coordinator: CeremonyCoordinator,
+    participant: CurrentContributor,
+    upload_id: LazyJson<String>,
+) -> Result<()> {
+    let upload = {
+        let mut write_lock = coordinator.write().await;
+        write_lock.record_activity(&participant);
+        write_lock
+            .chunk_upload(&participant, &upload_id)
+            .map_err(ResponseError::CoordinatorError)?
+            .clone()
+    };
+
+    if !upload.is_complete() {
+        return Err(ResponseError::CoordinatorError(CoordinatorError::ChunkUploadIncomplete));
+    }
+
+    let s3_ctx = rest_utils::ceremony_s3_ctx(&coordinator).await?;
+    let contribution = s3_ctx
+        .download_contribution_parts(upload.round_height, &upload.parts)
+        .await?;
+    let checked = rest_utils::check_chunk_upload(&upload, &contribution);
+
+    if checked.is_ok() {
+        s3_ctx.upload_contribution(upload.round_height, contribution).await?;
+    }
+
+    coordinator
+        .write()
+        .await
+        .remove_chunk_upload(&participant, &upload_id)
+        .map_err(ResponseError::CoordinatorError)?;
+    s3_ctx.delete_contribution_parts(upload.round_height).await?;
+
+    checked
+}
+
 /// Notify the [Coordinator](`crate::Coordinator`) of a finished and uploaded [Contribution](`crate::objects::Contribution`). This will unlock the given [Chunk](`crate::objects::Chunk`).
 #[post(
     "/contributor/contribute_chunk",
@@ -117,17 +291,38 @@ pub async fn get_contribution_url(
     data = "<contribute_chunk_request>"
 )]
 pub async fn contribute_chunk(
-    coordinator: &State<Coordinator>,
+    coordinator: CeremonyCoordinator,
     participant: CurrentContributor,
-    contribute_chunk_request: LazyJson<PostChunkRequest>,
+    contribute_chunk_request: ValidJson<PostChunkRequest>,
 ) -> Result<()> {
     // Download contribution and its signature from S3 to local disk from the provided Urls
-    let s3_ctx = S3Ctx::new().await?;
+    let s3_ctx = rest_utils::ceremony_s3_ctx(&coordinator).await?;
     let (contribution, contribution_sig) = s3_ctx.get_contribution(contribute_chunk_request.round_height).await?;
+
+    // Scan the contribution before taking the lock, against the minimum size of the current round
+    let minimum_size = {
+        let read_lock = coordinator.read().await;
+        let round_height = read_lock
+            .current_round_height()
+            .map_err(ResponseError::CoordinatorError)?;
+        read_lock.environment().minimum_contribution_size(round_height)
+    };
+    let (contribution, anomalies, hash) = task::spawn_blocking(move || {
+        let anomalies = contribution_anomalies(&contribution, minimum_size);
+        let hash = calculate_hash(&contribution).to_vec();
+        (contribution, anomalies, hash)
+    })
+    .await?;
     let mut write_lock = (*coordinator).clone().write_owned().await;
 
     task::spawn_blocking(move || {
-        write_lock.write_contribution(contribute_chunk_request.contribution_locator, contribution)?;
+        // Reject contributions for a lock invalidated by a round reset before touching the round files
+        write_lock.check_contribution_epoch(&participant, 0)?;
+        write_lock.check_free_disk_space((contribution.len() + contribution_sig.len()) as u64)?;
+        let locator = contribute_chunk_request.contribution_locator;
+        let task = Task::new(locator.chunk_id(), locator.contribution_id());
+        write_lock.validate_contribution_sanity(&task, anomalies)?;
+        write_lock.write_contribution_with_hash(locator, contribution, hash)?;
         write_lock.write_contribution_file_signature(
             contribute_chunk_request.contribution_signature_locator,
             serde_json::from_slice(&contribution_sig)?,
@@ -138,16 +333,229 @@ pub async fn contribute_chunk(
     .map_or_else(|e| Err(ResponseError::CoordinatorError(e)), |_| Ok(()))
 }
 
+/// Add the contributions to several chunks locked by the contributor at once. The batch is atomic: if any of the
+/// contributions fails, none of them is added and the files written for the batch are removed.
+#[post("/chunks/contribute-batch", format = "json", data = "<contribute_batch_request>")]
+pub async fn contribute_chunk_batch(
+    coordinator: CeremonyCoordinator,
+    participant: CurrentContributor,
+    contribute_batch_request: ValidJson<PostChunkBatchRequest>,
+) -> Result<()> {
+    // Download the contributions and their signatures from S3 to local disk
+    let s3_ctx = rest_utils::ceremony_s3_ctx(&coordinator).await?;
+    let mut contributions = Vec::with_capacity(contribute_batch_request.contributions.len());
+    for request in contribute_batch_request.contributions.iter() {
+        let locator = &request.contribution_locator;
+        contributions.push(
+            s3_ctx
+                .get_chunk_contribution(locator.round_height(), locator.chunk_id(), locator.contribution_id())
+                .await?,
+        );
+    }
+
+    // Scan the contributions before taking the lock, against the minimum size of the current round
+    let minimum_size = {
+        let read_lock = coordinator.read().await;
+        let round_height = read_lock
+            .current_round_height()
+            .map_err(ResponseError::CoordinatorError)?;
+        read_lock.environment().minimum_contribution_size(round_height)
+    };
+    let (contributions, anomalies, hashes) = task::spawn_blocking(move || {
+        let anomalies: Vec<_> = contributions
+            .iter()
+            .map(|(contribution, _)| contribution_anomalies(contribution, minimum_size))
+            .collect();
+        let hashes: Vec<_> = contributions
+            .iter()
+            .map(|(contribution, _)| calculate_hash(contribution).to_vec())
+            .collect();
+        (contributions, anomalies, hashes)
+    })
+    .await?;
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    task::spawn_blocking(move || {
+        let chunk_ids: Vec<u64> = contribute_batch_request
+            .contributions
+            .iter()
+            .map(|request| request.contribution_locator.chunk_id())
+            .collect();
+
+        // Reject contributions for a lock invalidated by a round reset before touching the round files
+        for chunk_id in chunk_ids.iter() {
+            write_lock.check_contribution_epoch(&participant, *chunk_id)?;
+        }
+        write_lock.check_free_disk_space(
+            contributions
+                .iter()
+                .map(|(contribution, contribution_sig)| (contribution.len() + contribution_sig.len()) as u64)
+                .sum(),
+        )?;
+        // Check all the contributions and parse their signatures before writing any file
+        let mut signatures = Vec::with_capacity(contributions.len());
+        for (request, anomalies) in contribute_batch_request.contributions.iter().zip(anomalies) {
+            let locator = &request.contribution_locator;
+            let task = Task::new(locator.chunk_id(), locator.contribution_id());
+            write_lock.validate_contribution_sanity(&task, anomalies)?;
+        }
+        for (_, contribution_sig) in contributions.iter() {
+            signatures.push(serde_json::from_slice(contribution_sig)?);
+        }
+
+        let result = contribute_batch_request
+            .contributions
+            .iter()
+            .zip(contributions.into_iter().zip(hashes).zip(signatures))
+            .try_for_each(|(request, (((contribution, _), hash), signature))| {
+                write_lock.write_contribution_with_hash(request.contribution_locator, contribution, hash)?;
+                write_lock.write_contribution_file_signature(request.contribution_signature_locator, signature)
+            })
+            .and_then(|_| write_lock.try_contribute_batch(&participant, &chunk_ids));
+
+        // Don't leave the files of a failed batch behind
+        if let Err(error) = result {
+            for request in contribute_batch_request.contributions.iter() {
+                write_lock.remove_contribution(request.contribution_locator, request.contribution_signature_locator)?;
+            }
+            return Err(error);
+        }
+
+        Ok(())
+    })
+    .await?
+    .map_or_else(|e| Err(ResponseError::CoordinatorError(e)), |_| Ok(()))
+}
+
 /// Update the [Coordinator](`crate::Coordinator`) state. This endpoint is accessible only by the coordinator itself.
 #[cfg(debug_assertions)]
 #[get("/update")]
-pub async fn update_coordinator(coordinator: &State<Coordinator>, _auth: ServerAuth) -> Result<()> {
-    rest_utils::perform_coordinator_update((*coordinator).clone()).await
+pub async fn update_coordinator(coordinator: CeremonyCoordinator, _auth: ServerAuth) -> Result<()> {
+    rest_utils::perform_coordinator_update((*coordinator).clone(), None)
+        .await
+        .map(|_| ())
+}
+
+/// Reinstate a contributor dropped by mistake, adding them back to the queue. This endpoint is accessible only by the
+/// coordinator itself.
+#[cfg(debug_assertions)]
+#[post("/reinstate", format = "json", data = "<participant>")]
+pub async fn reinstate_participant(
+    coordinator: CeremonyCoordinator,
+    _auth: ServerAuth,
+    participant: Json<Participant>,
+) -> Result<()> {
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    task::spawn_blocking(move || write_lock.reinstate_dropped_participant(&participant))
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Release the lock held by a participant on a single chunk, without dropping the participant. This endpoint is
+/// accessible only by the coordinator itself.
+#[cfg(debug_assertions)]
+#[post("/contributor/<id>/release_lock/<chunk_id>")]
+pub async fn force_release_lock(
+    coordinator: CeremonyCoordinator,
+    _auth: ServerAuth,
+    id: String,
+    chunk_id: u64,
+) -> Result<()> {
+    let participant = Participant::new_contributor(&id);
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    task::spawn_blocking(move || write_lock.force_release_lock(&participant, chunk_id))
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Set the number of chunks a contributor can lock at once, at least 1 and lower than the limit of the ceremony, or
+/// clear it with `null`. This endpoint is accessible only by the coordinator itself.
+#[cfg(debug_assertions)]
+#[post("/contributor/<id>/lock_limit", format = "json", data = "<limit>")]
+pub async fn set_participant_lock_limit(
+    coordinator: CeremonyCoordinator,
+    _auth: ServerAuth,
+    id: String,
+    limit: Json<Option<usize>>,
+) -> Result<()> {
+    let participant = Participant::new_contributor(&id);
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    task::spawn_blocking(move || write_lock.set_participant_lock_limit(&participant, limit.into_inner()))
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Freeze the queue, rejecting new participants without disrupting the queued and current ones. This endpoint is
+/// accessible only by the coordinator itself.
+#[cfg(debug_assertions)]
+#[post("/queue/freeze")]
+pub async fn freeze_queue(coordinator: CeremonyCoordinator, _auth: ServerAuth) -> Result<()> {
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    task::spawn_blocking(move || write_lock.freeze_queue())
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Unfreeze the queue, accepting new participants again. This endpoint is accessible only by the coordinator itself.
+#[cfg(debug_assertions)]
+#[post("/queue/unfreeze")]
+pub async fn unfreeze_queue(coordinator: CeremonyCoordinator, _auth: ServerAuth) -> Result<()> {
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    task::spawn_blocking(move || write_lock.unfreeze_queue())
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Preview what a reset of the current round would do, without resetting it. This endpoint is accessible only by the
+/// coordinator itself.
+#[cfg(debug_assertions)]
+#[get("/reset_preview?<force_rollback>", format = "json")]
+pub async fn preview_reset_round(
+    coordinator: CeremonyCoordinator,
+    _auth: ServerAuth,
+    force_rollback: bool,
+) -> Result<Json<crate::coordinator_state::ResetPreview>> {
+    coordinator
+        .read()
+        .await
+        .state()
+        .preview_reset_current_round(force_rollback)
+        .map(Json)
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Reorder the assigned tasks of the contributor, to choose which chunk to attempt next. The tasks must be a permutation of the currently assigned ones.
+#[post("/contributor/<id>/reorder", format = "json", data = "<tasks>")]
+pub async fn reorder_tasks(
+    coordinator: CeremonyCoordinator,
+    participant: Participant,
+    id: String,
+    tasks: LazyJson<Vec<Task>>,
+) -> Result<()> {
+    // Contributors can only reorder their own tasks
+    if participant.address() != id {
+        return Err(ResponseError::UnauthorizedParticipant(
+            participant,
+            format!("/contributor/{}/reorder", id),
+            "Cannot reorder the tasks of another contributor".to_string(),
+        ));
+    }
+
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    task::spawn_blocking(move || write_lock.reorder_assigned_tasks(&participant, tasks.0))
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))
 }
 
 /// Let the [Coordinator](`crate::Coordinator`) know that the participant is still alive and participating (or waiting to participate) in the ceremony.
 #[post("/contributor/heartbeat")]
-pub async fn heartbeat(coordinator: &State<Coordinator>, participant: Participant) -> Result<()> {
+pub async fn heartbeat(coordinator: CeremonyCoordinator, participant: Participant) -> Result<()> {
     coordinator
         .write()
         .await
@@ -155,50 +563,152 @@ pub async fn heartbeat(coordinator: &State<Coordinator>, participant: Participan
         .map_err(|e| ResponseError::CoordinatorError(e))
 }
 
-/// Stop the [Coordinator](`crate::Coordinator`) and shuts the rest server down. This endpoint is accessible only by the coordinator itself.
+/// Remove the contributor from the queue on their own request. The contributor cannot join the queue again before the cooldown set in the environment.
+#[post("/contributor/leave_queue")]
+pub async fn leave_queue(coordinator: CeremonyCoordinator, participant: Participant) -> Result<()> {
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    task::spawn_blocking(move || write_lock.leave_queue(&participant))
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Restore the contributor to the current round after a disconnection, within the reconnect window set in the environment.
+#[post("/contributor/reconnect")]
+pub async fn reconnect(coordinator: CeremonyCoordinator, participant: Participant) -> Result<()> {
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    match task::spawn_blocking(move || write_lock.reconnect(&participant)).await? {
+        Ok(()) => Ok(()),
+        Err(CoordinatorError::ParticipantNotFound(participant)) => {
+            Err(ResponseError::ParticipantNotInRound(participant))
+        }
+        Err(e) => Err(ResponseError::CoordinatorError(e)),
+    }
+}
+
+/// Stop the ceremony targeted by the request, draining its [Coordinator](`crate::Coordinator`) until the chunks already
+/// locked are contributed, for up to the drain timeout. The rest server is shut down once all the ceremonies it hosts are
+/// stopped, right away if it hosts a single ceremony. This endpoint is accessible only by the coordinator itself.
 #[get("/stop")]
-pub async fn stop_coordinator(_auth: ServerAuth, shutdown: Shutdown) {
-    // Shut Rocket server down
+pub async fn stop_coordinator(
+    coordinator: CeremonyCoordinator,
+    _auth: ServerAuth,
+    shutdown: Shutdown,
+    ceremonies: Option<&State<Ceremonies>>,
+) {
+    rest_utils::drain_coordinators(vec![(*coordinator).clone()]).await;
+
+    // Shut Rocket server down once no ceremony is left running
+    if let Some(ceremonies) = ceremonies {
+        for ceremony in ceremonies.values() {
+            if !ceremony.read().await.is_draining() {
+                return;
+            }
+        }
+    }
     shutdown.notify();
 }
 
+/// Cancel the precommit of the next round, moving the staged participants back to the queue. This endpoint is accessible only by the coordinator itself.
+#[get("/cancel_precommit", format = "json")]
+pub async fn cancel_precommit(coordinator: CeremonyCoordinator, _auth: ServerAuth) -> Result<Json<Vec<Participant>>> {
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    task::spawn_blocking(move || write_lock.cancel_precommit())
+        .await?
+        .map(Json)
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
 /// Verify all the pending contributions. This endpoint is accessible only by the coordinator itself.
 #[cfg(debug_assertions)]
 #[get("/verify")]
-pub async fn verify_chunks(coordinator: &State<Coordinator>, _auth: ServerAuth) -> Result<()> {
-    rest_utils::perform_verify_chunks((*coordinator).clone(), &S3Ctx::new().await?).await
+pub async fn verify_chunks(coordinator: CeremonyCoordinator, _auth: ServerAuth) -> Result<()> {
+    let s3_ctx = rest_utils::ceremony_s3_ctx(&coordinator).await?;
+    rest_utils::perform_verify_chunks((*coordinator).clone(), &s3_ctx)
+        .await
+        .map(|_| ())
+}
+
+/// Reads the token files of the cohorts from the given serialized zip folder.
+fn read_tokens_zip(tokens: &[u8]) -> Result<Vec<HashSet<String>>> {
+    let mut zip = zip::ZipArchive::new(Cursor::new(tokens)).map_err(|e| ResponseError::IoError(e.to_string()))?;
+    let mut cohorts: HashMap<String, Vec<u8>> = HashMap::new();
+    let file_names: Vec<String> = zip.file_names().map(|name| name.to_owned()).collect();
+
+    for file in file_names {
+        let mut buffer = Vec::new();
+        zip.by_name(file.as_str())
+            .map_err(|e| ResponseError::IoError(e.to_string()))?
+            .read_to_end(&mut buffer)
+            .map_err(|e| ResponseError::IoError(e.to_string()))?;
+        cohorts.insert(file, buffer);
+    }
+
+    // NOTE: tokens currently in use are still in memory, no need to take care of those
+    Ok(CoordinatorState::load_tokens_from_bytes(&cohorts))
+}
+
+/// Serializes the token files of the cohorts into a zip folder, in the format read by [read_tokens_zip].
+fn build_tokens_zip(tokens: &[HashSet<String>]) -> Result<Vec<u8>> {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+
+    for (cohort, cohort_tokens) in tokens.iter().enumerate() {
+        let bytes = serde_json::to_vec(cohort_tokens).map_err(|e| ResponseError::IoError(e.to_string()))?;
+        zip.start_file(
+            CoordinatorState::cohort_tokens_file_name(cohort + 1),
+            zip::write::FileOptions::default(),
+        )
+        .map_err(|e| ResponseError::IoError(e.to_string()))?;
+        zip.write_all(&bytes)
+            .map_err(|e| ResponseError::IoError(e.to_string()))?;
+    }
+
+    Ok(zip
+        .finish()
+        .map_err(|e| ResponseError::IoError(e.to_string()))?
+        .into_inner())
+}
+
+/// Persists the given serialized zip folder of tokens to disk, replacing the extracted token files at the given path.
+/// New tokens MUST be written to file in case of a coordinator restart.
+fn write_tokens_zip(tokens: &[u8], tokens_path: &str) -> Result<()> {
+    let mut zip = zip::ZipArchive::new(Cursor::new(tokens)).map_err(|e| ResponseError::IoError(e.to_string()))?;
+    let mut zip_file = std::fs::File::options()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(TOKENS_ZIP_FILE)
+        .map_err(|e| ResponseError::IoError(e.to_string()))?;
+
+    zip_file
+        .write_all(tokens)
+        .map_err(|e| ResponseError::IoError(e.to_string()))?;
+
+    if let Err(e) = std::fs::remove_dir_all(tokens_path) {
+        // Log the error and continue
+        warn!("Error while removing old tokens folder: {}", e);
+    }
+    zip.extract(tokens_path)
+        .map_err(|e| ResponseError::IoError(e.to_string()))?;
+
+    Ok(())
 }
 
 /// Load new tokens to update the future cohorts. The `tokens` parameter is the serialized zip folder
 #[post("/update_cohorts", format = "json", data = "<tokens>")]
 pub async fn update_cohorts(
-    coordinator: &State<Coordinator>,
+    coordinator: CeremonyCoordinator,
     _auth: ServerAuth,
     tokens: LazyJson<Vec<u8>>,
 ) -> Result<()> {
-    let reader = Cursor::new(tokens.clone());
-    let mut zip = zip::ZipArchive::new(reader).map_err(|e| ResponseError::IoError(e.to_string()))?;
-    let mut zip_clone = zip.clone();
-
-    let new_tokens = task::spawn_blocking(move || -> Result<Vec<HashSet<String>>> {
-        let mut cohorts: HashMap<String, Vec<u8>> = HashMap::new();
-        let file_names: Vec<String> = zip_clone.file_names().map(|name| name.to_owned()).collect();
-
-        for file in file_names {
-            let mut buffer = Vec::new();
-            zip_clone
-                .by_name(file.as_str())
-                .map_err(|e| ResponseError::IoError(e.to_string()))?
-                .read_to_end(&mut buffer)
-                .map_err(|e| ResponseError::IoError(e.to_string()))?;
-            cohorts.insert(file, buffer);
-        }
-
-        // NOTE: tokens currently in use are still in memory, no need to take care of those
-        Ok(CoordinatorState::load_tokens_from_bytes(&cohorts))
-    })
-    .await
-    .unwrap()?;
+    let LazyJson(tokens) = tokens;
+    let zip_tokens = tokens.clone();
+    let new_tokens = task::spawn_blocking(move || read_tokens_zip(&zip_tokens))
+        .await
+        .unwrap()?;
 
     // Check that the new tokens for the current cohort match the old ones (to prevent inconsistencies during contributions in the current cohort)
     let read_lock = coordinator.read().await;
@@ -216,62 +726,174 @@ pub async fn update_cohorts(
         }
         _ => return Err(ResponseError::InvalidNewTokens),
     }
+    let tokens_path = read_lock.environment().tokens_path();
     drop(read_lock);
 
     // Persist new tokens to disk
-    // New tokens MUST be written to file in case of a coordinator restart
-    task::spawn_blocking(move || -> Result<()> {
-        let mut zip_file = std::fs::File::options()
-            .read(true)
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(TOKENS_ZIP_FILE)
-            .map_err(|e| ResponseError::IoError(e.to_string()))?;
+    task::spawn_blocking(move || write_tokens_zip(&tokens, &tokens_path))
+        .await
+        .unwrap()?;
 
-        zip_file
-            .write_all(&tokens)
-            .map_err(|e| ResponseError::IoError(e.to_string()))?;
+    // Update cohorts in coordinator's state
+    coordinator.write().await.update_tokens(new_tokens);
 
-        if let Err(e) = std::fs::remove_dir_all(&*TOKENS_PATH) {
-            // Log the error and continue
-            warn!("Error while removing old tokens folder: {}", e);
+    Ok(())
+}
+
+/// Atomically replace the tokens and the durations of the cohorts of the ceremony. The tokens in use and the current
+/// cohort are preserved, an inconsistent schedule is rejected without changing anything.
+#[post("/token_schedule", format = "json", data = "<schedule>")]
+pub async fn replace_token_schedule(
+    coordinator: CeremonyCoordinator,
+    _auth: Secret,
+    schedule: LazyJson<TokenScheduleRequest>,
+) -> Result<()> {
+    let LazyJson(schedule) = schedule;
+    let zip_tokens = schedule.tokens.clone();
+    let new_tokens = task::spawn_blocking(move || read_tokens_zip(&zip_tokens))
+        .await
+        .unwrap()?;
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    task::spawn_blocking(move || {
+        write_lock
+            .state()
+            .check_token_schedule(&new_tokens, &schedule.cohort_durations)
+            .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+        // Persist the new tokens to disk before saving the new schedule, restoring the old tokens if the schedule
+        // cannot be saved
+        let tokens_path = write_lock.environment().tokens_path();
+        let old_tokens = build_tokens_zip(write_lock.state().get_tokens())?;
+        write_tokens_zip(&schedule.tokens, &tokens_path)?;
+
+        if let Err(e) = write_lock.replace_token_schedule(new_tokens, schedule.cohort_durations) {
+            write_tokens_zip(&old_tokens, &tokens_path)?;
+            return Err(ResponseError::CoordinatorError(e));
         }
-        zip.extract(&*TOKENS_PATH)
-            .map_err(|e| ResponseError::IoError(e.to_string()))?;
 
         Ok(())
     })
-    .await
-    .unwrap()?;
+    .await?
+}
 
-    // Update cohorts in coordinator's state
-    coordinator.write().await.update_tokens(new_tokens);
+/// Merge the tokens of a future cohort into the ones of another cohort which is not past, removing the emptied cohort.
+/// The merged tokens replace the compressed token folder, locally and on S3 where it's downloaded from at startup. The
+/// merge is already saved when the upload to S3 fails, so the failure is only logged instead of inviting a retry which
+/// would merge other cohorts.
+#[post("/merge_cohorts", format = "json", data = "<request>")]
+pub async fn merge_cohorts(
+    coordinator: CeremonyCoordinator,
+    _auth: Secret,
+    request: LazyJson<MergeCohortsRequest>,
+) -> Result<()> {
+    let LazyJson(request) = request;
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    let zip_tokens = task::spawn_blocking(move || {
+        write_lock
+            .merge_cohorts(request.from, request.into)
+            .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+        let zip_tokens = build_tokens_zip(write_lock.state().get_tokens())?;
+        write_tokens_zip(&zip_tokens, &write_lock.environment().tokens_path())?;
+
+        Ok::<_, ResponseError>(zip_tokens)
+    })
+    .await??;
+
+    let uploaded = match rest_utils::ceremony_s3_ctx(&coordinator).await {
+        Ok(s3_ctx) => s3_ctx.upload_tokens(zip_tokens).await.map_err(ResponseError::from),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = uploaded {
+        warn!(
+            "Failed to upload the merged tokens to S3, upload {} before restarting the coordinator: {}",
+            TOKENS_ZIP_FILE, e
+        );
+    }
 
     Ok(())
 }
 
+/// Reload the ip addresses exempted from the ip ban from the file set in the NAMADA_MPC_IP_BAN_EXEMPTIONS env variable
+#[post("/update_ip_ban_exemptions")]
+pub async fn update_ip_ban_exemptions(
+    coordinator: CeremonyCoordinator,
+    _auth: Secret,
+) -> Result<Json<HashSet<IpAddr>>> {
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    task::spawn_blocking(move || write_lock.reload_ip_ban_exemptions())
+        .await?
+        .map(Json)
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Update the number of seconds to wait after aggregation before starting the next round, overriding the environment
+#[post("/queue_wait_time", format = "json", data = "<seconds>")]
+pub async fn update_queue_wait_time(coordinator: CeremonyCoordinator, _auth: Secret, seconds: Json<u64>) -> Result<()> {
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    task::spawn_blocking(move || write_lock.set_queue_wait_time(seconds.into_inner()))
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Get the timing metrics of the participant in the current round. The averages of all the participants are only
+/// reported to the coordinator verifier.
+#[get("/participant/metrics", format = "json")]
+pub async fn get_participant_metrics(
+    coordinator: CeremonyCoordinator,
+    participant: RoundParticipant,
+) -> Result<Json<ParticipantMetrics>> {
+    coordinator
+        .read()
+        .await
+        .state()
+        .participant_metrics(&participant)
+        .map(Json)
+        .ok_or_else(|| ResponseError::ParticipantNotInRound(participant.clone()))
+}
+
 /// Get the queue status of the contributor.
 #[get("/contributor/queue_status", format = "json")]
 pub async fn get_contributor_queue_status(
-    coordinator: &State<Coordinator>,
+    coordinator: CeremonyCoordinator,
     participant: Participant,
 ) -> Json<ContributorStatus> {
     let contributor = participant.clone();
 
     let read_lock = (*coordinator).clone().read_owned().await;
     // Check that the contributor is authorized to lock a chunk in the current round.
-    if task::spawn_blocking(move || read_lock.is_current_contributor(&contributor))
-        .await
-        .unwrap()
+    if let Some((retries, cohort)) = task::spawn_blocking(move || {
+        if read_lock.is_current_contributor(&contributor) {
+            let cohort = read_lock.state().participant_cohort(&contributor);
+            read_lock
+                .state()
+                .remaining_contribution_retries(&contributor)
+                .map(|retries| (retries, cohort))
+        } else {
+            None
+        }
+    })
+    .await
+    .unwrap()
     {
-        return Json(ContributorStatus::Round);
+        return Json(ContributorStatus::InRound {
+            retries,
+            cohort: cohort.map(|cohort| cohort as u64 + 1),
+        });
     }
 
     let read_lock = coordinator.read().await;
 
     if read_lock.is_queue_contributor(&participant) {
         let queue_size = read_lock.number_of_queue_contributors() as u64;
+        let cohort = read_lock
+            .state()
+            .participant_cohort(&participant)
+            .map(|cohort| cohort as u64 + 1);
 
         let queue_position = match read_lock.state().queue_contributor_info(&participant) {
             Some((_, Some(round), _, _)) => round - read_lock.state().current_round_height(),
@@ -279,7 +901,14 @@ pub async fn get_contributor_queue_status(
             None => return Json(ContributorStatus::Other),
         };
 
-        return Json(ContributorStatus::Queue(queue_position, queue_size));
+        let estimated_seconds = read_lock.state().estimated_wait_for(&participant);
+
+        return Json(ContributorStatus::InQueue {
+            position: queue_position,
+            size: queue_size,
+            cohort,
+            estimated_seconds,
+        });
     }
 
     if read_lock.is_finished_contributor(&participant) {
@@ -297,9 +926,9 @@ pub async fn get_contributor_queue_status(
 /// Write [`ContributionInfo`] to disk
 #[post("/contributor/contribution_info", format = "json", data = "<request>")]
 pub async fn post_contribution_info(
-    coordinator: &State<Coordinator>,
+    coordinator: CeremonyCoordinator,
     participant: CurrentContributor,
-    request: LazyJson<ContributionInfo>,
+    request: ValidJson<ContributionInfo>,
 ) -> Result<()> {
     // Validate info
     if request.public_key != participant.address() {
@@ -338,7 +967,7 @@ pub async fn post_contribution_info(
 /// Uploads the attestation for a contribution
 #[post("/contributor/attestation", format = "json", data = "<request>")]
 pub async fn post_attestation(
-    coordinator: &State<Coordinator>,
+    coordinator: CeremonyCoordinator,
     participant: Participant,
     request: LazyJson<(u64, String)>,
 ) -> Result<()> {
@@ -391,7 +1020,7 @@ pub async fn post_attestation(
 /// Retrieve the contributions' info. This endpoint is accessible by anyone and does not require a signed request.
 #[cfg(debug_assertions)]
 #[get("/contribution_info")]
-pub async fn get_contributions_info(coordinator: &State<Coordinator>) -> Result<Vec<u8>> {
+pub async fn get_contributions_info(coordinator: CeremonyCoordinator) -> Result<Vec<u8>> {
     let read_lock = (*coordinator).clone().read_owned().await;
     let summary = task::spawn_blocking(move || read_lock.storage().get_contributions_summary())
         .await?
@@ -400,9 +1029,86 @@ pub async fn get_contributions_info(coordinator: &State<Coordinator>) -> Result<
     Ok(summary)
 }
 
+/// Retrieve the contributions' info of a single participant. This endpoint is accessible by anyone and does not require a signed request.
+#[get("/contributor/<id>/contributions", format = "json")]
+pub async fn get_participant_contributions_info(
+    coordinator: CeremonyCoordinator,
+    id: String,
+) -> Result<Json<Vec<TrimmedContributionInfo>>> {
+    let participant = Participant::new_contributor(&id);
+    let read_lock = (*coordinator).clone().read_owned().await;
+
+    task::spawn_blocking(move || read_lock.participant_contributions_info(&participant))
+        .await?
+        .map(Json)
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Explain the assignment of the tasks of a contributor of the current round: the inputs of the formula and the
+/// resulting ordered chunks. This endpoint is accessible by anyone and does not require a signed request.
+#[get("/contributor/<id>/assignment-explain", format = "json")]
+pub async fn get_assignment_explanation(
+    coordinator: CeremonyCoordinator,
+    id: String,
+) -> Result<Json<AssignmentExplanation>> {
+    let participant = Participant::new_contributor(&id);
+
+    match coordinator.read().await.state().assignment_explanation(&participant) {
+        Ok(explanation) => Ok(Json(explanation)),
+        Err(CoordinatorError::ParticipantNotFound(participant)) => {
+            Err(ResponseError::ParticipantNotInRound(participant))
+        }
+        Err(e) => Err(ResponseError::CoordinatorError(e)),
+    }
+}
+
+/// Retrieve the contributions' info of the given round, once it is finished. This endpoint is accessible by anyone and does not require a signed request.
+#[get("/round/<round_height>/contributions", format = "json")]
+pub async fn get_round_contribution_info(
+    coordinator: CeremonyCoordinator,
+    round_height: u64,
+) -> Result<Json<Vec<TrimmedContributionInfo>>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+
+    match task::spawn_blocking(move || read_lock.round_contributions_info(round_height)).await? {
+        Ok(contributions_info) => Ok(Json(contributions_info)),
+        Err(e @ (CoordinatorError::RoundDoesNotExist | CoordinatorError::RoundNotComplete)) => {
+            Err(ResponseError::RoundContributionsUnavailable(round_height, e))
+        }
+        Err(e) => Err(ResponseError::CoordinatorError(e)),
+    }
+}
+
+/// Retrieve the Merkle root over the contributions of the given round. This endpoint is accessible by anyone and does not require a signed request.
+#[get("/round/<round_height>/root", format = "json")]
+pub async fn get_round_merkle_root(coordinator: CeremonyCoordinator, round_height: u64) -> Result<Json<MerkleHash>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+
+    task::spawn_blocking(move || read_lock.round_merkle_root(round_height))
+        .await?
+        .map(Json)
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Retrieve the Merkle proof of the contribution of a participant to the given round. This endpoint is accessible by anyone and does not require a signed request.
+#[get("/round/<round_height>/proof/<id>", format = "json")]
+pub async fn get_round_merkle_proof(
+    coordinator: CeremonyCoordinator,
+    round_height: u64,
+    id: String,
+) -> Result<Json<MerkleProof>> {
+    let participant = Participant::new_contributor(&id);
+    let read_lock = (*coordinator).clone().read_owned().await;
+
+    task::spawn_blocking(move || read_lock.round_merkle_proof(round_height, &participant))
+        .await?
+        .map(Json)
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
 /// Retrieve the coordinator.json status file
 #[get("/coordinator_status")]
-pub async fn get_coordinator_state(coordinator: &State<Coordinator>, _auth: Secret) -> Result<Vec<u8>> {
+pub async fn get_coordinator_state(coordinator: CeremonyCoordinator, _auth: Secret) -> Result<Vec<u8>> {
     let read_lock = (*coordinator).clone().read_owned().await;
     let state = task::spawn_blocking(move || read_lock.storage().get_coordinator_state())
         .await?
@@ -411,12 +1117,188 @@ pub async fn get_coordinator_state(coordinator: &State<Coordinator>, _auth: Secr
     Ok(state)
 }
 
-/// Retrieve healthcheck info. This endpoint is accessible by anyone and does not require a signed request.
-#[get("/healthcheck", format = "json")]
-pub async fn get_healthcheck() -> Result<String> {
-    let content = fs::read_to_string(HEALTH_PATH.as_str())
-        .await
-        .map_err(|e| ResponseError::IoError(e.to_string()))?;
+/// Retrieve the detailed list of the contributors in the queue, ordered by queue position
+#[get("/queue", format = "json")]
+pub async fn get_queue(coordinator: CeremonyCoordinator, _auth: Secret) -> Json<Vec<QueueEntry>> {
+    Json(coordinator.read().await.state().queue_entries())
+}
+
+/// Retrieve the tentative assignment of the queued contributors to the buckets of the next round
+#[get("/next-round/assignment", format = "json")]
+pub async fn get_next_round_assignment(
+    coordinator: CeremonyCoordinator,
+    _auth: Secret,
+) -> Json<Vec<NextRoundAssignment>> {
+    Json(coordinator.read().await.state().next_round_assignment())
+}
+
+/// Retrieve the tasks completed by the contributors of the current round which are neither pending verification nor verified
+#[get("/verifications/unassigned", format = "json")]
+pub async fn get_unassigned_completed_tasks(coordinator: CeremonyCoordinator, _auth: Secret) -> Json<Vec<Task>> {
+    Json(coordinator.read().await.state().unassigned_completed_tasks())
+}
+
+/// Retrieve the histogram of the reliability scores of the contributors in the queue
+#[get("/queue/reliability", format = "json")]
+pub async fn get_queue_reliability(coordinator: CeremonyCoordinator, _auth: Secret) -> Json<Vec<ReliabilityBucket>> {
+    Json(coordinator.read().await.state().queue_reliability_histogram())
+}
+
+/// Retrieve a structured snapshot of the coordinator state machine, including the reasons blocking the next round
+#[get("/diagnostics", format = "json")]
+pub async fn get_diagnostics(coordinator: CeremonyCoordinator, _auth: Secret) -> Json<Diagnostics> {
+    Json(coordinator.read().await.diagnostics())
+}
+
+/// Retrieve the blacklisted tokens and ip addresses with the associated participants. The tokens are reduced to their
+/// prefix if `redact` is set, to share the blacklists with less trusted operators
+#[get("/blacklists?<redact>", format = "json")]
+pub async fn get_blacklists(coordinator: CeremonyCoordinator, _auth: Secret, redact: bool) -> Json<Blacklists> {
+    Json(coordinator.read().await.state().blacklists(redact))
+}
+
+/// Retrieve the tokens currently in use with the associated participants
+#[get("/tokens_in_use", format = "json")]
+pub async fn get_tokens_in_use(coordinator: CeremonyCoordinator, _auth: Secret) -> Json<Vec<TokenInUse>> {
+    Json(coordinator.read().await.state().tokens_in_use())
+}
+
+/// Retrieve the activity of the verifiers of the coordinator in the current round
+#[get("/verifier_activity", format = "json")]
+pub async fn get_verifier_activity(coordinator: CeremonyCoordinator, _auth: Secret) -> Json<Vec<VerifierActivity>> {
+    Json(coordinator.read().await.state().verifier_activity())
+}
+
+/// Release a token stuck in use by a participant who is no longer active in the ceremony, returning the participant
+#[post("/tokens_in_use/release", format = "json", data = "<token>")]
+pub async fn release_token(
+    coordinator: CeremonyCoordinator,
+    _auth: Secret,
+    token: Json<String>,
+) -> Result<Json<Participant>> {
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    task::spawn_blocking(move || write_lock.release_token(&token))
+        .await?
+        .map(Json)
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Retrieve the timeouts currently applied by the coordinator
+#[get("/timeouts", format = "json")]
+pub async fn get_timeouts(coordinator: CeremonyCoordinator, _auth: Secret) -> Json<Timeouts> {
+    Json(coordinator.read().await.timeouts())
+}
+
+/// Retrieve the number of seconds the coordinator waits after aggregation before starting the next round
+#[get("/queue_wait_time", format = "json")]
+pub async fn get_queue_wait_time(coordinator: CeremonyCoordinator, _auth: Secret) -> Json<u64> {
+    Json(coordinator.read().await.queue_wait_time())
+}
+
+/// Retrieve the participants who finished the given round, including the rounds archived to storage
+#[get("/finished_participants/<round_height>", format = "json")]
+pub async fn get_finished_participants(
+    coordinator: CeremonyCoordinator,
+    round_height: u64,
+    _auth: Secret,
+) -> Result<Json<FinishedParticipants>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+
+    task::spawn_blocking(move || read_lock.finished_participants(round_height))
+        .await?
+        .map(Json)
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Retrieve the version of the coordinator and its uptime. This endpoint is accessible by anyone and does not require a signed request.
+#[get("/version", format = "json")]
+pub async fn get_version(coordinator: CeremonyCoordinator) -> Json<VersionInfo> {
+    Json(coordinator.read().await.version_info())
+}
+
+/// Retrieve the average number of seconds a contributor takes to compute a task in the current round, if known. This endpoint is accessible by anyone and does not require a signed request.
+#[get("/contributor/average_task_time", format = "json")]
+pub async fn get_contributor_average_task_time(coordinator: CeremonyCoordinator) -> Json<Option<u64>> {
+    Json(
+        coordinator
+            .read()
+            .await
+            .current_round_metrics()
+            .and_then(|metrics| metrics.contributor_average_per_task()),
+    )
+}
+
+/// Retrieve the metrics of the current round in the Prometheus text exposition format. This endpoint is only mounted if the NAMADA_METRICS_ENABLE env variable is set and does not require a signed request.
+#[get("/metrics")]
+pub async fn get_metrics(coordinator: CeremonyCoordinator) -> (ContentType, String) {
+    let read_lock = (*coordinator).clone().read_owned().await;
+
+    (
+        ContentType::Plain,
+        task::spawn_blocking(move || metrics::render(&read_lock)).await.unwrap(),
+    )
+}
+
+/// Stream the [StatusEvent](`crate::StatusEvent`)s of the [Coordinator](`crate::Coordinator`) as Server-Sent Events, one after every update of
+/// the coordinator and round advance. A slow client misses the oldest events instead of holding the coordinator back.
+#[get("/events")]
+pub async fn get_events(coordinator: CeremonyCoordinator, mut shutdown: Shutdown) -> EventStream![] {
+    let mut events = coordinator.read().await.subscribe_status();
+
+    EventStream! {
+        loop {
+            let event = select! {
+                event = events.recv() => match event {
+                    Ok(event) => event,
+                    Err(RecvError::Closed) => break,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("Status events client lagging behind, skipped {} events", skipped);
+                        continue;
+                    }
+                },
+                _ = &mut shutdown => break,
+            };
+
+            yield Event::json(&event);
+        }
+    }
+}
+
+/// Retrieve a public summary of the progress of the ceremony. This endpoint is accessible by anyone and does not require a signed request.
+#[get("/transparency", format = "json")]
+pub async fn get_transparency(coordinator: CeremonyCoordinator) -> Result<Json<TransparencyInfo>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+
+    task::spawn_blocking(move || read_lock.transparency_info())
+        .await?
+        .map(Json)
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Retrieve healthcheck info, checking the integrity of the storage of the coordinator. Responds with 503 if any of the
+/// checks fails. With `shallow=true`, only returns the content of the file at HEALTH_PATH, a cheap liveness check for
+/// the load balancers. This endpoint is accessible by anyone and does not require a signed request.
+#[get("/healthcheck?<shallow>", format = "json")]
+pub async fn get_healthcheck(
+    coordinator: CeremonyCoordinator,
+    shallow: Option<bool>,
+) -> Result<Either<String, (Status, Json<HealthReport>)>> {
+    if shallow.unwrap_or_default() {
+        let content = fs::read_to_string(HEALTH_PATH.as_str())
+            .await
+            .map_err(|e| ResponseError::IoError(e.to_string()))?;
+
+        return Ok(Either::Left(content));
+    }
+
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let report = task::spawn_blocking(move || read_lock.health_report()).await?;
+    let status = if report.healthy {
+        Status::Ok
+    } else {
+        Status::ServiceUnavailable
+    };
 
-    Ok(content)
+    Ok(Either::Right((status, Json(report))))
 }
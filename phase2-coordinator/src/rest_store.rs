@@ -0,0 +1,305 @@
+// Bounded store of the short-lived entries of the rest API
+
+use crate::TimeSource;
+
+use lazy_static::lazy_static;
+use std::{
+    borrow::Borrow,
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+    sync::Arc,
+};
+use time::{Duration, OffsetDateTime};
+
+/// The env variable holding the maximum number of entries of each [BoundedStore].
+pub const STORE_CAPACITY_ENV: &str = "NAMADA_MPC_STORE_CAPACITY";
+/// The default maximum number of entries of each [BoundedStore].
+pub const DEFAULT_STORE_CAPACITY: usize = 100_000;
+
+lazy_static! {
+    static ref STORE_CAPACITY: usize = match std::env::var(STORE_CAPACITY_ENV) {
+        Ok(capacity) => capacity
+            .parse::<usize>()
+            .ok()
+            .filter(|capacity| *capacity > 0)
+            .expect("NAMADA_MPC_STORE_CAPACITY must be a positive integer"),
+        Err(_) => DEFAULT_STORE_CAPACITY,
+    };
+}
+
+struct Entry<V> {
+    value: V,
+    expires_at: OffsetDateTime,
+    /// The sequence number of the insertion, orders the entries expiring at the same time.
+    inserted: u64,
+    /// The sequence number of the last use, orders the entries by recency.
+    used: u64,
+}
+
+/// A store of short-lived entries, such as the nonces and challenges handed
+/// out to the participants, which cannot grow past a fixed capacity.
+///
+/// Entries expire after a fixed time to live. Once the capacity is reached,
+/// the expired entries are evicted first and only then the least recently
+/// used one, so that a flood of new entries cannot push out the valid recent
+/// ones before the stale ones.
+pub struct BoundedStore<K, V> {
+    entries: HashMap<K, Entry<V>>,
+    /// The keys ordered by expiration time.
+    by_expiry: BTreeMap<(OffsetDateTime, u64), K>,
+    /// The keys ordered by last use.
+    by_use: BTreeMap<u64, K>,
+    capacity: usize,
+    ttl: Duration,
+    sequence: u64,
+    time: Arc<dyn TimeSource>,
+}
+
+impl<K: Clone + Eq + Hash, V> BoundedStore<K, V> {
+    /// Creates a store with the given time to live, whose capacity is read
+    /// from the NAMADA_MPC_STORE_CAPACITY env variable.
+    pub fn from_env(ttl: Duration, time: Arc<dyn TimeSource>) -> Self {
+        Self::new(*STORE_CAPACITY, ttl, time)
+    }
+
+    pub fn new(capacity: usize, ttl: Duration, time: Arc<dyn TimeSource>) -> Self {
+        assert!(capacity > 0, "The capacity of the store must be positive");
+
+        Self {
+            entries: HashMap::new(),
+            by_expiry: BTreeMap::new(),
+            by_use: BTreeMap::new(),
+            capacity,
+            ttl,
+            sequence: 0,
+            time,
+        }
+    }
+
+    /// The number of entries in the store, including the expired ones which
+    /// weren't evicted yet.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn next_sequence(&mut self) -> u64 {
+        self.sequence += 1;
+        self.sequence
+    }
+
+    /// Inserts an entry expiring after the time to live of the store,
+    /// replacing any previous entry of the same key. Returns the key of
+    /// the entry evicted to make room for the new one, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<K> {
+        self.remove(&key);
+
+        let now = self.time.now_utc();
+        self.evict_expired(now);
+        let evicted = if self.entries.len() >= self.capacity {
+            self.evict_least_recently_used()
+        } else {
+            None
+        };
+
+        let sequence = self.next_sequence();
+        let expires_at = now + self.ttl;
+        self.by_expiry.insert((expires_at, sequence), key.clone());
+        self.by_use.insert(sequence, key.clone());
+        self.entries.insert(key, Entry {
+            value,
+            expires_at,
+            inserted: sequence,
+            used: sequence,
+        });
+
+        evicted
+    }
+
+    /// Returns the value of the given entry, unless expired, and marks it
+    /// as recently used. The time to live of the entry is left unchanged.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let now = self.time.now_utc();
+        if self.entries.get(key)?.expires_at <= now {
+            self.remove(key);
+            return None;
+        }
+
+        let sequence = self.next_sequence();
+        let entry = self.entries.get_mut(key)?;
+        let stored_key = self
+            .by_use
+            .remove(&entry.used)
+            .expect("The key of an entry must be in the recency index");
+        entry.used = sequence;
+        self.by_use.insert(sequence, stored_key);
+
+        Some(&entry.value)
+    }
+
+    /// Removes the given entry and returns its value, unless expired. Used
+    /// to consume single-use entries like nonces.
+    pub fn take<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let now = self.time.now_utc();
+        self.remove(key)
+            .filter(|(_, expires_at)| *expires_at > now)
+            .map(|(value, _)| value)
+    }
+
+    fn remove<Q>(&mut self, key: &Q) -> Option<(V, OffsetDateTime)>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let entry = self.entries.remove(key)?;
+        self.by_expiry.remove(&(entry.expires_at, entry.inserted));
+        self.by_use.remove(&entry.used);
+
+        Some((entry.value, entry.expires_at))
+    }
+
+    /// Evicts the entries expired at the given time, returns their number.
+    fn evict_expired(&mut self, now: OffsetDateTime) -> usize {
+        let mut evicted = 0;
+        loop {
+            let key = match self.by_expiry.iter().next() {
+                Some((&(expires_at, _), key)) if expires_at <= now => key.clone(),
+                _ => break,
+            };
+            self.remove(&key);
+            evicted += 1;
+        }
+
+        evicted
+    }
+
+    fn evict_least_recently_used(&mut self) -> Option<K> {
+        let key = self.by_use.values().next()?.clone();
+        self.remove(&key);
+
+        Some(key)
+    }
+
+    /// Evicts all the expired entries, returns their number.
+    pub fn purge_expired(&mut self) -> usize {
+        let now = self.time.now_utc();
+        self.evict_expired(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MockTimeSource;
+
+    fn store(capacity: usize) -> (BoundedStore<String, u64>, Arc<MockTimeSource>) {
+        let time = Arc::new(MockTimeSource::new(OffsetDateTime::now_utc()));
+        (BoundedStore::new(capacity, Duration::seconds(60), time.clone()), time)
+    }
+
+    #[test]
+    fn test_ttl_expiry() {
+        let (mut store, time) = store(10);
+
+        store.insert(String::from("first"), 1);
+        time.update(|t| t + Duration::seconds(30));
+        store.insert(String::from("second"), 2);
+
+        // Using an entry doesn't extend its time to live.
+        time.update(|t| t + Duration::seconds(29));
+        assert_eq!(Some(&1), store.get("first"));
+        time.update(|t| t + Duration::seconds(1));
+        assert_eq!(None, store.get("first"));
+        assert_eq!(Some(&2), store.get("second"));
+        assert_eq!(1, store.len());
+
+        // Expired entries cannot be consumed.
+        time.update(|t| t + Duration::seconds(30));
+        assert_eq!(None, store.take("second"));
+        assert!(store.is_empty());
+
+        store.insert(String::from("third"), 3);
+        assert_eq!(Some(3), store.take("third"));
+        assert_eq!(None, store.take("third"));
+
+        store.insert(String::from("fourth"), 4);
+        time.update(|t| t + Duration::seconds(60));
+        assert_eq!(1, store.purge_expired());
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let (mut store, _time) = store(3);
+
+        for (i, key) in ["a", "b", "c"].iter().enumerate() {
+            assert_eq!(None, store.insert(key.to_string(), i as u64));
+        }
+
+        // The least recently used entry is evicted once the store is full.
+        assert_eq!(Some(&0), store.get("a"));
+        assert_eq!(Some(String::from("b")), store.insert(String::from("d"), 3));
+        assert_eq!(None, store.get("b"));
+        assert_eq!(3, store.len());
+
+        // Replacing an entry doesn't evict any other.
+        assert_eq!(None, store.insert(String::from("c"), 4));
+        assert_eq!(Some(&4), store.get("c"));
+        assert_eq!(Some(String::from("a")), store.insert(String::from("e"), 5));
+    }
+
+    #[test]
+    fn test_expired_evicted_before_recent() {
+        let (mut store, time) = store(100);
+
+        // Stale entries fill half of the store.
+        for i in 0..50 {
+            store.insert(format!("stale_{}", i), i);
+        }
+        time.update(|t| t + Duration::seconds(45));
+        let recent: Vec<String> = (0..10).map(|i| format!("recent_{}", i)).collect();
+        for key in &recent {
+            store.insert(key.clone(), 0);
+        }
+        time.update(|t| t + Duration::seconds(15));
+
+        // A flood of new entries evicts the expired ones first.
+        for i in 0..40 {
+            assert_eq!(None, store.insert(format!("flood_{}", i), i));
+        }
+        for key in &recent {
+            assert_eq!(Some(&0), store.get(key.as_str()));
+        }
+        assert_eq!(50, store.len());
+
+        // Under sustained load the store stays within its capacity, and the
+        // recently used entries outlive the others.
+        for i in 40..1000 {
+            store.insert(format!("flood_{}", i), i);
+            if i % 10 == 0 {
+                for key in &recent {
+                    assert!(store.get(key.as_str()).is_some());
+                }
+            }
+        }
+        assert_eq!(store.capacity(), store.len());
+        for key in &recent {
+            assert_eq!(Some(&0), store.get(key.as_str()));
+        }
+    }
+}
@@ -2,25 +2,26 @@
 
 use crate::{
     authentication::{Production, Signature},
-    coordinator_state::TOKEN_BLACKLIST,
-    objects::Task,
+    coordinator_state::{ChunkUpload, Diagnostics, TOKEN_BLACKLIST},
+    objects::{task::check_contribution_id, ContributionInfo, Task},
+    rest_store::BoundedStore,
     s3::{S3Ctx, S3Error},
     storage::{ContributionLocator, ContributionSignatureLocator},
-    CoordinatorError, Participant,
+    CoordinatorError, Participant, SystemTimeSource, TimeSource,
 };
 
 pub use crate::{coordinator_state::TOKENS_PATH, s3::TOKENS_ZIP_FILE};
 use blake2::Digest;
 use rocket::{
     catch,
-    data::FromData,
+    data::{ByteUnit, Data, FromData, Limits},
     error,
-    http::{ContentType, Status},
+    fairing::{Fairing, Info, Kind},
+    http::{ContentType, HeaderMap, Status},
     request::{FromRequest, Outcome, Request},
     response::{Responder, Response},
     serde::{Deserialize, DeserializeOwned, Serialize},
     tokio::{sync::RwLock, task},
-    State,
 };
 
 use anyhow::anyhow;
@@ -29,15 +30,43 @@ use sha2::Sha256;
 use subtle::ConstantTimeEq;
 
 use lazy_static::lazy_static;
-use std::{borrow::Cow, convert::TryFrom, io::Cursor, net::IpAddr, ops::Deref, sync::Arc, time::Duration};
+use rand::Rng;
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    io::Cursor,
+    net::IpAddr,
+    ops::Deref,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 use thiserror::Error;
-use tracing::warn;
+use time::OffsetDateTime;
+use tracing::{debug, info, warn};
 
 #[cfg(debug_assertions)]
 pub const UPDATE_TIME: Duration = Duration::from_secs(5);
 #[cfg(not(debug_assertions))]
 pub const UPDATE_TIME: Duration = Duration::from_secs(60);
 
+/// The env variable holding the number of seconds between two updates of the coordinator, [UPDATE_TIME] if unset.
+pub const UPDATE_INTERVAL_ENV: &str = "NAMADA_UPDATE_INTERVAL_SECONDS";
+/// The env variable holding the number of seconds the interval between two updates backs off to while they find no
+/// work. The interval doesn't back off if unset.
+pub const UPDATE_INTERVAL_MAX_ENV: &str = "NAMADA_UPDATE_INTERVAL_MAX_SECONDS";
+/// The env variable holding the number of seconds between two verifications of the contributions, [UPDATE_TIME] if
+/// unset.
+pub const VERIFY_INTERVAL_ENV: &str = "NAMADA_VERIFY_INTERVAL_SECONDS";
+/// The env variable holding the number of seconds the interval between two verifications backs off to while they
+/// find no contribution. The interval doesn't back off if unset.
+pub const VERIFY_INTERVAL_MAX_ENV: &str = "NAMADA_VERIFY_INTERVAL_MAX_SECONDS";
+/// The env variable holding the maximum number of seconds to wait, before shutting down, for the chunks locked by the
+/// contributors to be contributed. The shutdown doesn't wait if unset.
+pub const DRAIN_TIMEOUT_ENV: &str = "NAMADA_DRAIN_TIMEOUT";
+/// The interval between two checks of the chunks still locked while draining.
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 pub const UNKNOWN: &str = "Unknown";
 pub const TOKEN_REGEX: &str = r"^[A-HJ-NP-Za-km-z1-9]{115}$";
 
@@ -47,6 +76,27 @@ pub const PUBKEY_HEADER: &str = "ATS-Pubkey";
 pub const SIGNATURE_HEADER: &str = "ATS-Signature";
 pub const CONTENT_LENGTH_HEADER: &str = "Content-Length";
 pub const ACCESS_SECRET_HEADER: &str = "Access-Secret";
+pub const JOIN_SIGNATURE_HEADER: &str = "ATS-Join-Signature";
+
+// Body size limits
+/// The env variable holding the body size limit of the JSON endpoints.
+pub const JSON_LIMIT_ENV: &str = "NAMADA_MPC_JSON_LIMIT";
+/// The env variable holding the body size limit of the endpoints receiving uploads.
+pub const UPLOAD_LIMIT_ENV: &str = "NAMADA_MPC_UPLOAD_LIMIT";
+/// The endpoints receiving uploads, limited by [UPLOAD_LIMIT_ENV] instead of [JSON_LIMIT_ENV].
+const UPLOAD_ROUTES: [&str; 3] = ["update_cohorts", "put_chunk_part", "replace_token_schedule"];
+
+/// The env variable holding the number of requests per minute an address can make to join the queue, unlimited if
+/// unset or 0.
+pub const JOIN_RATE_ENV: &str = "NAMADA_JOIN_RATE_PER_MINUTE";
+
+/// Headers whose value must never end up in the logs
+const REDACTED_HEADERS: [&str; 4] = [ACCESS_SECRET_HEADER, SIGNATURE_HEADER, "Authorization", "Cookie"];
+const REDACTED: &str = "<redacted>";
+/// The env variable setting the fraction of the requests logged by the [`RequestSampler`]
+pub const LOG_SAMPLE_RATE_ENV: &str = "NAMADA_MPC_LOG_SAMPLE_RATE";
+/// The maximum size of the bodies logged by the [`RequestSampler`], only the size of the bigger ones is logged
+const MAX_SAMPLED_BODY_SIZE: usize = 4096;
 
 lazy_static! {
     pub(crate) static ref HEALTH_PATH: String = match std::env::var("HEALTH_PATH") {
@@ -55,10 +105,67 @@ lazy_static! {
     };
     pub(crate) static ref ACCESS_SECRET: String =
         std::env::var("ACCESS_SECRET").expect("Missing required env ACCESS_SECRET");
+    static ref JOIN_RATE_PER_MINUTE: u32 = match std::env::var(JOIN_RATE_ENV) {
+        Ok(rate) => rate
+            .parse::<u32>()
+            .expect("NAMADA_JOIN_RATE_PER_MINUTE must be a non-negative integer"),
+        Err(_) => 0,
+    };
 }
 
 pub(crate) type Coordinator = Arc<RwLock<crate::Coordinator>>;
 
+/// The coordinators of the ceremonies hosted by the server, keyed by ceremony name.
+pub type Ceremonies = HashMap<String, Arc<RwLock<crate::Coordinator>>>;
+
+/// The prefix of the mount point of the routes of a named ceremony, followed by the name of the ceremony.
+pub const CEREMONY_MOUNT_PREFIX: &str = "/ceremony/";
+
+/// Returns the mount point of the routes of the given ceremony.
+pub fn ceremony_mount_point(name: &str) -> String {
+    format!("{}{}", CEREMONY_MOUNT_PREFIX, name)
+}
+
+/// The [Coordinator](`crate::Coordinator`) of the ceremony targeted by the request. Routes mounted at
+/// `/ceremony/<name>` target the named ceremony among the managed [Ceremonies], the other routes target the managed
+/// [Coordinator](`crate::Coordinator`).
+pub struct CeremonyCoordinator(Coordinator);
+
+impl Deref for CeremonyCoordinator {
+    type Target = Coordinator;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for CeremonyCoordinator {
+    type Error = ResponseError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let ceremony = request
+            .route()
+            .and_then(|route| route.uri.base().strip_prefix(CEREMONY_MOUNT_PREFIX));
+
+        let coordinator = match ceremony {
+            Some(name) => request
+                .rocket()
+                .state::<Ceremonies>()
+                .and_then(|ceremonies| ceremonies.get(name)),
+            None => request.rocket().state::<Coordinator>(),
+        };
+
+        match coordinator {
+            Some(coordinator) => Outcome::Success(Self(coordinator.clone())),
+            None => Outcome::Failure((
+                Status::NotFound,
+                ResponseError::UnknownCeremony(ceremony.unwrap_or_default().to_string()),
+            )),
+        }
+    }
+}
+
 /// Server errors. Also includes errors generated by the managed [Coordinator](`crate::Coordinator`).
 #[derive(Error, Debug)]
 pub enum ResponseError {
@@ -68,6 +175,8 @@ pub enum ResponseError {
     CoordinatorError(CoordinatorError),
     #[error("Contribution info is not valid: {0}")]
     InvalidContributionInfo(String),
+    #[error("Invalid request body: {0} {1}")]
+    InvalidField(String, String),
     #[error("The required access secret is either missing or invalid")]
     InvalidSecret,
     #[error("Header {0} is badly formatted")]
@@ -88,6 +197,12 @@ pub enum ResponseError {
     MissingSigningKey,
     #[error("Couldn't parse string to int: {0}")]
     ParseError(#[from] std::num::ParseIntError),
+    #[error("The request body exceeds the limit of {0}")]
+    PayloadTooLarge(ByteUnit),
+    #[error("The participant {0} is not in the current round")]
+    ParticipantNotInRound(Participant),
+    #[error("The contributions of round {0} are not available: {1}")]
+    RoundContributionsUnavailable(u64, CoordinatorError),
     #[error("Thread panicked: {0}")]
     RuntimeError(#[from] task::JoinError),
     #[error("Error with S3: {0}")]
@@ -98,10 +213,14 @@ pub enum ResponseError {
     ShutdownError(String),
     #[error("The provided token is currently being used in the ceremony")]
     TokenAlreadyInUse,
+    #[error("Too many requests, retry later")]
+    TooManyRequests,
     #[error("The provided token has already been used in the ceremony")]
     BlacklistedToken,
     #[error("The participant {0} is not allowed to access the endpoint {1} because of: {2}")]
     UnauthorizedParticipant(Participant, String, String),
+    #[error("No ceremony named \"{0}\" is hosted by the server")]
+    UnknownCeremony(String),
     #[error("Could not find contributor with public key {0}")]
     UnknownContributor(String),
     #[error("Could not find the provided Task {0} in coordinator state")]
@@ -118,6 +237,26 @@ impl<'r> Responder<'r, 'static> for ResponseError {
         let response_code = match self {
             ResponseError::BlacklistedToken => Status::Unauthorized,
             ResponseError::CeremonyIsOver => Status::Unauthorized,
+            ResponseError::CoordinatorError(CoordinatorError::CeremonyPaused { .. }) => Status::ServiceUnavailable,
+            ResponseError::CoordinatorError(CoordinatorError::ChunkUploadIncomplete) => Status::Conflict,
+            ResponseError::CoordinatorError(CoordinatorError::ChunkUploadMissing) => Status::NotFound,
+            ResponseError::CoordinatorError(CoordinatorError::ChunkUploadPartInvalid) => Status::UnprocessableEntity,
+            ResponseError::CoordinatorError(CoordinatorError::CoordinatorDraining) => Status::ServiceUnavailable,
+            ResponseError::CoordinatorError(CoordinatorError::QueueFrozen) => Status::ServiceUnavailable,
+            ResponseError::CoordinatorError(CoordinatorError::CohortMergeInvalid(_)) => Status::Conflict,
+            ResponseError::CoordinatorError(CoordinatorError::LockLimitInvalid) => Status::UnprocessableEntity,
+            ResponseError::CoordinatorError(CoordinatorError::StorageSpaceInsufficient { .. }) => {
+                Status::InsufficientStorage
+            }
+            ResponseError::CoordinatorError(CoordinatorError::TokenForFutureCohort { .. }) => Status::Forbidden,
+            ResponseError::CoordinatorError(CoordinatorError::TokenNotInUse) => Status::NotFound,
+            ResponseError::CoordinatorError(CoordinatorError::TokenScheduleDuplicateToken(_)) => {
+                Status::UnprocessableEntity
+            }
+            ResponseError::CoordinatorError(CoordinatorError::TokenScheduleInconsistent(_)) => Status::Conflict,
+            ResponseError::CoordinatorError(CoordinatorError::TokenScheduleInvalid) => Status::UnprocessableEntity,
+            ResponseError::CoordinatorError(CoordinatorError::TokenStillInUse(_)) => Status::Conflict,
+            ResponseError::InvalidField(_, _) => Status::UnprocessableEntity,
             ResponseError::InvalidHeader(_) => Status::BadRequest,
             ResponseError::InvalidSecret => Status::Unauthorized,
             ResponseError::InvalidSignature => Status::BadRequest,
@@ -126,9 +265,14 @@ impl<'r> Responder<'r, 'static> for ResponseError {
             ResponseError::MissingRequiredHeader(h) if h == CONTENT_LENGTH_HEADER => Status::LengthRequired,
             ResponseError::MissingRequiredHeader(_) => Status::BadRequest,
             ResponseError::MissingSigningKey => Status::BadRequest,
+            ResponseError::ParticipantNotInRound(_) => Status::NotFound,
+            ResponseError::PayloadTooLarge(_) => Status::PayloadTooLarge,
+            ResponseError::RoundContributionsUnavailable(_, _) => Status::NotFound,
             ResponseError::SerdeError(_) => Status::UnprocessableEntity,
             ResponseError::TokenAlreadyInUse => Status::Unauthorized,
+            ResponseError::TooManyRequests => Status::TooManyRequests,
             ResponseError::UnauthorizedParticipant(_, _, _) => Status::Unauthorized,
+            ResponseError::UnknownCeremony(_) => Status::NotFound,
             ResponseError::WrongDigestEncoding(_) => Status::BadRequest,
             _ => Status::InternalServerError,
         };
@@ -166,6 +310,10 @@ pub fn missing_required_header(req: &Request) -> ResponseError {
 
 #[catch(455)]
 pub fn unprocessable_entity(req: &Request) -> ResponseError {
+    if let Some(invalid) = req.local_cache(|| Option::<InvalidField>::None) {
+        return ResponseError::InvalidField(invalid.field.clone(), invalid.reason.clone());
+    }
+
     let message = req.local_cache(|| UNKNOWN.to_string());
     ResponseError::SerdeError(message.to_string())
 }
@@ -182,6 +330,23 @@ pub fn invalid_header(req: &Request) -> ResponseError {
     ResponseError::InvalidHeader(header)
 }
 
+#[catch(458)]
+pub fn participant_not_in_round(req: &Request) -> ResponseError {
+    let participant = req.local_cache(|| Participant::new_contributor(UNKNOWN));
+    ResponseError::ParticipantNotInRound(participant.clone())
+}
+
+#[catch(459)]
+pub fn payload_too_large(req: &Request) -> ResponseError {
+    let limit = req.local_cache(|| Limits::JSON);
+    ResponseError::PayloadTooLarge(*limit)
+}
+
+#[catch(429)]
+pub fn too_many_requests() -> ResponseError {
+    ResponseError::TooManyRequests
+}
+
 #[catch(512)]
 pub fn io_error(req: &Request) -> ResponseError {
     let message = req.local_cache(|| UNKNOWN.to_string());
@@ -326,6 +491,8 @@ impl<'r> FromRequest<'r> for Participant {
 pub struct NewParticipant {
     pub participant: Participant,
     pub ip_address: Option<IpAddr>,
+    /// The signature of the token by the participant, if provided
+    pub join_signature: Option<String>,
 }
 
 #[rocket::async_trait]
@@ -333,6 +500,14 @@ impl<'r> FromRequest<'r> for NewParticipant {
     type Error = ResponseError;
 
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        // Check the rate of the requests of the address before verifying the signature
+        if let (Some(limiter), Some(ip)) = (request.rocket().state::<JoinRateLimiter>(), request.client_ip()) {
+            if !limiter.check(ip) {
+                warn!("Too many requests to join the queue from {}", ip);
+                return Outcome::Failure((Status::TooManyRequests, ResponseError::TooManyRequests));
+            }
+        }
+
         let pubkey = match request.verify_signature() {
             Ok(h) => h,
             Err(e) => return Outcome::Failure((Status::new(452), e)),
@@ -340,7 +515,7 @@ impl<'r> FromRequest<'r> for NewParticipant {
 
         // Check that the signature comes from an unknown contributor
         let coordinator = request
-            .guard::<&State<Coordinator>>()
+            .guard::<CeremonyCoordinator>()
             .await
             .succeeded()
             .expect("Managed state should always be retrievable");
@@ -350,9 +525,13 @@ impl<'r> FromRequest<'r> for NewParticipant {
         if let Err(e) = coordinator
             .read()
             .await
-            .state()
             .add_to_queue_checks(&participant, ip_address.as_ref())
         {
+            // A frozen queue is a temporary condition, not a rejection of the participant
+            if let CoordinatorError::QueueFrozen = e {
+                return Outcome::Failure((Status::ServiceUnavailable, ResponseError::CoordinatorError(e)));
+            }
+
             // Cache error data for the error catcher
             request.local_cache(|| participant.clone());
             request.local_cache(|| (request.uri().to_string(), e.to_string()));
@@ -363,9 +542,12 @@ impl<'r> FromRequest<'r> for NewParticipant {
             ));
         }
 
+        let join_signature = request.headers().get_one(JOIN_SIGNATURE_HEADER).map(String::from);
+
         Outcome::Success(Self {
             participant,
             ip_address,
+            join_signature,
         })
     }
 }
@@ -393,7 +575,7 @@ impl<'r> FromRequest<'r> for CurrentContributor {
 
         // Check that the signature comes from the current contributor by matching the public key
         let coordinator = request
-            .guard::<&State<Coordinator>>()
+            .guard::<CeremonyCoordinator>()
             .await
             .succeeded()
             .expect("Managed state should always be retrievable");
@@ -426,6 +608,54 @@ impl<'r> FromRequest<'r> for CurrentContributor {
     }
 }
 
+/// Implements the signature verification on the incoming request of a contributor of the current round, or of a
+/// verifier of the coordinator, via [`FromRequest`].
+pub struct RoundParticipant(Participant);
+
+impl Deref for RoundParticipant {
+    type Target = Participant;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RoundParticipant {
+    type Error = ResponseError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let pubkey = match request.verify_signature() {
+            Ok(h) => h,
+            Err(e) => return Outcome::Failure((Status::new(452), e)),
+        };
+
+        let coordinator = request
+            .guard::<CeremonyCoordinator>()
+            .await
+            .succeeded()
+            .expect("Managed state should always be retrievable");
+
+        let read_lock = coordinator.read().await;
+        let verifier = Participant::new_verifier(pubkey);
+        if read_lock.environment().is_coordinator_verifier(&verifier) {
+            return Outcome::Success(Self(verifier));
+        }
+
+        let participant = Participant::new_contributor(pubkey);
+        if !read_lock.is_current_contributor(&participant) {
+            drop(read_lock);
+
+            // Cache error data for the error catcher
+            request.local_cache(|| participant.clone());
+
+            return Outcome::Failure((Status::new(458), ResponseError::ParticipantNotInRound(participant)));
+        }
+
+        Outcome::Success(Self(participant))
+    }
+}
+
 /// Implements the secret token verification on the incoming server request via [`FromRequest`]. Used to restrict access to endpoints only when headers contain the valid secret.
 /// Can be used as an alternative to [`ServerAuth`] when the body of the request carries no data (and thus doesn't need a signature on that)
 pub struct Secret;
@@ -457,7 +687,7 @@ impl<'r> FromRequest<'r> for ServerAuth {
 
         // Check that the signature comes from the coordinator by matching the default verifier key
         let coordinator = request
-            .guard::<&State<Coordinator>>()
+            .guard::<CeremonyCoordinator>()
             .await
             .succeeded()
             .expect("Managed state should always be retrievable");
@@ -543,6 +773,14 @@ impl<'r, T: DeserializeOwned> FromData<'r> for LazyJson<T> {
             }
         };
 
+        let limit = body_limit(req);
+        if expected_content.len as u64 > limit.as_u64() {
+            // Cache error data for the error catcher
+            req.local_cache(|| limit);
+
+            return rocket::data::Outcome::Failure((Status::new(459), ResponseError::PayloadTooLarge(limit)));
+        }
+
         let body = match data.open(expected_content.len.into()).into_bytes().await {
             Ok(bytes) => bytes.into_inner(),
             Err(e) => {
@@ -553,9 +791,7 @@ impl<'r, T: DeserializeOwned> FromData<'r> for LazyJson<T> {
             }
         };
 
-        let mut hasher = Sha256::new();
-        hasher.update(&body);
-        let digest = base64::encode(hasher.finalize());
+        let digest = sha256_digest(&body);
         if digest != expected_content.digest {
             // Cache error data for the error catcher
             req.local_cache(|| (expected_digest.to_owned(), expected_content.digest.to_string()));
@@ -578,7 +814,132 @@ impl<'r, T: DeserializeOwned> FromData<'r> for LazyJson<T> {
     }
 }
 
+/// Returns the body size limit of the route handling the request, `json/<route name>` if set or `json` otherwise.
+fn body_limit(req: &Request<'_>) -> ByteUnit {
+    let route = req.route().and_then(|route| route.name.as_deref()).unwrap_or_default();
+
+    req.limits().find(["json", route]).unwrap_or(Limits::JSON)
+}
+
+/// Overrides the given body size limits with the ones set in the [JSON_LIMIT_ENV] and [UPLOAD_LIMIT_ENV] env variables.
+///
+/// # Panics
+/// If a limit in the env is not a valid size, e.g. `1 MiB`.
+pub fn body_limits(mut limits: Limits) -> Limits {
+    let env_limit = |env: &str| {
+        std::env::var(env).ok().map(|limit| {
+            limit
+                .parse::<ByteUnit>()
+                .unwrap_or_else(|_| panic!("{} must be a size, e.g. 1 MiB", env))
+        })
+    };
+
+    if let Some(limit) = env_limit(JSON_LIMIT_ENV) {
+        limits = limits.limit("json", limit);
+    }
+    if let Some(limit) = env_limit(UPLOAD_LIMIT_ENV) {
+        for route in UPLOAD_ROUTES.iter() {
+            limits = limits.limit(format!("json/{}", route), limit);
+        }
+    }
+
+    limits
+}
+
+/// A field of a request body which failed the validation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidField {
+    /// The path of the field in the body.
+    pub field: String,
+    /// Why the field is invalid.
+    pub reason: String,
+}
+
+impl InvalidField {
+    fn new(field: &str, reason: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Field-level validation of a deserialized request body.
+pub trait Validate {
+    /// Returns the first invalid field of the body, if any.
+    fn validate(&self) -> std::result::Result<(), InvalidField>;
+}
+
+/// Type to handle lazy deserialization of json encoded inputs which are then validated field by field.
+pub struct ValidJson<T>(pub T);
+
+impl<T> Deref for ValidJson<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[rocket::async_trait]
+impl<'r, T: DeserializeOwned + Validate> FromData<'r> for ValidJson<T> {
+    type Error = ResponseError;
+
+    async fn from_data(req: &'r Request<'_>, data: rocket::data::Data<'r>) -> rocket::data::Outcome<'r, Self> {
+        let obj = match LazyJson::<T>::from_data(req, data).await {
+            rocket::data::Outcome::Success(LazyJson(obj)) => obj,
+            rocket::data::Outcome::Failure(e) => return rocket::data::Outcome::Failure(e),
+            rocket::data::Outcome::Forward(data) => return rocket::data::Outcome::Forward(data),
+        };
+
+        match obj.validate() {
+            Ok(()) => rocket::data::Outcome::Success(ValidJson(obj)),
+            Err(invalid) => {
+                // Cache error data for the error catcher
+                req.local_cache(|| Some(invalid.clone()));
+                rocket::data::Outcome::Failure((
+                    Status::new(455),
+                    ResponseError::InvalidField(invalid.field, invalid.reason),
+                ))
+            }
+        }
+    }
+}
+
+/// Checks that the given string is the hex encoding of a 64 bytes hash.
+fn validate_hash(field: &str, hash: &str) -> std::result::Result<(), InvalidField> {
+    if hash.len() != 128 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(InvalidField::new(field, "must be 128 hex chars"));
+    }
+
+    Ok(())
+}
+
+impl Validate for ContributionInfo {
+    fn validate(&self) -> std::result::Result<(), InvalidField> {
+        if self.public_key.is_empty() {
+            return Err(InvalidField::new("public_key", "must not be empty"));
+        }
+
+        if self.ceremony_round == 0 {
+            return Err(InvalidField::new("ceremony_round", "must be > 0"));
+        }
+
+        validate_hash("contribution_hash", &self.contribution_hash)?;
+        validate_hash("contribution_file_hash", &self.contribution_file_hash)
+    }
+}
+
 /// The status of the contributor related to the current round.
+///
+/// The coordinator reports the contributor in the queue with [ContributorStatus::InQueue], which gives their position,
+/// the size of the queue, the cohort, starting from 1, in which they joined the queue and the estimated seconds before
+/// joining a round, missing while the round metrics are being calculated. The contributor in the round is reported
+/// with [ContributorStatus::InRound], which gives the number of retries left in case of a contribution failing
+/// verification and their cohort. The cohort is missing if the coordinator has no record of it.
+///
+/// [ContributorStatus::Queue] and [ContributorStatus::Round] are kept unchanged for the clients predating the new
+/// variants.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum ContributorStatus {
     Queue(u64, u64),
@@ -586,6 +947,35 @@ pub enum ContributorStatus {
     Finished,
     Banned,
     Other,
+    InRound {
+        retries: u8,
+        cohort: Option<u64>,
+    },
+    InQueue {
+        position: u64,
+        size: u64,
+        cohort: Option<u64>,
+        estimated_seconds: Option<u64>,
+    },
+}
+
+/// The status of a ceremony token: not used yet, used by a contributor in the queue or in the round, blacklisted after
+/// a contribution, or unknown if it does not belong to any cohort.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum TokenStatus {
+    Fresh,
+    Spent,
+    Blacklisted,
+    Unknown,
+}
+
+/// The eligibility of a ceremony token, checked before joining the queue. The cohorts start from 1: the cohort of the
+/// token is missing if the token does not belong to any cohort, the current cohort is missing if the ceremony is over.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TokenEligibility {
+    pub status: TokenStatus,
+    pub token_cohort: Option<u64>,
+    pub current_cohort: Option<u64>,
 }
 
 /// Request to post a [Chunk](`crate::objects::Chunk`).
@@ -610,6 +1000,198 @@ impl PostChunkRequest {
     }
 }
 
+impl Validate for PostChunkRequest {
+    fn validate(&self) -> std::result::Result<(), InvalidField> {
+        if self.round_height == 0 {
+            return Err(InvalidField::new("round_height", "must be > 0"));
+        }
+
+        let locator = &self.contribution_locator;
+        if locator.round_height() != self.round_height {
+            return Err(InvalidField::new(
+                "contribution_locator.round_height",
+                "must match round_height",
+            ));
+        }
+
+        if check_contribution_id(locator.contribution_id()).is_err() {
+            return Err(InvalidField::new("contribution_locator.contribution_id", "must be > 0"));
+        }
+
+        if locator.is_verified() {
+            return Err(InvalidField::new("contribution_locator.is_verified", "must be false"));
+        }
+
+        let signature_locator = &self.contribution_signature_locator;
+        if (
+            signature_locator.round_height(),
+            signature_locator.chunk_id(),
+            signature_locator.contribution_id(),
+            signature_locator.is_verified(),
+        ) != (
+            locator.round_height(),
+            locator.chunk_id(),
+            locator.contribution_id(),
+            locator.is_verified(),
+        ) {
+            return Err(InvalidField::new(
+                "contribution_signature_locator",
+                "must match contribution_locator",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Request to post the contributions to several [Chunk](`crate::objects::Chunk`)s locked by the contributor at once.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct PostChunkBatchRequest {
+    pub round_height: u64,
+    pub contributions: Vec<PostChunkRequest>,
+}
+
+impl Validate for PostChunkBatchRequest {
+    fn validate(&self) -> std::result::Result<(), InvalidField> {
+        if self.contributions.is_empty() {
+            return Err(InvalidField::new("contributions", "must not be empty"));
+        }
+
+        let mut chunk_ids = HashSet::new();
+        for (index, contribution) in self.contributions.iter().enumerate() {
+            if contribution.round_height != self.round_height {
+                return Err(InvalidField::new(
+                    &format!("contributions[{}].round_height", index),
+                    "must match round_height",
+                ));
+            }
+
+            if let Err(invalid) = contribution.validate() {
+                return Err(InvalidField::new(
+                    &format!("contributions[{}].{}", index, invalid.field),
+                    &invalid.reason,
+                ));
+            }
+
+            if !chunk_ids.insert(contribution.contribution_locator.chunk_id()) {
+                return Err(InvalidField::new(
+                    &format!("contributions[{}].contribution_locator.chunk_id", index),
+                    "must be unique in the batch",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Request to replace the token schedule of the ceremony.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct TokenScheduleRequest {
+    /// The serialized zip folder of the token files of the cohorts.
+    pub tokens: Vec<u8>,
+    /// The durations, in seconds, of the cohorts, the last one applying to all the following cohorts.
+    pub cohort_durations: Vec<u64>,
+}
+
+/// Request to merge the tokens of a cohort into the ones of another cohort.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct MergeCohortsRequest {
+    /// The cohort, starting from 0, whose tokens are merged and which is removed.
+    pub from: usize,
+    /// The cohort, starting from 0, receiving the tokens.
+    pub into: usize,
+}
+
+/// Request to start or resume a resumable upload of a contribution.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct InitiateChunkUploadRequest {
+    pub round_height: u64,
+    /// The size in bytes of the contribution.
+    pub total_size: u64,
+    /// The base64 encoded SHA-256 of the contribution.
+    pub checksum: String,
+}
+
+impl Validate for InitiateChunkUploadRequest {
+    fn validate(&self) -> std::result::Result<(), InvalidField> {
+        if self.round_height == 0 {
+            return Err(InvalidField::new("round_height", "must be > 0"));
+        }
+
+        if self.total_size == 0 {
+            return Err(InvalidField::new("total_size", "must be > 0"));
+        }
+
+        match base64::decode(&self.checksum) {
+            Ok(digest) if digest.len() == 32 => Ok(()),
+            _ => Err(InvalidField::new("checksum", "must be a base64 encoded SHA-256")),
+        }
+    }
+}
+
+/// Request to upload a part of a resumable upload of a contribution.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct PutChunkPartRequest {
+    pub upload_id: String,
+    /// The byte offset of the part in the contribution.
+    pub offset: u64,
+    /// The base64 encoded bytes of the part.
+    pub data: String,
+    /// The base64 encoded SHA-256 of the part.
+    pub checksum: String,
+}
+
+impl PutChunkPartRequest {
+    pub fn new(upload_id: String, offset: u64, data: &[u8]) -> Self {
+        Self {
+            upload_id,
+            offset,
+            data: base64::encode(data),
+            checksum: sha256_digest(data),
+        }
+    }
+
+    /// Returns the decoded bytes of the part.
+    pub fn decode_data(&self) -> std::result::Result<Vec<u8>, InvalidField> {
+        base64::decode(&self.data).map_err(|_| InvalidField::new("data", "must be base64 encoded"))
+    }
+}
+
+impl Validate for PutChunkPartRequest {
+    fn validate(&self) -> std::result::Result<(), InvalidField> {
+        let data = self.decode_data()?;
+        if data.is_empty() {
+            return Err(InvalidField::new("data", "must not be empty"));
+        }
+
+        if sha256_digest(&data) != self.checksum {
+            return Err(InvalidField::new("checksum", "must match the SHA-256 of data"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks that the contribution assembled from the parts of a resumable upload matches the checksum declared when
+/// initiating the upload. The contribution must not be published otherwise.
+pub fn check_chunk_upload(upload: &ChunkUpload, contribution: &[u8]) -> Result<()> {
+    let checksum = sha256_digest(contribution);
+    if contribution.len() as u64 != upload.total_size || checksum != upload.checksum {
+        return Err(ResponseError::MismatchingChecksum(upload.checksum.clone(), checksum));
+    }
+
+    Ok(())
+}
+
+/// Returns the base64 encoded SHA-256 of the given data.
+pub fn sha256_digest(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+
+    base64::encode(hasher.finalize())
+}
+
 /// Checks the validity of the token for the ceremony.
 /// Returns the current cohort index
 pub(crate) async fn token_check(coordinator: Coordinator, token: &str) -> Result<u64> {
@@ -627,6 +1209,12 @@ pub(crate) async fn token_check(coordinator: Coordinator, token: &str) -> Result
         }
     }
 
+    // Check that the token doesn't belong to a cohort which is not open yet, to tell the contributor when to come back
+    read_lock
+        .state()
+        .check_token_cohort(token)
+        .map_err(ResponseError::CoordinatorError)?;
+
     // Check that the token is correct for the current cohort number
     let cohort = read_lock.state().get_current_cohort_index();
     let tokens = match read_lock.state().tokens(cohort) {
@@ -641,7 +1229,7 @@ pub(crate) async fn token_check(coordinator: Coordinator, token: &str) -> Result
     Ok((cohort + 1) as u64)
 }
 
-/// Performs the verification of the pending contributions
+/// Performs the verification of the pending contributions, returning whether any contribution was verified
 ///
 /// # Cancel safety
 ///
@@ -649,44 +1237,56 @@ pub(crate) async fn token_check(coordinator: Coordinator, token: &str) -> Result
 ///
 /// Because of the use of [`tokio::sync::rwlock::RwLock::write_owned`], which is not cancel safe, and a spawned blocking
 /// task, which cannot be cancelled, this function is not cancel safe.
-pub async fn perform_verify_chunks(coordinator: Coordinator, s3_ctx: &S3Ctx) -> Result<()> {
+pub async fn perform_verify_chunks(coordinator: Coordinator, s3_ctx: &S3Ctx) -> Result<bool> {
     // Get all the pending verifications, loop on each one of them and perform verification
     // Technically, since we don't chunk contributions and we only have one contribution per round, we will always get
     // one pending verification at max.
     let mut write_lock = coordinator.write_owned().await;
 
-    // NOTE: we are going to rely on the single default verifier built in the coordinator itself,
-    //  no external verifiers
-    let contributions_info = task::spawn_blocking(move || -> Result<Vec<u8>> {
-        for (task, _) in write_lock.get_pending_verifications().to_owned() {
-            if let Err(e) = write_lock.default_verify(&task) {
-                warn!("Error while verifying a contribution: {}. Restarting the round...", e);
-                // FIXME: the verify_masp function may panic but the program doesn't shut down because we are executing it on a separate thread. It would be better though to make that function return a Result instead of panicking. Revert of round should be moved inside default_verify
-
-                // Get the participant who produced the contribution
-                let finished_contributor = write_lock
-                    .state()
-                    .current_round_finished_contributors()
-                    .unwrap()
-                    .first()
-                    .unwrap()
-                    .clone();
-
-                // Reset the round to prevent a coordinator stall (the corrupted contribution is not automatically dropped)
-                write_lock
-                    .reset_round()
-                    .map_err(|e| ResponseError::CoordinatorError(e))?;
-
-                // Ban the participant who produced the invalid contribution. Must be banned after the reset beacuse one can't ban a finished contributor
-                write_lock
-                    .ban_participant(&finished_contributor)
-                    .map_err(|e| ResponseError::CoordinatorError(e))?;
+    // NOTE: we are going to rely on the verifiers built in the coordinator itself, no external verifiers. Each of them
+    //  verifies the tasks assigned to it by the VerifierSelector of the coordinator, including the verifiers of the
+    //  current cohort which are not among the default ones
+    let (contributions_info, verified) = task::spawn_blocking(move || -> Result<(Vec<u8>, bool)> {
+        let mut verified = false;
+        for verifier in write_lock.get_assigned_verifiers() {
+            loop {
+                // Pull the tasks through the verifier lock so that the verifier never holds more than its limit
+                let task = match write_lock.fetch_verifier_task(&verifier) {
+                    Ok(Some(task)) => task,
+                    Ok(None) | Err(CoordinatorError::ParticipantHasLockedMaximumChunks) => break,
+                    Err(e) => return Err(ResponseError::CoordinatorError(e)),
+                };
+                verified = true;
+
+                if let Err(e) = write_lock.default_verify(&task) {
+                    warn!("Error while verifying a contribution: {}", e);
+                    // FIXME: the verify_masp function may panic but the program doesn't shut down because we are executing it on a separate thread. It would be better though to make that function return a Result instead of panicking. Revert of round should be moved inside default_verify
+
+                    // Get the participant who produced the contribution
+                    let contributor = write_lock
+                        .current_round()
+                        .and_then(|round| {
+                            round
+                                .chunk(task.chunk_id())?
+                                .get_contribution(task.contribution_id())?
+                                .get_contributor()
+                                .clone()
+                                .ok_or(CoordinatorError::ContributionMissing)
+                        })
+                        .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+                    // Let the participant retry the contribution, or ban them if their retries are exhausted
+                    write_lock
+                        .handle_failed_verification(&contributor, &task)
+                        .map_err(|e| ResponseError::CoordinatorError(e))?;
+                }
             }
         }
 
         write_lock
             .storage()
             .get_contributions_summary()
+            .map(|contributions_info| (contributions_info, verified))
             .map_err(|e| ResponseError::CoordinatorError(e))
     })
     .await??;
@@ -695,10 +1295,24 @@ pub async fn perform_verify_chunks(coordinator: Coordinator, s3_ctx: &S3Ctx) ->
     s3_ctx
         .upload_contributions_info(contributions_info)
         .await
+        .map(|_| verified)
         .map_err(|e| ResponseError::CoordinatorError(CoordinatorError::Error(anyhow!(e.to_string()))))
 }
 
-/// Performs the update of the [Coordinator](`crate::Coordinator`)
+/// Creates the [S3Ctx] of the ceremony of the given [Coordinator](`crate::Coordinator`)
+pub async fn ceremony_s3_ctx(coordinator: &Coordinator) -> Result<S3Ctx> {
+    let ceremony_name = coordinator
+        .read()
+        .await
+        .environment()
+        .ceremony_name()
+        .map(str::to_owned);
+
+    Ok(S3Ctx::for_ceremony(ceremony_name.as_deref()).await?)
+}
+
+/// Performs the update of the [Coordinator](`crate::Coordinator`), returning whether it changed the state of the
+/// ceremony. The interval between two periodic updates, if any, is reported in the status report of the update
 ///
 /// # Cancel safety
 ///
@@ -706,10 +1320,497 @@ pub async fn perform_verify_chunks(coordinator: Coordinator, s3_ctx: &S3Ctx) ->
 ///
 /// Because of the use of [`tokio::sync::rwlock::RwLock::write_owned`], which is not cancel safe, and a spawned blocking
 /// task, which cannot be cancelled, this function is not cancel safe.
-pub async fn perform_coordinator_update(coordinator: Coordinator) -> Result<()> {
+pub async fn perform_coordinator_update(coordinator: Coordinator, update_interval: Option<Duration>) -> Result<bool> {
     let mut write_lock = coordinator.write_owned().await;
 
-    task::spawn_blocking(move || write_lock.update())
-        .await?
-        .map_err(|e| ResponseError::CoordinatorError(e))
+    task::spawn_blocking(move || -> std::result::Result<bool, CoordinatorError> {
+        if let Some(update_interval) = update_interval {
+            write_lock.set_update_interval(update_interval);
+        }
+        let before = write_lock.diagnostics();
+        write_lock.update()?;
+        Ok(has_progressed(&before, &write_lock.diagnostics()))
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Requests the trusted timestamps of the contributions accepted by the [Coordinator](`crate::Coordinator`) since the
+/// last call, if enabled. The coordinator lock is not held while waiting for the time stamping authority, and a failed
+/// request or a failure to store the timestamp is logged, leaving the contribution without a timestamp.
+pub async fn perform_timestamp_contributions(coordinator: Coordinator) -> Result<()> {
+    let (authority, pending_timestamps) = match coordinator.write().await.take_pending_timestamps() {
+        Some(pending) => pending,
+        None => return Ok(()),
+    };
+
+    for pending in pending_timestamps {
+        let request_authority = authority.clone();
+        let response_hash = pending.response_hash.clone();
+        let timestamp = match task::spawn_blocking(move || request_authority.timestamp(&response_hash)).await? {
+            Ok(timestamp) => timestamp,
+            Err(e) => {
+                warn!("Failed to timestamp the contribution with {}: {}", authority.url(), e);
+                continue;
+            }
+        };
+
+        let mut write_lock = coordinator.clone().write_owned().await;
+        let stored = pending.clone();
+        if let Err(e) = task::spawn_blocking(move || write_lock.store_timestamp(&stored, timestamp)).await? {
+            warn!(
+                "Failed to store the timestamp of the contribution {} to chunk {} of round {}: {}",
+                pending.contribution_id, pending.chunk_id, pending.round_height, e
+            );
+            continue;
+        }
+    }
+
+    Ok(())
+}
+
+/// Drains the given [Coordinator](`crate::Coordinator`)s before the shutdown: new participants and locks are rejected
+/// while the chunks already locked are contributed, until none is left or [DRAIN_TIMEOUT_ENV] seconds elapsed
+pub async fn drain_coordinators(coordinators: Vec<Coordinator>) {
+    let timeout = match std::env::var(DRAIN_TIMEOUT_ENV) {
+        Ok(seconds) => Duration::from_secs(
+            seconds
+                .parse::<u64>()
+                .expect("NAMADA_DRAIN_TIMEOUT must be a number of seconds"),
+        ),
+        Err(_) => Duration::ZERO,
+    };
+
+    for coordinator in &coordinators {
+        coordinator.read().await.start_draining();
+    }
+
+    let deadline = rocket::tokio::time::Instant::now() + timeout;
+    loop {
+        let mut locks = 0;
+        for coordinator in &coordinators {
+            locks += coordinator.read().await.number_of_contributor_locks();
+        }
+
+        if locks == 0 {
+            return;
+        }
+        if rocket::tokio::time::Instant::now() >= deadline {
+            warn!("Drain timed out with {} chunks still locked", locks);
+            return;
+        }
+
+        info!("Waiting for {} locked chunks to be contributed before shutting down", locks);
+        rocket::tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+    }
+}
+
+/// Returns whether the ceremony progressed between the two snapshots, ignoring the reasons blocking the next round
+/// which change with the time alone
+fn has_progressed(before: &Diagnostics, after: &Diagnostics) -> bool {
+    let strip = |diagnostics: &Diagnostics| Diagnostics {
+        advance_blockers: Vec::new(),
+        ..diagnostics.clone()
+    };
+
+    strip(before) != strip(after)
+}
+
+/// The interval of a periodic task, doubled up to a maximum every time the task finds no work and reset to the
+/// minimum as soon as it finds some
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdaptiveInterval {
+    minimum: Duration,
+    maximum: Duration,
+    current: Duration,
+}
+
+impl AdaptiveInterval {
+    pub fn new(minimum: Duration, maximum: Duration) -> Self {
+        Self {
+            minimum,
+            maximum: maximum.max(minimum),
+            current: minimum,
+        }
+    }
+
+    /// Reads the minimum and maximum number of seconds of the interval from the given env variables. The minimum
+    /// defaults to [UPDATE_TIME] and the maximum to the minimum, disabling the backoff.
+    pub fn from_env(minimum_env: &str, maximum_env: &str) -> Self {
+        let seconds = |env: &str| {
+            std::env::var(env).ok().map(|seconds| {
+                Duration::from_secs(
+                    seconds
+                        .parse::<u64>()
+                        .unwrap_or_else(|_| panic!("{} must be a number of seconds", env)),
+                )
+            })
+        };
+        let minimum = seconds(minimum_env).unwrap_or(UPDATE_TIME);
+        let maximum = seconds(maximum_env).unwrap_or(minimum);
+
+        Self::new(minimum, maximum)
+    }
+
+    /// Returns the time to wait before the next run of the task
+    pub fn current(&self) -> Duration {
+        self.current
+    }
+
+    /// Adapts the interval to the outcome of the last run of the task and returns it
+    pub fn next(&mut self, found_work: bool) -> Duration {
+        self.current = match found_work {
+            true => self.minimum,
+            false => self.current.saturating_mul(2).min(self.maximum),
+        };
+
+        self.current
+    }
+}
+
+/// Token-bucket rate limiter of the requests to join the queue, keyed by source IP. Every address can make up to the
+/// rate set in the NAMADA_JOIN_RATE_PER_MINUTE env variable in a burst, then one request per 1/rate minute. The buckets
+/// idle for a minute are full again, so they are evicted from the underlying [`BoundedStore`].
+pub struct JoinRateLimiter {
+    rate_per_minute: u32,
+    /// The remaining requests of each address and the last time they were refilled.
+    buckets: Mutex<BoundedStore<IpAddr, (u32, OffsetDateTime)>>,
+    time: Arc<dyn TimeSource>,
+}
+
+impl JoinRateLimiter {
+    /// Returns the rate limiter set in the env, if any.
+    pub fn from_env() -> Option<Self> {
+        match *JOIN_RATE_PER_MINUTE {
+            0 => None,
+            rate_per_minute => Some(Self::new(rate_per_minute, Arc::new(SystemTimeSource::new()))),
+        }
+    }
+
+    pub fn new(rate_per_minute: u32, time: Arc<dyn TimeSource>) -> Self {
+        Self {
+            rate_per_minute,
+            buckets: Mutex::new(BoundedStore::from_env(time::Duration::MINUTE, time.clone())),
+            time,
+        }
+    }
+
+    /// Consumes a request of the given address, returns `false` if the address exceeded its rate.
+    pub fn check(&self, ip: IpAddr) -> bool {
+        let now = self.time.now_utc();
+        let mut buckets = self.buckets.lock().expect("Unable to lock the rate limiter");
+        let (tokens, refilled_at) = buckets.take(&ip).unwrap_or((self.rate_per_minute, now));
+
+        // Refill a request for every elapsed period of 1/rate minute.
+        let period = time::Duration::MINUTE / self.rate_per_minute;
+        let periods = ((now - refilled_at) / period).max(0.0) as u32;
+        let (tokens, refilled_at) = match periods {
+            0 => (tokens, refilled_at),
+            _ if tokens.saturating_add(periods) >= self.rate_per_minute => (self.rate_per_minute, now),
+            _ => (tokens + periods, refilled_at + period * periods),
+        };
+
+        match tokens.checked_sub(1) {
+            Some(tokens) => {
+                buckets.insert(ip, (tokens, refilled_at));
+                true
+            }
+            None => {
+                buckets.insert(ip, (0, refilled_at));
+                false
+            }
+        }
+    }
+}
+
+/// Marks a request as selected for verbose logging by the [`RequestSampler`]
+struct Sampled(bool);
+
+/// Formats the headers for logging, hiding the value of the sensitive ones
+pub fn redacted_headers(headers: &HeaderMap<'_>) -> Vec<String> {
+    headers
+        .iter()
+        .map(|header| {
+            let sensitive = REDACTED_HEADERS
+                .iter()
+                .any(|name| header.name().as_str().eq_ignore_ascii_case(name));
+            let value = if sensitive { REDACTED } else { header.value() };
+
+            format!("{}: {}", header.name(), value)
+        })
+        .collect()
+}
+
+/// Formats a body for logging, hiding the values of the JSON fields holding tokens or signatures. A body made of a
+/// single JSON string, like the token sent to join the queue, is hidden entirely, as well as a body which is not JSON.
+pub fn redacted_body(body: &[u8]) -> String {
+    fn redact(value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(fields) => {
+                for (name, field) in fields.iter_mut() {
+                    let name = name.to_lowercase();
+                    if name.contains("token") || name.contains("signature") {
+                        *field = serde_json::Value::String(REDACTED.to_string());
+                    } else {
+                        redact(field);
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => items.iter_mut().for_each(redact),
+            _ => (),
+        }
+    }
+
+    match serde_json::from_slice::<serde_json::Value>(body) {
+        Ok(value) if value.is_string() => REDACTED.to_string(),
+        Ok(mut value) => {
+            redact(&mut value);
+            value.to_string()
+        }
+        Err(_) => REDACTED.to_string(),
+    }
+}
+
+/// Fairing logging at debug level the headers and bodies of a random fraction of the requests and of their responses.
+/// The bodies bigger than [`MAX_SAMPLED_BODY_SIZE`] are only logged with their size. The fraction is read from the
+/// NAMADA_MPC_LOG_SAMPLE_RATE env variable and defaults to 0 (disabled).
+pub struct RequestSampler {
+    sample_rate: f64,
+}
+
+impl RequestSampler {
+    /// Reads the sample rate from the env, returns an error if it's not a number between 0 and 1
+    pub fn from_env() -> std::result::Result<Self, String> {
+        let sample_rate = match std::env::var(LOG_SAMPLE_RATE_ENV) {
+            Ok(rate) => rate
+                .parse::<f64>()
+                .ok()
+                .filter(|rate| (0.0..=1.0).contains(rate))
+                .ok_or_else(|| format!("{} must be a number between 0 and 1, got {}", LOG_SAMPLE_RATE_ENV, rate))?,
+            Err(_) => 0.0,
+        };
+
+        Ok(Self { sample_rate })
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for RequestSampler {
+    fn info(&self) -> Info {
+        Info {
+            name: "Sampled request logger",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, data: &mut Data) {
+        let sampled = self.sample_rate > 0.0 && rand::thread_rng().gen::<f64>() < self.sample_rate;
+        req.local_cache(|| Sampled(sampled));
+
+        if sampled {
+            // Peeking leaves the body to the route
+            let body = data.peek(MAX_SAMPLED_BODY_SIZE).await;
+            let body = match data.peek_complete() {
+                true => redacted_body(body),
+                false => String::from("<too large>"),
+            };
+
+            debug!(
+                "Sampled request: {} {} from {:?}, body size: {}, headers: {:?}, body: {}",
+                req.method(),
+                req.uri().path(),
+                req.client_ip(),
+                req.headers().get_one(CONTENT_LENGTH_HEADER).unwrap_or(UNKNOWN),
+                redacted_headers(req.headers()),
+                body
+            );
+        }
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        if req.local_cache(|| Sampled(false)).0 {
+            let size = res.body().preset_size();
+            let body = match size {
+                Some(size) if size <= MAX_SAMPLED_BODY_SIZE => match res.body_mut().to_bytes().await {
+                    Ok(body) => {
+                        let logged = redacted_body(&body);
+                        res.set_sized_body(body.len(), Cursor::new(body));
+                        logged
+                    }
+                    Err(e) => format!("<unreadable: {}>", e),
+                },
+                _ => String::from("<too large>"),
+            };
+
+            debug!(
+                "Sampled response: {} {} -> {}, body size: {:?}, headers: {:?}, body: {}",
+                req.method(),
+                req.uri().path(),
+                res.status(),
+                size,
+                redacted_headers(res.headers()),
+                body
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::http::Header;
+
+    #[test]
+    fn test_adaptive_interval() {
+        let mut interval = AdaptiveInterval::new(Duration::from_secs(5), Duration::from_secs(30));
+        assert_eq!(Duration::from_secs(5), interval.current());
+
+        // The interval doubles while no work is found, up to the maximum.
+        assert_eq!(Duration::from_secs(10), interval.next(false));
+        assert_eq!(Duration::from_secs(20), interval.next(false));
+        assert_eq!(Duration::from_secs(30), interval.next(false));
+        assert_eq!(Duration::from_secs(30), interval.next(false));
+
+        // Finding work resets it to the minimum.
+        assert_eq!(Duration::from_secs(5), interval.next(true));
+
+        // A maximum below the minimum disables the backoff.
+        let mut interval = AdaptiveInterval::new(Duration::from_secs(5), Duration::ZERO);
+        assert_eq!(Duration::from_secs(5), interval.next(false));
+    }
+
+    #[test]
+    fn test_redacted_headers() {
+        let mut headers = HeaderMap::new();
+        headers.add(Header::new(ACCESS_SECRET_HEADER, "top-secret"));
+        headers.add(Header::new("ats-signature", "deadbeef"));
+        headers.add(Header::new(PUBKEY_HEADER, "pubkey"));
+        headers.add(Header::new(CONTENT_LENGTH_HEADER, "42"));
+
+        let redacted = redacted_headers(&headers).join("\n");
+
+        assert!(!redacted.contains("top-secret"));
+        assert!(!redacted.contains("deadbeef"));
+        assert!(redacted.contains(&format!("{}: {}", ACCESS_SECRET_HEADER, REDACTED)));
+        assert!(redacted.contains(&format!("ats-signature: {}", REDACTED)));
+        assert!(redacted.contains(&format!("{}: pubkey", PUBKEY_HEADER)));
+        assert!(redacted.contains(&format!("{}: 42", CONTENT_LENGTH_HEADER)));
+    }
+
+    #[test]
+    fn test_redacted_body() {
+        // The token sent to join the queue
+        assert_eq!(REDACTED, redacted_body(br#""secret-token""#));
+
+        let body = br#"{"tokens":[1,2],"round":1,"contributions":[{"signature":"deadbeef","chunk_id":0}]}"#;
+        let redacted = redacted_body(body);
+        assert!(!redacted.contains("deadbeef"));
+        assert!(redacted.contains(&format!(r#""tokens":"{}""#, REDACTED)));
+        assert!(redacted.contains(&format!(r#""signature":"{}""#, REDACTED)));
+        assert!(redacted.contains(r#""round":1"#));
+        assert!(redacted.contains(r#""chunk_id":0"#));
+
+        // A body which is not JSON is not logged
+        assert_eq!(REDACTED, redacted_body(b"binary contribution"));
+    }
+
+    #[test]
+    fn test_chunk_upload_parts() {
+        let contribution = b"a contribution uploaded in parts".to_vec();
+        let mut upload = ChunkUpload {
+            upload_id: String::from("upload"),
+            participant: Participant::new_contributor("contributor"),
+            round_height: 1,
+            total_size: contribution.len() as u64,
+            checksum: sha256_digest(&contribution),
+            parts: std::collections::BTreeMap::new(),
+        };
+
+        // The parts are sent base64 encoded and can be received in any order.
+        let mut staged = std::collections::BTreeMap::new();
+        for offset in [20, 0, 10] {
+            let end = (offset + 10).min(contribution.len());
+            let part = PutChunkPartRequest::new(upload.upload_id.clone(), offset as u64, &contribution[offset..end]);
+            assert_eq!(Ok(()), part.validate());
+
+            let data = part.decode_data().unwrap();
+            upload.parts.insert(part.offset, data.len() as u64);
+            staged.insert(part.offset, data);
+        }
+        assert!(upload.is_complete());
+
+        let assembled: Vec<u8> = staged.values().flatten().copied().collect();
+        assert!(check_chunk_upload(&upload, &assembled).is_ok());
+
+        // A tampered or truncated contribution is not published.
+        let mut tampered = assembled.clone();
+        tampered[0] ^= 1;
+        assert!(matches!(
+            check_chunk_upload(&upload, &tampered),
+            Err(ResponseError::MismatchingChecksum(_, _))
+        ));
+        assert!(matches!(
+            check_chunk_upload(&upload, &assembled[..10]),
+            Err(ResponseError::MismatchingChecksum(_, _))
+        ));
+
+        // Parts which are not base64 encoded are rejected.
+        let mut part = PutChunkPartRequest::new(upload.upload_id.clone(), 0, &contribution[..10]);
+        part.data = String::from("not base64!");
+        assert_eq!(
+            Err(InvalidField::new("data", "must be base64 encoded")),
+            part.validate()
+        );
+    }
+
+    #[test]
+    fn test_coordinator_error_messages() {
+        let participant = Participant::new_contributor("contributor");
+
+        assert_eq!(
+            "Coordinator failed: The participant is missing the pending task 0/1",
+            ResponseError::CoordinatorError(CoordinatorError::ParticipantMissingPendingTask {
+                pending_task: Task::new(0, 1)
+            })
+            .to_string()
+        );
+        assert_eq!(
+            "Coordinator failed: The participant contributor.contributor was not found",
+            ResponseError::CoordinatorError(CoordinatorError::ParticipantNotFound(participant)).to_string()
+        );
+        assert_eq!(
+            "Coordinator failed: The audit log expected the entry 2 but found the entry 3",
+            ResponseError::CoordinatorError(CoordinatorError::AuditLogSequenceMismatch { expected: 2, found: 3 })
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_join_rate_limiter() {
+        let time = Arc::new(crate::MockTimeSource::new(OffsetDateTime::now_utc()));
+        let limiter = JoinRateLimiter::new(3, time.clone());
+        let ip: IpAddr = "1.2.3.4".parse().unwrap();
+        let other_ip: IpAddr = "5.6.7.8".parse().unwrap();
+
+        // A burst up to the rate is allowed, then the address is limited
+        for _ in 0..3 {
+            assert!(limiter.check(ip));
+        }
+        assert!(!limiter.check(ip));
+        assert!(limiter.check(other_ip));
+
+        // A request is refilled every 20 seconds
+        time.update(|t| t + time::Duration::seconds(19));
+        assert!(!limiter.check(ip));
+        time.update(|t| t + time::Duration::seconds(1));
+        assert!(limiter.check(ip));
+        assert!(!limiter.check(ip));
+
+        // The bucket is full again after a minute
+        time.update(|t| t + time::Duration::minutes(5));
+        for _ in 0..3 {
+            assert!(limiter.check(ip));
+        }
+        assert!(!limiter.check(ip));
+    }
 }
@@ -1,18 +1,20 @@
 use lazy_static::lazy_static;
 use rocket::tokio::{io::AsyncReadExt, time};
-use rusoto_core::{region::Region, request::TlsError};
+use rusoto_core::{RusotoError, region::Region, request::TlsError};
 use rusoto_credential::{AwsCredentials, ChainProvider, CredentialsError, ProvideAwsCredentials};
 use rusoto_s3::{
     util::{PreSignedRequest, PreSignedRequestOption},
     DeleteObjectRequest,
     GetObjectRequest,
+    HeadBucketRequest,
     HeadObjectRequest,
+    ListObjectsV2Request,
     PutObjectRequest,
     S3Client,
     StreamingBody,
     S3,
 };
-use std::str::FromStr;
+use std::{collections::BTreeMap, future::Future, str::FromStr};
 use thiserror::Error;
 use tracing::warn;
 
@@ -48,20 +50,58 @@ pub enum S3Error {
     EmptyContribution,
     #[error("S3 contribution file signature is present but empty")]
     EmptyContributionSignature,
+    #[error("Head request on S3 failed: {0}")]
+    HeadError(String),
     #[error("Error in IO: {0}")]
     IOError(#[from] std::io::Error),
+    #[error("Listing of S3 files failed: {0}")]
+    ListError(String),
     #[error("Upload of file to S3 failed: {0}")]
     UploadError(String),
 }
 
 type Result<T> = std::result::Result<T, S3Error>;
 
+/// Sends the request built by `request` until it succeeds, retrying the throttled and failed requests with an
+/// exponential backoff at most [MAX_REQUEST_RETRY] times, see
+/// https://docs.aws.amazon.com/elastictranscoder/latest/developerguide/error-handling.html#api-retries
+async fn retry_request<T, E, F, Fut>(description: &str, mut request: F) -> std::result::Result<T, RusotoError<E>>
+where
+    E: std::error::Error + 'static,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = std::result::Result<T, RusotoError<E>>>,
+{
+    let mut attempt = 0u32;
+
+    loop {
+        let e = match request().await {
+            Ok(output) => return Ok(output),
+            Err(e) => e,
+        };
+
+        let retryable = match &e {
+            RusotoError::Unknown(inner) => matches!(inner.status.as_u16(), 429 | 500 | 502 | 503 | 504),
+            _ => false,
+        };
+        if !retryable || attempt >= MAX_REQUEST_RETRY {
+            return Err(e);
+        }
+
+        warn!("Retrying s3 {} request because of: {}", description, e);
+        let sleep_time = 2u32.pow(attempt) * BACKOFF_SLEEP_TIME_MILLISECS;
+        attempt += 1;
+        time::sleep(std::time::Duration::from_millis(sleep_time.into())).await;
+    }
+}
+
 pub struct S3Ctx {
     client: S3Client,
     bucket: &'static String,
     region: &'static Region,
     options: PreSignedRequestOption,
     credentials: AwsCredentials,
+    /// The prefix of the keys of the objects of a named ceremony.
+    prefix: Option<String>,
 }
 
 impl S3Ctx {
@@ -79,88 +119,47 @@ impl S3Ctx {
             region: &S3_REGION,
             options,
             credentials,
+            prefix: None,
         })
     }
 
+    /// Creates the context of the given ceremony. The objects of a named ceremony are stored under the `<name>/`
+    /// prefix, except for the compressed token folder which is shared by all the ceremonies.
+    pub async fn for_ceremony(name: Option<&str>) -> Result<Self> {
+        let mut ctx = Self::new().await?;
+        ctx.prefix = name.map(|name| format!("{}/", name));
+
+        Ok(ctx)
+    }
+
+    /// Key of the given object in the namespace of the ceremony.
+    fn key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix.as_deref().unwrap_or_default(), key)
+    }
+
     /// Upload contributors.json file to S3 for the frontend
     pub(crate) async fn upload_contributions_info(&self, contributions_info: Vec<u8>) -> Result<()> {
         // First delete the old file to allow triggering the lambda
         let delete_object_request = DeleteObjectRequest {
             bucket: self.bucket.clone(),
-            key: "contributors.json".to_string(),
+            key: self.key("contributors.json"),
             ..Default::default()
         };
 
-        let mut attempt = 0u32;
-
-        while let Err(e) = self.client.delete_object(delete_object_request.clone()).await {
-            match e {
-                rusoto_core::RusotoError::Unknown(ref inner) => {
-                    match inner.status.as_u16() {
-                        429 | 500 | 502 | 503 | 504 => {
-                            // If enough attempts return
-                            if attempt >= MAX_REQUEST_RETRY {
-                                return Err(S3Error::DeleteError(e.to_string()));
-                            }
-
-                            // Exponential backoff, https://docs.aws.amazon.com/elastictranscoder/latest/developerguide/error-handling.html#api-retries
-                            warn!("Retrying s3 delete contributors.json request because of: {}", e);
-                            let sleep_time = 2u32.pow(attempt) * BACKOFF_SLEEP_TIME_MILLISECS;
-                            attempt += 1;
-                            time::sleep(std::time::Duration::from_millis(sleep_time.into())).await;
-                        }
-                        _ => return Err(S3Error::DeleteError(e.to_string())),
-                    }
-                }
-                _ => return Err(S3Error::DeleteError(e.to_string())),
-            }
-        }
-
-        attempt = 0;
+        retry_request("delete contributors.json", || {
+            self.client.delete_object(delete_object_request.clone())
+        })
+        .await
+        .map_err(|e| S3Error::DeleteError(e.to_string()))?;
 
         // Upload the updated file
-        let mut put_object_request = PutObjectRequest {
-            bucket: self.bucket.clone(),
-            key: "contributors.json".to_string(),
-            body: Some(StreamingBody::from(contributions_info.clone())),
-            ..Default::default()
-        };
-
-        while let Err(e) = self.client.put_object(put_object_request).await {
-            match e {
-                rusoto_core::RusotoError::Unknown(ref inner) => {
-                    match inner.status.as_u16() {
-                        429 | 500 | 502 | 503 | 504 => {
-                            // If enough attempts return
-                            if attempt >= MAX_REQUEST_RETRY {
-                                return Err(S3Error::UploadError(e.to_string()));
-                            }
-
-                            // Exponential backoff, https://docs.aws.amazon.com/elastictranscoder/latest/developerguide/error-handling.html#api-retries
-                            put_object_request = PutObjectRequest {
-                                bucket: self.bucket.clone(),
-                                key: "contributors.json".to_string(),
-                                body: Some(StreamingBody::from(contributions_info.clone())),
-                                ..Default::default()
-                            };
-
-                            warn!("Retrying s3 upload contributors.json request because of: {}", e);
-                            let sleep_time = 2u32.pow(attempt) * BACKOFF_SLEEP_TIME_MILLISECS;
-                            attempt += 1;
-                            time::sleep(std::time::Duration::from_millis(sleep_time.into())).await;
-                        }
-                        _ => return Err(S3Error::UploadError(e.to_string())),
-                    }
-                }
-                _ => return Err(S3Error::UploadError(e.to_string())),
-            }
-        }
-
-        Ok(())
+        self.upload_object("contributors.json".to_string(), contributions_info)
+            .await
     }
 
     /// Get the url of a challenge on S3.
     pub(crate) async fn get_challenge_url(&self, key: String) -> Option<String> {
+        let key = self.key(&key);
         let head = HeadObjectRequest {
             bucket: self.bucket.clone(),
             key: key.clone(),
@@ -182,45 +181,9 @@ impl S3Ctx {
 
     /// Upload a challenge to S3. Returns the presigned url to get it.
     pub(crate) async fn upload_challenge(&self, key: String, challenge: Vec<u8>) -> Result<String> {
-        let mut put_object_request = PutObjectRequest {
-            bucket: self.bucket.clone(),
-            key: key.clone(),
-            body: Some(StreamingBody::from(challenge.clone())),
-            ..Default::default()
-        };
-
-        let mut attempt = 0u32;
-
-        while let Err(e) = self.client.put_object(put_object_request).await {
-            match e {
-                rusoto_core::RusotoError::Unknown(ref inner) => {
-                    match inner.status.as_u16() {
-                        429 | 500 | 502 | 503 | 504 => {
-                            // If enough attempts return
-                            if attempt >= MAX_REQUEST_RETRY {
-                                return Err(S3Error::UploadError(e.to_string()));
-                            }
-
-                            // Exponential backoff, https://docs.aws.amazon.com/elastictranscoder/latest/developerguide/error-handling.html#api-retries
-                            put_object_request = PutObjectRequest {
-                                bucket: self.bucket.clone(),
-                                key: key.clone(),
-                                body: Some(StreamingBody::from(challenge.clone())),
-                                ..Default::default()
-                            };
-
-                            warn!("Retrying s3 upload challenge request because of: {}", e);
-                            let sleep_time = 2u32.pow(attempt) * BACKOFF_SLEEP_TIME_MILLISECS;
-                            attempt += 1;
-                            time::sleep(std::time::Duration::from_millis(sleep_time.into())).await;
-                        }
-                        _ => return Err(S3Error::UploadError(e.to_string())),
-                    }
-                }
-                _ => return Err(S3Error::UploadError(e.to_string())),
-            }
-        }
+        self.upload_object(key.clone(), challenge).await?;
 
+        let key = self.key(&key);
         let get = GetObjectRequest {
             bucket: self.bucket.clone(),
             key,
@@ -234,12 +197,12 @@ impl S3Ctx {
     pub(crate) fn get_contribution_urls(&self, contrib_key: String, contrib_sig_key: String) -> (String, String) {
         let get_contrib = PutObjectRequest {
             bucket: self.bucket.clone(),
-            key: contrib_key,
+            key: self.key(&contrib_key),
             ..Default::default()
         };
         let get_sig = PutObjectRequest {
             bucket: self.bucket.clone(),
-            key: contrib_sig_key,
+            key: self.key(&contrib_sig_key),
             ..Default::default()
         };
 
@@ -255,33 +218,11 @@ impl S3Ctx {
     async fn get_object(&self, get_request: GetObjectRequest) -> Result<Vec<u8>> {
         let mut buffer = Vec::new();
 
-        let mut attempt = 0u32;
-
-        let stream = loop {
-            match self.client.get_object(get_request.clone()).await {
-                Ok(i) => break i.body.ok_or(S3Error::EmptyContribution)?,
-                Err(e) => match e {
-                    rusoto_core::RusotoError::Unknown(ref inner) => {
-                        match inner.status.as_u16() {
-                            429 | 500 | 502 | 503 | 504 => {
-                                // If enough attempts return
-                                if attempt >= MAX_REQUEST_RETRY {
-                                    return Err(S3Error::DownloadError(e.to_string()));
-                                }
-
-                                // Exponential backoff, https://docs.aws.amazon.com/elastictranscoder/latest/developerguide/error-handling.html#api-retries
-                                warn!("Retrying s3 get object request because of: {}", e);
-                                let sleep_time = 2u32.pow(attempt) * BACKOFF_SLEEP_TIME_MILLISECS;
-                                attempt += 1;
-                                time::sleep(std::time::Duration::from_millis(sleep_time.into())).await;
-                            }
-                            _ => return Err(S3Error::DownloadError(e.to_string())),
-                        }
-                    }
-                    _ => return Err(S3Error::DownloadError(e.to_string())),
-                },
-            }
-        };
+        let stream = retry_request("get object", || self.client.get_object(get_request.clone()))
+            .await
+            .map_err(|e| S3Error::DownloadError(e.to_string()))?
+            .body
+            .ok_or(S3Error::EmptyContribution)?;
 
         stream.into_async_read().read_to_end(&mut buffer).await?;
 
@@ -290,33 +231,237 @@ impl S3Ctx {
 
     /// Retrieve a contribution and its signature from S3.
     pub(crate) async fn get_contribution(&self, round_height: u64) -> Result<(Vec<u8>, Vec<u8>)> {
+        self.get_chunk_contribution(round_height, 0, 1).await
+    }
+
+    /// Key of the given contribution to a chunk, relative to the namespace of the ceremony.
+    fn contribution_key(round_height: u64, chunk_id: u64, contribution_id: u64) -> String {
+        format!(
+            "round_{}/chunk_{}/contribution_{}.unverified",
+            round_height, chunk_id, contribution_id
+        )
+    }
+
+    /// Download the given contribution to a chunk and its signature.
+    pub(crate) async fn get_chunk_contribution(
+        &self,
+        round_height: u64,
+        chunk_id: u64,
+        contribution_id: u64,
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        let contrib_key = Self::contribution_key(round_height, chunk_id, contribution_id);
         let get_contrib = GetObjectRequest {
             bucket: self.bucket.clone(),
-            key: format!("round_{}/chunk_0/contribution_1.unverified", round_height),
+            key: self.key(&contrib_key),
             ..Default::default()
         };
         let get_sig = GetObjectRequest {
             bucket: self.bucket.clone(),
-            key: format!("round_{}/chunk_0/contribution_1.unverified.signature", round_height),
+            key: self.key(&format!("{}.signature", contrib_key)),
             ..Default::default()
         };
 
         rocket::tokio::try_join!(self.get_object(get_contrib), self.get_object(get_sig))
     }
 
-    /// Retrieve the compressed token folder.
-    pub async fn get_tokens(&self) -> Result<Vec<u8>> {
-        let key = match std::env::var("AWS_S3_PROD") {
+    /// Prefix of the keys of the staged parts of a resumable upload of the contribution to the given round. The parts
+    /// are kept apart from the contribution so that an incomplete upload is never mistaken for it.
+    fn contribution_parts_prefix(round_height: u64) -> String {
+        format!("{}.parts/", Self::contribution_key(round_height, 0, 1))
+    }
+
+    /// Stage a part, starting at the given byte offset, of a resumable upload of the contribution to the given round.
+    pub(crate) async fn upload_contribution_part(&self, round_height: u64, offset: u64, data: Vec<u8>) -> Result<()> {
+        let key = format!("{}part_{}", Self::contribution_parts_prefix(round_height), offset);
+
+        self.upload_object(key, data).await
+    }
+
+    /// Assemble the given staged parts, mapping their byte offset to their length, of a resumable upload of the
+    /// contribution to the given round.
+    pub(crate) async fn download_contribution_parts(
+        &self,
+        round_height: u64,
+        parts: &BTreeMap<u64, u64>,
+    ) -> Result<Vec<u8>> {
+        let mut contribution = Vec::with_capacity(parts.values().sum::<u64>() as usize);
+        for (offset, length) in parts.iter() {
+            let key = format!("{}part_{}", Self::contribution_parts_prefix(round_height), offset);
+            let part = self.download_object(key).await?;
+
+            if part.len() as u64 != *length {
+                return Err(S3Error::DownloadError(format!(
+                    "part at offset {} has {} bytes instead of {}",
+                    offset,
+                    part.len(),
+                    length
+                )));
+            }
+            contribution.extend(part);
+        }
+
+        Ok(contribution)
+    }
+
+    /// Delete the staged parts of a resumable upload of the contribution to the given round.
+    pub(crate) async fn delete_contribution_parts(&self, round_height: u64) -> Result<()> {
+        for key in self.list_keys(Self::contribution_parts_prefix(round_height)).await? {
+            self.delete_object(key).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Publish the assembled contribution to the given round, where the contributor would have uploaded it directly.
+    pub(crate) async fn upload_contribution(&self, round_height: u64, contribution: Vec<u8>) -> Result<()> {
+        self.upload_object(Self::contribution_key(round_height, 0, 1), contribution)
+            .await
+    }
+
+    /// Key of the compressed token folder for the current environment.
+    fn tokens_key() -> String {
+        match std::env::var("AWS_S3_PROD") {
             Ok(t) if t == "true" => format!("production/{}", TOKENS_ZIP_FILE),
             _ => format!("master/{}", TOKENS_ZIP_FILE),
-        };
+        }
+    }
 
+    /// Retrieve the compressed token folder.
+    pub async fn get_tokens(&self) -> Result<Vec<u8>> {
         let get_tokens = GetObjectRequest {
             bucket: self.bucket.clone(),
-            key,
+            key: Self::tokens_key(),
             ..Default::default()
         };
 
         self.get_object(get_tokens).await
     }
+
+    /// Replace the compressed token folder, shared by all the ceremonies.
+    pub async fn upload_tokens(&self, tokens: Vec<u8>) -> Result<()> {
+        self.put_object(Self::tokens_key(), tokens).await
+    }
+
+    /// Key of the completion record of the ceremony for the current environment.
+    fn completion_key() -> String {
+        match std::env::var("AWS_S3_PROD") {
+            Ok(t) if t == "true" => "production/completion.json".to_string(),
+            _ => "master/completion.json".to_string(),
+        }
+    }
+
+    /// Upload the completion record of the ceremony.
+    pub async fn upload_completion(&self, completion: Vec<u8>) -> Result<()> {
+        self.upload_object(Self::completion_key(), completion).await
+    }
+
+    /// Upload an object to S3.
+    pub(crate) async fn upload_object(&self, key: String, data: Vec<u8>) -> Result<()> {
+        self.put_object(self.key(&key), data).await
+    }
+
+    /// Upload an object to S3 at the given key, outside of the namespace of the ceremony.
+    async fn put_object(&self, key: String, data: Vec<u8>) -> Result<()> {
+        retry_request(&format!("upload {}", key), || {
+            self.client.put_object(PutObjectRequest {
+                bucket: self.bucket.clone(),
+                key: key.clone(),
+                body: Some(StreamingBody::from(data.clone())),
+                ..Default::default()
+            })
+        })
+        .await
+        .map(|_| ())
+        .map_err(|e| S3Error::UploadError(e.to_string()))
+    }
+
+    /// Download an object from S3.
+    pub(crate) async fn download_object(&self, key: String) -> Result<Vec<u8>> {
+        let get_request = GetObjectRequest {
+            bucket: self.bucket.clone(),
+            key: self.key(&key),
+            ..Default::default()
+        };
+
+        self.get_object(get_request).await
+    }
+
+    /// Delete an object from S3.
+    pub(crate) async fn delete_object(&self, key: String) -> Result<()> {
+        let delete_object_request = DeleteObjectRequest {
+            bucket: self.bucket.clone(),
+            key: self.key(&key),
+            ..Default::default()
+        };
+
+        retry_request("delete object", || {
+            self.client.delete_object(delete_object_request.clone())
+        })
+        .await
+        .map(|_| ())
+        .map_err(|e| S3Error::DeleteError(e.to_string()))
+    }
+
+    /// List the keys of the objects starting with the given prefix, relative to the namespace of the ceremony.
+    pub(crate) async fn list_keys(&self, prefix: String) -> Result<Vec<String>> {
+        let prefix = self.key(&prefix);
+        let namespace = self.prefix.as_deref().unwrap_or_default();
+        let mut keys = Vec::new();
+        let mut continuation_token = None;
+
+        loop {
+            let list_request = ListObjectsV2Request {
+                bucket: self.bucket.clone(),
+                prefix: Some(prefix.clone()),
+                continuation_token,
+                ..Default::default()
+            };
+
+            let output = retry_request("list objects", || self.client.list_objects_v2(list_request.clone()))
+                .await
+                .map_err(|e| S3Error::ListError(e.to_string()))?;
+
+            keys.extend(
+                output
+                    .contents
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|object| object.key)
+                    .map(|key| key[namespace.len()..].to_string()),
+            );
+
+            match output.next_continuation_token {
+                Some(token) if output.is_truncated.unwrap_or_default() => continuation_token = Some(token),
+                _ => return Ok(keys),
+            }
+        }
+    }
+
+    /// Check that the bucket is accessible with the current credentials.
+    pub async fn check_bucket(&self) -> Result<()> {
+        let head = HeadBucketRequest {
+            bucket: self.bucket.clone(),
+            ..Default::default()
+        };
+
+        self.client
+            .head_bucket(head)
+            .await
+            .map_err(|e| S3Error::HeadError(e.to_string()))
+    }
+
+    /// Check that the compressed token folder exists, without downloading it.
+    pub async fn check_tokens(&self) -> Result<()> {
+        let head = HeadObjectRequest {
+            bucket: self.bucket.clone(),
+            key: Self::tokens_key(),
+            ..Default::default()
+        };
+
+        self.client
+            .head_object(head)
+            .await
+            .map(|_| ())
+            .map_err(|e| S3Error::HeadError(e.to_string()))
+    }
 }
@@ -0,0 +1,190 @@
+//! Publication of the secret granting access to the reserved endpoints.
+//!
+//! The secret is generated at startup and published to SSM so that the operators can retrieve it. A failed publication
+//! is retried with exponential backoff. If it keeps failing and a fallback path is set in the
+//! [SECRET_FALLBACK_PATH_ENV] env variable, the secret is written to that file (readable by the owner only) and the
+//! coordinator keeps running with it, otherwise the error is returned.
+
+use crate::s3::REGION;
+use rocket::tokio::time;
+use rusoto_ssm::{PutParameterRequest, Ssm, SsmClient};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+use tracing::{error, warn};
+
+/// The env variable holding the path of the file to write the secret to when its publication fails.
+pub const SECRET_FALLBACK_PATH_ENV: &str = "NAMADA_MPC_SECRET_FALLBACK_PATH";
+
+const BACKOFF_SLEEP_TIME_MILLISECS: u32 = 100;
+const MAX_PUBLISH_RETRY: u32 = 3;
+
+/// The service the secret is published to.
+#[rocket::async_trait]
+pub trait SecretPublisher: Send + Sync {
+    /// Publishes the given secret.
+    async fn publish(&self, secret: &str) -> Result<(), String>;
+}
+
+/// Where the secret ended up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretLocation {
+    /// The secret has been published.
+    Published,
+    /// The publication failed and the secret has been written to the given file.
+    Fallback(PathBuf),
+}
+
+/// Publishes the secret, retrying on failure. If every attempt fails, the secret is written to the fallback file, if
+/// any, otherwise the last error is returned.
+pub async fn publish_secret<P: SecretPublisher>(
+    publisher: &P,
+    secret: &str,
+    fallback_path: Option<&Path>,
+) -> Result<SecretLocation, String> {
+    let mut attempt = 0u32;
+
+    while let Err(e) = publisher.publish(secret).await {
+        if attempt >= MAX_PUBLISH_RETRY {
+            let path = match fallback_path {
+                Some(path) => path,
+                None => return Err(e),
+            };
+
+            error!(
+                "Publication of the secret failed, falling back to {}: {}",
+                path.display(),
+                e
+            );
+            write_secret_file(path, secret).map_err(|e| format!("Error while writing the secret file: {}", e))?;
+
+            return Ok(SecretLocation::Fallback(path.to_path_buf()));
+        }
+
+        warn!("Retrying publication of the secret because of: {}", e);
+        let sleep_time = 2u32.pow(attempt) * BACKOFF_SLEEP_TIME_MILLISECS;
+        attempt += 1;
+        time::sleep(std::time::Duration::from_millis(sleep_time.into())).await;
+    }
+
+    Ok(SecretLocation::Published)
+}
+
+/// Writes the secret to the given file, readable and writable by the owner only.
+fn write_secret_file(path: &Path, secret: &str) -> std::io::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(path)?;
+    file.write_all(secret.as_bytes())?;
+    file.sync_all()
+}
+
+/// [`SecretPublisher`] backed by Amazon Parameter Store.
+pub struct SsmSecretPublisher {
+    ssm: SsmClient,
+}
+
+impl Default for SsmSecretPublisher {
+    fn default() -> Self {
+        Self {
+            ssm: SsmClient::new(REGION.clone()),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl SecretPublisher for SsmSecretPublisher {
+    async fn publish(&self, secret: &str) -> Result<(), String> {
+        let env = match std::env::var("AWS_S3_PROD") {
+            Ok(val) if val == "true" => "production",
+            _ => "master",
+        };
+
+        let put_request = PutParameterRequest {
+            description: Some("Trusted setup endpoints secret".to_string()),
+            name: format!("/namada/trusted-setup/{}/secret", env),
+            overwrite: Some(true),
+            type_: Some("SecureString".to_string()),
+            value: secret.to_string(),
+            ..Default::default()
+        };
+
+        self.ssm
+            .put_parameter(put_request)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Fails the first `failures` publications.
+    struct MockPublisher {
+        failures: u32,
+        attempts: AtomicU32,
+    }
+
+    impl MockPublisher {
+        fn new(failures: u32) -> Self {
+            Self {
+                failures,
+                attempts: AtomicU32::new(0),
+            }
+        }
+    }
+
+    #[rocket::async_trait]
+    impl SecretPublisher for MockPublisher {
+        async fn publish(&self, _secret: &str) -> Result<(), String> {
+            match self.attempts.fetch_add(1, Ordering::SeqCst) < self.failures {
+                true => Err("service unavailable".to_string()),
+                false => Ok(()),
+            }
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_publish_secret_retries() {
+        let publisher = MockPublisher::new(MAX_PUBLISH_RETRY);
+
+        let location = publish_secret(&publisher, "secret", None).await.unwrap();
+        assert_eq!(location, SecretLocation::Published);
+        assert_eq!(publisher.attempts.load(Ordering::SeqCst), MAX_PUBLISH_RETRY + 1);
+    }
+
+    #[rocket::async_test]
+    async fn test_publish_secret_fallback() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secret");
+
+        // Without a fallback the error is returned
+        let publisher = MockPublisher::new(u32::MAX);
+        assert!(publish_secret(&publisher, "secret", None).await.is_err());
+        assert!(!path.exists());
+
+        // With a fallback the secret is kept locally
+        let publisher = MockPublisher::new(u32::MAX);
+        let location = publish_secret(&publisher, "secret", Some(&path)).await.unwrap();
+        assert_eq!(location, SecretLocation::Fallback(path.clone()));
+        assert_eq!(publisher.attempts.load(Ordering::SeqCst), MAX_PUBLISH_RETRY + 1);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "secret");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
@@ -1,11 +1,12 @@
 use crate::{
-    environment::Environment,
-    objects::{ContributionFileSignature, ContributionInfo, Round, TrimmedContributionInfo},
+    coordinator_state::FinishedParticipants,
+    environment::{Environment, StateFormat},
+    objects::{ContributionFileSignature, ContributionInfo, Round, TrimmedContributionInfo, VerificationCache},
     storage::{
-        ContributionLocator, ContributionSignatureLocator, Locator, Object, ObjectReader, ObjectWriter, StorageLocator,
-        StorageObject,
+        ContributionLocator, ContributionSignatureLocator, Locator, Object, ObjectReader, ObjectWriter, Storage,
+        StorageCipher, StorageLocator,
     },
-    CoordinatorError, CoordinatorState,
+    CeremonyCompletion, CoordinatorError, CoordinatorState,
 };
 
 use anyhow::Result;
@@ -19,18 +20,29 @@ use std::{
     ops::{Deref, DerefMut},
     path::{Path, PathBuf},
     str::FromStr,
+    sync::atomic::{AtomicBool, Ordering},
 };
-use tracing::{debug, error, trace};
+use tracing::{debug, error, trace, warn};
 
 use super::{LocatorPath, StorageAction};
 
+/// The prefix of a coordinator state file saved in bincode, which can't start a JSON file.
+const BINCODE_STATE_PREFIX: &[u8] = b"\0bincode\0";
+
 #[derive(Debug)]
 pub struct Disk {
     environment: Environment,
     resolver: DiskResolver,
+    /// The cipher of the contribution files, if they are encrypted at rest.
+    cipher: Option<StorageCipher>,
 }
 
 impl Disk {
+    /// Returns the path of the given locator in the storage of the given environment, without loading the storage.
+    pub fn locator_path(environment: &Environment, locator: &Locator) -> Result<LocatorPath, CoordinatorError> {
+        DiskResolver::new(environment.local_base_directory()).to_path(locator)
+    }
+
     /// Loads a new instance of `Disk`.
     pub fn load(environment: &Environment) -> Result<Self, CoordinatorError>
     where
@@ -49,13 +61,17 @@ impl Disk {
         let mut storage = Self {
             environment: environment.clone(),
             resolver: DiskResolver::new(environment.local_base_directory()),
+            cipher: match environment.encrypt_contributions() {
+                true => Some(StorageCipher::from_env()?),
+                false => None,
+            },
         };
 
         // Create the coordinator state locator if it does not exist yet.
         if !storage.exists(&Locator::CoordinatorState) {
             storage.insert(
                 Locator::CoordinatorState,
-                Object::CoordinatorState(CoordinatorState::new(environment.clone())),
+                Object::CoordinatorState(CoordinatorState::new(environment.clone())?),
             )?;
         }
 
@@ -92,14 +108,17 @@ impl Disk {
         }
 
         // Open the file.
-        let file = OpenOptions::new()
+        let mut file = OpenOptions::new()
             .read(true)
             .write(true)
             .create_new(true)
             .open(locator_path)?;
 
-        // Set the initial file size.
-        file.set_len(size)?;
+        // Set the initial file size, encrypting the empty content if required.
+        match self.cipher_for(&locator) {
+            Some(cipher) => file.write_all(&cipher.encrypt(&vec![0u8; size as usize])?)?,
+            None => file.set_len(size)?,
+        }
 
         trace!("Initialized {}", self.to_path(&locator)?);
         Ok(())
@@ -138,7 +157,7 @@ impl Disk {
         Ok(fs::read(path)?)
     }
 
-    /// Retrieve the json encoded state file
+    /// Retrieve the json encoded state file, converted from bincode if needed
     pub fn get_coordinator_state(&self) -> Result<Vec<u8>, CoordinatorError> {
         // Check that the given locator exists in storage.
         if !self.exists(&Locator::CoordinatorState) {
@@ -150,8 +169,12 @@ impl Disk {
         }
 
         let path = self.to_path(&Locator::CoordinatorState)?;
+        let file_bytes = fs::read(path)?;
 
-        Ok(fs::read(path)?)
+        match file_bytes.strip_prefix(BINCODE_STATE_PREFIX) {
+            Some(_) => Ok(serde_json::to_vec_pretty(&Self::decode_coordinator_state(&file_bytes)?)?),
+            None => Ok(file_bytes),
+        }
     }
 
     /// Returns a copy of an object at the given locator in storage, if it exists.
@@ -166,11 +189,11 @@ impl Disk {
         }
 
         // read the file to a byte array
-        let file_bytes = fs::read(path)?;
+        let file_bytes = self.decrypt(locator, fs::read(path)?)?;
 
         let object = match locator {
             Locator::CoordinatorState => {
-                let coordinator_state: CoordinatorState = serde_json::from_slice(&file_bytes)?;
+                let coordinator_state = Self::decode_coordinator_state(&file_bytes)?;
                 Ok(Object::CoordinatorState(coordinator_state))
             }
             Locator::RoundHeight => {
@@ -248,6 +271,18 @@ impl Disk {
                 let summary: Vec<TrimmedContributionInfo> = serde_json::from_slice(&file_bytes)?;
                 Ok(Object::ContributionsInfoSummary(summary))
             }
+            Locator::FinishedParticipants { round_height: _ } => {
+                let participants: FinishedParticipants = serde_json::from_slice(&file_bytes)?;
+                Ok(Object::FinishedParticipants(participants))
+            }
+            Locator::CeremonyCompletion => {
+                let completion: CeremonyCompletion = serde_json::from_slice(&file_bytes)?;
+                Ok(Object::CeremonyCompletion(completion))
+            }
+            Locator::VerificationCache => {
+                let cache = VerificationCache::from_bytes(&file_bytes)?;
+                Ok(Object::VerificationCache(cache))
+            }
         };
 
         trace!("Fetched {}", self.to_path(locator)?);
@@ -285,9 +320,20 @@ impl Disk {
             return Err(CoordinatorError::StorageLocatorMissing);
         }
 
+        let bytes = match (self.cipher_for(locator), &object) {
+            (Some(cipher), _) => {
+                write_atomically(path.as_path(), &cipher.encrypt(&object.to_bytes())?)?;
+
+                trace!("Updated {}", self.to_path(&locator)?);
+                return Ok(());
+            }
+            (None, Object::CoordinatorState(state)) => self.encode_coordinator_state(state)?,
+            (None, _) => object.to_bytes(),
+        };
+
         let mut file = OpenOptions::new().write(true).open(path)?;
-        file.set_len(object.size())?;
-        file.write_all(&object.to_bytes())?;
+        file.set_len(bytes.len() as u64)?;
+        file.write_all(&bytes)?;
         // Sync all in-memory data to disk.
         file.flush()?;
 
@@ -295,6 +341,35 @@ impl Disk {
         Ok(())
     }
 
+    ///
+    /// Appends the given bytes to an existing object for the given locator in storage,
+    /// if it exists, instead of rewriting it. The object must not be encrypted at rest.
+    ///
+    pub fn append(&mut self, locator: &Locator, bytes: &[u8]) -> Result<(), CoordinatorError> {
+        let path = self.to_path(locator)?;
+        trace!("Appending to {}", path);
+
+        // Check that the given locator exists in storage.
+        if !self.exists(locator) {
+            error!("Locator missing in call to append() in storage.");
+            return Err(CoordinatorError::StorageLocatorMissing);
+        }
+
+        // Check that the object is stored in plain text.
+        if self.cipher_for(locator).is_some() {
+            error!("Locator in call to append() is encrypted in storage.");
+            return Err(CoordinatorError::StorageFailed);
+        }
+
+        let mut file = OpenOptions::new().append(true).open(path)?;
+        file.write_all(bytes)?;
+        // Sync all in-memory data to disk.
+        file.flush()?;
+
+        trace!("Appended to {}", self.to_path(&locator)?);
+        Ok(())
+    }
+
     /// Copies an object from the given source locator to the given destination locator.
     pub fn copy(&mut self, source_locator: &Locator, destination_locator: &Locator) -> Result<(), CoordinatorError> {
         trace!(
@@ -362,8 +437,73 @@ impl Disk {
         // Open the file.
         let file = OpenOptions::new().read(true).write(true).open(path.clone())?;
 
+        // Strip the encryption overhead from the size of the file.
+        let size = match self.cipher_for(locator) {
+            Some(_) => file.metadata()?.len().saturating_sub(StorageCipher::OVERHEAD),
+            None => file.metadata()?.len(),
+        };
+
         trace!("Fetched size of {}", path);
-        Ok(file.metadata()?.len())
+        Ok(size)
+    }
+
+    /// Encodes the coordinator state in the format of the environment.
+    fn encode_coordinator_state(&self, state: &CoordinatorState) -> Result<Vec<u8>, CoordinatorError> {
+        match self.environment.state_format() {
+            StateFormat::Json => Ok(serde_json::to_vec_pretty(state)?),
+            StateFormat::Bincode => {
+                let mut bytes = BINCODE_STATE_PREFIX.to_vec();
+                bincode::serialize_into(&mut bytes, state)?;
+                Ok(bytes)
+            }
+        }
+    }
+
+    /// Decodes the coordinator state, whichever format it was saved in.
+    fn decode_coordinator_state(bytes: &[u8]) -> Result<CoordinatorState, CoordinatorError> {
+        match bytes.strip_prefix(BINCODE_STATE_PREFIX) {
+            Some(bytes) => Ok(bincode::deserialize(bytes)?),
+            None => Ok(serde_json::from_slice(bytes)?),
+        }
+    }
+
+    /// Copies the object at the given locator to the given writer, returning its size. The objects which are not
+    /// encrypted at rest are streamed from their file instead of being loaded into memory.
+    pub fn copy_to<W: Write + ?Sized>(&self, locator: &Locator, writer: &mut W) -> Result<u64, CoordinatorError> {
+        if self.cipher_for(locator).is_some() {
+            let data = self.reader(locator)?;
+            writer.write_all(&data)?;
+            return Ok(data.len() as u64);
+        }
+
+        // Check that the locator exists in storage.
+        let path = self.to_path(locator)?;
+        if !self.exists(locator) {
+            error!("Locator {} missing in call to copy_to() in storage.", path);
+            return Err(CoordinatorError::StorageLocatorMissing);
+        }
+
+        let mut file = File::open(path)?;
+        Ok(std::io::copy(&mut file, writer)?)
+    }
+
+    /// Returns the cipher for the given locator, if its object is encrypted at rest.
+    fn cipher_for(&self, locator: &Locator) -> Option<&StorageCipher> {
+        match locator {
+            Locator::ContributionFile(_) => self.cipher.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Decrypts the data read from the given locator, if its object is encrypted at rest.
+    fn decrypt(&self, locator: &Locator, data: Vec<u8>) -> Result<Vec<u8>, CoordinatorError> {
+        match self.cipher_for(locator) {
+            Some(cipher) => cipher.decrypt(&data).map_err(|e| {
+                error!("Unable to decrypt {:?}", locator);
+                e
+            }),
+            None => Ok(data),
+        }
     }
 
     /// Process a [StorageAction] which mutates the storage.
@@ -480,6 +620,88 @@ impl StorageLocator for Disk {
     }
 }
 
+impl Storage for Disk {
+    #[inline]
+    fn initialize(&mut self, locator: Locator, size: u64) -> Result<(), CoordinatorError> {
+        Disk::initialize(self, locator, size)
+    }
+
+    #[inline]
+    fn exists(&self, locator: &Locator) -> bool {
+        Disk::exists(self, locator)
+    }
+
+    #[inline]
+    fn get(&self, locator: &Locator) -> Result<Object, CoordinatorError> {
+        Disk::get(self, locator)
+    }
+
+    #[inline]
+    fn insert(&mut self, locator: Locator, object: Object) -> Result<(), CoordinatorError> {
+        Disk::insert(self, locator, object)
+    }
+
+    #[inline]
+    fn update(&mut self, locator: &Locator, object: Object) -> Result<(), CoordinatorError> {
+        Disk::update(self, locator, object)
+    }
+
+    #[inline]
+    fn copy(&mut self, source_locator: &Locator, destination_locator: &Locator) -> Result<(), CoordinatorError> {
+        Disk::copy(self, source_locator, destination_locator)
+    }
+
+    #[inline]
+    fn remove(&mut self, locator: &Locator) -> Result<(), CoordinatorError> {
+        Disk::remove(self, locator)
+    }
+
+    #[inline]
+    fn size(&self, locator: &Locator) -> Result<u64, CoordinatorError> {
+        Disk::size(self, locator)
+    }
+
+    #[inline]
+    fn process(&mut self, action: StorageAction) -> Result<()> {
+        Disk::process(self, action)
+    }
+
+    #[inline]
+    fn reader(&self, locator: &Locator) -> Result<Box<dyn ObjectReader>, CoordinatorError> {
+        Ok(Box::new(Disk::reader(self, locator)?))
+    }
+
+    #[inline]
+    fn writer(&self, locator: &Locator) -> Result<Box<dyn ObjectWriter>, CoordinatorError> {
+        Ok(Box::new(Disk::writer(self, locator)?))
+    }
+
+    #[inline]
+    fn append(&mut self, locator: &Locator, bytes: &[u8]) -> Result<(), CoordinatorError> {
+        Disk::append(self, locator, bytes)
+    }
+
+    #[inline]
+    fn copy_to(&self, locator: &Locator, writer: &mut dyn Write) -> Result<u64, CoordinatorError> {
+        Disk::copy_to(self, locator, writer)
+    }
+
+    #[inline]
+    fn get_contributions_summary(&self) -> Result<Vec<u8>, CoordinatorError> {
+        Disk::get_contributions_summary(self)
+    }
+
+    #[inline]
+    fn get_coordinator_state(&self) -> Result<Vec<u8>, CoordinatorError> {
+        Disk::get_coordinator_state(self)
+    }
+
+    #[inline]
+    fn clear_info_files(&mut self, round_height: u64) {
+        Disk::clear_info_files(self, round_height)
+    }
+}
+
 pub struct DiskObjectReader {
     data: Vec<u8>,
 }
@@ -502,42 +724,117 @@ impl ObjectReader for DiskObjectReader {}
 
 pub struct DiskObjectWriter {
     _file: File,
-    memmap: memmap::MmapMut,
+    data: DiskObjectData,
+    /// Whether the content was borrowed mutably since the last flush of an encrypted file.
+    dirty: AtomicBool,
+}
+
+/// Writes the given bytes to a temporary file next to the given path, then renames it
+/// over the path, so that a crash never leaves a partially written file behind.
+fn write_atomically(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+
+    let mut file = File::create(&temp_path)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    fs::rename(&temp_path, path)
+}
+
+/// The content of the file behind a [DiskObjectWriter].
+enum DiskObjectData {
+    /// The file mapped in memory.
+    Mapped(memmap::MmapMut),
+    /// The decrypted content of a file encrypted at rest, which is
+    /// encrypted back to the file on flush and drop.
+    Encrypted {
+        plaintext: Vec<u8>,
+        cipher: StorageCipher,
+        path: LocatorPath,
+    },
+}
+
+impl Deref for DiskObjectData {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            DiskObjectData::Mapped(memmap) => &memmap[..],
+            DiskObjectData::Encrypted { plaintext, .. } => &plaintext[..],
+        }
+    }
+}
+
+impl DerefMut for DiskObjectData {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            DiskObjectData::Mapped(memmap) => &mut memmap[..],
+            DiskObjectData::Encrypted { plaintext, .. } => &mut plaintext[..],
+        }
+    }
 }
 
 impl Deref for DiskObjectWriter {
     type Target = [u8];
 
     fn deref(&self) -> &Self::Target {
-        &*self.memmap
+        &*self.data
     }
 }
 
 impl DerefMut for DiskObjectWriter {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut *self.memmap
+        *self.dirty.get_mut() = true;
+        &mut *self.data
     }
 }
 
 impl AsMut<[u8]> for DiskObjectWriter {
     fn as_mut(&mut self) -> &mut [u8] {
-        self.memmap.as_mut()
+        *self.dirty.get_mut() = true;
+        &mut *self.data
     }
 }
 
 impl ObjectWriter for DiskObjectWriter {
     fn flush(&self) -> std::io::Result<()> {
-        self.memmap.flush()
+        match &self.data {
+            DiskObjectData::Mapped(memmap) => memmap.flush(),
+            DiskObjectData::Encrypted {
+                plaintext,
+                cipher,
+                path,
+            } => {
+                let encrypted = cipher
+                    .encrypt(plaintext)
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, format!("{:?}", e)))?;
+                write_atomically(path.as_path(), &encrypted)?;
+                self.dirty.store(false, Ordering::SeqCst);
+                Ok(())
+            }
+        }
     }
 }
 
-impl StorageObject for Disk {
-    type Reader = DiskObjectReader;
-    type Writer = DiskObjectWriter;
+impl Drop for DiskObjectWriter {
+    fn drop(&mut self) {
+        // The writes to an encrypted file are only persisted once encrypted. They should be
+        // flushed explicitly to handle the errors, this is a last resort.
+        if let DiskObjectData::Encrypted { ref path, .. } = self.data {
+            if self.dirty.load(Ordering::SeqCst) {
+                warn!("The encrypted file {} was not flushed before being dropped", path);
+                if let Err(e) = self.flush() {
+                    error!("Unable to write the encrypted file {} - {}", path, e);
+                }
+            }
+        }
+    }
+}
 
+impl Disk {
     /// Returns an object reader for the given locator.
     #[inline]
-    fn reader<'a>(&self, locator: &Locator) -> Result<Self::Reader, CoordinatorError> {
+    pub fn reader(&self, locator: &Locator) -> Result<DiskObjectReader, CoordinatorError> {
         let path = self.to_path(&locator)?;
 
         // Check that the locator exists in storage.
@@ -553,6 +850,7 @@ impl StorageObject for Disk {
         file.file()
             .read_to_end(&mut data)
             .map_err(|e| CoordinatorError::IOError(e))?;
+        let data = self.decrypt(locator, data)?;
 
         match locator {
             Locator::RoundFile { round_height } => {
@@ -597,7 +895,7 @@ impl StorageObject for Disk {
 
     /// Returns an object writer for the given locator.
     #[inline]
-    fn writer(&self, locator: &Locator) -> Result<Self::Writer, CoordinatorError> {
+    pub fn writer(&self, locator: &Locator) -> Result<DiskObjectWriter, CoordinatorError> {
         let path = self.to_path(&locator)?;
 
         // Check that the locator exists in storage.
@@ -606,15 +904,22 @@ impl StorageObject for Disk {
             return Err(CoordinatorError::StorageLocatorMissing);
         }
 
-        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        let file = OpenOptions::new().read(true).write(true).open(path.clone())?;
 
-        // Load the file into memory.
-        let memmap = unsafe { MmapOptions::new().map_mut(&file.file())? };
+        // Load the file into memory, decrypting it if required.
+        let data = match self.cipher_for(locator) {
+            Some(cipher) => DiskObjectData::Encrypted {
+                plaintext: self.decrypt(locator, fs::read(path.clone())?)?,
+                cipher: cipher.clone(),
+                path,
+            },
+            None => DiskObjectData::Mapped(unsafe { MmapOptions::new().map_mut(&file.file())? }),
+        };
         match locator {
             Locator::RoundFile { round_height: _ } => {
                 // Check that the round size is correct.
                 let expected_size = Object::round_file_size(&self.environment);
-                let found_size = memmap.len() as u64;
+                let found_size = data.len() as u64;
                 debug!("File size of {} is {}", self.to_path(locator)?, found_size);
                 if found_size != expected_size {
                     error!(
@@ -630,7 +935,7 @@ impl StorageObject for Disk {
                     contribution_locator.round_height(),
                     contribution_locator.contribution_id(),
                 );
-                let found_size = memmap.len() as u64;
+                let found_size = data.len() as u64;
                 debug!("File size of {} is {}", self.to_path(locator)?, found_size);
                 if found_size != expected_size {
                     error!(
@@ -643,18 +948,22 @@ impl StorageObject for Disk {
             _ => {}
         }
 
-        Ok(DiskObjectWriter { _file: file, memmap })
+        Ok(DiskObjectWriter {
+            _file: file,
+            data,
+            dirty: AtomicBool::new(false),
+        })
     }
 }
 
 #[derive(Debug)]
-struct DiskResolver {
+pub(crate) struct DiskResolver {
     base: String,
 }
 
 impl DiskResolver {
     #[inline]
-    fn new(base: &str) -> Self {
+    pub(crate) fn new(base: &str) -> Self {
         Self { base: base.to_string() }
     }
 }
@@ -714,6 +1023,11 @@ impl StorageLocator for DiskResolver {
                 self.base, round_height
             ),
             Locator::ContributionsInfoSummary => format!("{}/contributors.json", self.base),
+            Locator::FinishedParticipants { round_height } => {
+                format!("{}/finished_participants.json", self.round_directory(*round_height))
+            }
+            Locator::CeremonyCompletion => format!("{}/completion.json", self.base),
+            Locator::VerificationCache => format!("{}/verification_cache.json", self.base),
         };
         // Sanitize the path.
         LocatorPath::try_from(Path::new(&path))
@@ -759,6 +1073,16 @@ impl StorageLocator for DiskResolver {
             return Ok(Locator::RoundHeight);
         }
 
+        // Check if it matches the ceremony completion file.
+        if key == "completion.json" {
+            return Ok(Locator::CeremonyCompletion);
+        }
+
+        // Check if it matches the verification cache file.
+        if key == "verification_cache.json" {
+            return Ok(Locator::VerificationCache);
+        }
+
         // Parse the key into its components.
         if let Some((round, remainder)) = key.splitn(2, "/").collect_tuple() {
             // Check if it resembles the round directory.
@@ -784,6 +1108,11 @@ impl StorageLocator for DiskResolver {
                         return Ok(Locator::RoundFile { round_height });
                     }
 
+                    // Check if it matches the archived finished participants.
+                    if remainder == "finished_participants.json" {
+                        return Ok(Locator::FinishedParticipants { round_height });
+                    }
+
                     // Parse the path into its components.
                     if let Some((chunk, path)) = remainder.splitn(2, "/").collect_tuple() {
                         // Check if it resembles the chunk directory.
@@ -901,7 +1230,50 @@ impl DiskResolver {
 #[cfg(test)]
 mod tests {
     use super::*;
-    // use crate::testing::prelude::*;
+    use crate::{
+        environment::{Parameters, Testing},
+        testing::prelude::*,
+    };
+
+    #[test]
+    #[serial]
+    fn test_coordinator_state_formats() {
+        initialize_test_environment(&TEST_ENVIRONMENT);
+        let bincode_environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .state_format(StateFormat::Bincode)
+            .into();
+
+        let mut state = CoordinatorState::new(TEST_ENVIRONMENT.clone()).unwrap();
+        state.initialize(2);
+        let expected = serde_json::to_value(&state).unwrap();
+
+        // The state is saved in bincode, and loaded back by a storage saving in json.
+        let mut disk = Disk::load(&bincode_environment).unwrap();
+        state.save(&mut disk).unwrap();
+        let file_bytes = fs::read(disk.to_path(&Locator::CoordinatorState).unwrap()).unwrap();
+        assert!(file_bytes.starts_with(BINCODE_STATE_PREFIX));
+
+        let mut disk = Disk::load(&TEST_ENVIRONMENT).unwrap();
+        match disk.get(&Locator::CoordinatorState).unwrap() {
+            Object::CoordinatorState(saved) => assert_eq!(expected, serde_json::to_value(&saved).unwrap()),
+            _ => panic!("Expected the coordinator state"),
+        }
+        let json: serde_json::Value = serde_json::from_slice(&disk.get_coordinator_state().unwrap()).unwrap();
+        assert_eq!(expected, json);
+
+        // The next save switches the file back to json.
+        state.save(&mut disk).unwrap();
+        let file_bytes = disk.get_coordinator_state().unwrap();
+        assert_eq!(expected, serde_json::from_slice::<serde_json::Value>(&file_bytes).unwrap());
+        match Disk::load(&bincode_environment)
+            .unwrap()
+            .get(&Locator::CoordinatorState)
+            .unwrap()
+        {
+            Object::CoordinatorState(saved) => assert_eq!(expected, serde_json::to_value(&saved).unwrap()),
+            _ => panic!("Expected the coordinator state"),
+        }
+    }
 
     #[test]
     fn test_to_path_coordinator_state() {
@@ -945,6 +1317,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_to_path_ceremony_completion() {
+        let locator = DiskResolver::new("./transcript/test");
+
+        assert_eq!(
+            LocatorPath::from("./transcript/test/completion.json"),
+            locator.to_path(&Locator::CeremonyCompletion).unwrap()
+        );
+        assert_eq!(
+            Locator::CeremonyCompletion,
+            locator.to_locator(&"./transcript/test/completion.json".into()).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_to_path_verification_cache() {
+        let locator = DiskResolver::new("./transcript/test");
+
+        assert_eq!(
+            LocatorPath::from("./transcript/test/verification_cache.json"),
+            locator.to_path(&Locator::VerificationCache).unwrap()
+        );
+        assert_eq!(
+            Locator::VerificationCache,
+            locator
+                .to_locator(&"./transcript/test/verification_cache.json".into())
+                .unwrap(),
+        );
+    }
+
     #[test]
     fn test_to_path_round_state() {
         let locator = DiskResolver::new("./transcript/test");
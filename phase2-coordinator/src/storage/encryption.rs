@@ -0,0 +1,119 @@
+use crate::CoordinatorError;
+
+use aes_gcm::{
+    aead::{Aead, NewAead},
+    Aes256Gcm, Key, Nonce,
+};
+use rand::Rng;
+use std::fmt;
+use tracing::error;
+
+/// The env variable holding the hex encoded key used to encrypt the contribution files at rest.
+pub const STORAGE_KEY_ENV: &str = "NAMADA_MPC_STORAGE_KEY";
+
+/// The length in bytes of the key.
+const KEY_LENGTH: usize = 32;
+/// The length in bytes of the nonce prepended to the encrypted data.
+const NONCE_LENGTH: usize = 12;
+/// The length in bytes of the authentication tag appended to the encrypted data.
+const TAG_LENGTH: usize = 16;
+
+/// Encrypts and decrypts the contribution files stored on disk with AES-256-GCM.
+///
+/// The encrypted data is laid out as `nonce || ciphertext || tag`, with a
+/// fresh random nonce for every write.
+#[derive(Clone)]
+pub struct StorageCipher {
+    cipher: Aes256Gcm,
+}
+
+impl StorageCipher {
+    /// The number of bytes added by the encryption to the size of the data.
+    pub const OVERHEAD: u64 = (NONCE_LENGTH + TAG_LENGTH) as u64;
+
+    /// Creates a new cipher from the given key.
+    pub fn new(key: &[u8]) -> Result<Self, CoordinatorError> {
+        if key.len() != KEY_LENGTH {
+            error!("The storage key should be {} bytes but found {}", KEY_LENGTH, key.len());
+            return Err(CoordinatorError::StorageEncryptionKeyInvalid);
+        }
+
+        Ok(Self {
+            cipher: Aes256Gcm::new(Key::from_slice(key)),
+        })
+    }
+
+    /// Creates a new cipher from the hex encoded key in the [STORAGE_KEY_ENV] env variable.
+    pub fn from_env() -> Result<Self, CoordinatorError> {
+        let key = std::env::var(STORAGE_KEY_ENV).map_err(|_| {
+            error!("Missing env {} required to encrypt the storage", STORAGE_KEY_ENV);
+            CoordinatorError::StorageEncryptionKeyInvalid
+        })?;
+
+        Self::new(&hex::decode(key.trim()).map_err(|_| CoordinatorError::StorageEncryptionKeyInvalid)?)
+    }
+
+    /// Encrypts the given data.
+    pub fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CoordinatorError> {
+        let mut nonce = [0u8; NONCE_LENGTH];
+        rand::thread_rng().fill(&mut nonce[..]);
+
+        let ciphertext = self
+            .cipher
+            .encrypt(Nonce::from_slice(&nonce), data)
+            .map_err(|_| CoordinatorError::StorageEncryptionFailed)?;
+
+        let mut encrypted = Vec::with_capacity(NONCE_LENGTH + ciphertext.len());
+        encrypted.extend_from_slice(&nonce);
+        encrypted.extend_from_slice(&ciphertext);
+
+        Ok(encrypted)
+    }
+
+    /// Decrypts the given data, checking that it was not tampered with.
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CoordinatorError> {
+        if data.len() < Self::OVERHEAD as usize {
+            return Err(CoordinatorError::StorageEncryptionFailed);
+        }
+
+        let (nonce, ciphertext) = data.split_at(NONCE_LENGTH);
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| CoordinatorError::StorageEncryptionFailed)
+    }
+}
+
+impl fmt::Debug for StorageCipher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Never expose the key
+        f.write_str("StorageCipher")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let cipher = StorageCipher::new(&[7u8; KEY_LENGTH]).unwrap();
+        let data = b"contribution".to_vec();
+
+        let encrypted = cipher.encrypt(&data).unwrap();
+        assert_eq!(data.len() as u64 + StorageCipher::OVERHEAD, encrypted.len() as u64);
+        assert_ne!(data, encrypted[NONCE_LENGTH..NONCE_LENGTH + data.len()].to_vec());
+        assert_eq!(data, cipher.decrypt(&encrypted).unwrap());
+
+        // Tampered data
+        let mut tampered = encrypted.clone();
+        tampered[NONCE_LENGTH] ^= 1;
+        assert!(cipher.decrypt(&tampered).is_err());
+
+        // Wrong key
+        let other_cipher = StorageCipher::new(&[8u8; KEY_LENGTH]).unwrap();
+        assert!(other_cipher.decrypt(&encrypted).is_err());
+
+        // Invalid key
+        assert!(StorageCipher::new(&[7u8; 16]).is_err());
+    }
+}
@@ -0,0 +1,441 @@
+use crate::{
+    environment::Environment,
+    storage::{
+        DiskResolver, Locator, LocatorPath, Object, ObjectReader, ObjectWriter, Storage, StorageAction, StorageLocator,
+    },
+    CoordinatorError, CoordinatorState,
+};
+
+use anyhow::Result;
+use std::{
+    collections::HashMap,
+    io::Write,
+    ops::{Deref, DerefMut},
+    sync::{Arc, RwLock},
+};
+use tracing::{error, trace, warn};
+
+/// The encoded objects in a [MemoryStorage], shared with its writers.
+type MemoryObjects = Arc<RwLock<HashMap<Locator, Vec<u8>>>>;
+
+/// A storage keeping the objects of the ceremony in memory.
+///
+/// The locators resolve to the same paths as the ones of [Disk](super::Disk) for
+/// the same environment, but nothing is ever written to the file system.
+#[derive(Debug)]
+pub struct MemoryStorage {
+    resolver: DiskResolver,
+    /// The objects in storage, encoded as they would be in their files.
+    objects: MemoryObjects,
+}
+
+impl MemoryStorage {
+    /// Loads a new instance of `MemoryStorage`.
+    pub fn load(environment: &Environment) -> Result<Self, CoordinatorError> {
+        trace!("Loading memory storage");
+
+        let mut storage = Self {
+            resolver: DiskResolver::new(environment.local_base_directory()),
+            objects: Arc::new(RwLock::new(HashMap::new())),
+        };
+
+        storage.insert(
+            Locator::CoordinatorState,
+            Object::CoordinatorState(CoordinatorState::new(environment.clone())?),
+        )?;
+        storage.insert(
+            Locator::ContributionsInfoSummary,
+            Object::ContributionsInfoSummary(vec![]),
+        )?;
+
+        trace!("Loaded memory storage");
+        Ok(storage)
+    }
+
+    /// Returns the bytes of the object at the given locator, if it exists.
+    fn bytes(&self, locator: &Locator) -> Option<Vec<u8>> {
+        self.objects.read().unwrap().get(locator).cloned()
+    }
+
+    /// Returns `true` if the given locator is cleared when resetting the round at the given height.
+    fn is_round_file(locator: &Locator, round_height: u64) -> bool {
+        match locator {
+            Locator::RoundFile { round_height: height } | Locator::FinishedParticipants { round_height: height } => {
+                *height == round_height || *height == round_height + 1
+            }
+            // The initial contributions of the round are kept.
+            Locator::ContributionFile(contribution_locator) => {
+                let height = contribution_locator.round_height();
+                height == round_height + 1 || (height == round_height && contribution_locator.contribution_id() != 0)
+            }
+            Locator::ContributionFileSignature(signature_locator) => {
+                let height = signature_locator.round_height();
+                height == round_height + 1 || (height == round_height && signature_locator.contribution_id() != 0)
+            }
+            _ => false,
+        }
+    }
+
+    /// Clears all the objects related to a round, as [Disk](super::Disk) does on a round reset.
+    fn clear_round_files(&mut self, round_height: u64) {
+        self.objects
+            .write()
+            .unwrap()
+            .retain(|locator, _| !Self::is_round_file(locator, round_height));
+
+        self.clear_info_files(round_height);
+    }
+}
+
+impl StorageLocator for MemoryStorage {
+    #[inline]
+    fn to_path(&self, locator: &Locator) -> Result<LocatorPath, CoordinatorError> {
+        self.resolver.to_path(locator)
+    }
+
+    #[inline]
+    fn to_locator(&self, path: &LocatorPath) -> Result<Locator, CoordinatorError> {
+        self.resolver.to_locator(path)
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn initialize(&mut self, locator: Locator, size: u64) -> Result<(), CoordinatorError> {
+        if self.exists(&locator) {
+            error!("Locator {:?} in call to initialize() already exists in storage.", locator);
+            return Err(CoordinatorError::StorageLocatorAlreadyExists);
+        }
+
+        self.objects.write().unwrap().insert(locator, vec![0u8; size as usize]);
+        Ok(())
+    }
+
+    fn exists(&self, locator: &Locator) -> bool {
+        self.objects.read().unwrap().contains_key(locator)
+    }
+
+    fn get(&self, locator: &Locator) -> Result<Object, CoordinatorError> {
+        match self.bytes(locator) {
+            Some(bytes) => Object::from_bytes(locator, &bytes),
+            None => {
+                error!("Locator missing in call to get() in storage - {:?}", locator);
+                Err(CoordinatorError::StorageLocatorMissing)
+            }
+        }
+    }
+
+    fn insert(&mut self, locator: Locator, object: Object) -> Result<(), CoordinatorError> {
+        if self.exists(&locator) {
+            error!("Locator in call to insert() already exists in storage.");
+            return Err(CoordinatorError::StorageLocatorAlreadyExists);
+        }
+
+        self.objects.write().unwrap().insert(locator, object.to_bytes());
+        Ok(())
+    }
+
+    fn update(&mut self, locator: &Locator, object: Object) -> Result<(), CoordinatorError> {
+        match self.objects.write().unwrap().get_mut(locator) {
+            Some(bytes) => {
+                *bytes = object.to_bytes();
+                Ok(())
+            }
+            None => {
+                error!("Locator missing in call to update() in storage.");
+                Err(CoordinatorError::StorageLocatorMissing)
+            }
+        }
+    }
+
+    fn copy(&mut self, source_locator: &Locator, destination_locator: &Locator) -> Result<(), CoordinatorError> {
+        let bytes = match self.bytes(source_locator) {
+            Some(bytes) => bytes,
+            None => {
+                error!("Source locator missing in call to copy() in storage.");
+                return Err(CoordinatorError::StorageLocatorMissing);
+            }
+        };
+
+        if self.exists(destination_locator) {
+            error!("Destination locator in call to copy() already exists in storage.");
+            return Err(CoordinatorError::StorageLocatorAlreadyExists);
+        }
+
+        self.objects.write().unwrap().insert(destination_locator.clone(), bytes);
+        Ok(())
+    }
+
+    fn remove(&mut self, locator: &Locator) -> Result<(), CoordinatorError> {
+        match self.objects.write().unwrap().remove(locator) {
+            Some(_) => Ok(()),
+            None => {
+                error!("Locator in call to remove() doesn't exist in storage.");
+                Err(CoordinatorError::StorageLocatorMissing)
+            }
+        }
+    }
+
+    fn size(&self, locator: &Locator) -> Result<u64, CoordinatorError> {
+        match self.objects.read().unwrap().get(locator) {
+            Some(bytes) => Ok(bytes.len() as u64),
+            None => {
+                error!("Locator missing in call to size() in storage.");
+                Err(CoordinatorError::StorageLocatorMissing)
+            }
+        }
+    }
+
+    fn process(&mut self, action: StorageAction) -> Result<()> {
+        match action {
+            StorageAction::Remove(remove_action) => {
+                let locator = remove_action.try_into_locator(self)?;
+                Ok(self.remove(&locator)?)
+            }
+            StorageAction::Update(update_action) => Ok(self.update(&update_action.locator, update_action.object)?),
+            StorageAction::ClearRoundFiles(round_height) => Ok(self.clear_round_files(round_height)),
+        }
+    }
+
+    fn reader(&self, locator: &Locator) -> Result<Box<dyn ObjectReader>, CoordinatorError> {
+        match self.bytes(locator) {
+            Some(data) => Ok(Box::new(MemoryObjectReader { data })),
+            None => {
+                error!("Locator {:?} missing in call to reader() in storage.", locator);
+                Err(CoordinatorError::StorageLocatorMissing)
+            }
+        }
+    }
+
+    fn writer(&self, locator: &Locator) -> Result<Box<dyn ObjectWriter>, CoordinatorError> {
+        match self.bytes(locator) {
+            Some(data) => Ok(Box::new(MemoryObjectWriter {
+                objects: self.objects.clone(),
+                locator: locator.clone(),
+                data,
+            })),
+            None => {
+                error!("Locator {:?} missing in call to writer() in storage.", locator);
+                Err(CoordinatorError::StorageLocatorMissing)
+            }
+        }
+    }
+
+    fn append(&mut self, locator: &Locator, bytes: &[u8]) -> Result<(), CoordinatorError> {
+        match self.objects.write().unwrap().get_mut(locator) {
+            Some(data) => {
+                data.extend_from_slice(bytes);
+                Ok(())
+            }
+            None => {
+                error!("Locator missing in call to append() in storage.");
+                Err(CoordinatorError::StorageLocatorMissing)
+            }
+        }
+    }
+
+    fn copy_to(&self, locator: &Locator, writer: &mut dyn Write) -> Result<u64, CoordinatorError> {
+        let data = self.reader(locator)?;
+        writer.write_all(&data)?;
+        Ok(data.len() as u64)
+    }
+
+    fn get_contributions_summary(&self) -> Result<Vec<u8>, CoordinatorError> {
+        Ok(self.reader(&Locator::ContributionsInfoSummary)?.to_vec())
+    }
+
+    fn get_coordinator_state(&self) -> Result<Vec<u8>, CoordinatorError> {
+        Ok(self.reader(&Locator::CoordinatorState)?.to_vec())
+    }
+
+    fn clear_info_files(&mut self, round_height: u64) {
+        if let Err(e) = self.remove(&Locator::ContributionInfoFile { round_height }) {
+            warn!("Could not delete contribution file: {}", e);
+        }
+
+        // Trim the contributions summary of the cleared round.
+        match self.get(&Locator::ContributionsInfoSummary) {
+            Ok(Object::ContributionsInfoSummary(mut summary)) => {
+                if summary.last().map(|contribution| contribution.ceremony_round()) == Some(round_height) {
+                    summary.pop();
+                    if let Err(e) = self.update(
+                        &Locator::ContributionsInfoSummary,
+                        Object::ContributionsInfoSummary(summary),
+                    ) {
+                        warn!("Could not update contribution summary file: {}", e);
+                    }
+                }
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Could not retrieve contribution summary file: {}", e),
+        }
+    }
+}
+
+/// A copy of an object of a [MemoryStorage].
+pub struct MemoryObjectReader {
+    data: Vec<u8>,
+}
+
+impl Deref for MemoryObjectReader {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &*self.data
+    }
+}
+
+impl AsRef<[u8]> for MemoryObjectReader {
+    fn as_ref(&self) -> &[u8] {
+        self.data.as_ref()
+    }
+}
+
+impl ObjectReader for MemoryObjectReader {}
+
+/// A copy of an object of a [MemoryStorage], written back to the storage on flush and drop.
+pub struct MemoryObjectWriter {
+    objects: MemoryObjects,
+    locator: Locator,
+    data: Vec<u8>,
+}
+
+impl Deref for MemoryObjectWriter {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &*self.data
+    }
+}
+
+impl DerefMut for MemoryObjectWriter {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut *self.data
+    }
+}
+
+impl AsMut<[u8]> for MemoryObjectWriter {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut *self.data
+    }
+}
+
+impl ObjectWriter for MemoryObjectWriter {
+    fn flush(&self) -> std::io::Result<()> {
+        // The object may have been removed from the storage in the meantime.
+        if let Some(bytes) = self.objects.write().unwrap().get_mut(&self.locator) {
+            *bytes = self.data.clone();
+        }
+        Ok(())
+    }
+}
+
+impl Drop for MemoryObjectWriter {
+    fn drop(&mut self) {
+        if let Some(bytes) = self.objects.write().unwrap().get_mut(&self.locator) {
+            *bytes = std::mem::take(&mut self.data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        storage::{ContributionLocator, Disk, RemoveAction},
+        testing::prelude::*,
+    };
+
+    #[test]
+    #[serial]
+    fn test_memory_storage_round_trip() {
+        let mut storage = MemoryStorage::load(&TEST_ENVIRONMENT).unwrap();
+
+        let locator = Locator::RoundHeight;
+        assert!(matches!(
+            storage.update(&locator, Object::RoundHeight(1)),
+            Err(CoordinatorError::StorageLocatorMissing)
+        ));
+        storage.insert(locator, Object::RoundHeight(1)).unwrap();
+        storage.update(&locator, Object::RoundHeight(2)).unwrap();
+        assert!(matches!(storage.get(&locator), Ok(Object::RoundHeight(2))));
+
+        // The state is saved through the storage trait.
+        let mut state = CoordinatorState::new(TEST_ENVIRONMENT.clone()).unwrap();
+        state.initialize(2);
+        state.save(&mut storage).unwrap();
+        match storage.get(&Locator::CoordinatorState).unwrap() {
+            Object::CoordinatorState(saved) => assert_eq!(
+                serde_json::to_value(&state).unwrap(),
+                serde_json::to_value(&saved).unwrap()
+            ),
+            _ => panic!("Expected the coordinator state"),
+        }
+
+        storage.process(StorageAction::Remove(RemoveAction::new(locator))).unwrap();
+        assert!(!storage.exists(&locator));
+    }
+
+    #[test]
+    #[serial]
+    fn test_memory_storage_clear_round_files() {
+        let mut storage = MemoryStorage::load(&TEST_ENVIRONMENT).unwrap();
+
+        let initial = Locator::ContributionFile(ContributionLocator::new(1, 0, 0, true));
+        let contribution = Locator::ContributionFile(ContributionLocator::new(1, 0, 1, false));
+        let next_round = Locator::ContributionFile(ContributionLocator::new(2, 0, 0, true));
+        for locator in [initial, contribution, next_round] {
+            storage.initialize(locator, 8).unwrap();
+        }
+        assert_eq!(8, storage.size(&contribution).unwrap());
+
+        storage.process(StorageAction::ClearRoundFiles(1)).unwrap();
+        assert!(storage.exists(&initial));
+        assert!(!storage.exists(&contribution));
+        assert!(!storage.exists(&next_round));
+    }
+
+    #[test]
+    #[serial]
+    fn test_memory_storage_writer() {
+        let mut storage = MemoryStorage::load(&TEST_ENVIRONMENT).unwrap();
+
+        let locator = Locator::ContributionFile(ContributionLocator::new(1, 0, 1, false));
+        storage.initialize(locator, 4).unwrap();
+
+        let mut writer = storage.writer(&locator).unwrap();
+        writer.copy_from_slice(&[1, 2, 3, 4]);
+        writer.flush().unwrap();
+        assert_eq!(&[1, 2, 3, 4], &storage.reader(&locator).unwrap()[..]);
+
+        // The writes are also persisted when the writer is dropped.
+        writer[0] = 5;
+        drop(writer);
+        assert_eq!(&[5, 2, 3, 4], &storage.reader(&locator).unwrap()[..]);
+
+        storage.append(&locator, &[6]).unwrap();
+        let mut copy = Vec::new();
+        assert_eq!(5, storage.copy_to(&locator, &mut copy).unwrap());
+        assert_eq!(vec![5, 2, 3, 4, 6], copy);
+    }
+
+    #[test]
+    #[serial]
+    fn test_memory_storage_locators_match_disk() {
+        initialize_test_environment(&TEST_ENVIRONMENT);
+
+        let disk = Disk::load(&TEST_ENVIRONMENT).unwrap();
+        let memory = MemoryStorage::load(&TEST_ENVIRONMENT).unwrap();
+
+        for locator in [
+            Locator::CoordinatorState,
+            Locator::RoundHeight,
+            Locator::RoundState { round_height: 1 },
+            Locator::ContributionFile(ContributionLocator::new(1, 0, 1, false)),
+            Locator::ContributionsInfoSummary,
+        ] {
+            let path = disk.to_path(&locator).unwrap();
+            assert_eq!(path, memory.to_path(&locator).unwrap());
+            assert_eq!(locator, memory.to_locator(&path).unwrap());
+        }
+    }
+}
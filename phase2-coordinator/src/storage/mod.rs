@@ -1,5 +1,11 @@
 pub mod disk;
 pub use disk::*;
 
+pub mod encryption;
+pub use encryption::*;
+
+pub mod memory;
+pub use memory::*;
+
 pub mod storage;
 pub use storage::*;
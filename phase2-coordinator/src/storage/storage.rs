@@ -1,7 +1,8 @@
 use crate::{
+    coordinator_state::FinishedParticipants,
     environment::Environment,
-    objects::{ContributionFileSignature, ContributionInfo, Round, TrimmedContributionInfo},
-    CoordinatorError, CoordinatorState,
+    objects::{ContributionFileSignature, ContributionInfo, Round, TrimmedContributionInfo, VerificationCache},
+    CeremonyCompletion, CoordinatorError, CoordinatorState,
 };
 use phase2::helpers::CurveKind;
 use snarkvm_curves::{bls12_377::Bls12_377, bw6_761::BW6_761};
@@ -9,12 +10,11 @@ use snarkvm_curves::{bls12_377::Bls12_377, bw6_761::BW6_761};
 use serde::{Deserialize, Serialize};
 use std::{
     convert::TryFrom,
+    io::Write,
     ops::{Deref, DerefMut},
     path::{Path, PathBuf},
 };
 
-use super::Disk;
-
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct ContributionLocator {
     round_height: u64,
@@ -107,6 +107,9 @@ pub enum Locator {
     ContributionFileSignature(ContributionSignatureLocator),
     ContributionInfoFile { round_height: u64 },
     ContributionsInfoSummary,
+    FinishedParticipants { round_height: u64 },
+    CeremonyCompletion,
+    VerificationCache,
 }
 
 impl From<ContributionLocator> for Locator {
@@ -132,6 +135,9 @@ pub enum Object {
     ContributionFileSignature(ContributionFileSignature),
     ContributionInfoFile(ContributionInfo),
     ContributionsInfoSummary(Vec<TrimmedContributionInfo>),
+    FinishedParticipants(FinishedParticipants),
+    CeremonyCompletion(CeremonyCompletion),
+    VerificationCache(VerificationCache),
 }
 
 impl Object {
@@ -153,9 +159,33 @@ impl Object {
             Object::ContributionsInfoSummary(summary) => {
                 serde_json::to_vec_pretty(summary).expect("Contribution info summary to bytes failed")
             }
+            Object::FinishedParticipants(participants) => {
+                serde_json::to_vec_pretty(participants).expect("Finished participants to bytes failed")
+            }
+            Object::CeremonyCompletion(completion) => {
+                serde_json::to_vec_pretty(completion).expect("Ceremony completion to bytes failed")
+            }
+            Object::VerificationCache(cache) => cache.to_bytes(),
         }
     }
 
+    /// Decodes the object stored at the given locator from its bytes.
+    pub fn from_bytes(locator: &Locator, bytes: &[u8]) -> Result<Self, CoordinatorError> {
+        Ok(match locator {
+            Locator::CoordinatorState => Object::CoordinatorState(serde_json::from_slice(bytes)?),
+            Locator::RoundHeight => Object::RoundHeight(serde_json::from_slice(bytes)?),
+            Locator::RoundState { .. } => Object::RoundState(serde_json::from_slice(bytes)?),
+            Locator::RoundFile { .. } => Object::RoundFile(bytes.to_vec()),
+            Locator::ContributionFile(_) => Object::ContributionFile(bytes.to_vec()),
+            Locator::ContributionFileSignature(_) => Object::ContributionFileSignature(serde_json::from_slice(bytes)?),
+            Locator::ContributionInfoFile { .. } => Object::ContributionInfoFile(serde_json::from_slice(bytes)?),
+            Locator::ContributionsInfoSummary => Object::ContributionsInfoSummary(serde_json::from_slice(bytes)?),
+            Locator::FinishedParticipants { .. } => Object::FinishedParticipants(serde_json::from_slice(bytes)?),
+            Locator::CeremonyCompletion => Object::CeremonyCompletion(serde_json::from_slice(bytes)?),
+            Locator::VerificationCache => Object::VerificationCache(VerificationCache::from_bytes(bytes)?),
+        })
+    }
+
     /// Returns the size in bytes of the object.
     pub fn size(&self) -> u64 {
         match self {
@@ -167,6 +197,9 @@ impl Object {
             Object::ContributionFileSignature(_) => self.to_bytes().len() as u64,
             Object::ContributionInfoFile(_) => self.to_bytes().len() as u64,
             Object::ContributionsInfoSummary(_) => self.to_bytes().len() as u64,
+            Object::FinishedParticipants(_) => self.to_bytes().len() as u64,
+            Object::CeremonyCompletion(_) => self.to_bytes().len() as u64,
+            Object::VerificationCache(_) => self.to_bytes().len() as u64,
         }
     }
 
@@ -224,9 +257,9 @@ impl Object {
     }
 }
 
-pub trait ObjectReader: AsRef<[u8]> + Deref<Target = [u8]> {}
+pub trait ObjectReader: AsRef<[u8]> + Deref<Target = [u8]> + Send {}
 
-pub trait ObjectWriter: AsMut<[u8]> + DerefMut<Target = [u8]> {
+pub trait ObjectWriter: AsMut<[u8]> + DerefMut<Target = [u8]> + Send {
     fn flush(&self) -> std::io::Result<()>;
 }
 
@@ -301,14 +334,14 @@ pub enum LocatorOrPath {
 }
 
 impl LocatorOrPath {
-    pub fn try_into_locator(self, storage: &Disk) -> Result<Locator, CoordinatorError> {
+    pub fn try_into_locator<S: StorageLocator + ?Sized>(self, storage: &S) -> Result<Locator, CoordinatorError> {
         match self {
             LocatorOrPath::Path(path) => storage.to_locator(&path),
             LocatorOrPath::Locator(locator) => Ok(locator),
         }
     }
 
-    pub fn try_into_path(self, storage: &Disk) -> Result<LocatorPath, CoordinatorError> {
+    pub fn try_into_path<S: StorageLocator + ?Sized>(self, storage: &S) -> Result<LocatorPath, CoordinatorError> {
         match self {
             LocatorOrPath::Path(path) => Ok(path),
             LocatorOrPath::Locator(locator) => storage.to_path(&locator),
@@ -350,11 +383,11 @@ impl RemoveAction {
 
     /// Obtain the location of the item to be removed from [Storage]
     /// as a [Locator].
-    pub fn try_into_locator(self, storage: &Disk) -> Result<Locator, CoordinatorError> {
+    pub fn try_into_locator<S: StorageLocator + ?Sized>(self, storage: &S) -> Result<Locator, CoordinatorError> {
         self.locator_or_path.try_into_locator(storage)
     }
 
-    pub fn try_into_path(self, storage: &Disk) -> Result<LocatorPath, CoordinatorError> {
+    pub fn try_into_path<S: StorageLocator + ?Sized>(self, storage: &S) -> Result<LocatorPath, CoordinatorError> {
         self.locator_or_path.try_into_path(storage)
     }
 }
@@ -382,13 +415,56 @@ pub trait StorageLocator {
     fn to_locator(&self, path: &LocatorPath) -> Result<Locator, CoordinatorError>;
 }
 
-pub trait StorageObject {
-    type Reader: ObjectReader;
-    type Writer: ObjectWriter;
+/// The operations on the objects of the ceremony provided by a storage backend.
+///
+/// [Disk](super::Disk) is the backend of the coordinator in production, while
+/// [MemoryStorage](super::MemoryStorage) keeps the objects in memory.
+pub trait Storage: StorageLocator + Send + Sync {
+    /// Initializes the location corresponding to the given locator.
+    fn initialize(&mut self, locator: Locator, size: u64) -> Result<(), CoordinatorError>;
+
+    /// Checks whether the given locator exists in the storage or not.
+    fn exists(&self, locator: &Locator) -> bool;
+
+    /// Returns a copy of an object at the given locator in storage, if it exists.
+    fn get(&self, locator: &Locator) -> Result<Object, CoordinatorError>;
+
+    /// Inserts a new object at the given locator into storage, if it does not exist.
+    fn insert(&mut self, locator: Locator, object: Object) -> Result<(), CoordinatorError>;
+
+    /// Updates an existing object for the given locator in storage, if it exists.
+    fn update(&mut self, locator: &Locator, object: Object) -> Result<(), CoordinatorError>;
+
+    /// Copies an object from the given source locator to the given destination locator.
+    fn copy(&mut self, source_locator: &Locator, destination_locator: &Locator) -> Result<(), CoordinatorError>;
+
+    /// Removes the object corresponding to the given locator from storage.
+    fn remove(&mut self, locator: &Locator) -> Result<(), CoordinatorError>;
+
+    /// Returns the size of the object stored at the given locator.
+    fn size(&self, locator: &Locator) -> Result<u64, CoordinatorError>;
+
+    /// Process a [StorageAction] which mutates the storage.
+    fn process(&mut self, action: StorageAction) -> anyhow::Result<()>;
 
     /// Returns an object reader for the given locator.
-    fn reader(&self, locator: &Locator) -> Result<Self::Reader, CoordinatorError>;
+    fn reader(&self, locator: &Locator) -> Result<Box<dyn ObjectReader>, CoordinatorError>;
 
     /// Returns an object writer for the given locator.
-    fn writer(&self, locator: &Locator) -> Result<Self::Writer, CoordinatorError>;
+    fn writer(&self, locator: &Locator) -> Result<Box<dyn ObjectWriter>, CoordinatorError>;
+
+    /// Appends the given bytes to an existing object for the given locator in storage, if it exists.
+    fn append(&mut self, locator: &Locator, bytes: &[u8]) -> Result<(), CoordinatorError>;
+
+    /// Copies the object at the given locator to the given writer, returning its size.
+    fn copy_to(&self, locator: &Locator, writer: &mut dyn Write) -> Result<u64, CoordinatorError>;
+
+    /// Returns the json encoded summary of the contributions.
+    fn get_contributions_summary(&self) -> Result<Vec<u8>, CoordinatorError>;
+
+    /// Returns the json encoded coordinator state.
+    fn get_coordinator_state(&self) -> Result<Vec<u8>, CoordinatorError>;
+
+    /// Removes the contribution info file of the given round, and trims it from the contributions summary.
+    fn clear_info_files(&mut self, round_height: u64);
 }
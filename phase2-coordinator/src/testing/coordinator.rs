@@ -2,14 +2,12 @@ use crate::{
     authentication::Dummy,
     environment::{Environment, Parameters, Testing},
     objects::{Participant, Round},
-    storage::Disk,
+    storage::{Disk, MemoryStorage},
     Coordinator, CoordinatorError,
 };
 
 use once_cell::sync::Lazy;
 use serde_diff::{Diff, SerdeDiff};
-#[cfg(test)]
-use serial_test::serial;
 use std::{path::Path, sync::Arc};
 use time::{macros::datetime, OffsetDateTime};
 use tracing::*;
@@ -146,7 +144,7 @@ pub fn test_round_1_partial_json() -> anyhow::Result<Round> {
 /// Creates the initial round for testing purposes only.
 pub fn test_round_0() -> anyhow::Result<Round> {
     // Define test storage.
-    let mut test_storage = test_storage(&TEST_ENVIRONMENT);
+    let mut test_storage = MemoryStorage::load(&TEST_ENVIRONMENT)?;
 
     Ok(Round::new(
         &TEST_ENVIRONMENT,
@@ -166,10 +164,7 @@ pub fn print_diff<S: SerdeDiff>(a: &S, b: &S) {
 }
 
 #[test]
-#[serial]
 fn test_round_0_matches() {
-    initialize_test_environment(&TEST_ENVIRONMENT);
-
     let expected = test_round_0_json().unwrap();
     let candidate = test_round_0().unwrap();
 
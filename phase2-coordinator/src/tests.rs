@@ -1,18 +1,19 @@
 use crate::{
-    authentication::Dummy,
+    authentication::{Dummy, KeyPair},
     commands::{Seed, SigningKey, SEED_LENGTH},
-    environment::{Environment, Parameters, Settings, Testing},
+    environment::{ContributionWindow, Environment, Parameters, Settings, Testing},
     objects::Task,
-    storage::{Disk, StorageLocator},
+    storage::{ContributionLocator, Locator, Storage, StorageLocator},
     testing::prelude::*,
     Coordinator, CoordinatorError, MockTimeSource, Participant, Round,
 };
 use phase2::{helpers::CurveKind, ContributionMode, ProvingSystem};
-use time::OffsetDateTime;
+use time::{macros::datetime, OffsetDateTime};
 
 use fs_err as fs;
 use rand::RngCore;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use setup_utils::calculate_hash;
 use std::{
     collections::{HashSet, LinkedList},
     iter::FromIterator,
@@ -1417,7 +1418,7 @@ fn coordinator_drop_several_contributors() {
     assert_eq!(0, coordinator.number_of_queue_contributors());
 }
 
-fn check_round_matches_storage_files(storage: &Disk, round: &Round) {
+fn check_round_matches_storage_files(storage: &dyn Storage, round: &Round) {
     debug!("Checking round {}", round.round_height());
     for chunk in round.chunks() {
         debug!("Checking chunk {}", chunk.chunk_id());
@@ -2121,6 +2122,148 @@ fn contributor_timeout_drop_test() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Test that the work requests of a contributor count as heartbeats,
+/// so that an active contributor is not dropped for missing them.
+#[test]
+#[serial]
+fn contributor_activity_heartbeat_test() -> anyhow::Result<()> {
+    let time = Arc::new(MockTimeSource::new(OffsetDateTime::now_utc()));
+
+    let parameters = Parameters::Custom(Settings::new(
+        ContributionMode::Chunked,
+        ProvingSystem::Groth16,
+        CurveKind::Bls12_377,
+        6,  /* power */
+        16, /* batch_size */
+        16, /* chunk_size */
+    ));
+
+    let testing_deployment: Testing = Testing::from(parameters)
+        .contributor_seen_timeout(time::Duration::minutes(5))
+        .participant_lock_timeout(time::Duration::minutes(20))
+        .heartbeat_on_activity(true);
+
+    let environment = initialize_test_environment(&Environment::from(testing_deployment));
+
+    // Instantiate a coordinator.
+    let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Dummy), time.clone())?;
+
+    // Initialize the ceremony to round 0.
+    coordinator.initialize()?;
+
+    let (contributor1, contributor_signing_key1, seed1) = create_contributor("1");
+    let token = String::from("test_token");
+    let contributor_1_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+
+    coordinator.add_to_queue(contributor1.clone(), Some(contributor_1_ip), token, 10)?;
+
+    // Update the ceremony to round 1.
+    coordinator.update()?;
+    assert_eq!(1, coordinator.current_contributors().len());
+
+    // Lock a chunk, without any explicit heartbeat.
+    time.update(|prev| prev + time::Duration::minutes(4));
+    let (chunk_id, locked_locators) = coordinator.try_lock(&contributor1)?;
+    coordinator.update()?;
+
+    // Contribute to the chunk, past the timeout since joining the round.
+    time.update(|prev| prev + time::Duration::minutes(4));
+    let response_locator = locked_locators.next_contribution();
+    coordinator.run_computation(
+        response_locator.round_height(),
+        chunk_id,
+        response_locator.contribution_id(),
+        &contributor1,
+        &contributor_signing_key1,
+        &seed1,
+    )?;
+    coordinator.try_contribute(&contributor1, chunk_id)?;
+    coordinator.update()?;
+
+    // Lock the next chunk, past the timeout since the first lock.
+    time.update(|prev| prev + time::Duration::minutes(4));
+    coordinator.try_lock(&contributor1)?;
+    coordinator.update()?;
+
+    // Check that the contributor has never been dropped.
+    assert_eq!(1, coordinator.current_contributors().len());
+    assert_eq!(contributor1, coordinator.current_contributors().get(0).unwrap().0);
+    assert!(coordinator.dropped_participants().is_empty());
+
+    Ok(())
+}
+
+/// Test that the chunks can only be locked during the [Environment::contribution_windows],
+/// while the contributions to locked chunks and the heartbeats are always accepted.
+#[test]
+#[serial]
+fn contribution_window_test() -> anyhow::Result<()> {
+    let time = Arc::new(MockTimeSource::new(datetime!(2022-01-03 08:00 UTC)));
+
+    let parameters = Parameters::Custom(Settings::new(
+        ContributionMode::Chunked,
+        ProvingSystem::Groth16,
+        CurveKind::Bls12_377,
+        6,  /* power */
+        16, /* batch_size */
+        16, /* chunk_size */
+    ));
+
+    let window = ContributionWindow::new(time::Time::from_hms(9, 0, 0)?, time::Time::from_hms(17, 0, 0)?);
+    let testing_deployment: Testing = Testing::from(parameters).contribution_windows(&[window]);
+
+    let environment = initialize_test_environment(&Environment::from(testing_deployment));
+
+    // Instantiate a coordinator.
+    let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Dummy), time.clone())?;
+
+    // Initialize the ceremony to round 0.
+    coordinator.initialize()?;
+
+    let (contributor1, contributor_signing_key1, seed1) = create_contributor("1");
+    let token = String::from("test_token");
+    let contributor_1_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+
+    coordinator.add_to_queue(contributor1.clone(), Some(contributor_1_ip), token, 10)?;
+
+    // Update the ceremony to round 1.
+    coordinator.update()?;
+    assert_eq!(1, coordinator.current_contributors().len());
+
+    // Before the window, the lock is rejected but the heartbeat is accepted.
+    match coordinator.try_lock(&contributor1) {
+        Err(CoordinatorError::CeremonyPaused { next_open }) => assert_eq!(datetime!(2022-01-03 09:00 UTC), next_open),
+        result => panic!("Unexpected result {:?}", result),
+    }
+    coordinator.heartbeat(&contributor1)?;
+
+    // In the window, the lock is accepted.
+    time.set_time(datetime!(2022-01-03 10:00 UTC));
+    let (chunk_id, locked_locators) = coordinator.try_lock(&contributor1)?;
+
+    let response_locator = locked_locators.next_contribution();
+    coordinator.run_computation(
+        response_locator.round_height(),
+        chunk_id,
+        response_locator.contribution_id(),
+        &contributor1,
+        &contributor_signing_key1,
+        &seed1,
+    )?;
+
+    // After the window, the contribution to the locked chunk is accepted,
+    // but the next lock is rejected until the next day.
+    time.set_time(datetime!(2022-01-03 17:30 UTC));
+    coordinator.try_contribute(&contributor1, chunk_id)?;
+    match coordinator.try_lock(&contributor1) {
+        Err(CoordinatorError::CeremonyPaused { next_open }) => assert_eq!(datetime!(2022-01-04 09:00 UTC), next_open),
+        result => panic!("Unexpected result {:?}", result),
+    }
+    coordinator.heartbeat(&contributor1)?;
+
+    Ok(())
+}
+
 /// Test that participant who is waiting for a verifier to verify
 /// chunks that it depends on is not dropped from the round.
 #[test]
@@ -2470,6 +2613,450 @@ fn rollback_locked_chunk() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Test that a lock which outlived [Environment::participant_lock_timeout]
+/// across an unclean shutdown is released when the coordinator is
+/// initialized again.
+#[test]
+#[serial]
+fn release_orphaned_locks_on_initialize() -> anyhow::Result<()> {
+    let time = Arc::new(MockTimeSource::new(OffsetDateTime::now_utc()));
+
+    let parameters = Parameters::Custom(Settings::new(
+        ContributionMode::Chunked,
+        ProvingSystem::Groth16,
+        CurveKind::Bls12_377,
+        6,  /* power */
+        16, /* batch_size */
+        16, /* chunk_size */
+    ));
+
+    let testing_deployment: Testing = Testing::from(parameters)
+        .contributor_seen_timeout(time::Duration::minutes(20))
+        .participant_lock_timeout(time::Duration::minutes(10));
+
+    let environment = initialize_test_environment(&Environment::from(testing_deployment));
+
+    // Instantiate a coordinator.
+    let mut coordinator = Coordinator::new_with_time(environment.clone(), Arc::new(Dummy), time.clone())?;
+
+    // Initialize the ceremony to round 0.
+    coordinator.initialize()?;
+
+    let (contributor1, contributor_signing_key1, seed1) = create_contributor("1");
+    let contributor_1_ip = IpAddr::V4("0.0.0.1".parse().unwrap());
+    let token = String::from("test_token");
+
+    coordinator.add_to_queue(contributor1.clone(), Some(contributor_1_ip), token, 10)?;
+
+    // Update the ceremony to round 1.
+    coordinator.update()?;
+
+    coordinator.contribute(&contributor1, &contributor_signing_key1, &seed1)?;
+    let (chunk_id, _) = coordinator.try_lock(&contributor1)?;
+
+    let (_, contributor_info) = &coordinator.current_contributors()[0];
+    assert_eq!(1, contributor_info.locked_chunks().len());
+    assert!(coordinator
+        .current_round()?
+        .chunk(chunk_id)?
+        .is_locked_by(&contributor1));
+
+    // Shut down without cleanup, and restart once the lock timed out.
+    drop(coordinator);
+    time.update(|prev| prev + time::Duration::minutes(11));
+
+    let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Dummy), time.clone())?;
+    coordinator.initialize()?;
+
+    // The lock is released and the task is back in the assigned tasks.
+    let (_, contributor_info) = &coordinator.current_contributors()[0];
+    assert_eq!(0, contributor_info.locked_chunks().len());
+    assert!(contributor_info.assigned_tasks().contains(&Task::new(chunk_id, 1)));
+    assert_eq!(&None, coordinator.current_round()?.chunk(chunk_id)?.lock_holder());
+
+    Ok(())
+}
+
+/// Test that a contributor holding several locks can contribute to all the
+/// locked chunks at once, and that a failing batch is rolled back entirely.
+#[test]
+#[serial]
+fn contribute_batch() -> anyhow::Result<()> {
+    let parameters = Parameters::Custom(Settings::new(
+        ContributionMode::Chunked,
+        ProvingSystem::Groth16,
+        CurveKind::Bls12_377,
+        6,  /* power */
+        16, /* batch_size */
+        16, /* chunk_size */
+    ));
+
+    let testing_deployment: Testing = Testing::from(parameters).contributor_lock_chunk_limit(2);
+    let environment = initialize_test_environment(&Environment::from(testing_deployment));
+
+    // Instantiate a coordinator.
+    let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+
+    // Initialize the ceremony to round 0.
+    coordinator.initialize()?;
+
+    let (contributor1, contributor_signing_key1, seed1) = create_contributor("1");
+    let contributor_1_ip = IpAddr::V4("0.0.0.1".parse().unwrap());
+    let token = String::from("test_token");
+
+    coordinator.add_to_queue(contributor1.clone(), Some(contributor_1_ip), token, 10)?;
+
+    // Update the ceremony to round 1.
+    coordinator.update()?;
+
+    // Lock two chunks, and compute the contribution of the first one only.
+    let (chunk_id_1, locked_locators_1) = coordinator.try_lock(&contributor1)?;
+    let (chunk_id_2, locked_locators_2) = coordinator.try_lock(&contributor1)?;
+    let response_locator_1 = locked_locators_1.next_contribution();
+    let response_locator_2 = locked_locators_2.next_contribution();
+    coordinator.run_computation(
+        response_locator_1.round_height(),
+        chunk_id_1,
+        response_locator_1.contribution_id(),
+        &contributor1,
+        &contributor_signing_key1,
+        &seed1,
+    )?;
+
+    // The batch fails on the second chunk and is rolled back.
+    assert!(coordinator
+        .try_contribute_batch(&contributor1, &[chunk_id_1, chunk_id_2])
+        .is_err());
+    let (_, contributor_info) = &coordinator.current_contributors()[0];
+    assert_eq!(2, contributor_info.locked_chunks().len());
+    assert!(contributor_info.completed_tasks().is_empty());
+    assert!(coordinator.get_pending_verifications().is_empty());
+    let round = coordinator.current_round()?;
+    assert!(round.chunk(chunk_id_1)?.is_locked_by(&contributor1));
+    assert_eq!(0, round.chunk(chunk_id_1)?.current_contribution_id());
+    assert!(!coordinator
+        .storage()
+        .exists(&Locator::ContributionFile(response_locator_1)));
+
+    // The signature left by the failed batch can be removed as well.
+    let signature_locator_1 = locked_locators_1.next_contribution_file_signature();
+    coordinator.remove_contribution(response_locator_1, signature_locator_1)?;
+    assert!(
+        !coordinator
+            .storage()
+            .exists(&Locator::ContributionFileSignature(signature_locator_1))
+    );
+
+    // Duplicate chunks are rejected.
+    assert!(matches!(
+        coordinator.try_contribute_batch(&contributor1, &[chunk_id_1, chunk_id_1]),
+        Err(CoordinatorError::ContributionBatchInvalid)
+    ));
+
+    // The batch succeeds once both contributions are computed.
+    for (chunk_id, response_locator) in [(chunk_id_1, response_locator_1), (chunk_id_2, response_locator_2)] {
+        coordinator.run_computation(
+            response_locator.round_height(),
+            chunk_id,
+            response_locator.contribution_id(),
+            &contributor1,
+            &contributor_signing_key1,
+            &seed1,
+        )?;
+    }
+    let locators = coordinator.try_contribute_batch(&contributor1, &[chunk_id_1, chunk_id_2])?;
+    assert_eq!(vec![response_locator_1, response_locator_2], locators);
+
+    let (_, contributor_info) = &coordinator.current_contributors()[0];
+    assert!(contributor_info.locked_chunks().is_empty());
+    assert_eq!(2, contributor_info.completed_tasks().len());
+    assert_eq!(2, coordinator.get_pending_verifications().len());
+    let round = coordinator.current_round()?;
+    assert!(!round.chunk(chunk_id_1)?.is_locked_by(&contributor1));
+    assert!(!round.chunk(chunk_id_2)?.is_locked_by(&contributor1));
+
+    Ok(())
+}
+
+/// Test that the aggregation of a round fails when the same contribution was
+/// submitted for two chunks and NAMADA_REJECT_DUPLICATES is set.
+#[test]
+#[serial]
+fn reject_duplicate_contributions() -> anyhow::Result<()> {
+    // The flag is only read once a duplicate is found, which no other test does.
+    std::env::set_var("NAMADA_REJECT_DUPLICATES", "true");
+
+    let parameters = Parameters::Custom(Settings::new(
+        ContributionMode::Chunked,
+        ProvingSystem::Groth16,
+        CurveKind::Bls12_377,
+        6,  /* power */
+        16, /* batch_size */
+        16, /* chunk_size */
+    ));
+    let environment = initialize_test_environment(&Testing::from(parameters).into());
+    let number_of_chunks = environment.number_of_chunks() as usize;
+
+    // Instantiate a coordinator.
+    let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+
+    // Initialize the ceremony to round 0.
+    coordinator.initialize()?;
+
+    let (contributor, contributor_signing_key, seed) = create_contributor("1");
+    let contributor_ip = IpAddr::V4("0.0.0.1".parse().unwrap());
+    let (verifier, verifier_signing_key) = create_verifier("1");
+    let token = String::from("test_token");
+    coordinator.add_to_queue(contributor.clone(), Some(contributor_ip), token, 10)?;
+
+    // Update the ceremony to round 1, and run contribution and verification for all the chunks.
+    coordinator.update()?;
+    assert_eq!(1, coordinator.current_round_height()?);
+    for _ in 0..number_of_chunks {
+        coordinator.contribute(&contributor, &contributor_signing_key, &seed)?;
+        verify_task_if_available(&mut coordinator, &verifier, &verifier_signing_key)?;
+    }
+
+    // Submit the contribution to chunk 0 as the contribution to chunk 1.
+    let contribution = coordinator
+        .storage()
+        .reader(&Locator::ContributionFile(ContributionLocator::new(1, 0, 1, false)))?
+        .to_vec();
+    let hash = calculate_hash(&contribution).to_vec();
+    coordinator.write_contribution_with_hash(ContributionLocator::new(1, 1, 1, false), contribution, hash)?;
+
+    // The aggregation is rejected and the ceremony stays in round 1.
+    assert!(matches!(
+        coordinator.update(),
+        Err(CoordinatorError::ContributionDuplicated { first, second })
+            if first == Task::new(0, 1) && second == Task::new(1, 1)
+    ));
+    assert_eq!(1, coordinator.current_round_height()?);
+    assert!(!coordinator.state().is_current_round_aggregated());
+
+    Ok(())
+}
+
+/// Test that the activity of the verifier of the coordinator is reported
+/// as it completes the verifications of the round.
+#[test]
+#[serial]
+fn verifier_activity() -> anyhow::Result<()> {
+    let parameters = Parameters::Custom(Settings::new(
+        ContributionMode::Chunked,
+        ProvingSystem::Groth16,
+        CurveKind::Bls12_377,
+        6,  /* power */
+        16, /* batch_size */
+        16, /* chunk_size */
+    ));
+
+    let testing_deployment: Testing = Testing::from(parameters).contributor_lock_chunk_limit(2);
+    let environment = initialize_test_environment(&Environment::from(testing_deployment));
+    let verifier = environment.coordinator_verifiers()[0].clone();
+
+    // Instantiate a coordinator.
+    let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+
+    // Initialize the ceremony to round 0.
+    coordinator.initialize()?;
+
+    let (contributor1, contributor_signing_key1, seed1) = create_contributor("1");
+    let contributor_1_ip = IpAddr::V4("0.0.0.1".parse().unwrap());
+    coordinator.add_to_queue(
+        contributor1.clone(),
+        Some(contributor_1_ip),
+        String::from("test_token"),
+        10,
+    )?;
+
+    // Update the ceremony to round 1.
+    coordinator.update()?;
+
+    // The verifier is idle before any contribution.
+    let activity = coordinator.state().verifier_activity();
+    assert_eq!(1, activity.len());
+    assert_eq!(verifier, activity[0].verifier);
+    assert_eq!(0, activity[0].tasks_verified);
+    assert_eq!(0, activity[0].pending_verifications);
+    assert_eq!(None, activity[0].average_seconds_per_task);
+    assert_eq!(None, activity[0].last_active_at);
+
+    // Contribute to two chunks.
+    for _ in 0..2 {
+        let (chunk_id, locked_locators) = coordinator.try_lock(&contributor1)?;
+        let response_locator = locked_locators.next_contribution();
+        coordinator.run_computation(
+            response_locator.round_height(),
+            chunk_id,
+            response_locator.contribution_id(),
+            &contributor1,
+            &contributor_signing_key1,
+            &seed1,
+        )?;
+        coordinator.try_contribute(&contributor1, chunk_id)?;
+    }
+
+    let activity = coordinator.state().verifier_activity();
+    assert_eq!(0, activity[0].tasks_verified);
+    assert_eq!(2, activity[0].pending_verifications);
+
+    // The counters increment as the verifications complete.
+    for verified in 1..=2 {
+        let task = fetch_task_for_verifier(&coordinator).unwrap();
+        coordinator.default_verify(&task)?;
+
+        let activity = coordinator.state().verifier_activity();
+        assert_eq!(verified, activity[0].tasks_verified);
+        assert_eq!(2 - verified, activity[0].pending_verifications);
+        assert!(activity[0].average_seconds_per_task.is_some());
+        assert!(activity[0].last_active_at.is_some());
+    }
+
+    Ok(())
+}
+
+/// Test that a verifier of the current cohort, which is not among the default
+/// verifiers, verifies the contributions of the round with its own signing key
+/// until the round is complete.
+#[test]
+#[serial]
+fn cohort_verifier_completes_round() -> anyhow::Result<()> {
+    let parameters = Parameters::Custom(Settings::new(
+        ContributionMode::Chunked,
+        ProvingSystem::Groth16,
+        CurveKind::Bls12_377,
+        6,  /* power */
+        16, /* batch_size */
+        16, /* chunk_size */
+    ));
+
+    let cohort_keypair = KeyPair::new();
+    let cohort_verifier = Participant::new_verifier(cohort_keypair.pubkey());
+    let testing_deployment: Testing = Testing::from(parameters).cohort_verifiers(0, &[cohort_keypair]);
+    let environment = initialize_test_environment(&Environment::from(testing_deployment));
+    assert!(!environment.coordinator_verifiers().contains(&cohort_verifier));
+
+    // Instantiate a coordinator.
+    let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+
+    // Initialize the ceremony to round 0.
+    coordinator.initialize()?;
+
+    let contributor = create_contributor_test_details("1");
+    let contributor_ip = IpAddr::V4("0.0.0.1".parse().unwrap());
+    coordinator.add_to_queue(
+        contributor.participant.clone(),
+        Some(contributor_ip),
+        String::from("test_token"),
+        10,
+    )?;
+
+    // Update the ceremony to round 1.
+    coordinator.update()?;
+    assert_eq!(1, coordinator.current_round_height()?);
+
+    // Contribute to all the chunks, and verify the contributions as the verification task does.
+    while !coordinator.current_contributors()[0].1.pending_tasks().is_empty() {
+        contributor.contribute_to(&mut coordinator)?;
+
+        assert_eq!(vec![cohort_verifier.clone()], coordinator.get_assigned_verifiers());
+        for verifier in coordinator.get_assigned_verifiers() {
+            while let Some(task) = coordinator.fetch_verifier_task(&verifier)? {
+                coordinator.default_verify(&task)?;
+            }
+        }
+        assert!(coordinator.get_pending_verifications().is_empty());
+    }
+
+    // The round is complete and the ceremony moves to the next round.
+    assert!(coordinator.current_round()?.is_complete());
+    let (next_contributor, _, _) = create_contributor("2");
+    let next_contributor_ip = IpAddr::V4("0.0.0.2".parse().unwrap());
+    coordinator.add_to_queue(
+        next_contributor,
+        Some(next_contributor_ip),
+        String::from("test_token_2"),
+        10,
+    )?;
+    coordinator.update()?;
+    assert_eq!(2, coordinator.current_round_height()?);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn archive_finished_participants_past_retention() -> anyhow::Result<()> {
+    let parameters = Parameters::Custom(Settings::new(
+        ContributionMode::Chunked,
+        ProvingSystem::Groth16,
+        CurveKind::Bls12_377,
+        6,  /* power */
+        16, /* batch_size */
+        16, /* chunk_size */
+    ));
+
+    let testing_deployment: Testing = Testing::from(parameters).finished_rounds_retention(Some(1));
+    let environment = initialize_test_environment(&Environment::from(testing_deployment));
+    let number_of_chunks = environment.number_of_chunks() as usize;
+
+    // Instantiate a coordinator.
+    let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+
+    // Initialize the ceremony to round 0.
+    coordinator.initialize()?;
+
+    let (verifier, verifier_signing_key) = create_verifier("1");
+    let token = String::from("test_token");
+
+    // Advance to round 3, with a different contributor for each round.
+    let mut contributors = Vec::new();
+    for id in 1..=3u8 {
+        let (contributor, contributor_signing_key, seed) = create_contributor(&id.to_string());
+        let contributor_ip = IpAddr::V4(Ipv4Addr::new(0, 0, 0, id));
+        coordinator.add_to_queue(contributor.clone(), Some(contributor_ip), token.clone(), 10)?;
+
+        // Advance the ceremony to the next round.
+        coordinator.update()?;
+        assert_eq!(id as u64, coordinator.current_round_height()?);
+
+        if id < 3 {
+            for _ in 0..number_of_chunks {
+                coordinator.contribute(&contributor, &contributor_signing_key, &seed)?;
+                verify_task_if_available(&mut coordinator, &verifier, &verifier_signing_key)?;
+            }
+        }
+        contributors.push(contributor);
+    }
+
+    // Round 1 has been archived to storage, round 2 is still in memory.
+    assert!(coordinator.state().finished_participants(1).is_none());
+    assert!(coordinator
+        .storage()
+        .exists(&Locator::FinishedParticipants { round_height: 1 }));
+    assert!(coordinator.state().finished_participants(2).is_some());
+    assert!(!coordinator
+        .storage()
+        .exists(&Locator::FinishedParticipants { round_height: 2 }));
+
+    // The archived round is still queryable.
+    let archived = coordinator.finished_participants(1)?;
+    assert_eq!(1, archived.contributors.len());
+    assert!(archived.contributors.contains_key(&contributors[0]));
+    let retained = coordinator.finished_participants(2)?;
+    assert!(retained.contributors.contains_key(&contributors[1]));
+
+    // Archived contributors are still accounted for.
+    assert_eq!(2, coordinator.state().number_of_finished_contributors());
+    assert!(matches!(
+        coordinator.add_to_queue_checks(&contributors[0], None),
+        Err(CoordinatorError::ParticipantAlreadyAdded)
+    ));
+
+    Ok(())
+}
+
 #[test]
 #[serial]
 fn round_on_groth16_bls12_377() {
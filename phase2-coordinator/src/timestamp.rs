@@ -0,0 +1,428 @@
+//! RFC 3161 trusted timestamps of the contributions.
+//!
+//! When the [TSA_URL_ENV] env variable is set, the coordinator requests a timestamp of every contribution it accepts
+//! from the time stamping authority at that url, and stores the returned token in the
+//! [Contribution](crate::objects::Contribution) of the round, alongside the location of its contribution file
+//! signature. The requests are sent from the verification task, once the coordinator lock is released. The timestamped message is the SHA-256 of the response hash of the contribution, as time stamping
+//! authorities don't support BLAKE2b. A token is checked against the hash of its contribution with
+//! [verify_timestamp_token].
+//!
+//! Only the message imprint and the time of a token are checked here, the signature of the authority over the token
+//! must be checked against its certificate, e.g. with `openssl ts -verify`.
+
+use crate::CoordinatorError;
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+use time::{macros::format_description, OffsetDateTime, PrimitiveDateTime};
+
+/// The env variable holding the url of the time stamping authority. The contributions are not timestamped if unset.
+pub const TSA_URL_ENV: &str = "NAMADA_TSA_URL";
+
+/// The timeout of a request to the time stamping authority.
+const TSA_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The DER encoded object identifier of SHA-256, 2.16.840.1.101.3.4.2.1.
+const SHA256_OID: [u8; 9] = [0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01];
+
+const TAG_BOOLEAN: u8 = 0x01;
+const TAG_INTEGER: u8 = 0x02;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_NULL: u8 = 0x05;
+const TAG_OID: u8 = 0x06;
+const TAG_GENERALIZED_TIME: u8 = 0x18;
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_SET: u8 = 0x31;
+const TAG_EXPLICIT_0: u8 = 0xa0;
+
+/// A trusted timestamp of a contribution.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct TimestampToken {
+    /// The url of the time stamping authority which issued the token.
+    pub authority: String,
+    /// The base64 encoded DER of the RFC 3161 time stamp token.
+    pub token: String,
+}
+
+/// A contribution accepted by the coordinator, waiting for its trusted timestamp.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingTimestamp {
+    pub round_height: u64,
+    pub chunk_id: u64,
+    pub contribution_id: u64,
+    /// The response hash of the contribution.
+    pub response_hash: Vec<u8>,
+}
+
+/// Returns the message timestamped for the contribution with the given hash.
+pub fn timestamp_message_imprint(contribution_hash: &[u8]) -> Vec<u8> {
+    Sha256::digest(contribution_hash).to_vec()
+}
+
+/// The client of an RFC 3161 time stamping authority.
+#[derive(Debug, Clone)]
+pub struct TimestampAuthority {
+    url: String,
+}
+
+impl TimestampAuthority {
+    /// Creates a client of the time stamping authority at the given url.
+    pub fn new<S: Into<String>>(url: S) -> Self {
+        Self { url: url.into() }
+    }
+
+    /// Returns the url of the time stamping authority.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    ///
+    /// Requests a timestamp of the contribution with the given hash. Blocks
+    /// until the authority replies or [TSA_TIMEOUT] elapses.
+    ///
+    pub fn timestamp(&self, contribution_hash: &[u8]) -> Result<TimestampToken, CoordinatorError> {
+        let imprint = timestamp_message_imprint(contribution_hash);
+        let nonce = OsRng.next_u64();
+        let failed = |e: reqwest::Error| CoordinatorError::TimestampRequestFailed(e.to_string());
+
+        let response = reqwest::blocking::Client::builder()
+            .timeout(TSA_TIMEOUT)
+            .build()
+            .map_err(failed)?
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/timestamp-query")
+            .body(encode_request(&imprint, nonce))
+            .send()
+            .and_then(|response| response.error_for_status())
+            .map_err(failed)?;
+        let reply = response.bytes().map_err(failed)?;
+
+        let token = decode_reply(&reply)?;
+        let info = decode_token(token)?;
+        if info.imprint != imprint || info.nonce != Some(nonce) {
+            return Err(CoordinatorError::TimestampRequestFailed(
+                "the token doesn't match the request".to_string(),
+            ));
+        }
+
+        Ok(TimestampToken {
+            authority: self.url.clone(),
+            token: base64::encode(token),
+        })
+    }
+}
+
+///
+/// Checks that the given token timestamps the contribution with the given
+/// hash, and returns the time of the timestamp.
+///
+/// Only the message imprint is checked, not the signature of the authority
+/// over the token, see the [module](self) documentation.
+///
+pub fn verify_timestamp_token(
+    token: &TimestampToken,
+    contribution_hash: &[u8],
+) -> Result<OffsetDateTime, CoordinatorError> {
+    let bytes = base64::decode(&token.token).map_err(|e| CoordinatorError::TimestampTokenInvalid(e.to_string()))?;
+    let info = decode_token(&bytes)?;
+
+    if info.imprint != timestamp_message_imprint(contribution_hash) {
+        return Err(CoordinatorError::TimestampTokenMismatch);
+    }
+
+    Ok(info.time)
+}
+
+/// The fields of a time stamp token checked by the coordinator.
+struct TimestampInfo {
+    /// The SHA-256 of the timestamped message.
+    imprint: Vec<u8>,
+    /// The time of the timestamp.
+    time: OffsetDateTime,
+    /// The nonce of the request, if any.
+    nonce: Option<u64>,
+}
+
+/// Encodes a DER element with the given tag and content.
+fn der(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut element = vec![tag];
+    match content.len() {
+        length if length < 0x80 => element.push(length as u8),
+        length => {
+            let bytes: Vec<u8> = length.to_be_bytes().iter().copied().skip_while(|byte| *byte == 0).collect();
+            element.push(0x80 | bytes.len() as u8);
+            element.extend(bytes);
+        }
+    }
+    element.extend_from_slice(content);
+
+    element
+}
+
+/// Encodes the given unsigned integer as a DER INTEGER.
+fn der_unsigned(value: u64) -> Vec<u8> {
+    let mut bytes: Vec<u8> = value.to_be_bytes().iter().copied().skip_while(|byte| *byte == 0).collect();
+    // A leading zero keeps the integer positive.
+    if bytes.first().map_or(true, |byte| byte & 0x80 != 0) {
+        bytes.insert(0, 0);
+    }
+
+    der(TAG_INTEGER, &bytes)
+}
+
+/// Encodes the TimeStampReq of the given SHA-256 imprint, asking for the certificate of the authority.
+fn encode_request(imprint: &[u8], nonce: u64) -> Vec<u8> {
+    let algorithm = der(TAG_SEQUENCE, &[der(TAG_OID, &SHA256_OID), der(TAG_NULL, &[])].concat());
+    let message_imprint = der(TAG_SEQUENCE, &[algorithm, der(TAG_OCTET_STRING, imprint)].concat());
+
+    der(
+        TAG_SEQUENCE,
+        &[
+            der_unsigned(1),
+            message_imprint,
+            der_unsigned(nonce),
+            der(TAG_BOOLEAN, &[0xff]),
+        ]
+        .concat(),
+    )
+}
+
+/// A reader of consecutive DER elements.
+struct DerReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> DerReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Reads the next element, returning its tag, its content and its whole encoding.
+    fn next(&mut self) -> Result<(u8, &'a [u8], &'a [u8]), CoordinatorError> {
+        let malformed = || CoordinatorError::TimestampTokenInvalid("malformed DER".to_string());
+
+        let tag = *self.bytes.first().ok_or_else(malformed)?;
+        let first_length = *self.bytes.get(1).ok_or_else(malformed)?;
+        let (header, length) = match first_length {
+            length if length < 0x80 => (2, length as usize),
+            0x81..=0x84 => {
+                let size = (first_length & 0x7f) as usize;
+                let length_bytes = self.bytes.get(2..2 + size).ok_or_else(malformed)?;
+                let length = length_bytes.iter().fold(0usize, |length, byte| length << 8 | *byte as usize);
+                (2 + size, length)
+            }
+            _ => return Err(malformed()),
+        };
+
+        let end = header.checked_add(length).ok_or_else(malformed)?;
+        let element = self.bytes.get(..end).ok_or_else(malformed)?;
+        self.bytes = &self.bytes[end..];
+
+        Ok((tag, &element[header..], element))
+    }
+
+    /// Reads the next element, which must have the given tag, and returns its content.
+    fn expect(&mut self, tag: u8) -> Result<&'a [u8], CoordinatorError> {
+        match self.next()? {
+            (found, content, _) if found == tag => Ok(content),
+            (found, _, _) => Err(CoordinatorError::TimestampTokenInvalid(format!(
+                "expected the tag {:#04x}, found {:#04x}",
+                tag, found
+            ))),
+        }
+    }
+}
+
+/// Decodes a TimeStampResp and returns the time stamp token it holds.
+fn decode_reply(reply: &[u8]) -> Result<&[u8], CoordinatorError> {
+    let mut response = DerReader::new(DerReader::new(reply).expect(TAG_SEQUENCE)?);
+
+    // Only the granted and granted with modifications statuses hold a token.
+    let status = DerReader::new(response.expect(TAG_SEQUENCE)?).expect(TAG_INTEGER)?;
+    if status != [0] && status != [1] {
+        return Err(CoordinatorError::TimestampRequestFailed(format!(
+            "the request was rejected with the status {}",
+            hex::encode(status)
+        )));
+    }
+
+    match response.next()? {
+        (TAG_SEQUENCE, _, token) => Ok(token),
+        _ => Err(CoordinatorError::TimestampTokenInvalid("missing token".to_string())),
+    }
+}
+
+/// Decodes the TSTInfo of the given time stamp token.
+fn decode_token(token: &[u8]) -> Result<TimestampInfo, CoordinatorError> {
+    // ContentInfo ::= SEQUENCE { contentType, [0] EXPLICIT SignedData }
+    let mut content_info = DerReader::new(DerReader::new(token).expect(TAG_SEQUENCE)?);
+    content_info.expect(TAG_OID)?;
+    let signed_data = DerReader::new(content_info.expect(TAG_EXPLICIT_0)?).expect(TAG_SEQUENCE)?;
+
+    // SignedData ::= SEQUENCE { version, digestAlgorithms, encapContentInfo, ... }
+    let mut signed_data = DerReader::new(signed_data);
+    signed_data.expect(TAG_INTEGER)?;
+    signed_data.expect(TAG_SET)?;
+    let mut encapsulated = DerReader::new(signed_data.expect(TAG_SEQUENCE)?);
+    encapsulated.expect(TAG_OID)?;
+    let info = DerReader::new(encapsulated.expect(TAG_EXPLICIT_0)?).expect(TAG_OCTET_STRING)?;
+
+    // TSTInfo ::= SEQUENCE { version, policy, messageImprint, serialNumber, genTime, accuracy, ordering, nonce, ... }
+    let mut info = DerReader::new(DerReader::new(info).expect(TAG_SEQUENCE)?);
+    info.expect(TAG_INTEGER)?;
+    info.expect(TAG_OID)?;
+    let mut message_imprint = DerReader::new(info.expect(TAG_SEQUENCE)?);
+    let algorithm = DerReader::new(message_imprint.expect(TAG_SEQUENCE)?).expect(TAG_OID)?;
+    if algorithm != SHA256_OID {
+        return Err(CoordinatorError::TimestampTokenInvalid(
+            "the message imprint is not a SHA-256".to_string(),
+        ));
+    }
+    let imprint = message_imprint.expect(TAG_OCTET_STRING)?.to_vec();
+    info.expect(TAG_INTEGER)?;
+    let time = decode_generalized_time(info.expect(TAG_GENERALIZED_TIME)?)?;
+
+    // The optional accuracy and ordering come before the nonce, which is the only integer left.
+    let mut nonce = None;
+    while !info.is_empty() {
+        if let (TAG_INTEGER, content, _) = info.next()? {
+            nonce = Some(
+                content
+                    .iter()
+                    .skip_while(|byte| **byte == 0)
+                    .fold(0u64, |nonce, byte| nonce << 8 | *byte as u64),
+            );
+        }
+    }
+
+    Ok(TimestampInfo { imprint, time, nonce })
+}
+
+/// Decodes a GeneralizedTime in UTC, ignoring the fraction of seconds.
+fn decode_generalized_time(content: &[u8]) -> Result<OffsetDateTime, CoordinatorError> {
+    let invalid = || CoordinatorError::TimestampTokenInvalid("invalid time".to_string());
+
+    let time = std::str::from_utf8(content).map_err(|_| invalid())?;
+    if !time.ends_with('Z') {
+        return Err(invalid());
+    }
+    let seconds = time.get(..14).ok_or_else(invalid)?;
+    let format = format_description!("[year][month][day][hour][minute][second]");
+
+    Ok(PrimitiveDateTime::parse(seconds, format).map_err(|_| invalid())?.assume_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    /// Encodes the time stamp token of the given imprint, without the certificates and the signatures of a real one.
+    fn encode_token(imprint: &[u8], nonce: u64) -> Vec<u8> {
+        let algorithm = der(TAG_SEQUENCE, &[der(TAG_OID, &SHA256_OID), der(TAG_NULL, &[])].concat());
+        let info = der(
+            TAG_SEQUENCE,
+            &[
+                der_unsigned(1),
+                der(TAG_OID, &[0x2a, 0x03]),
+                der(TAG_SEQUENCE, &[algorithm.clone(), der(TAG_OCTET_STRING, imprint)].concat()),
+                der_unsigned(42),
+                der(TAG_GENERALIZED_TIME, b"20221016123456.789Z"),
+                der(TAG_SEQUENCE, &der_unsigned(1)),
+                der_unsigned(nonce),
+            ]
+            .concat(),
+        );
+        let encapsulated = der(
+            TAG_SEQUENCE,
+            &[
+                der(TAG_OID, &[0x2a, 0x04]),
+                der(TAG_EXPLICIT_0, &der(TAG_OCTET_STRING, &info)),
+            ]
+            .concat(),
+        );
+        let signed_data = der(
+            TAG_SEQUENCE,
+            &[der_unsigned(3), der(TAG_SET, &algorithm), encapsulated, der(TAG_SET, &[])].concat(),
+        );
+
+        der(
+            TAG_SEQUENCE,
+            &[der(TAG_OID, &[0x2a, 0x05]), der(TAG_EXPLICIT_0, &signed_data)].concat(),
+        )
+    }
+
+    #[test]
+    fn test_der_length() {
+        assert_eq!(vec![TAG_OCTET_STRING, 2, 7, 7], der(TAG_OCTET_STRING, &[7; 2]));
+        assert_eq!(&[TAG_OCTET_STRING, 0x81, 200], &der(TAG_OCTET_STRING, &[7; 200])[..3]);
+        assert_eq!(&[TAG_OCTET_STRING, 0x82, 0x01, 0x2c], &der(TAG_OCTET_STRING, &[7; 300])[..4]);
+        assert_eq!(vec![TAG_INTEGER, 2, 0, 0x80], der_unsigned(0x80));
+        assert_eq!(vec![TAG_INTEGER, 1, 0], der_unsigned(0));
+
+        let element = der(TAG_OCTET_STRING, &[7; 300]);
+        let mut reader = DerReader::new(&element);
+        assert_eq!(300, reader.expect(TAG_OCTET_STRING).unwrap().len());
+        assert!(reader.is_empty());
+        assert!(DerReader::new(&element[..100]).next().is_err());
+    }
+
+    #[test]
+    fn test_request_imprint() {
+        let imprint = timestamp_message_imprint(&[1; 64]);
+        let request = encode_request(&imprint, 5);
+
+        let mut request = DerReader::new(DerReader::new(&request).expect(TAG_SEQUENCE).unwrap());
+        assert_eq!([1], request.expect(TAG_INTEGER).unwrap());
+        let mut message_imprint = DerReader::new(request.expect(TAG_SEQUENCE).unwrap());
+        message_imprint.expect(TAG_SEQUENCE).unwrap();
+        assert_eq!(imprint, message_imprint.expect(TAG_OCTET_STRING).unwrap());
+        assert_eq!([5], request.expect(TAG_INTEGER).unwrap());
+        assert_eq!([0xff], request.expect(TAG_BOOLEAN).unwrap());
+    }
+
+    #[test]
+    fn test_verify_timestamp_token() {
+        let contribution_hash = [1; 64];
+        let token = encode_token(&timestamp_message_imprint(&contribution_hash), u64::MAX);
+
+        // The token is extracted from a granted reply.
+        let status = der(TAG_SEQUENCE, &der_unsigned(0));
+        let reply = der(TAG_SEQUENCE, &[status, token.clone()].concat());
+        assert_eq!(token, decode_reply(&reply).unwrap());
+        let rejected = der(TAG_SEQUENCE, &der(TAG_SEQUENCE, &der_unsigned(2)));
+        assert!(matches!(
+            decode_reply(&rejected),
+            Err(CoordinatorError::TimestampRequestFailed(_))
+        ));
+
+        assert_eq!(Some(u64::MAX), decode_token(&token).unwrap().nonce);
+
+        let token = TimestampToken {
+            authority: "http://tsa.test".to_string(),
+            token: base64::encode(&token),
+        };
+        assert_eq!(
+            datetime!(2022-10-16 12:34:56 UTC),
+            verify_timestamp_token(&token, &contribution_hash).unwrap()
+        );
+        assert!(matches!(
+            verify_timestamp_token(&token, &[2; 64]),
+            Err(CoordinatorError::TimestampTokenMismatch)
+        ));
+
+        let truncated = TimestampToken {
+            token: base64::encode(&base64::decode(&token.token).unwrap()[..50]),
+            ..token
+        };
+        assert!(matches!(
+            verify_timestamp_token(&truncated, &contribution_hash),
+            Err(CoordinatorError::TimestampTokenInvalid(_))
+        ));
+    }
+}
@@ -5,6 +5,7 @@
 //  NOTE: these tests require the phase1radix files to be placed in the phase2-coordinator folder
 
 use std::{
+    collections::HashSet,
     io::Write,
     net::{IpAddr, SocketAddr},
     sync::Arc,
@@ -14,21 +15,25 @@ use blake2::Digest;
 use phase2_coordinator::{
     authentication::{KeyPair, Production, Signature},
     commands::{Computation, RandomSource},
-    coordinator_state::CoordinatorState,
-    environment::Testing,
+    coordinator_state::{Blacklists, CoordinatorState, ParticipantMetrics, TokenInUse},
+    environment::{Environment, Testing, Timeouts},
     objects::{ContributionInfo, LockedLocators, TrimmedContributionInfo},
     rest,
     rest_utils::{
-        self, ContributorStatus, PostChunkRequest, ACCESS_SECRET_HEADER, BODY_DIGEST_HEADER, CONTENT_LENGTH_HEADER,
-        PUBKEY_HEADER, SIGNATURE_HEADER, TOKENS_ZIP_FILE,
+        self, ContributorStatus, InitiateChunkUploadRequest, MergeCohortsRequest, PostChunkRequest, PutChunkPartRequest,
+        TokenEligibility,
+        TokenStatus, ACCESS_SECRET_HEADER, BODY_DIGEST_HEADER, CONTENT_LENGTH_HEADER, PUBKEY_HEADER, SIGNATURE_HEADER,
+        TOKENS_ZIP_FILE,
     },
     storage::{ContributionLocator, ContributionSignatureLocator, Object},
     testing::coordinator,
-    ContributionFileSignature, ContributionState, Coordinator, Participant,
+    ContributionFileSignature, ContributionState, Coordinator, HealthReport, MockTimeSource, Participant,
+    SystemTimeSource, TimeSource, VersionInfo,
 };
 use reqwest::header::{HeaderValue, CONTENT_TYPE};
 use rocket::{
     catchers,
+    data::{Limits, ToByteUnit},
     http::{ContentType, Header, Status},
     local::blocking::{Client, LocalRequest},
     routes,
@@ -36,6 +41,7 @@ use rocket::{
     Build, Rocket,
 };
 use serde::Serialize;
+use serial_test::serial;
 use sha2::Sha256;
 use zip::write::FileOptions;
 
@@ -59,11 +65,16 @@ struct TestCtx {
 
 /// Build the rocket server for testing with the proper configuration.
 fn build_context() -> TestCtx {
+    build_context_with_time(Testing::default(), Arc::new(SystemTimeSource::new()))
+}
+
+/// Build the rocket server for testing with the given environment and time source.
+fn build_context_with_time(environment: Testing, time: Arc<dyn TimeSource>) -> TestCtx {
     std::env::set_var("TOKEN_BLACKLIST", "true");
     std::env::set_var("NAMADA_MPC_IP_BAN", "true");
 
     // Reset storage to prevent state conflicts between tests and initialize test environment
-    let environment = coordinator::initialize_test_environment(&Testing::default().into());
+    let environment = coordinator::initialize_test_environment(&environment.into());
 
     // Create token file
     // Need a fixed-name temp dir because of the lazy_static variables based on env
@@ -84,7 +95,7 @@ fn build_context() -> TestCtx {
     std::env::set_var("NAMADA_TOKENS_PATH", tmp_dir.path());
 
     // Instantiate the coordinator
-    let mut coordinator = Coordinator::new(environment, Arc::new(Production)).unwrap();
+    let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Production), time).unwrap();
 
     let keypair1 = KeyPair::new();
     let keypair2 = KeyPair::new();
@@ -130,21 +141,47 @@ fn build_context() -> TestCtx {
             "/",
             routes![
                 rest::join_queue,
+                rest::check_eligibility,
                 rest::lock_chunk,
                 rest::contribute_chunk,
+                rest::contribute_chunk_batch,
                 rest::update_coordinator,
+                rest::reinstate_participant,
+                rest::force_release_lock,
+                rest::set_participant_lock_limit,
+                rest::freeze_queue,
+                rest::unfreeze_queue,
+                rest::preview_reset_round,
                 rest::heartbeat,
+                rest::reconnect,
                 rest::stop_coordinator,
                 rest::verify_chunks,
                 rest::get_contributor_queue_status,
+                rest::get_participant_metrics,
                 rest::post_contribution_info,
                 rest::get_contributions_info,
+                rest::get_round_contribution_info,
                 rest::get_healthcheck,
                 rest::get_contribution_url,
+                rest::initiate_chunk_upload,
+                rest::put_chunk_part,
+                rest::complete_chunk_upload,
                 rest::get_challenge_url,
                 rest::get_coordinator_state,
+                rest::get_blacklists,
+                rest::get_tokens_in_use,
+                rest::get_verifier_activity,
+                rest::release_token,
                 rest::update_cohorts,
-                rest::post_attestation
+                rest::replace_token_schedule,
+                rest::merge_cohorts,
+                rest::update_ip_ban_exemptions,
+                rest::get_queue_wait_time,
+                rest::update_queue_wait_time,
+                rest::get_timeouts,
+                rest::post_attestation,
+                rest::get_version,
+                rest::get_metrics
             ],
         )
         .manage(coordinator)
@@ -157,7 +194,10 @@ fn build_context() -> TestCtx {
                 rest_utils::io_error,
                 rest_utils::unprocessable_entity,
                 rest_utils::mismatching_checksum,
-                rest_utils::invalid_header
+                rest_utils::invalid_header,
+                rest_utils::participant_not_in_round,
+                rest_utils::payload_too_large,
+                rest_utils::too_many_requests
             ],
         );
 
@@ -247,6 +287,110 @@ fn get_status() {
     assert!(response.body().is_some());
 }
 
+#[test]
+fn queue_wait_time() {
+    let access_token = "test-access_token";
+    std::env::set_var("ACCESS_SECRET", access_token);
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Wrong, invalid token
+    let mut req = client.post("/queue_wait_time").json(&300u64);
+    req.add_header(Header::new(ACCESS_SECRET_HEADER, "wrong token"));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(response.body().is_some());
+
+    // Ok, update the queue wait time
+    req = client.post("/queue_wait_time").json(&300u64);
+    req.add_header(Header::new(ACCESS_SECRET_HEADER, access_token));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.body().is_none());
+
+    // Ok, the new queue wait time is returned
+    req = client.get("/queue_wait_time");
+    req.add_header(Header::new(ACCESS_SECRET_HEADER, access_token));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(300, response.into_json::<u64>().unwrap());
+
+    // Ok, the timeouts report the new queue wait time
+    req = client.get("/timeouts");
+    req.add_header(Header::new(ACCESS_SECRET_HEADER, access_token));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(300, response.into_json::<Timeouts>().unwrap().queue_wait_time);
+}
+
+#[test]
+fn get_blacklists() {
+    let access_token = "test-access_token";
+    std::env::set_var("ACCESS_SECRET", access_token);
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Wrong, invalid token
+    let mut req = client.get("/blacklists");
+    req.add_header(Header::new(ACCESS_SECRET_HEADER, "wrong token"));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(response.body().is_some());
+
+    // Ok, nothing has been blacklisted yet
+    req = client.get("/blacklists");
+    req.add_header(Header::new(ACCESS_SECRET_HEADER, access_token));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let blacklists: Blacklists = response.into_json().unwrap();
+    assert!(blacklists.tokens.is_empty());
+    assert!(blacklists.ips.is_empty());
+
+    // Ok, with the tokens redacted
+    req = client.get("/blacklists?redact=true");
+    req.add_header(Header::new(ACCESS_SECRET_HEADER, access_token));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.into_json::<Blacklists>().is_some());
+}
+
+#[test]
+fn tokens_in_use() {
+    let access_token = "test-access_token";
+    std::env::set_var("ACCESS_SECRET", access_token);
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Wrong, invalid token
+    let mut req = client.get("/tokens_in_use");
+    req.add_header(Header::new(ACCESS_SECRET_HEADER, "wrong token"));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(response.body().is_some());
+
+    // Ok, the token of the contributor in the round is in use
+    req = client.get("/tokens_in_use");
+    req.add_header(Header::new(ACCESS_SECRET_HEADER, access_token));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let tokens: Vec<TokenInUse> = response.into_json().unwrap();
+    assert_eq!(1, tokens.len());
+    assert_eq!(ctx.contributors[0]._inner, tokens[0].participant);
+
+    // Wrong, the contributor is still active
+    req = client.post("/tokens_in_use/release").json(&tokens[0].token);
+    req.add_header(Header::new(ACCESS_SECRET_HEADER, access_token));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Conflict);
+    assert!(response.body().is_some());
+
+    // Wrong, the token is not in use
+    req = client.post("/tokens_in_use/release").json(&"unknown_token");
+    req.add_header(Header::new(ACCESS_SECRET_HEADER, access_token));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+}
+
 fn get_serialized_tokens_zip(tokens: Vec<&str>) -> Vec<u8> {
     let w = std::io::Cursor::new(Vec::new());
     let mut zip_writer = zip::ZipWriter::new(w);
@@ -308,6 +452,94 @@ fn update_cohorts() {
     assert!(std::fs::metadata(TOKENS_ZIP_FILE).is_ok());
 }
 
+#[test]
+fn merge_cohorts() {
+    let access_token = "test-access_token";
+    std::env::set_var("ACCESS_SECRET", access_token);
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Schedule two future cohorts after the active one
+    let tokens = get_serialized_tokens_zip(vec![
+        "[\"9nFeNpukSn1eVwNc2vkfP7rdLh2njm5ewmCGxSLTW3GYmKP51fKjbRUvHDmntjEaQiq7iFux9tumgWEWVHwHQCs31oitpqBpMWpMydo1DnuFyLpsD6C\", \"9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek\", \"9nFeNpukSn1eVwNc2vkfP8SP4HrxTh9F86CY5pNWw8RF3jZa91q2i3yvE7ugpn9w2RzoZBZrdskgckmvJuVKq6ZWxfV8TepZYFd9SeARGHexi7tGGV2\"]",
+        "[\"token_2\"]",
+        "[\"token_3\"]",
+    ]);
+    let mut req = client.post("/update_cohorts");
+    req = set_request::<Vec<u8>>(req, &ctx.coordinator.keypair, Some(&tokens));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    std::fs::remove_file(TOKENS_ZIP_FILE).ok();
+
+    // Wrong, invalid token
+    let request = MergeCohortsRequest { from: 2, into: 1 };
+    req = client.post("/merge_cohorts");
+    req = set_request(req, &ctx.coordinator.keypair, Some(&request));
+    req.add_header(Header::new(ACCESS_SECRET_HEADER, "wrong token"));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // Wrong, the active cohort cannot be merged
+    let invalid_request = MergeCohortsRequest { from: 0, into: 1 };
+    req = client.post("/merge_cohorts");
+    req = set_request(req, &ctx.coordinator.keypair, Some(&invalid_request));
+    req.add_header(Header::new(ACCESS_SECRET_HEADER, access_token));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Conflict);
+    assert!(std::fs::metadata(TOKENS_ZIP_FILE).is_err());
+
+    // Merge the last cohort into the second one, the compressed token folder is rewritten
+    req = client.post("/merge_cohorts");
+    req = set_request(req, &ctx.coordinator.keypair, Some(&request));
+    req.add_header(Header::new(ACCESS_SECRET_HEADER, access_token));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    let mut zip = zip::ZipArchive::new(std::fs::File::open(TOKENS_ZIP_FILE).unwrap()).unwrap();
+    assert_eq!(2, zip.len());
+    let merged: HashSet<String> = serde_json::from_reader(zip.by_name("namada_tokens_cohort_2.json").unwrap()).unwrap();
+    assert_eq!(
+        HashSet::from([String::from("token_2"), String::from("token_3")]),
+        merged
+    );
+    assert!(!ctx._tokens_tmp_dir.path().join("namada_tokens_cohort_3.json").exists());
+}
+
+#[test]
+fn body_size_limits() {
+    let ctx = build_context();
+
+    let new_tokens = get_serialized_tokens_zip(vec![
+        "[\"9nFeNpukSn1eVwNc2vkfP7rdLh2njm5ewmCGxSLTW3GYmKP51fKjbRUvHDmntjEaQiq7iFux9tumgWEWVHwHQCs31oitpqBpMWpMydo1DnuFyLpsD6C\", \"9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek\", \"9nFeNpukSn1eVwNc2vkfP8SP4HrxTh9F86CY5pNWw8RF3jZa91q2i3yvE7ugpn9w2RzoZBZrdskgckmvJuVKq6ZWxfV8TepZYFd9SeARGHexi7tGGV2\"]",
+        "[\"9nFeNpukSn1eVwNc2vkfP8TAaw6DXNAgCNpxiQc437BxT3iF2xUMdo6wYQjqwxHwAZjVhQzdH3QMpJSbXvaDcnkVu6Ktt22AfYDypK2h72vuQK9fGNp\"]",
+    ]);
+    let upload_size = serde_json::to_string(&new_tokens).unwrap().len();
+
+    // Small limit for the json endpoints, the size of the upload for the upload endpoints
+    let limits = Limits::default()
+        .limit("json", 64.bytes())
+        .limit("json/update_cohorts", upload_size.bytes());
+    let rocket = ctx
+        .rocket
+        .configure(rocket::Config::figment().merge(("limits", limits)));
+    let client = Client::tracked(rocket).expect("Invalid rocket instance");
+
+    // Wrong, body exceeds the json limit
+    let mut contrib_info = ContributionInfo::default();
+    contrib_info.public_key = ctx.contributors[0].keypair.pubkey().to_owned();
+    let mut req = client.post("/contributor/contribution_info");
+    req = set_request::<ContributionInfo>(req, &ctx.contributors[0].keypair, Some(&contrib_info));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+    assert!(response.body().is_some());
+
+    // Ok, upload of the configured size
+    req = client.post("/update_cohorts");
+    req = set_request::<Vec<u8>>(req, &ctx.coordinator.keypair, Some(&new_tokens));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+}
+
 #[test]
 fn stop_coordinator() {
     let ctx = build_context();
@@ -326,6 +558,24 @@ fn stop_coordinator() {
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Ok);
     assert!(response.body().is_none());
+
+    // The drained coordinator rejects new participants and locks
+    let socket_address = SocketAddr::new(ctx.unknown_participant.address, 8080);
+    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = set_request::<String>(
+        req,
+        &ctx.unknown_participant.keypair,
+        Some(&format!(
+            "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek"
+        )),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+
+    req = client.get("/contributor/lock_chunk");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::ServiceUnavailable);
 }
 
 #[test]
@@ -340,7 +590,7 @@ fn get_healthcheck() {
     let ctx = build_context();
     let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
 
-    let req = client.get("/healthcheck");
+    let req = client.get("/healthcheck?shallow=true");
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Ok);
     assert!(response.body().is_some());
@@ -353,6 +603,58 @@ fn get_healthcheck() {
     }
 }
 
+#[test]
+fn get_deep_healthcheck() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    let req = client.get("/healthcheck");
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let report: HealthReport = response.into_json().unwrap();
+    assert_eq!(
+        HealthReport {
+            healthy: true,
+            round_state: true,
+            coordinator_state: true,
+            tokens_readable: true,
+            cohorts_consistent: true,
+        },
+        report
+    );
+}
+
+#[test]
+fn get_version() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    let req = client.get("/version");
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    let version: VersionInfo = response.into_json().unwrap();
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+    assert!(version.uptime_seconds >= 0);
+}
+
+#[test]
+fn get_metrics() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    let req = client.get("/metrics");
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::Plain));
+
+    let metrics = response.into_string().unwrap();
+    assert!(metrics.contains("# TYPE ceremony_contributors_active gauge\n"));
+    assert!(metrics.contains("\nceremony_contributors_active 1\n"));
+    assert!(metrics.contains("\nceremony_queue_length 0\n"));
+    assert!(metrics.contains("\nceremony_participants_banned 0\n"));
+}
+
 #[test]
 fn get_contributor_queue_status() {
     let ctx = build_context();
@@ -374,9 +676,170 @@ fn get_contributor_queue_status() {
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Ok);
     match response.into_json::<ContributorStatus>().unwrap() {
-        ContributorStatus::Round => (),
+        ContributorStatus::InRound {
+            retries: 0,
+            cohort: Some(1),
+        } => (),
+        _ => panic!("Wrong ContributorStatus"),
+    }
+}
+
+#[test]
+fn check_eligibility() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Unsigned request
+    let response = client
+        .post("/contributor/check_eligibility")
+        .header(ContentType::JSON)
+        .body(serde_json::to_string("unknown_token").unwrap())
+        .dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let check = |token: &str| {
+        let mut req = client.post("/contributor/check_eligibility");
+        req = set_request::<String>(req, &ctx.unknown_participant.keypair, Some(&token.to_string()));
+        let response = req.dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        response.into_json::<TokenEligibility>().unwrap()
+    };
+
+    // Token of the contributor in the round
+    let eligibility = check(
+        "9nFeNpukSn1eVwNc2vkfP7rdLh2njm5ewmCGxSLTW3GYmKP51fKjbRUvHDmntjEaQiq7iFux9tumgWEWVHwHQCs31oitpqBpMWpMydo1DnuFyLpsD6C",
+    );
+    assert_eq!(eligibility.status, TokenStatus::Spent);
+    assert_eq!(eligibility.token_cohort, Some(1));
+    assert_eq!(eligibility.current_cohort, Some(1));
+
+    // Unused token
+    let eligibility = check(
+        "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek",
+    );
+    assert_eq!(eligibility.status, TokenStatus::Fresh);
+    assert_eq!(eligibility.token_cohort, Some(1));
+
+    // Unknown token
+    let eligibility = check("unknown_token");
+    assert_eq!(eligibility.status, TokenStatus::Unknown);
+    assert_eq!(eligibility.token_cohort, None);
+}
+
+#[test]
+fn multiple_ceremonies() {
+    let ctx = build_context();
+
+    // Each ceremony reads the token files from its own folder
+    for name in &["a", "b"] {
+        let tokens_dir = ctx._tokens_tmp_dir.path().join(name);
+        std::fs::create_dir(&tokens_dir).unwrap();
+        std::fs::copy(
+            ctx._tokens_tmp_dir.path().join("namada_tokens_cohort_1.json"),
+            tokens_dir.join("namada_tokens_cohort_1.json"),
+        )
+        .unwrap();
+    }
+
+    let environment: phase2_coordinator::environment::Environment = Testing::default().into();
+    let mut ceremonies = rest_utils::Ceremonies::new();
+    for name in &["a", "b"] {
+        let mut coordinator = Coordinator::new(environment.for_ceremony(name), Arc::new(Production)).unwrap();
+        coordinator.initialize().unwrap();
+        ceremonies.insert(name.to_string(), Arc::new(RwLock::new(coordinator)));
+    }
+
+    // Only join the queue of the first ceremony
+    let contributor = &ctx.contributors[1];
+    let token = String::from(
+        "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek",
+    );
+    ceremonies["a"]
+        .try_write()
+        .unwrap()
+        .add_to_queue(contributor._inner.clone(), Some(contributor.address), token, 10)
+        .unwrap();
+
+    let mut rocket = rocket::build().manage(ceremonies);
+    for name in &["a", "b"] {
+        rocket = rocket.mount(rest_utils::ceremony_mount_point(name), routes![
+            rest::get_contributor_queue_status
+        ]);
+    }
+    let client = Client::tracked(rocket).expect("Invalid rocket instance");
+
+    let mut req = client.get("/ceremony/a/contributor/queue_status");
+    req = set_request::<()>(req, &contributor.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    match response.into_json::<ContributorStatus>().unwrap() {
+        ContributorStatus::InQueue {
+            size: 1,
+            cohort: Some(1),
+            ..
+        } => (),
         _ => panic!("Wrong ContributorStatus"),
     }
+
+    req = client.get("/ceremony/b/contributor/queue_status");
+    req = set_request::<()>(req, &contributor.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    match response.into_json::<ContributorStatus>().unwrap() {
+        ContributorStatus::Other => (),
+        _ => panic!("Wrong ContributorStatus"),
+    }
+
+    // Unknown ceremony
+    req = client.get("/ceremony/c/contributor/queue_status");
+    req = set_request::<()>(req, &contributor.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[test]
+fn stop_one_of_multiple_ceremonies() {
+    let ctx = build_context();
+
+    // Each ceremony reads the token files from its own folder
+    for name in &["x", "y"] {
+        let tokens_dir = ctx._tokens_tmp_dir.path().join(name);
+        std::fs::create_dir(&tokens_dir).unwrap();
+        std::fs::copy(
+            ctx._tokens_tmp_dir.path().join("namada_tokens_cohort_1.json"),
+            tokens_dir.join("namada_tokens_cohort_1.json"),
+        )
+        .unwrap();
+    }
+
+    let environment: phase2_coordinator::environment::Environment = Testing::default().into();
+    let mut ceremonies = rest_utils::Ceremonies::new();
+    for name in &["x", "y"] {
+        let mut coordinator = Coordinator::new(environment.for_ceremony(name), Arc::new(Production)).unwrap();
+        coordinator.initialize().unwrap();
+        ceremonies.insert(name.to_string(), Arc::new(RwLock::new(coordinator)));
+    }
+    let (ceremony_x, ceremony_y) = (ceremonies["x"].clone(), ceremonies["y"].clone());
+
+    let mut rocket = rocket::build().manage(ceremonies);
+    for name in &["x", "y"] {
+        rocket = rocket.mount(rest_utils::ceremony_mount_point(name), routes![rest::stop_coordinator]);
+    }
+    let client = Client::tracked(rocket).expect("Invalid rocket instance");
+
+    // Only the targeted ceremony is stopped
+    let mut req = client.get("/ceremony/x/stop");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(ceremony_x.blocking_read().is_draining());
+    assert!(!ceremony_y.blocking_read().is_draining());
+
+    req = client.get("/ceremony/y/stop");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(ceremony_y.blocking_read().is_draining());
 }
 
 #[test]
@@ -399,6 +862,78 @@ fn heartbeat() {
     assert!(response.body().is_none());
 }
 
+#[test]
+fn reconnect() {
+    let time = Arc::new(MockTimeSource::new(time::OffsetDateTime::now_utc()));
+    let environment = Testing::default().reconnect_window(time::Duration::minutes(5));
+    let contributor_seen_timeout = Environment::from(environment.clone()).contributor_seen_timeout();
+    let ctx = build_context_with_time(environment, time.clone());
+    let coordinator = ctx.rocket.state::<Arc<RwLock<Coordinator>>>().unwrap().clone();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // An unknown participant cannot reconnect
+    let mut req = client.post("/contributor/reconnect");
+    req = set_request::<()>(req, &ctx.unknown_participant.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+
+    // A contributor which is not disconnected cannot reconnect
+    req = client.post("/contributor/reconnect");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+
+    // The contributor is disconnected, and the lock it holds is released
+    time.update(|prev| prev + contributor_seen_timeout + time::Duration::seconds(1));
+    coordinator.blocking_write().update().unwrap();
+    let round = coordinator.blocking_read().current_round().unwrap();
+    assert!(round.chunks().iter().all(|chunk| chunk.lock_holder().is_none()));
+
+    // Ok
+    req = client.post("/contributor/reconnect");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.body().is_none());
+
+    // The contributor locks its chunk again
+    req = client.get("/contributor/lock_chunk");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[test]
+fn get_participant_metrics() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Wrong, participant is not in the current round
+    let mut req = client.get("/participant/metrics");
+    req = set_request::<()>(req, &ctx.unknown_participant.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert!(response.body().is_some());
+
+    // Ok, the contributor holds the lock on a task
+    req = client.get("/participant/metrics");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let metrics = response.into_json::<ParticipantMetrics>().unwrap();
+    assert_eq!(metrics.pending_tasks, 1);
+    assert_eq!(metrics.completed_tasks, 0);
+    assert!(metrics.contributor_average_per_task.is_none());
+
+    // Ok, the coordinator verifier is always served
+    req = client.get("/participant/metrics");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let metrics = response.into_json::<ParticipantMetrics>().unwrap();
+    assert_eq!(metrics.pending_tasks, 0);
+}
+
 #[test]
 fn update_coordinator() {
     let ctx = build_context();
@@ -510,6 +1045,111 @@ fn join_queue() {
     assert!(response.body().is_some());
 }
 
+#[test]
+fn join_queue_frozen() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    let socket_address = SocketAddr::new(ctx.unknown_participant.address, 8080);
+    let token = "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek";
+
+    let mut req = client.post("/queue/freeze");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    // Wrong request, the queue is frozen
+    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = set_request::<String>(req, &ctx.unknown_participant.keypair, Some(&token.to_string()));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+
+    req = client.post("/queue/unfreeze");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    // Ok request
+    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = set_request::<String>(req, &ctx.unknown_participant.keypair, Some(&token.to_string()));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[test]
+#[serial]
+fn join_queue_ip_ban_exemptions() {
+    let access_token = "test-access_token";
+    std::env::set_var("ACCESS_SECRET", access_token);
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    let shared_ip = IpAddr::V4("0.0.0.5".parse().unwrap());
+    let exemptions_file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(exemptions_file.path(), format!("[\"{}\"]", shared_ip)).unwrap();
+    std::env::set_var("NAMADA_MPC_IP_BAN_EXEMPTIONS", exemptions_file.path());
+
+    // Wrong request, non-exempted IP already in queue
+    let socket_address = SocketAddr::new(ctx.contributors[0].address, 8080);
+    let mut req = client.post("/contributor/join_queue").remote(socket_address);
+    req = set_request::<String>(
+        req,
+        &ctx.unknown_participant.keypair,
+        Some(&format!(
+            "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek"
+        )),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(response.body().is_some());
+
+    // Reload the exemptions, restoring the env right after so that it doesn't leak into other tests
+    req = client.post("/update_ip_ban_exemptions");
+    req.add_header(Header::new(ACCESS_SECRET_HEADER, access_token));
+    let response = req.dispatch();
+    std::env::remove_var("NAMADA_MPC_IP_BAN_EXEMPTIONS");
+    assert_eq!(response.status(), Status::Ok);
+    let exemptions: Vec<IpAddr> = response.into_json().unwrap();
+    assert_eq!(exemptions, vec![shared_ip]);
+
+    // Wrong request, the exemption lifts the ip ban but not the check of the token, already in use
+    let socket_address = SocketAddr::new(shared_ip, 8080);
+    let keypair = KeyPair::new();
+    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = set_request::<String>(
+        req,
+        &keypair,
+        Some(&format!(
+            "9nFeNpukSn1eVwNc2vkfP7rdLh2njm5ewmCGxSLTW3GYmKP51fKjbRUvHDmntjEaQiq7iFux9tumgWEWVHwHQCs31oitpqBpMWpMydo1DnuFyLpsD6C"
+        )),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // Ok requests, two contributors sharing the exempted IP
+    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = set_request::<String>(
+        req,
+        &ctx.unknown_participant.keypair,
+        Some(&format!(
+            "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek"
+        )),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = set_request::<String>(
+        req,
+        &ctx.contributors[1].keypair,
+        Some(&format!(
+            "9nFeNpukSn1eVwNc2vkfP8SP4HrxTh9F86CY5pNWw8RF3jZa91q2i3yvE7ugpn9w2RzoZBZrdskgckmvJuVKq6ZWxfV8TepZYFd9SeARGHexi7tGGV2"
+        )),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+}
+
 /// Test wrong usage of lock_chunk.
 #[test]
 fn wrong_lock_chunk() {
@@ -612,6 +1252,179 @@ fn wrong_contribute_chunk() {
     assert!(response.body().is_some());
 }
 
+/// Test the field-level validation of the contribute_chunk body.
+#[test]
+fn malformed_contribute_chunk() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    let cases = vec![
+        (
+            PostChunkRequest::new(
+                0,
+                ContributionLocator::new(0, 0, 1, false),
+                ContributionSignatureLocator::new(0, 0, 1, false),
+            ),
+            "round_height must be > 0",
+        ),
+        (
+            PostChunkRequest::new(
+                ROUND_HEIGHT,
+                ContributionLocator::new(ROUND_HEIGHT + 1, 0, 1, false),
+                ContributionSignatureLocator::new(ROUND_HEIGHT + 1, 0, 1, false),
+            ),
+            "contribution_locator.round_height must match round_height",
+        ),
+        (
+            PostChunkRequest::new(
+                ROUND_HEIGHT,
+                ContributionLocator::new(ROUND_HEIGHT, 0, 0, false),
+                ContributionSignatureLocator::new(ROUND_HEIGHT, 0, 0, false),
+            ),
+            "contribution_locator.contribution_id must be > 0",
+        ),
+        (
+            PostChunkRequest::new(
+                ROUND_HEIGHT,
+                ContributionLocator::new(ROUND_HEIGHT, 0, 1, true),
+                ContributionSignatureLocator::new(ROUND_HEIGHT, 0, 1, true),
+            ),
+            "contribution_locator.is_verified must be false",
+        ),
+        (
+            PostChunkRequest::new(
+                ROUND_HEIGHT,
+                ContributionLocator::new(ROUND_HEIGHT, 0, 1, false),
+                ContributionSignatureLocator::new(ROUND_HEIGHT, 0, 2, false),
+            ),
+            "contribution_signature_locator must match contribution_locator",
+        ),
+    ];
+
+    for (request, message) in cases {
+        let mut req = client.post("/contributor/contribute_chunk");
+        req = set_request::<PostChunkRequest>(req, &ctx.contributors[0].keypair, Some(&request));
+        let response = req.dispatch();
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+        assert_eq!(
+            response.into_string().unwrap(),
+            format!("Invalid request body: {}", message)
+        );
+    }
+}
+
+/// Test wrong usage of the resumable upload of a contribution.
+#[test]
+fn wrong_chunk_upload() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    let cases = vec![
+        (
+            InitiateChunkUploadRequest {
+                round_height: ROUND_HEIGHT,
+                total_size: 0,
+                checksum: rest_utils::sha256_digest(b"contribution"),
+            },
+            "total_size must be > 0",
+        ),
+        (
+            InitiateChunkUploadRequest {
+                round_height: ROUND_HEIGHT,
+                total_size: 12,
+                checksum: String::from("not a checksum"),
+            },
+            "checksum must be a base64 encoded SHA-256",
+        ),
+    ];
+
+    for (request, message) in cases {
+        let mut req = client.post("/upload/chunk/initiate");
+        req = set_request(req, &ctx.contributors[0].keypair, Some(&request));
+        let response = req.dispatch();
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+        assert_eq!(
+            response.into_string().unwrap(),
+            format!("Invalid request body: {}", message)
+        );
+    }
+
+    // Non-current-contributor
+    let request = InitiateChunkUploadRequest {
+        round_height: ROUND_HEIGHT,
+        total_size: 12,
+        checksum: rest_utils::sha256_digest(b"contribution"),
+    };
+    let mut req = client.post("/upload/chunk/initiate");
+    req = set_request(req, &ctx.contributors[1].keypair, Some(&request));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // Part with a wrong checksum
+    let mut part = PutChunkPartRequest::new(String::from("upload"), 0, b"contribution");
+    part.checksum = rest_utils::sha256_digest(b"other");
+    req = client.post("/upload/chunk/part");
+    req = set_request(req, &ctx.contributors[0].keypair, Some(&part));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        response.into_string().unwrap(),
+        "Invalid request body: checksum must match the SHA-256 of data"
+    );
+
+    // Part and completion of an unknown upload
+    let part = PutChunkPartRequest::new(String::from("upload"), 0, b"contribution");
+    req = client.post("/upload/chunk/part");
+    req = set_request(req, &ctx.contributors[0].keypair, Some(&part));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+
+    req = client.post("/upload/chunk/complete");
+    req = set_request(req, &ctx.contributors[0].keypair, Some(&String::from("upload")));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+/// Test the field-level validation of the contribution_info body.
+#[test]
+fn malformed_contribution_info() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    let mut valid_info = ContributionInfo::default();
+    valid_info.public_key = ctx.contributors[0].keypair.pubkey().to_owned();
+    valid_info.ceremony_round = ROUND_HEIGHT;
+    valid_info.contribution_hash = "ab".repeat(64);
+    valid_info.contribution_file_hash = "cd".repeat(64);
+
+    let mut missing_key = valid_info.clone();
+    missing_key.public_key = String::new();
+    let mut zero_round = valid_info.clone();
+    zero_round.ceremony_round = 0;
+    let mut short_hash = valid_info.clone();
+    short_hash.contribution_hash = "ab".repeat(32);
+    let mut non_hex_hash = valid_info.clone();
+    non_hex_hash.contribution_file_hash = "zz".repeat(64);
+
+    let cases = vec![
+        (missing_key, "public_key must not be empty"),
+        (zero_round, "ceremony_round must be > 0"),
+        (short_hash, "contribution_hash must be 128 hex chars"),
+        (non_hex_hash, "contribution_file_hash must be 128 hex chars"),
+    ];
+
+    for (info, message) in cases {
+        let mut req = client.post("/contributor/contribution_info");
+        req = set_request::<ContributionInfo>(req, &ctx.contributors[0].keypair, Some(&info));
+        let response = req.dispatch();
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+        assert_eq!(
+            response.into_string().unwrap(),
+            format!("Invalid request body: {}", message)
+        );
+    }
+}
+
 #[test]
 fn wrong_verify() {
     let ctx = build_context();
@@ -742,6 +1555,7 @@ fn contribution() {
     let contribution_file_signature_locator = ContributionSignatureLocator::new(ROUND_HEIGHT, 0, 1, false);
 
     let response_hash = calculate_hash(contribution.as_ref());
+    let contribution_hash = calculate_hash(&contribution[challenge_hash.len()..]);
 
     let contribution_state = ContributionState::new(challenge_hash.to_vec(), response_hash.to_vec(), None).unwrap();
 
@@ -774,6 +1588,8 @@ fn contribution() {
         .unwrap()
         .current_contribution()
         .round_height();
+    contrib_info.contribution_hash = hex::encode(contribution_hash);
+    contrib_info.contribution_file_hash = hex::encode(response_hash);
     contrib_info.try_sign(&ctx.contributors[0].keypair).unwrap();
 
     req = client.post("/contributor/contribution_info");
@@ -811,6 +1627,11 @@ fn contribution() {
     assert!(!summary[0].is_own_seed_of_randomness());
     assert_eq!(summary[0].ceremony_round(), 1);
 
+    // The contributions info of a round that doesn't exist yet is not found
+    req = client.get("/round/2/contributions");
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+
     // Update cohorts
     assert!(std::fs::metadata(TOKENS_ZIP_FILE).is_err());
     let new_valid_tokens = get_serialized_tokens_zip(vec![